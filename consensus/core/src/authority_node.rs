@@ -98,6 +98,59 @@ impl ConsensusAuthority {
         }
     }
 
+    /// Starts an authority which misbehaves according to `byzantine_behavior`, for testing the
+    /// rest of the committee against it.
+    #[cfg(any(test, msim))]
+    pub async fn start_with_byzantine_behavior(
+        network_type: ConsensusNetwork,
+        own_index: AuthorityIndex,
+        committee: Committee,
+        parameters: Parameters,
+        protocol_config: ProtocolConfig,
+        protocol_keypair: ProtocolKeyPair,
+        network_keypair: NetworkKeyPair,
+        transaction_verifier: Arc<dyn TransactionVerifier>,
+        commit_consumer: CommitConsumer,
+        registry: Registry,
+        boot_counter: u64,
+        byzantine_behavior: crate::ByzantineBehavior,
+    ) -> Self {
+        assert!(committee.is_valid_index(own_index));
+        let context = Context::new(
+            own_index,
+            committee,
+            parameters,
+            protocol_config,
+            initialise_metrics(registry),
+            Arc::new(Clock::new()),
+        )
+        .with_byzantine_behavior(byzantine_behavior);
+        match network_type {
+            ConsensusNetwork::Anemo => Self::WithAnemo(
+                AuthorityNode::start_with_context(
+                    context,
+                    protocol_keypair,
+                    network_keypair,
+                    transaction_verifier,
+                    commit_consumer,
+                    boot_counter,
+                )
+                .await,
+            ),
+            ConsensusNetwork::Tonic => Self::WithTonic(
+                AuthorityNode::start_with_context(
+                    context,
+                    protocol_keypair,
+                    network_keypair,
+                    transaction_verifier,
+                    commit_consumer,
+                    boot_counter,
+                )
+                .await,
+            ),
+        }
+    }
+
     pub async fn stop(self) {
         match self {
             Self::WithAnemo(authority) => authority.stop().await,
@@ -181,14 +234,36 @@ where
             own_index, committee, parameters, protocol_config.version, boot_counter
         );
         assert!(committee.is_valid_index(own_index));
-        let context = Arc::new(Context::new(
+        let context = Context::new(
             own_index,
             committee,
             parameters,
             protocol_config,
             initialise_metrics(registry),
             Arc::new(Clock::new()),
-        ));
+        );
+        Self::start_with_context(
+            context,
+            protocol_keypair,
+            network_keypair,
+            transaction_verifier,
+            commit_consumer,
+            boot_counter,
+        )
+        .await
+    }
+
+    /// Starts the authority with an already created `Context`. This allows tests and simtests to
+    /// customize the context, e.g. to inject byzantine behaviors.
+    pub(crate) async fn start_with_context(
+        context: Context,
+        protocol_keypair: ProtocolKeyPair,
+        network_keypair: NetworkKeyPair,
+        transaction_verifier: Arc<dyn TransactionVerifier>,
+        commit_consumer: CommitConsumer,
+        boot_counter: u64,
+    ) -> Self {
+        let context = Arc::new(context);
         let start_time = Instant::now();
 
         let (tx_client, tx_receiver) = TransactionClient::new(context.clone());
@@ -426,6 +501,7 @@ mod tests {
 
     use super::*;
     use crate::block::GENESIS_ROUND;
+    use crate::byzantine::ByzantineBehavior;
     use crate::{block::BlockAPI as _, transaction::NoopTransactionVerifier, CommittedSubDag};
 
     #[rstest]
@@ -699,26 +775,165 @@ mod tests {
     }
 
     // TODO: create a fixture
-    async fn make_authority(
+    /// Runs a committee where the last authority misbehaves according to `byzantine_behavior`,
+    /// and checks that the honest authorities commit all submitted transactions (liveness)
+    /// with the same commit sequence (safety).
+    #[rstest]
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_authority_committee_with_byzantine_authority(
+        #[values(ConsensusNetwork::Anemo, ConsensusNetwork::Tonic)] network_type: ConsensusNetwork,
+        #[values(
+            ByzantineBehavior::default().with_equivocations(2),
+            ByzantineBehavior::default().with_withheld_peers(vec![AuthorityIndex::new_for_test(0), AuthorityIndex::new_for_test(1)]),
+            ByzantineBehavior::default().with_proposal_delay(Duration::from_secs(1)),
+            ByzantineBehavior::default().with_invalid_signatures(),
+            ByzantineBehavior::default().with_equivocations(1).with_withheld_peers(vec![AuthorityIndex::new_for_test(0)]),
+        )]
+        byzantine_behavior: ByzantineBehavior,
+    ) {
+        telemetry_subscribers::init_for_testing();
+        let db_registry = Registry::new();
+        DBMetrics::init(&db_registry);
+
+        const NUM_OF_AUTHORITIES: usize = 4;
+        let (committee, keypairs) = local_committee_and_keys(0, [1; NUM_OF_AUTHORITIES].to_vec());
+        let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+        let byzantine_index = AuthorityIndex::new_for_test(NUM_OF_AUTHORITIES as u32 - 1);
+
+        let temp_dirs = (0..NUM_OF_AUTHORITIES)
+            .map(|_| TempDir::new().unwrap())
+            .collect::<Vec<_>>();
+
+        let mut output_receivers = Vec::with_capacity(committee.size());
+        let mut authorities = Vec::with_capacity(committee.size());
+        for (index, _authority_info) in committee.authorities() {
+            let behavior = if index == byzantine_index {
+                byzantine_behavior.clone()
+            } else {
+                ByzantineBehavior::default()
+            };
+            let (authority, receiver) = make_authority_with_byzantine_behavior(
+                index,
+                &temp_dirs[index.value()],
+                committee.clone(),
+                keypairs.clone(),
+                network_type,
+                protocol_config.clone(),
+                behavior,
+            )
+            .await;
+            output_receivers.push(receiver);
+            authorities.push(authority);
+        }
+
+        // Submit transactions only to honest authorities, which must include them in their blocks.
+        const NUM_TRANSACTIONS: u8 = 15;
+        let mut submitted_transactions = BTreeSet::<Vec<u8>>::new();
+        for i in 0..NUM_TRANSACTIONS {
+            let txn = vec![i; 16];
+            submitted_transactions.insert(txn.clone());
+            authorities[i as usize % (authorities.len() - 1)]
+                .transaction_client()
+                .submit(vec![txn])
+                .await
+                .unwrap();
+        }
+
+        let mut committed_leaders = Vec::with_capacity(committee.size() - 1);
+        for receiver in &mut output_receivers[..byzantine_index.value()] {
+            let mut expected_transactions = submitted_transactions.clone();
+            let mut leaders = vec![];
+            while !expected_transactions.is_empty() {
+                let committed_subdag = timeout(Duration::from_secs(10), receiver.recv())
+                    .await
+                    .expect("Timed out waiting for transactions to be committed")
+                    .unwrap();
+                for b in &committed_subdag.blocks {
+                    if byzantine_behavior.invalid_signatures {
+                        assert_ne!(b.author(), byzantine_index);
+                    }
+                    for txn in b.transactions().iter().map(|t| t.data().to_vec()) {
+                        assert!(
+                            expected_transactions.remove(&txn),
+                            "Transaction not submitted or already seen: {:?}",
+                            txn
+                        );
+                    }
+                }
+                leaders.push((committed_subdag.commit_ref, committed_subdag.leader));
+            }
+            committed_leaders.push(leaders);
+        }
+
+        // Honest authorities must agree on the commit sequence they have in common.
+        let common_len = committed_leaders.iter().map(Vec::len).min().unwrap();
+        for leaders in &committed_leaders[1..] {
+            assert_eq!(leaders[..common_len], committed_leaders[0][..common_len]);
+        }
+
+        // Stop all authorities and exit.
+        for authority in authorities {
+            authority.stop().await;
+        }
+    }
+
+    async fn make_authority_with_byzantine_behavior(
         index: AuthorityIndex,
         db_dir: &TempDir,
         committee: Committee,
         keypairs: Vec<(NetworkKeyPair, ProtocolKeyPair)>,
         network_type: ConsensusNetwork,
-        boot_counter: u64,
         protocol_config: ProtocolConfig,
+        byzantine_behavior: ByzantineBehavior,
     ) -> (ConsensusAuthority, UnboundedReceiver<CommittedSubDag>) {
-        let registry = Registry::new();
+        let protocol_keypair = keypairs[index].1.clone();
+        let network_keypair = keypairs[index].0.clone();
+
+        let (commit_consumer, commit_receiver, _) = CommitConsumer::new(0);
+
+        let authority = ConsensusAuthority::start_with_byzantine_behavior(
+            network_type,
+            index,
+            committee,
+            authority_parameters(db_dir),
+            protocol_config,
+            protocol_keypair,
+            network_keypair,
+            Arc::new(NoopTransactionVerifier {}),
+            commit_consumer,
+            Registry::new(),
+            0,
+            byzantine_behavior,
+        )
+        .await;
 
+        (authority, commit_receiver)
+    }
+
+    fn authority_parameters(db_dir: &TempDir) -> Parameters {
         // Cache less blocks to exercise commit sync.
-        let parameters = Parameters {
+        Parameters {
             db_path: db_dir.path().to_path_buf(),
             dag_state_cached_rounds: 5,
             commit_sync_parallel_fetches: 2,
             commit_sync_batch_size: 3,
             sync_last_known_own_block_timeout: Duration::from_millis(2_000),
             ..Default::default()
-        };
+        }
+    }
+
+    async fn make_authority(
+        index: AuthorityIndex,
+        db_dir: &TempDir,
+        committee: Committee,
+        keypairs: Vec<(NetworkKeyPair, ProtocolKeyPair)>,
+        network_type: ConsensusNetwork,
+        boot_counter: u64,
+        protocol_config: ProtocolConfig,
+    ) -> (ConsensusAuthority, UnboundedReceiver<CommittedSubDag>) {
+        let registry = Registry::new();
+
+        let parameters = authority_parameters(db_dir);
         let txn_verifier = NoopTransactionVerifier {};
 
         let protocol_keypair = keypairs[index].1.clone();
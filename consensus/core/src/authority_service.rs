@@ -235,13 +235,24 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
     ) -> ConsensusResult<BlockStream> {
         fail_point_async!("consensus-rpc-response");
 
+        // Withheld peers only receive own blocks by fetching them.
+        #[cfg(any(test, msim))]
+        if !self.context.byzantine_behavior.should_broadcast_to(peer) {
+            return Ok(Box::pin(stream::pending()));
+        }
+
         let dag_state = self.dag_state.read();
         // Find recent own blocks that have not been received by the peer.
         // If last_received is a valid and more blocks have been proposed since then, this call is
         // guaranteed to return at least some recent blocks, which will help with liveness.
+        let missed_blocks = dag_state.get_cached_blocks(self.context.own_index, last_received + 1);
+        #[cfg(any(test, msim))]
+        let missed_blocks = missed_blocks
+            .into_iter()
+            .map(|block| self.context.byzantine_behavior.block_for_peer(peer, block))
+            .collect::<Vec<_>>();
         let missed_blocks = stream::iter(
-            dag_state
-                .get_cached_blocks(self.context.own_index, last_received + 1)
+            missed_blocks
                 .into_iter()
                 .map(|block| block.serialized().clone()),
         );
@@ -251,6 +262,11 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
             self.rx_block_broadcaster.resubscribe(),
             self.subscription_counter.clone(),
         );
+        #[cfg(any(test, msim))]
+        let broadcasted_blocks = {
+            let byzantine_behavior = self.context.byzantine_behavior.clone();
+            broadcasted_blocks.map(move |block| byzantine_behavior.block_for_peer(peer, block))
+        };
 
         // Return a stream of blocks that first yields missed blocks as requested, then new blocks.
         Ok(Box::pin(missed_blocks.chain(
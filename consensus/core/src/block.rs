@@ -475,7 +475,7 @@ impl SignedBlock {
     }
 
     /// Clears signature for testing.
-    #[cfg(test)]
+    #[cfg(any(test, msim))]
     pub(crate) fn clear_signature(&mut self) {
         self.signature = Bytes::default();
     }
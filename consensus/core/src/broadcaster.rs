@@ -51,6 +51,10 @@ impl Broadcaster {
             if index == context.own_index {
                 continue;
             }
+            #[cfg(any(test, msim))]
+            if !context.byzantine_behavior.should_broadcast_to(index) {
+                continue;
+            }
             senders.spawn(Self::push_blocks(
                 context.clone(),
                 network_client.clone(),
@@ -137,6 +141,8 @@ impl Broadcaster {
                             continue;
                         }
                     };
+                    #[cfg(any(test, msim))]
+                    let block = context.byzantine_behavior.block_for_peer(peer, block);
                     requests.push(send_block(network_client.clone(), peer, rtt_estimate, block.clone()));
                     if last_block.is_none() || last_block.as_ref().unwrap().round() < block.round() {
                         last_block = Some(block);
@@ -0,0 +1,223 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Byzantine behaviors that can be injected into an authority for testing.
+//!
+//! The behaviors are configured on the `Context` of an authority, and are applied by `Core`
+//! when proposing blocks, and by `Broadcaster` or the subscription handler of `AuthorityService`
+//! when sending them to peers. Honest authorities use the default `ByzantineBehavior`, which
+//! does not alter any block. Only available in tests and simtests.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use consensus_config::{AuthorityIndex, ProtocolKeyPair};
+use parking_lot::Mutex;
+
+use crate::{
+    block::{Block, BlockAPI as _, BlockRef, BlockV1, SignedBlock, VerifiedBlock},
+    Round,
+};
+
+/// Number of recent rounds for which equivocating blocks are kept around for broadcasting.
+const EQUIVOCATING_BLOCKS_RETAINED_ROUNDS: Round = 10;
+
+#[derive(Clone, Default)]
+pub struct ByzantineBehavior {
+    /// Number of additional conflicting blocks proposed for every round. Each peer is sent one of
+    /// the proposed blocks, based on its index.
+    pub(crate) equivocations: usize,
+    /// Peers that proposed blocks are never broadcasted to. They can still fetch the blocks via
+    /// the synchronizer once they are referenced by other authorities.
+    pub(crate) withheld_peers: BTreeSet<AuthorityIndex>,
+    /// Delay between proposing a block and broadcasting it to peers.
+    pub(crate) proposal_delay: Option<Duration>,
+    /// When true, proposed blocks are signed with an invalid signature.
+    pub(crate) invalid_signatures: bool,
+
+    // Equivocating blocks proposed in each slot, keyed by the reference of the block accepted
+    // locally, until they are broadcasted.
+    equivocating_blocks: Arc<Mutex<BTreeMap<BlockRef, Vec<VerifiedBlock>>>>,
+}
+
+impl ByzantineBehavior {
+    pub fn with_equivocations(mut self, equivocations: usize) -> Self {
+        self.equivocations = equivocations;
+        self
+    }
+
+    pub fn with_withheld_peers(mut self, peers: Vec<AuthorityIndex>) -> Self {
+        self.withheld_peers = peers.into_iter().collect();
+        self
+    }
+
+    pub fn with_proposal_delay(mut self, delay: Duration) -> Self {
+        self.proposal_delay = Some(delay);
+        self
+    }
+
+    pub fn with_invalid_signatures(mut self) -> Self {
+        self.invalid_signatures = true;
+        self
+    }
+
+    pub fn is_honest(&self) -> bool {
+        self.equivocations == 0
+            && self.withheld_peers.is_empty()
+            && self.proposal_delay.is_none()
+            && !self.invalid_signatures
+    }
+
+    /// Returns true if proposed blocks should be broadcasted to the peer.
+    pub(crate) fn should_broadcast_to(&self, peer: AuthorityIndex) -> bool {
+        !self.withheld_peers.contains(&peer)
+    }
+
+    /// Applies the signature related behaviors to a newly signed own block.
+    pub(crate) fn sign_block(&self, mut signed_block: SignedBlock) -> SignedBlock {
+        if self.invalid_signatures {
+            signed_block.clear_signature();
+        }
+        signed_block
+    }
+
+    /// Creates the blocks equivocating with the newly proposed `block`, so they can be sent to
+    /// peers instead of it. The equivocating blocks are never accepted locally.
+    pub(crate) fn equivocate(&self, block: &VerifiedBlock, signer: &ProtocolKeyPair) {
+        if self.equivocations == 0 {
+            return;
+        }
+        let mut equivocating_blocks: Vec<VerifiedBlock> = vec![];
+        for i in 1..=self.equivocations {
+            // Equivocating blocks carry no transactions, so the same transaction can not be
+            // committed more than once. Rotating the ancestors from other authorities ensures the
+            // blocks differ even when there are no transactions to propose.
+            let mut ancestors = block.ancestors().to_vec();
+            if ancestors.len() > 2 {
+                ancestors[1..].rotate_left(i % (ancestors.len() - 1));
+            }
+            let equivocating_block = Block::V1(BlockV1::new(
+                block.epoch(),
+                block.round(),
+                block.author(),
+                block.timestamp_ms(),
+                ancestors,
+                vec![],
+                block.commit_votes().to_vec(),
+                vec![],
+            ));
+            let signed_block = self.sign_block(
+                SignedBlock::new(equivocating_block, signer).expect("Block signing failed."),
+            );
+            let serialized = signed_block
+                .serialize()
+                .expect("Block serialization failed.");
+            let equivocating_block = VerifiedBlock::new_verified(signed_block, serialized);
+            if equivocating_block.reference() != block.reference()
+                && !equivocating_blocks.contains(&equivocating_block)
+            {
+                equivocating_blocks.push(equivocating_block);
+            }
+        }
+        let mut all_equivocating_blocks = self.equivocating_blocks.lock();
+        all_equivocating_blocks.insert(block.reference(), equivocating_blocks);
+        // Peers lagging further behind will receive the locally accepted blocks.
+        all_equivocating_blocks.retain(|block_ref, _| {
+            block_ref.round + EQUIVOCATING_BLOCKS_RETAINED_ROUNDS > block.round()
+        });
+    }
+
+    /// Returns the block to send to the peer instead of the locally accepted `block`, choosing
+    /// among the block and the blocks equivocating with it.
+    pub(crate) fn block_for_peer(
+        &self,
+        peer: AuthorityIndex,
+        block: VerifiedBlock,
+    ) -> VerifiedBlock {
+        if self.equivocations == 0 {
+            return block;
+        }
+        let equivocating_blocks = self.equivocating_blocks.lock();
+        let Some(blocks) = equivocating_blocks.get(&block.reference()) else {
+            return block;
+        };
+        match peer.value() % (blocks.len() + 1) {
+            0 => block,
+            i => blocks[i - 1].clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        block::{genesis_blocks, TestBlock},
+        context::Context,
+    };
+
+    #[tokio::test]
+    async fn test_equivocating_blocks_for_peers() {
+        let (context, keys) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let behavior = ByzantineBehavior::default().with_equivocations(2);
+        assert!(!behavior.is_honest());
+
+        let ancestors = genesis_blocks(context.clone())
+            .iter()
+            .map(|block| block.reference())
+            .collect::<Vec<_>>();
+        let block = Block::V1(BlockV1::new(
+            0,
+            1,
+            context.own_index,
+            10,
+            ancestors,
+            vec![],
+            vec![],
+            vec![],
+        ));
+        let signed_block = SignedBlock::new(block, &keys[0].1).unwrap();
+        let serialized = signed_block.serialize().unwrap();
+        let block = VerifiedBlock::new_verified(signed_block, serialized);
+
+        behavior.equivocate(&block, &keys[0].1);
+
+        let sent_blocks = context
+            .committee
+            .authorities()
+            .map(|(peer, _)| behavior.block_for_peer(peer, block.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(sent_blocks[0], block);
+        assert_eq!(sent_blocks[3], block);
+        for equivocating_block in &sent_blocks[1..3] {
+            assert_ne!(equivocating_block.reference(), block.reference());
+            assert_eq!(equivocating_block.slot(), block.slot());
+            assert!(equivocating_block.verify_signature(&context).is_ok());
+        }
+        assert_ne!(sent_blocks[1], sent_blocks[2]);
+
+        // Blocks without equivocations are sent unchanged.
+        let other_block = VerifiedBlock::new_for_test(TestBlock::new(2, 0).build());
+        assert_eq!(
+            behavior.block_for_peer(AuthorityIndex::new_for_test(1), other_block.clone()),
+            other_block
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_signatures() {
+        let (context, keys) = Context::new_for_test(4);
+        let behavior = ByzantineBehavior::default().with_invalid_signatures();
+
+        let block = TestBlock::new(1, 0).build();
+        let signed_block = SignedBlock::new(block, &keys[0].1).unwrap();
+        assert!(signed_block.verify_signature(&context).is_ok());
+
+        let signed_block = behavior.sign_block(signed_block);
+        assert!(signed_block.verify_signature(&context).is_err());
+    }
+}
@@ -11,6 +11,8 @@ use sui_protocol_config::ProtocolConfig;
 use tempfile::TempDir;
 use tokio::time::Instant;
 
+#[cfg(any(test, msim))]
+use crate::byzantine::ByzantineBehavior;
 #[cfg(test)]
 use crate::metrics::test_metrics;
 use crate::{block::BlockTimestampMs, metrics::Metrics};
@@ -31,6 +33,9 @@ pub(crate) struct Context {
     pub metrics: Arc<Metrics>,
     /// Access to local clock
     pub clock: Arc<Clock>,
+    /// Byzantine behaviors injected into this authority.
+    #[cfg(any(test, msim))]
+    pub byzantine_behavior: ByzantineBehavior,
 }
 
 impl Context {
@@ -49,6 +54,8 @@ impl Context {
             protocol_config,
             metrics,
             clock,
+            #[cfg(any(test, msim))]
+            byzantine_behavior: ByzantineBehavior::default(),
         }
     }
 
@@ -94,6 +101,12 @@ impl Context {
         self.parameters = parameters;
         self
     }
+
+    #[cfg(any(test, msim))]
+    pub(crate) fn with_byzantine_behavior(mut self, byzantine_behavior: ByzantineBehavior) -> Self {
+        self.byzantine_behavior = byzantine_behavior;
+        self
+    }
 }
 
 /// A clock that allows to derive the current UNIX system timestamp while guaranteeing that timestamp
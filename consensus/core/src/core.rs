@@ -459,6 +459,8 @@ impl Core {
         ));
         let signed_block =
             SignedBlock::new(block, &self.block_signer).expect("Block signing failed.");
+        #[cfg(any(test, msim))]
+        let signed_block = self.context.byzantine_behavior.sign_block(signed_block);
         let serialized = signed_block
             .serialize()
             .expect("Block serialization failed.");
@@ -470,6 +472,11 @@ impl Core {
         // Unnecessary to verify own blocks.
         let verified_block = VerifiedBlock::new_verified(signed_block, serialized);
 
+        #[cfg(any(test, msim))]
+        self.context
+            .byzantine_behavior
+            .equivocate(&verified_block, &self.block_signer);
+
         // Accept the block into BlockManager and DagState.
         let (accepted_blocks, missing) = self
             .block_manager
@@ -853,6 +860,16 @@ impl CoreSignals {
                 return Ok(());
             }
 
+            #[cfg(any(test, msim))]
+            if let Some(delay) = self.context.byzantine_behavior.proposal_delay {
+                let tx_block_broadcast = self.tx_block_broadcast.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    let _ = tx_block_broadcast.send(block);
+                });
+                return Ok(());
+            }
+
             if let Err(err) = self.tx_block_broadcast.send(block) {
                 warn!("Couldn't broadcast the block to any receiver: {err}");
                 return Err(ConsensusError::Shutdown);
@@ -0,0 +1,554 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{
+    block::{BlockAPI as _, BlockRef, Round, VerifiedBlock},
+    commit::{CommitAPI as _, CommitIndex, TrustedCommit},
+    error::ConsensusResult,
+    storage::{rocksdb_store::RocksDBStore, Store},
+};
+
+/// Number of commits read from the store at a time, when looking for the commits
+/// of the exported rounds.
+const COMMITS_SCAN_BATCH_SIZE: CommitIndex = 1000;
+
+/// DagExport is a snapshot of the DAG of a validator over a range of rounds, read
+/// offline from its consensus store. It contains the blocks with their ancestors,
+/// the commits and the leader decisions that can be inferred from the commits.
+///
+/// The snapshot can be serialized to JSON, rendered as a Graphviz graph or
+/// converted into the DAG syntax of `test_dag_parser`, so that production
+/// incidents can be reproduced in unit tests.
+#[derive(Clone, Debug, Serialize)]
+pub struct DagExport {
+    /// Inferred from the authorities of the exported blocks and their ancestors.
+    pub committee_size: usize,
+    pub start_round: Round,
+    pub end_round: Round,
+    pub blocks: Vec<ExportedBlock>,
+    pub commits: Vec<ExportedCommit>,
+    pub leader_decisions: Vec<ExportedLeaderDecision>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct ExportedBlockRef {
+    pub round: Round,
+    pub author: usize,
+    pub digest: String,
+}
+
+impl From<&BlockRef> for ExportedBlockRef {
+    fn from(block_ref: &BlockRef) -> Self {
+        Self {
+            round: block_ref.round,
+            author: block_ref.author.value(),
+            digest: format!("{:?}", block_ref.digest),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExportedBlock {
+    pub reference: ExportedBlockRef,
+    pub timestamp_ms: u64,
+    pub ancestors: Vec<ExportedBlockRef>,
+    pub num_transactions: usize,
+    /// The commit that included the block, if the block has been committed.
+    pub commit_index: Option<CommitIndex>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExportedCommit {
+    pub index: CommitIndex,
+    pub timestamp_ms: u64,
+    pub leader: ExportedBlockRef,
+    pub blocks: Vec<ExportedBlockRef>,
+}
+
+/// Leader decisions are inferred from the commits: rounds between two consecutive
+/// committed leaders are considered skipped. This assumes one leader per round,
+/// and the elected leader of a skipped round is not known without the schedule.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "decision")]
+pub enum ExportedLeaderDecision {
+    Commit {
+        round: Round,
+        leader: ExportedBlockRef,
+        commit_index: CommitIndex,
+    },
+    Skip {
+        round: Round,
+    },
+}
+
+impl DagExport {
+    /// Reads the DAG between `start_round` and `end_round` (inclusive) from the
+    /// consensus RocksDB store at `db_path`, which is the per-epoch directory of
+    /// the validator's consensus db. The validator must not be running, since
+    /// the store cannot be opened concurrently.
+    pub fn from_rocksdb(
+        db_path: &Path,
+        start_round: Round,
+        end_round: Round,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            db_path.exists(),
+            "Consensus store {} does not exist",
+            db_path.display()
+        );
+        anyhow::ensure!(
+            start_round <= end_round,
+            "Start round {start_round} is after end round {end_round}"
+        );
+        let db_path = db_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid consensus store path"))?;
+        let store = RocksDBStore::new(db_path);
+        Self::from_store(&store, start_round, end_round)
+            .map_err(|e| anyhow::anyhow!("Failed to read consensus store: {e}"))
+    }
+
+    pub(crate) fn from_store(
+        store: &dyn Store,
+        start_round: Round,
+        end_round: Round,
+    ) -> ConsensusResult<Self> {
+        let blocks = store.scan_blocks_by_rounds(start_round, end_round)?;
+        let (previous_commit, commits) = read_commits(store, start_round, end_round)?;
+
+        let committed_blocks = commits
+            .iter()
+            .flat_map(|commit| {
+                commit
+                    .blocks()
+                    .iter()
+                    .map(|block_ref| (*block_ref, commit.index()))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let committee_size = blocks
+            .iter()
+            .flat_map(|block| {
+                std::iter::once(block.author()).chain(block.ancestors().iter().map(|a| a.author))
+            })
+            .map(|author| author.value() + 1)
+            .max()
+            .unwrap_or_default();
+
+        let mut leader_decisions = vec![];
+        let mut last_leader_round = previous_commit.map_or(0, |commit| commit.leader().round);
+        for commit in &commits {
+            let leader = commit.leader();
+            for round in (last_leader_round + 1).max(start_round)..leader.round {
+                leader_decisions.push(ExportedLeaderDecision::Skip { round });
+            }
+            leader_decisions.push(ExportedLeaderDecision::Commit {
+                round: leader.round,
+                leader: (&leader).into(),
+                commit_index: commit.index(),
+            });
+            last_leader_round = leader.round;
+        }
+
+        Ok(Self {
+            committee_size,
+            start_round,
+            end_round,
+            blocks: blocks
+                .iter()
+                .map(|block| export_block(block, committed_blocks.get(&block.reference())))
+                .collect(),
+            commits: commits
+                .iter()
+                .map(|commit| ExportedCommit {
+                    index: commit.index(),
+                    timestamp_ms: commit.timestamp_ms(),
+                    leader: (&commit.leader()).into(),
+                    blocks: commit.blocks().iter().map(Into::into).collect(),
+                })
+                .collect(),
+            leader_decisions,
+        })
+    }
+
+    /// Renders the DAG as a Graphviz digraph, with one rank per round. Blocks are
+    /// colored by the commit that included them, committed leaders are outlined
+    /// and weak links are dashed. Ancestors outside of the exported rounds are
+    /// omitted.
+    pub fn to_graphviz(&self) -> String {
+        const COMMIT_COLORS: [&str; 2] = ["lightblue", "lightgreen"];
+
+        let leaders = self
+            .commits
+            .iter()
+            .map(|commit| &commit.leader)
+            .collect::<BTreeSet<_>>();
+        let skipped_rounds = self
+            .leader_decisions
+            .iter()
+            .filter_map(|decision| match decision {
+                ExportedLeaderDecision::Skip { round } => Some(*round),
+                ExportedLeaderDecision::Commit { .. } => None,
+            })
+            .collect::<BTreeSet<_>>();
+        let exported = self
+            .blocks
+            .iter()
+            .map(|block| &block.reference)
+            .collect::<BTreeSet<_>>();
+
+        let mut dot = String::new();
+        writeln!(dot, "digraph DAG {{").unwrap();
+        writeln!(dot, "    rankdir=LR;").unwrap();
+        writeln!(
+            dot,
+            "    node [shape=box, style=filled, fontname=\"monospace\"];"
+        )
+        .unwrap();
+
+        let mut blocks_by_round: BTreeMap<Round, Vec<&ExportedBlock>> = BTreeMap::new();
+        for block in &self.blocks {
+            blocks_by_round
+                .entry(block.reference.round)
+                .or_default()
+                .push(block);
+        }
+
+        for (round, blocks) in &blocks_by_round {
+            let round_label = if skipped_rounds.contains(round) {
+                format!("Round {round}\\nleader skipped")
+            } else {
+                format!("Round {round}")
+            };
+            writeln!(dot, "    subgraph round_{round} {{").unwrap();
+            writeln!(dot, "        rank=same;").unwrap();
+            writeln!(
+                dot,
+                "        \"round_{round}\" [shape=plaintext, style=\"\", label=\"{round_label}\"];"
+            )
+            .unwrap();
+            for block in blocks {
+                let fill_color = block.commit_index.map_or("white", |index| {
+                    COMMIT_COLORS[index as usize % COMMIT_COLORS.len()]
+                });
+                let commit_label = block
+                    .commit_index
+                    .map_or("uncommitted".to_string(), |index| format!("commit {index}"));
+                let peripheries = if leaders.contains(&block.reference) {
+                    2
+                } else {
+                    1
+                };
+                writeln!(
+                    dot,
+                    "        \"{}\" [label=\"{}\\n{}\\n{} txns\", fillcolor={fill_color}, peripheries={peripheries}];",
+                    node_id(&block.reference),
+                    slot_name(&block.reference),
+                    commit_label,
+                    block.num_transactions,
+                )
+                .unwrap();
+            }
+            writeln!(dot, "    }}").unwrap();
+        }
+
+        for block in &self.blocks {
+            for ancestor in &block.ancestors {
+                if !exported.contains(ancestor) {
+                    continue;
+                }
+                let style = if ancestor.round + 1 == block.reference.round {
+                    "solid"
+                } else {
+                    "dashed"
+                };
+                writeln!(
+                    dot,
+                    "    \"{}\" -> \"{}\" [style={style}];",
+                    node_id(&block.reference),
+                    node_id(ancestor),
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+
+    /// Converts the DAG into the syntax of `test_dag_parser`, so it can be loaded
+    /// with `parse_dag` in a unit test. Rounds are rebased so that `start_round`
+    /// becomes round 1, which links to all genesis blocks. Ancestors before
+    /// `start_round` are dropped, and digests and timestamps are not preserved.
+    /// The syntax identifies authorities with a single letter and blocks by slot,
+    /// so larger committees and equivocating blocks are not supported.
+    pub fn to_dag_string(&self) -> anyhow::Result<String> {
+        anyhow::ensure!(
+            self.committee_size <= 26,
+            "DAG syntax supports up to 26 authorities, found {}",
+            self.committee_size
+        );
+
+        let mut slots = BTreeSet::new();
+        for block in &self.blocks {
+            anyhow::ensure!(
+                slots.insert((block.reference.round, block.reference.author)),
+                "Equivocating blocks are not supported in DAG syntax, found multiple blocks at {}",
+                slot_name(&block.reference)
+            );
+        }
+
+        let rebase = |round: Round| round - self.start_round + 1;
+        let mut dag = String::new();
+        writeln!(dag, "DAG {{").unwrap();
+        writeln!(dag, "    Round 0 : {{ {} }},", self.committee_size).unwrap();
+
+        let mut current_round = None;
+        for block in &self.blocks {
+            if current_round != Some(block.reference.round) {
+                if current_round.is_some() {
+                    writeln!(dag, "    }},").unwrap();
+                }
+                current_round = Some(block.reference.round);
+                writeln!(dag, "    Round {} : {{", rebase(block.reference.round)).unwrap();
+            }
+
+            let ancestors = if block.reference.round == self.start_round {
+                "*".to_string()
+            } else {
+                block
+                    .ancestors
+                    .iter()
+                    .filter(|ancestor| ancestor.round >= self.start_round)
+                    .map(|ancestor| {
+                        format!(
+                            "{}{}",
+                            authority_name(ancestor.author),
+                            rebase(ancestor.round)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            writeln!(
+                dag,
+                "        {} -> [{ancestors}],",
+                authority_name(block.reference.author)
+            )
+            .unwrap();
+        }
+        if current_round.is_some() {
+            writeln!(dag, "    }},").unwrap();
+        }
+        writeln!(dag, "}}").unwrap();
+
+        Ok(dag)
+    }
+}
+
+/// Reads the commits with leaders between `start_round` and `end_round`, along
+/// with the last commit before them which is needed to infer skipped leaders.
+/// Commits are scanned backwards from the last commit, since the exported rounds
+/// are usually recent.
+fn read_commits(
+    store: &dyn Store,
+    start_round: Round,
+    end_round: Round,
+) -> ConsensusResult<(Option<TrustedCommit>, Vec<TrustedCommit>)> {
+    let Some(last_commit) = store.read_last_commit()? else {
+        return Ok((None, vec![]));
+    };
+
+    let mut commits = vec![];
+    let mut end = last_commit.index();
+    loop {
+        let start = end.saturating_sub(COMMITS_SCAN_BATCH_SIZE - 1).max(1);
+        let batch = store.scan_commits((start..=end).into())?;
+        let reached_start_round = batch
+            .first()
+            .map_or(true, |commit| commit.leader().round < start_round);
+        commits.extend(batch.into_iter().rev());
+        if reached_start_round || start == 1 {
+            break;
+        }
+        end = start - 1;
+    }
+    commits.reverse();
+
+    let previous_commit = commits
+        .iter()
+        .rev()
+        .find(|commit| commit.leader().round < start_round)
+        .cloned();
+    commits.retain(|commit| (start_round..=end_round).contains(&commit.leader().round));
+
+    Ok((previous_commit, commits))
+}
+
+fn export_block(block: &VerifiedBlock, commit_index: Option<&CommitIndex>) -> ExportedBlock {
+    ExportedBlock {
+        reference: (&block.reference()).into(),
+        timestamp_ms: block.timestamp_ms(),
+        ancestors: block.ancestors().iter().map(Into::into).collect(),
+        num_transactions: block.transactions().len(),
+        commit_index: commit_index.copied(),
+    }
+}
+
+// Follows the `Display` format of `AuthorityIndex`.
+fn authority_name(author: usize) -> String {
+    if author < 26 {
+        ((b'A' + author as u8) as char).to_string()
+    } else {
+        format!("[{author:02}]")
+    }
+}
+
+fn slot_name(block_ref: &ExportedBlockRef) -> String {
+    format!("{}{}", authority_name(block_ref.author), block_ref.round)
+}
+
+fn node_id(block_ref: &ExportedBlockRef) -> String {
+    format!(
+        "{}_{}_{}",
+        block_ref.round, block_ref.author, block_ref.digest
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        block::Slot,
+        context::Context,
+        storage::{mem_store::MemStore, WriteBatch},
+        test_dag_builder::DagBuilder,
+        test_dag_parser::parse_dag,
+    };
+
+    // Builds a DAG for rounds 1 ~ 6 where the leader of round 3 is missing, and
+    // writes its blocks and commits to a store.
+    fn populate_store() -> (DagBuilder, MemStore) {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut dag_builder = DagBuilder::new(context);
+        dag_builder.layers(1..=2).build();
+        dag_builder.layer(3).no_leader_block(vec![]).build();
+        dag_builder.layers(4..=6).build();
+
+        let commits = dag_builder
+            .get_sub_dag_and_commits(1..=6)
+            .into_iter()
+            .map(|(_sub_dag, commit)| commit)
+            .collect::<Vec<_>>();
+        let store = MemStore::new();
+        store
+            .write(
+                WriteBatch::default()
+                    .blocks(dag_builder.all_blocks())
+                    .commits(commits),
+            )
+            .unwrap();
+
+        (dag_builder, store)
+    }
+
+    // Returns the ancestor slots of every block, keyed by block slot.
+    fn connections(blocks: &[VerifiedBlock]) -> BTreeMap<Slot, BTreeSet<Slot>> {
+        blocks
+            .iter()
+            .map(|block| {
+                (
+                    Slot::from(block.reference()),
+                    block.ancestors().iter().map(|a| Slot::from(*a)).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_export_blocks_and_leader_decisions() {
+        let (dag_builder, store) = populate_store();
+
+        let export = DagExport::from_store(&store, 1, 6).unwrap();
+        assert_eq!(export.committee_size, 4);
+        assert_eq!(export.blocks.len(), dag_builder.all_blocks().len());
+        assert_eq!(export.commits.len(), 5);
+        // Only the blocks of the last round, other than the leader, are not committed.
+        assert!(export
+            .blocks
+            .iter()
+            .all(|block| block.commit_index.is_some() || block.reference.round == 6));
+
+        let decisions = export
+            .leader_decisions
+            .iter()
+            .map(|decision| match decision {
+                ExportedLeaderDecision::Commit { round, .. } => (*round, true),
+                ExportedLeaderDecision::Skip { round } => (*round, false),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            decisions,
+            vec![
+                (1, true),
+                (2, true),
+                (3, false),
+                (4, true),
+                (5, true),
+                (6, true)
+            ]
+        );
+
+        // The previous commit is used to infer skipped leaders in a partial export.
+        let export = DagExport::from_store(&store, 3, 5).unwrap();
+        assert_eq!(export.commits.len(), 2);
+        assert_eq!(
+            export.leader_decisions.first(),
+            Some(&ExportedLeaderDecision::Skip { round: 3 })
+        );
+
+        let dot = export.to_graphviz();
+        assert!(dot.starts_with("digraph DAG {"));
+        assert!(dot.contains("leader skipped"));
+        assert!(dot.contains("peripheries=2"));
+    }
+
+    #[tokio::test]
+    async fn test_export_to_dag_string() {
+        let (dag_builder, store) = populate_store();
+
+        let export = DagExport::from_store(&store, 1, 6).unwrap();
+        let dag_str = export.to_dag_string().unwrap();
+        let (_, parsed_dag_builder) = parse_dag(&dag_str).expect("Invalid dag");
+        assert_eq!(
+            connections(&parsed_dag_builder.all_blocks()),
+            connections(&dag_builder.all_blocks())
+        );
+
+        // A partial export is rebased to start at round 1.
+        let export = DagExport::from_store(&store, 4, 6).unwrap();
+        let dag_str = export.to_dag_string().unwrap();
+        let (_, parsed_dag_builder) = parse_dag(&dag_str).expect("Invalid dag");
+        let rebased = connections(&parsed_dag_builder.all_blocks());
+        let original = connections(&dag_builder.blocks(5..=6));
+        assert_eq!(rebased.len(), 12);
+        for (slot, ancestors) in original {
+            let rebased_slot = Slot::new(slot.round - 3, slot.authority);
+            let rebased_ancestors = ancestors
+                .iter()
+                .map(|ancestor| Slot::new(ancestor.round - 3, ancestor.authority))
+                .collect::<BTreeSet<_>>();
+            assert_eq!(rebased[&rebased_slot], rebased_ancestors);
+        }
+    }
+}
@@ -10,6 +10,7 @@ use std::{
 
 use consensus_config::AuthorityIndex;
 use serde::{Deserialize, Serialize};
+use sui_protocol_config::ConsensusLeaderScoringStrategy;
 
 use crate::{
     block::{BlockAPI, BlockDigest, BlockRef, Slot},
@@ -165,12 +166,210 @@ impl ReputationScores {
     }
 }
 
+/// ScoringStrategy turns the votes collected in a `ScoringSubdag` into reputation
+/// scores. The scores are used to build the `LeaderSwapTable`, so a strategy must
+/// only depend on the committed subdags in order for all authorities to derive the
+/// same leader schedule.
+pub(crate) trait ScoringStrategy: Send + Sync {
+    fn calculate_scores_per_authority(&self, subdag: &ScoringSubdag) -> Vec<u64>;
+
+    // Returns which committed blocks need their propagation tracked by the
+    // `ScoringSubdag` for this strategy.
+    fn propagation_tracking(&self) -> PropagationTracking {
+        PropagationTracking::None
+    }
+
+    fn name(&self) -> &'static str;
+}
+
+/// The committed blocks whose propagation through the DAG is tracked by the
+/// `ScoringSubdag`. Tracking all blocks is more expensive than only tracking
+/// leader votes, so it is only done for strategies that need it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PropagationTracking {
+    None,
+    Votes,
+    AllBlocks,
+}
+
+/// Returns the scoring strategy configured in the protocol config. Defaults to
+/// `DistributedVoteScoringStrategy` when no strategy is set.
+pub(crate) fn scoring_strategy(context: &Context) -> Box<dyn ScoringStrategy> {
+    match context.protocol_config.consensus_leader_scoring_strategy() {
+        ConsensusLeaderScoringStrategy::None | ConsensusLeaderScoringStrategy::DistributedVote => {
+            Box::new(DistributedVoteScoringStrategy {})
+        }
+        ConsensusLeaderScoringStrategy::CertifiedVote => Box::new(CertifiedVoteScoringStrategy {}),
+        ConsensusLeaderScoringStrategy::CertificateLatency => {
+            Box::new(CertificateLatencyScoringStrategy {})
+        }
+        ConsensusLeaderScoringStrategy::PropagationDelay => {
+            Box::new(PropagationDelayScoringStrategy {})
+        }
+    }
+}
+
+/// This scoring strategy aims to give scores based on overall vote distribution.
+/// Instead of only giving one point for each vote that is included in 2f+1
+/// blocks. We give a score equal to the amount of stake of all blocks that
+/// included the vote.
+pub(crate) struct DistributedVoteScoringStrategy {}
+
+impl ScoringStrategy for DistributedVoteScoringStrategy {
+    fn calculate_scores_per_authority(&self, subdag: &ScoringSubdag) -> Vec<u64> {
+        let num_authorities = subdag.context.committee.size();
+        let mut scores_per_authority = vec![0_u64; num_authorities];
+
+        for (vote, stake_agg) in subdag.votes.iter() {
+            let authority = vote.author;
+            let stake = stake_agg.stake();
+            tracing::trace!(
+                "[{}] scores +{stake} reputation for {authority}!",
+                subdag.context.own_index,
+            );
+            scores_per_authority[authority.value()] += stake;
+        }
+        scores_per_authority
+    }
+
+    fn name(&self) -> &'static str {
+        "DistributedVote"
+    }
+}
+
+/// This scoring strategy gives points equal to the amount of stake in blocks
+/// that include the authority's vote, if that amount of total_stake > 2f+1.
+/// We consider this a certified vote.
+pub(crate) struct CertifiedVoteScoringStrategy {}
+
+impl ScoringStrategy for CertifiedVoteScoringStrategy {
+    fn calculate_scores_per_authority(&self, subdag: &ScoringSubdag) -> Vec<u64> {
+        let num_authorities = subdag.context.committee.size();
+        let mut scores_per_authority = vec![0_u64; num_authorities];
+
+        for (vote, stake_agg) in subdag.votes.iter() {
+            let authority = vote.author;
+            if stake_agg.reached_threshold(&subdag.context.committee) {
+                let stake = stake_agg.stake();
+                tracing::trace!(
+                    "[{}] scores +{stake} reputation for {authority}!",
+                    subdag.context.own_index,
+                );
+                scores_per_authority[authority.value()] += stake;
+            }
+        }
+        scores_per_authority
+    }
+
+    fn name(&self) -> &'static str {
+        "CertifiedVote"
+    }
+}
+
+/// This scoring strategy rewards authorities whose votes are certified quickly.
+/// Strong links always come from the round right after the vote, so a vote that
+/// is only included through weak links by a quorum took longer to be certified.
+/// Each certified vote scores the stake of all blocks that included it, divided
+/// by the number of rounds it took for the vote to be included by 2f+1 stake.
+/// Votes that never get certified do not score.
+pub(crate) struct CertificateLatencyScoringStrategy {}
+
+impl ScoringStrategy for CertificateLatencyScoringStrategy {
+    fn calculate_scores_per_authority(&self, subdag: &ScoringSubdag) -> Vec<u64> {
+        let num_authorities = subdag.context.committee.size();
+        let mut scores_per_authority = vec![0_u64; num_authorities];
+
+        for (vote, propagation) in subdag.block_propagation.iter() {
+            let Some(quorum_round) = propagation.quorum_round else {
+                continue;
+            };
+            let authority = vote.author;
+            let latency = quorum_round.saturating_sub(vote.round).max(1) as u64;
+            let score = propagation.stake.stake() / latency;
+            tracing::trace!(
+                "[{}] scores +{score} reputation for {authority} with certificate latency {latency}!",
+                subdag.context.own_index,
+            );
+            scores_per_authority[authority.value()] += score;
+        }
+        scores_per_authority
+    }
+
+    fn propagation_tracking(&self) -> PropagationTracking {
+        PropagationTracking::Votes
+    }
+
+    fn name(&self) -> &'static str {
+        "CertificateLatency"
+    }
+}
+
+/// The number of rounds after which a block that has not been included by 2f+1
+/// stake no longer scores under the `PropagationDelayScoringStrategy`.
+const MAX_SCORED_PROPAGATION_DELAY: Round = 5;
+
+/// This scoring strategy rewards authorities whose blocks propagate quickly
+/// through the DAG, which is the same signal the `RoundProber` measures from
+/// probed peer rounds. Probed rounds are local to each authority though, so here
+/// the propagation delay of a block is derived from the committed subdags instead:
+/// it is the number of rounds after the block's round until the block is included
+/// by 2f+1 stake. Every block scores `MAX_SCORED_PROPAGATION_DELAY` minus its delay,
+/// so a block included by a quorum in the very next round gets the full score.
+pub(crate) struct PropagationDelayScoringStrategy {}
+
+impl ScoringStrategy for PropagationDelayScoringStrategy {
+    fn calculate_scores_per_authority(&self, subdag: &ScoringSubdag) -> Vec<u64> {
+        let num_authorities = subdag.context.committee.size();
+        let mut scores_per_authority = vec![0_u64; num_authorities];
+
+        for (block_ref, propagation) in subdag.block_propagation.iter() {
+            let Some(quorum_round) = propagation.quorum_round else {
+                continue;
+            };
+            let authority = block_ref.author;
+            let delay = quorum_round.saturating_sub(block_ref.round + 1);
+            let score = MAX_SCORED_PROPAGATION_DELAY.saturating_sub(delay) as u64;
+            tracing::trace!(
+                "[{}] scores +{score} reputation for {authority} with propagation delay {delay}!",
+                subdag.context.own_index,
+            );
+            scores_per_authority[authority.value()] += score;
+        }
+        scores_per_authority
+    }
+
+    fn propagation_tracking(&self) -> PropagationTracking {
+        PropagationTracking::AllBlocks
+    }
+
+    fn name(&self) -> &'static str {
+        "PropagationDelay"
+    }
+}
+
+/// BlockPropagation tracks the stake of the committed blocks that include a block,
+/// along with the round at which that stake first reached quorum.
+pub(crate) struct BlockPropagation {
+    pub(crate) stake: StakeAggregator<QuorumThreshold>,
+    pub(crate) quorum_round: Option<Round>,
+}
+
+impl BlockPropagation {
+    fn new() -> Self {
+        Self {
+            stake: StakeAggregator::new(),
+            quorum_round: None,
+        }
+    }
+}
+
 /// ScoringSubdag represents the scoring votes in a collection of subdags across
 /// multiple commits.
 /// These subdags are "scoring" for the purposes of leader schedule change. As
 /// new subdags are added, the DAG is traversed and votes for leaders are recorded
 /// and scored along with stake. On a leader schedule change, finalized reputation
-/// scores will be calculated based on the votes & stake collected in this struct.
+/// scores will be calculated based on the votes & stake collected in this struct,
+/// using the `ScoringStrategy` selected by the protocol config.
 pub(crate) struct ScoringSubdag {
     pub(crate) context: Arc<Context>,
     pub(crate) commit_range: Option<CommitRange>,
@@ -181,15 +380,22 @@ pub(crate) struct ScoringSubdag {
     // Note: Inlcuding stake aggregator so that we can quickly check if it exceeds
     // quourum threshold and only include those scores for certain scoring strategies.
     pub(crate) votes: BTreeMap<BlockRef, StakeAggregator<QuorumThreshold>>,
+    // A map of committed blocks to the stake of all blocks that include them.
+    // Only populated for the blocks the scoring strategy tracks propagation for.
+    pub(crate) block_propagation: BTreeMap<BlockRef, BlockPropagation>,
+    strategy: Box<dyn ScoringStrategy>,
 }
 
 impl ScoringSubdag {
     pub(crate) fn new(context: Arc<Context>) -> Self {
+        let strategy = scoring_strategy(&context);
         Self {
             context,
             commit_range: None,
             leaders: HashSet::new(),
             votes: BTreeMap::new(),
+            block_propagation: BTreeMap::new(),
+            strategy,
         }
     }
 
@@ -220,7 +426,20 @@ impl ScoringSubdag {
             // Check each block in subdag. Blocks are in order so we should traverse the
             // oldest blocks first
             for block in subdag.blocks {
+                let mut is_vote = false;
                 for ancestor in block.ancestors() {
+                    // Both strong and weak links propagate a block, so check
+                    // them before skipping weak links.
+                    if let Some(propagation) = self.block_propagation.get_mut(ancestor) {
+                        if propagation
+                            .stake
+                            .add(block.author(), &self.context.committee)
+                            && propagation.quorum_round.is_none()
+                        {
+                            propagation.quorum_round = Some(block.round());
+                        }
+                    }
+
                     // Weak links may point to blocks with lower round numbers
                     // than strong links.
                     if ancestor.round != block.round().saturating_sub(1) {
@@ -241,6 +460,7 @@ impl ScoringSubdag {
                             .votes
                             .insert(block.reference(), StakeAggregator::new())
                             .is_none(), "Vote {block} already exists. Duplicate vote found for leader {ancestor}");
+                        is_vote = true;
                     }
 
                     if let Some(stake) = self.votes.get_mut(ancestor) {
@@ -253,12 +473,22 @@ impl ScoringSubdag {
                         stake.add(block.author(), &self.context.committee);
                     }
                 }
+
+                let track_propagation = match self.strategy.propagation_tracking() {
+                    PropagationTracking::None => false,
+                    PropagationTracking::Votes => is_vote,
+                    PropagationTracking::AllBlocks => true,
+                };
+                if track_propagation {
+                    self.block_propagation
+                        .insert(block.reference(), BlockPropagation::new());
+                }
             }
         }
     }
 
     // Iterate through votes and calculate scores for each authority based on
-    // scoring strategy that is used. (Vote or CertifiedVote)
+    // the scoring strategy that is used.
     pub(crate) fn calculate_scores(&self) -> ReputationScores {
         let _s = self
             .context
@@ -268,7 +498,11 @@ impl ScoringSubdag {
             .with_label_values(&["ScoringSubdag::calculate_scores"])
             .start_timer();
 
-        let scores_per_authority = self.score_distributed_votes();
+        tracing::debug!(
+            "Calculating scores with {} scoring strategy",
+            self.strategy.name()
+        );
+        let scores_per_authority = self.strategy.calculate_scores_per_authority(self);
 
         // TODO: Normalize scores
         ReputationScores::new(
@@ -279,49 +513,6 @@ impl ScoringSubdag {
         )
     }
 
-    /// This scoring strategy aims to give scores based on overall vote distribution.
-    /// Instead of only giving one point for each vote that is included in 2f+1
-    /// blocks. We give a score equal to the amount of stake of all blocks that
-    /// included the vote.
-    fn score_distributed_votes(&self) -> Vec<u64> {
-        let num_authorities = self.context.committee.size();
-        let mut scores_per_authority = vec![0_u64; num_authorities];
-
-        for (vote, stake_agg) in self.votes.iter() {
-            let authority = vote.author;
-            let stake = stake_agg.stake();
-            tracing::trace!(
-                "[{}] scores +{stake} reputation for {authority}!",
-                self.context.own_index,
-            );
-            scores_per_authority[authority.value()] += stake;
-        }
-        scores_per_authority
-    }
-
-    /// This scoring strategy gives points equal to the amount of stake in blocks
-    /// that include the authority's vote, if that amount of total_stake > 2f+1.
-    /// We consider this a certified vote.
-    // TODO: This will be used for ancestor selection
-    #[allow(unused)]
-    fn score_certified_votes(&self) -> Vec<u64> {
-        let num_authorities = self.context.committee.size();
-        let mut scores_per_authority = vec![0_u64; num_authorities];
-
-        for (vote, stake_agg) in self.votes.iter() {
-            let authority = vote.author;
-            if stake_agg.reached_threshold(&self.context.committee) {
-                let stake = stake_agg.stake();
-                tracing::trace!(
-                    "[{}] scores +{stake} reputation for {authority}!",
-                    self.context.own_index,
-                );
-                scores_per_authority[authority.value()] += stake;
-            }
-        }
-        scores_per_authority
-    }
-
     pub(crate) fn scored_subdags_count(&self) -> usize {
         if let Some(commit_range) = &self.commit_range {
             commit_range.size()
@@ -331,12 +522,16 @@ impl ScoringSubdag {
     }
 
     pub(crate) fn is_empty(&self) -> bool {
-        self.leaders.is_empty() && self.votes.is_empty() && self.commit_range.is_none()
+        self.leaders.is_empty()
+            && self.votes.is_empty()
+            && self.block_propagation.is_empty()
+            && self.commit_range.is_none()
     }
 
     pub(crate) fn clear(&mut self) {
         self.leaders.clear();
         self.votes.clear();
+        self.block_propagation.clear();
         self.commit_range = None;
     }
 }
@@ -585,11 +780,138 @@ mod tests {
             scoring_subdag.add_subdags(vec![sub_dag]);
         }
 
-        let scores_per_authority = scoring_subdag.score_certified_votes();
+        let scores_per_authority =
+            CertifiedVoteScoringStrategy {}.calculate_scores_per_authority(&scoring_subdag);
         assert_eq!(scores_per_authority, vec![4, 4, 4, 4]);
         assert_eq!(scoring_subdag.commit_range.unwrap(), (1..=4).into());
     }
 
+    #[tokio::test]
+    async fn test_scoring_strategy_from_protocol_config() {
+        for (configured, expected) in [
+            (ConsensusLeaderScoringStrategy::None, "DistributedVote"),
+            (
+                ConsensusLeaderScoringStrategy::DistributedVote,
+                "DistributedVote",
+            ),
+            (
+                ConsensusLeaderScoringStrategy::CertifiedVote,
+                "CertifiedVote",
+            ),
+            (
+                ConsensusLeaderScoringStrategy::CertificateLatency,
+                "CertificateLatency",
+            ),
+            (
+                ConsensusLeaderScoringStrategy::PropagationDelay,
+                "PropagationDelay",
+            ),
+        ] {
+            let mut context = Context::new_for_test(4).0;
+            context
+                .protocol_config
+                .set_consensus_leader_scoring_strategy_for_testing(configured);
+            assert_eq!(scoring_strategy(&context).name(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_certificate_latency_scoring_subdag() {
+        telemetry_subscribers::init_for_testing();
+        let mut context = Context::new_for_test(4).0;
+        context
+            .protocol_config
+            .set_consensus_leader_scoring_strategy_for_testing(
+                ConsensusLeaderScoringStrategy::CertificateLatency,
+            );
+        let context = Arc::new(context);
+
+        let mut dag_builder = build_dag_with_weakly_linked_authority(context.clone());
+        let mut scoring_subdag = ScoringSubdag::new(context.clone());
+
+        for (sub_dag, _commit) in dag_builder.get_sub_dag_and_commits(1..=5) {
+            scoring_subdag.add_subdags(vec![sub_dag]);
+        }
+
+        // Only votes are tracked by this strategy.
+        assert!(scoring_subdag
+            .block_propagation
+            .keys()
+            .all(|block_ref| scoring_subdag.votes.contains_key(block_ref)));
+
+        // The vote of authority 3 for the leader of round 1 is only certified
+        // through weak links in round 4, so it scores half of its stake.
+        let scores = scoring_subdag.calculate_scores();
+        assert_eq!(scores.scores_per_authority, vec![7, 7, 7, 2]);
+        assert_eq!(scores.commit_range, (1..=4).into());
+    }
+
+    #[tokio::test]
+    async fn test_propagation_delay_scoring_subdag() {
+        telemetry_subscribers::init_for_testing();
+        let mut context = Context::new_for_test(4).0;
+        context
+            .protocol_config
+            .set_consensus_leader_scoring_strategy_for_testing(
+                ConsensusLeaderScoringStrategy::PropagationDelay,
+            );
+        let context = Arc::new(context);
+
+        let mut dag_builder = build_dag_with_weakly_linked_authority(context.clone());
+        let mut scoring_subdag = ScoringSubdag::new(context.clone());
+
+        for (sub_dag, _commit) in dag_builder.get_sub_dag_and_commits(1..=5) {
+            scoring_subdag.add_subdags(vec![sub_dag]);
+        }
+
+        // Authority 3 has no block in round 3 and its block in round 2 reaches
+        // a quorum one round late. Blocks of round 4 are never included by a
+        // quorum of committed blocks and do not score.
+        let scores = scoring_subdag.calculate_scores();
+        assert_eq!(scores.scores_per_authority, vec![15, 15, 15, 9]);
+
+        scoring_subdag.clear();
+        assert!(scoring_subdag.is_empty());
+    }
+
+    // Builds a DAG for rounds 1 ~ 5 where the blocks of round 3 do not link to
+    // authority 3, which does not propose in round 3 either. Its block of round 2
+    // is weakly linked by all blocks of round 4 instead.
+    fn build_dag_with_weakly_linked_authority(context: Arc<Context>) -> DagBuilder {
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=2).build();
+        dag_builder
+            .layer(3)
+            .authorities(vec![
+                AuthorityIndex::new_for_test(0),
+                AuthorityIndex::new_for_test(1),
+                AuthorityIndex::new_for_test(2),
+            ])
+            .skip_ancestor_links(vec![AuthorityIndex::new_for_test(3)]);
+
+        let mut ancestors = dag_builder
+            .blocks(3..=3)
+            .iter()
+            .map(|block| block.reference())
+            .collect::<Vec<_>>();
+        ancestors.extend(
+            dag_builder
+                .blocks(2..=2)
+                .iter()
+                .filter(|block| block.author() == AuthorityIndex::new_for_test(3))
+                .map(|block| block.reference()),
+        );
+        let connections = context
+            .committee
+            .authorities()
+            .map(|(authority, _)| (authority, ancestors.clone()))
+            .collect::<Vec<_>>();
+        dag_builder.layer_with_connections(connections, 4);
+        dag_builder.layer(5).build();
+
+        dag_builder
+    }
+
     // TODO: Remove all tests below this when DistributedVoteScoring is enabled.
     #[tokio::test]
     async fn test_reputation_score_calculator() {
@@ -8,6 +8,8 @@ mod block;
 mod block_manager;
 mod block_verifier;
 mod broadcaster;
+#[cfg(any(test, msim))]
+mod byzantine;
 mod commit;
 mod commit_consumer;
 mod commit_observer;
@@ -16,6 +18,7 @@ mod commit_vote_monitor;
 mod context;
 mod core;
 mod core_thread;
+mod dag_export;
 mod dag_state;
 mod error;
 mod leader_schedule;
@@ -32,6 +35,9 @@ mod threshold_clock;
 mod transaction;
 mod universal_committer;
 
+#[cfg(test)]
+#[path = "tests/leader_scoring_simulation_tests.rs"]
+mod leader_scoring_simulation_tests;
 #[cfg(test)]
 #[path = "tests/randomized_tests.rs"]
 mod randomized_tests;
@@ -48,8 +54,13 @@ pub use authority_node::ConsensusAuthority;
 pub use block::{BlockAPI, Round, TransactionIndex};
 /// Exported API for testing.
 pub use block::{TestBlock, Transaction, VerifiedBlock};
+#[cfg(any(test, msim))]
+pub use byzantine::ByzantineBehavior;
 pub use commit::{CommitDigest, CommitIndex, CommitRef, CommittedSubDag};
 pub use commit_consumer::{CommitConsumer, CommitConsumerMonitor};
+pub use dag_export::{
+    DagExport, ExportedBlock, ExportedBlockRef, ExportedCommit, ExportedLeaderDecision,
+};
 pub use network::{
     connection_monitor::{AnemoConnectionMonitor, ConnectionMonitorHandle, ConnectionStatus},
     metrics::{MetricsMakeCallbackHandler, NetworkRouteMetrics, QuinnConnectionMetrics},
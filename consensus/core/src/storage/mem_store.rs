@@ -129,6 +129,23 @@ impl Store for MemStore {
         Ok(blocks)
     }
 
+    fn scan_blocks_by_rounds(
+        &self,
+        start_round: Round,
+        end_round: Round,
+    ) -> ConsensusResult<Vec<VerifiedBlock>> {
+        let inner = self.inner.read();
+        let blocks = inner
+            .blocks
+            .range((
+                Included((start_round, AuthorityIndex::MIN, BlockDigest::MIN)),
+                Included((end_round, AuthorityIndex::MAX, BlockDigest::MAX)),
+            ))
+            .map(|(_, block)| block.clone())
+            .collect();
+        Ok(blocks)
+    }
+
     fn contains_block_at_slot(&self, slot: Slot) -> ConsensusResult<bool> {
         let inner = self.inner.read();
         let found = inner
@@ -212,6 +212,27 @@ impl Store for RocksDBStore {
         Ok(blocks)
     }
 
+    fn scan_blocks_by_rounds(
+        &self,
+        start_round: Round,
+        end_round: Round,
+    ) -> ConsensusResult<Vec<VerifiedBlock>> {
+        let mut blocks = vec![];
+        for kv in self.blocks.safe_range_iter((
+            Included((start_round, AuthorityIndex::MIN, BlockDigest::MIN)),
+            Included((end_round, AuthorityIndex::MAX, BlockDigest::MAX)),
+        )) {
+            let ((round, author, digest), serialized) = kv?;
+            let signed_block: SignedBlock =
+                bcs::from_bytes(&serialized).map_err(ConsensusError::MalformedBlock)?;
+            let block = VerifiedBlock::new_verified(signed_block, serialized);
+            // Makes sure block data is not corrupted, by comparing digests.
+            assert_eq!(BlockRef::new(round, author, digest), block.reference());
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
     // The method returns the last `num_of_rounds` rounds blocks by author in round ascending order.
     // When a `before_round` is defined then the blocks of round `<=before_round` are returned. If not
     // then the max value for round will be used as cut off.
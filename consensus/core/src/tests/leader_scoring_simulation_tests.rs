@@ -0,0 +1,305 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use consensus_config::AuthorityIndex;
+use parking_lot::RwLock;
+use sui_protocol_config::ConsensusLeaderScoringStrategy;
+
+use crate::{
+    block::{BlockAPI, BlockRef, Slot},
+    commit::DecidedLeader,
+    context::Context,
+    dag_state::DagState,
+    leader_schedule::{LeaderSchedule, LeaderSwapTable},
+    leader_scoring::ScoringSubdag,
+    linearizer::Linearizer,
+    storage::mem_store::MemStore,
+    test_dag_builder::DagBuilder,
+    universal_committer::universal_committer_builder::UniversalCommitterBuilder,
+    Round,
+};
+
+const NUM_AUTHORITIES: usize = 10;
+const NUM_ROUNDS: Round = 100;
+
+// With pipelining a leader is decided two rounds after it is proposed: votes
+// are in the next round and certificates in the round after.
+const DIRECT_DECISION_ROUNDS: Round = 2;
+
+/// The behavior of an authority in the simulated DAG.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AuthorityBehavior {
+    Honest,
+    /// Blocks reach the other authorities too late to be strongly linked, so
+    /// they are only weakly linked two rounds after they are proposed.
+    Slow,
+    /// Never votes for the leader of the previous round.
+    WithholdVotes,
+}
+
+/// Outcome of a simulated scoring window followed by an evaluation window that
+/// uses the leader schedule derived from the scores.
+struct SimulationResult {
+    swap_table: LeaderSwapTable,
+    skipped_leaders: usize,
+    committed_leaders: usize,
+    average_commit_latency: f64,
+}
+
+/// Builds a DAG for rounds 1 ~ `NUM_ROUNDS` where every authority follows its
+/// behavior. Leaders used to decide which links are withheld are elected with
+/// the provided `LeaderSchedule`.
+fn build_dag(
+    context: Arc<Context>,
+    behaviors: &[AuthorityBehavior],
+    leader_schedule: &LeaderSchedule,
+) -> DagBuilder {
+    let mut dag_builder = DagBuilder::new(context.clone());
+    let mut previous_round: Vec<BlockRef> = dag_builder.genesis_block_refs();
+    let mut two_rounds_ago: Vec<BlockRef> = vec![];
+
+    for round in 1..=NUM_ROUNDS {
+        let leader = leader_schedule.elect_leader(round - 1, 0);
+        let connections = context
+            .committee
+            .authorities()
+            .map(|(authority, _)| {
+                let mut ancestors = previous_round
+                    .iter()
+                    .filter(|ancestor| {
+                        if ancestor.author == authority || ancestor.round == 0 {
+                            return true;
+                        }
+                        if behaviors[ancestor.author.value()] == AuthorityBehavior::Slow {
+                            return false;
+                        }
+                        !(behaviors[authority.value()] == AuthorityBehavior::WithholdVotes
+                            && ancestor.author == leader)
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+                ancestors.extend(
+                    two_rounds_ago
+                        .iter()
+                        .filter(|ancestor| {
+                            ancestor.author != authority
+                                && behaviors[ancestor.author.value()] == AuthorityBehavior::Slow
+                        })
+                        .cloned(),
+                );
+                (authority, ancestors)
+            })
+            .collect::<Vec<_>>();
+
+        dag_builder.layer_with_connections(connections, round);
+
+        two_rounds_ago = std::mem::replace(
+            &mut previous_round,
+            dag_builder
+                .blocks(round..=round)
+                .iter()
+                .map(|block| block.reference())
+                .collect(),
+        );
+        two_rounds_ago.retain(|ancestor| ancestor.round > 0);
+    }
+
+    dag_builder
+}
+
+/// Runs the committer over a DAG built with the provided behaviors and leader
+/// schedule. Returns the decided leaders and the dag state holding the DAG.
+fn decide_leaders(
+    context: Arc<Context>,
+    behaviors: &[AuthorityBehavior],
+    leader_schedule: Arc<LeaderSchedule>,
+) -> (Vec<DecidedLeader>, Arc<RwLock<DagState>>) {
+    let dag_state = Arc::new(RwLock::new(DagState::new(
+        context.clone(),
+        Arc::new(MemStore::new()),
+    )));
+    let dag_builder = build_dag(context.clone(), behaviors, &leader_schedule);
+    dag_builder.persist_all_blocks(dag_state.clone());
+
+    let committer =
+        UniversalCommitterBuilder::new(context.clone(), leader_schedule, dag_state.clone())
+            .with_pipeline(true)
+            .build();
+    let sequence = committer.try_decide(Slot::new_for_test(0, 0));
+
+    (sequence, dag_state)
+}
+
+/// Simulates a scoring window using the default leader schedule, builds the
+/// `LeaderSwapTable` from the scores of the configured strategy and then runs
+/// an evaluation window with the resulting schedule.
+fn simulate(
+    strategy: ConsensusLeaderScoringStrategy,
+    behaviors: &[AuthorityBehavior],
+    apply_swap_table: bool,
+) -> SimulationResult {
+    let mut context = Context::new_for_test(NUM_AUTHORITIES).0;
+    context
+        .protocol_config
+        .set_consensus_distributed_vote_scoring_strategy_for_testing(true);
+    context
+        .protocol_config
+        .set_consensus_leader_scoring_strategy_for_testing(strategy);
+    let context = Arc::new(context);
+
+    // Scoring window.
+    let leader_schedule = Arc::new(LeaderSchedule::new(
+        context.clone(),
+        LeaderSwapTable::default(),
+    ));
+    let (sequence, dag_state) = decide_leaders(context.clone(), behaviors, leader_schedule.clone());
+    let committed_leaders = sequence
+        .into_iter()
+        .filter_map(|decided| decided.into_committed_block())
+        .collect::<Vec<_>>();
+    let mut linearizer = Linearizer::new(dag_state, leader_schedule);
+    let sub_dags = linearizer.handle_commit(committed_leaders);
+    let last_commit_index = sub_dags.last().unwrap().commit_ref.index;
+
+    let mut scoring_subdag = ScoringSubdag::new(context.clone());
+    scoring_subdag.add_subdags(sub_dags);
+    let swap_table = LeaderSwapTable::new(
+        context.clone(),
+        last_commit_index,
+        scoring_subdag.calculate_scores(),
+    );
+
+    // Evaluation window.
+    let leader_schedule = Arc::new(LeaderSchedule::new(
+        context.clone(),
+        if apply_swap_table {
+            swap_table.clone()
+        } else {
+            LeaderSwapTable::default()
+        },
+    ));
+    let (sequence, _dag_state) = decide_leaders(context.clone(), behaviors, leader_schedule);
+
+    let committed_rounds = sequence
+        .iter()
+        .filter_map(|decided| match decided {
+            DecidedLeader::Commit(block) => Some(block.round()),
+            DecidedLeader::Skip(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let skipped_leaders = sequence.len() - committed_rounds.len();
+
+    // A block proposed in a round is committed by the first committed leader of
+    // a later round, which is decided `DIRECT_DECISION_ROUNDS` after that.
+    let latencies = (1..*committed_rounds.last().unwrap())
+        .map(|round| {
+            let commit_round = committed_rounds
+                .iter()
+                .find(|commit_round| **commit_round > round)
+                .unwrap();
+            commit_round - round + DIRECT_DECISION_ROUNDS
+        })
+        .collect::<Vec<_>>();
+    let average_commit_latency =
+        latencies.iter().map(|latency| *latency as f64).sum::<f64>() / latencies.len() as f64;
+
+    tracing::info!(
+        "{strategy:?} (swap table applied: {apply_swap_table}): bad nodes {:?}, {} committed & {skipped_leaders} skipped leaders, average commit latency {average_commit_latency:.2} rounds",
+        swap_table.bad_nodes.keys().collect::<Vec<_>>(),
+        committed_rounds.len(),
+    );
+
+    SimulationResult {
+        swap_table,
+        skipped_leaders,
+        committed_leaders: committed_rounds.len(),
+        average_commit_latency,
+    }
+}
+
+fn behaviors(slow: &[u32], withhold_votes: &[u32]) -> Vec<AuthorityBehavior> {
+    (0..NUM_AUTHORITIES as u32)
+        .map(|authority| {
+            if slow.contains(&authority) {
+                AuthorityBehavior::Slow
+            } else if withhold_votes.contains(&authority) {
+                AuthorityBehavior::WithholdVotes
+            } else {
+                AuthorityBehavior::Honest
+            }
+        })
+        .collect()
+}
+
+/// Every strategy should identify a slow authority as a bad node, and swapping
+/// it out of the schedule should remove the leaders skipped because of it.
+#[tokio::test]
+async fn test_leader_scoring_strategies_with_slow_authority() {
+    telemetry_subscribers::init_for_testing();
+    let slow = AuthorityIndex::new_for_test(3);
+    let behaviors = behaviors(&[slow.value() as u32], &[]);
+
+    let baseline = simulate(ConsensusLeaderScoringStrategy::None, &behaviors, false);
+    assert!(baseline.skipped_leaders > 0);
+
+    for strategy in [
+        ConsensusLeaderScoringStrategy::DistributedVote,
+        ConsensusLeaderScoringStrategy::CertifiedVote,
+        ConsensusLeaderScoringStrategy::CertificateLatency,
+        ConsensusLeaderScoringStrategy::PropagationDelay,
+    ] {
+        let result = simulate(strategy, &behaviors, true);
+        assert!(
+            result.swap_table.bad_nodes.contains_key(&slow),
+            "{strategy:?} did not detect slow authority {slow}"
+        );
+        assert_eq!(result.skipped_leaders, 0, "{strategy:?}");
+        assert!(result.committed_leaders >= baseline.committed_leaders);
+        assert!(result.average_commit_latency < baseline.average_commit_latency);
+    }
+}
+
+/// Strategies based on votes should identify authorities withholding votes, while
+/// the propagation delay of their blocks is not affected. Withholding votes does
+/// not prevent leaders from being committed, so it does not affect latency.
+#[tokio::test]
+async fn test_leader_scoring_strategies_with_adversarial_authorities() {
+    telemetry_subscribers::init_for_testing();
+    let slow = AuthorityIndex::new_for_test(3);
+    let withholder = AuthorityIndex::new_for_test(7);
+    let behaviors = behaviors(&[slow.value() as u32], &[withholder.value() as u32]);
+
+    let baseline = simulate(ConsensusLeaderScoringStrategy::None, &behaviors, false);
+
+    for strategy in [
+        ConsensusLeaderScoringStrategy::DistributedVote,
+        ConsensusLeaderScoringStrategy::CertifiedVote,
+        ConsensusLeaderScoringStrategy::CertificateLatency,
+    ] {
+        let result = simulate(strategy, &behaviors, true);
+        assert_eq!(
+            result
+                .swap_table
+                .bad_nodes
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>(),
+            vec![slow, withholder],
+            "{strategy:?}"
+        );
+        assert_eq!(result.skipped_leaders, 0, "{strategy:?}");
+        assert!(result.average_commit_latency < baseline.average_commit_latency);
+    }
+
+    let result = simulate(
+        ConsensusLeaderScoringStrategy::PropagationDelay,
+        &behaviors,
+        true,
+    );
+    assert!(result.swap_table.bad_nodes.contains_key(&slow));
+    let scores = &result.swap_table.reputation_scores.scores_per_authority;
+    assert_eq!(scores[withholder.value()], *scores.iter().max().unwrap());
+    assert_eq!(result.skipped_leaders, 0);
+}
@@ -1,16 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{env, sync::Arc};
+use std::{collections::BTreeMap, env, sync::Arc, time::Duration};
 
-use consensus_config::AuthorityIndex;
+use consensus_config::{AuthorityIndex, NetworkKeyPair, ProtocolKeyPair};
 use parking_lot::RwLock;
 use rand::{prelude::SliceRandom, rngs::StdRng, Rng, SeedableRng};
 
 use crate::{
-    block::{BlockAPI, Slot},
+    block::{genesis_blocks, BlockAPI, BlockRef, SignedBlock, Slot, TestBlock, VerifiedBlock},
     block_manager::BlockManager,
     block_verifier::NoopBlockVerifier,
+    byzantine::ByzantineBehavior,
     commit::DecidedLeader,
     context::Context,
     dag_state::DagState,
@@ -169,6 +170,284 @@ async fn test_randomized_dag_and_decision_sequence() {
     }
 }
 
+/// Test builds a randomized dag in which up to f authorities misbehave according to a random
+/// `ByzantineBehavior`, the same behaviors that can be injected into a running authority:
+/// - Equivocating authorities send a different block of the same slot to each peer.
+/// - Blocks withheld from a peer or delayed are not linked by that peer in the following round,
+///   the peer only fetches them once they are referenced by others.
+/// - Blocks with invalid signatures are rejected by every honest authority.
+///
+/// Honest authorities link to 2f+1 or more honest blocks, always including the leader of the
+/// previous round, along with the byzantine blocks they received. The blocks are fed through the
+/// BlockManager of two honest authorities in different orders, which must decide the same
+/// sequence of leaders (safety) in which every honest leader is committed (liveness).
+#[tokio::test]
+async fn test_randomized_dag_with_byzantine_authorities() {
+    let mut random_test_setup = random_test_setup();
+
+    for _ in 0..NUM_RUNS {
+        let num_authorities = random_test_setup.seeded_rng.gen_range(4..10);
+        let num_byzantine = (num_authorities - 1) / 3;
+
+        let mut authorities = (0..num_authorities as u32)
+            .map(AuthorityIndex::new_for_test)
+            .collect::<Vec<_>>();
+        authorities.shuffle(&mut random_test_setup.seeded_rng);
+        let byzantine = authorities[..num_byzantine]
+            .iter()
+            .map(|authority| {
+                let behavior =
+                    random_byzantine_behavior(&mut random_test_setup.seeded_rng, num_authorities);
+                (*authority, behavior)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let honest = &authorities[num_byzantine..];
+
+        let (context, keys) = Context::new_for_test(num_authorities);
+        let blocks = create_random_byzantine_dag(
+            &mut random_test_setup.seeded_rng,
+            Arc::new(context),
+            &keys,
+            &byzantine,
+            NUM_ROUNDS,
+        );
+
+        tracing::info!(
+            "Running test with committee size {num_authorities}, {num_byzantine} byzantine \
+            authorities & {NUM_ROUNDS} rounds in the DAG..."
+        );
+
+        let mut sequences = vec![];
+        for observer in &honest[..2] {
+            let mut authority = authority_setup(num_authorities, observer.value() as u32);
+            let sequence = decide_leaders(
+                &mut authority,
+                blocks.clone(),
+                &mut random_test_setup.seeded_rng,
+            );
+
+            // Every leader is decided up to the last honest one, as honest leaders are directly
+            // committed and allow the preceding leaders to be decided indirectly.
+            let last_honest_leader_round = (1..=NUM_ROUNDS - 2)
+                .rev()
+                .find(|round| !byzantine.contains_key(&authority.committer.get_leaders(*round)[0]))
+                .unwrap();
+            assert!(sequence.len() >= last_honest_leader_round as usize);
+
+            for (i, decided_leader) in sequence.iter().enumerate() {
+                // First sequenced leader should be in round 1.
+                let leader_round = i as u32 + 1;
+                let leader = authority.committer.get_leaders(leader_round)[0];
+                assert_eq!(decided_leader.round(), leader_round);
+                assert_eq!(decided_leader.authority(), leader);
+                if !byzantine.contains_key(&leader) {
+                    assert!(
+                        matches!(decided_leader, DecidedLeader::Commit(_)),
+                        "Expected honest leader {leader} of round {leader_round} to be committed"
+                    );
+                }
+            }
+
+            sequences.push(sequence);
+        }
+
+        // Despite equivocations, at most one block is committed for each leader slot and both
+        // authorities commit the same blocks.
+        assert_eq!(sequences[0], sequences[1]);
+    }
+}
+
+/// Feeds the blocks in random chunks through the BlockManager of the authority, deciding leaders
+/// after each chunk, and returns the decided leaders.
+fn decide_leaders(
+    authority: &mut AuthorityTestFixture,
+    mut blocks: Vec<VerifiedBlock>,
+    rng: &mut StdRng,
+) -> Vec<DecidedLeader> {
+    blocks.shuffle(rng);
+
+    let mut sequenced_leaders = vec![];
+    let mut last_decided = Slot::new_for_test(0, 0);
+    let mut i = 0;
+    while i < blocks.len() {
+        let chunk_size = rng.gen_range(1..=(blocks.len() - i));
+        let chunk = &blocks[i..i + chunk_size];
+
+        let _ = authority.block_manager.try_accept_blocks(chunk.to_vec());
+        let sequence = authority.committer.try_decide(last_decided);
+
+        if let Some(leader_status) = sequence.last() {
+            last_decided = Slot::new(leader_status.round(), leader_status.authority());
+            sequenced_leaders.extend(sequence);
+        }
+
+        i += chunk_size;
+    }
+
+    assert!(authority.block_manager.is_empty());
+    sequenced_leaders
+}
+
+fn random_byzantine_behavior(rng: &mut StdRng, committee_size: usize) -> ByzantineBehavior {
+    let peers = (0..committee_size as u32)
+        .filter(|_| rng.gen_bool(0.5))
+        .map(AuthorityIndex::new_for_test)
+        .collect::<Vec<_>>();
+    let equivocations = rng.gen_range(1..=3);
+
+    match rng.gen_range(0..5) {
+        0 => ByzantineBehavior::default().with_equivocations(equivocations),
+        1 => ByzantineBehavior::default().with_withheld_peers(peers),
+        2 => ByzantineBehavior::default()
+            .with_proposal_delay(Duration::from_millis(rng.gen_range(100..1000))),
+        3 => ByzantineBehavior::default().with_invalid_signatures(),
+        _ => ByzantineBehavior::default()
+            .with_equivocations(equivocations)
+            .with_withheld_peers(peers),
+    }
+}
+
+/// Builds a randomized dag where the blocks of byzantine authorities are altered by their
+/// behavior before being received by the honest authorities. Returns all the blocks an honest
+/// authority would accept.
+fn create_random_byzantine_dag(
+    rng: &mut StdRng,
+    context: Arc<Context>,
+    keys: &[(NetworkKeyPair, ProtocolKeyPair)],
+    byzantine: &BTreeMap<AuthorityIndex, ByzantineBehavior>,
+    num_rounds: u32,
+) -> Vec<VerifiedBlock> {
+    let committee = &context.committee;
+    let leader_schedule = LeaderSchedule::new(context.clone(), LeaderSwapTable::default());
+    let genesis = genesis_blocks(context.clone())
+        .iter()
+        .map(|block| (block.author(), block.clone()))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut all_blocks = vec![];
+    // Blocks of the previous round, as accepted locally by their authors.
+    let mut previous_round = genesis;
+
+    for round in 1..=num_rounds {
+        let previous_leader = leader_schedule.elect_leader(round - 1, 0);
+        let timestamp_ms = round as u64 * 1000;
+        let mut current_round = BTreeMap::new();
+
+        for (authority, _) in committee.authorities() {
+            let ancestors = if round == 1 {
+                previous_round
+                    .values()
+                    .map(|block| block.reference())
+                    .collect()
+            } else if byzantine.contains_key(&authority) {
+                // Byzantine authorities link to their own block and to every honest block.
+                previous_round
+                    .iter()
+                    .filter(|(author, _)| **author == authority || !byzantine.contains_key(author))
+                    .map(|(_, block)| block.reference())
+                    .collect()
+            } else {
+                honest_ancestors(
+                    rng,
+                    &context,
+                    authority,
+                    previous_leader,
+                    &previous_round,
+                    byzantine,
+                )
+            };
+
+            let block = TestBlock::new(round, authority.value() as u32)
+                .set_timestamp_ms(timestamp_ms + authority.value() as u64)
+                .set_ancestors(ancestors)
+                .build();
+
+            let Some(behavior) = byzantine.get(&authority) else {
+                let block = VerifiedBlock::new_for_test(block);
+                all_blocks.push(block.clone());
+                current_round.insert(authority, block);
+                continue;
+            };
+
+            let signer = &keys[authority.value()].1;
+            let signed_block = behavior.sign_block(SignedBlock::new(block, signer).unwrap());
+            let serialized = signed_block.serialize().unwrap();
+            let block = VerifiedBlock::new_verified(signed_block, serialized);
+            behavior.equivocate(&block, signer);
+
+            // Honest authorities reject blocks with invalid signatures, so neither the block nor
+            // any block equivocating with it is ever accepted.
+            if block.verify_signature(&context).is_ok() {
+                let mut blocks = committee
+                    .authorities()
+                    .map(|(peer, _)| behavior.block_for_peer(peer, block.clone()))
+                    .collect::<Vec<_>>();
+                blocks.push(block.clone());
+                blocks.sort_by_key(|block| block.reference());
+                blocks.dedup_by_key(|block| block.reference());
+                all_blocks.extend(blocks);
+            }
+            current_round.insert(authority, block);
+        }
+
+        previous_round = current_round;
+    }
+
+    all_blocks
+}
+
+/// Picks the ancestors of a block from an honest authority: its own block, the leader of the
+/// previous round and enough other honest blocks to reach a quorum, along with the byzantine
+/// blocks received in time.
+fn honest_ancestors(
+    rng: &mut StdRng,
+    context: &Context,
+    authority: AuthorityIndex,
+    previous_leader: AuthorityIndex,
+    previous_round: &BTreeMap<AuthorityIndex, VerifiedBlock>,
+    byzantine: &BTreeMap<AuthorityIndex, ByzantineBehavior>,
+) -> Vec<BlockRef> {
+    let committee = &context.committee;
+    let mut ancestors = vec![previous_round[&authority].reference()];
+    let mut stake = committee.stake(authority);
+
+    if previous_leader != authority && !byzantine.contains_key(&previous_leader) {
+        ancestors.push(previous_round[&previous_leader].reference());
+        stake += committee.stake(previous_leader);
+    }
+
+    let mut others = previous_round
+        .iter()
+        .filter(|(author, _)| {
+            **author != authority && **author != previous_leader && !byzantine.contains_key(author)
+        })
+        .collect::<Vec<_>>();
+    others.shuffle(rng);
+    for (author, block) in others {
+        if !committee.reached_quorum(stake) || rng.gen_bool(0.5) {
+            ancestors.push(block.reference());
+            stake += committee.stake(*author);
+        }
+    }
+
+    for (author, behavior) in byzantine {
+        let block = &previous_round[author];
+        if behavior.invalid_signatures
+            || behavior.proposal_delay.is_some()
+            || !behavior.should_broadcast_to(authority)
+        {
+            continue;
+        }
+        ancestors.push(
+            behavior
+                .block_for_peer(authority, block.clone())
+                .reference(),
+        );
+    }
+
+    ancestors
+}
+
 struct AuthorityTestFixture {
     context: Arc<Context>,
     dag_state: Arc<RwLock<DagState>>,
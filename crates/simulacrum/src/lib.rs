@@ -10,13 +10,14 @@
 //!
 //! [`Simulacrum`]: crate::Simulacrum
 
+use std::collections::BTreeMap;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 use fastcrypto::traits::Signer;
-use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use rand::rngs::OsRng;
 use sui_config::verifier_signing_config::VerifierSigningConfig;
 use sui_config::{genesis, transaction_deny_config::TransactionDenyConfig};
@@ -35,7 +36,7 @@ use sui_types::transaction::EndOfEpochTransactionKind;
 use sui_types::{
     base_types::SuiAddress,
     committee::Committee,
-    effects::TransactionEffects,
+    effects::{TransactionEffects, TransactionEffectsAPI},
     error::ExecutionError,
     gas_coin::MIST_PER_SUI,
     inner_temporary_store::InnerTemporaryStore,
@@ -579,6 +580,12 @@ impl<T: Send + Sync, V: store::SimulatorStore + Send + Sync> RestStateReader for
         Ok(0)
     }
 
+    fn get_lowest_available_checkpoint_object_history(
+        &self,
+    ) -> sui_types::storage::error::Result<CheckpointSequenceNumber> {
+        Ok(0)
+    }
+
     fn get_chain_identifier(
         &self,
     ) -> sui_types::storage::error::Result<sui_types::digests::ChainIdentifier> {
@@ -618,12 +625,142 @@ impl<T: Send + Sync, V: store::SimulatorStore + Send + Sync> RestStateReader for
         todo!()
     }
 
+    fn get_object_version_at_checkpoint(
+        &self,
+        object_id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> sui_types::storage::error::Result<Option<sui_types::base_types::SequenceNumber>> {
+        // There is no object history index here, so look for the last change to the object in the
+        // effects of the checkpoints up to `checkpoint`.
+        for sequence_number in (0..=checkpoint).rev() {
+            let Some(summary) =
+                ReadStore::get_checkpoint_by_sequence_number(self, sequence_number)?
+            else {
+                continue;
+            };
+            let contents =
+                ReadStore::get_checkpoint_contents_by_digest(self, &summary.content_digest)?
+                    .ok_or_else(|| {
+                        sui_types::storage::error::Error::missing(format!(
+                            "missing contents of checkpoint {sequence_number}"
+                        ))
+                    })?;
+
+            let mut last_change = None;
+            for digests in contents.iter() {
+                let effects = ReadStore::get_transaction_effects(self, &digests.transaction)?
+                    .ok_or_else(|| {
+                        sui_types::storage::error::Error::missing(format!(
+                            "missing effects of transaction {}",
+                            digests.transaction
+                        ))
+                    })?;
+                if let Some(change) = effects
+                    .object_changes()
+                    .into_iter()
+                    .find(|change| change.id == object_id)
+                {
+                    last_change = Some(change.output_version);
+                }
+            }
+            if let Some(version) = last_change {
+                return Ok(version);
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn dynamic_field_iter_at_checkpoint(
+        &self,
+        _parent: ObjectID,
+        _checkpoint: CheckpointSequenceNumber,
+        _cursor: Option<ObjectID>,
+    ) -> sui_types::storage::error::Result<
+        Box<
+            dyn Iterator<
+                    Item = (
+                        sui_types::storage::DynamicFieldKey,
+                        sui_types::storage::DynamicFieldIndexInfo,
+                    ),
+                > + '_,
+        >,
+    > {
+        Err(sui_types::storage::error::Error::custom(
+            "historical dynamic fields are not supported by the simulator",
+        ))
+    }
+
     fn get_coin_info(
         &self,
         _coin_type: &StructTag,
     ) -> sui_types::storage::error::Result<Option<sui_types::storage::CoinInfo>> {
         todo!()
     }
+
+    fn account_coins_iter(
+        &self,
+        owner: SuiAddress,
+        coin_type: &StructTag,
+        cursor: Option<(u64, ObjectID)>,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = sui_types::storage::AccountCoinInfo> + '_>,
+    > {
+        let coin_type = TypeTag::Struct(Box::new(coin_type.to_owned()));
+        let mut coins: Vec<_> = self
+            .store()
+            .owned_objects(owner)
+            .filter(|object| object.coin_type_maybe().as_ref() == Some(&coin_type))
+            .filter_map(|object| {
+                Some(sui_types::storage::AccountCoinInfo {
+                    object_id: object.id(),
+                    version: object.version(),
+                    digest: object.digest(),
+                    balance: object.as_coin_maybe()?.value(),
+                    previous_transaction: object.previous_transaction,
+                })
+            })
+            .collect();
+
+        // Order coins from greatest to least balance, the same as the REST index does
+        let sort_key = |balance: u64, object_id: ObjectID| (std::cmp::Reverse(balance), object_id);
+        coins.sort_by_key(|coin| sort_key(coin.balance, coin.object_id));
+
+        let iter = coins.into_iter().skip_while(move |coin| {
+            cursor.is_some_and(|(balance, object_id)| {
+                sort_key(coin.balance, coin.object_id) < sort_key(balance, object_id)
+            })
+        });
+
+        Ok(Box::new(iter))
+    }
+
+    fn get_account_balances(
+        &self,
+        owner: SuiAddress,
+    ) -> sui_types::storage::error::Result<Vec<sui_types::storage::AccountBalanceInfo>> {
+        let mut balances = BTreeMap::new();
+
+        for object in self.store().owned_objects(owner) {
+            let (Some(TypeTag::Struct(coin_type)), Some(coin)) =
+                (object.coin_type_maybe(), object.as_coin_maybe())
+            else {
+                continue;
+            };
+
+            let balance = balances.entry(*coin_type.clone()).or_insert_with(|| {
+                sui_types::storage::AccountBalanceInfo {
+                    coin_type: *coin_type,
+                    coin_object_count: 0,
+                    total_balance: 0,
+                }
+            });
+            balance.coin_object_count += 1;
+            balance.total_balance += coin.value() as u128;
+        }
+
+        Ok(balances.into_values().collect())
+    }
 }
 
 impl Simulacrum {
@@ -0,0 +1,177 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Writes analytics tables to local parquet files for checkpoints which are not read through the
+//! ingestion pipeline, e.g. checkpoints loaded from the database of a stopped node.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use sui_data_ingestion_core::Worker;
+use sui_rest_api::CheckpointData;
+
+use crate::handlers::checkpoint_handler::CheckpointHandler;
+use crate::handlers::df_handler::DynamicFieldHandler;
+use crate::handlers::event_handler::EventHandler;
+use crate::handlers::move_call_handler::MoveCallHandler;
+use crate::handlers::object_handler::ObjectHandler;
+use crate::handlers::package_handler::PackageHandler;
+use crate::handlers::transaction_handler::TransactionHandler;
+use crate::handlers::transaction_objects_handler::TransactionObjectsHandler;
+use crate::handlers::wrapped_object_handler::WrappedObjectHandler;
+use crate::handlers::AnalyticsHandler;
+use crate::package_store::PackageFallback;
+use crate::writers::parquet_writer::ParquetWriter;
+use crate::writers::AnalyticsWriter;
+use crate::{FileType, ParquetSchema};
+
+/// Writes the rows of `file_type` for `checkpoints` under `output_dir`, using the same layout as
+/// the analytics indexer: a new file is started at every epoch and after every
+/// `checkpoint_interval` checkpoints, so that rows are not buffered in memory for a whole epoch.
+/// Checkpoints must be consecutive. Packages needed to resolve object and event types are cached
+/// under `package_cache_path` and read from `fallback` when missing. Returns the number of rows
+/// written.
+pub async fn export_parquet(
+    file_type: FileType,
+    output_dir: &Path,
+    checkpoint_interval: u64,
+    package_cache_path: &Path,
+    fallback: Arc<dyn PackageFallback>,
+    checkpoints: impl Iterator<Item = Result<CheckpointData>>,
+) -> Result<u64> {
+    match file_type {
+        FileType::Checkpoint => {
+            export(
+                Box::new(CheckpointHandler::new()),
+                output_dir,
+                checkpoint_interval,
+                checkpoints,
+            )
+            .await
+        }
+        FileType::Object => {
+            let handler = ObjectHandler::with_package_fallback(package_cache_path, fallback);
+            export(
+                Box::new(handler),
+                output_dir,
+                checkpoint_interval,
+                checkpoints,
+            )
+            .await
+        }
+        FileType::Transaction => {
+            export(
+                Box::new(TransactionHandler::new()),
+                output_dir,
+                checkpoint_interval,
+                checkpoints,
+            )
+            .await
+        }
+        FileType::TransactionObjects => {
+            export(
+                Box::new(TransactionObjectsHandler::new()),
+                output_dir,
+                checkpoint_interval,
+                checkpoints,
+            )
+            .await
+        }
+        FileType::Event => {
+            let handler = EventHandler::with_package_fallback(package_cache_path, fallback);
+            export(
+                Box::new(handler),
+                output_dir,
+                checkpoint_interval,
+                checkpoints,
+            )
+            .await
+        }
+        FileType::MoveCall => {
+            export(
+                Box::new(MoveCallHandler::new()),
+                output_dir,
+                checkpoint_interval,
+                checkpoints,
+            )
+            .await
+        }
+        FileType::MovePackage => {
+            export(
+                Box::new(PackageHandler::new()),
+                output_dir,
+                checkpoint_interval,
+                checkpoints,
+            )
+            .await
+        }
+        FileType::DynamicField => {
+            let handler = DynamicFieldHandler::with_package_fallback(package_cache_path, fallback);
+            export(
+                Box::new(handler),
+                output_dir,
+                checkpoint_interval,
+                checkpoints,
+            )
+            .await
+        }
+        FileType::WrappedObject => {
+            let handler = WrappedObjectHandler::with_package_fallback(package_cache_path, fallback);
+            export(
+                Box::new(handler),
+                output_dir,
+                checkpoint_interval,
+                checkpoints,
+            )
+            .await
+        }
+    }
+}
+
+async fn export<S: Serialize + ParquetSchema + 'static>(
+    handler: Box<dyn AnalyticsHandler<S>>,
+    output_dir: &Path,
+    checkpoint_interval: u64,
+    checkpoints: impl Iterator<Item = Result<CheckpointData>>,
+) -> Result<u64> {
+    if checkpoint_interval == 0 {
+        bail!("The checkpoint interval must be positive");
+    }
+    let mut writer: Box<dyn AnalyticsWriter<S>> =
+        Box::new(ParquetWriter::new(output_dir, handler.file_type()?, 0)?);
+    let mut current_epoch = None;
+    let mut file_start_checkpoint = 0;
+    let mut next_checkpoint = 0;
+    let mut rows_written = 0;
+    for checkpoint_data in checkpoints {
+        let checkpoint_data = checkpoint_data?;
+        let epoch = checkpoint_data.checkpoint_summary.epoch;
+        let sequence_number = checkpoint_data.checkpoint_summary.sequence_number;
+        if current_epoch.is_some() && sequence_number != next_checkpoint {
+            bail!("Expected checkpoint {next_checkpoint}, got {sequence_number}");
+        }
+        if current_epoch != Some(epoch) {
+            if current_epoch.is_some() {
+                writer.flush(sequence_number)?;
+            }
+            writer.reset(epoch, sequence_number)?;
+            current_epoch = Some(epoch);
+            file_start_checkpoint = sequence_number;
+        } else if sequence_number - file_start_checkpoint >= checkpoint_interval {
+            writer.flush(sequence_number)?;
+            writer.reset(epoch, sequence_number)?;
+            file_start_checkpoint = sequence_number;
+        }
+        handler.process_checkpoint(&checkpoint_data).await?;
+        let rows = handler.read().await?;
+        writer.write(&rows)?;
+        rows_written += rows.len() as u64;
+        next_checkpoint = sequence_number + 1;
+    }
+    if current_epoch.is_some() {
+        writer.flush(next_checkpoint)?;
+    }
+    Ok(rows_written)
+}
@@ -5,6 +5,7 @@ use anyhow::Result;
 use fastcrypto::encoding::{Base64, Encoding};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
 use sui_data_ingestion_core::Worker;
 use sui_indexer::errors::IndexerError;
 use sui_types::object::bounded_visitor::BoundedVisitor;
@@ -16,14 +17,14 @@ use tracing::warn;
 use sui_indexer::types::owner_to_owner_info;
 use sui_json_rpc_types::SuiMoveValue;
 use sui_package_resolver::Resolver;
-use sui_rest_api::{CheckpointData, CheckpointTransaction};
+use sui_rest_api::{CheckpointData, CheckpointTransaction, Client};
 use sui_types::base_types::ObjectID;
 use sui_types::dynamic_field::visitor as DFV;
 use sui_types::dynamic_field::{DynamicFieldName, DynamicFieldType};
 use sui_types::object::Object;
 
 use crate::handlers::AnalyticsHandler;
-use crate::package_store::{LocalDBPackageStore, PackageCache};
+use crate::package_store::{LocalDBPackageStore, PackageCache, PackageFallback};
 use crate::tables::DynamicFieldEntry;
 use crate::FileType;
 
@@ -91,7 +92,12 @@ impl AnalyticsHandler<DynamicFieldEntry> for DynamicFieldHandler {
 
 impl DynamicFieldHandler {
     pub fn new(store_path: &Path, rest_uri: &str) -> Self {
-        let package_store = LocalDBPackageStore::new(&store_path.join("dynamic_field"), rest_uri);
+        Self::with_package_fallback(store_path, Arc::new(Client::new(rest_uri)))
+    }
+
+    pub fn with_package_fallback(store_path: &Path, fallback: Arc<dyn PackageFallback>) -> Self {
+        let package_store =
+            LocalDBPackageStore::with_fallback(&store_path.join("dynamic_field"), fallback);
         let state = State {
             dynamic_fields: vec![],
             package_store: package_store.clone(),
@@ -3,20 +3,20 @@
 
 use anyhow::Result;
 use fastcrypto::encoding::{Base64, Encoding};
-use move_core_types::annotated_value::MoveValue;
 use sui_types::SYSTEM_PACKAGE_ADDRESSES;
 
 use std::path::Path;
+use std::sync::Arc;
 use sui_data_ingestion_core::Worker;
 use tokio::sync::Mutex;
 
 use crate::handlers::AnalyticsHandler;
-use crate::package_store::{LocalDBPackageStore, PackageCache};
+use crate::package_store::{LocalDBPackageStore, PackageCache, PackageFallback};
 use crate::tables::EventEntry;
 use crate::FileType;
 use sui_json_rpc_types::type_and_fields_from_move_event_data;
 use sui_package_resolver::Resolver;
-use sui_rest_api::CheckpointData;
+use sui_rest_api::{CheckpointData, Client};
 use sui_types::digests::TransactionDigest;
 use sui_types::effects::TransactionEvents;
 use sui_types::event::Event;
@@ -88,7 +88,11 @@ impl AnalyticsHandler<EventEntry> for EventHandler {
 
 impl EventHandler {
     pub fn new(store_path: &Path, rest_uri: &str) -> Self {
-        let package_store = LocalDBPackageStore::new(&store_path.join("event"), rest_uri);
+        Self::with_package_fallback(store_path, Arc::new(Client::new(rest_uri)))
+    }
+
+    pub fn with_package_fallback(store_path: &Path, fallback: Arc<dyn PackageFallback>) -> Self {
+        let package_store = LocalDBPackageStore::with_fallback(&store_path.join("event"), fallback);
         let state = State {
             events: vec![],
             package_store: package_store.clone(),
@@ -115,13 +119,13 @@ impl EventHandler {
                 type_,
                 contents,
             } = event;
-            let layout = state
+            let move_value = state
                 .resolver
-                .type_layout(move_core_types::language_storage::TypeTag::Struct(
-                    Box::new(type_.clone()),
-                ))
+                .move_value(
+                    move_core_types::language_storage::TypeTag::Struct(Box::new(type_.clone())),
+                    contents,
+                )
                 .await?;
-            let move_value = MoveValue::simple_deserialize(contents, &layout)?;
             let (_, event_json) = type_and_fields_from_move_event_data(move_value)?;
             let entry = EventEntry {
                 transaction_digest: digest.base58_encode(),
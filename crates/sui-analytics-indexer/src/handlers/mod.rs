@@ -4,7 +4,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use anyhow::{anyhow, Result};
-use move_core_types::annotated_value::{MoveStruct, MoveTypeLayout, MoveValue};
+use move_core_types::annotated_value::{MoveStruct, MoveValue};
 use move_core_types::language_storage::{StructTag, TypeTag};
 use sui_data_ingestion_core::Worker;
 
@@ -12,7 +12,6 @@ use sui_package_resolver::{PackageStore, Resolver};
 use sui_types::base_types::ObjectID;
 use sui_types::effects::TransactionEffects;
 use sui_types::effects::TransactionEffectsAPI;
-use sui_types::object::bounded_visitor::BoundedVisitor;
 use sui_types::object::{Object, Owner};
 use sui_types::transaction::TransactionData;
 use sui_types::transaction::TransactionDataAPI;
@@ -171,16 +170,13 @@ async fn get_move_struct<T: PackageStore>(
     contents: &[u8],
     resolver: &Resolver<T>,
 ) -> Result<MoveStruct> {
-    let move_struct = match resolver
-        .type_layout(TypeTag::Struct(Box::new(struct_tag.clone())))
+    match resolver
+        .move_value(TypeTag::Struct(Box::new(struct_tag.clone())), contents)
         .await?
     {
-        MoveTypeLayout::Struct(move_struct_layout) => {
-            BoundedVisitor::deserialize_struct(contents, &move_struct_layout)
-        }
+        MoveValue::Struct(move_struct) => Ok(move_struct),
         _ => Err(anyhow!("Object is not a move struct")),
-    }?;
-    Ok(move_struct)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -4,13 +4,14 @@
 use anyhow::Result;
 use fastcrypto::encoding::{Base64, Encoding};
 use std::path::Path;
+use std::sync::Arc;
 use sui_data_ingestion_core::Worker;
 use sui_types::SYSTEM_PACKAGE_ADDRESSES;
 use tokio::sync::Mutex;
 
 use sui_json_rpc_types::SuiMoveStruct;
 use sui_package_resolver::Resolver;
-use sui_rest_api::{CheckpointData, CheckpointTransaction};
+use sui_rest_api::{CheckpointData, CheckpointTransaction, Client};
 use sui_types::effects::TransactionEffects;
 use sui_types::object::Object;
 
@@ -19,7 +20,7 @@ use crate::handlers::{
     ObjectStatusTracker,
 };
 
-use crate::package_store::{LocalDBPackageStore, PackageCache};
+use crate::package_store::{LocalDBPackageStore, PackageCache, PackageFallback};
 use crate::tables::{ObjectEntry, ObjectStatus};
 use crate::FileType;
 
@@ -88,7 +89,12 @@ impl AnalyticsHandler<ObjectEntry> for ObjectHandler {
 
 impl ObjectHandler {
     pub fn new(store_path: &Path, rest_uri: &str) -> Self {
-        let package_store = LocalDBPackageStore::new(&store_path.join("object"), rest_uri);
+        Self::with_package_fallback(store_path, Arc::new(Client::new(rest_uri)))
+    }
+
+    pub fn with_package_fallback(store_path: &Path, fallback: Arc<dyn PackageFallback>) -> Self {
+        let package_store =
+            LocalDBPackageStore::with_fallback(&store_path.join("object"), fallback);
         let state = State {
             objects: vec![],
             package_store: package_store.clone(),
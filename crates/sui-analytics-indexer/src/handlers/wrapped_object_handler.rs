@@ -4,17 +4,18 @@
 use anyhow::Result;
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::sync::Arc;
 use sui_data_ingestion_core::Worker;
 use sui_types::SYSTEM_PACKAGE_ADDRESSES;
 use tokio::sync::Mutex;
 
 use sui_package_resolver::Resolver;
-use sui_rest_api::{CheckpointData, CheckpointTransaction};
+use sui_rest_api::{CheckpointData, CheckpointTransaction, Client};
 use sui_types::object::Object;
 
 use crate::handlers::{get_move_struct, parse_struct, AnalyticsHandler};
 
-use crate::package_store::{LocalDBPackageStore, PackageCache};
+use crate::package_store::{LocalDBPackageStore, PackageCache, PackageFallback};
 use crate::tables::WrappedObjectEntry;
 use crate::FileType;
 
@@ -82,7 +83,12 @@ impl AnalyticsHandler<WrappedObjectEntry> for WrappedObjectHandler {
 
 impl WrappedObjectHandler {
     pub fn new(store_path: &Path, rest_uri: &str) -> Self {
-        let package_store = LocalDBPackageStore::new(&store_path.join("wrapped_object"), rest_uri);
+        Self::with_package_fallback(store_path, Arc::new(Client::new(rest_uri)))
+    }
+
+    pub fn with_package_fallback(store_path: &Path, fallback: Arc<dyn PackageFallback>) -> Self {
+        let package_store =
+            LocalDBPackageStore::with_fallback(&store_path.join("wrapped_object"), fallback);
         let state = Mutex::new(State {
             wrapped_objects: vec![],
             package_store: package_store.clone(),
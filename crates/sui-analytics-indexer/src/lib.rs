@@ -51,11 +51,14 @@ use crate::writers::AnalyticsWriter;
 pub mod analytics_metrics;
 pub mod analytics_processor;
 pub mod errors;
+pub mod export;
 mod handlers;
 mod package_store;
 pub mod tables;
 mod writers;
 
+pub use package_store::PackageFallback;
+
 const EPOCH_DIR_PREFIX: &str = "epoch_";
 const CHECKPOINT_DIR_PREFIX: &str = "checkpoints";
 const OBJECT_DIR_PREFIX: &str = "objects";
@@ -62,21 +62,38 @@ impl PackageStoreTables {
     }
 }
 
+/// Source of package objects which are missing from the local store.
+#[async_trait]
+pub trait PackageFallback: Send + Sync + 'static {
+    async fn get_package(&self, id: ObjectID) -> anyhow::Result<Object>;
+}
+
+#[async_trait]
+impl PackageFallback for Client {
+    async fn get_package(&self, id: ObjectID) -> anyhow::Result<Object> {
+        Ok(self.get_object(id).await?)
+    }
+}
+
 /// Store which keeps package objects in a local rocksdb store. It is expected that this store is
 /// kept updated with latest version of package objects while iterating over checkpoints. If the
-/// local db is missing (or gets deleted), packages are fetched from a full node and local store is
-/// updated
+/// local db is missing (or gets deleted), packages are fetched from the fallback (a full node by
+/// default) and local store is updated
 #[derive(Clone)]
 pub struct LocalDBPackageStore {
     package_store_tables: Arc<PackageStoreTables>,
-    fallback_client: Client,
+    fallback: Arc<dyn PackageFallback>,
 }
 
 impl LocalDBPackageStore {
     pub fn new(path: &Path, rest_url: &str) -> Self {
+        Self::with_fallback(path, Arc::new(Client::new(rest_url)))
+    }
+
+    pub fn with_fallback(path: &Path, fallback: Arc<dyn PackageFallback>) -> Self {
         Self {
             package_store_tables: PackageStoreTables::new(path),
-            fallback_client: Client::new(rest_url),
+            fallback,
         }
     }
 
@@ -98,8 +115,8 @@ impl LocalDBPackageStore {
             object
         } else {
             let object = self
-                .fallback_client
-                .get_object(ObjectID::from(id))
+                .fallback
+                .get_package(ObjectID::from(id))
                 .await
                 .map_err(|_| PackageResolverError::PackageNotFound(id))?;
             self.update(&object)?;
@@ -475,12 +475,14 @@ mod test {
         let separate_randomness_budget;
         {
             let mut rng = thread_rng();
-            mode = if rng.gen_bool(0.33) {
+            mode = if rng.gen_bool(0.25) {
                 PerObjectCongestionControlMode::TotalGasBudget
-            } else if rng.gen_bool(0.5) {
+            } else if rng.gen_bool(0.33) {
                 PerObjectCongestionControlMode::TotalTxCount
-            } else {
+            } else if rng.gen_bool(0.5) {
                 PerObjectCongestionControlMode::TotalGasBudgetWithCap
+            } else {
+                PerObjectCongestionControlMode::ExecutionTimeEstimate
             };
             checkpoint_budget_factor = rng.gen_range(1..20);
             txn_count_limit = rng.gen_range(1..=10);
@@ -516,6 +518,14 @@ mod test {
             config.set_per_object_congestion_control_mode_for_testing(mode);
             match mode {
                 PerObjectCongestionControlMode::None => panic!("Congestion control mode cannot be None in test_simulated_load_shared_object_congestion_control"),
+                PerObjectCongestionControlMode::ExecutionTimeEstimate => {
+                    // Budget `txn_count_limit` transactions of up to 10 commands that were never
+                    // observed, per object per commit.
+                    config.set_execution_time_estimate_default_command_cost_us_for_testing(1_000);
+                    config.set_max_execution_time_observation_estimates_for_testing(1_000);
+                    config.set_max_accumulated_txn_cost_per_object_in_narwhal_commit_for_testing(txn_count_limit * 10_000);
+                    config.set_max_accumulated_txn_cost_per_object_in_mysticeti_commit_for_testing(txn_count_limit * 10_000);
+                },
                 PerObjectCongestionControlMode::TotalGasBudget => {
                     config.set_max_accumulated_txn_cost_per_object_in_narwhal_commit_for_testing(total_gas_limit);
                     config.set_max_accumulated_txn_cost_per_object_in_mysticeti_commit_for_testing(total_gas_limit);
@@ -252,7 +252,7 @@ async fn request_sign_bridge_action_into_certification(
         state,
         |name, client| {
             Box::pin(async move {
-                let start = std::time::Instant::now();
+                let start = tokio::time::Instant::now();
                 let timeout = Duration::from_millis(TOTAL_TIMEOUT_MS);
                 let retry_interval = Duration::from_millis(RETRY_INTERVAL_MS);
                 while start.elapsed() < timeout {
@@ -5,6 +5,8 @@
 
 use crate::crypto::{verify_signed_bridge_action, BridgeAuthorityPublicKeyBytes};
 use crate::error::{BridgeError, BridgeResult};
+#[cfg(test)]
+use crate::server::handler::BridgeRequestHandlerTrait;
 use crate::server::APPLICATION_JSON;
 use crate::types::{BridgeAction, BridgeCommittee, VerifiedSignedBridgeAction};
 use fastcrypto::encoding::{Encoding, Hex};
@@ -24,6 +26,20 @@ pub struct BridgeClient {
     authority: BridgeAuthorityPublicKeyBytes,
     committee: Arc<BridgeCommittee>,
     base_url: Option<Url>,
+    #[cfg(test)]
+    in_process_handler: Option<InProcessHandler>,
+}
+
+/// Serves signing requests by calling the authority's handler directly instead of over HTTP.
+#[cfg(test)]
+#[derive(Clone)]
+struct InProcessHandler(Arc<dyn BridgeRequestHandlerTrait + Send + Sync>);
+
+#[cfg(test)]
+impl std::fmt::Debug for InProcessHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("InProcessHandler")
+    }
 }
 
 impl BridgeClient {
@@ -41,9 +57,24 @@ impl BridgeClient {
             authority: authority_name.clone(),
             base_url: Url::from_str(&member.base_url).ok(),
             committee,
+            #[cfg(test)]
+            in_process_handler: None,
         })
     }
 
+    /// Creates a client whose signing requests are served by `handler` in process, so tests
+    /// don't need to run authority servers.
+    #[cfg(test)]
+    pub fn new_in_process(
+        authority_name: BridgeAuthorityPublicKeyBytes,
+        committee: Arc<BridgeCommittee>,
+        handler: Arc<dyn BridgeRequestHandlerTrait + Send + Sync>,
+    ) -> BridgeResult<Self> {
+        let mut client = Self::new(authority_name, committee)?;
+        client.in_process_handler = Some(InProcessHandler(handler));
+        Ok(client)
+    }
+
     #[cfg(test)]
     pub fn update_committee(&mut self, committee: Arc<BridgeCommittee>) {
         self.committee = committee;
@@ -190,6 +221,12 @@ impl BridgeClient {
         &self,
         action: BridgeAction,
     ) -> BridgeResult<VerifiedSignedBridgeAction> {
+        #[cfg(test)]
+        if let Some(InProcessHandler(handler)) = &self.in_process_handler {
+            return self
+                .request_sign_bridge_action_in_process(handler.as_ref(), action)
+                .await;
+        }
         if self.base_url.is_none() {
             return Err(BridgeError::InvalidAuthorityUrl(self.authority.clone()));
         }
@@ -226,6 +263,44 @@ impl BridgeClient {
             &self.committee,
         )
     }
+
+    // Mirrors the routing of the server and the error mapping of `request_sign_bridge_action`.
+    #[cfg(test)]
+    async fn request_sign_bridge_action_in_process(
+        &self,
+        handler: &(dyn BridgeRequestHandlerTrait + Send + Sync),
+        action: BridgeAction,
+    ) -> BridgeResult<VerifiedSignedBridgeAction> {
+        let result = match &action {
+            BridgeAction::EthToSuiBridgeAction(a) => {
+                handler
+                    .handle_eth_tx_hash(Hex::encode(a.eth_tx_hash.0), a.eth_event_index)
+                    .await
+            }
+            BridgeAction::SuiToEthBridgeAction(a) => {
+                handler
+                    .handle_sui_tx_digest(a.sui_tx_digest.to_string(), a.sui_tx_event_index)
+                    .await
+            }
+            _ => handler.handle_governance_action(action.clone()).await,
+        };
+        let signed_bridge_action = match result {
+            Ok(axum::Json(signed_bridge_action)) => signed_bridge_action,
+            Err(BridgeError::TxNotFinalized) => return Err(BridgeError::TxNotFinalized),
+            Err(e) => {
+                return Err(BridgeError::RestAPIError(format!(
+                    "request_sign_bridge_action failed: {:?}",
+                    e
+                )))
+            }
+        };
+        verify_signed_bridge_action(
+            &action,
+            signed_bridge_action,
+            &self.authority,
+            &self.committee,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -5,6 +5,7 @@ use crate::abi::EthBridgeConfig;
 use crate::crypto::BridgeAuthorityKeyPair;
 use crate::error::BridgeError;
 use crate::eth_client::EthClient;
+use crate::eth_syncer::EthUnfinalizedHead;
 use crate::metered_eth_provider::new_metered_eth_provider;
 use crate::metered_eth_provider::MeteredEthHttpProvier;
 use crate::metrics::BridgeMetrics;
@@ -62,6 +63,11 @@ pub struct EthConfig {
     /// reprocess the events from this block number every time it starts.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub eth_contracts_start_block_override: Option<u64>,
+    /// When set, EthSyncer also syncs events up to this unfinalized head ("safe" or "latest")
+    /// so they can be pre-validated before finalization. Events in blocks that get reorged out
+    /// are retracted. Actions are still only executed once finalized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_unfinalized_head: Option<EthUnfinalizedHead>,
 }
 
 #[serde_as]
@@ -142,6 +148,24 @@ pub struct MetricsConfig {
 pub struct WatchdogConfig {
     /// Total supplies to watch on Sui. Mapping from coin name to coin type tag
     pub total_supplies: BTreeMap<String, String>,
+    /// When set, reconcile locked amounts on Eth against minted supplies on Sui and watch the
+    /// remaining rate limits of each route.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reconciliation: Option<ReconciliationConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReconciliationConfig {
+    /// Alert when the amount of a token locked on Eth and its supply minted on Sui differ by
+    /// more than this, in basis points of the locked amount.
+    pub supply_drift_threshold_bps: u64,
+    /// Alert when the remaining rate limit of a route falls below this, in basis points of the
+    /// route's limit.
+    pub min_limit_headroom_bps: u64,
+    /// If set, alerts are also posted as JSON to this URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alert_webhook_url: Option<String>,
 }
 
 impl Config for BridgeNodeConfig {}
@@ -242,6 +266,7 @@ impl BridgeNodeConfig {
                 .eth_contracts_start_block_fallback
                 .unwrap(),
             eth_contracts_start_block_override: self.eth.eth_contracts_start_block_override,
+            eth_unfinalized_head: self.eth.eth_unfinalized_head,
             sui_bridge_module_last_processed_event_id_override: self
                 .sui
                 .sui_bridge_module_last_processed_event_id_override,
@@ -417,6 +442,7 @@ pub struct BridgeClientConfig {
     // See `BridgeNodeConfig` for the explanation of following two fields.
     pub eth_contracts_start_block_fallback: u64,
     pub eth_contracts_start_block_override: Option<u64>,
+    pub eth_unfinalized_head: Option<EthUnfinalizedHead>,
     pub sui_bridge_module_last_processed_event_id_override: Option<EventID>,
 }
 
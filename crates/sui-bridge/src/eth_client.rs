@@ -11,7 +11,7 @@ use crate::metrics::BridgeMetrics;
 use crate::types::{BridgeAction, EthLog, RawEthLog};
 use ethers::providers::{JsonRpcClient, Middleware, Provider};
 use ethers::types::TxHash;
-use ethers::types::{Block, Filter};
+use ethers::types::{Block, Filter, H256, U64};
 use tap::TapFallible;
 
 #[cfg(test)]
@@ -118,19 +118,40 @@ where
     }
 
     pub async fn get_last_finalized_block_id(&self) -> BridgeResult<u64> {
+        self.get_last_block_id("finalized").await
+    }
+
+    /// Returns the number of the last block with the given tag, e.g. "safe" or "latest".
+    pub async fn get_last_block_id(&self, tag: &str) -> BridgeResult<u64> {
         let block: Result<Option<Block<ethers::types::TxHash>>, ethers::prelude::ProviderError> =
             self.provider
-                .request("eth_getBlockByNumber", ("finalized", false))
+                .request("eth_getBlockByNumber", (tag, false))
                 .await;
-        let block = block?.ok_or(BridgeError::TransientProviderError(
-            "Provider fails to return last finalized block".into(),
-        ))?;
+        let block = block?.ok_or(BridgeError::TransientProviderError(format!(
+            "Provider fails to return last {tag} block"
+        )))?;
         let number = block.number.ok_or(BridgeError::TransientProviderError(
             "Provider returns block without number".into(),
         ))?;
         Ok(number.as_u64())
     }
 
+    /// Returns the hash of the canonical block at the given height, or None if there is no
+    /// such block yet.
+    pub async fn get_block_hash(&self, block_number: u64) -> BridgeResult<Option<H256>> {
+        let block: Result<Option<Block<ethers::types::TxHash>>, ethers::prelude::ProviderError> =
+            self.provider
+                .request("eth_getBlockByNumber", (U64::from(block_number), false))
+                .await;
+        let Some(block) = block? else {
+            return Ok(None);
+        };
+        let hash = block.hash.ok_or(BridgeError::TransientProviderError(
+            "Provider returns block without hash".into(),
+        ))?;
+        Ok(Some(hash))
+    }
+
     // Note: query may fail if range is too big. Callsite is responsible
     // for chunking the query.
     pub async fn get_events_in_range(
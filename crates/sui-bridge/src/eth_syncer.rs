@@ -5,24 +5,31 @@
 //! concerned contracts. Each contract is associated with a start block number, and the syncer will
 //! only query from that block number onwards. The syncer also keeps track of the last finalized
 //! block on Ethereum and will only query for events up to that block number.
+//!
+//! Optionally, the syncer also follows an unfinalized head ("safe" or "latest") so events can be
+//! observed before they are finalized. Since unfinalized blocks may be reorged out, it keeps a
+//! journal of the block hashes it synced and retracts the events of blocks that are no longer
+//! canonical.
 
 use crate::error::BridgeResult;
 use crate::eth_client::EthClient;
 use crate::metrics::BridgeMetrics;
 use crate::retry_with_max_elapsed_time;
 use crate::types::EthLog;
-use ethers::types::Address as EthAddress;
+use ethers::types::{Address as EthAddress, H256};
 use mysten_metrics::spawn_logged_monitored_task;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
 use tokio::time::{self, Duration, Instant};
-use tracing::error;
+use tracing::{error, warn};
 
 const ETH_LOG_QUERY_MAX_BLOCK_RANGE: u64 = 1000;
 const ETH_EVENTS_CHANNEL_SIZE: usize = 1000;
 const FINALIZED_BLOCK_QUERY_INTERVAL: Duration = Duration::from_secs(5);
+const UNFINALIZED_BLOCK_QUERY_INTERVAL: Duration = Duration::from_secs(2);
 
 pub struct EthSyncer<P> {
     eth_client: Arc<EthClient<P>>,
@@ -32,6 +39,76 @@ pub struct EthSyncer<P> {
 /// Map from contract address to their start block.
 pub type EthTargetAddresses = HashMap<EthAddress, u64>;
 
+/// The unfinalized head that events are synced up to ahead of finalization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EthUnfinalizedHead {
+    Safe,
+    Latest,
+}
+
+impl EthUnfinalizedHead {
+    /// The block tag of this head in `eth_getBlockByNumber`.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            EthUnfinalizedHead::Safe => "safe",
+            EthUnfinalizedHead::Latest => "latest",
+        }
+    }
+}
+
+/// Events observed in unfinalized blocks. Events in finalized blocks are always delivered again
+/// through the finalized events channel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnfinalizedEthLogs {
+    /// Logs of the given contract in unfinalized blocks up to the given block.
+    Observed(EthAddress, u64, Vec<EthLog>),
+    /// Previously observed logs of the given contract whose blocks were reorged out. The given
+    /// block is the last one that is still canonical.
+    Retracted(EthAddress, u64, Vec<EthLog>),
+}
+
+/// Block hashes and logs of the unfinalized blocks synced for one contract, used to detect
+/// reorgs and to retract the logs of reorged blocks.
+#[derive(Debug, Default)]
+struct UnfinalizedLogJournal {
+    /// Hashes of the blocks with logs and of the last block of each query.
+    block_hashes: BTreeMap<u64, H256>,
+    logs: BTreeMap<u64, Vec<EthLog>>,
+}
+
+impl UnfinalizedLogJournal {
+    fn record(&mut self, end_block: u64, end_block_hash: H256, logs: Vec<EthLog>) {
+        for log in logs {
+            if let Some(hash) = log.log.block_hash {
+                self.block_hashes.insert(log.block_number, hash);
+            }
+            self.logs.entry(log.block_number).or_default().push(log);
+        }
+        self.block_hashes.insert(end_block, end_block_hash);
+    }
+
+    /// Drops the blocks up to `finalized_block`, which can no longer be reorged out.
+    fn prune(&mut self, finalized_block: u64) {
+        self.block_hashes = self.block_hashes.split_off(&(finalized_block + 1));
+        self.logs = self.logs.split_off(&(finalized_block + 1));
+    }
+
+    /// Drops the blocks after `block` and returns their logs.
+    fn rollback(&mut self, block: u64) -> Vec<EthLog> {
+        self.block_hashes.retain(|b, _| *b <= block);
+        self.logs
+            .split_off(&(block + 1))
+            .into_values()
+            .flatten()
+            .collect()
+    }
+
+    fn last_block(&self) -> Option<u64> {
+        self.block_hashes.keys().next_back().copied()
+    }
+}
+
 #[allow(clippy::new_without_default)]
 impl<P> EthSyncer<P>
 where
@@ -51,6 +128,36 @@ where
         Vec<JoinHandle<()>>,
         mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
         watch::Receiver<u64>,
+    )> {
+        let (task_handles, eth_events_rx, last_finalized_block_rx, _) =
+            self.run_inner(metrics, None).await?;
+        Ok((task_handles, eth_events_rx, last_finalized_block_rx))
+    }
+
+    /// Like `run`, but additionally syncs events in unfinalized blocks up to `head`. These are
+    /// delivered through a separate channel, along with retractions when blocks are reorged out.
+    pub async fn run_with_unfinalized_tracking(
+        self,
+        metrics: Arc<BridgeMetrics>,
+        head: EthUnfinalizedHead,
+    ) -> BridgeResult<(
+        Vec<JoinHandle<()>>,
+        mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
+        watch::Receiver<u64>,
+        mysten_metrics::metered_channel::Receiver<UnfinalizedEthLogs>,
+    )> {
+        self.run_inner(metrics, Some(head)).await
+    }
+
+    async fn run_inner(
+        self,
+        metrics: Arc<BridgeMetrics>,
+        unfinalized_head: Option<EthUnfinalizedHead>,
+    ) -> BridgeResult<(
+        Vec<JoinHandle<()>>,
+        mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
+        watch::Receiver<u64>,
+        mysten_metrics::metered_channel::Receiver<UnfinalizedEthLogs>,
     )> {
         let (eth_evnets_tx, eth_events_rx) = mysten_metrics::metered_channel::channel(
             ETH_EVENTS_CHANNEL_SIZE,
@@ -59,6 +166,14 @@ where
                 .channel_inflight
                 .with_label_values(&["eth_events_queue"]),
         );
+        let (unfinalized_eth_events_tx, unfinalized_eth_events_rx) =
+            mysten_metrics::metered_channel::channel(
+                ETH_EVENTS_CHANNEL_SIZE,
+                &mysten_metrics::get_metrics()
+                    .unwrap()
+                    .channel_inflight
+                    .with_label_values(&["unfinalized_eth_events_queue"]),
+            );
         let last_finalized_block = self.eth_client.get_last_finalized_block_id().await?;
         let (last_finalized_block_tx, last_finalized_block_rx) =
             watch::channel(last_finalized_block);
@@ -73,6 +188,23 @@ where
             )
         ));
         for (contract_address, start_block) in self.contract_addresses {
+            if let Some(head) = unfinalized_head {
+                let unfinalized_eth_events_tx_clone = unfinalized_eth_events_tx.clone();
+                let last_finalized_block_rx_clone = last_finalized_block_rx.clone();
+                let eth_client_clone = self.eth_client.clone();
+                let metrics_clone = metrics.clone();
+                task_handles.push(spawn_logged_monitored_task!(
+                    Self::run_unfinalized_event_listening_task(
+                        contract_address,
+                        start_block,
+                        head,
+                        last_finalized_block_rx_clone,
+                        unfinalized_eth_events_tx_clone,
+                        eth_client_clone,
+                        metrics_clone,
+                    )
+                ));
+            }
             let eth_evnets_tx_clone = eth_evnets_tx.clone();
             let last_finalized_block_rx_clone = last_finalized_block_rx.clone();
             let eth_client_clone = self.eth_client.clone();
@@ -88,7 +220,12 @@ where
                 )
             ));
         }
-        Ok((task_handles, eth_events_rx, last_finalized_block_rx))
+        Ok((
+            task_handles,
+            eth_events_rx,
+            last_finalized_block_rx,
+            unfinalized_eth_events_rx,
+        ))
     }
 
     async fn run_finalized_block_refresh_task(
@@ -203,6 +340,154 @@ where
             start_block = end_block + 1;
         }
     }
+
+    async fn run_unfinalized_event_listening_task(
+        contract_address: EthAddress,
+        start_block: u64,
+        head: EthUnfinalizedHead,
+        last_finalized_block_receiver: watch::Receiver<u64>,
+        events_sender: mysten_metrics::metered_channel::Sender<UnfinalizedEthLogs>,
+        eth_client: Arc<EthClient<P>>,
+        metrics: Arc<BridgeMetrics>,
+    ) {
+        tracing::info!(contract_address=?contract_address, "Starting unfinalized eth events listening task up to the {} head", head.tag());
+        let contract_address_str = contract_address.to_string();
+        let mut journal = UnfinalizedLogJournal::default();
+        let mut next_block = start_block;
+        let mut interval = time::interval(UNFINALIZED_BLOCK_QUERY_INTERVAL);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            // Blocks up to the finalized block are synced by `run_event_listening_task`.
+            let last_finalized_block = *last_finalized_block_receiver.borrow();
+            journal.prune(last_finalized_block);
+            let first_unfinalized_block = start_block.max(last_finalized_block + 1);
+            next_block = next_block.max(first_unfinalized_block);
+
+            if let Some(last_synced_block) = journal.last_block() {
+                let canonical_block =
+                    match Self::find_last_canonical_block(&eth_client, &journal).await {
+                        Ok(block) => block,
+                        Err(e) => {
+                            error!(?contract_address, "Failed to check for eth reorgs: {:?}", e);
+                            continue;
+                        }
+                    };
+                if canonical_block != Some(last_synced_block) {
+                    // If no journaled block is canonical anymore, the fork is at or below the
+                    // finalized block.
+                    let fork_block = canonical_block.unwrap_or(first_unfinalized_block - 1);
+                    let retracted = journal.rollback(fork_block);
+                    warn!(
+                        ?contract_address,
+                        fork_block,
+                        last_synced_block,
+                        "Observed eth reorg, retracting {} events",
+                        retracted.len()
+                    );
+                    metrics
+                        .eth_reorgs
+                        .with_label_values(&[&contract_address_str])
+                        .inc();
+                    metrics
+                        .eth_reorg_depth
+                        .with_label_values(&[&contract_address_str])
+                        .observe((last_synced_block - fork_block) as f64);
+                    next_block = fork_block + 1;
+                    if !retracted.is_empty() {
+                        metrics
+                            .eth_retracted_events
+                            .with_label_values(&[&contract_address_str])
+                            .inc_by(retracted.len() as u64);
+                        events_sender
+                            .send(UnfinalizedEthLogs::Retracted(
+                                contract_address,
+                                fork_block,
+                                retracted,
+                            ))
+                            .await
+                            .expect("All unfinalized Eth event channel receivers are closed");
+                    }
+                }
+            }
+
+            let head_block = match eth_client.get_last_block_id(head.tag()).await {
+                Ok(block) => block,
+                Err(e) => {
+                    error!("Failed to get last {} block: {:?}", head.tag(), e);
+                    continue;
+                }
+            };
+            if head_block < next_block {
+                continue;
+            }
+            let end_block =
+                std::cmp::min(next_block + ETH_LOG_QUERY_MAX_BLOCK_RANGE - 1, head_block);
+            // The hash is fetched before the logs, so that a reorg in between leaves a stale hash
+            // in the journal and gets detected on the next round.
+            let end_block_hash = match eth_client.get_block_hash(end_block).await {
+                Ok(Some(hash)) => hash,
+                Ok(None) => {
+                    warn!(
+                        ?contract_address,
+                        "Eth block {end_block} is no longer available"
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    error!("Failed to get hash of eth block {end_block}: {:?}", e);
+                    continue;
+                }
+            };
+            let events = match eth_client
+                .get_events_in_range(contract_address, next_block, end_block)
+                .await
+            {
+                Ok(events) => events,
+                Err(e) => {
+                    error!("Failed to get unfinalized events from eth client: {:?}", e);
+                    continue;
+                }
+            };
+            let len = events.len();
+            journal.record(end_block, end_block_hash, events.clone());
+            if len != 0 {
+                tracing::info!(
+                    ?contract_address,
+                    start_block = next_block,
+                    end_block,
+                    "Observed {len} new unfinalized Eth events",
+                );
+                events_sender
+                    .send(UnfinalizedEthLogs::Observed(
+                        contract_address,
+                        end_block,
+                        events,
+                    ))
+                    .await
+                    .expect("All unfinalized Eth event channel receivers are closed");
+            }
+            metrics
+                .last_synced_unfinalized_eth_blocks
+                .with_label_values(&[&contract_address_str])
+                .set(end_block as i64);
+            next_block = end_block + 1;
+        }
+    }
+
+    /// Returns the latest journaled block whose hash still matches the canonical chain, or None
+    /// if there is no such block.
+    async fn find_last_canonical_block(
+        eth_client: &EthClient<P>,
+        journal: &UnfinalizedLogJournal,
+    ) -> BridgeResult<Option<u64>> {
+        for (block, hash) in journal.block_hashes.iter().rev() {
+            if eth_client.get_block_hash(*block).await? == Some(*hash) {
+                return Ok(Some(*block));
+            }
+        }
+        Ok(None)
+    }
 }
 
 #[cfg(test)]
@@ -215,7 +500,9 @@ mod tests {
 
     use crate::{
         eth_mock_provider::EthMockProvider,
-        test_utils::{mock_get_logs, mock_last_finalized_block},
+        test_utils::{
+            mock_block_hash, mock_get_logs, mock_last_finalized_block, mock_last_unfinalized_block,
+        },
     };
 
     use super::*;
@@ -501,4 +788,159 @@ mod tests {
         assert_eq!(received_logs, vec![eth_log2.clone()]);
         Ok(())
     }
+
+    fn unfinalized_log(block_number: u64, block_hash: H256) -> (Log, EthLog) {
+        let log = Log {
+            address: EthAddress::zero(),
+            transaction_hash: Some(TxHash::random()),
+            block_number: Some(U64::from(block_number)),
+            block_hash: Some(block_hash),
+            log_index: Some(U256::from(0)),
+            ..Default::default()
+        };
+        let eth_log = EthLog {
+            block_number,
+            tx_hash: log.transaction_hash.unwrap(),
+            log_index_in_tx: 0,
+            log: log.clone(),
+        };
+        (log, eth_log)
+    }
+
+    #[test]
+    fn test_unfinalized_log_journal() {
+        let mut journal = UnfinalizedLogJournal::default();
+        assert_eq!(journal.last_block(), None);
+
+        let (_, log1) = unfinalized_log(101, H256::random());
+        let (_, log2) = unfinalized_log(103, H256::random());
+        journal.record(104, H256::random(), vec![log1.clone(), log2.clone()]);
+        assert_eq!(journal.last_block(), Some(104));
+        assert_eq!(
+            journal.block_hashes.keys().copied().collect::<Vec<_>>(),
+            vec![101, 103, 104]
+        );
+
+        // Finalized blocks are dropped
+        journal.prune(101);
+        assert_eq!(
+            journal.block_hashes.keys().copied().collect::<Vec<_>>(),
+            vec![103, 104]
+        );
+
+        // Rolling back returns the logs of the dropped blocks only
+        assert_eq!(journal.rollback(103), vec![]);
+        assert_eq!(journal.last_block(), Some(103));
+        assert_eq!(journal.rollback(102), vec![log2]);
+        assert_eq!(journal.last_block(), None);
+    }
+
+    #[tokio::test]
+    async fn test_unfinalized_eth_log_sync_with_reorg() -> anyhow::Result<()> {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let mock_provider = EthMockProvider::new();
+        let head = EthUnfinalizedHead::Latest;
+        mock_last_finalized_block(&mock_provider, 100);
+        mock_get_logs(&mock_provider, EthAddress::zero(), 100, 100, vec![]);
+        let client = EthClient::new_mocked(
+            mock_provider.clone(),
+            HashSet::from_iter(vec![EthAddress::zero()]),
+        );
+        let addresses = HashMap::from_iter(vec![(EthAddress::zero(), 100)]);
+
+        // Block 102 has a log on the first fork
+        let fork_a_hash = H256::random();
+        let (log_a, eth_log_a) = unfinalized_log(102, fork_a_hash);
+        mock_last_unfinalized_block(&mock_provider, head, 102);
+        mock_block_hash(&mock_provider, 102, fork_a_hash);
+        mock_get_logs(&mock_provider, EthAddress::zero(), 101, 102, vec![log_a]);
+
+        let metrics = Arc::new(BridgeMetrics::new_for_testing());
+        let (_handles, mut logs_rx, _finalized_block_rx, mut unfinalized_logs_rx) =
+            EthSyncer::new(Arc::new(client), addresses)
+                .run_with_unfinalized_tracking(metrics.clone(), head)
+                .await
+                .unwrap();
+
+        assert_eq!(
+            unfinalized_logs_rx.recv().await.unwrap(),
+            UnfinalizedEthLogs::Observed(EthAddress::zero(), 102, vec![eth_log_a.clone()])
+        );
+        // Only block 100 is finalized
+        let (_, end_block, received_logs) = logs_rx.recv().await.unwrap();
+        assert_eq!(end_block, 100);
+        assert!(received_logs.is_empty());
+
+        // Block 102 is reorged out. Its log is retracted and the new fork is synced.
+        let fork_b_hash = H256::random();
+        let (log_b, eth_log_b) = unfinalized_log(102, fork_b_hash);
+        mock_get_logs(&mock_provider, EthAddress::zero(), 101, 102, vec![log_b]);
+        mock_block_hash(&mock_provider, 102, fork_b_hash);
+        assert_eq!(
+            unfinalized_logs_rx.recv().await.unwrap(),
+            UnfinalizedEthLogs::Retracted(EthAddress::zero(), 100, vec![eth_log_a])
+        );
+        assert_eq!(
+            unfinalized_logs_rx.recv().await.unwrap(),
+            UnfinalizedEthLogs::Observed(EthAddress::zero(), 102, vec![eth_log_b.clone()])
+        );
+        let address_str = EthAddress::zero().to_string();
+        assert_eq!(
+            metrics.eth_reorgs.with_label_values(&[&address_str]).get(),
+            1
+        );
+        assert_eq!(
+            metrics
+                .eth_retracted_events
+                .with_label_values(&[&address_str])
+                .get(),
+            1
+        );
+
+        // The head advances, then only block 104 is reorged out. The log in block 103 stays.
+        let block_103_hash = H256::random();
+        let (log_c, eth_log_c) = unfinalized_log(103, block_103_hash);
+        mock_block_hash(&mock_provider, 103, block_103_hash);
+        mock_block_hash(&mock_provider, 104, H256::random());
+        mock_get_logs(&mock_provider, EthAddress::zero(), 103, 104, vec![log_c]);
+        mock_last_unfinalized_block(&mock_provider, head, 104);
+        assert_eq!(
+            unfinalized_logs_rx.recv().await.unwrap(),
+            UnfinalizedEthLogs::Observed(EthAddress::zero(), 104, vec![eth_log_c])
+        );
+
+        mock_get_logs(&mock_provider, EthAddress::zero(), 104, 104, vec![]);
+        mock_block_hash(&mock_provider, 104, H256::random());
+        let start = Instant::now();
+        while metrics.eth_reorgs.with_label_values(&[&address_str]).get() < 2 {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "Timed out waiting for the reorg of block 104"
+            );
+            time::sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(
+            metrics
+                .eth_reorg_depth
+                .with_label_values(&[&address_str])
+                .get_sample_sum(),
+            // 2 blocks rolled back in the first reorg, 1 in the second
+            3.0
+        );
+        // No events were retracted by the second reorg
+        assert_eq!(
+            metrics
+                .eth_retracted_events
+                .with_label_values(&[&address_str])
+                .get(),
+            1
+        );
+        assert_eq!(
+            unfinalized_logs_rx.try_recv().unwrap_err(),
+            TryRecvError::Empty
+        );
+        Ok(())
+    }
 }
@@ -29,6 +29,9 @@ pub mod utils;
 #[cfg(any(feature = "test-utils", test))]
 pub(crate) mod eth_mock_provider;
 
+#[cfg(test)]
+pub(crate) mod simulator;
+
 #[cfg(test)]
 pub(crate) mod sui_mock_client;
 
@@ -20,6 +20,8 @@ const FINE_GRAINED_LATENCY_SEC_BUCKETS: &[f64] = &[
     200., 250., 300., 350., 400.,
 ];
 
+const ETH_REORG_DEPTH_BUCKETS: &[f64] = &[1., 2., 3., 4., 5., 6., 8., 10., 16., 32., 64., 128.];
+
 /// Starts a task to periodically push metrics to a configured endpoint if a metrics push endpoint
 /// is configured.
 pub fn start_metrics_push_task(
@@ -90,6 +92,10 @@ pub struct BridgeMetrics {
     pub(crate) last_synced_sui_checkpoints: IntGaugeVec,
     pub(crate) last_finalized_eth_block: IntGauge,
     pub(crate) last_synced_eth_blocks: IntGaugeVec,
+    pub(crate) last_synced_unfinalized_eth_blocks: IntGaugeVec,
+    pub(crate) eth_reorgs: IntCounterVec,
+    pub(crate) eth_reorg_depth: HistogramVec,
+    pub(crate) eth_retracted_events: IntCounterVec,
 
     pub(crate) sui_watcher_received_events: IntCounter,
     pub(crate) sui_watcher_received_actions: IntCounter,
@@ -97,6 +103,7 @@ pub struct BridgeMetrics {
     pub(crate) eth_watcher_received_events: IntCounter,
     pub(crate) eth_watcher_received_actions: IntCounter,
     pub(crate) eth_watcher_unrecognized_events: IntCounter,
+    pub(crate) eth_watcher_unfinalized_actions: IntGauge,
     pub(crate) action_executor_already_processed_actions: IntCounter,
     pub(crate) action_executor_signing_queue_received_actions: IntCounter,
     pub(crate) action_executor_signing_queue_skipped_actions: IntCounter,
@@ -224,6 +231,12 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            eth_watcher_unfinalized_actions: register_int_gauge_with_registry!(
+                "bridge_eth_watcher_unfinalized_actions",
+                "Current number of pre-validated actions from unfinalized eth blocks",
+                registry,
+            )
+            .unwrap(),
             action_executor_already_processed_actions: register_int_counter_with_registry!(
                 "bridge_action_executor_already_processed_actions",
                 "Total number of already processed actions action executor",
@@ -289,6 +302,35 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            last_synced_unfinalized_eth_blocks: register_int_gauge_vec_with_registry!(
+                "bridge_last_synced_unfinalized_eth_blocks",
+                "The latest unfinalized eth blocks synced for each contract",
+                &["contract_address"],
+                registry,
+            )
+            .unwrap(),
+            eth_reorgs: register_int_counter_vec_with_registry!(
+                "bridge_eth_reorgs",
+                "Total number of eth reorgs observed in unfinalized blocks, by contract",
+                &["contract_address"],
+                registry,
+            )
+            .unwrap(),
+            eth_reorg_depth: register_histogram_vec_with_registry!(
+                "bridge_eth_reorg_depth",
+                "Number of unfinalized eth blocks rolled back per reorg, by contract",
+                &["contract_address"],
+                ETH_REORG_DEPTH_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            eth_retracted_events: register_int_counter_vec_with_registry!(
+                "bridge_eth_retracted_events",
+                "Total number of unfinalized eth events retracted because of reorgs, by contract",
+                &["contract_address"],
+                registry,
+            )
+            .unwrap(),
             last_finalized_eth_block: register_int_gauge_with_registry!(
                 "bridge_last_finalized_eth_block",
                 "The latest finalized eth block observed",
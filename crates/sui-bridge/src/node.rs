@@ -7,13 +7,17 @@ use crate::metered_eth_provider::MeteredEthHttpProvier;
 use crate::sui_bridge_watchdog::eth_bridge_status::EthBridgeStatus;
 use crate::sui_bridge_watchdog::eth_vault_balance::EthVaultBalance;
 use crate::sui_bridge_watchdog::metrics::WatchdogMetrics;
+use crate::sui_bridge_watchdog::reconciliation::{
+    AlertNotifier, BridgeReconciliation, LogNotifier, WebhookNotifier,
+};
 use crate::sui_bridge_watchdog::sui_bridge_status::SuiBridgeStatus;
 use crate::sui_bridge_watchdog::total_supplies::TotalSupplies;
 use crate::sui_bridge_watchdog::{BridgeWatchDog, Observable};
 use crate::sui_client::SuiBridgeClient;
 use crate::types::BridgeCommittee;
 use crate::utils::{
-    get_committee_voting_power_by_name, get_eth_contract_addresses, get_validator_names_by_pub_keys,
+    get_committee_voting_power_by_name, get_eth_contract_addresses, get_eth_contracts,
+    get_validator_names_by_pub_keys,
 };
 use crate::{
     action_executor::BridgeActionExecutor,
@@ -171,7 +175,7 @@ async fn start_watchdog(
     );
 
     let eth_bridge_status = EthBridgeStatus::new(
-        eth_provider,
+        eth_provider.clone(),
         eth_bridge_proxy_address,
         watchdog_metrics.eth_bridge_paused.clone(),
     );
@@ -195,6 +199,24 @@ async fn start_watchdog(
             );
             observables.push(Box::new(total_supplies));
         }
+        if let Some(reconciliation_config) = watchdog_config.reconciliation {
+            let eth_contracts = get_eth_contracts(eth_bridge_proxy_address, &eth_provider)
+                .await
+                .unwrap_or_else(|e| panic!("get_eth_contracts should not fail: {}", e));
+            let mut notifiers: Vec<Box<dyn AlertNotifier>> = vec![Box::new(LogNotifier)];
+            if let Some(url) = &reconciliation_config.alert_webhook_url {
+                notifiers.push(Box::new(WebhookNotifier::new(url.clone())));
+            }
+            let reconciliation = BridgeReconciliation::new(
+                sui_client.clone(),
+                eth_provider,
+                eth_contracts,
+                reconciliation_config,
+                notifiers,
+                watchdog_metrics.clone(),
+            );
+            observables.push(Box::new(reconciliation));
+        }
     }
 
     BridgeWatchDog::new(observables).run().await
@@ -223,12 +245,25 @@ async fn start_client_components(
     let sui_client = client_config.sui_client.clone();
 
     let mut all_handles = vec![];
-    let (task_handles, eth_events_rx, _) =
-        EthSyncer::new(client_config.eth_client.clone(), eth_contracts_to_watch)
-            .run(metrics.clone())
-            .await
-            .expect("Failed to start eth syncer");
-    all_handles.extend(task_handles);
+    let eth_syncer = EthSyncer::new(client_config.eth_client.clone(), eth_contracts_to_watch);
+    let (eth_events_rx, unfinalized_eth_events_rx) = match client_config.eth_unfinalized_head {
+        Some(head) => {
+            let (task_handles, eth_events_rx, _, unfinalized_eth_events_rx) = eth_syncer
+                .run_with_unfinalized_tracking(metrics.clone(), head)
+                .await
+                .expect("Failed to start eth syncer");
+            all_handles.extend(task_handles);
+            (eth_events_rx, Some(unfinalized_eth_events_rx))
+        }
+        None => {
+            let (task_handles, eth_events_rx, _) = eth_syncer
+                .run(metrics.clone())
+                .await
+                .expect("Failed to start eth syncer");
+            all_handles.extend(task_handles);
+            (eth_events_rx, None)
+        }
+    };
 
     let (task_handles, sui_events_rx) = SuiSyncer::new(
         client_config.sui_client,
@@ -291,7 +326,7 @@ async fn start_client_components(
     );
     all_handles.push(spawn_logged_monitored_task!(monitor.run()));
 
-    let orchestrator = BridgeOrchestrator::new(
+    let mut orchestrator = BridgeOrchestrator::new(
         sui_client,
         sui_events_rx,
         eth_events_rx,
@@ -300,6 +335,9 @@ async fn start_client_components(
         eth_monitor_tx,
         metrics,
     );
+    if let Some(unfinalized_eth_events_rx) = unfinalized_eth_events_rx {
+        orchestrator = orchestrator.with_unfinalized_eth_events(unfinalized_eth_events_rx);
+    }
 
     all_handles.extend(orchestrator.run(bridge_action_executor).await);
     Ok(all_handles)
@@ -572,6 +610,7 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: None,
                 eth_contracts_start_block_override: None,
+                eth_unfinalized_head: None,
             },
             approved_governance_actions: vec![],
             run_client: false,
@@ -639,6 +678,7 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: Some(0),
                 eth_contracts_start_block_override: None,
+                eth_unfinalized_head: None,
             },
             approved_governance_actions: vec![],
             run_client: true,
@@ -717,6 +757,7 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: Some(0),
                 eth_contracts_start_block_override: Some(0),
+                eth_unfinalized_head: None,
             },
             approved_governance_actions: vec![],
             run_client: true,
@@ -11,23 +11,32 @@ use crate::action_executor::{
     submit_to_executor, BridgeActionExecutionWrapper, BridgeActionExecutorTrait,
 };
 use crate::error::BridgeError;
+use crate::eth_syncer::UnfinalizedEthLogs;
 use crate::events::SuiBridgeEvent;
 use crate::metrics::BridgeMetrics;
 use crate::storage::BridgeOrchestratorTables;
 use crate::sui_client::{SuiClient, SuiClientInner};
-use crate::types::EthLog;
+use crate::types::{BridgeAction, BridgeActionDigest, BridgeActionStatus, EthLog};
 use ethers::types::Address as EthAddress;
 use mysten_metrics::spawn_logged_monitored_task;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use sui_json_rpc_types::SuiEvent;
 use sui_types::Identifier;
 use tokio::task::JoinHandle;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+/// Actions pre-validated from unfinalized eth blocks, keyed by digest. They are kept in memory
+/// only: on restart the unfinalized blocks are synced again from the last finalized block.
+pub type UnfinalizedEthActions = Arc<Mutex<HashMap<BridgeActionDigest, BridgeAction>>>;
 
 pub struct BridgeOrchestrator<C> {
-    _sui_client: Arc<SuiClient<C>>,
+    sui_client: Arc<SuiClient<C>>,
     sui_events_rx: mysten_metrics::metered_channel::Receiver<(Identifier, Vec<SuiEvent>)>,
     eth_events_rx: mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
+    unfinalized_eth_events_rx:
+        Option<mysten_metrics::metered_channel::Receiver<UnfinalizedEthLogs>>,
+    unfinalized_eth_actions: UnfinalizedEthActions,
     store: Arc<BridgeOrchestratorTables>,
     sui_monitor_tx: mysten_metrics::metered_channel::Sender<SuiBridgeEvent>,
     eth_monitor_tx: mysten_metrics::metered_channel::Sender<EthBridgeEvent>,
@@ -48,9 +57,11 @@ where
         metrics: Arc<BridgeMetrics>,
     ) -> Self {
         Self {
-            _sui_client: sui_client,
+            sui_client,
             sui_events_rx,
             eth_events_rx,
+            unfinalized_eth_events_rx: None,
+            unfinalized_eth_actions: Default::default(),
             store,
             sui_monitor_tx,
             eth_monitor_tx,
@@ -58,6 +69,20 @@ where
         }
     }
 
+    /// Pre-validates events from unfinalized eth blocks into actions, tracked until they are
+    /// finalized or retracted by a reorg.
+    pub fn with_unfinalized_eth_events(
+        mut self,
+        unfinalized_eth_events_rx: mysten_metrics::metered_channel::Receiver<UnfinalizedEthLogs>,
+    ) -> Self {
+        self.unfinalized_eth_events_rx = Some(unfinalized_eth_events_rx);
+        self
+    }
+
+    pub fn unfinalized_eth_actions(&self) -> UnfinalizedEthActions {
+        self.unfinalized_eth_actions.clone()
+    }
+
     pub async fn run(
         self,
         bridge_action_executor: impl BridgeActionExecutorTrait,
@@ -91,9 +116,22 @@ where
                 .expect("Submit to executor should not fail");
         }
 
+        if let Some(unfinalized_eth_events_rx) = self.unfinalized_eth_events_rx {
+            task_handles.push(spawn_logged_monitored_task!(
+                Self::run_unfinalized_eth_watcher(
+                    self.sui_client.clone(),
+                    store_clone.clone(),
+                    self.unfinalized_eth_actions.clone(),
+                    unfinalized_eth_events_rx,
+                    self.metrics.clone(),
+                )
+            ));
+        }
+
         let metrics_clone = self.metrics.clone();
         task_handles.push(spawn_logged_monitored_task!(Self::run_eth_watcher(
             store_clone,
+            self.unfinalized_eth_actions,
             executor_sender,
             self.eth_events_rx,
             self.eth_monitor_tx,
@@ -195,6 +233,7 @@ where
 
     async fn run_eth_watcher(
         store: Arc<BridgeOrchestratorTables>,
+        unfinalized_eth_actions: UnfinalizedEthActions,
         executor_tx: mysten_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
         mut eth_events_rx: mysten_metrics::metered_channel::Receiver<(
             ethers::types::Address,
@@ -264,6 +303,16 @@ where
                 store
                     .insert_pending_actions(&actions)
                     .expect("Store operation should not fail");
+                // Finalized actions are no longer tracked as unfinalized.
+                {
+                    let mut unfinalized = unfinalized_eth_actions.lock().unwrap();
+                    for action in &actions {
+                        unfinalized.remove(&action.digest());
+                    }
+                    metrics
+                        .eth_watcher_unfinalized_actions
+                        .set(unfinalized.len() as i64);
+                }
                 // Execution will remove the pending actions from DB when the action is completed.
                 for action in actions {
                     submit_to_executor(&executor_tx, action)
@@ -278,6 +327,97 @@ where
         }
         panic!("Eth event channel was closed");
     }
+
+    async fn run_unfinalized_eth_watcher(
+        sui_client: Arc<SuiClient<C>>,
+        store: Arc<BridgeOrchestratorTables>,
+        unfinalized_eth_actions: UnfinalizedEthActions,
+        mut unfinalized_eth_events_rx: mysten_metrics::metered_channel::Receiver<
+            UnfinalizedEthLogs,
+        >,
+        metrics: Arc<BridgeMetrics>,
+    ) {
+        info!("Starting unfinalized eth watcher task");
+        while let Some(unfinalized_logs) = unfinalized_eth_events_rx.recv().await {
+            match unfinalized_logs {
+                UnfinalizedEthLogs::Observed(contract, end_block, logs) => {
+                    // Skip logs that the eth watcher has already processed as finalized.
+                    let cursor = store
+                        .get_eth_event_cursors(&[contract])
+                        .expect("Store operation should not fail")[0];
+                    let logs = logs
+                        .into_iter()
+                        .filter(|log| cursor.map_or(true, |cursor| log.block_number > cursor))
+                        .collect::<Vec<_>>();
+                    // Skip actions that are already approved or claimed on Sui, e.g. replays.
+                    let mut actions = vec![];
+                    for action in Self::eth_logs_to_actions(&logs) {
+                        let status = sui_client
+                            .get_token_transfer_action_onchain_status_until_success(
+                                action.chain_id() as u8,
+                                action.seq_number(),
+                            )
+                            .await;
+                        if !matches!(
+                            status,
+                            BridgeActionStatus::Approved | BridgeActionStatus::Claimed
+                        ) {
+                            actions.push(action);
+                        }
+                    }
+                    if actions.is_empty() {
+                        continue;
+                    }
+                    info!(
+                        ?contract,
+                        end_block,
+                        "Pre-validated {} actions from unfinalized Eth blocks: {:?}",
+                        actions.len(),
+                        actions
+                    );
+                    let mut unfinalized = unfinalized_eth_actions.lock().unwrap();
+                    for action in actions {
+                        unfinalized.insert(action.digest(), action);
+                    }
+                }
+                UnfinalizedEthLogs::Retracted(contract, fork_block, logs) => {
+                    let actions = Self::eth_logs_to_actions(&logs);
+                    warn!(
+                        ?contract,
+                        fork_block,
+                        "Retracting {} actions from reorged Eth blocks: {:?}",
+                        actions.len(),
+                        actions
+                    );
+                    // Only the in-memory pre-validation is retracted. Actions in the pending
+                    // WAL come from finalized blocks and must still be executed.
+                    let mut unfinalized = unfinalized_eth_actions.lock().unwrap();
+                    for action in &actions {
+                        unfinalized.remove(&action.digest());
+                    }
+                }
+            }
+            metrics
+                .eth_watcher_unfinalized_actions
+                .set(unfinalized_eth_actions.lock().unwrap().len() as i64);
+        }
+        panic!("Unfinalized eth event channel was closed");
+    }
+
+    fn eth_logs_to_actions(logs: &[EthLog]) -> Vec<BridgeAction> {
+        logs.iter()
+            .filter_map(|log| {
+                let bridge_event = EthBridgeEvent::try_from_eth_log(log)?;
+                match bridge_event.try_into_bridge_action(log.tx_hash, log.log_index_in_tx) {
+                    Ok(action) => action,
+                    Err(e) => {
+                        error!(eth_tx_hash=?log.tx_hash, eth_event_index=?log.log_index_in_tx, "Error converting EthBridgeEvent to BridgeAction: {:?}", e);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -288,6 +428,7 @@ mod tests {
     };
     use ethers::types::{Address as EthAddress, TxHash};
     use prometheus::Registry;
+    use std::collections::HashMap;
     use std::str::FromStr;
 
     use super::*;
@@ -437,6 +578,154 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_unfinalized_eth_watcher_task() {
+        let (
+            _sui_events_tx,
+            sui_events_rx,
+            eth_events_tx,
+            eth_events_rx,
+            sui_monitor_tx,
+            _sui_monitor_rx,
+            eth_monitor_tx,
+            _eth_monitor_rx,
+            sui_client,
+            store,
+        ) = setup();
+        let (unfinalized_eth_events_tx, unfinalized_eth_events_rx) =
+            mysten_metrics::metered_channel::channel(
+                100,
+                &mysten_metrics::get_metrics()
+                    .unwrap()
+                    .channel_inflight
+                    .with_label_values(&["unit_test_unfinalized_eth_events_queue"]),
+            );
+        let address = EthAddress::random();
+        let (log, bridge_action) = get_test_log_and_action(address, TxHash::random(), 10);
+        let eth_log = EthLog {
+            log: log.clone(),
+            tx_hash: log.transaction_hash.unwrap(),
+            block_number: log.block_number.unwrap().as_u64(),
+            log_index_in_tx: 10,
+        };
+        let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let registry = Registry::new();
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+        let orchestrator = BridgeOrchestrator::new(
+            Arc::new(sui_client),
+            sui_events_rx,
+            eth_events_rx,
+            store.clone(),
+            sui_monitor_tx,
+            eth_monitor_tx,
+            metrics,
+        )
+        .with_unfinalized_eth_events(unfinalized_eth_events_rx);
+        let unfinalized = orchestrator.unfinalized_eth_actions();
+        let _handles = orchestrator.run(executor).await;
+
+        // Observed actions are pre-validated but not executed
+        unfinalized_eth_events_tx
+            .send(UnfinalizedEthLogs::Observed(
+                address,
+                eth_log.block_number,
+                vec![eth_log.clone()],
+            ))
+            .await
+            .unwrap();
+        wait_for_unfinalized_actions(&unfinalized, vec![bridge_action.clone()]).await;
+        assert!(executor_requested_action_rx.try_recv().is_err());
+
+        // Retracted actions are removed
+        unfinalized_eth_events_tx
+            .send(UnfinalizedEthLogs::Retracted(
+                address,
+                eth_log.block_number - 1,
+                vec![eth_log.clone()],
+            ))
+            .await
+            .unwrap();
+        wait_for_unfinalized_actions(&unfinalized, vec![]).await;
+
+        // Observed again on the new fork, then finalized
+        unfinalized_eth_events_tx
+            .send(UnfinalizedEthLogs::Observed(
+                address,
+                eth_log.block_number,
+                vec![eth_log.clone()],
+            ))
+            .await
+            .unwrap();
+        wait_for_unfinalized_actions(&unfinalized, vec![bridge_action.clone()]).await;
+        eth_events_tx
+            .send((address, eth_log.block_number, vec![eth_log.clone()]))
+            .await
+            .unwrap();
+        assert_eq!(
+            executor_requested_action_rx.recv().await.unwrap(),
+            bridge_action.digest()
+        );
+        wait_for_unfinalized_actions(&unfinalized, vec![]).await;
+
+        // Logs of blocks that are already finalized are ignored
+        unfinalized_eth_events_tx
+            .send(UnfinalizedEthLogs::Observed(
+                address,
+                eth_log.block_number,
+                vec![eth_log.clone()],
+            ))
+            .await
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        assert!(unfinalized.lock().unwrap().is_empty());
+
+        // A retraction never removes actions from the pending WAL
+        let (finalized_log, finalized_action) =
+            get_test_log_and_action(address, TxHash::random(), 11);
+        store
+            .insert_pending_actions(&[finalized_action.clone()])
+            .unwrap();
+        unfinalized
+            .lock()
+            .unwrap()
+            .insert(finalized_action.digest(), finalized_action.clone());
+        let finalized_eth_log = EthLog {
+            log: finalized_log.clone(),
+            tx_hash: finalized_log.transaction_hash.unwrap(),
+            block_number: finalized_log.block_number.unwrap().as_u64(),
+            log_index_in_tx: 11,
+        };
+        unfinalized_eth_events_tx
+            .send(UnfinalizedEthLogs::Retracted(
+                address,
+                finalized_eth_log.block_number - 1,
+                vec![finalized_eth_log],
+            ))
+            .await
+            .unwrap();
+        wait_for_unfinalized_actions(&unfinalized, vec![]).await;
+        assert!(store
+            .get_all_pending_actions()
+            .contains_key(&finalized_action.digest()));
+    }
+
+    async fn wait_for_unfinalized_actions(
+        unfinalized: &UnfinalizedEthActions,
+        expected: Vec<BridgeAction>,
+    ) {
+        let expected = expected
+            .into_iter()
+            .map(|a| (a.digest(), a))
+            .collect::<HashMap<_, _>>();
+        let start = std::time::Instant::now();
+        while *unfinalized.lock().unwrap() != expected {
+            if start.elapsed().as_secs() > 5 {
+                panic!("Timed out waiting for unfinalized actions {:?}", expected);
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+    }
+
     #[tokio::test]
     /// Test that when orchestrator starts, all pending actions are sent to executor
     async fn test_resume_actions_in_pending_logs() {
@@ -0,0 +1,107 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bridge authorities that sign actions observed on the simulated chains.
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::Json;
+use ethers::types::TxHash;
+use sui_types::digests::TransactionDigest;
+
+use super::chains::{SimulatedEthChain, SimulatedSuiChain};
+use crate::crypto::{BridgeAuthorityKeyPair, BridgeAuthoritySignInfo};
+use crate::error::BridgeError;
+use crate::server::governance_verifier::GovernanceVerifier;
+use crate::server::handler::{ActionVerifier, BridgeRequestHandlerTrait};
+use crate::types::{BridgeAction, SignedBridgeAction};
+
+/// Signs token transfers only once they are finalized on their source chain, and governance
+/// actions only once approved, like `BridgeRequestHandler` does. Authorities serve the node's
+/// `BridgeClient`s in process. They can be taken down, in which case all requests fail.
+#[derive(Clone)]
+pub(crate) struct SimulatedAuthority {
+    signer: Arc<BridgeAuthorityKeyPair>,
+    eth_chain: Arc<Mutex<SimulatedEthChain>>,
+    sui_chain: Arc<SimulatedSuiChain>,
+    approved_governance_actions: Arc<Mutex<Vec<BridgeAction>>>,
+    is_down: Arc<AtomicBool>,
+}
+
+impl SimulatedAuthority {
+    pub(crate) fn new(
+        signer: BridgeAuthorityKeyPair,
+        eth_chain: Arc<Mutex<SimulatedEthChain>>,
+        sui_chain: Arc<SimulatedSuiChain>,
+    ) -> Self {
+        Self {
+            signer: Arc::new(signer),
+            eth_chain,
+            sui_chain,
+            approved_governance_actions: Default::default(),
+            is_down: Default::default(),
+        }
+    }
+
+    pub(crate) fn approve_governance_action(&self, action: BridgeAction) {
+        self.approved_governance_actions
+            .lock()
+            .unwrap()
+            .push(action);
+    }
+
+    pub(crate) fn set_down(&self, is_down: bool) {
+        self.is_down.store(is_down, Ordering::Relaxed);
+    }
+
+    fn sign(&self, action: BridgeAction) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        if self.is_down.load(Ordering::Relaxed) {
+            return Err(BridgeError::RestAPIError(
+                "Simulated authority is down".to_string(),
+            ));
+        }
+        let sig = BridgeAuthoritySignInfo::new(&action, &self.signer);
+        Ok(Json(SignedBridgeAction::new_from_data_and_sig(action, sig)))
+    }
+}
+
+#[async_trait]
+impl BridgeRequestHandlerTrait for SimulatedAuthority {
+    async fn handle_eth_tx_hash(
+        &self,
+        tx_hash_hex: String,
+        event_idx: u16,
+    ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        let tx_hash = TxHash::from_str(&tx_hash_hex).map_err(|_| BridgeError::InvalidTxHash)?;
+        let action = self
+            .eth_chain
+            .lock()
+            .unwrap()
+            .finalized_deposit(tx_hash, event_idx)?;
+        self.sign(BridgeAction::EthToSuiBridgeAction(action))
+    }
+
+    async fn handle_sui_tx_digest(
+        &self,
+        tx_digest_base58: String,
+        event_idx: u16,
+    ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        let tx_digest = TransactionDigest::from_str(&tx_digest_base58)
+            .map_err(|_| BridgeError::InvalidTxHash)?;
+        let action = self.sui_chain.deposit_at(tx_digest, event_idx)?;
+        self.sign(BridgeAction::SuiToEthBridgeAction(action))
+    }
+
+    async fn handle_governance_action(
+        &self,
+        action: BridgeAction,
+    ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        let verifier =
+            GovernanceVerifier::new(self.approved_governance_actions.lock().unwrap().clone())?;
+        let action = verifier.verify(action).await?;
+        self.sign(action)
+    }
+}
@@ -0,0 +1,789 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deterministic stand-ins for the two sides of the bridge.
+//!
+//! `SimulatedEthChain` models the bridge contract on Ethereum: blocks of deposits, finality and
+//! reorgs. `SimulatedSuiChain` models the bridge object on Sui: deposits, and the execution of
+//! the token transfer transactions built by the action executor.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+
+use ethers::abi::{long_signature, ParamType};
+use ethers::types::{Address as EthAddress, Log, TxHash, H256};
+use fastcrypto::hash::Keccak256;
+use fastcrypto::traits::{RecoverableSignature, ToFromBytes};
+use serde::de::DeserializeOwned;
+use sui_json_rpc_types::{
+    SuiEvent, SuiExecutionStatus, SuiTransactionBlockEffects, SuiTransactionBlockEvents,
+    SuiTransactionBlockResponse,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::bridge::{BridgeChainId, MoveTypeBridgeMessageKey};
+use sui_types::digests::TransactionDigest;
+use sui_types::event::EventID;
+use sui_types::transaction::{
+    Argument, CallArg, Command, ProgrammableTransaction, Transaction, TransactionDataAPI,
+    TransactionKind,
+};
+use sui_types::Identifier;
+
+use crate::abi::EthToSuiTokenBridgeV1;
+use crate::crypto::{BridgeAuthorityPublicKeyBytes, BridgeAuthorityRecoverableSignature};
+use crate::error::{BridgeError, BridgeResult};
+use crate::events::{
+    EmittedSuiToEthTokenBridgeV1, MoveTokenDepositedEvent, SuiToEthTokenBridgeV1,
+    TokenTransferAlreadyApproved, TokenTransferAlreadyClaimed, TokenTransferApproved,
+    TokenTransferClaimed, TokenTransferLimitExceed,
+};
+use crate::sui_mock_client::{MockTransactionExecutor, SuiMockClient};
+use crate::types::{
+    BridgeAction, BridgeActionStatus, BridgeActionType, BridgeCommittee, EmergencyActionType,
+    EthLog, EthToSuiBridgeAction, SuiToEthBridgeAction, VerifiedCertifiedBridgeAction,
+};
+
+pub(crate) const SIMULATED_ETH_CHAIN_ID: BridgeChainId = BridgeChainId::EthCustom;
+pub(crate) const SIMULATED_SUI_CHAIN_ID: BridgeChainId = BridgeChainId::SuiCustom;
+
+/// USD price, with 4 decimal places, of every token unless overridden.
+pub(crate) const DEFAULT_TOKEN_PRICE: u64 = 10_000;
+
+// Token amounts on the bridge are adjusted to 8 decimal places.
+const SUI_ADJUSTED_AMOUNT_MULTIPLIER: u128 = 100_000_000;
+
+/// Returns the USD value, with 4 decimal places, of a Sui adjusted token amount.
+fn notional_value(amount: u64, price: u64) -> u64 {
+    (amount as u128 * price as u128 / SUI_ADJUSTED_AMOUNT_MULTIPLIER) as u64
+}
+
+/// Checks that `signatures` over `action` are from distinct committee members carrying at
+/// least the approval threshold of the action, like the bridge contracts do.
+pub(crate) fn verify_quorum(
+    action: &BridgeAction,
+    signatures: &[Vec<u8>],
+    committee: &BridgeCommittee,
+) -> BridgeResult<()> {
+    let msg = action.to_bytes();
+    let mut signers = BTreeSet::new();
+    let mut stake = 0;
+    for sig in signatures {
+        let sig = BridgeAuthorityRecoverableSignature::from_bytes(sig)
+            .map_err(|e| BridgeError::Generic(format!("Malformed signature: {:?}", e)))?;
+        let pubkey = sig
+            .recover_with_hash::<Keccak256>(&msg)
+            .map_err(|e| BridgeError::Generic(format!("Failed to recover signer: {:?}", e)))?;
+        let pubkey = BridgeAuthorityPublicKeyBytes::from(&pubkey);
+        if !signers.insert(pubkey.clone()) {
+            return Err(BridgeError::Generic(format!(
+                "Duplicate signature from {:?}",
+                pubkey
+            )));
+        }
+        stake += committee.active_stake(&pubkey);
+    }
+    if stake < action.approval_threshold() {
+        return Err(BridgeError::Generic(format!(
+            "Insufficient stake {} for action {:?}",
+            stake,
+            action.key()
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the signatures of a certified action as they are submitted on chain.
+pub(crate) fn certificate_signatures(certificate: &VerifiedCertifiedBridgeAction) -> Vec<Vec<u8>> {
+    certificate
+        .auth_sig()
+        .signatures
+        .values()
+        .map(|sig| sig.as_bytes().to_vec())
+        .collect()
+}
+
+// Governance actions of each type are executed in nonce order, and each nonce only once.
+fn check_governance_nonce(
+    next_nonces: &mut BTreeMap<u8, u64>,
+    action: &BridgeAction,
+) -> BridgeResult<()> {
+    let next_nonce = next_nonces.entry(action.action_type() as u8).or_default();
+    if action.seq_number() != *next_nonce {
+        return Err(BridgeError::Generic(format!(
+            "Unexpected nonce {} for {:?}, expected {}",
+            action.seq_number(),
+            action.action_type(),
+            next_nonce
+        )));
+    }
+    *next_nonce += 1;
+    Ok(())
+}
+
+#[derive(Debug)]
+pub(crate) struct SimulatedEthChain {
+    committee: Arc<BridgeCommittee>,
+    contract_address: EthAddress,
+    finality_depth: u64,
+    // Deposits in each canonical block, where block `n` is at index `n - 1`.
+    blocks: Vec<Vec<EthToSuiBridgeAction>>,
+    // Deposits that were in canonical blocks before being reorged out.
+    orphaned: Vec<EthToSuiBridgeAction>,
+    next_nonce: u64,
+    // Never rolled back, so reorged out transactions are not reused.
+    next_tx: u64,
+    supported_tokens: BTreeSet<u8>,
+    token_prices: BTreeMap<u8, u64>,
+    paused: bool,
+    limits: HashMap<BridgeChainId, u64>,
+    governance_nonces: BTreeMap<u8, u64>,
+}
+
+impl SimulatedEthChain {
+    pub(crate) fn new(
+        committee: Arc<BridgeCommittee>,
+        finality_depth: u64,
+        supported_tokens: Vec<u8>,
+    ) -> Self {
+        Self {
+            committee,
+            contract_address: EthAddress::repeat_byte(0xb),
+            finality_depth,
+            blocks: vec![],
+            orphaned: vec![],
+            next_nonce: 0,
+            next_tx: 1,
+            supported_tokens: supported_tokens.into_iter().collect(),
+            token_prices: BTreeMap::new(),
+            paused: false,
+            limits: HashMap::new(),
+            governance_nonces: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn contract_address(&self) -> EthAddress {
+        self.contract_address
+    }
+
+    pub(crate) fn head(&self) -> u64 {
+        self.blocks.len() as u64
+    }
+
+    pub(crate) fn finalized_block(&self) -> u64 {
+        self.head().saturating_sub(self.finality_depth)
+    }
+
+    pub(crate) fn is_token_supported(&self, token_id: u8) -> bool {
+        self.supported_tokens.contains(&token_id)
+    }
+
+    pub(crate) fn limit(&self, sending_chain: BridgeChainId) -> Option<u64> {
+        self.limits.get(&sending_chain).copied()
+    }
+
+    pub(crate) fn token_price(&self, token_id: u8) -> u64 {
+        self.token_prices
+            .get(&token_id)
+            .copied()
+            .unwrap_or(DEFAULT_TOKEN_PRICE)
+    }
+
+    pub(crate) fn orphaned_deposits(&self) -> &[EthToSuiBridgeAction] {
+        &self.orphaned
+    }
+
+    /// Deposits tokens to be bridged to `recipient` on Sui, in a newly mined block.
+    pub(crate) fn deposit(
+        &mut self,
+        token_id: u8,
+        amount: u64,
+        sender: EthAddress,
+        recipient: SuiAddress,
+    ) -> BridgeResult<EthToSuiBridgeAction> {
+        if self.paused {
+            return Err(BridgeError::Generic("Eth bridge is paused".to_string()));
+        }
+        if !self.supported_tokens.contains(&token_id) {
+            return Err(BridgeError::UnknownTokenId(token_id));
+        }
+        let action = EthToSuiBridgeAction {
+            eth_tx_hash: TxHash::from_low_u64_be(self.next_tx),
+            eth_event_index: 0,
+            eth_bridge_event: EthToSuiTokenBridgeV1 {
+                nonce: self.next_nonce,
+                sui_chain_id: SIMULATED_SUI_CHAIN_ID,
+                eth_chain_id: SIMULATED_ETH_CHAIN_ID,
+                sui_address: recipient,
+                eth_address: sender,
+                token_id,
+                sui_adjusted_amount: amount,
+            },
+        };
+        self.next_tx += 1;
+        self.next_nonce += 1;
+        self.blocks.push(vec![action.clone()]);
+        Ok(action)
+    }
+
+    /// Mines `count` empty blocks.
+    pub(crate) fn mine(&mut self, count: u64) {
+        for _ in 0..count {
+            self.blocks.push(vec![]);
+        }
+    }
+
+    /// Replaces the latest `depth` blocks with empty ones and returns the deposits in them.
+    /// Finalized blocks are never reorged out.
+    pub(crate) fn reorg(&mut self, depth: u64) -> Vec<EthToSuiBridgeAction> {
+        assert!(
+            depth <= self.head() - self.finalized_block(),
+            "Can not reorg finalized blocks"
+        );
+        let fork_point = (self.head() - depth) as usize;
+        let orphaned = self
+            .blocks
+            .split_off(fork_point)
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        self.next_nonce -= orphaned.len() as u64;
+        self.orphaned.extend(orphaned.iter().cloned());
+        self.mine(depth);
+        orphaned
+    }
+
+    /// Returns the logs of the deposits in blocks `from_block` to `to_block`, both included.
+    pub(crate) fn logs(&self, from_block: u64, to_block: u64) -> Vec<EthLog> {
+        (from_block.max(1)..=to_block.min(self.head()))
+            .flat_map(|block_number| {
+                self.blocks[block_number as usize - 1]
+                    .iter()
+                    .map(move |action| self.deposit_log(block_number, action))
+            })
+            .collect()
+    }
+
+    /// Returns the deposit at the given position, if it is in a finalized block.
+    pub(crate) fn finalized_deposit(
+        &self,
+        tx_hash: TxHash,
+        event_index: u16,
+    ) -> BridgeResult<EthToSuiBridgeAction> {
+        for (i, block) in self.blocks.iter().enumerate() {
+            if let Some(action) = block
+                .iter()
+                .find(|a| a.eth_tx_hash == tx_hash && a.eth_event_index == event_index)
+            {
+                if i as u64 + 1 > self.finalized_block() {
+                    return Err(BridgeError::TxNotFinalized);
+                }
+                return Ok(action.clone());
+            }
+        }
+        Err(BridgeError::NoBridgeEventsInTxPosition)
+    }
+
+    /// Returns the canonical deposit with the given nonce.
+    pub(crate) fn canonical_deposit(&self, nonce: u64) -> Option<&EthToSuiBridgeAction> {
+        self.blocks
+            .iter()
+            .flatten()
+            .find(|a| a.eth_bridge_event.nonce == nonce)
+    }
+
+    /// Executes a certified governance action targeting Ethereum.
+    pub(crate) fn execute_governance_action(
+        &mut self,
+        certificate: &VerifiedCertifiedBridgeAction,
+    ) -> BridgeResult<()> {
+        let action = certificate.data();
+        if action.chain_id() != SIMULATED_ETH_CHAIN_ID {
+            return Err(BridgeError::Generic(format!(
+                "Action {:?} does not target Eth",
+                action.key()
+            )));
+        }
+        verify_quorum(
+            action,
+            &certificate_signatures(certificate),
+            &self.committee,
+        )?;
+        check_governance_nonce(&mut self.governance_nonces, action)?;
+        match action {
+            BridgeAction::EmergencyAction(a) => {
+                self.paused = a.action_type == EmergencyActionType::Pause;
+            }
+            BridgeAction::LimitUpdateAction(a) => {
+                self.limits.insert(a.sending_chain_id, a.new_usd_limit);
+            }
+            BridgeAction::AddTokensOnEvmAction(a) => {
+                for (token_id, price) in a.token_ids.iter().zip(&a.token_prices) {
+                    self.supported_tokens.insert(*token_id);
+                    self.token_prices.insert(*token_id, *price);
+                }
+            }
+            _ => {
+                return Err(BridgeError::Generic(format!(
+                    "Unsupported governance action {:?}",
+                    action.action_type()
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    fn deposit_log(&self, block_number: u64, action: &EthToSuiBridgeAction) -> EthLog {
+        let event = &action.eth_bridge_event;
+        // Note: must use `encode` rather than `encode_packged`
+        let data = ethers::abi::encode(&[
+            ethers::abi::Token::Uint(event.token_id.into()),
+            ethers::abi::Token::Uint(event.sui_adjusted_amount.into()),
+            ethers::abi::Token::Address(event.eth_address),
+            ethers::abi::Token::Bytes(event.sui_address.to_vec()),
+        ]);
+        let log = Log {
+            address: self.contract_address,
+            topics: vec![
+                long_signature(
+                    "TokensDeposited",
+                    &[
+                        ParamType::Uint(8),
+                        ParamType::Uint(64),
+                        ParamType::Uint(8),
+                        ParamType::Uint(8),
+                        ParamType::Uint(64),
+                        ParamType::Address,
+                        ParamType::Bytes,
+                    ],
+                ),
+                H256::from_low_u64_be(event.eth_chain_id as u64),
+                H256::from_low_u64_be(event.nonce),
+                H256::from_low_u64_be(event.sui_chain_id as u64),
+            ],
+            data: data.into(),
+            block_hash: Some(H256::from_low_u64_be(block_number)),
+            block_number: Some(block_number.into()),
+            transaction_hash: Some(action.eth_tx_hash),
+            log_index: Some(action.eth_event_index.into()),
+            ..Default::default()
+        };
+        EthLog {
+            block_number,
+            tx_hash: action.eth_tx_hash,
+            log_index_in_tx: action.eth_event_index,
+            log,
+        }
+    }
+}
+
+// State of the bridge object on Sui.
+#[derive(Debug, Default)]
+struct SuiChainState {
+    next_nonce: u64,
+    deposits: BTreeMap<(TransactionDigest, u16), SuiToEthBridgeAction>,
+    approvals: BTreeMap<(u8, u64), BridgeAction>,
+    // Messages with a quorum of signatures that conflict with an already approved message.
+    conflicting_approvals: Vec<BridgeAction>,
+    claimed: BTreeSet<(u8, u64)>,
+    limit_exceeded: BTreeSet<(u8, u64)>,
+    claimed_value: HashMap<BridgeChainId, u64>,
+    limits: HashMap<BridgeChainId, u64>,
+    paused: bool,
+    governance_nonces: BTreeMap<u8, u64>,
+}
+
+/// The Sui side of the bridge. Registered as the transaction executor of a `SuiMockClient`,
+/// it executes the token transfer transactions submitted by the action executor and keeps the
+/// onchain status reported by the client up to date.
+#[derive(Debug)]
+pub(crate) struct SimulatedSuiChain {
+    committee: Arc<BridgeCommittee>,
+    client: SuiMockClient,
+    state: Mutex<SuiChainState>,
+}
+
+impl SimulatedSuiChain {
+    pub(crate) fn new(committee: Arc<BridgeCommittee>, client: SuiMockClient) -> Arc<Self> {
+        client.set_is_bridge_paused(false);
+        let chain = Arc::new(Self {
+            committee,
+            client: client.clone(),
+            state: Mutex::new(SuiChainState::default()),
+        });
+        client.set_transaction_executor(chain.clone());
+        chain
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.state.lock().unwrap().paused
+    }
+
+    /// Deposits tokens to be bridged to `recipient` on Ethereum, returning the emitted event.
+    pub(crate) fn deposit(
+        &self,
+        token_id: u8,
+        amount: u64,
+        sender: SuiAddress,
+        recipient: EthAddress,
+        module: &Identifier,
+    ) -> BridgeResult<(SuiEvent, SuiToEthBridgeAction)> {
+        let mut state = self.state.lock().unwrap();
+        if state.paused {
+            return Err(BridgeError::Generic("Sui bridge is paused".to_string()));
+        }
+        // Deposits are identified by their nonce, which also gives them a deterministic digest.
+        let mut digest = [0u8; 32];
+        digest[..8].copy_from_slice(&(state.next_nonce + 1).to_be_bytes());
+        let action = SuiToEthBridgeAction {
+            sui_tx_digest: TransactionDigest::new(digest),
+            sui_tx_event_index: 0,
+            sui_bridge_event: EmittedSuiToEthTokenBridgeV1 {
+                nonce: state.next_nonce,
+                sui_chain_id: SIMULATED_SUI_CHAIN_ID,
+                eth_chain_id: SIMULATED_ETH_CHAIN_ID,
+                sui_address: sender,
+                eth_address: recipient,
+                token_id,
+                amount_sui_adjusted: amount,
+            },
+        };
+        state.next_nonce += 1;
+        state.deposits.insert(
+            (action.sui_tx_digest, action.sui_tx_event_index),
+            action.clone(),
+        );
+        let emitted_event = MoveTokenDepositedEvent {
+            seq_num: action.sui_bridge_event.nonce,
+            source_chain: SIMULATED_SUI_CHAIN_ID as u8,
+            sender_address: sender.to_vec(),
+            target_chain: SIMULATED_ETH_CHAIN_ID as u8,
+            target_address: recipient.as_bytes().to_vec(),
+            token_type: token_id,
+            amount_sui_adjusted: amount,
+        };
+        let event = SuiEvent {
+            id: EventID {
+                tx_digest: action.sui_tx_digest,
+                event_seq: action.sui_tx_event_index as u64,
+            },
+            package_id: ObjectID::ZERO,
+            transaction_module: module.clone(),
+            sender,
+            type_: SuiToEthTokenBridgeV1.get().unwrap().clone(),
+            parsed_json: serde_json::json!({}),
+            bcs: bcs::to_bytes(&emitted_event).unwrap(),
+            timestamp_ms: None,
+        };
+        Ok((event, action))
+    }
+
+    /// Returns the deposit at the given position.
+    pub(crate) fn deposit_at(
+        &self,
+        tx_digest: TransactionDigest,
+        event_index: u16,
+    ) -> BridgeResult<SuiToEthBridgeAction> {
+        self.state
+            .lock()
+            .unwrap()
+            .deposits
+            .get(&(tx_digest, event_index))
+            .cloned()
+            .ok_or(BridgeError::NoBridgeEventsInTxPosition)
+    }
+
+    pub(crate) fn deposits(&self) -> Vec<SuiToEthBridgeAction> {
+        self.state
+            .lock()
+            .unwrap()
+            .deposits
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the approved message for every (source chain, nonce).
+    pub(crate) fn approvals(&self) -> BTreeMap<(u8, u64), BridgeAction> {
+        self.state.lock().unwrap().approvals.clone()
+    }
+
+    pub(crate) fn conflicting_approvals(&self) -> Vec<BridgeAction> {
+        self.state.lock().unwrap().conflicting_approvals.clone()
+    }
+
+    pub(crate) fn is_claimed(&self, source_chain: BridgeChainId, nonce: u64) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .claimed
+            .contains(&(source_chain as u8, nonce))
+    }
+
+    pub(crate) fn is_limit_exceeded(&self, source_chain: BridgeChainId, nonce: u64) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .limit_exceeded
+            .contains(&(source_chain as u8, nonce))
+    }
+
+    /// Returns the total USD value claimed from `sending_chain`, and its limit if any.
+    pub(crate) fn claimed_value(&self, sending_chain: BridgeChainId) -> (u64, Option<u64>) {
+        let state = self.state.lock().unwrap();
+        (
+            state
+                .claimed_value
+                .get(&sending_chain)
+                .copied()
+                .unwrap_or_default(),
+            state.limits.get(&sending_chain).copied(),
+        )
+    }
+
+    /// Executes a certified governance action targeting Sui.
+    pub(crate) fn execute_governance_action(
+        &self,
+        certificate: &VerifiedCertifiedBridgeAction,
+    ) -> BridgeResult<()> {
+        let action = certificate.data();
+        if action.chain_id() != SIMULATED_SUI_CHAIN_ID {
+            return Err(BridgeError::Generic(format!(
+                "Action {:?} does not target Sui",
+                action.key()
+            )));
+        }
+        verify_quorum(
+            action,
+            &certificate_signatures(certificate),
+            &self.committee,
+        )?;
+        let mut state = self.state.lock().unwrap();
+        check_governance_nonce(&mut state.governance_nonces, action)?;
+        match action {
+            BridgeAction::EmergencyAction(a) => {
+                state.paused = a.action_type == EmergencyActionType::Pause;
+                self.client.set_is_bridge_paused(state.paused);
+            }
+            BridgeAction::LimitUpdateAction(a) => {
+                state.limits.insert(a.sending_chain_id, a.new_usd_limit);
+            }
+            _ => {
+                return Err(BridgeError::Generic(format!(
+                    "Unsupported governance action {:?}",
+                    action.action_type()
+                )))
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MockTransactionExecutor for SimulatedSuiChain {
+    fn execute(&self, tx: &Transaction) -> BridgeResult<SuiTransactionBlockResponse> {
+        let tx_digest = *tx.digest();
+        let (action, signatures, claim) = decode_token_transfer_transaction(tx)?;
+        let mut state = self.state.lock().unwrap();
+        // The bridge module aborts on any of the below, failing the transaction.
+        if state.paused {
+            return Ok(failed_response(tx_digest, "Bridge is paused"));
+        }
+        if let Err(e) = verify_quorum(&action, &signatures, &self.committee) {
+            return Ok(failed_response(tx_digest, &format!("{:?}", e)));
+        }
+        let key = (action.chain_id() as u8, action.seq_number());
+        let mut events = vec![];
+        match state.approvals.get(&key) {
+            Some(approved) if approved.to_bytes() != action.to_bytes() => {
+                state.conflicting_approvals.push(action);
+                return Ok(failed_response(tx_digest, "Conflicting message"));
+            }
+            Some(_) => {
+                events.push(TokenTransferAlreadyApproved.get().unwrap());
+            }
+            None => {
+                state.approvals.insert(key, action.clone());
+                events.push(TokenTransferApproved.get().unwrap());
+            }
+        }
+
+        if claim {
+            if state.claimed.contains(&key) {
+                events.push(TokenTransferAlreadyClaimed.get().unwrap());
+            } else {
+                let BridgeAction::EthToSuiBridgeAction(transfer) = &action else {
+                    return Ok(failed_response(
+                        tx_digest,
+                        "Only Eth transfers are claimable",
+                    ));
+                };
+                let event = &transfer.eth_bridge_event;
+                let value = notional_value(event.sui_adjusted_amount, DEFAULT_TOKEN_PRICE);
+                let limit = state
+                    .limits
+                    .get(&event.eth_chain_id)
+                    .copied()
+                    .unwrap_or(u64::MAX);
+                let claimed_value = state.claimed_value.entry(event.eth_chain_id).or_default();
+                if claimed_value.saturating_add(value) > limit {
+                    state.limit_exceeded.insert(key);
+                    events.push(TokenTransferLimitExceed.get().unwrap());
+                } else {
+                    *claimed_value += value;
+                    state.limit_exceeded.remove(&key);
+                    state.claimed.insert(key);
+                    events.push(TokenTransferClaimed.get().unwrap());
+                }
+            }
+        }
+
+        let status = if state.claimed.contains(&key) {
+            BridgeActionStatus::Claimed
+        } else {
+            BridgeActionStatus::Approved
+        };
+        self.client.set_action_onchain_status(&action, status);
+
+        let message_key = MoveTypeBridgeMessageKey {
+            source_chain: key.0,
+            message_type: BridgeActionType::TokenTransfer as u8,
+            bridge_seq_num: key.1,
+        };
+        let events = events
+            .into_iter()
+            .enumerate()
+            .map(|(i, type_)| SuiEvent {
+                id: EventID {
+                    tx_digest,
+                    event_seq: i as u64,
+                },
+                package_id: ObjectID::ZERO,
+                transaction_module: sui_types::bridge::BRIDGE_MODULE_NAME.to_owned(),
+                sender: tx.data().transaction_data().sender(),
+                type_: type_.clone(),
+                parsed_json: serde_json::json!({}),
+                bcs: bcs::to_bytes(&message_key).unwrap(),
+                timestamp_ms: None,
+            })
+            .collect();
+        let mut response = transaction_response(tx_digest, SuiExecutionStatus::Success);
+        response.events = Some(SuiTransactionBlockEvents { data: events });
+        Ok(response)
+    }
+}
+
+fn transaction_response(
+    tx_digest: TransactionDigest,
+    status: SuiExecutionStatus,
+) -> SuiTransactionBlockResponse {
+    let mut response = SuiTransactionBlockResponse::new(tx_digest);
+    response.effects = Some(SuiTransactionBlockEffects::new_for_testing(
+        tx_digest, status,
+    ));
+    response.events = Some(SuiTransactionBlockEvents { data: vec![] });
+    response
+}
+
+fn failed_response(tx_digest: TransactionDigest, error: &str) -> SuiTransactionBlockResponse {
+    transaction_response(
+        tx_digest,
+        SuiExecutionStatus::Failure {
+            error: error.to_string(),
+        },
+    )
+}
+
+fn pure_arg<T: DeserializeOwned>(pt: &ProgrammableTransaction, arg: &Argument) -> BridgeResult<T> {
+    let Argument::Input(i) = arg else {
+        return Err(BridgeError::Generic(format!(
+            "Expected an input argument, got {:?}",
+            arg
+        )));
+    };
+    match pt.inputs.get(*i as usize) {
+        Some(CallArg::Pure(bytes)) => bcs::from_bytes(bytes).map_err(|e| {
+            BridgeError::BridgeSerializationError(format!(
+                "Failed to deserialize input {}: {:?}",
+                i, e
+            ))
+        }),
+        input => Err(BridgeError::Generic(format!(
+            "Expected a pure input, got {:?}",
+            input
+        ))),
+    }
+}
+
+/// Decodes a transaction built by `build_sui_transaction` for a token transfer into the
+/// approved message, its signatures and whether the transfer is claimed too.
+fn decode_token_transfer_transaction(
+    tx: &Transaction,
+) -> BridgeResult<(BridgeAction, Vec<Vec<u8>>, bool)> {
+    let TransactionKind::ProgrammableTransaction(pt) = tx.data().transaction_data().kind() else {
+        return Err(BridgeError::Generic(
+            "Expected a programmable transaction".to_string(),
+        ));
+    };
+    let (Some(Command::MoveCall(create_message)), Some(Command::MoveCall(approve))) =
+        (pt.commands.first(), pt.commands.get(1))
+    else {
+        return Err(BridgeError::Generic(
+            "Expected a token transfer approval".to_string(),
+        ));
+    };
+    let [source_chain, nonce, sender, target_chain, target, token_id, amount] =
+        create_message.arguments.as_slice()
+    else {
+        return Err(BridgeError::Generic(
+            "Unexpected token bridge message arguments".to_string(),
+        ));
+    };
+    let source_chain = BridgeChainId::try_from(pure_arg::<u8>(pt, source_chain)?)
+        .map_err(|e| BridgeError::Generic(format!("Invalid source chain: {:?}", e)))?;
+    let target_chain = BridgeChainId::try_from(pure_arg::<u8>(pt, target_chain)?)
+        .map_err(|e| BridgeError::Generic(format!("Invalid target chain: {:?}", e)))?;
+    let nonce = pure_arg::<u64>(pt, nonce)?;
+    let sender = pure_arg::<Vec<u8>>(pt, sender)?;
+    let target = pure_arg::<Vec<u8>>(pt, target)?;
+    let token_id = pure_arg::<u8>(pt, token_id)?;
+    let amount = pure_arg::<u64>(pt, amount)?;
+    let signatures = pure_arg::<Vec<Vec<u8>>>(
+        pt,
+        approve.arguments.get(2).ok_or(BridgeError::Generic(
+            "Missing signatures argument".to_string(),
+        ))?,
+    )?;
+    let claim = pt.commands.len() > 2;
+
+    let invalid_address = |e| BridgeError::Generic(format!("Invalid address: {:?}", e));
+    // The transaction position of the source event is not part of the message.
+    let action = if source_chain == SIMULATED_SUI_CHAIN_ID {
+        BridgeAction::SuiToEthBridgeAction(SuiToEthBridgeAction {
+            sui_tx_digest: TransactionDigest::new([0; 32]),
+            sui_tx_event_index: 0,
+            sui_bridge_event: EmittedSuiToEthTokenBridgeV1 {
+                nonce,
+                sui_chain_id: source_chain,
+                eth_chain_id: target_chain,
+                sui_address: SuiAddress::from_bytes(sender).map_err(invalid_address)?,
+                eth_address: EthAddress::from_slice(&target),
+                token_id,
+                amount_sui_adjusted: amount,
+            },
+        })
+    } else {
+        BridgeAction::EthToSuiBridgeAction(EthToSuiBridgeAction {
+            eth_tx_hash: TxHash::zero(),
+            eth_event_index: 0,
+            eth_bridge_event: EthToSuiTokenBridgeV1 {
+                nonce,
+                sui_chain_id: target_chain,
+                eth_chain_id: source_chain,
+                sui_address: SuiAddress::from_bytes(target).map_err(invalid_address)?,
+                eth_address: EthAddress::from_slice(&sender),
+                token_id,
+                sui_adjusted_amount: amount,
+            },
+        })
+    };
+    Ok((action, signatures, claim))
+}
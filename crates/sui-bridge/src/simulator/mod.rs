@@ -0,0 +1,707 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A deterministic simulator replaying scripted bridge activity through a bridge node.
+//!
+//! The simulator runs the node's `BridgeOrchestrator`, `BridgeActionExecutor` and
+//! `BridgeAuthorityAggregator` against simulated Ethereum and Sui chains and a committee of
+//! simulated authorities. Scripts are JSON lists of `SimulationStep`s, see the `scripts`
+//! directory, which drive both chains and inject authority failures, node restarts and Ethereum
+//! reorgs. `Simulator::assert_invariants` then checks that no conflicting messages were approved,
+//! that every approval matches a canonical deposit and that transfer limits were enforced.
+//!
+//! Keys and addresses are derived from a seed and authorities are called in process, so a
+//! simulation only depends on its script. The simulator must run with a paused tokio clock,
+//! which `AdvanceTime` and `Settle` steps advance.
+
+mod authority;
+mod chains;
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use ethers::types::Address as EthAddress;
+use fastcrypto::traits::KeyPair;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use sui_json_rpc_types::SuiEvent;
+use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
+use sui_types::crypto::{get_key_pair_from_rng, SuiKeyPair};
+use sui_types::event::EventID;
+use sui_types::gas_coin::GasCoin;
+use sui_types::object::Owner;
+use sui_types::{parse_sui_type_tag, Identifier, TypeTag};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::action_executor::BridgeActionExecutor;
+use crate::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
+use crate::client::bridge_client::BridgeClient;
+use crate::crypto::{BridgeAuthorityKeyPair, BridgeAuthorityPublicKeyBytes};
+use crate::error::BridgeResult;
+use crate::metrics::BridgeMetrics;
+use crate::orchestrator::BridgeOrchestrator;
+use crate::storage::BridgeOrchestratorTables;
+use crate::sui_client::SuiClient;
+use crate::sui_mock_client::SuiMockClient;
+use crate::types::{BridgeAction, BridgeAuthority, BridgeCommittee, EthLog, IsBridgePaused};
+use authority::SimulatedAuthority;
+pub(crate) use chains::{
+    SimulatedEthChain, SimulatedSuiChain, SIMULATED_ETH_CHAIN_ID, SIMULATED_SUI_CHAIN_ID,
+};
+
+// Both are measured on the paused tokio clock, settling takes no wall clock time.
+const SETTLE_TIMEOUT: Duration = Duration::from_secs(600);
+const SETTLE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) enum SimulationStep {
+    /// Deposits tokens on Sui to be bridged to Ethereum.
+    SuiDeposit {
+        token_id: u8,
+        amount: u64,
+    },
+    /// Deposits tokens on Ethereum to be bridged to Sui, in a new block.
+    EthDeposit {
+        token_id: u8,
+        amount: u64,
+    },
+    /// Mines empty blocks on Ethereum.
+    EthBlocks(u64),
+    /// Replaces the given number of latest blocks on Ethereum with empty blocks.
+    EthReorg(u64),
+    /// Approves a governance action on all authorities, certifies it with the committee and
+    /// executes it on its target chain.
+    Governance(BridgeAction),
+    /// Makes the authority at the given index fail all requests.
+    StopAuthority(usize),
+    StartAuthority(usize),
+    /// Restarts the bridge node. Actions that were not completed are resumed from its WAL.
+    RestartNode,
+    /// Lets the bridge node run until it has completed all observed actions.
+    Settle,
+    /// Lets the bridge node run for the given number of milliseconds.
+    AdvanceTime(u64),
+}
+
+/// Parses a script, a JSON list of steps such as `[{"EthBlocks": 3}, "Settle"]`.
+pub(crate) fn parse_script(script: &str) -> Vec<SimulationStep> {
+    serde_json::from_str(script).expect("Invalid simulation script")
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SimulatorConfig {
+    /// Seed of the keys and addresses used in the simulation.
+    pub seed: u64,
+    pub voting_powers: Vec<u64>,
+    pub eth_finality_depth: u64,
+    pub eth_supported_tokens: Vec<u8>,
+    /// When true, Ethereum logs are delivered to the node as soon as they are mined rather
+    /// than once they are finalized, so reorged out deposits are observed by the node too.
+    pub deliver_unfinalized_eth_logs: bool,
+}
+
+impl Default for SimulatorConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            voting_powers: vec![2500, 2500, 2500, 2500],
+            eth_finality_depth: 3,
+            eth_supported_tokens: vec![0, 1, 2, 3, 4],
+            deliver_unfinalized_eth_logs: false,
+        }
+    }
+}
+
+// Handles of a running bridge node.
+struct SimulatedNode {
+    tasks: Vec<JoinHandle<()>>,
+    sui_events_tx: mysten_metrics::metered_channel::Sender<(Identifier, Vec<SuiEvent>)>,
+    eth_events_tx: mysten_metrics::metered_channel::Sender<(EthAddress, u64, Vec<EthLog>)>,
+    bridge_pause_tx: tokio::sync::watch::Sender<IsBridgePaused>,
+}
+
+pub(crate) struct Simulator {
+    config: SimulatorConfig,
+    authorities: Vec<SimulatedAuthority>,
+    eth_chain: Arc<Mutex<SimulatedEthChain>>,
+    sui_chain: Arc<SimulatedSuiChain>,
+    sui_client: Arc<SuiClient<SuiMockClient>>,
+    aggregator: Arc<ArcSwap<BridgeAuthorityAggregator>>,
+    store: Arc<BridgeOrchestratorTables>,
+    sui_key: SuiKeyPair,
+    sui_address: SuiAddress,
+    gas_object_id: ObjectID,
+    sui_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
+    sui_module: Identifier,
+    node: Option<SimulatedNode>,
+    // Latest Ethereum block and Sui event delivered to the node.
+    delivered_eth_block: u64,
+    delivered_sui_event: Option<EventID>,
+    rejected_deposits: usize,
+    governance_results: Vec<(BridgeAction, BridgeResult<()>)>,
+    rng: StdRng,
+    _requested_transactions:
+        tokio::sync::broadcast::Receiver<sui_types::digests::TransactionDigest>,
+    _store_dir: tempfile::TempDir,
+}
+
+impl Simulator {
+    pub(crate) async fn new(config: SimulatorConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let mut members = vec![];
+        let mut keys = vec![];
+        for (i, voting_power) in config.voting_powers.iter().enumerate() {
+            let (_, key): (_, BridgeAuthorityKeyPair) = get_key_pair_from_rng(&mut rng);
+            members.push(BridgeAuthority {
+                sui_address: SuiAddress::generate(&mut rng),
+                pubkey: key.public().clone(),
+                voting_power: *voting_power,
+                // Never connected to, requests are served in process.
+                base_url: format!("http://authority-{i}.simulated"),
+                is_blocklisted: false,
+            });
+            keys.push(key);
+        }
+        let committee = Arc::new(BridgeCommittee::new(members).unwrap());
+
+        let sui_client_mock = SuiMockClient::default();
+        let requested_transactions = sui_client_mock.subscribe_to_requested_transactions();
+        let eth_chain = Arc::new(Mutex::new(SimulatedEthChain::new(
+            committee.clone(),
+            config.eth_finality_depth,
+            config.eth_supported_tokens.clone(),
+        )));
+        let sui_chain = SimulatedSuiChain::new(committee.clone(), sui_client_mock.clone());
+
+        let mut authorities = vec![];
+        let mut clients = BTreeMap::new();
+        for key in keys {
+            let name = BridgeAuthorityPublicKeyBytes::from(key.public());
+            let authority = SimulatedAuthority::new(key, eth_chain.clone(), sui_chain.clone());
+            let client = BridgeClient::new_in_process(
+                name.clone(),
+                committee.clone(),
+                Arc::new(authority.clone()),
+            )
+            .unwrap();
+            clients.insert(name, Arc::new(client));
+            authorities.push(authority);
+        }
+        let aggregator = BridgeAuthorityAggregator {
+            committee,
+            clients: Arc::new(clients),
+            metrics: Arc::new(BridgeMetrics::new_for_testing()),
+            committee_keys_to_names: Arc::new(BTreeMap::new()),
+        };
+
+        let (sui_address, kp): (_, fastcrypto::secp256k1::Secp256k1KeyPair) =
+            get_key_pair_from_rng(&mut rng);
+        let gas_object_ref = (
+            ObjectID::new(rng.gen()),
+            SequenceNumber::new(),
+            ObjectDigest::new([0; 32]),
+        );
+        sui_client_mock.add_gas_object_info(
+            GasCoin::new_for_testing(1_000_000_000_000),
+            gas_object_ref,
+            Owner::AddressOwner(sui_address),
+        );
+        // Every token can be claimed on Sui, including the ones only added on Ethereum later.
+        let sui_token_type_tags = (0..=u8::MAX)
+            .map(|token_id| {
+                let type_tag = format!("0xb::simulated_token::TOKEN{}", token_id);
+                (token_id, parse_sui_type_tag(&type_tag).unwrap())
+            })
+            .collect::<HashMap<_, _>>();
+        let store_dir = tempfile::tempdir().unwrap();
+
+        let mut simulator = Self {
+            config,
+            authorities,
+            eth_chain,
+            sui_chain,
+            sui_client: Arc::new(SuiClient::new_for_testing(sui_client_mock)),
+            aggregator: Arc::new(ArcSwap::new(Arc::new(aggregator))),
+            store: BridgeOrchestratorTables::new(store_dir.path()),
+            sui_key: SuiKeyPair::from(kp),
+            sui_address,
+            gas_object_id: gas_object_ref.0,
+            sui_token_type_tags: Arc::new(ArcSwap::new(Arc::new(sui_token_type_tags))),
+            sui_module: sui_types::bridge::BRIDGE_MODULE_NAME.to_owned(),
+            node: None,
+            delivered_eth_block: 0,
+            delivered_sui_event: None,
+            rejected_deposits: 0,
+            governance_results: vec![],
+            rng,
+            _requested_transactions: requested_transactions,
+            _store_dir: store_dir,
+        };
+        simulator.node = Some(simulator.start_node().await);
+        simulator
+    }
+
+    pub(crate) fn sui_chain(&self) -> &SimulatedSuiChain {
+        &self.sui_chain
+    }
+
+    pub(crate) fn eth_chain(&self) -> std::sync::MutexGuard<'_, SimulatedEthChain> {
+        self.eth_chain.lock().unwrap()
+    }
+
+    /// Number of deposits rejected by either chain, e.g. because the bridge is paused.
+    pub(crate) fn rejected_deposits(&self) -> usize {
+        self.rejected_deposits
+    }
+
+    pub(crate) fn governance_results(&self) -> &[(BridgeAction, BridgeResult<()>)] {
+        &self.governance_results
+    }
+
+    pub(crate) fn pending_actions(&self) -> Vec<BridgeAction> {
+        self.store.get_all_pending_actions().into_values().collect()
+    }
+
+    pub(crate) async fn run(&mut self, steps: Vec<SimulationStep>) {
+        for step in steps {
+            tracing::info!("Running simulation step: {:?}", step);
+            self.run_step(step).await;
+        }
+    }
+
+    pub(crate) async fn run_script(&mut self, script: &str) {
+        self.run(parse_script(script)).await
+    }
+
+    async fn run_step(&mut self, step: SimulationStep) {
+        match step {
+            SimulationStep::SuiDeposit { token_id, amount } => {
+                let sender = SuiAddress::generate(&mut self.rng);
+                let recipient = EthAddress::from(self.rng.gen::<[u8; 20]>());
+                match self
+                    .sui_chain
+                    .deposit(token_id, amount, sender, recipient, &self.sui_module)
+                {
+                    Ok((event, _)) => {
+                        self.delivered_sui_event = Some(event.id);
+                        self.node()
+                            .sui_events_tx
+                            .send((self.sui_module.clone(), vec![event]))
+                            .await
+                            .unwrap();
+                    }
+                    Err(_) => self.rejected_deposits += 1,
+                }
+            }
+            SimulationStep::EthDeposit { token_id, amount } => {
+                let sender = EthAddress::from(self.rng.gen::<[u8; 20]>());
+                let recipient = SuiAddress::generate(&mut self.rng);
+                let result = self
+                    .eth_chain()
+                    .deposit(token_id, amount, sender, recipient);
+                if result.is_err() {
+                    self.rejected_deposits += 1;
+                }
+                self.deliver_eth_logs().await;
+            }
+            SimulationStep::EthBlocks(count) => {
+                self.eth_chain().mine(count);
+                self.deliver_eth_logs().await;
+            }
+            SimulationStep::EthReorg(depth) => {
+                self.eth_chain().reorg(depth);
+                self.deliver_eth_logs().await;
+            }
+            SimulationStep::Governance(action) => {
+                let result = self.execute_governance_action(action.clone()).await;
+                self.governance_results.push((action, result));
+            }
+            SimulationStep::StopAuthority(index) => self.authorities[index].set_down(true),
+            SimulationStep::StartAuthority(index) => self.authorities[index].set_down(false),
+            SimulationStep::RestartNode => {
+                for task in &self.node().tasks {
+                    task.abort();
+                }
+                self.node = Some(self.start_node().await);
+            }
+            SimulationStep::Settle => {
+                let deadline = Instant::now() + SETTLE_TIMEOUT;
+                while !self.is_settled() {
+                    assert!(
+                        Instant::now() < deadline,
+                        "Bridge node did not settle, pending actions: {:?}",
+                        self.pending_actions()
+                    );
+                    tokio::time::sleep(SETTLE_POLL_INTERVAL).await;
+                }
+            }
+            SimulationStep::AdvanceTime(millis) => {
+                tokio::time::sleep(Duration::from_millis(millis)).await
+            }
+        }
+    }
+
+    /// Checks the invariants that must hold whatever the script.
+    pub(crate) fn assert_invariants(&self) {
+        let conflicting_approvals = self.sui_chain.conflicting_approvals();
+        assert!(
+            conflicting_approvals.is_empty(),
+            "Conflicting messages were approved: {:?}",
+            conflicting_approvals
+        );
+
+        let sui_deposits = self.sui_chain.deposits();
+        let eth_chain = self.eth_chain();
+        for ((source_chain, nonce), approved) in self.sui_chain.approvals() {
+            let deposit = if source_chain == SIMULATED_SUI_CHAIN_ID as u8 {
+                sui_deposits
+                    .iter()
+                    .find(|d| d.sui_bridge_event.nonce == nonce)
+                    .map(|d| BridgeAction::SuiToEthBridgeAction(d.clone()))
+            } else {
+                eth_chain
+                    .canonical_deposit(nonce)
+                    .map(|d| BridgeAction::EthToSuiBridgeAction(d.clone()))
+            };
+            assert_eq!(
+                deposit.map(|d| d.to_bytes()),
+                Some(approved.to_bytes()),
+                "Approved message {:?} does not match a canonical deposit",
+                approved
+            );
+        }
+
+        for sending_chain in [SIMULATED_ETH_CHAIN_ID, SIMULATED_SUI_CHAIN_ID] {
+            let (claimed_value, limit) = self.sui_chain.claimed_value(sending_chain);
+            assert!(
+                claimed_value <= limit.unwrap_or(u64::MAX),
+                "Claimed {} from {:?} over the limit {:?}",
+                claimed_value,
+                sending_chain,
+                limit
+            );
+        }
+    }
+
+    fn node(&self) -> &SimulatedNode {
+        self.node.as_ref().expect("Bridge node is started")
+    }
+
+    async fn start_node(&self) -> SimulatedNode {
+        let metrics = Arc::new(BridgeMetrics::new_for_testing());
+        let channel_inflight = |name| {
+            mysten_metrics::get_metrics()
+                .unwrap()
+                .channel_inflight
+                .with_label_values(&[name])
+        };
+        let (sui_events_tx, sui_events_rx) = mysten_metrics::metered_channel::channel(
+            100,
+            &channel_inflight("simulator_sui_events_queue"),
+        );
+        let (eth_events_tx, eth_events_rx) = mysten_metrics::metered_channel::channel(
+            100,
+            &channel_inflight("simulator_eth_events_queue"),
+        );
+        let (sui_monitor_tx, mut sui_monitor_rx) = mysten_metrics::metered_channel::channel(
+            10000,
+            &channel_inflight("simulator_sui_monitor_queue"),
+        );
+        let (eth_monitor_tx, mut eth_monitor_rx) = mysten_metrics::metered_channel::channel(
+            10000,
+            &channel_inflight("simulator_eth_monitor_queue"),
+        );
+        let (bridge_pause_tx, bridge_pause_rx) =
+            tokio::sync::watch::channel(self.sui_chain.is_paused());
+
+        let executor = BridgeActionExecutor::new(
+            self.sui_client.clone(),
+            self.aggregator.clone(),
+            self.store.clone(),
+            self.sui_key.copy(),
+            self.sui_address,
+            self.gas_object_id,
+            self.sui_token_type_tags.clone(),
+            bridge_pause_rx,
+            metrics.clone(),
+        )
+        .await;
+        let orchestrator = BridgeOrchestrator::new(
+            self.sui_client.clone(),
+            sui_events_rx,
+            eth_events_rx,
+            self.store.clone(),
+            sui_monitor_tx,
+            eth_monitor_tx,
+            metrics,
+        );
+        let mut tasks = orchestrator.run(executor).await;
+        // The monitor is not simulated, drop the events sent to it.
+        tasks.push(tokio::spawn(async move {
+            while sui_monitor_rx.recv().await.is_some() {}
+        }));
+        tasks.push(tokio::spawn(async move {
+            while eth_monitor_rx.recv().await.is_some() {}
+        }));
+        SimulatedNode {
+            tasks,
+            sui_events_tx,
+            eth_events_tx,
+            bridge_pause_tx,
+        }
+    }
+
+    // Delivers the logs of the Ethereum blocks mined since the last delivery, up to the latest
+    // finalized block unless unfinalized logs are delivered too.
+    async fn deliver_eth_logs(&mut self) {
+        let (contract_address, end_block, logs) = {
+            let eth_chain = self.eth_chain();
+            let end_block = if self.config.deliver_unfinalized_eth_logs {
+                eth_chain.head()
+            } else {
+                eth_chain.finalized_block()
+            };
+            if end_block <= self.delivered_eth_block {
+                return;
+            }
+            (
+                eth_chain.contract_address(),
+                end_block,
+                eth_chain.logs(self.delivered_eth_block + 1, end_block),
+            )
+        };
+        self.delivered_eth_block = end_block;
+        self.node()
+            .eth_events_tx
+            .send((contract_address, end_block, logs))
+            .await
+            .unwrap();
+    }
+
+    async fn execute_governance_action(&mut self, action: BridgeAction) -> BridgeResult<()> {
+        for authority in &self.authorities {
+            authority.approve_governance_action(action.clone());
+        }
+        let certificate = self
+            .aggregator
+            .load()
+            .request_committee_signatures(action.clone())
+            .await?;
+        if action.chain_id() == SIMULATED_ETH_CHAIN_ID {
+            self.eth_chain().execute_governance_action(&certificate)
+        } else {
+            self.sui_chain.execute_governance_action(&certificate)?;
+            // The node watches the bridge status on Sui to stop executing actions while paused.
+            self.node()
+                .bridge_pause_tx
+                .send_replace(self.sui_chain.is_paused());
+            Ok(())
+        }
+    }
+
+    fn is_settled(&self) -> bool {
+        let eth_cursor = self
+            .store
+            .get_eth_event_cursors(&[self.eth_chain().contract_address()])
+            .unwrap()[0];
+        let sui_cursor = self
+            .store
+            .get_sui_event_cursors(&[self.sui_module.clone()])
+            .unwrap()[0];
+        eth_cursor.unwrap_or_default() == self.delivered_eth_block
+            && sui_cursor == self.delivered_sui_event
+            && self.store.get_all_pending_actions().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::init_all_struct_tags;
+    use crate::types::{EmergencyAction, EmergencyActionType};
+    use prometheus::Registry;
+
+    // One token, with 8 decimal places.
+    const ONE_TOKEN: u64 = 100_000_000;
+
+    async fn setup(config: SimulatorConfig) -> Simulator {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        init_all_struct_tags();
+        Simulator::new(config).await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_transfers_with_authority_down() {
+        let mut simulator = setup(SimulatorConfig::default()).await;
+        simulator
+            .run_script(include_str!("scripts/transfers_with_authority_down.json"))
+            .await;
+        simulator.assert_invariants();
+
+        let approvals = simulator.sui_chain().approvals();
+        assert_eq!(approvals.len(), 4);
+        for nonce in 0..2 {
+            assert!(approvals.contains_key(&(SIMULATED_SUI_CHAIN_ID as u8, nonce)));
+            assert!(simulator
+                .sui_chain()
+                .is_claimed(SIMULATED_ETH_CHAIN_ID, nonce));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reorged_deposit_is_never_approved() {
+        let mut simulator = setup(SimulatorConfig {
+            deliver_unfinalized_eth_logs: true,
+            ..Default::default()
+        })
+        .await;
+        simulator
+            .run_script(include_str!("scripts/unfinalized_deposit.json"))
+            .await;
+        // The node observed the deposit and is waiting for it to be finalized.
+        let pending_actions = simulator.pending_actions();
+        assert_eq!(pending_actions.len(), 1);
+        let BridgeAction::EthToSuiBridgeAction(orphaned) = pending_actions[0].clone() else {
+            panic!("Unexpected pending action: {:?}", pending_actions[0]);
+        };
+
+        // The deposit with the same nonce on the new fork is for another amount.
+        simulator
+            .run_script(include_str!("scripts/reorg_and_redeposit.json"))
+            .await;
+        simulator.assert_invariants();
+
+        assert_eq!(simulator.eth_chain().orphaned_deposits().len(), 1);
+        assert_eq!(simulator.eth_chain().orphaned_deposits()[0], orphaned);
+        let approvals = simulator.sui_chain().approvals();
+        assert_eq!(approvals.len(), 1);
+        let BridgeAction::EthToSuiBridgeAction(approved) =
+            approvals[&(SIMULATED_ETH_CHAIN_ID as u8, 0)].clone()
+        else {
+            panic!("Unexpected approved action: {:?}", approvals);
+        };
+        assert_ne!(approved.eth_tx_hash, orphaned.eth_tx_hash);
+        assert_eq!(approved.eth_bridge_event.sui_adjusted_amount, 3 * ONE_TOKEN);
+        assert!(simulator.pending_actions().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_node_restart_resumes_pending_actions() {
+        let mut simulator = setup(SimulatorConfig::default()).await;
+        simulator
+            .run_script(include_str!("scripts/node_restarts.json"))
+            .await;
+        simulator.assert_invariants();
+
+        assert_eq!(simulator.sui_chain().approvals().len(), 6);
+        for nonce in 0..3 {
+            assert!(simulator
+                .sui_chain()
+                .is_claimed(SIMULATED_ETH_CHAIN_ID, nonce));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_limit_update_is_enforced() {
+        let mut simulator = setup(SimulatorConfig::default()).await;
+        simulator
+            .run_script(include_str!("scripts/limit_update.json"))
+            .await;
+        simulator.assert_invariants();
+
+        assert!(simulator
+            .governance_results()
+            .iter()
+            .all(|(_, result)| result.is_ok()));
+        assert_eq!(
+            simulator.eth_chain().limit(SIMULATED_SUI_CHAIN_ID),
+            Some(20_000)
+        );
+        let sui_chain = simulator.sui_chain();
+        assert!(sui_chain.is_claimed(SIMULATED_ETH_CHAIN_ID, 0));
+        // The second transfer is approved, but can not be claimed over the limit.
+        assert!(sui_chain
+            .approvals()
+            .contains_key(&(SIMULATED_ETH_CHAIN_ID as u8, 1)));
+        assert!(!sui_chain.is_claimed(SIMULATED_ETH_CHAIN_ID, 1));
+        assert!(sui_chain.is_limit_exceeded(SIMULATED_ETH_CHAIN_ID, 1));
+        assert_eq!(
+            sui_chain.claimed_value(SIMULATED_ETH_CHAIN_ID),
+            (10_000, Some(15_000))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_emergency_pause() {
+        let mut simulator = setup(SimulatorConfig::default()).await;
+        simulator
+            .run_script(include_str!("scripts/emergency_pause.json"))
+            .await;
+        simulator.assert_invariants();
+
+        // Deposits on Sui are rejected, the ones from Ethereum wait in the WAL.
+        assert_eq!(simulator.rejected_deposits(), 1);
+        assert!(simulator.sui_chain().approvals().is_empty());
+        assert_eq!(simulator.pending_actions().len(), 1);
+
+        // A replayed pause is rejected.
+        simulator
+            .run(vec![SimulationStep::Governance(
+                BridgeAction::EmergencyAction(EmergencyAction {
+                    nonce: 0,
+                    chain_id: SIMULATED_SUI_CHAIN_ID,
+                    action_type: EmergencyActionType::Pause,
+                }),
+            )])
+            .await;
+        assert!(simulator.governance_results()[1].1.is_err());
+
+        // Skipped actions are resumed when the node restarts after unpausing.
+        simulator
+            .run_script(include_str!("scripts/emergency_unpause.json"))
+            .await;
+        simulator.assert_invariants();
+        assert!(simulator.sui_chain().is_claimed(SIMULATED_ETH_CHAIN_ID, 0));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_add_tokens_on_evm() {
+        let mut simulator = setup(SimulatorConfig::default()).await;
+        simulator
+            .run_script(include_str!("scripts/add_tokens_on_evm.json"))
+            .await;
+        simulator.assert_invariants();
+
+        let new_token_id = 5;
+        assert_eq!(simulator.rejected_deposits(), 1);
+        assert!(simulator.eth_chain().is_token_supported(new_token_id));
+        assert_eq!(simulator.eth_chain().token_price(new_token_id), 20_000);
+        assert!(simulator.sui_chain().is_claimed(SIMULATED_ETH_CHAIN_ID, 0));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_simulation_is_deterministic() {
+        let mut approvals = vec![];
+        for _ in 0..2 {
+            let mut simulator = setup(SimulatorConfig::default()).await;
+            simulator
+                .run_script(include_str!("scripts/transfers_with_authority_down.json"))
+                .await;
+            approvals.push(
+                simulator
+                    .sui_chain()
+                    .approvals()
+                    .into_iter()
+                    .map(|(key, action)| (key, action.to_bytes()))
+                    .collect::<Vec<_>>(),
+            );
+        }
+        assert_eq!(approvals[0], approvals[1]);
+    }
+}
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::{
-    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, IntGauge, IntGaugeVec,
-    Registry,
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry, IntCounterVec, IntGauge, IntGaugeVec, Registry,
 };
 
 #[derive(Clone, Debug)]
@@ -12,6 +12,10 @@ pub struct WatchdogMetrics {
     pub total_supplies: IntGaugeVec,
     pub eth_bridge_paused: IntGauge,
     pub sui_bridge_paused: IntGauge,
+    pub eth_vault_locked_amounts: IntGaugeVec,
+    pub supply_drifts: IntGaugeVec,
+    pub limit_headrooms: IntGaugeVec,
+    pub reconciliation_alerts: IntCounterVec,
 }
 
 impl WatchdogMetrics {
@@ -42,6 +46,34 @@ impl WatchdogMetrics {
                 registry,
             )
             .unwrap(),
+            eth_vault_locked_amounts: register_int_gauge_vec_with_registry!(
+                "bridge_eth_vault_locked_amounts",
+                "Current amounts of tokens locked in eth vault, in Sui decimals",
+                &["token_name"],
+                registry,
+            )
+            .unwrap(),
+            supply_drifts: register_int_gauge_vec_with_registry!(
+                "bridge_supply_drifts",
+                "Current supply minted on Sui minus amount locked on eth, in Sui decimals",
+                &["token_name"],
+                registry,
+            )
+            .unwrap(),
+            limit_headrooms: register_int_gauge_vec_with_registry!(
+                "bridge_limit_headrooms",
+                "Remaining rate limit of each route in the current window, in USD with 8 decimals",
+                &["source_chain", "destination_chain"],
+                registry,
+            )
+            .unwrap(),
+            reconciliation_alerts: register_int_counter_vec_with_registry!(
+                "bridge_reconciliation_alerts",
+                "Total number of raised reconciliation alerts, by alert type",
+                &["alert_type"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 
@@ -15,6 +15,7 @@ use tracing::{error_span, info, Instrument};
 pub mod eth_bridge_status;
 pub mod eth_vault_balance;
 pub mod metrics;
+pub mod reconciliation;
 pub mod sui_bridge_status;
 pub mod total_supplies;
 
@@ -0,0 +1,498 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The BridgeReconciliation observable cross-checks the two sides of the bridge. For each token
+//! native to Eth, the amount locked in the Eth vault should match the supply minted on Sui. For
+//! each route, the remaining rate limit in the current window is tracked. Alerts are raised when
+//! either goes past the configured thresholds, and handed to pluggable notifiers.
+
+use crate::abi::EthERC20;
+use crate::config::ReconciliationConfig;
+use crate::metered_eth_provider::MeteredEthHttpProvier;
+use crate::sui_bridge_watchdog::metrics::WatchdogMetrics;
+use crate::sui_bridge_watchdog::Observable;
+use crate::sui_client::SuiBridgeClient;
+use crate::utils::EthBridgeContracts;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use ethers::providers::Provider;
+use ethers::types::{Address as EthAddress, U256};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use sui_types::bridge::{BridgeChainId, BridgeSummary};
+use sui_types::TypeTag;
+use tokio::time::Duration;
+use tracing::{error, info, warn};
+
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Amount of a token locked on Eth and minted on Sui, both in Sui decimals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenSupply {
+    pub token_name: String,
+    pub locked_on_eth: u64,
+    pub minted_on_sui: u64,
+}
+
+/// Rate limit of a route and the amount used in the current window, in USD with 8 decimals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteHeadroom {
+    pub source: BridgeChainId,
+    pub destination: BridgeChainId,
+    pub limit: u64,
+    pub used: u64,
+}
+
+impl RouteHeadroom {
+    pub fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReconciliationAlert {
+    SupplyDrift {
+        token_name: String,
+        locked_on_eth: u64,
+        minted_on_sui: u64,
+        drift_bps: u64,
+    },
+    LowLimitHeadroom {
+        source: BridgeChainId,
+        destination: BridgeChainId,
+        remaining: u64,
+        limit: u64,
+    },
+}
+
+impl ReconciliationAlert {
+    fn alert_type(&self) -> &'static str {
+        match self {
+            ReconciliationAlert::SupplyDrift { .. } => "supply_drift",
+            ReconciliationAlert::LowLimitHeadroom { .. } => "low_limit_headroom",
+        }
+    }
+
+    /// Identifies the token or route the alert is about, so an ongoing condition is only
+    /// notified once.
+    fn key(&self) -> String {
+        match self {
+            ReconciliationAlert::SupplyDrift { token_name, .. } => {
+                format!("{}:{}", self.alert_type(), token_name)
+            }
+            ReconciliationAlert::LowLimitHeadroom {
+                source,
+                destination,
+                ..
+            } => format!("{}:{}:{}", self.alert_type(), source, destination),
+        }
+    }
+}
+
+/// Compares supplies and route headrooms against the thresholds in `config`.
+pub fn reconcile(
+    supplies: &[TokenSupply],
+    headrooms: &[RouteHeadroom],
+    config: &ReconciliationConfig,
+) -> Vec<ReconciliationAlert> {
+    let mut alerts = vec![];
+    for supply in supplies {
+        let drift = supply.locked_on_eth.abs_diff(supply.minted_on_sui) as u128;
+        let drift_bps = (drift * BPS_DENOMINATOR / supply.locked_on_eth.max(1) as u128)
+            .try_into()
+            .unwrap_or(u64::MAX);
+        if drift_bps > config.supply_drift_threshold_bps {
+            alerts.push(ReconciliationAlert::SupplyDrift {
+                token_name: supply.token_name.clone(),
+                locked_on_eth: supply.locked_on_eth,
+                minted_on_sui: supply.minted_on_sui,
+                drift_bps,
+            });
+        }
+    }
+    for headroom in headrooms {
+        let remaining = headroom.remaining();
+        if (remaining as u128) * BPS_DENOMINATOR
+            < (headroom.limit as u128) * config.min_limit_headroom_bps as u128
+        {
+            alerts.push(ReconciliationAlert::LowLimitHeadroom {
+                source: headroom.source,
+                destination: headroom.destination,
+                remaining,
+                limit: headroom.limit,
+            });
+        }
+    }
+    alerts
+}
+
+#[async_trait]
+pub trait AlertNotifier: Send + Sync {
+    async fn notify(&self, alert: &ReconciliationAlert);
+}
+
+/// Logs alerts at warn level.
+pub struct LogNotifier;
+
+#[async_trait]
+impl AlertNotifier for LogNotifier {
+    async fn notify(&self, alert: &ReconciliationAlert) {
+        warn!("Bridge reconciliation alert: {:?}", alert);
+    }
+}
+
+/// Posts alerts as JSON to a webhook.
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+}
+
+#[async_trait]
+impl AlertNotifier for WebhookNotifier {
+    async fn notify(&self, alert: &ReconciliationAlert) {
+        let result = self
+            .client
+            .post(&self.url)
+            .json(alert)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status());
+        if let Err(e) = result {
+            error!("Error posting reconciliation alert to webhook: {:?}", e);
+        }
+    }
+}
+
+pub struct BridgeReconciliation {
+    sui_client: Arc<SuiBridgeClient>,
+    eth_provider: Arc<Provider<MeteredEthHttpProvier>>,
+    eth_contracts: EthBridgeContracts<MeteredEthHttpProvier>,
+    config: ReconciliationConfig,
+    notifiers: Vec<Box<dyn AlertNotifier>>,
+    metrics: WatchdogMetrics,
+    active_alerts: Mutex<HashSet<String>>,
+}
+
+impl BridgeReconciliation {
+    pub fn new(
+        sui_client: Arc<SuiBridgeClient>,
+        eth_provider: Arc<Provider<MeteredEthHttpProvier>>,
+        eth_contracts: EthBridgeContracts<MeteredEthHttpProvier>,
+        config: ReconciliationConfig,
+        notifiers: Vec<Box<dyn AlertNotifier>>,
+        metrics: WatchdogMetrics,
+    ) -> Self {
+        Self {
+            sui_client,
+            eth_provider,
+            eth_contracts,
+            config,
+            notifiers,
+            metrics,
+            active_alerts: Mutex::new(HashSet::new()),
+        }
+    }
+
+    async fn get_supplies(&self, summary: &BridgeSummary) -> anyhow::Result<Vec<TokenSupply>> {
+        let token_types = self
+            .sui_client
+            .get_token_id_map()
+            .await
+            .map_err(|e| anyhow!("Failed to get token id map: {:?}", e))?;
+        let mut supplies = vec![];
+        for (_, metadata) in &summary.treasury.supported_tokens {
+            // Sui native tokens are locked on Sui rather than on Eth
+            if metadata.native_token {
+                continue;
+            }
+            let token_type = token_types
+                .get(&metadata.id)
+                .ok_or_else(|| anyhow!("No type found for token id {}", metadata.id))?;
+            let minted_on_sui = self
+                .sui_client
+                .sui_client()
+                .coin_read_api()
+                .get_total_supply(token_type.to_canonical_string(true))
+                .await?
+                .value;
+
+            let token_address: EthAddress = self
+                .eth_contracts
+                .config
+                .token_address_of(metadata.id)
+                .call()
+                .await?;
+            let sui_decimals = self
+                .eth_contracts
+                .config
+                .token_sui_decimal_of(metadata.id)
+                .call()
+                .await?;
+            let token_contract = EthERC20::new(token_address, self.eth_provider.clone());
+            let eth_decimals = token_contract.decimals().call().await?;
+            let balance = token_contract
+                .balance_of(self.eth_contracts.vault.address())
+                .call()
+                .await?;
+            let locked_on_eth = if eth_decimals >= sui_decimals {
+                balance / U256::exp10((eth_decimals - sui_decimals) as usize)
+            } else {
+                balance.saturating_mul(U256::exp10((sui_decimals - eth_decimals) as usize))
+            };
+
+            supplies.push(TokenSupply {
+                token_name: token_name(token_type),
+                locked_on_eth: saturating_u64(locked_on_eth),
+                minted_on_sui,
+            });
+        }
+        Ok(supplies)
+    }
+
+    async fn get_headrooms(&self, summary: &BridgeSummary) -> anyhow::Result<Vec<RouteHeadroom>> {
+        let current_hour = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() / 3600;
+
+        // Routes into Sui are limited on Sui
+        let mut headrooms = summary
+            .limiter
+            .transfer_limit
+            .iter()
+            .map(|(source, destination, limit)| {
+                let used = summary
+                    .limiter
+                    .transfer_records
+                    .iter()
+                    .find(|(s, d, _)| s == source && d == destination)
+                    .map(|(_, _, record)| record.window_amount(current_hour))
+                    .unwrap_or_default();
+                RouteHeadroom {
+                    source: *source,
+                    destination: *destination,
+                    limit: *limit,
+                    used,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Routes out of Sui are limited on Eth
+        let sui_chain_id = summary.chain_id;
+        let eth_chain_id: u8 = self.eth_contracts.config.chain_id().call().await?;
+        let limit = self
+            .eth_contracts
+            .limiter
+            .chain_limits(sui_chain_id)
+            .call()
+            .await?;
+        let used = self
+            .eth_contracts
+            .limiter
+            .calculate_window_amount(sui_chain_id)
+            .call()
+            .await?;
+        headrooms.push(RouteHeadroom {
+            source: BridgeChainId::try_from(sui_chain_id)?,
+            destination: BridgeChainId::try_from(eth_chain_id)?,
+            limit,
+            used: saturating_u64(used),
+        });
+        Ok(headrooms)
+    }
+
+    async fn notify(&self, alerts: Vec<ReconciliationAlert>) {
+        let new_alerts = {
+            let mut active_alerts = self.active_alerts.lock().unwrap();
+            let keys = alerts.iter().map(|a| a.key()).collect::<HashSet<_>>();
+            let new_alerts = alerts
+                .into_iter()
+                .filter(|a| !active_alerts.contains(&a.key()))
+                .collect::<Vec<_>>();
+            *active_alerts = keys;
+            new_alerts
+        };
+        for alert in new_alerts {
+            self.metrics
+                .reconciliation_alerts
+                .with_label_values(&[alert.alert_type()])
+                .inc();
+            for notifier in &self.notifiers {
+                notifier.notify(&alert).await;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Observable for BridgeReconciliation {
+    fn name(&self) -> &str {
+        "BridgeReconciliation"
+    }
+
+    async fn observe_and_report(&self) {
+        // Both supplies and headrooms are read from the same snapshot of the bridge object
+        let summary = match self.sui_client.get_bridge_summary().await {
+            Ok(summary) => summary,
+            Err(e) => {
+                error!("Error getting bridge summary for reconciliation: {:?}", e);
+                return;
+            }
+        };
+        let supplies = match self.get_supplies(&summary).await {
+            Ok(supplies) => supplies,
+            Err(e) => {
+                error!("Error getting token supplies for reconciliation: {:?}", e);
+                return;
+            }
+        };
+        let headrooms = match self.get_headrooms(&summary).await {
+            Ok(headrooms) => headrooms,
+            Err(e) => {
+                error!("Error getting route limits for reconciliation: {:?}", e);
+                return;
+            }
+        };
+        for supply in &supplies {
+            self.metrics
+                .eth_vault_locked_amounts
+                .with_label_values(&[&supply.token_name])
+                .set(supply.locked_on_eth as i64);
+            self.metrics
+                .supply_drifts
+                .with_label_values(&[&supply.token_name])
+                .set(supply.minted_on_sui as i64 - supply.locked_on_eth as i64);
+        }
+        for headroom in &headrooms {
+            self.metrics
+                .limit_headrooms
+                .with_label_values(&[
+                    &headroom.source.to_string(),
+                    &headroom.destination.to_string(),
+                ])
+                .set(headroom.remaining() as i64);
+        }
+        info!(
+            "Reconciled supplies: {:?}, route headrooms: {:?}",
+            supplies, headrooms
+        );
+        self.notify(reconcile(&supplies, &headrooms, &self.config))
+            .await;
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(60)
+    }
+}
+
+fn token_name(token_type: &TypeTag) -> String {
+    match token_type {
+        TypeTag::Struct(tag) => tag.name.to_string(),
+        _ => token_type.to_string(),
+    }
+}
+
+fn saturating_u64(value: U256) -> u64 {
+    if value > U256::from(u64::MAX) {
+        u64::MAX
+    } else {
+        value.as_u64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ReconciliationConfig {
+        ReconciliationConfig {
+            supply_drift_threshold_bps: 100,
+            min_limit_headroom_bps: 1000,
+            alert_webhook_url: None,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_supplies() {
+        let supplies = vec![
+            // 0.5% drift, within threshold
+            TokenSupply {
+                token_name: "ETH".to_string(),
+                locked_on_eth: 1_000_000,
+                minted_on_sui: 995_000,
+            },
+            // 2% more minted than locked
+            TokenSupply {
+                token_name: "USDC".to_string(),
+                locked_on_eth: 1_000_000,
+                minted_on_sui: 1_020_000,
+            },
+            // Minted without anything locked
+            TokenSupply {
+                token_name: "USDT".to_string(),
+                locked_on_eth: 0,
+                minted_on_sui: 1,
+            },
+        ];
+        assert_eq!(
+            reconcile(&supplies, &[], &config()),
+            vec![
+                ReconciliationAlert::SupplyDrift {
+                    token_name: "USDC".to_string(),
+                    locked_on_eth: 1_000_000,
+                    minted_on_sui: 1_020_000,
+                    drift_bps: 200,
+                },
+                ReconciliationAlert::SupplyDrift {
+                    token_name: "USDT".to_string(),
+                    locked_on_eth: 0,
+                    minted_on_sui: 1,
+                    drift_bps: 10_000,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reconcile_headrooms() {
+        let headrooms = vec![
+            RouteHeadroom {
+                source: BridgeChainId::EthSepolia,
+                destination: BridgeChainId::SuiTestnet,
+                limit: 1000,
+                used: 900,
+            },
+            RouteHeadroom {
+                source: BridgeChainId::SuiTestnet,
+                destination: BridgeChainId::EthSepolia,
+                limit: 1000,
+                used: 899,
+            },
+            // Used can exceed the limit after the limit is lowered
+            RouteHeadroom {
+                source: BridgeChainId::EthMainnet,
+                destination: BridgeChainId::SuiMainnet,
+                limit: 1000,
+                used: 2000,
+            },
+        ];
+        assert_eq!(
+            reconcile(&[], &headrooms, &config()),
+            vec![ReconciliationAlert::LowLimitHeadroom {
+                source: BridgeChainId::EthMainnet,
+                destination: BridgeChainId::SuiMainnet,
+                remaining: 0,
+                limit: 1000,
+            }]
+        );
+    }
+}
@@ -27,6 +27,12 @@ use sui_types::Identifier;
 use crate::sui_client::SuiClientInner;
 use crate::types::{BridgeAction, BridgeActionStatus, IsBridgePaused};
 
+/// Executes transactions submitted to `SuiMockClient` in place of preset responses, e.g. to
+/// simulate the effects of bridge transactions on chain.
+pub trait MockTransactionExecutor: Send + Sync + std::fmt::Debug {
+    fn execute(&self, tx: &Transaction) -> BridgeResult<SuiTransactionBlockResponse>;
+}
+
 /// Mock client used in test environments.
 #[allow(clippy::type_complexity)]
 #[derive(Clone, Debug)]
@@ -45,6 +51,7 @@ pub struct SuiMockClient {
     onchain_status: Arc<Mutex<HashMap<(u8, u64), BridgeActionStatus>>>,
     bridge_committee_summary: Arc<Mutex<Option<BridgeCommitteeSummary>>>,
     is_paused: Arc<Mutex<Option<IsBridgePaused>>>,
+    transaction_executor: Arc<Mutex<Option<Arc<dyn MockTransactionExecutor>>>>,
     requested_transactions_tx: tokio::sync::broadcast::Sender<TransactionDigest>,
 }
 
@@ -62,6 +69,7 @@ impl SuiMockClient {
             onchain_status: Default::default(),
             bridge_committee_summary: Default::default(),
             is_paused: Default::default(),
+            transaction_executor: Default::default(),
             requested_transactions_tx: tokio::sync::broadcast::channel(10000).0,
         }
     }
@@ -129,6 +137,12 @@ impl SuiMockClient {
         *self.wildcard_transaction_response.lock().unwrap() = Some(response);
     }
 
+    /// Sets the executor used for all transactions without a preset response. It takes
+    /// precedence over the wildcard transaction response.
+    pub fn set_transaction_executor(&self, executor: Arc<dyn MockTransactionExecutor>) {
+        *self.transaction_executor.lock().unwrap() = Some(executor);
+    }
+
     pub fn set_latest_checkpoint_sequence_number(&self, value: u64) {
         self.latest_checkpoint_sequence_number
             .store(value, std::sync::atomic::Ordering::Relaxed);
@@ -272,8 +286,12 @@ impl SuiClientInner for SuiMockClient {
         tx: Transaction,
     ) -> Result<SuiTransactionBlockResponse, BridgeError> {
         self.requested_transactions_tx.send(*tx.digest()).unwrap();
-        match self.transaction_responses.lock().unwrap().get(tx.digest()) {
-            Some(response) => response.clone(),
+        if let Some(response) = self.transaction_responses.lock().unwrap().get(tx.digest()) {
+            return response.clone();
+        }
+        let executor = self.transaction_executor.lock().unwrap().clone();
+        match executor {
+            Some(executor) => executor.execute(&tx),
             None => self
                 .wildcard_transaction_response
                 .lock()
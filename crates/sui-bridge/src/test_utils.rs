@@ -3,6 +3,7 @@
 
 use crate::abi::EthToSuiTokenBridgeV1;
 use crate::eth_mock_provider::EthMockProvider;
+use crate::eth_syncer::EthUnfinalizedHead;
 use crate::events::SuiBridgeEvent;
 use crate::server::mock_handler::run_mock_server;
 use crate::sui_transaction_builder::build_sui_transaction;
@@ -23,7 +24,7 @@ use ethers::abi::{long_signature, ParamType};
 use ethers::types::Address as EthAddress;
 use ethers::types::{
     Block, BlockNumber, Filter, FilterBlockOption, Log, TransactionReceipt, TxHash, ValueOrArray,
-    U64,
+    H256, U64,
 };
 use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::traits::KeyPair;
@@ -179,6 +180,35 @@ pub fn mock_last_finalized_block(mock_provider: &EthMockProvider, block_number:
         .unwrap();
 }
 
+pub fn mock_last_unfinalized_block(
+    mock_provider: &EthMockProvider,
+    head: EthUnfinalizedHead,
+    block_number: u64,
+) {
+    let block = Block::<ethers::types::TxHash> {
+        number: Some(U64::from(block_number)),
+        ..Default::default()
+    };
+    mock_provider
+        .add_response("eth_getBlockByNumber", (head.tag(), false), block)
+        .unwrap();
+}
+
+pub fn mock_block_hash(mock_provider: &EthMockProvider, block_number: u64, hash: H256) {
+    let block = Block::<ethers::types::TxHash> {
+        number: Some(U64::from(block_number)),
+        hash: Some(hash),
+        ..Default::default()
+    };
+    mock_provider
+        .add_response(
+            "eth_getBlockByNumber",
+            (U64::from(block_number), false),
+            block,
+        )
+        .unwrap();
+}
+
 // Mocks eth_getLogs and eth_getTransactionReceipt for the given address and block range.
 // The input log needs to have transaction_hash set.
 pub fn mock_get_logs(
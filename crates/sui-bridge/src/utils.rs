@@ -198,6 +198,7 @@ pub fn generate_bridge_node_config_and_write_to_file(
             eth_bridge_chain_id: BridgeChainId::EthSepolia as u8,
             eth_contracts_start_block_fallback: Some(0),
             eth_contracts_start_block_override: None,
+            eth_unfinalized_head: None,
         },
         approved_governance_actions: vec![],
         run_client,
@@ -213,6 +214,7 @@ pub fn generate_bridge_node_config_and_write_to_file(
                 "0xd0e89b2af5e4910726fbcd8b8dd37bb79b29e5f83f7491bca830e94f7f226d29::eth::ETH"
                     .to_string(),
             )]),
+            reconciliation: None,
         }),
     };
     if run_client {
@@ -206,6 +206,36 @@ pub struct NodeConfig {
     /// By default, write stall is enabled on validators but not on fullnodes.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enable_db_write_stall: Option<bool>,
+
+    /// If set, a sample of dry-run transactions is gas profiled. Only effective when the node
+    /// is built with the `tracing` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dry_run_gas_profile_config: Option<DryRunGasProfileConfig>,
+}
+
+/// Every gas profile writes several files, so dry-runs are sampled and the number of profiles
+/// written by a node is capped.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DryRunGasProfileConfig {
+    /// Path prefix of the profile files.
+    pub path: PathBuf,
+
+    /// Only one in every `sample_rate` dry-runs is profiled.
+    #[serde(default = "default_dry_run_gas_profile_sample_rate")]
+    pub sample_rate: u64,
+
+    /// Profiling stops once this many profiles have been written.
+    #[serde(default = "default_dry_run_gas_profile_max_profiles")]
+    pub max_profiles: u64,
+}
+
+fn default_dry_run_gas_profile_sample_rate() -> u64 {
+    100
+}
+
+fn default_dry_run_gas_profile_max_profiles() -> u64 {
+    1_000
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
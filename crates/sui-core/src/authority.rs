@@ -36,7 +36,7 @@ use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use std::{
     collections::{HashMap, HashSet},
@@ -158,6 +158,7 @@ use crate::state_accumulator::{AccumulatorStore, StateAccumulator, WrappedObject
 use crate::subscription_handler::SubscriptionHandler;
 use crate::transaction_input_loader::TransactionInputLoader;
 use crate::transaction_manager::TransactionManager;
+use crate::transaction_trace::TransactionTracer;
 
 #[cfg(msim)]
 pub use crate::checkpoints::checkpoint_executor::{
@@ -209,6 +210,7 @@ pub mod authority_store_pruner;
 pub mod authority_store_tables;
 pub mod authority_store_types;
 pub mod epoch_start_configuration;
+pub mod execution_time_estimator;
 pub mod shared_object_congestion_tracker;
 pub mod shared_object_version_manager;
 #[cfg(any(test, feature = "test-utils"))]
@@ -322,6 +324,10 @@ pub struct AuthorityMetrics {
     // Tracks the rate of transactions starts execution in execution driver.
     // Similar reason for using a Mutex here as to `txn_ready_rate_tracker`.
     pub execution_rate_tracker: Arc<Mutex<RateTracker>>,
+
+    // Records per-stage latencies of sampled transactions. Shared with the components which
+    // handle each stage, and configured at runtime through the admin interface.
+    pub transaction_tracer: Arc<TransactionTracer>,
 }
 
 // Override default Prom buckets for positive numbers in 0-10M range
@@ -762,6 +768,7 @@ impl AuthorityMetrics {
             execution_queueing_latency: LatencyObserver::new(),
             txn_ready_rate_tracker: Arc::new(Mutex::new(RateTracker::new(Duration::from_secs(10)))),
             execution_rate_tracker: Arc::new(Mutex::new(RateTracker::new(Duration::from_secs(10)))),
+            transaction_tracer: Arc::new(TransactionTracer::default()),
         }
     }
 }
@@ -821,6 +828,9 @@ pub struct AuthorityState {
     pub overload_info: AuthorityOverloadInfo,
 
     pub validator_tx_finalizer: Option<Arc<ValidatorTxFinalizer<NetworkAuthorityClient>>>,
+
+    /// Number of dry-runs seen while gas profiling is enabled, used to sample and cap profiles.
+    dry_run_profile_count: AtomicU64,
 }
 
 /// The authority state encapsulates all state, drives execution, and ensures safety.
@@ -1105,6 +1115,12 @@ impl AuthorityState {
         if !self.overload_info.is_overload.load(Ordering::Relaxed) {
             return Ok(());
         }
+        if self
+            .overload_info
+            .is_exempt_sender(&tx_data.transaction_data().sender())
+        {
+            return Ok(());
+        }
 
         let load_shedding_percentage = self
             .overload_info
@@ -1648,6 +1664,7 @@ impl AuthorityState {
         let transaction_data = &certificate.data().intent_message().value;
         let (kind, signer, gas) = transaction_data.execution_parts();
 
+        let execution_start_time = tokio::time::Instant::now();
         #[allow(unused_mut)]
         let (inner_temp_store, _, mut effects, execution_error_opt) =
             epoch_store.executor().execute_transaction_to_effects(
@@ -1672,6 +1689,7 @@ impl AuthorityState {
                 signer,
                 tx_digest,
             );
+        epoch_store.record_local_execution_time(tx_data.kind(), execution_start_time.elapsed());
 
         fail_point_if!("cp_execution_nondeterminism", || {
             #[cfg(msim)]
@@ -1704,6 +1722,22 @@ impl AuthorityState {
         self.prepare_certificate(&execution_guard, certificate, input_objects, epoch_store)
     }
 
+    /// Gas profiles a sample of dry-runs at function granularity, until the configured number of
+    /// profiles is written. Instructions are not tracked, as they make profiles too large.
+    fn dry_run_profiler_config(&self) -> Option<sui_execution::VMProfilerConfig> {
+        let config = self.config.dry_run_gas_profile_config.as_ref()?;
+        let sample_rate = config.sample_rate.max(1);
+        let count = self.dry_run_profile_count.fetch_add(1, Ordering::Relaxed);
+        if count % sample_rate != 0 || count / sample_rate >= config.max_profiles {
+            return None;
+        }
+        Some(sui_execution::VMProfilerConfig {
+            full_path: config.path.clone(),
+            track_bytecode_instructions: false,
+            use_long_function_name: false,
+        })
+    }
+
     #[instrument(skip_all)]
     #[allow(clippy::type_complexity)]
     pub async fn dry_exec_transaction(
@@ -1830,8 +1864,9 @@ impl AuthorityState {
         let (kind, signer, _) = transaction.execution_parts();
 
         let silent = true;
-        let executor = sui_execution::executor(protocol_config, silent, None)
-            .expect("Creating an executor should not fail here");
+        let executor =
+            sui_execution::executor(protocol_config, silent, self.dry_run_profiler_config())
+                .expect("Creating an executor should not fail here");
 
         let expensive_checks = false;
         let (inner_temp_store, _, effects, _execution_error) = executor
@@ -2016,8 +2051,9 @@ impl AuthorityState {
         let (kind, signer, _) = transaction.execution_parts();
 
         let silent = true;
-        let executor = sui_execution::executor(protocol_config, silent, None)
-            .expect("Creating an executor should not fail here");
+        let executor =
+            sui_execution::executor(protocol_config, silent, self.dry_run_profiler_config())
+                .expect("Creating an executor should not fail here");
 
         let expensive_checks = false;
         let (inner_temp_store, _, effects, _execution_error) = executor
@@ -2901,6 +2937,7 @@ impl AuthorityState {
             config,
             overload_info: AuthorityOverloadInfo::default(),
             validator_tx_finalizer,
+            dry_run_profile_count: AtomicU64::new(0),
         });
 
         // Start a task to execute ready certificates.
@@ -18,6 +18,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use sui_config::node::ExpensiveSafetyCheckConfig;
 use sui_macros::fail_point_arg;
 use sui_types::accumulator::Accumulator;
@@ -50,6 +51,9 @@ use typed_store::{
 
 use super::authority_store_tables::ENV_VAR_LOCKS_BLOCK_CACHE_SIZE;
 use super::epoch_start_configuration::EpochStartConfigTrait;
+use super::execution_time_estimator::{
+    ExecutionTimeEstimator, ExecutionTimeObserver, KeyObservationWrites, KeyObservations,
+};
 use super::shared_object_congestion_tracker::{
     CongestionPerObjectDebt, SharedObjectCongestionTracker,
 };
@@ -89,7 +93,7 @@ use prometheus::IntCounter;
 use std::str::FromStr;
 use sui_execution::{self, Executor};
 use sui_macros::fail_point;
-use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_protocol_config::{Chain, PerObjectCongestionControlMode, ProtocolConfig, ProtocolVersion};
 use sui_storage::mutex_table::{MutexGuard, MutexTable};
 use sui_types::effects::TransactionEffects;
 use sui_types::executable_transaction::{
@@ -101,8 +105,9 @@ use sui_types::messages_checkpoint::{
 };
 use sui_types::messages_consensus::VersionedDkgConfirmation;
 use sui_types::messages_consensus::{
-    check_total_jwk_size, AuthorityCapabilitiesV1, AuthorityCapabilitiesV2, ConsensusTransaction,
-    ConsensusTransactionKey, ConsensusTransactionKind,
+    check_execution_time_observation_size, check_total_jwk_size, AuthorityCapabilitiesV1,
+    AuthorityCapabilitiesV2, ConsensusTransaction, ConsensusTransactionKey,
+    ConsensusTransactionKind, ExecutionTimeObservation, ExecutionTimeObservationKey,
 };
 use sui_types::storage::GetSharedLocks;
 use sui_types::sui_system_state::epoch_start_sui_system_state::{
@@ -381,6 +386,12 @@ pub struct AuthorityPerEpochStore {
     /// aggregator for JWK votes
     jwk_aggregator: Mutex<JwkAggregator>,
 
+    /// Execution time estimates agreed upon through consensus, used by the ExecutionTimeEstimate
+    /// congestion control mode.
+    execution_time_estimator: Mutex<ExecutionTimeEstimator>,
+    /// Execution times measured locally, to be shared through consensus.
+    execution_time_observer: ExecutionTimeObserver,
+
     /// State machine managing randomness DKG and generation.
     randomness_manager: OnceCell<tokio::sync::Mutex<RandomnessManager>>,
     randomness_reporter: OnceCell<RandomnessReporter>,
@@ -614,6 +625,12 @@ pub struct AuthorityEpochTables {
     /// Accumulated per-object debts for congestion control.
     pub(crate) congestion_control_object_debts: DBMap<ObjectID, CongestionPerObjectDebt>,
     pub(crate) congestion_control_randomness_object_debts: DBMap<ObjectID, CongestionPerObjectDebt>,
+
+    /// Latest execution time estimates shared through consensus by each authority, for each of
+    /// the most recently observed keys.
+    pub(crate) execution_time_observations: DBMap<ExecutionTimeObservationKey, KeyObservations>,
+    /// Generation of the latest execution time observation processed from each authority.
+    pub(crate) execution_time_observation_generations: DBMap<AuthorityName, u64>,
 }
 
 fn signed_transactions_table_default_config() -> DBOptions {
@@ -951,6 +968,18 @@ impl AuthorityPerEpochStore {
 
         let jwk_aggregator = Mutex::new(jwk_aggregator);
 
+        let execution_time_estimator = Mutex::new(ExecutionTimeEstimator::new(
+            committee.clone(),
+            tables
+                .execution_time_observation_generations
+                .unbounded_iter()
+                .seek_to_first(),
+            tables
+                .execution_time_observations
+                .unbounded_iter()
+                .seek_to_first(),
+        ));
+
         let s = Arc::new(Self {
             name,
             committee,
@@ -981,6 +1010,8 @@ impl AuthorityPerEpochStore {
             execution_component,
             chain_identifier,
             jwk_aggregator,
+            execution_time_estimator,
+            execution_time_observer: ExecutionTimeObserver::new(),
             randomness_manager: OnceCell::new(),
             randomness_reporter: OnceCell::new(),
         });
@@ -2335,6 +2366,40 @@ impl AuthorityPerEpochStore {
         Ok(result?)
     }
 
+    fn uses_execution_time_estimates(&self) -> bool {
+        self.protocol_config().per_object_congestion_control_mode()
+            == PerObjectCongestionControlMode::ExecutionTimeEstimate
+    }
+
+    /// Record how long this validator took to execute a transaction, for the execution time
+    /// estimates shared through consensus.
+    pub fn record_local_execution_time(&self, kind: &TransactionKind, duration: Duration) {
+        if self.uses_execution_time_estimates() {
+            self.execution_time_observer.record(kind, duration);
+        }
+    }
+
+    /// Returns an observation with the local execution time estimates that changed since they were
+    /// last shared, to be submitted to consensus, or None if there is nothing to share.
+    pub fn take_execution_time_observation(
+        &self,
+        authority: AuthorityName,
+    ) -> Option<ExecutionTimeObservation> {
+        if !self.uses_execution_time_estimates() {
+            return None;
+        }
+        let max_estimates = self
+            .protocol_config()
+            .max_execution_time_observation_estimates_as_option()?;
+        let estimates = self
+            .execution_time_observer
+            .take_changed_estimates(max_estimates as usize);
+        if estimates.is_empty() {
+            return None;
+        }
+        Some(ExecutionTimeObservation::new(authority, estimates))
+    }
+
     fn record_jwk_vote(
         &self,
         output: &mut ConsensusCommitOutput,
@@ -2617,6 +2682,31 @@ impl AuthorityPerEpochStore {
                     return None;
                 }
             }
+            SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                kind: ConsensusTransactionKind::ExecutionTimeObservation(observation),
+                ..
+            }) => {
+                if transaction.sender_authority() != observation.authority {
+                    warn!(
+                        "ExecutionTimeObservation authority {} does not match its author from consensus {}",
+                        observation.authority, transaction.certificate_author_index
+                    );
+                    return None;
+                }
+                let within_size_limits = self
+                    .protocol_config()
+                    .max_execution_time_observation_estimates_as_option()
+                    .is_some_and(|max_estimates| {
+                        check_execution_time_observation_size(observation, max_estimates)
+                    });
+                if !within_size_limits {
+                    warn!(
+                        "{:?} sent execution time observation that exceeded max size",
+                        transaction.sender_authority().concise()
+                    );
+                    return None;
+                }
+            }
             SequencedConsensusTransactionKind::External(ConsensusTransaction {
                 kind: ConsensusTransactionKind::NewJWKFetched(authority, id, jwk),
                 ..
@@ -2861,13 +2951,18 @@ impl AuthorityPerEpochStore {
 
         // We track transaction execution cost separately for regular transactions and transactions using randomness, since
         // they will be in different PendingCheckpoints.
+        // Execution time estimates are snapshotted before the observations in this commit are
+        // processed, so that all validators use the same estimates for the whole commit. Snapshots
+        // share their structure with the estimator, so later updates only copy what they change.
         let tables = self.tables()?;
+        let execution_time_estimates = self.execution_time_estimator.lock().estimates();
         let shared_object_congestion_tracker = SharedObjectCongestionTracker::from_protocol_config(
             &tables,
             self.protocol_config(),
             consensus_commit_info.round,
             false,
             &sequenced_transactions,
+            execution_time_estimates.clone(),
         )?;
         let shared_object_using_randomness_congestion_tracker =
             SharedObjectCongestionTracker::from_protocol_config(
@@ -2876,6 +2971,7 @@ impl AuthorityPerEpochStore {
                 consensus_commit_info.round,
                 true,
                 &sequenced_randomness_transactions,
+                execution_time_estimates,
             )?;
 
         // We always order transactions using randomness last.
@@ -3616,6 +3712,44 @@ impl AuthorityPerEpochStore {
                 }
                 Ok(ConsensusCertificateResult::ConsensusMessage)
             }
+            SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                kind: ConsensusTransactionKind::ExecutionTimeObservation(observation),
+                ..
+            }) => {
+                let authority = observation.authority;
+                if !self.uses_execution_time_estimates() {
+                    debug!(
+                        "Ignoring ExecutionTimeObservation from {:?} because execution time estimates are not used",
+                        authority.concise()
+                    );
+                } else if self
+                    .get_reconfig_state_read_lock_guard()
+                    .should_accept_consensus_certs()
+                {
+                    if let Some(writes) = self
+                        .execution_time_estimator
+                        .lock()
+                        .process_observation(observation)
+                    {
+                        output.insert_execution_time_observation(
+                            authority,
+                            observation.generation,
+                            writes,
+                        );
+                    } else {
+                        debug!(
+                            "Ignoring stale ExecutionTimeObservation from {:?}",
+                            authority.concise()
+                        );
+                    }
+                } else {
+                    debug!(
+                        "Ignoring ExecutionTimeObservation from {:?} because of end of epoch",
+                        authority.concise()
+                    );
+                }
+                Ok(ConsensusCertificateResult::ConsensusMessage)
+            }
             SequencedConsensusTransactionKind::External(ConsensusTransaction {
                 kind: ConsensusTransactionKind::NewJWKFetched(authority, jwk_id, jwk),
                 ..
@@ -4226,6 +4360,7 @@ pub(crate) struct ConsensusCommitOutput {
     // congestion control state
     congestion_control_object_debts: Vec<(ObjectID, u64)>,
     congestion_control_randomness_object_debts: Vec<(ObjectID, u64)>,
+    execution_time_observations: Vec<(AuthorityName, u64, KeyObservationWrites)>,
 }
 
 impl ConsensusCommitOutput {
@@ -4339,6 +4474,16 @@ impl ConsensusCommitOutput {
         self.congestion_control_randomness_object_debts = object_debts;
     }
 
+    fn insert_execution_time_observation(
+        &mut self,
+        authority: AuthorityName,
+        generation: u64,
+        writes: KeyObservationWrites,
+    ) {
+        self.execution_time_observations
+            .push((authority, generation, writes));
+    }
+
     pub fn write_to_batch(
         self,
         epoch_store: &AuthorityPerEpochStore,
@@ -4458,6 +4603,21 @@ impl ConsensusCommitOutput {
                 }),
         )?;
 
+        // Observations are written in consensus order, so later writes overwrite earlier ones.
+        for (authority, generation, writes) in self.execution_time_observations {
+            batch.insert_batch(
+                &tables.execution_time_observation_generations,
+                [(authority, generation)],
+            )?;
+            for (key, observations) in writes {
+                match observations {
+                    Some(observations) => batch
+                        .insert_batch(&tables.execution_time_observations, [(key, observations)])?,
+                    None => batch.delete_batch(&tables.execution_time_observations, [key])?,
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -1,7 +1,6 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::ops::Not;
 use std::sync::Arc;
 use std::{iter, mem, thread};
 
@@ -338,13 +337,7 @@ impl AuthorityStore {
         &self,
         event_digest: &TransactionEventsDigest,
     ) -> Result<Option<TransactionEvents>, TypedStoreError> {
-        let data = self
-            .perpetual_tables
-            .events
-            .safe_range_iter((*event_digest, 0)..=(*event_digest, usize::MAX))
-            .map_ok(|(_, event)| event)
-            .collect::<Result<Vec<_>, TypedStoreError>>()?;
-        Ok(data.is_empty().not().then_some(TransactionEvents { data }))
+        self.perpetual_tables.get_events(event_digest)
     }
 
     pub fn multi_get_events(
@@ -537,12 +530,8 @@ impl AuthorityStore {
     }
 
     pub fn multi_object_exists_by_key(&self, object_keys: &[ObjectKey]) -> SuiResult<Vec<bool>> {
-        Ok(self
-            .perpetual_tables
-            .objects
-            .multi_contains_keys(object_keys.to_vec())?
-            .into_iter()
-            .collect())
+        self.perpetual_tables
+            .multi_object_exists_by_key(object_keys)
     }
 
     fn get_object_ref_prior_to_key(
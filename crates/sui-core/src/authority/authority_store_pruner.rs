@@ -298,7 +298,11 @@ impl AuthorityStorePruner {
         )?;
 
         if let Some(rest_index) = rest_index {
-            rest_index.prune(&checkpoint_content_to_prune)?;
+            rest_index.prune(
+                checkpoint_number,
+                &checkpoint_content_to_prune,
+                effects_to_prune,
+            )?;
         }
         perpetual_batch.write()?;
         checkpoints_batch.write()?;
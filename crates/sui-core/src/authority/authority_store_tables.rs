@@ -8,7 +8,7 @@ use std::path::Path;
 use sui_types::accumulator::Accumulator;
 use sui_types::base_types::SequenceNumber;
 use sui_types::digests::TransactionEventsDigest;
-use sui_types::effects::TransactionEffects;
+use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::storage::MarkerValue;
 use typed_store::metrics::SamplingInterval;
 use typed_store::rocks::util::{empty_compaction_filter, reference_count_merge_operator};
@@ -23,7 +23,7 @@ use crate::authority::authority_store_types::{
     StoreMoveObjectWrapper, StoreObject, StoreObjectPair, StoreObjectValue, StoreObjectWrapper,
 };
 use crate::authority::epoch_start_configuration::EpochStartConfiguration;
-use typed_store::DBMapUtils;
+use typed_store::{DBMapUtils, TypedStoreError};
 
 const ENV_VAR_OBJECTS_BLOCK_CACHE_SIZE: &str = "OBJECTS_BLOCK_CACHE_MB";
 pub(crate) const ENV_VAR_LOCKS_BLOCK_CACHE_SIZE: &str = "LOCKS_BLOCK_CACHE_MB";
@@ -371,6 +371,18 @@ impl AuthorityPerpetualTables {
         Ok(self.effects.get(&effect_digest)?)
     }
 
+    pub fn get_events(
+        &self,
+        event_digest: &TransactionEventsDigest,
+    ) -> Result<Option<TransactionEvents>, TypedStoreError> {
+        let data = self
+            .events
+            .safe_range_iter((*event_digest, 0)..=(*event_digest, usize::MAX))
+            .map(|entry| entry.map(|(_, event)| event))
+            .collect::<Result<Vec<_>, TypedStoreError>>()?;
+        Ok((!data.is_empty()).then_some(TransactionEvents { data }))
+    }
+
     // DEPRECATED as the backing table has been moved to authority_per_epoch_store.
     // Please do not add new accessors/callsites.
     pub fn get_checkpoint_sequence_number(
@@ -380,6 +392,10 @@ impl AuthorityPerpetualTables {
         Ok(self.executed_transactions_to_checkpoint.get(digest)?)
     }
 
+    pub fn multi_object_exists_by_key(&self, object_keys: &[ObjectKey]) -> SuiResult<Vec<bool>> {
+        Ok(self.objects.multi_contains_keys(object_keys)?)
+    }
+
     pub fn get_newer_object_keys(
         &self,
         object: &(ObjectID, SequenceNumber),
@@ -0,0 +1,516 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Execution time estimates used by the `ExecutionTimeEstimate` shared object congestion control
+//! mode.
+//!
+//! Each validator measures how long the transactions it executes take, and periodically shares its
+//! estimates through consensus ([ExecutionTimeObserver]). Every validator aggregates the shared
+//! observations in consensus order into a stake-weighted median per key
+//! ([ExecutionTimeEstimator]), so that all validators agree on the estimated cost of each
+//! transaction, and therefore on which transactions to defer.
+//!
+//! Only a bounded number of keys is tracked, both locally and in the aggregated estimates. The
+//! least recently observed keys are forgotten first, in an order that is persisted with the
+//! observations, so that validators that restart still agree on which keys to forget.
+
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::AuthorityName;
+use sui_types::committee::{Committee, CommitteeTrait, StakeUnit};
+use sui_types::messages_consensus::{ExecutionTimeObservation, ExecutionTimeObservationKey};
+use sui_types::transaction::TransactionKind;
+
+/// Weight given to the latest sample, in the moving average of a key's local execution time.
+const OBSERVATION_SMOOTHING: f64 = 0.2;
+
+/// Relative change in a key's local execution time after which it is shared again.
+const MIN_CHANGE_TO_SHARE: f64 = 0.1;
+
+/// Maximum number of keys whose execution time is tracked locally.
+const MAX_LOCAL_OBSERVATIONS: usize = 10_000;
+
+/// Maximum number of keys with an estimate. Observations of the least recently observed keys are
+/// dropped to make room for new ones.
+const MAX_ESTIMATED_KEYS: usize = 20_000;
+
+/// Measures the execution time of transactions executed by this validator.
+pub struct ExecutionTimeObserver {
+    observations: Mutex<LruCache<ExecutionTimeObservationKey, LocalObservation>>,
+}
+
+struct LocalObservation {
+    /// Moving average of the execution time.
+    average: Duration,
+    /// The estimate that was last shared through consensus.
+    shared: Option<Duration>,
+}
+
+/// Aggregates the execution time observations sequenced by consensus into estimates. Must only be
+/// updated from the consensus handler, so that all validators see the same estimates.
+pub struct ExecutionTimeEstimator {
+    committee: Arc<Committee>,
+    /// Generation of the latest observation processed from each authority.
+    generations: HashMap<AuthorityName, u64>,
+    /// Observations of each key, from the least to the most recently observed key.
+    observations: LruCache<ExecutionTimeObservationKey, KeyObservations>,
+    /// Sequence number of the next key observation.
+    next_sequence: u64,
+    max_keys: usize,
+    estimates: ExecutionTimeEstimates,
+}
+
+/// The observations of a key, as persisted in the epoch tables.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyObservations {
+    /// Sequence number of the latest observation of the key, which orders keys by how recently
+    /// they were observed.
+    pub sequence: u64,
+    /// Latest estimate shared by each authority.
+    pub durations: BTreeMap<AuthorityName, Duration>,
+}
+
+/// Changes to the persisted observations made by processing an observation, in order: keys are
+/// either updated, or removed (None) when they are forgotten.
+pub type KeyObservationWrites = Vec<(ExecutionTimeObservationKey, Option<KeyObservations>)>;
+
+/// Snapshot of the execution time estimates agreed upon through consensus. Snapshots share their
+/// structure, so that taking one and updating the estimates afterwards is cheap.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionTimeEstimates(im::HashMap<ExecutionTimeObservationKey, Duration>);
+
+impl ExecutionTimeObserver {
+    pub fn new() -> Self {
+        Self {
+            observations: Mutex::new(LruCache::new(
+                NonZeroUsize::new(MAX_LOCAL_OBSERVATIONS).unwrap(),
+            )),
+        }
+    }
+
+    /// Record that executing a transaction of `kind` took `duration`. The execution time of
+    /// individual commands is not measured, so it is split evenly between them.
+    pub fn record(&self, kind: &TransactionKind, duration: Duration) {
+        let TransactionKind::ProgrammableTransaction(pt) = kind else {
+            return;
+        };
+        if pt.commands.is_empty() {
+            return;
+        }
+
+        let per_command = duration / pt.commands.len() as u32;
+        let mut observations = self.observations.lock();
+        for command in &pt.commands {
+            let key = ExecutionTimeObservationKey::from_command(command);
+            if let Some(observation) = observations.get_mut(&key) {
+                observation.average = observation.average.mul_f64(1.0 - OBSERVATION_SMOOTHING)
+                    + per_command.mul_f64(OBSERVATION_SMOOTHING);
+            } else {
+                observations.put(
+                    key,
+                    LocalObservation {
+                        average: per_command,
+                        shared: None,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Returns up to `max_estimates` estimates that have never been shared, or that changed
+    /// significantly since they were last shared, most recently used keys first, and marks them as
+    /// shared.
+    pub fn take_changed_estimates(
+        &self,
+        max_estimates: usize,
+    ) -> Vec<(ExecutionTimeObservationKey, Duration)> {
+        let mut observations = self.observations.lock();
+        let mut changed = vec![];
+        for (key, observation) in observations.iter_mut() {
+            if changed.len() >= max_estimates {
+                break;
+            }
+
+            let significant = observation.shared.map_or(true, |shared| {
+                let difference = if observation.average > shared {
+                    observation.average - shared
+                } else {
+                    shared - observation.average
+                };
+                difference.as_secs_f64() > shared.as_secs_f64() * MIN_CHANGE_TO_SHARE
+            });
+
+            if significant {
+                observation.shared = Some(observation.average);
+                changed.push((key.clone(), observation.average));
+            }
+        }
+        changed
+    }
+}
+
+impl Default for ExecutionTimeObserver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExecutionTimeEstimator {
+    /// Create an estimator from the observations that were already processed this epoch.
+    pub fn new(
+        committee: Arc<Committee>,
+        generations: impl IntoIterator<Item = (AuthorityName, u64)>,
+        observations: impl IntoIterator<Item = (ExecutionTimeObservationKey, KeyObservations)>,
+    ) -> Self {
+        let mut estimator = Self {
+            committee,
+            generations: generations.into_iter().collect(),
+            observations: LruCache::unbounded(),
+            next_sequence: 0,
+            max_keys: MAX_ESTIMATED_KEYS,
+            estimates: ExecutionTimeEstimates::default(),
+        };
+
+        // Restore the order in which keys were observed.
+        let mut observations: Vec<_> = observations.into_iter().collect();
+        observations.sort_by_key(|(_, observations)| observations.sequence);
+        for (key, observations) in observations {
+            estimator.next_sequence = observations.sequence + 1;
+            estimator.update_estimate(&key, &observations);
+            estimator.observations.put(key, observations);
+        }
+        estimator
+    }
+
+    /// Process an observation sequenced by consensus, and return the resulting changes to the
+    /// persisted observations. Returns None if the observation is older than one already processed
+    /// from the same authority, in which case it is ignored.
+    pub fn process_observation(
+        &mut self,
+        observation: &ExecutionTimeObservation,
+    ) -> Option<KeyObservationWrites> {
+        let ExecutionTimeObservation {
+            authority,
+            generation,
+            estimates,
+        } = observation;
+
+        if self
+            .generations
+            .get(authority)
+            .is_some_and(|latest| latest >= generation)
+        {
+            return None;
+        }
+        self.generations.insert(*authority, *generation);
+
+        let mut writes = vec![];
+        for (key, duration) in estimates {
+            let mut observations = self.observations.pop(key).unwrap_or_default();
+            observations.sequence = self.next_sequence;
+            self.next_sequence += 1;
+            observations.durations.insert(*authority, *duration);
+
+            self.update_estimate(key, &observations);
+            writes.push((key.clone(), Some(observations.clone())));
+            self.observations.put(key.clone(), observations);
+
+            while self.observations.len() > self.max_keys {
+                let Some((forgotten, _)) = self.observations.pop_lru() else {
+                    break;
+                };
+                self.estimates.0.remove(&forgotten);
+                writes.push((forgotten, None));
+            }
+        }
+        Some(writes)
+    }
+
+    pub fn estimates(&self) -> ExecutionTimeEstimates {
+        self.estimates.clone()
+    }
+
+    fn update_estimate(
+        &mut self,
+        key: &ExecutionTimeObservationKey,
+        observations: &KeyObservations,
+    ) {
+        match stake_weighted_median(&self.committee, &observations.durations) {
+            Some(estimate) => self.estimates.0.insert(key.clone(), estimate),
+            None => self.estimates.0.remove(key),
+        };
+    }
+}
+
+impl ExecutionTimeEstimates {
+    /// Estimated execution time of a transaction of `kind`, in microseconds: the sum of the
+    /// estimates for its commands, where commands without an estimate cost
+    /// `default_command_cost_us`.
+    pub fn transaction_cost(&self, kind: &TransactionKind, default_command_cost_us: u64) -> u64 {
+        let TransactionKind::ProgrammableTransaction(pt) = kind else {
+            return default_command_cost_us;
+        };
+
+        pt.commands
+            .iter()
+            .map(|command| {
+                self.0
+                    .get(&ExecutionTimeObservationKey::from_command(command))
+                    .map_or(default_command_cost_us, |estimate| {
+                        estimate.as_micros() as u64
+                    })
+            })
+            .fold(0, u64::saturating_add)
+    }
+}
+
+/// The median of `observations`, weighted by the stake of the authorities that made them. Keys
+/// only get an estimate once authorities with at least a quorum of stake observed them, so that
+/// the median always falls within the range of estimates of honest authorities.
+fn stake_weighted_median(
+    committee: &Committee,
+    observations: &BTreeMap<AuthorityName, Duration>,
+) -> Option<Duration> {
+    let mut weighted: Vec<(Duration, StakeUnit)> = observations
+        .iter()
+        .map(|(authority, duration)| (*duration, committee.weight(authority)))
+        .collect();
+
+    let total: StakeUnit = weighted.iter().map(|(_, stake)| stake).sum();
+    if total < committee.quorum_threshold() {
+        return None;
+    }
+
+    weighted.sort();
+    let mut accumulated = 0;
+    for (duration, stake) in weighted {
+        accumulated += stake;
+        if 2 * accumulated >= total {
+            return Some(duration);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sui_types::base_types::ObjectID;
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::Identifier;
+
+    fn move_call(package: ObjectID) -> ExecutionTimeObservationKey {
+        ExecutionTimeObservationKey::MoveEntryPoint {
+            package,
+            module: "m".to_string(),
+            function: "f".to_string(),
+        }
+    }
+
+    /// Observations as they would be persisted by `estimator`.
+    fn stored(
+        estimator: &ExecutionTimeEstimator,
+    ) -> Vec<(ExecutionTimeObservationKey, KeyObservations)> {
+        estimator
+            .observations
+            .iter()
+            .map(|(key, observations)| (key.clone(), observations.clone()))
+            .collect()
+    }
+
+    fn observation(
+        authority: AuthorityName,
+        generation: u64,
+        estimates: Vec<(ExecutionTimeObservationKey, Duration)>,
+    ) -> ExecutionTimeObservation {
+        ExecutionTimeObservation {
+            authority,
+            generation,
+            estimates,
+        }
+    }
+
+    #[test]
+    fn test_estimates_are_stake_weighted_medians() {
+        let (committee, _) = Committee::new_simple_test_committee_of_size(4);
+        let authorities: Vec<_> = committee.names().copied().collect();
+        let mut estimator = ExecutionTimeEstimator::new(Arc::new(committee), [], []);
+        let key = move_call(ObjectID::random());
+
+        // Less than a quorum of authorities is not enough to set an estimate.
+        for (authority, micros) in authorities[..2].iter().zip([100, 10]) {
+            assert!(estimator
+                .process_observation(&observation(
+                    *authority,
+                    1,
+                    vec![(key.clone(), Duration::from_micros(micros))],
+                ))
+                .is_some());
+        }
+        assert_eq!(estimator.estimates(), ExecutionTimeEstimates::default());
+
+        assert!(estimator
+            .process_observation(&observation(
+                authorities[2],
+                1,
+                vec![(key.clone(), Duration::from_micros(1000))],
+            ))
+            .is_some());
+        assert_eq!(
+            estimator.estimates().0.get(&key),
+            Some(&Duration::from_micros(100))
+        );
+
+        assert!(estimator
+            .process_observation(&observation(
+                authorities[3],
+                1,
+                vec![(key.clone(), Duration::from_micros(50))],
+            ))
+            .is_some());
+        assert_eq!(
+            estimator.estimates().0.get(&key),
+            Some(&Duration::from_micros(50))
+        );
+
+        // Stale observations are ignored.
+        assert!(estimator
+            .process_observation(&observation(
+                authorities[3],
+                1,
+                vec![(key.clone(), Duration::from_micros(5000))],
+            ))
+            .is_none());
+        assert_eq!(
+            estimator.estimates().0.get(&key),
+            Some(&Duration::from_micros(50))
+        );
+
+        // Estimator rebuilt from stored observations agrees.
+        let rebuilt = ExecutionTimeEstimator::new(
+            estimator.committee.clone(),
+            estimator.generations.clone(),
+            stored(&estimator),
+        );
+        assert_eq!(rebuilt.estimates(), estimator.estimates());
+    }
+
+    #[test]
+    fn test_least_recently_observed_keys_are_forgotten() {
+        let (committee, _) = Committee::new_simple_test_committee_of_size(1);
+        let authority = *committee.names().next().unwrap();
+        let mut estimator = ExecutionTimeEstimator::new(Arc::new(committee), [], []);
+        estimator.max_keys = 2;
+
+        let keys: Vec<_> = (0..3).map(|_| move_call(ObjectID::random())).collect();
+        let estimate = |key: &ExecutionTimeObservationKey| (key.clone(), Duration::from_micros(10));
+
+        let writes = estimator
+            .process_observation(&observation(authority, 1, vec![estimate(&keys[0])]))
+            .unwrap();
+        assert_eq!(writes.len(), 1);
+        estimator
+            .process_observation(&observation(authority, 2, vec![estimate(&keys[1])]))
+            .unwrap();
+        // Observing the first key again makes the second one the least recently observed.
+        estimator
+            .process_observation(&observation(authority, 3, vec![estimate(&keys[0])]))
+            .unwrap();
+
+        let writes = estimator
+            .process_observation(&observation(authority, 4, vec![estimate(&keys[2])]))
+            .unwrap();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes[0].0, keys[2]);
+        assert_eq!(writes[1], (keys[1].clone(), None));
+
+        let estimates = estimator.estimates();
+        assert!(estimates.0.contains_key(&keys[0]));
+        assert!(!estimates.0.contains_key(&keys[1]));
+        assert!(estimates.0.contains_key(&keys[2]));
+
+        // A rebuilt estimator forgets keys in the same order.
+        let mut rebuilt = ExecutionTimeEstimator::new(
+            estimator.committee.clone(),
+            estimator.generations.clone(),
+            stored(&estimator),
+        );
+        rebuilt.max_keys = 2;
+        let fourth = move_call(ObjectID::random());
+        let expected = estimator
+            .process_observation(&observation(authority, 5, vec![estimate(&fourth)]))
+            .unwrap();
+        let actual = rebuilt
+            .process_observation(&observation(authority, 5, vec![estimate(&fourth)]))
+            .unwrap();
+        assert_eq!(actual, expected);
+        assert_eq!(actual[1], (keys[0].clone(), None));
+        assert_eq!(rebuilt.estimates(), estimator.estimates());
+    }
+
+    #[test]
+    fn test_transaction_cost() {
+        let (committee, _) = Committee::new_simple_test_committee_of_size(1);
+        let authority = *committee.names().next().unwrap();
+        let mut estimator = ExecutionTimeEstimator::new(Arc::new(committee), [], []);
+
+        let observed = ObjectID::random();
+        estimator.process_observation(&observation(
+            authority,
+            1,
+            vec![(move_call(observed), Duration::from_micros(300))],
+        ));
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        for package in [observed, observed, ObjectID::random()] {
+            builder.programmable_move_call(
+                package,
+                Identifier::new("m").unwrap(),
+                Identifier::new("f").unwrap(),
+                vec![],
+                vec![],
+            );
+        }
+        let kind = TransactionKind::ProgrammableTransaction(builder.finish());
+
+        assert_eq!(
+            estimator.estimates().transaction_cost(&kind, 1000),
+            300 + 300 + 1000
+        );
+    }
+
+    #[test]
+    fn test_observer_shares_significant_changes() {
+        let observer = ExecutionTimeObserver::new();
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let package = ObjectID::random();
+        builder.programmable_move_call(
+            package,
+            Identifier::new("m").unwrap(),
+            Identifier::new("f").unwrap(),
+            vec![],
+            vec![],
+        );
+        let kind = TransactionKind::ProgrammableTransaction(builder.finish());
+
+        observer.record(&kind, Duration::from_micros(100));
+        assert_eq!(
+            observer.take_changed_estimates(10),
+            vec![(move_call(package), Duration::from_micros(100))]
+        );
+
+        // Small changes are not shared again.
+        observer.record(&kind, Duration::from_micros(110));
+        assert!(observer.take_changed_estimates(10).is_empty());
+
+        observer.record(&kind, Duration::from_micros(1000));
+        assert_eq!(observer.take_changed_estimates(10).len(), 1);
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::authority_per_epoch_store::AuthorityEpochTables;
+use super::execution_time_estimator::ExecutionTimeEstimates;
 use crate::authority::transaction_deferral::DeferralKey;
 use crate::consensus_handler::VerifiedSequencedConsensusTransaction;
 use narwhal_types::Round;
@@ -25,7 +26,8 @@ use tracing::trace;
 // objects.
 //
 // The mode field determines how the cost is calculated. The cost can be calculated based on the total gas
-// budget, or total number of transaction count.
+// budget, total number of transaction count, or the execution time of the transaction estimated from
+// validators' observations.
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct SharedObjectCongestionTracker {
     object_execution_cost: HashMap<ObjectID, u64>,
@@ -34,6 +36,8 @@ pub struct SharedObjectCongestionTracker {
     gas_budget_based_txn_cost_cap_factor: Option<u64>,
     gas_budget_based_txn_cost_absolute_cap: Option<u64>,
     max_txn_cost_overage_per_object_in_commit: u64,
+    execution_time_estimates: ExecutionTimeEstimates,
+    execution_time_estimate_default_command_cost_us: Option<u64>,
 }
 
 impl SharedObjectCongestionTracker {
@@ -75,15 +79,30 @@ impl SharedObjectCongestionTracker {
             gas_budget_based_txn_cost_cap_factor,
             gas_budget_based_txn_cost_absolute_cap,
             max_txn_cost_overage_per_object_in_commit,
+            execution_time_estimates: ExecutionTimeEstimates::default(),
+            execution_time_estimate_default_command_cost_us: None,
         }
     }
 
+    // Sets the execution time estimates used as transaction cost in ExecutionTimeEstimate mode.
+    // Transactions are charged `default_command_cost_us` for each command without an estimate.
+    pub fn with_execution_time_estimates(
+        mut self,
+        estimates: ExecutionTimeEstimates,
+        default_command_cost_us: Option<u64>,
+    ) -> Self {
+        self.execution_time_estimates = estimates;
+        self.execution_time_estimate_default_command_cost_us = default_command_cost_us;
+        self
+    }
+
     pub fn from_protocol_config(
         tables: &AuthorityEpochTables,
         protocol_config: &ProtocolConfig,
         round: Round,
         for_randomness: bool,
         transactions: &[VerifiedSequencedConsensusTransaction],
+        execution_time_estimates: ExecutionTimeEstimates,
     ) -> SuiResult<Self> {
         let max_accumulated_txn_cost_per_object_in_commit =
             protocol_config.max_accumulated_txn_cost_per_object_in_mysticeti_commit_as_option();
@@ -107,6 +126,10 @@ impl SharedObjectCongestionTracker {
             protocol_config
                 .max_txn_cost_overage_per_object_in_commit_as_option()
                 .unwrap_or(0),
+        )
+        .with_execution_time_estimates(
+            execution_time_estimates,
+            protocol_config.execution_time_estimate_default_command_cost_us_as_option(),
         ))
     }
 
@@ -131,6 +154,16 @@ impl SharedObjectCongestionTracker {
             PerObjectCongestionControlMode::TotalGasBudgetWithCap => {
                 Some(std::cmp::min(cert.gas_budget(), self.get_tx_cost_cap(cert)))
             }
+            PerObjectCongestionControlMode::ExecutionTimeEstimate => {
+                let cost = self.execution_time_estimates.transaction_cost(
+                    cert.transaction_data().kind(),
+                    self.execution_time_estimate_default_command_cost_us.expect(
+                        "default command cost must be set if ExecutionTimeEstimate mode is used.",
+                    ),
+                );
+                // Every transaction must advance the cost of the objects it writes.
+                Some(cost.max(1))
+            }
         }
     }
 
@@ -462,14 +495,16 @@ mod object_cost_tests {
 
         // Set max_accumulated_txn_cost_per_object_in_commit to only allow 1 transaction to go through.
         let max_accumulated_txn_cost_per_object_in_commit = match mode {
-            PerObjectCongestionControlMode::None => unreachable!(),
+            PerObjectCongestionControlMode::None
+            | PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => tx_gas_budget + 1,
             PerObjectCongestionControlMode::TotalTxCount => 2,
             PerObjectCongestionControlMode::TotalGasBudgetWithCap => tx_gas_budget - 1,
         };
 
         let shared_object_congestion_tracker = match mode {
-            PerObjectCongestionControlMode::None => unreachable!(),
+            PerObjectCongestionControlMode::None
+            | PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => {
                 // Construct object execution cost as following
                 //                1     10
@@ -679,14 +714,16 @@ mod object_cost_tests {
         // Set max_accumulated_txn_cost_per_object_in_commit to only allow 1 transaction to go through
         // before overage occurs.
         let max_accumulated_txn_cost_per_object_in_commit = match mode {
-            PerObjectCongestionControlMode::None => unreachable!(),
+            PerObjectCongestionControlMode::None
+            | PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => tx_gas_budget + 1,
             PerObjectCongestionControlMode::TotalTxCount => 2,
             PerObjectCongestionControlMode::TotalGasBudgetWithCap => tx_gas_budget - 1,
         };
 
         let shared_object_congestion_tracker = match mode {
-            PerObjectCongestionControlMode::None => unreachable!(),
+            PerObjectCongestionControlMode::None
+            | PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => {
                 // Construct object execution cost as following
                 //                90    102
@@ -816,7 +853,8 @@ mod object_cost_tests {
         let cert = build_transaction(&[(object_id_0, true), (object_id_1, false)], 10);
         shared_object_congestion_tracker.bump_object_execution_cost(&cert);
         let expected_object_0_cost = match mode {
-            PerObjectCongestionControlMode::None => unreachable!(),
+            PerObjectCongestionControlMode::None
+            | PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => 20,
             PerObjectCongestionControlMode::TotalTxCount => 11,
             PerObjectCongestionControlMode::TotalGasBudgetWithCap => 13, // 2 objects, 1 command.
@@ -847,7 +885,8 @@ mod object_cost_tests {
             10,
         );
         let expected_object_cost = match mode {
-            PerObjectCongestionControlMode::None => unreachable!(),
+            PerObjectCongestionControlMode::None
+            | PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => 30,
             PerObjectCongestionControlMode::TotalTxCount => 12,
             PerObjectCongestionControlMode::TotalGasBudgetWithCap => 17, // 3 objects, 1 command
@@ -884,7 +923,8 @@ mod object_cost_tests {
             30,
         );
         let expected_object_cost = match mode {
-            PerObjectCongestionControlMode::None => unreachable!(),
+            PerObjectCongestionControlMode::None
+            | PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => 60,
             PerObjectCongestionControlMode::TotalTxCount => 13,
             PerObjectCongestionControlMode::TotalGasBudgetWithCap => 45, // 3 objects, 7 commands
@@ -931,14 +971,16 @@ mod object_cost_tests {
         // Set max_accumulated_txn_cost_per_object_in_commit to only allow 1 transaction to go through
         // before overage occurs.
         let max_accumulated_txn_cost_per_object_in_commit = match mode {
-            PerObjectCongestionControlMode::None => unreachable!(),
+            PerObjectCongestionControlMode::None
+            | PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget
             | PerObjectCongestionControlMode::TotalGasBudgetWithCap => 90,
             PerObjectCongestionControlMode::TotalTxCount => 2,
         };
 
         let mut shared_object_congestion_tracker = match mode {
-            PerObjectCongestionControlMode::None => unreachable!(),
+            PerObjectCongestionControlMode::None
+            | PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => {
                 // Starting with two objects with accumulated cost 80.
                 SharedObjectCongestionTracker::new(
@@ -984,7 +1026,8 @@ mod object_cost_tests {
         let accumulated_debts = shared_object_congestion_tracker.accumulated_debts();
         assert_eq!(accumulated_debts.len(), 1);
         match mode {
-            PerObjectCongestionControlMode::None => unreachable!(),
+            PerObjectCongestionControlMode::None
+            | PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => {
                 assert_eq!(accumulated_debts[0], (shared_obj_0, 90)); // init 80 + cost 100 - budget 90 = 90
             }
@@ -1058,4 +1101,106 @@ mod object_cost_tests {
         assert_eq!(accumulated_debts.len(), 1);
         assert_eq!(accumulated_debts[0], (object_id_2, 200));
     }
+
+    #[test]
+    fn test_execution_time_estimate_cost() {
+        use crate::authority::execution_time_estimator::ExecutionTimeEstimator;
+        use std::sync::Arc;
+        use std::time::Duration;
+        use sui_types::committee::Committee;
+        use sui_types::messages_consensus::{
+            ExecutionTimeObservation, ExecutionTimeObservationKey,
+        };
+
+        let shared_obj = ObjectID::random();
+        let cheap_package = ObjectID::random();
+        let expensive_package = ObjectID::random();
+
+        let (committee, _) = Committee::new_simple_test_committee_of_size(1);
+        let authority = *committee.names().next().unwrap();
+        let mut estimator = ExecutionTimeEstimator::new(Arc::new(committee), [], []);
+        estimator.process_observation(&ExecutionTimeObservation {
+            authority,
+            generation: 1,
+            estimates: [(cheap_package, 10), (expensive_package, 1000)]
+                .into_iter()
+                .map(|(package, micros)| {
+                    (
+                        ExecutionTimeObservationKey::MoveEntryPoint {
+                            package,
+                            module: "unimportant_module".to_string(),
+                            function: "unimportant_function".to_string(),
+                        },
+                        Duration::from_micros(micros),
+                    )
+                })
+                .collect(),
+        });
+
+        let build_call = |package: ObjectID, gas_budget: u64| {
+            let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+            let mut pt_builder = ProgrammableTransactionBuilder::new();
+            let argument = pt_builder
+                .obj(ObjectArg::SharedObject {
+                    id: shared_obj,
+                    initial_shared_version: SequenceNumber::new(),
+                    mutable: true,
+                })
+                .unwrap();
+            pt_builder.programmable_move_call(
+                package,
+                Identifier::new("unimportant_module").unwrap(),
+                Identifier::new("unimportant_function").unwrap(),
+                vec![],
+                vec![argument],
+            );
+            VerifiedExecutableTransaction::new_system(
+                VerifiedTransaction::new_unchecked(
+                    TestTransactionBuilder::new(sender, random_object_ref(), 1000)
+                        .with_gas_budget(gas_budget)
+                        .programmable(pt_builder.finish())
+                        .build_and_sign(&keypair),
+                ),
+                0,
+            )
+        };
+
+        let mut shared_object_congestion_tracker = SharedObjectCongestionTracker::new(
+            [],
+            PerObjectCongestionControlMode::ExecutionTimeEstimate,
+            Some(500),
+            None,
+            None,
+            0,
+        )
+        .with_execution_time_estimates(estimator.estimates(), Some(100));
+
+        // Cost follows the estimated execution time, regardless of the gas budget.
+        let cheap_tx = build_call(cheap_package, 1_000_000_000);
+        let expensive_tx = build_call(expensive_package, 1);
+        let unobserved_tx = build_call(ObjectID::random(), 1);
+        assert_eq!(
+            shared_object_congestion_tracker.get_tx_cost(&cheap_tx),
+            Some(10)
+        );
+        assert_eq!(
+            shared_object_congestion_tracker.get_tx_cost(&expensive_tx),
+            Some(1000)
+        );
+        assert_eq!(
+            shared_object_congestion_tracker.get_tx_cost(&unobserved_tx),
+            Some(100)
+        );
+
+        assert!(shared_object_congestion_tracker
+            .should_defer_due_to_object_congestion(&expensive_tx, &HashMap::new(), 0)
+            .is_some());
+        for _ in 0..50 {
+            assert!(shared_object_congestion_tracker
+                .should_defer_due_to_object_congestion(&cheap_tx, &HashMap::new(), 0)
+                .is_none());
+            shared_object_congestion_tracker.bump_object_execution_cost(&cheap_tx);
+        }
+        assert_eq!(shared_object_congestion_tracker.max_cost(), 500);
+    }
 }
@@ -58,6 +58,7 @@ use crate::{
     traffic_controller::parse_ip,
     traffic_controller::policies::TrafficTally,
     traffic_controller::TrafficController,
+    transaction_trace::TransactionStage,
 };
 use crate::{
     consensus_adapter::ConnectionMonitorStatusForTests,
@@ -423,6 +424,8 @@ impl ValidatorService {
             client_id_source: _,
         } = self.clone();
         let transaction = request.into_inner();
+        let transaction_tracer = state.metrics.transaction_tracer.clone();
+        transaction_tracer.record(transaction.digest(), TransactionStage::TransactionReceived);
         let epoch_store = state.load_epoch_store_one_call_per_task();
 
         transaction.validity_check(epoch_store.protocol_config(), epoch_store.epoch())?;
@@ -476,6 +479,7 @@ impl ValidatorService {
                     metrics.num_rejected_tx_in_epoch_boundary.inc();
                 }
             })?;
+        transaction_tracer.record(tx_digest, TransactionStage::TransactionSigned);
 
         if let Some(error) = validator_pushback_error {
             // TODO: right now, we still sign the txn, but just don't return it. We can also skip signing
@@ -512,6 +516,8 @@ impl ValidatorService {
             include_output_objects,
             include_auxiliary_data,
         } = request.into_inner();
+        let transaction_tracer = state.metrics.transaction_tracer.clone();
+        transaction_tracer.record(transaction.digest(), TransactionStage::TransactionReceived);
 
         transaction.validity_check(epoch_store.protocol_config(), epoch_store.epoch())?;
 
@@ -571,6 +577,8 @@ impl ValidatorService {
             ));
         }
 
+        transaction_tracer.record(tx_digest, TransactionStage::TransactionSigned);
+
         let _latency_metric_guard = metrics.handle_transaction_consensus_latency.start_timer();
         let span = error_span!("handle_transaction_v2", tx_digest = ?transaction.digest());
         self.handle_submit_to_consensus(
@@ -621,6 +629,12 @@ impl ValidatorService {
             !self.state.is_fullnode(epoch_store),
             SuiError::FullNodeCantHandleCertificate.into()
         );
+        for certificate in certificates.iter() {
+            self.state
+                .metrics
+                .transaction_tracer
+                .record(certificate.digest(), TransactionStage::CertificateReceived);
+        }
 
         let shared_object_tx = certificates
             .iter()
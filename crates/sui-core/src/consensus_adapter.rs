@@ -41,6 +41,7 @@ use crate::consensus_handler::{classify, SequencedConsensusTransactionKey};
 use crate::consensus_throughput_calculator::{ConsensusThroughputProfiler, Level};
 use crate::epoch::reconfiguration::{ReconfigState, ReconfigurationInitiator};
 use crate::metrics::LatencyObserver;
+use crate::transaction_trace::{TransactionStage, TransactionTracer};
 use consensus_core::ConnectionStatus;
 use mysten_metrics::{spawn_monitored_task, GaugeGuard, GaugeGuardFutureExt};
 use sui_protocol_config::ProtocolConfig;
@@ -221,6 +222,8 @@ pub struct ConsensusAdapter {
     low_scoring_authorities: ArcSwap<Arc<ArcSwap<HashMap<AuthorityName, u64>>>>,
     /// The throughput profiler to be used when making decisions to submit to consensus
     consensus_throughput_profiler: ArcSwapOption<ConsensusThroughputProfiler>,
+    /// Records when sampled transactions are submitted to consensus
+    transaction_tracer: ArcSwapOption<TransactionTracer>,
     /// A structure to register metrics
     metrics: ConsensusAdapterMetrics,
     /// Semaphore limiting parallel submissions to consensus
@@ -276,6 +279,7 @@ impl ConsensusAdapter {
             submit_semaphore: Semaphore::new(max_pending_local_submissions),
             latency_observer: LatencyObserver::new(),
             consensus_throughput_profiler: ArcSwapOption::empty(),
+            transaction_tracer: ArcSwapOption::empty(),
             protocol_config,
         }
     }
@@ -291,6 +295,10 @@ impl ConsensusAdapter {
         self.consensus_throughput_profiler.store(Some(profiler))
     }
 
+    pub fn set_transaction_tracer(&self, tracer: Arc<TransactionTracer>) {
+        self.transaction_tracer.store(Some(tracer))
+    }
+
     // todo - this probably need to hold some kind of lock to make sure epoch does not change while we are recovering
     pub fn submit_recovered(self: &Arc<Self>, epoch_store: &Arc<AuthorityPerEpochStore>) {
         // Currently narwhal worker might lose transactions on restart, so we need to resend them
@@ -770,6 +778,14 @@ impl ConsensusAdapter {
                     };
                 }
 
+                if let Some(tracer) = self.transaction_tracer.load().as_ref() {
+                    for transaction in transactions.iter() {
+                        if let ConsensusTransactionKey::Certificate(digest) = transaction.key() {
+                            tracer.record(&digest, TransactionStage::SubmittedToConsensus);
+                        }
+                    }
+                }
+
                 // we want to record the num of retries when reporting latency but to avoid label
                 // cardinality we do some simple bucketing to give us a good enough idea of how
                 // many retries happened associated with the latency.
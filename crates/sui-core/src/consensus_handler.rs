@@ -49,6 +49,7 @@ use crate::{
     execution_cache::ObjectCacheRead,
     scoring_decision::update_low_scoring_authorities,
     transaction_manager::TransactionManager,
+    transaction_trace::TransactionStage,
 };
 
 pub struct ConsensusHandlerInitializer {
@@ -398,6 +399,15 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
                     continue;
                 }
 
+                if let SequencedConsensusTransactionKey::External(
+                    ConsensusTransactionKey::Certificate(digest),
+                ) = sequenced_transaction.key()
+                {
+                    self.metrics
+                        .transaction_tracer
+                        .record(&digest, TransactionStage::Sequenced);
+                }
+
                 all_transactions.push(sequenced_transaction);
             }
         }
@@ -549,6 +559,7 @@ pub(crate) fn classify(transaction: &ConsensusTransaction) -> &'static str {
         ConsensusTransactionKind::EndOfPublish(_) => "end_of_publish",
         ConsensusTransactionKind::CapabilityNotification(_) => "capability_notification",
         ConsensusTransactionKind::CapabilityNotificationV2(_) => "capability_notification_v2",
+        ConsensusTransactionKind::ExecutionTimeObservation(_) => "execution_time_observation",
         ConsensusTransactionKind::NewJWKFetched(_, _, _) => "new_jwk_fetched",
         ConsensusTransactionKind::RandomnessStateUpdate(_, _) => "randomness_state_update",
         ConsensusTransactionKind::RandomnessDkgMessage(_, _) => "randomness_dkg_message",
@@ -966,20 +977,24 @@ mod tests {
         BlockAPI, CommitDigest, CommitRef, CommittedSubDag, TestBlock, Transaction, VerifiedBlock,
     };
     use prometheus::Registry;
-    use sui_protocol_config::ConsensusTransactionOrdering;
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+
+    use sui_protocol_config::{ConsensusTransactionOrdering, PerObjectCongestionControlMode};
     use sui_types::{
         base_types::{random_object_ref, AuthorityName, SuiAddress},
         committee::Committee,
         crypto::deterministic_random_account_key,
         messages_consensus::{
             AuthorityCapabilitiesV1, ConsensusTransaction, ConsensusTransactionKind,
-            TransactionIndex,
+            ExecutionTimeObservation, ExecutionTimeObservationKey, TransactionIndex,
         },
         object::Object,
         supported_protocol_versions::SupportedProtocolVersions,
         transaction::{
             CertifiedTransaction, SenderSignedData, TransactionData, TransactionDataAPI,
         },
+        SUI_FRAMEWORK_PACKAGE_ID,
     };
 
     use super::*;
@@ -1186,6 +1201,142 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    pub async fn test_consensus_commit_handler_execution_time_estimates() {
+        // GIVEN a committee of 4 that uses execution time estimates for congestion control. Each
+        // object can be used for 2500us of execution per commit, and commands that were never
+        // observed cost 100us.
+        let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+            config.set_per_object_congestion_control_mode_for_testing(
+                PerObjectCongestionControlMode::ExecutionTimeEstimate,
+            );
+            config.set_execution_time_estimate_default_command_cost_us_for_testing(100);
+            config.set_max_execution_time_observation_estimates_for_testing(10);
+            config.set_max_accumulated_txn_cost_per_object_in_narwhal_commit_for_testing(2500);
+            config.set_max_accumulated_txn_cost_per_object_in_mysticeti_commit_for_testing(2500);
+            config.set_max_txn_cost_overage_per_object_in_commit_for_testing(0);
+            config.set_max_deferral_rounds_for_congestion_control_for_testing(1000);
+            config
+        });
+
+        // AND 5 gas objects, and a shared object.
+        let (sender, keypair) = deterministic_random_account_key();
+        let gas_objects: Vec<Object> = (0..5)
+            .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), sender))
+            .collect();
+        let shared_object = Object::shared_for_testing();
+        let mut all_objects = gas_objects.clone();
+        all_objects.push(shared_object.clone());
+
+        let network_config =
+            sui_swarm_config::network_config_builder::ConfigBuilder::new_with_temp_dir()
+                .committee_size(NonZeroUsize::new(4).unwrap())
+                .with_objects(all_objects.clone())
+                .build();
+
+        let state = TestAuthorityBuilder::new()
+            .with_network_config(&network_config, 0)
+            .build()
+            .await;
+
+        let epoch_store = state.epoch_store_for_testing().clone();
+        let consensus_committee = epoch_store.epoch_start_state().get_consensus_committee();
+        let metrics = Arc::new(AuthorityMetrics::new(&Registry::new()));
+        let throughput_calculator = ConsensusThroughputCalculator::new(None, metrics.clone());
+        let mut consensus_handler = ConsensusHandler::new(
+            epoch_store.clone(),
+            Arc::new(CheckpointServiceNoop {}),
+            state.transaction_manager().clone(),
+            state.get_object_cache_reader().clone(),
+            Arc::new(ArcSwap::default()),
+            consensus_committee.clone(),
+            metrics,
+            Arc::new(throughput_calculator),
+        );
+
+        let commit = |index: u32, round: u32, transactions: Vec<(u32, ConsensusTransaction)>| {
+            let blocks: Vec<_> = transactions
+                .into_iter()
+                .map(|(author, transaction)| {
+                    VerifiedBlock::new_for_test(
+                        TestBlock::new(round, author)
+                            .set_transactions(vec![Transaction::new(
+                                bcs::to_bytes(&transaction).unwrap(),
+                            )])
+                            .build(),
+                    )
+                })
+                .collect();
+            let leader_block = blocks[0].clone();
+            CommittedSubDag::new(
+                leader_block.reference(),
+                blocks.clone(),
+                vec![vec![]; blocks.len()],
+                leader_block.timestamp_ms(),
+                CommitRef::new(index, CommitDigest::MIN),
+                vec![],
+            )
+        };
+
+        // WHEN 3 of the 4 validators, a quorum, observe that the transactions' entry point takes
+        // 1000us to execute.
+        let key = ExecutionTimeObservationKey::MoveEntryPoint {
+            package: SUI_FRAMEWORK_PACKAGE_ID,
+            module: "object_basics".to_string(),
+            function: "create".to_string(),
+        };
+        let observations = (0..3)
+            .map(|author| {
+                let authority = *epoch_store.committee().authority_by_index(author).unwrap();
+                let observation = ExecutionTimeObservation {
+                    authority,
+                    generation: 1,
+                    estimates: vec![(key.clone(), Duration::from_micros(1000))],
+                };
+                (
+                    author,
+                    ConsensusTransaction::new_execution_time_observation(observation),
+                )
+            })
+            .collect();
+        consensus_handler
+            .handle_consensus_commit(commit(1, 10, observations))
+            .await;
+
+        // AND 5 transactions using the shared object are sequenced in the next commit.
+        let mut transactions = vec![];
+        for gas_object in &gas_objects {
+            let transaction = test_user_transaction(
+                &state,
+                sender,
+                &keypair,
+                gas_object.clone(),
+                vec![shared_object.clone()],
+            )
+            .await;
+            transactions.push((
+                0,
+                ConsensusTransaction::new_user_transaction_message(
+                    &state.name,
+                    transaction.inner().clone(),
+                ),
+            ));
+        }
+        consensus_handler
+            .handle_consensus_commit(commit(2, 20, transactions))
+            .await;
+
+        // THEN only 2 of them fit in the object's budget, according to the estimate. Without the
+        // observations, all 5 would have been scheduled at the default cost.
+        let deferred: usize = epoch_store
+            .get_all_deferred_transactions_for_test()
+            .unwrap()
+            .iter()
+            .map(|(_, transactions)| transactions.len())
+            .sum();
+        assert_eq!(deferred, 3);
+    }
+
     #[tokio::test]
     pub async fn test_consensus_transaction_handler() {
         // GIVEN
@@ -9,7 +9,9 @@ use mysten_metrics::monitored_scope;
 use prometheus::{register_int_counter_with_registry, IntCounter, Registry};
 use sui_types::{
     error::{SuiError, SuiResult},
-    messages_consensus::{ConsensusTransaction, ConsensusTransactionKind},
+    messages_consensus::{
+        check_execution_time_observation_size, ConsensusTransaction, ConsensusTransactionKind,
+    },
     transaction::Transaction,
 };
 use tap::TapFallible;
@@ -84,6 +86,25 @@ impl SuiTxValidator {
 
                 ConsensusTransactionKind::CapabilityNotification(_) => {}
 
+                ConsensusTransactionKind::ExecutionTimeObservation(observation) => {
+                    let Some(max_estimates) = epoch_store
+                        .protocol_config()
+                        .max_execution_time_observation_estimates_as_option()
+                    else {
+                        return Err(SuiError::UnexpectedMessage(
+                            "ConsensusTransactionKind::ExecutionTimeObservation is unsupported"
+                                .to_string(),
+                        ));
+                    };
+                    if !check_execution_time_observation_size(observation, max_estimates) {
+                        warn!("batch verification error: execution time observation too large");
+                        return Err(SuiError::UnexpectedMessage(format!(
+                            "ExecutionTimeObservation with {} estimates exceeds the maximum size",
+                            observation.estimates.len()
+                        )));
+                    }
+                }
+
                 ConsensusTransactionKind::EndOfPublish(_)
                 | ConsensusTransactionKind::NewJWKFetched(_, _, _)
                 | ConsensusTransactionKind::CapabilityNotificationV2(_)
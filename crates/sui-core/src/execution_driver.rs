@@ -21,6 +21,7 @@ use tracing::{error, error_span, info, trace, Instrument};
 
 use crate::authority::AuthorityState;
 use crate::transaction_manager::PendingCertificate;
+use crate::transaction_trace::TransactionStage;
 
 #[cfg(test)]
 #[path = "unit_tests/execution_driver_tests.rs"]
@@ -137,6 +138,8 @@ pub async fn execution_process(
             if let Ok(true) = authority.is_tx_already_executed(&digest) {
                 return;
             }
+            let transaction_tracer = &authority.metrics.transaction_tracer;
+            transaction_tracer.record(&digest, TransactionStage::ExecutionStarted);
             let mut attempts = 0;
             loop {
                 fail_point_async!("transaction_execution_delay");
@@ -158,6 +161,7 @@ pub async fn execution_process(
                     break;
                 }
             }
+            transaction_tracer.record(&digest, TransactionStage::Executed);
             authority
                 .metrics
                 .execution_driver_executed_transactions
@@ -44,6 +44,7 @@ mod transaction_input_loader;
 mod transaction_manager;
 pub mod transaction_orchestrator;
 mod transaction_outputs;
+pub mod transaction_trace;
 pub mod validator_tx_finalizer;
 pub mod verify_indexes;
 
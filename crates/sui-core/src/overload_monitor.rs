@@ -3,13 +3,16 @@
 
 use crate::authority::AuthorityState;
 use mysten_metrics::monitored_scope;
+use parking_lot::RwLock;
 use std::cmp::{max, min};
+use std::collections::BTreeMap;
 use std::hash::Hasher;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Weak;
 use std::time::Duration;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use sui_config::node::AuthorityOverloadConfig;
+use sui_types::base_types::SuiAddress;
 use sui_types::digests::TransactionDigest;
 use sui_types::error::SuiError;
 use sui_types::error::SuiResult;
@@ -25,6 +28,35 @@ pub struct AuthorityOverloadInfo {
 
     /// The calculated percentage of transactions to drop.
     pub load_shedding_percentage: AtomicU32,
+
+    /// Overrides of the calculated load shedding set by the operator.
+    load_shedding_override: RwLock<LoadSheddingOverride>,
+}
+
+/// Load shedding overrides set by the operator through the admin interface. They take precedence
+/// over the load shedding percentage calculated from the overload signals.
+#[derive(Clone, Debug, Default)]
+pub struct LoadSheddingOverride {
+    /// When set, this percentage of transactions is dropped regardless of the overload signals.
+    pub pinned_percentage: Option<u32>,
+
+    /// When set, the calculated load shedding percentage never exceeds this value.
+    pub max_percentage: Option<u32>,
+
+    /// Senders whose transactions are never dropped by load shedding, until the given deadline.
+    pub exempt_senders: BTreeMap<SuiAddress, Instant>,
+}
+
+impl LoadSheddingOverride {
+    fn apply(&self, load_shedding_percentage: u32) -> u32 {
+        if let Some(pinned_percentage) = self.pinned_percentage {
+            return pinned_percentage;
+        }
+        match self.max_percentage {
+            Some(max_percentage) => min(load_shedding_percentage, max_percentage),
+            None => load_shedding_percentage,
+        }
+    }
 }
 
 impl AuthorityOverloadInfo {
@@ -38,6 +70,101 @@ impl AuthorityOverloadInfo {
         self.is_overload.store(false, Ordering::Relaxed);
         self.load_shedding_percentage.store(0, Ordering::Relaxed);
     }
+
+    // Applies the load shedding overrides to `load_shedding_percentage` calculated from the
+    // overload signals, and updates the overload status. Returns the applied percentage.
+    fn update_load_shedding(&self, load_shedding_percentage: u32) -> u32 {
+        let load_shedding_percentage = min(
+            self.load_shedding_override
+                .read()
+                .apply(load_shedding_percentage),
+            100,
+        );
+        if load_shedding_percentage > 0 {
+            self.set_overload(load_shedding_percentage);
+        } else {
+            self.clear_overload();
+        }
+        load_shedding_percentage
+    }
+
+    pub fn load_shedding_override(&self) -> LoadSheddingOverride {
+        self.load_shedding_override.read().clone()
+    }
+
+    /// Drops `percentage` of transactions regardless of the overload signals, until the pin is
+    /// cleared. Clearing the pin turns off load shedding until the overload monitor calculates it
+    /// again.
+    pub fn pin_load_shedding(&self, percentage: Option<u32>) {
+        self.load_shedding_override.write().pinned_percentage = percentage;
+        match percentage {
+            Some(percentage) => {
+                self.update_load_shedding(percentage);
+            }
+            None => self.clear_overload(),
+        }
+    }
+
+    /// Caps the calculated load shedding percentage, or removes the cap.
+    pub fn cap_load_shedding(&self, max_percentage: Option<u32>) {
+        self.load_shedding_override.write().max_percentage = max_percentage;
+        self.update_load_shedding(self.load_shedding_percentage.load(Ordering::Relaxed));
+    }
+
+    /// Exempts transactions from `sender` from load shedding for `duration`.
+    pub fn exempt_sender(&self, sender: SuiAddress, duration: Duration) {
+        let now = Instant::now();
+        let mut load_shedding_override = self.load_shedding_override.write();
+        load_shedding_override
+            .exempt_senders
+            .retain(|_, deadline| *deadline > now);
+        load_shedding_override
+            .exempt_senders
+            .insert(sender, now + duration);
+    }
+
+    /// Removes the load shedding exemption of `sender`. Returns whether it was exempt.
+    pub fn remove_exempt_sender(&self, sender: &SuiAddress) -> bool {
+        self.load_shedding_override
+            .write()
+            .exempt_senders
+            .remove(sender)
+            .is_some_and(|deadline| deadline > Instant::now())
+    }
+
+    pub fn is_exempt_sender(&self, sender: &SuiAddress) -> bool {
+        self.load_shedding_override
+            .read()
+            .exempt_senders
+            .get(sender)
+            .is_some_and(|deadline| *deadline > Instant::now())
+    }
+}
+
+/// The signals that the overload monitor calculates load shedding from.
+#[derive(Debug)]
+pub struct OverloadSignals {
+    /// How long transactions wait in the execution queue.
+    pub execution_queueing_latency: Duration,
+    pub txn_ready_rate: f64,
+    pub execution_rate: f64,
+    /// The number of transactions pending or being executed in the transaction manager.
+    pub transaction_manager_backlog: usize,
+}
+
+impl OverloadSignals {
+    pub fn collect(authority: &AuthorityState) -> Self {
+        Self {
+            execution_queueing_latency: authority
+                .metrics
+                .execution_queueing_latency
+                .latency()
+                .unwrap_or_default(),
+            txn_ready_rate: authority.metrics.txn_ready_rate_tracker.lock().rate(),
+            execution_rate: authority.metrics.execution_rate_tracker.lock().rate(),
+            transaction_manager_backlog: authority.transaction_manager().inflight_queue_len(),
+        }
+    }
 }
 
 const STEADY_OVERLOAD_REDUCTION_PERCENTAGE: u32 = 10;
@@ -81,42 +208,32 @@ fn check_authority_overload(
     }
 
     let authority = authority_arc.unwrap();
-    let queueing_latency = authority
-        .metrics
-        .execution_queueing_latency
-        .latency()
-        .unwrap_or_default();
-    let txn_ready_rate = authority.metrics.txn_ready_rate_tracker.lock().rate();
-    let execution_rate = authority.metrics.execution_rate_tracker.lock().rate();
+    let signals = OverloadSignals::collect(&authority);
 
     debug!(
         "Check authority overload signal, queueing latency {:?}, ready rate {:?}, execution rate {:?}.",
-        queueing_latency, txn_ready_rate, execution_rate
+        signals.execution_queueing_latency, signals.txn_ready_rate, signals.execution_rate
     );
 
-    let (is_overload, load_shedding_percentage) = check_overload_signals(
+    let (_, load_shedding_percentage) = check_overload_signals(
         config,
         authority
             .overload_info
             .load_shedding_percentage
             .load(Ordering::Relaxed),
-        queueing_latency,
-        txn_ready_rate,
-        execution_rate,
+        signals.execution_queueing_latency,
+        signals.txn_ready_rate,
+        signals.execution_rate,
     );
 
-    if is_overload {
-        authority
-            .overload_info
-            .set_overload(load_shedding_percentage);
-    } else {
-        authority.overload_info.clear_overload();
-    }
+    let load_shedding_percentage = authority
+        .overload_info
+        .update_load_shedding(load_shedding_percentage);
 
     authority
         .metrics
         .authority_overload_status
-        .set(is_overload as i64);
+        .set((load_shedding_percentage > 0) as i64);
     authority
         .metrics
         .authority_load_shedding_percentage
@@ -321,6 +438,48 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn test_load_shedding_override() {
+        let overload_info = AuthorityOverloadInfo::default();
+        let load_shedding_percentage = || {
+            overload_info
+                .load_shedding_percentage
+                .load(Ordering::Relaxed)
+        };
+
+        // Without overrides, the calculated percentage is applied.
+        assert_eq!(overload_info.update_load_shedding(40), 40);
+
+        // A cap applies immediately, and to future calculations.
+        overload_info.cap_load_shedding(Some(30));
+        assert_eq!(load_shedding_percentage(), 30);
+        assert_eq!(overload_info.update_load_shedding(50), 30);
+        assert_eq!(overload_info.update_load_shedding(20), 20);
+
+        // A pin takes precedence over both the calculated percentage and the cap.
+        overload_info.pin_load_shedding(Some(60));
+        assert!(overload_info.is_overload.load(Ordering::Relaxed));
+        assert_eq!(load_shedding_percentage(), 60);
+        assert_eq!(overload_info.update_load_shedding(0), 60);
+
+        // Clearing the pin turns off load shedding until the next calculation.
+        overload_info.pin_load_shedding(None);
+        assert!(!overload_info.is_overload.load(Ordering::Relaxed));
+        assert_eq!(overload_info.update_load_shedding(50), 30);
+        overload_info.cap_load_shedding(None);
+        assert_eq!(overload_info.update_load_shedding(50), 50);
+
+        // Exemptions expire.
+        let sender = SuiAddress::random_for_testing_only();
+        overload_info.exempt_sender(sender, Duration::from_secs(60));
+        assert!(overload_info.is_exempt_sender(&sender));
+        assert!(!overload_info.is_exempt_sender(&SuiAddress::random_for_testing_only()));
+        assert!(overload_info.remove_exempt_sender(&sender));
+        assert!(!overload_info.is_exempt_sender(&sender));
+        overload_info.exempt_sender(sender, Duration::ZERO);
+        assert!(!overload_info.is_exempt_sender(&sender));
+    }
+
     #[test]
     pub fn test_calculate_load_shedding_ratio() {
         assert_eq!(calculate_load_shedding_percentage(95.0, 100.1), 0);
@@ -12,12 +12,13 @@ use std::fmt::{Debug, Formatter};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
+use sui_storage::write_path_pending_tx_log::WritePathPendingTransactionLog;
 use sui_types::base_types::{AuthorityName, ObjectRef, TransactionDigest};
 use sui_types::committee::{Committee, EpochId, StakeUnit};
 use sui_types::messages_grpc::HandleCertificateRequestV3;
 use sui_types::quorum_driver_types::{
     ExecuteTransactionRequestV3, QuorumDriverEffectsQueueResult, QuorumDriverError,
-    QuorumDriverResponse, QuorumDriverResult,
+    QuorumDriverResponse, QuorumDriverResult, SubmissionStatus,
 };
 use tap::TapFallible;
 use tokio::sync::Semaphore;
@@ -85,6 +86,9 @@ pub struct QuorumDriver<A: Clone> {
     notifier: Arc<NotifyRead<TransactionDigest, QuorumDriverResult>>,
     metrics: Arc<QuorumDriverMetrics>,
     max_retry_times: u32,
+    // Where the progress of transactions is recorded, when they are submitted through the
+    // durable submission queue of TransactionOrchestrator.
+    submission_log: Option<Arc<WritePathPendingTransactionLog>>,
 }
 
 impl<A: Clone> QuorumDriver<A> {
@@ -95,6 +99,7 @@ impl<A: Clone> QuorumDriver<A> {
         notifier: Arc<NotifyRead<TransactionDigest, QuorumDriverResult>>,
         metrics: Arc<QuorumDriverMetrics>,
         max_retry_times: u32,
+        submission_log: Option<Arc<WritePathPendingTransactionLog>>,
     ) -> Self {
         Self {
             validators,
@@ -103,6 +108,7 @@ impl<A: Clone> QuorumDriver<A> {
             notifier,
             metrics,
             max_retry_times,
+            submission_log,
         }
     }
 
@@ -238,6 +244,15 @@ impl<A: Clone> QuorumDriver<A> {
         debug!(?tx_digest, "notify QuorumDriver task result");
         self.notifier.notify(tx_digest, response);
     }
+
+    fn update_submission_status(&self, tx_digest: &TransactionDigest, status: SubmissionStatus) {
+        let Some(submission_log) = &self.submission_log else {
+            return;
+        };
+        if let Err(err) = submission_log.update_status(tx_digest, status) {
+            warn!(?tx_digest, "Failed to update submission status: {err}");
+        }
+    }
 }
 
 impl<A> QuorumDriver<A>
@@ -647,6 +662,7 @@ where
         reconfig_observer: Arc<dyn ReconfigObserver<A> + Sync + Send>,
         metrics: Arc<QuorumDriverMetrics>,
         max_retry_times: u32,
+        submission_log: Option<Arc<WritePathPendingTransactionLog>>,
     ) -> Self {
         let (task_tx, task_rx) = mpsc::channel::<QuorumDriverTask>(TASK_QUEUE_SIZE);
         let (subscriber_tx, subscriber_rx) =
@@ -658,6 +674,7 @@ where
             notifier,
             metrics.clone(),
             max_retry_times,
+            submission_log,
         ));
         let metrics_clone = metrics.clone();
         let processor_handle = {
@@ -722,6 +739,7 @@ where
             notifier: Arc::new(NotifyRead::new()),
             metrics: self.quorum_driver_metrics.clone(),
             max_retry_times: self.quorum_driver.max_retry_times,
+            submission_log: self.quorum_driver.submission_log.clone(),
         });
         let metrics = self.quorum_driver_metrics.clone();
         let processor_handle = {
@@ -786,6 +804,12 @@ where
         let transaction = &request.transaction;
         let tx_digest = *transaction.digest();
         let is_single_writer_tx = !transaction.contains_shared_object();
+        quorum_driver.update_submission_status(
+            &tx_digest,
+            SubmissionStatus::Submitted {
+                attempts: old_retry_times + 1,
+            },
+        );
 
         let timer = Instant::now();
         let (tx_cert, newly_formed) = match tx_cert {
@@ -830,6 +854,7 @@ where
             },
             Some(tx_cert) => (tx_cert, false),
         };
+        quorum_driver.update_submission_status(&tx_digest, SubmissionStatus::Certified);
 
         let response = match quorum_driver
             .process_certificate(
@@ -977,6 +1002,7 @@ pub struct QuorumDriverHandlerBuilder<A: Clone> {
     notifier: Option<Arc<NotifyRead<TransactionDigest, QuorumDriverResult>>>,
     reconfig_observer: Option<Arc<dyn ReconfigObserver<A> + Sync + Send>>,
     max_retry_times: u32,
+    submission_log: Option<Arc<WritePathPendingTransactionLog>>,
 }
 
 impl<A> QuorumDriverHandlerBuilder<A>
@@ -990,6 +1016,7 @@ where
             notifier: None,
             reconfig_observer: None,
             max_retry_times: TX_MAX_RETRY_TIMES,
+            submission_log: None,
         }
     }
 
@@ -1009,6 +1036,15 @@ where
         self
     }
 
+    /// Records the progress of transactions in `submission_log`, so clients can track them.
+    pub(crate) fn with_submission_log(
+        mut self,
+        submission_log: Arc<WritePathPendingTransactionLog>,
+    ) -> Self {
+        self.submission_log = Some(submission_log);
+        self
+    }
+
     /// Used in tests when smaller number of retries is desired
     pub fn with_max_retry_times(mut self, max_retry_times: u32) -> Self {
         self.max_retry_times = max_retry_times;
@@ -1025,6 +1061,7 @@ where
                 .expect("Reconfig observer is missing"),
             self.metrics,
             self.max_retry_times,
+            self.submission_log,
         )
     }
 }
@@ -6,11 +6,14 @@ use crate::authority::AuthorityStore;
 use crate::checkpoints::CheckpointStore;
 use crate::par_index_live_object_set::LiveObjectIndexer;
 use crate::par_index_live_object_set::ParMakeLiveObjectIndexer;
+use itertools::Itertools;
 use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::TypeTag;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -20,11 +23,15 @@ use sui_types::base_types::MoveObjectType;
 use sui_types::base_types::ObjectID;
 use sui_types::base_types::SequenceNumber;
 use sui_types::base_types::SuiAddress;
+use sui_types::digests::ObjectDigest;
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::visitor as DFV;
+use sui_types::effects::TransactionEffects;
+use sui_types::effects::TransactionEffectsAPI;
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::layout_resolver::LayoutResolver;
 use sui_types::messages_checkpoint::CheckpointContents;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Object;
 use sui_types::object::Owner;
 use sui_types::storage::error::Error as StorageError;
@@ -38,12 +45,17 @@ use typed_store::traits::{TableSummary, TypedStoreDebug};
 use typed_store::DBMapUtils;
 use typed_store::TypedStoreError;
 
-const CURRENT_DB_VERSION: u64 = 0;
+const CURRENT_DB_VERSION: u64 = 3;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 struct MetadataInfo {
     /// Version of the Database
     version: u64,
+    /// Lowest checkpoint for which the history tables have complete information.
+    ///
+    /// The history tables are seeded from the live object set when the DB is initialized, so
+    /// they can't answer for any checkpoint prior to the one the live object set was taken at.
+    object_history_start: CheckpointSequenceNumber,
 }
 
 #[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -79,6 +91,104 @@ pub struct TransactionInfo {
     pub checkpoint: u64,
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct OwnerCoinIndexKey {
+    pub owner: SuiAddress,
+    /// Canonical string representation of the coin type
+    pub coin_type: String,
+    // the balance of the coin inverted `!coin.balance` in order to force sorting of coins to be
+    // from greatest to least
+    pub inverted_balance: u64,
+    pub object_id: ObjectID,
+}
+
+impl OwnerCoinIndexKey {
+    fn new(owner: SuiAddress, coin_type: &StructTag, balance: u64, object_id: ObjectID) -> Self {
+        Self {
+            owner,
+            coin_type: coin_type.to_canonical_string(true),
+            inverted_balance: !balance,
+            object_id,
+        }
+    }
+
+    fn balance_key(&self) -> OwnerBalanceKey {
+        OwnerBalanceKey {
+            owner: self.owner,
+            coin_type: self.coin_type.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct OwnerCoinIndexInfo {
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+    pub balance: u64,
+    pub previous_transaction: TransactionDigest,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct OwnerBalanceKey {
+    pub owner: SuiAddress,
+    /// Canonical string representation of the coin type
+    pub coin_type: String,
+}
+
+impl OwnerBalanceKey {
+    fn new(owner: SuiAddress, coin_type: &StructTag) -> Self {
+        Self {
+            owner,
+            coin_type: coin_type.to_canonical_string(true),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, Debug)]
+pub struct OwnerBalanceInfo {
+    pub coin_object_count: u64,
+    pub total_balance: u128,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct ObjectHistoryKey {
+    pub object_id: ObjectID,
+    pub checkpoint: CheckpointSequenceNumber,
+}
+
+impl ObjectHistoryKey {
+    fn new(object_id: ObjectID, checkpoint: CheckpointSequenceNumber) -> Self {
+        Self {
+            object_id,
+            checkpoint,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct ObjectHistoryInfo {
+    /// Version of the object as of the end of the checkpoint, or `None` if the object was deleted
+    /// or wrapped.
+    pub version: Option<SequenceNumber>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct DynamicFieldHistoryKey {
+    pub parent: ObjectID,
+    pub field_id: ObjectID,
+    pub checkpoint: CheckpointSequenceNumber,
+}
+
+impl DynamicFieldHistoryKey {
+    fn new(parent: ObjectID, field_id: ObjectID, checkpoint: CheckpointSequenceNumber) -> Self {
+        Self {
+            parent,
+            field_id,
+            checkpoint,
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub struct CoinIndexKey {
     coin_type: StructTag,
@@ -142,6 +252,32 @@ struct IndexStoreTables {
     /// Allows looking up information related to published Coins, like the ObjectID of its
     /// coorisponding CoinMetadata.
     coin: DBMap<CoinIndexKey, CoinIndexInfo>,
+
+    /// An index of the coins owned by each address.
+    ///
+    /// Allows an efficient iterator to list the coins of a specific type owned by an address,
+    /// ordered from greatest to least balance, as well as computing the balances of an address.
+    owner_coin: DBMap<OwnerCoinIndexKey, OwnerCoinIndexInfo>,
+
+    /// An index of the balance of each coin type owned by each address.
+    ///
+    /// Maintained alongside `owner_coin` so that balances can be looked up without iterating over
+    /// every coin. Entries are removed once an address no longer owns any coins of a type, so the
+    /// table is bounded by the live object set.
+    owner_balance: DBMap<OwnerBalanceKey, OwnerBalanceInfo>,
+
+    /// An index of the version of every object as of each checkpoint which changed it.
+    ///
+    /// Allows resolving the version an object had as of an arbitrary checkpoint. Once a checkpoint
+    /// is pruned, only the latest entry at or before it is kept for each object.
+    object_history: DBMap<ObjectHistoryKey, ObjectHistoryInfo>,
+
+    /// An index of the dynamic fields of every parent as of each checkpoint which changed them.
+    ///
+    /// A `None` value marks a field which was removed from the parent. Allows listing the dynamic
+    /// fields a parent had as of an arbitrary checkpoint. Pruned in the same manner as
+    /// `object_history`.
+    dynamic_field_history: DBMap<DynamicFieldHistoryKey, Option<DynamicFieldIndexInfo>>,
     // NOTE: Authors and Reviewers before adding any new tables ensure that they are either:
     // - bounded in size by the live object set
     // - are prune-able and have corresponding logic in the `prune` function
@@ -182,11 +318,12 @@ impl IndexStoreTables {
     ) -> Result<(), StorageError> {
         info!("Initializing REST indexes");
 
+        let highest_executed_checkpoint =
+            checkpoint_store.get_highest_executed_checkpoint_seq_number()?;
+
         // Iterate through available, executed checkpoints that have yet to be pruned
         // to initialize checkpoint and transaction based indexes.
-        if let Some(highest_executed_checkpint) =
-            checkpoint_store.get_highest_executed_checkpoint_seq_number()?
-        {
+        if let Some(highest_executed_checkpint) = highest_executed_checkpoint {
             let lowest_available_checkpoint = checkpoint_store
                 .get_highest_pruned_checkpoint_seq_number()?
                 .saturating_add(1);
@@ -222,11 +359,20 @@ impl IndexStoreTables {
             );
         }
 
+        // The history tables are seeded with the live object set as of the highest executed
+        // checkpoint. Transactions of the following checkpoint may have already been executed as
+        // well, so the history is only known to be complete starting with that checkpoint.
+        let (history_checkpoint, object_history_start) = match highest_executed_checkpoint {
+            Some(checkpoint) => (checkpoint, checkpoint + 1),
+            None => (0, 0),
+        };
+
         let coin_index = Mutex::new(HashMap::new());
 
         let make_live_object_indexer = RestParLiveObjectSetIndexer {
             tables: self,
             coin_index: &coin_index,
+            history_checkpoint,
             epoch_store,
             package_store,
         };
@@ -237,11 +383,13 @@ impl IndexStoreTables {
         )?;
 
         self.coin.multi_insert(coin_index.into_inner().unwrap())?;
+        self.init_owner_balances()?;
 
         self.meta.insert(
             &(),
             &MetadataInfo {
                 version: CURRENT_DB_VERSION,
+                object_history_start,
             },
         )?;
 
@@ -250,10 +398,59 @@ impl IndexStoreTables {
         Ok(())
     }
 
+    /// Populate the `owner_balance` index by summing up the entries of the `owner_coin` index,
+    /// which are grouped by owner and coin type.
+    fn init_owner_balances(&self) -> Result<(), StorageError> {
+        info!("Indexing owner balances");
+        let start_time = Instant::now();
+
+        let mut batch = self.owner_balance.batch();
+        let mut current: Option<(OwnerBalanceKey, OwnerBalanceInfo)> = None;
+
+        for (coin_key, coin_info) in self.owner_coin.unbounded_iter() {
+            match &mut current {
+                Some((key, info))
+                    if key.owner == coin_key.owner && key.coin_type == coin_key.coin_type =>
+                {
+                    info.coin_object_count += 1;
+                    info.total_balance += coin_info.balance as u128;
+                    continue;
+                }
+                _ => {}
+            }
+
+            if let Some(entry) = current.replace((
+                coin_key.balance_key(),
+                OwnerBalanceInfo {
+                    coin_object_count: 1,
+                    total_balance: coin_info.balance as u128,
+                },
+            )) {
+                batch.insert_batch(&self.owner_balance, [entry])?;
+
+                // Write out periodically so that the batch doesn't grow unbounded
+                if batch.size_in_bytes() >= 1 << 27 {
+                    std::mem::replace(&mut batch, self.owner_balance.batch()).write()?;
+                }
+            }
+        }
+
+        batch.insert_batch(&self.owner_balance, current)?;
+        batch.write()?;
+
+        info!(
+            "Indexing owner balances took {} seconds",
+            start_time.elapsed().as_secs()
+        );
+        Ok(())
+    }
+
     /// Prune data from this Index
     fn prune(
         &self,
+        pruned_checkpoint_watermark: CheckpointSequenceNumber,
         checkpoint_contents_to_prune: &[CheckpointContents],
+        effects_to_prune: &[TransactionEffects],
     ) -> Result<(), TypedStoreError> {
         let mut batch = self.transactions.batch();
 
@@ -263,6 +460,63 @@ impl IndexStoreTables {
 
         batch.delete_batch(&self.transactions, transactions_to_prune)?;
 
+        // Collapse the history of every object and dynamic field touched by the pruned
+        // transactions down to their state as of the pruned watermark
+        let mut objects = BTreeSet::new();
+        let mut dynamic_fields: BTreeSet<(ObjectID, ObjectID)> = BTreeSet::new();
+
+        for effects in effects_to_prune {
+            let old_objects = effects.old_object_metadata().into_iter();
+            let new_objects = effects
+                .all_changed_objects()
+                .into_iter()
+                .map(|(object_ref, owner, _)| (object_ref, owner));
+
+            for ((object_id, _, _), owner) in old_objects.chain(new_objects) {
+                objects.insert(object_id);
+                if let Owner::ObjectOwner(parent) = owner {
+                    dynamic_fields.insert((ObjectID::from(parent), object_id));
+                }
+            }
+
+            objects.extend(effects.all_tombstones().into_iter().map(|(id, _)| id));
+        }
+
+        for object_id in objects {
+            let upper_bound = ObjectHistoryKey::new(object_id, pruned_checkpoint_watermark);
+            let mut entries = self
+                .object_history
+                .range_iter(ObjectHistoryKey::new(object_id, 0)..=upper_bound)
+                .skip_prior_to(&upper_bound)?
+                .reverse();
+
+            // The latest entry is the state of the object as of the watermark, which only needs
+            // to be kept if the object still existed at that point
+            if let Some((key, info)) = entries.next() {
+                if info.version.is_none() {
+                    batch.delete_batch(&self.object_history, [key])?;
+                }
+            }
+            batch.delete_batch(&self.object_history, entries.map(|(key, _)| key))?;
+        }
+
+        for (parent, field_id) in dynamic_fields {
+            let upper_bound =
+                DynamicFieldHistoryKey::new(parent, field_id, pruned_checkpoint_watermark);
+            let mut entries = self
+                .dynamic_field_history
+                .range_iter(DynamicFieldHistoryKey::new(parent, field_id, 0)..=upper_bound)
+                .skip_prior_to(&upper_bound)?
+                .reverse();
+
+            if let Some((key, info)) = entries.next() {
+                if info.is_none() {
+                    batch.delete_batch(&self.dynamic_field_history, [key])?;
+                }
+            }
+            batch.delete_batch(&self.dynamic_field_history, entries.map(|(key, _)| key))?;
+        }
+
         batch.write()
     }
 
@@ -278,11 +532,12 @@ impl IndexStoreTables {
         );
 
         let mut batch = self.transactions.batch();
+        let sequence_number = checkpoint.checkpoint_summary.sequence_number;
 
         // transactions index
         {
             let info = TransactionInfo {
-                checkpoint: checkpoint.checkpoint_summary.sequence_number,
+                checkpoint: sequence_number,
             };
 
             batch.insert_batch(
@@ -297,10 +552,25 @@ impl IndexStoreTables {
         // object indexes
         {
             let mut coin_index = HashMap::new();
+            let mut owner_coin_changes = OwnerCoinChanges::default();
 
             for tx in &checkpoint.transactions {
                 // determine changes from removed objects
                 for removed_object in tx.removed_objects_pre_version() {
+                    batch.insert_batch(
+                        &self.object_history,
+                        [(
+                            ObjectHistoryKey::new(removed_object.id(), sequence_number),
+                            ObjectHistoryInfo { version: None },
+                        )],
+                    )?;
+
+                    if let Some((coin_key, coin_info)) =
+                        try_create_owner_coin_index_info(removed_object)
+                    {
+                        owner_coin_changes.remove(self, coin_key, coin_info.balance)?;
+                    }
+
                     match removed_object.owner() {
                         Owner::AddressOwner(address) => {
                             let owner_key = OwnerIndexKey::new(*address, removed_object.id());
@@ -311,6 +581,17 @@ impl IndexStoreTables {
                                 &self.dynamic_field,
                                 [DynamicFieldKey::new(*object_id, removed_object.id())],
                             )?;
+                            batch.insert_batch(
+                                &self.dynamic_field_history,
+                                [(
+                                    DynamicFieldHistoryKey::new(
+                                        (*object_id).into(),
+                                        removed_object.id(),
+                                        sequence_number,
+                                    ),
+                                    None,
+                                )],
+                            )?;
                         }
                         Owner::Shared { .. } | Owner::Immutable => {}
                     }
@@ -318,6 +599,27 @@ impl IndexStoreTables {
 
                 // determine changes from changed objects
                 for (object, old_object) in tx.changed_objects() {
+                    batch.insert_batch(
+                        &self.object_history,
+                        [(
+                            ObjectHistoryKey::new(object.id(), sequence_number),
+                            ObjectHistoryInfo {
+                                version: Some(object.version()),
+                            },
+                        )],
+                    )?;
+
+                    // The balance of a coin is part of its key so the old entry always needs to be
+                    // removed, even if the owner is unchanged
+                    if let Some((coin_key, coin_info)) =
+                        old_object.and_then(try_create_owner_coin_index_info)
+                    {
+                        owner_coin_changes.remove(self, coin_key, coin_info.balance)?;
+                    }
+                    if let Some((coin_key, coin_info)) = try_create_owner_coin_index_info(object) {
+                        owner_coin_changes.insert(self, coin_key, coin_info)?;
+                    }
+
                     if let Some(old_object) = old_object {
                         if old_object.owner() != object.owner() {
                             match old_object.owner() {
@@ -331,6 +633,17 @@ impl IndexStoreTables {
                                         &self.dynamic_field,
                                         [DynamicFieldKey::new(*object_id, old_object.id())],
                                     )?;
+                                    batch.insert_batch(
+                                        &self.dynamic_field_history,
+                                        [(
+                                            DynamicFieldHistoryKey::new(
+                                                (*object_id).into(),
+                                                old_object.id(),
+                                                sequence_number,
+                                            ),
+                                            None,
+                                        )],
+                                    )?;
                                 }
 
                                 Owner::Shared { .. } | Owner::Immutable => {}
@@ -351,7 +664,16 @@ impl IndexStoreTables {
                                     .flatten()
                             {
                                 let field_key = DynamicFieldKey::new(*parent, object.id());
-
+                                let history_key = DynamicFieldHistoryKey::new(
+                                    (*parent).into(),
+                                    object.id(),
+                                    sequence_number,
+                                );
+
+                                batch.insert_batch(
+                                    &self.dynamic_field_history,
+                                    [(history_key, Some(field_info.clone()))],
+                                )?;
                                 batch
                                     .insert_batch(&self.dynamic_field, [(field_key, field_info)])?;
                             }
@@ -382,6 +704,7 @@ impl IndexStoreTables {
             }
 
             batch.insert_batch(&self.coin, coin_index)?;
+            owner_coin_changes.write(self, &mut batch)?;
         }
 
         batch.write()?;
@@ -446,6 +769,100 @@ impl IndexStoreTables {
         };
         self.coin.get(&key)
     }
+
+    fn owner_coin_iter(
+        &self,
+        owner: SuiAddress,
+        coin_type: &StructTag,
+        cursor: Option<(u64, ObjectID)>,
+    ) -> Result<impl Iterator<Item = (OwnerCoinIndexKey, OwnerCoinIndexInfo)> + '_, TypedStoreError>
+    {
+        let (balance, object_id) = cursor.unwrap_or((u64::MAX, ObjectID::ZERO));
+        let lower_bound = OwnerCoinIndexKey::new(owner, coin_type, balance, object_id);
+        let upper_bound = OwnerCoinIndexKey::new(owner, coin_type, 0, ObjectID::MAX);
+
+        Ok(self.owner_coin.range_iter(lower_bound..=upper_bound))
+    }
+
+    fn owner_balance_iter(
+        &self,
+        owner: SuiAddress,
+    ) -> Result<impl Iterator<Item = (OwnerBalanceKey, OwnerBalanceInfo)> + '_, TypedStoreError>
+    {
+        let lower_bound = OwnerBalanceKey {
+            owner,
+            coin_type: String::new(),
+        };
+
+        let iter = self
+            .owner_balance
+            .unbounded_iter()
+            .skip_to(&lower_bound)?
+            .take_while(move |(key, _)| key.owner == owner);
+
+        Ok(iter)
+    }
+
+    fn get_owner_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: &StructTag,
+    ) -> Result<Option<OwnerBalanceInfo>, TypedStoreError> {
+        self.owner_balance
+            .get(&OwnerBalanceKey::new(owner, coin_type))
+    }
+
+    fn get_object_history_start(&self) -> Result<CheckpointSequenceNumber, TypedStoreError> {
+        self.meta
+            .get(&())
+            .map(|metadata| metadata.map_or(0, |metadata| metadata.object_history_start))
+    }
+
+    fn get_object_version_at_checkpoint(
+        &self,
+        object_id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<Option<SequenceNumber>, TypedStoreError> {
+        let upper_bound = ObjectHistoryKey::new(object_id, checkpoint);
+        let entry = self
+            .object_history
+            .range_iter(ObjectHistoryKey::new(object_id, 0)..=upper_bound)
+            .skip_prior_to(&upper_bound)?
+            .reverse()
+            .next();
+
+        Ok(entry.and_then(|(_, info)| info.version))
+    }
+
+    fn dynamic_field_iter_at_checkpoint(
+        &self,
+        parent: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+        cursor: Option<ObjectID>,
+    ) -> Result<impl Iterator<Item = (DynamicFieldKey, DynamicFieldIndexInfo)> + '_, TypedStoreError>
+    {
+        let lower_bound = DynamicFieldHistoryKey::new(parent, cursor.unwrap_or(ObjectID::ZERO), 0);
+        let upper_bound = DynamicFieldHistoryKey::new(parent, ObjectID::MAX, u64::MAX);
+
+        let iter = self
+            .dynamic_field_history
+            .range_iter(lower_bound..=upper_bound)
+            .filter(move |(key, _)| key.checkpoint <= checkpoint)
+            // Entries of a field are ordered by checkpoint so the last one of each field is its
+            // state as of the requested checkpoint
+            .coalesce(|previous, current| {
+                if previous.0.field_id == current.0.field_id {
+                    Ok(current)
+                } else {
+                    Err((previous, current))
+                }
+            })
+            .filter_map(|(key, info)| {
+                info.map(|info| (DynamicFieldKey::new(key.parent, key.field_id), info))
+            });
+
+        Ok(iter)
+    }
 }
 
 pub struct RestIndexStore {
@@ -500,9 +917,15 @@ impl RestIndexStore {
 
     pub fn prune(
         &self,
+        pruned_checkpoint_watermark: CheckpointSequenceNumber,
         checkpoint_contents_to_prune: &[CheckpointContents],
+        effects_to_prune: &[TransactionEffects],
     ) -> Result<(), TypedStoreError> {
-        self.tables.prune(checkpoint_contents_to_prune)
+        self.tables.prune(
+            pruned_checkpoint_watermark,
+            checkpoint_contents_to_prune,
+            effects_to_prune,
+        )
     }
 
     pub fn index_checkpoint(
@@ -537,12 +960,78 @@ impl RestIndexStore {
         self.tables.dynamic_field_iter(parent, cursor)
     }
 
+    /// Iterate over the owner index of every address, e.g. to verify it against the live object
+    /// set.
+    pub fn owner_index_iter(&self) -> impl Iterator<Item = (OwnerIndexKey, OwnerIndexInfo)> + '_ {
+        self.tables.owner.unbounded_iter()
+    }
+
+    pub fn get_owner_index_info(
+        &self,
+        owner: SuiAddress,
+        object_id: ObjectID,
+    ) -> Result<Option<OwnerIndexInfo>, TypedStoreError> {
+        self.tables.owner.get(&OwnerIndexKey::new(owner, object_id))
+    }
+
     pub fn get_coin_info(
         &self,
         coin_type: &StructTag,
     ) -> Result<Option<CoinIndexInfo>, TypedStoreError> {
         self.tables.get_coin_info(coin_type)
     }
+
+    pub fn owner_coin_iter(
+        &self,
+        owner: SuiAddress,
+        coin_type: &StructTag,
+        cursor: Option<(u64, ObjectID)>,
+    ) -> Result<impl Iterator<Item = (OwnerCoinIndexKey, OwnerCoinIndexInfo)> + '_, TypedStoreError>
+    {
+        self.tables.owner_coin_iter(owner, coin_type, cursor)
+    }
+
+    /// Iterate over the balance of every coin type owned by `owner`.
+    pub fn owner_balance_iter(
+        &self,
+        owner: SuiAddress,
+    ) -> Result<impl Iterator<Item = (OwnerBalanceKey, OwnerBalanceInfo)> + '_, TypedStoreError>
+    {
+        self.tables.owner_balance_iter(owner)
+    }
+
+    pub fn get_owner_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: &StructTag,
+    ) -> Result<Option<OwnerBalanceInfo>, TypedStoreError> {
+        self.tables.get_owner_balance(owner, coin_type)
+    }
+
+    /// Lowest checkpoint for which the history of objects and dynamic fields is complete.
+    pub fn get_object_history_start(&self) -> Result<CheckpointSequenceNumber, TypedStoreError> {
+        self.tables.get_object_history_start()
+    }
+
+    pub fn get_object_version_at_checkpoint(
+        &self,
+        object_id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<Option<SequenceNumber>, TypedStoreError> {
+        self.tables
+            .get_object_version_at_checkpoint(object_id, checkpoint)
+    }
+
+    pub fn dynamic_field_iter_at_checkpoint(
+        &self,
+        parent: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+        cursor: Option<ObjectID>,
+    ) -> Result<impl Iterator<Item = (DynamicFieldKey, DynamicFieldIndexInfo)> + '_, TypedStoreError>
+    {
+        self.tables
+            .dynamic_field_iter_at_checkpoint(parent, checkpoint, cursor)
+    }
 }
 
 fn try_create_dynamic_field_info(
@@ -622,9 +1111,142 @@ fn try_create_coin_index_info(object: &Object) -> Option<(CoinIndexKey, CoinInde
         })
 }
 
+fn try_create_owner_coin_index_info(
+    object: &Object,
+) -> Option<(OwnerCoinIndexKey, OwnerCoinIndexInfo)> {
+    // Only address owned coins are indexed
+    let Owner::AddressOwner(owner) = object.owner() else {
+        return None;
+    };
+
+    let TypeTag::Struct(coin_type) = object.coin_type_maybe()? else {
+        return None;
+    };
+    let balance = object.as_coin_maybe()?.value();
+
+    Some((
+        OwnerCoinIndexKey::new(*owner, &coin_type, balance, object.id()),
+        OwnerCoinIndexInfo {
+            version: object.version(),
+            digest: object.digest(),
+            balance,
+            previous_transaction: object.previous_transaction,
+        },
+    ))
+}
+
+/// Changes made to the `owner_coin` index by a checkpoint, along with the resulting changes to the
+/// `owner_balance` index.
+///
+/// A checkpoint can end up being indexed more than once, e.g. the checkpoint following the live
+/// object set the index was initialized from, so balances are only adjusted for coins which are
+/// actually added to or removed from the `owner_coin` index.
+#[derive(Default)]
+struct OwnerCoinChanges {
+    coins: HashMap<OwnerCoinIndexKey, Option<OwnerCoinIndexInfo>>,
+    /// Change in the number of coins and the total balance of each owner and coin type
+    balances: HashMap<OwnerBalanceKey, (i128, i128)>,
+}
+
+impl OwnerCoinChanges {
+    fn contains(
+        &self,
+        tables: &IndexStoreTables,
+        key: &OwnerCoinIndexKey,
+    ) -> Result<bool, TypedStoreError> {
+        match self.coins.get(key) {
+            Some(info) => Ok(info.is_some()),
+            None => tables.owner_coin.contains_key(key),
+        }
+    }
+
+    fn remove(
+        &mut self,
+        tables: &IndexStoreTables,
+        key: OwnerCoinIndexKey,
+        balance: u64,
+    ) -> Result<(), TypedStoreError> {
+        if self.contains(tables, &key)? {
+            let (count, total) = self.balances.entry(key.balance_key()).or_default();
+            *count -= 1;
+            *total -= balance as i128;
+        }
+        self.coins.insert(key, None);
+        Ok(())
+    }
+
+    fn insert(
+        &mut self,
+        tables: &IndexStoreTables,
+        key: OwnerCoinIndexKey,
+        info: OwnerCoinIndexInfo,
+    ) -> Result<(), TypedStoreError> {
+        if !self.contains(tables, &key)? {
+            let (count, total) = self.balances.entry(key.balance_key()).or_default();
+            *count += 1;
+            *total += info.balance as i128;
+        }
+        self.coins.insert(key, Some(info));
+        Ok(())
+    }
+
+    fn write(
+        self,
+        tables: &IndexStoreTables,
+        batch: &mut typed_store::rocks::DBBatch,
+    ) -> Result<(), StorageError> {
+        let (inserted, removed): (Vec<_>, Vec<_>) =
+            self.coins.into_iter().partition(|(_, info)| info.is_some());
+        batch.delete_batch(&tables.owner_coin, removed.into_iter().map(|(key, _)| key))?;
+        batch.insert_batch(
+            &tables.owner_coin,
+            inserted
+                .into_iter()
+                .filter_map(|(key, info)| info.map(|info| (key, info))),
+        )?;
+
+        let (keys, deltas): (Vec<_>, Vec<_>) = self
+            .balances
+            .into_iter()
+            .filter(|(_, delta)| *delta != (0, 0))
+            .unzip();
+        let current = tables.owner_balance.multi_get(&keys)?;
+
+        for ((key, (count, total)), current) in keys.into_iter().zip(deltas).zip(current) {
+            let current = current.unwrap_or_default();
+            let coin_object_count = u64::try_from(current.coin_object_count as i128 + count);
+            let total_balance = u128::try_from(current.total_balance as i128 + total);
+            let (Ok(coin_object_count), Ok(total_balance)) = (coin_object_count, total_balance)
+            else {
+                return Err(StorageError::custom(format!(
+                    "balance index for {key:?} became negative"
+                )));
+            };
+
+            if coin_object_count == 0 {
+                batch.delete_batch(&tables.owner_balance, [key])?;
+            } else {
+                batch.insert_batch(
+                    &tables.owner_balance,
+                    [(
+                        key,
+                        OwnerBalanceInfo {
+                            coin_object_count,
+                            total_balance,
+                        },
+                    )],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 struct RestParLiveObjectSetIndexer<'a> {
     tables: &'a IndexStoreTables,
     coin_index: &'a Mutex<HashMap<CoinIndexKey, CoinIndexInfo>>,
+    history_checkpoint: CheckpointSequenceNumber,
     epoch_store: &'a AuthorityPerEpochStore,
     package_store: &'a Arc<dyn BackingPackageStore + Send + Sync>,
 }
@@ -633,6 +1255,7 @@ struct RestLiveObjectIndexer<'a> {
     tables: &'a IndexStoreTables,
     batch: typed_store::rocks::DBBatch,
     coin_index: &'a Mutex<HashMap<CoinIndexKey, CoinIndexInfo>>,
+    history_checkpoint: CheckpointSequenceNumber,
     resolver: Box<dyn LayoutResolver + 'a>,
 }
 
@@ -644,6 +1267,7 @@ impl<'a> ParMakeLiveObjectIndexer for RestParLiveObjectSetIndexer<'a> {
             tables: self.tables,
             batch: self.tables.owner.batch(),
             coin_index: self.coin_index,
+            history_checkpoint: self.history_checkpoint,
             resolver: self
                 .epoch_store
                 .executor()
@@ -654,6 +1278,17 @@ impl<'a> ParMakeLiveObjectIndexer for RestParLiveObjectSetIndexer<'a> {
 
 impl<'a> LiveObjectIndexer for RestLiveObjectIndexer<'a> {
     fn index_object(&mut self, object: Object) -> Result<(), StorageError> {
+        // Object History Index
+        self.batch.insert_batch(
+            &self.tables.object_history,
+            [(
+                ObjectHistoryKey::new(object.id(), self.history_checkpoint),
+                ObjectHistoryInfo {
+                    version: Some(object.version()),
+                },
+            )],
+        )?;
+
         match object.owner {
             // Owner Index
             Owner::AddressOwner(owner) => {
@@ -661,6 +1296,12 @@ impl<'a> LiveObjectIndexer for RestLiveObjectIndexer<'a> {
                 let owner_info = OwnerIndexInfo::new(&object);
                 self.batch
                     .insert_batch(&self.tables.owner, [(owner_key, owner_info)])?;
+
+                // Coin Index
+                if let Some((coin_key, coin_info)) = try_create_owner_coin_index_info(&object) {
+                    self.batch
+                        .insert_batch(&self.tables.owner_coin, [(coin_key, coin_info)])?;
+                }
             }
 
             // Dynamic Field Index
@@ -669,7 +1310,16 @@ impl<'a> LiveObjectIndexer for RestLiveObjectIndexer<'a> {
                     try_create_dynamic_field_info(&object, self.resolver.as_mut())?
                 {
                     let field_key = DynamicFieldKey::new(parent, object.id());
-
+                    let history_key = DynamicFieldHistoryKey::new(
+                        parent.into(),
+                        object.id(),
+                        self.history_checkpoint,
+                    );
+
+                    self.batch.insert_batch(
+                        &self.tables.dynamic_field_history,
+                        [(history_key, Some(field_info.clone()))],
+                    )?;
                     self.batch
                         .insert_batch(&self.tables.dynamic_field, [(field_key, field_info)])?;
                 }
@@ -708,3 +1358,442 @@ impl<'a> LiveObjectIndexer for RestLiveObjectIndexer<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+    use sui_types::dynamic_field::DynamicFieldType;
+    use sui_types::effects::EffectsObjectChange;
+    use sui_types::execution_status::ExecutionStatus;
+    use sui_types::gas::GasCostSummary;
+
+    fn object_id(byte: u8) -> ObjectID {
+        ObjectID::new([byte; ObjectID::LENGTH])
+    }
+
+    fn field_info(name: u64) -> DynamicFieldIndexInfo {
+        DynamicFieldIndexInfo {
+            dynamic_field_type: DynamicFieldType::DynamicField,
+            name_type: TypeTag::U64,
+            name_value: bcs::to_bytes(&name).unwrap(),
+            dynamic_object_id: None,
+        }
+    }
+
+    fn insert_object_history(
+        tables: &IndexStoreTables,
+        object_id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+        version: Option<u64>,
+    ) {
+        tables
+            .object_history
+            .insert(
+                &ObjectHistoryKey::new(object_id, checkpoint),
+                &ObjectHistoryInfo {
+                    version: version.map(SequenceNumber::from_u64),
+                },
+            )
+            .unwrap();
+    }
+
+    fn insert_dynamic_field_history(
+        tables: &IndexStoreTables,
+        parent: ObjectID,
+        field_id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+        name: Option<u64>,
+    ) {
+        tables
+            .dynamic_field_history
+            .insert(
+                &DynamicFieldHistoryKey::new(parent, field_id, checkpoint),
+                &name.map(field_info),
+            )
+            .unwrap();
+    }
+
+    /// Effects of a transaction which deleted the given objects, which were owned by the provided
+    /// owners.
+    fn effects_deleting(objects: &[(ObjectID, Owner)]) -> TransactionEffects {
+        let changed_objects: BTreeMap<_, _> = objects
+            .iter()
+            .map(|(id, owner)| {
+                let old_object = (SequenceNumber::from_u64(1), ObjectDigest::random());
+                (
+                    *id,
+                    EffectsObjectChange::new(Some((old_object, *owner)), None, false, true),
+                )
+            })
+            .collect();
+
+        TransactionEffects::new_from_execution_v2(
+            ExecutionStatus::Success,
+            0,
+            GasCostSummary::default(),
+            vec![],
+            BTreeSet::new(),
+            TransactionDigest::random(),
+            SequenceNumber::from_u64(100),
+            changed_objects,
+            None,
+            None,
+            vec![],
+        )
+    }
+
+    fn object_history_checkpoints(
+        tables: &IndexStoreTables,
+        object_id: ObjectID,
+    ) -> Vec<CheckpointSequenceNumber> {
+        tables
+            .object_history
+            .unbounded_iter()
+            .filter(|(key, _)| key.object_id == object_id)
+            .map(|(key, _)| key.checkpoint)
+            .collect()
+    }
+
+    fn dynamic_fields_at_checkpoint(
+        tables: &IndexStoreTables,
+        parent: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+        cursor: Option<ObjectID>,
+    ) -> Vec<(ObjectID, DynamicFieldIndexInfo)> {
+        tables
+            .dynamic_field_iter_at_checkpoint(parent, checkpoint, cursor)
+            .unwrap()
+            .map(|(key, info)| {
+                assert_eq!(key.parent, parent);
+                (key.field_id, info)
+            })
+            .collect()
+    }
+
+    fn coin_key(
+        owner: SuiAddress,
+        coin_type: &StructTag,
+        balance: u64,
+        id: u8,
+    ) -> OwnerCoinIndexKey {
+        OwnerCoinIndexKey::new(owner, coin_type, balance, object_id(id))
+    }
+
+    fn coin_info(balance: u64) -> OwnerCoinIndexInfo {
+        OwnerCoinIndexInfo {
+            version: SequenceNumber::from_u64(1),
+            digest: ObjectDigest::random(),
+            balance,
+            previous_transaction: TransactionDigest::random(),
+        }
+    }
+
+    fn write_owner_coin_changes(
+        tables: &IndexStoreTables,
+        removed: &[OwnerCoinIndexKey],
+        inserted: &[OwnerCoinIndexKey],
+    ) {
+        let mut changes = OwnerCoinChanges::default();
+        for key in removed {
+            changes
+                .remove(tables, key.clone(), !key.inverted_balance)
+                .unwrap();
+        }
+        for key in inserted {
+            changes
+                .insert(tables, key.clone(), coin_info(!key.inverted_balance))
+                .unwrap();
+        }
+
+        let mut batch = tables.owner_coin.batch();
+        changes.write(tables, &mut batch).unwrap();
+        batch.write().unwrap();
+    }
+
+    fn owner_balances(tables: &IndexStoreTables, owner: SuiAddress) -> Vec<(String, u64, u128)> {
+        tables
+            .owner_balance_iter(owner)
+            .unwrap()
+            .map(|(key, info)| (key.coin_type, info.coin_object_count, info.total_balance))
+            .collect()
+    }
+
+    fn owner_coins(
+        tables: &IndexStoreTables,
+        owner: SuiAddress,
+        coin_type: &StructTag,
+        cursor: Option<(u64, ObjectID)>,
+    ) -> Vec<(u64, ObjectID)> {
+        tables
+            .owner_coin_iter(owner, coin_type, cursor)
+            .unwrap()
+            .map(|(key, info)| (info.balance, key.object_id))
+            .collect()
+    }
+
+    #[test]
+    fn owner_coins_and_balances() {
+        use sui_types::gas_coin::GAS;
+
+        let dir = tempfile::tempdir().unwrap();
+        let tables = IndexStoreTables::open(dir.path());
+        let owner = SuiAddress::random_for_testing_only();
+        let other_owner = SuiAddress::random_for_testing_only();
+        let sui = GAS::type_();
+        let other_coin = StructTag::from_str("0x1234::my_coin::MY_COIN").unwrap();
+        let sui_type = sui.to_canonical_string(true);
+        let other_type = other_coin.to_canonical_string(true);
+
+        let coins = [
+            coin_key(owner, &sui, 10, 1),
+            coin_key(owner, &sui, 30, 2),
+            coin_key(owner, &sui, 10, 3),
+            coin_key(owner, &sui, 20, 4),
+            coin_key(owner, &other_coin, 5, 5),
+            coin_key(other_owner, &sui, 100, 6),
+        ];
+        write_owner_coin_changes(&tables, &[], &coins);
+
+        // Coins are ordered from greatest to least balance, then by id
+        let all_coins = [
+            (30, object_id(2)),
+            (20, object_id(4)),
+            (10, object_id(1)),
+            (10, object_id(3)),
+        ];
+        assert_eq!(owner_coins(&tables, owner, &sui, None), all_coins);
+        assert_eq!(
+            owner_coins(&tables, owner, &sui, Some((10, object_id(3)))),
+            all_coins[3..]
+        );
+        assert_eq!(
+            owner_coins(&tables, owner, &sui, Some((20, object_id(4)))),
+            all_coins[1..]
+        );
+        assert_eq!(
+            owner_coins(&tables, owner, &other_coin, None),
+            [(5, object_id(5))]
+        );
+
+        let expected = vec![(sui_type.clone(), 4, 70), (other_type.clone(), 1, 5)];
+        assert_eq!(owner_balances(&tables, owner), expected);
+        assert_eq!(
+            owner_balances(&tables, other_owner),
+            [(sui_type.clone(), 1, 100)]
+        );
+        assert_eq!(
+            tables.get_owner_balance(owner, &sui).unwrap(),
+            Some(OwnerBalanceInfo {
+                coin_object_count: 4,
+                total_balance: 70,
+            })
+        );
+
+        // Re-indexing the same changes doesn't affect the balances
+        write_owner_coin_changes(&tables, &[], &coins);
+        assert_eq!(owner_balances(&tables, owner), expected);
+
+        // A coin whose balance changed within the same checkpoint, and a coin transferred away
+        let split = coin_key(owner, &sui, 25, 2);
+        write_owner_coin_changes(
+            &tables,
+            &[coins[1].clone(), coins[4].clone()],
+            &[split.clone(), coin_key(other_owner, &other_coin, 5, 5)],
+        );
+        assert_eq!(
+            owner_coins(&tables, owner, &sui, None),
+            [
+                (25, object_id(2)),
+                (20, object_id(4)),
+                (10, object_id(1)),
+                (10, object_id(3)),
+            ]
+        );
+        assert_eq!(owner_balances(&tables, owner), [(sui_type.clone(), 4, 65)]);
+        assert_eq!(tables.get_owner_balance(owner, &other_coin).unwrap(), None);
+        let expected = vec![(sui_type.clone(), 1, 100), (other_type, 1, 5)];
+        assert_eq!(owner_balances(&tables, other_owner), expected);
+
+        // Removing coins which were already removed doesn't affect the balances either
+        write_owner_coin_changes(&tables, &[coins[1].clone()], &[]);
+        assert_eq!(owner_balances(&tables, owner), [(sui_type.clone(), 4, 65)]);
+
+        write_owner_coin_changes(
+            &tables,
+            &[split, coins[0].clone(), coins[2].clone(), coins[3].clone()],
+            &[],
+        );
+        assert_eq!(owner_balances(&tables, owner), []);
+        assert_eq!(owner_coins(&tables, owner, &sui, None), []);
+
+        // Balances are rebuilt from the coin index when initializing
+        tables.owner_balance.unsafe_clear().unwrap();
+        tables.init_owner_balances().unwrap();
+        assert_eq!(owner_balances(&tables, owner), []);
+        assert_eq!(owner_balances(&tables, other_owner), expected);
+    }
+
+    #[test]
+    fn object_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let tables = IndexStoreTables::open(dir.path());
+        let object = object_id(1);
+        let other_object = object_id(2);
+
+        // Mutated in checkpoint 2, deleted in checkpoint 3 and re-added in checkpoint 5
+        insert_object_history(&tables, object, 1, Some(1));
+        insert_object_history(&tables, object, 2, Some(2));
+        insert_object_history(&tables, object, 3, None);
+        insert_object_history(&tables, object, 5, Some(5));
+        insert_object_history(&tables, object, 8, Some(8));
+        insert_object_history(&tables, other_object, 1, Some(1));
+
+        let version_at = |checkpoint| {
+            tables
+                .get_object_version_at_checkpoint(object, checkpoint)
+                .unwrap()
+                .map(|version| version.value())
+        };
+        let expected = [
+            None,
+            Some(1),
+            Some(2),
+            None,
+            None,
+            Some(5),
+            Some(5),
+            Some(5),
+            Some(8),
+        ];
+        for (checkpoint, version) in expected.into_iter().enumerate() {
+            assert_eq!(version_at(checkpoint as u64), version, "{checkpoint}");
+        }
+        assert_eq!(version_at(u64::MAX), Some(8));
+        assert_eq!(
+            tables
+                .get_object_version_at_checkpoint(object_id(3), 8)
+                .unwrap(),
+            None
+        );
+
+        let effects = [effects_deleting(&[(
+            object,
+            Owner::AddressOwner(SuiAddress::ZERO),
+        )])];
+
+        // Only the state as of the watermark is kept
+        tables.prune(2, &[], &effects).unwrap();
+        assert_eq!(object_history_checkpoints(&tables, object), [2, 3, 5, 8]);
+        assert_eq!(version_at(2), Some(2));
+
+        // An object which didn't exist at the watermark has no entry left for it
+        tables.prune(4, &[], &effects).unwrap();
+        assert_eq!(object_history_checkpoints(&tables, object), [5, 8]);
+        assert_eq!(version_at(4), None);
+
+        tables.prune(6, &[], &effects).unwrap();
+        assert_eq!(object_history_checkpoints(&tables, object), [5, 8]);
+        for checkpoint in 5..=7 {
+            assert_eq!(version_at(checkpoint), Some(5));
+        }
+        assert_eq!(version_at(8), Some(8));
+
+        // Objects untouched by the pruned transactions are left alone
+        assert_eq!(object_history_checkpoints(&tables, other_object), [1]);
+    }
+
+    #[test]
+    fn dynamic_field_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let tables = IndexStoreTables::open(dir.path());
+        let parent = object_id(0x10);
+        let other_parent = object_id(0x20);
+        let field_a = object_id(1);
+        let field_b = object_id(2);
+
+        // `field_a` is removed in checkpoint 3 and re-added with another name in checkpoint 5
+        insert_dynamic_field_history(&tables, parent, field_a, 1, Some(1));
+        insert_dynamic_field_history(&tables, parent, field_a, 3, None);
+        insert_dynamic_field_history(&tables, parent, field_a, 5, Some(5));
+        insert_dynamic_field_history(&tables, parent, field_b, 2, Some(2));
+        insert_dynamic_field_history(&tables, other_parent, field_a, 1, Some(1));
+
+        assert_eq!(dynamic_fields_at_checkpoint(&tables, parent, 0, None), []);
+        assert_eq!(
+            dynamic_fields_at_checkpoint(&tables, parent, 1, None),
+            [(field_a, field_info(1))]
+        );
+        assert_eq!(
+            dynamic_fields_at_checkpoint(&tables, parent, 2, None),
+            [(field_a, field_info(1)), (field_b, field_info(2))]
+        );
+        for checkpoint in 3..=4 {
+            assert_eq!(
+                dynamic_fields_at_checkpoint(&tables, parent, checkpoint, None),
+                [(field_b, field_info(2))]
+            );
+        }
+        assert_eq!(
+            dynamic_fields_at_checkpoint(&tables, parent, 5, None),
+            [(field_a, field_info(5)), (field_b, field_info(2))]
+        );
+        assert_eq!(
+            dynamic_fields_at_checkpoint(&tables, parent, 5, Some(field_b)),
+            [(field_b, field_info(2))]
+        );
+
+        let effects = [effects_deleting(&[
+            (field_a, Owner::ObjectOwner(parent.into())),
+            (field_b, Owner::ObjectOwner(parent.into())),
+        ])];
+        tables.prune(4, &[], &effects).unwrap();
+
+        let remaining: Vec<_> = tables
+            .dynamic_field_history
+            .unbounded_iter()
+            .map(|(key, _)| (key.parent, key.field_id, key.checkpoint))
+            .collect();
+        assert_eq!(
+            remaining,
+            [
+                (parent, field_a, 5),
+                (parent, field_b, 2),
+                (other_parent, field_a, 1)
+            ]
+        );
+        assert_eq!(
+            dynamic_fields_at_checkpoint(&tables, parent, 4, None),
+            [(field_b, field_info(2))]
+        );
+        assert_eq!(
+            dynamic_fields_at_checkpoint(&tables, parent, 5, None),
+            [(field_a, field_info(5)), (field_b, field_info(2))]
+        );
+        assert_eq!(
+            dynamic_fields_at_checkpoint(&tables, other_parent, 4, None),
+            [(field_a, field_info(1))]
+        );
+    }
+
+    #[test]
+    fn object_history_start() {
+        let dir = tempfile::tempdir().unwrap();
+        let tables = IndexStoreTables::open(dir.path());
+        assert_eq!(tables.get_object_history_start().unwrap(), 0);
+
+        // Nothing prior to the checkpoint the live object set was indexed at can be answered
+        tables
+            .meta
+            .insert(
+                &(),
+                &MetadataInfo {
+                    version: CURRENT_DB_VERSION,
+                    object_history_start: 7,
+                },
+            )
+            .unwrap();
+        assert_eq!(tables.get_object_history_start().unwrap(), 7);
+    }
+}
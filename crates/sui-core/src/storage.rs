@@ -5,6 +5,7 @@ use move_core_types::language_storage::StructTag;
 use parking_lot::Mutex;
 use std::sync::Arc;
 use sui_types::base_types::ObjectID;
+use sui_types::base_types::SequenceNumber;
 use sui_types::base_types::SuiAddress;
 use sui_types::base_types::TransactionDigest;
 use sui_types::committee::Committee;
@@ -20,8 +21,11 @@ use sui_types::messages_checkpoint::FullCheckpointContents;
 use sui_types::messages_checkpoint::VerifiedCheckpoint;
 use sui_types::messages_checkpoint::VerifiedCheckpointContents;
 use sui_types::object::Object;
+use sui_types::parse_sui_struct_tag;
 use sui_types::storage::error::Error as StorageError;
 use sui_types::storage::error::Result;
+use sui_types::storage::AccountBalanceInfo;
+use sui_types::storage::AccountCoinInfo;
 use sui_types::storage::AccountOwnedObjectInfo;
 use sui_types::storage::CoinInfo;
 use sui_types::storage::DynamicFieldIndexInfo;
@@ -38,6 +42,8 @@ use crate::checkpoints::CheckpointStore;
 use crate::epoch::committee_store::CommitteeStore;
 use crate::execution_cache::ExecutionCacheTraitPointers;
 use crate::rest_index::CoinIndexInfo;
+use crate::rest_index::OwnerCoinIndexInfo;
+use crate::rest_index::OwnerCoinIndexKey;
 use crate::rest_index::OwnerIndexInfo;
 use crate::rest_index::OwnerIndexKey;
 use crate::rest_index::RestIndexStore;
@@ -527,6 +533,18 @@ impl RestStateReader for RestReadStore {
         }
     }
 
+    fn get_lowest_available_checkpoint_object_history(
+        &self,
+    ) -> sui_types::storage::error::Result<CheckpointSequenceNumber> {
+        // Resolving a historical version requires the history index, which is collapsed as
+        // checkpoints are pruned, as well as the object data itself, which is pruned separately.
+        let object_history_start = self.index()?.get_object_history_start()?;
+
+        Ok(object_history_start
+            .max(self.get_lowest_available_checkpoint()?)
+            .max(self.get_lowest_available_checkpoint_objects()?))
+    }
+
     fn get_chain_identifier(
         &self,
     ) -> sui_types::storage::error::Result<sui_types::digests::ChainIdentifier> {
@@ -566,6 +584,31 @@ impl RestStateReader for RestReadStore {
         Ok(Box::new(iter) as _)
     }
 
+    fn get_object_version_at_checkpoint(
+        &self,
+        object_id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> sui_types::storage::error::Result<Option<SequenceNumber>> {
+        self.index()?
+            .get_object_version_at_checkpoint(object_id, checkpoint)
+            .map_err(Into::into)
+    }
+
+    fn dynamic_field_iter_at_checkpoint(
+        &self,
+        parent: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+        cursor: Option<ObjectID>,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = (DynamicFieldKey, DynamicFieldIndexInfo)> + '_>,
+    > {
+        let iter = self
+            .index()?
+            .dynamic_field_iter_at_checkpoint(parent, checkpoint, cursor)?;
+
+        Ok(Box::new(iter) as _)
+    }
+
     fn get_coin_info(
         &self,
         coin_type: &StructTag,
@@ -583,4 +626,65 @@ impl RestStateReader for RestReadStore {
             )
             .pipe(Ok)
     }
+
+    fn account_coins_iter(
+        &self,
+        owner: SuiAddress,
+        coin_type: &StructTag,
+        cursor: Option<(u64, ObjectID)>,
+    ) -> Result<Box<dyn Iterator<Item = AccountCoinInfo> + '_>> {
+        let iter = self
+            .index()?
+            .owner_coin_iter(owner, coin_type, cursor)?
+            .map(
+                |(
+                    OwnerCoinIndexKey { object_id, .. },
+                    OwnerCoinIndexInfo {
+                        version,
+                        digest,
+                        balance,
+                        previous_transaction,
+                    },
+                )| AccountCoinInfo {
+                    object_id,
+                    version,
+                    digest,
+                    balance,
+                    previous_transaction,
+                },
+            );
+
+        Ok(Box::new(iter) as _)
+    }
+
+    fn get_account_balances(&self, owner: SuiAddress) -> Result<Vec<AccountBalanceInfo>> {
+        self.index()?
+            .owner_balance_iter(owner)?
+            .map(|(key, info)| {
+                Ok(AccountBalanceInfo {
+                    coin_type: parse_sui_struct_tag(&key.coin_type)
+                        .map_err(StorageError::custom)?,
+                    coin_object_count: info.coin_object_count,
+                    total_balance: info.total_balance,
+                })
+            })
+            .collect()
+    }
+
+    fn get_account_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: &StructTag,
+    ) -> Result<AccountBalanceInfo> {
+        let info = self
+            .index()?
+            .get_owner_balance(owner, coin_type)?
+            .unwrap_or_default();
+
+        Ok(AccountBalanceInfo {
+            coin_type: coin_type.to_owned(),
+            coin_object_count: info.coin_object_count,
+            total_balance: info.total_balance,
+        })
+    }
 }
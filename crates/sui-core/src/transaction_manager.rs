@@ -27,6 +27,7 @@ use tokio::sync::mpsc::UnboundedSender;
 use tokio::time::Instant;
 use tracing::{error, info, instrument, trace, warn};
 
+use crate::transaction_trace::TransactionStage;
 use crate::{
     authority::authority_per_epoch_store::AuthorityPerEpochStore, execution_cache::ObjectCacheRead,
 };
@@ -778,6 +779,10 @@ impl TransactionManager {
             .executing_certificates
             .insert(*pending_certificate.certificate.digest()));
         self.metrics.txn_ready_rate_tracker.lock().record();
+        self.metrics.transaction_tracer.record(
+            pending_certificate.certificate.digest(),
+            TransactionStage::Ready,
+        );
         let _ = self.tx_ready_certificates.send(pending_certificate);
         self.metrics.transaction_manager_num_ready.inc();
         self.metrics.execution_driver_dispatch_queue.inc();
@@ -35,7 +35,7 @@ use sui_types::error::{SuiError, SuiResult};
 use sui_types::quorum_driver_types::{
     ExecuteTransactionRequestType, ExecuteTransactionRequestV3, ExecuteTransactionResponseV3,
     FinalizedEffects, IsTransactionExecutedLocally, QuorumDriverEffectsQueueResult,
-    QuorumDriverError, QuorumDriverResponse, QuorumDriverResult,
+    QuorumDriverError, QuorumDriverResponse, QuorumDriverResult, SubmissionStatus,
 };
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::transaction::{TransactionData, VerifiedTransaction};
@@ -43,7 +43,7 @@ use sui_types::transaction_executor::SimulateTransactionResult;
 use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::broadcast::Receiver;
 use tokio::task::JoinHandle;
-use tokio::time::timeout;
+use tokio::time::{sleep, timeout};
 use tracing::{debug, error, error_span, info, instrument, warn, Instrument};
 
 // How long to wait for local execution (including parents) before a timeout
@@ -52,6 +52,13 @@ const LOCAL_EXECUTION_TIMEOUT: Duration = Duration::from_secs(10);
 
 const WAIT_FOR_FINALITY_TIMEOUT: Duration = Duration::from_secs(30);
 
+// How often to check whether the epoch changed, to retry transactions which failed with
+// transient errors.
+const EPOCH_CHANGE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+// How long the submission status of a finished transaction can be queried.
+const SUBMISSION_STATUS_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 pub struct TransactiondOrchestrator<A: Clone> {
     quorum_driver_handler: Arc<QuorumDriverHandler<A>>,
     validator_state: Arc<AuthorityState>,
@@ -101,18 +108,19 @@ where
         let metrics = Arc::new(QuorumDriverMetrics::new(prometheus_registry));
         let notifier = Arc::new(NotifyRead::new());
         let reconfig_observer = Arc::new(reconfig_observer);
+        let pending_tx_log = Arc::new(WritePathPendingTransactionLog::new(
+            parent_path.join("fullnode_pending_transactions"),
+        ));
         let quorum_driver_handler = Arc::new(
             QuorumDriverHandlerBuilder::new(validators.clone(), metrics.clone())
                 .with_notifier(notifier.clone())
                 .with_reconfig_observer(reconfig_observer.clone())
+                .with_submission_log(pending_tx_log.clone())
                 .start(),
         );
 
         let effects_receiver = quorum_driver_handler.subscribe_to_effects();
         let metrics = Arc::new(TransactionOrchestratorMetrics::new(prometheus_registry));
-        let pending_tx_log = Arc::new(WritePathPendingTransactionLog::new(
-            parent_path.join("fullnode_pending_transactions"),
-        ));
         let pending_tx_log_clone = pending_tx_log.clone();
         let _local_executor_handle = {
             spawn_monitored_task!(async move {
@@ -120,6 +128,10 @@ where
             })
         };
         Self::schedule_txes_in_log(pending_tx_log.clone(), quorum_driver_handler.clone());
+        Self::schedule_queued_txes_on_epoch_change(
+            pending_tx_log.clone(),
+            quorum_driver_handler.clone(),
+        );
         Self {
             quorum_driver_handler,
             validator_state,
@@ -259,14 +271,20 @@ where
             in_flight.dec();
         });
 
-        let ticket = self
-            .submit(transaction.clone(), request, client_addr)
+        let ticket = match self
+            .submit(transaction.clone(), request, client_addr, false)
             .await
-            .map_err(|e| {
+        {
+            Ok(ticket) => ticket,
+            Err(e) => {
                 warn!(?tx_digest, "QuorumDriverInternalError: {e:?}");
-                QuorumDriverError::QuorumDriverInternalError(e)
-            })?;
+                self.finish_failed_transaction(&tx_digest);
+                return Err(QuorumDriverError::QuorumDriverInternalError(e));
+            }
+        };
 
+        // Quorum driver keeps processing the transaction after a timeout, and its result
+        // finishes the transaction in the pending transaction log.
         let Ok(result) = timeout(WAIT_FOR_FINALITY_TIMEOUT, ticket).await else {
             debug!(?tx_digest, "Timeout waiting for transaction finality.");
             self.metrics.wait_for_finality_timeout.inc();
@@ -281,9 +299,13 @@ where
         match result {
             Err(err) => {
                 warn!(?tx_digest, "QuorumDriverInternalError: {err:?}");
+                self.finish_failed_transaction(&tx_digest);
                 Err(QuorumDriverError::QuorumDriverInternalError(err))
             }
-            Ok(Err(err)) => Err(err),
+            Ok(Err(err)) => {
+                self.finish_failed_transaction(&tx_digest);
+                Err(err)
+            }
             Ok(Ok(response)) => {
                 good_response_metrics.inc();
                 Ok((transaction, response))
@@ -291,24 +313,60 @@ where
         }
     }
 
+    /// Removes a synchronously executed transaction which failed from the pending transaction
+    /// log, so that it is not submitted again after a restart. Queued transactions stay in the
+    /// log until the effects queue records their result.
+    fn finish_failed_transaction(&self, tx_digest: &TransactionDigest) {
+        match self.pending_tx_log.is_queued(tx_digest) {
+            Ok(true) => {}
+            Ok(false) => {
+                if let Err(err) = self.pending_tx_log.finish_transaction(tx_digest) {
+                    error!(
+                        ?tx_digest,
+                        "Failed to finish transaction in pending transaction log: {err}"
+                    );
+                }
+            }
+            Err(err) => {
+                error!(?tx_digest, "Failed to read pending transaction log: {err}");
+            }
+        }
+    }
+
     /// Submits the transaction to Quorum Driver for execution.
-    /// Returns an awaitable Future.
+    /// Returns an awaitable Future. Queued transactions have their submission status tracked
+    /// and are retried in the next epoch after transient errors.
     #[instrument(name = "tx_orchestrator_submit", level = "trace", skip_all)]
     async fn submit(
         &self,
         transaction: VerifiedTransaction,
         request: ExecuteTransactionRequestV3,
         client_addr: Option<SocketAddr>,
+        queued: bool,
     ) -> SuiResult<impl Future<Output = SuiResult<QuorumDriverResult>> + '_> {
         let tx_digest = *transaction.digest();
         let ticket = self.notifier.register_one(&tx_digest);
         // TODO(william) need to also write client adr to pending tx log below
         // so that we can re-execute with this client addr if we restart
-        if self
-            .pending_tx_log
-            .write_pending_transaction_maybe(&transaction)
-            .await?
-        {
+        let is_first_record = if queued {
+            self.pending_tx_log
+                .write_queued_transaction_maybe(&transaction)
+                .await?
+        } else {
+            self.pending_tx_log
+                .write_pending_transaction_maybe(&transaction)
+                .await?
+        };
+        // Transactions which failed with transient errors wait in the log for the next epoch,
+        // unless they are submitted again.
+        let is_waiting_for_next_epoch = !is_first_record
+            && matches!(
+                self.pending_tx_log.get_status(&tx_digest)?,
+                Some(SubmissionStatus::Queued {
+                    last_error: Some(_)
+                })
+            );
+        if is_first_record || is_waiting_for_next_epoch {
             debug!(?tx_digest, "no pending request in flight, submitting.");
             self.quorum_driver()
                 .submit_transaction_no_ticket(request.clone(), client_addr)
@@ -341,6 +399,40 @@ where
         })
     }
 
+    /// Submits the transaction to the durable submission queue, without waiting for it to be
+    /// finalized. Queued transactions are retried after fullnode restarts and, when they fail with
+    /// transient errors, in the next epoch. Their progress can be tracked by digest through
+    /// `get_submission_status`.
+    #[instrument(name = "tx_orchestrator_enqueue_transaction", level = "debug", skip_all,
+                 fields(tx_digest = ?request.transaction.digest()), err)]
+    pub async fn enqueue_transaction(
+        &self,
+        request: ExecuteTransactionRequestV3,
+        client_addr: Option<SocketAddr>,
+    ) -> Result<TransactionDigest, QuorumDriverError> {
+        let epoch_store = self.validator_state.load_epoch_store_one_call_per_task();
+        let transaction = epoch_store
+            .verify_transaction(request.transaction.clone())
+            .map_err(QuorumDriverError::InvalidUserSignature)?;
+        let tx_digest = *transaction.digest();
+        // The result is recorded in the submission status, so there is no need to wait for it.
+        let _ticket = self
+            .submit(transaction, request, client_addr, true)
+            .await
+            .map_err(|e| {
+                warn!(?tx_digest, "QuorumDriverInternalError: {e:?}");
+                QuorumDriverError::QuorumDriverInternalError(e)
+            })?;
+        Ok(tx_digest)
+    }
+
+    pub fn get_submission_status(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> SuiResult<Option<SubmissionStatus>> {
+        self.pending_tx_log.get_status(tx_digest)
+    }
+
     #[instrument(name = "tx_orchestrator_wait_for_finalized_tx_executed_locally_with_timeout", level = "debug", skip_all, fields(tx_digest = ?transaction.digest()), err)]
     async fn wait_for_finalized_tx_executed_locally_with_timeout(
         validator_state: &Arc<AuthorityState>,
@@ -408,8 +500,15 @@ where
     ) {
         loop {
             match effects_receiver.recv().await {
-                Ok(Ok((transaction, ..))) => {
+                Ok(Ok((transaction, response))) => {
                     let tx_digest = transaction.digest();
+                    Self::update_submission_status(
+                        &pending_transaction_log,
+                        tx_digest,
+                        SubmissionStatus::Executed {
+                            effects_digest: *response.effects_cert.digest(),
+                        },
+                    );
                     if let Err(err) = pending_transaction_log.finish_transaction(tx_digest) {
                         error!(
                             ?tx_digest,
@@ -417,7 +516,32 @@ where
                         );
                     }
                 }
-                Ok(Err((tx_digest, _err))) => {
+                Ok(Err((tx_digest, qd_error))) => {
+                    // Only queued transactions wait in the log for the next epoch, the callers
+                    // of synchronous execution already received the error.
+                    if Self::is_retryable_in_next_epoch(&qd_error)
+                        && Self::is_queued(&pending_transaction_log, &tx_digest)
+                    {
+                        debug!(
+                            ?tx_digest,
+                            "Transaction will be retried in the next epoch: {qd_error}"
+                        );
+                        Self::update_submission_status(
+                            &pending_transaction_log,
+                            &tx_digest,
+                            SubmissionStatus::Queued {
+                                last_error: Some(qd_error.to_string()),
+                            },
+                        );
+                        continue;
+                    }
+                    Self::update_submission_status(
+                        &pending_transaction_log,
+                        &tx_digest,
+                        SubmissionStatus::Failed {
+                            reason: qd_error.to_string(),
+                        },
+                    );
                     if let Err(err) = pending_transaction_log.finish_transaction(&tx_digest) {
                         error!(
                             ?tx_digest,
@@ -436,6 +560,40 @@ where
         }
     }
 
+    fn update_submission_status(
+        pending_transaction_log: &WritePathPendingTransactionLog,
+        tx_digest: &TransactionDigest,
+        status: SubmissionStatus,
+    ) {
+        if let Err(err) = pending_transaction_log.update_status(tx_digest, status) {
+            error!(?tx_digest, "Failed to update submission status: {err}");
+        }
+    }
+
+    fn is_queued(
+        pending_transaction_log: &WritePathPendingTransactionLog,
+        tx_digest: &TransactionDigest,
+    ) -> bool {
+        pending_transaction_log
+            .is_queued(tx_digest)
+            .unwrap_or_else(|err| {
+                error!(?tx_digest, "Failed to read pending transaction log: {err}");
+                false
+            })
+    }
+
+    // Object locks and validator load are reset at epoch boundaries, so these errors may not
+    // happen again in the next epoch.
+    fn is_retryable_in_next_epoch(error: &QuorumDriverError) -> bool {
+        matches!(
+            error,
+            QuorumDriverError::ObjectsDoubleUsed { .. }
+                | QuorumDriverError::FailedWithTransientErrorAfterMaximumAttempts { .. }
+                | QuorumDriverError::SystemOverload { .. }
+                | QuorumDriverError::SystemOverloadRetryAfter { .. }
+        )
+    }
+
     pub fn quorum_driver(&self) -> &Arc<QuorumDriverHandler<A>> {
         &self.quorum_driver_handler
     }
@@ -528,6 +686,58 @@ where
         });
     }
 
+    /// Submits the queued transactions which failed with transient errors again when the epoch
+    /// changes, and prunes the submission statuses of transactions which finished long ago.
+    fn schedule_queued_txes_on_epoch_change(
+        pending_tx_log: Arc<WritePathPendingTransactionLog>,
+        quorum_driver: Arc<QuorumDriverHandler<A>>,
+    ) {
+        spawn_logged_monitored_task!(async move {
+            let mut epoch = quorum_driver.current_epoch();
+            loop {
+                sleep(EPOCH_CHANGE_POLL_INTERVAL).await;
+                let current_epoch = quorum_driver.current_epoch();
+                if current_epoch == epoch {
+                    continue;
+                }
+                epoch = current_epoch;
+
+                match pending_tx_log.prune_statuses(SUBMISSION_STATUS_RETENTION) {
+                    Ok(pruned) => debug!("Pruned {pruned} submission statuses."),
+                    Err(err) => warn!("Failed to prune submission statuses: {err}"),
+                }
+
+                for tx in pending_tx_log.load_all_pending_transactions() {
+                    let tx_digest = *tx.digest();
+                    if !Self::is_queued(&pending_tx_log, &tx_digest)
+                        || !matches!(
+                            pending_tx_log.get_status(&tx_digest),
+                            Ok(Some(SubmissionStatus::Queued {
+                                last_error: Some(_)
+                            }))
+                        )
+                    {
+                        continue;
+                    }
+                    if let Err(err) = quorum_driver
+                        .submit_transaction_no_ticket(
+                            ExecuteTransactionRequestV3::new_v2(tx.into_inner()),
+                            None,
+                        )
+                        .await
+                    {
+                        warn!(
+                            ?tx_digest,
+                            "Failed to retry transaction in epoch {epoch}, err: {err:?}"
+                        );
+                    } else {
+                        debug!(?tx_digest, "Retrying transaction in epoch {epoch}");
+                    }
+                }
+            }
+        });
+    }
+
     pub fn load_all_pending_transactions(&self) -> Vec<VerifiedTransaction> {
         self.pending_tx_log.load_all_pending_transactions()
     }
@@ -711,6 +921,21 @@ where
         self.execute_transaction_v3(request, client_addr).await
     }
 
+    async fn submit_transaction(
+        &self,
+        request: ExecuteTransactionRequestV3,
+        client_addr: Option<std::net::SocketAddr>,
+    ) -> Result<TransactionDigest, QuorumDriverError> {
+        self.enqueue_transaction(request, client_addr).await
+    }
+
+    fn get_submission_status(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Result<Option<SubmissionStatus>, SuiError> {
+        TransactiondOrchestrator::get_submission_status(self, digest)
+    }
+
     fn simulate_transaction(
         &self,
         transaction: TransactionData,
@@ -0,0 +1,200 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-transaction traces, recording when a sampled transaction reaches each stage of its
+//! processing by this validator, from receiving it to executing it.
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+use sui_types::digests::TransactionDigest;
+use tracing::{info, info_span, Span};
+
+const MAX_TRACES: usize = 10_000;
+
+// Sample rates are stored in parts per million, so they can be read without locking.
+const SAMPLE_RATE_SCALE: u32 = 1_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionStage {
+    /// `authority_server` received the transaction for signing.
+    TransactionReceived,
+    /// The transaction was signed.
+    TransactionSigned,
+    /// `authority_server` received the certificate for execution.
+    CertificateReceived,
+    /// `consensus_adapter` submitted the transaction to consensus.
+    SubmittedToConsensus,
+    /// `consensus_handler` received the transaction sequenced by consensus.
+    Sequenced,
+    /// `transaction_manager` found all inputs of the transaction available.
+    Ready,
+    /// `execution_driver` started executing the transaction.
+    ExecutionStarted,
+    /// The transaction was executed.
+    Executed,
+}
+
+#[derive(Clone, Debug)]
+pub struct TransactionTrace {
+    pub digest: TransactionDigest,
+    /// When the first stage of the transaction was recorded.
+    pub start_time: SystemTime,
+    /// The stages reached by the transaction, with the time elapsed since the first stage.
+    pub stages: Vec<(TransactionStage, Duration)>,
+}
+
+struct ActiveTrace {
+    start: Instant,
+    trace: TransactionTrace,
+    // Covers the transaction from its first stage until it is executed, with an event for each
+    // stage. The span is exported when it is dropped.
+    span: Option<Span>,
+}
+
+/// Samples transactions by digest, so that every stage of a transaction makes the same sampling
+/// decision, and keeps the traces of the most recent sampled transactions.
+pub struct TransactionTracer {
+    sample_rate: AtomicU32,
+    traces: Mutex<LruCache<TransactionDigest, ActiveTrace>>,
+}
+
+impl Default for TransactionTracer {
+    fn default() -> Self {
+        Self {
+            sample_rate: AtomicU32::new(0),
+            traces: Mutex::new(LruCache::new(NonZeroUsize::new(MAX_TRACES).unwrap())),
+        }
+    }
+}
+
+impl TransactionTracer {
+    /// Sets the fraction of transactions which are traced, between 0 (disabled) and 1.
+    pub fn set_sample_rate(&self, sample_rate: f64) {
+        let sample_rate = (sample_rate.clamp(0.0, 1.0) * SAMPLE_RATE_SCALE as f64) as u32;
+        self.sample_rate.store(sample_rate, Ordering::Relaxed);
+    }
+
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate.load(Ordering::Relaxed) as f64 / SAMPLE_RATE_SCALE as f64
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.sample_rate.load(Ordering::Relaxed) > 0
+    }
+
+    fn is_sampled(&self, digest: &TransactionDigest) -> bool {
+        // Digests are uniformly distributed, so their prefix can be used directly.
+        let prefix = u64::from_le_bytes(digest.inner()[..8].try_into().unwrap());
+        prefix % (SAMPLE_RATE_SCALE as u64) < self.sample_rate.load(Ordering::Relaxed) as u64
+    }
+
+    /// Records that the transaction reached `stage`, if it is sampled. A trace starts with the
+    /// first stage recorded for the transaction, which depends on how it reached this validator.
+    pub fn record(&self, digest: &TransactionDigest, stage: TransactionStage) {
+        if !self.is_enabled() || !self.is_sampled(digest) {
+            return;
+        }
+
+        let mut traces = self.traces.lock();
+        if !traces.contains(digest) {
+            traces.put(
+                *digest,
+                ActiveTrace {
+                    start: Instant::now(),
+                    trace: TransactionTrace {
+                        digest: *digest,
+                        start_time: SystemTime::now(),
+                        stages: vec![],
+                    },
+                    // The transaction is already sampled, so the span bypasses the sampling of
+                    // the exporter (see `telemetry_subscribers::FORCE_SAMPLE_FIELD`).
+                    span: Some(info_span!(
+                        parent: None,
+                        "transaction_trace",
+                        tx_digest = ?digest,
+                        force_sample = true
+                    )),
+                },
+            );
+        }
+        let active = traces.get_mut(digest).unwrap();
+        if active
+            .trace
+            .stages
+            .iter()
+            .any(|(recorded, _)| *recorded == stage)
+        {
+            return;
+        }
+
+        let elapsed = active.start.elapsed();
+        active.trace.stages.push((stage, elapsed));
+        if let Some(span) = &active.span {
+            span.in_scope(|| info!(?stage, ?elapsed, "transaction stage"));
+        }
+        if stage == TransactionStage::Executed {
+            active.span = None;
+        }
+    }
+
+    pub fn get(&self, digest: &TransactionDigest) -> Option<TransactionTrace> {
+        self.traces
+            .lock()
+            .peek(digest)
+            .map(|active| active.trace.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_sampled_stages() {
+        let tracer = TransactionTracer::default();
+        let digest = TransactionDigest::random();
+
+        // Nothing is traced while sampling is disabled.
+        tracer.record(&digest, TransactionStage::CertificateReceived);
+        assert!(tracer.get(&digest).is_none());
+
+        tracer.set_sample_rate(1.0);
+        tracer.record(&digest, TransactionStage::CertificateReceived);
+        tracer.record(&digest, TransactionStage::Sequenced);
+        // Stages are only recorded the first time they are reached.
+        tracer.record(&digest, TransactionStage::Sequenced);
+        tracer.record(&digest, TransactionStage::Executed);
+
+        let trace = tracer.get(&digest).unwrap();
+        let stages: Vec<_> = trace.stages.iter().map(|(stage, _)| *stage).collect();
+        assert_eq!(
+            stages,
+            vec![
+                TransactionStage::CertificateReceived,
+                TransactionStage::Sequenced,
+                TransactionStage::Executed,
+            ]
+        );
+        assert!(trace.stages.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+
+    #[test]
+    fn test_sampling_is_deterministic() {
+        let tracer = TransactionTracer::default();
+        tracer.set_sample_rate(0.5);
+        let digests: Vec<_> = (0..1000).map(|_| TransactionDigest::random()).collect();
+        let sampled: Vec<_> = digests.iter().map(|d| tracer.is_sampled(d)).collect();
+        assert_eq!(
+            sampled,
+            digests
+                .iter()
+                .map(|d| tracer.is_sampled(d))
+                .collect::<Vec<_>>()
+        );
+        let count = sampled.iter().filter(|s| **s).count();
+        assert!((300..700).contains(&count), "{count}");
+    }
+}
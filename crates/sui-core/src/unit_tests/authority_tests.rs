@@ -5884,6 +5884,7 @@ async fn test_consensus_handler_per_object_congestion_control(
         PerObjectCongestionControlMode::TotalGasBudget => 5,
         PerObjectCongestionControlMode::TotalTxCount => 2,
         PerObjectCongestionControlMode::TotalGasBudgetWithCap => 5,
+        PerObjectCongestionControlMode::ExecutionTimeEstimate => 2,
     };
     let gas_objects_commit_1 = create_gas_objects(5 + non_congested_tx_count, sender);
     let gas_objects_commit_2 = create_gas_objects(non_congested_tx_count, sender);
@@ -5918,6 +5919,16 @@ async fn test_consensus_handler_per_object_congestion_control(
                 );
             protocol_config.set_gas_budget_based_txn_cost_cap_factor_for_testing(100_000_000);
         }
+        PerObjectCongestionControlMode::ExecutionTimeEstimate => {
+            // Nothing was observed by the single validator, so every transaction costs the
+            // default, and 2 fit in a commit.
+            protocol_config.set_execution_time_estimate_default_command_cost_us_for_testing(1000);
+            protocol_config.set_max_execution_time_observation_estimates_for_testing(10);
+            protocol_config
+                .set_max_accumulated_txn_cost_per_object_in_narwhal_commit_for_testing(2000);
+            protocol_config
+                .set_max_accumulated_txn_cost_per_object_in_mysticeti_commit_for_testing(2000);
+        }
     }
     protocol_config.set_max_deferral_rounds_for_congestion_control_for_testing(1000); // Set to a large number so that we don't hit this limit.
     protocol_config.set_max_txn_cost_overage_per_object_in_commit_for_testing(0);
@@ -6118,6 +6129,14 @@ async fn test_consensus_handler_per_object_congestion_control_using_budget_with_
     .await;
 }
 
+#[sim_test]
+async fn test_consensus_handler_per_object_congestion_control_using_execution_time_estimate() {
+    test_consensus_handler_per_object_congestion_control(
+        PerObjectCongestionControlMode::ExecutionTimeEstimate,
+    )
+    .await;
+}
+
 // Tests congestion control triggered transaction cancellation in consensus handler:
 //   1. Consensus handler cancels transactions that are deferred for too many rounds.
 //   2. Shared locks for cancelled transaction are set correctly.
@@ -0,0 +1,149 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use std::fs::File;
+use std::path::Path;
+use std::time::Duration;
+use sui_core::checkpoints::CheckpointStore;
+use sui_macros::sim_test;
+use sui_tool::db_tool::{execute_db_tool_command, DbToolCommand};
+use test_cluster::TestClusterBuilder;
+
+/// The number of parquet files under `dir`.
+fn parquet_files(dir: &Path) -> anyhow::Result<usize> {
+    let mut files = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        files += if path.is_dir() {
+            parquet_files(&path)?
+        } else {
+            1
+        };
+    }
+    Ok(files)
+}
+
+/// The number of rows in the parquet files under `dir`.
+fn parquet_rows(dir: &Path) -> anyhow::Result<i64> {
+    let mut rows = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            rows += parquet_rows(&path)?;
+        } else {
+            let reader = SerializedFileReader::new(File::open(path)?)?;
+            rows += reader.metadata().file_metadata().num_rows();
+        }
+    }
+    Ok(rows)
+}
+
+#[sim_test]
+async fn test_export_parquet() -> Result<(), anyhow::Error> {
+    // The epoch doesn't change during the test, so nothing is pruned.
+    let test_cluster = TestClusterBuilder::new()
+        .with_epoch_duration_ms(600_000)
+        .build()
+        .await;
+    let sender = test_cluster.get_address_0();
+    let receiver = test_cluster.get_address_1();
+    let mut digests = vec![];
+    for _ in 0..3 {
+        let tx = test_cluster
+            .test_transaction_builder_with_sender(sender)
+            .await
+            .transfer_sui(Some(1), receiver)
+            .build();
+        digests.push(test_cluster.sign_and_execute_transaction(&tx).await.digest);
+    }
+
+    // Wait for the fullnode to execute the checkpoint of the last transaction, then take a
+    // snapshot of its DB, which can be read while the node is running.
+    let last = *digests.last().unwrap();
+    loop {
+        let executed = test_cluster.fullnode_handle.sui_node.with(|node| {
+            let state = node.state();
+            let checkpoint = state
+                .get_transaction_checkpoint_for_tests(&last, &state.epoch_store_for_testing())
+                .unwrap();
+            let highest_executed = state
+                .get_checkpoint_store()
+                .get_highest_executed_checkpoint_seq_number()
+                .unwrap();
+            checkpoint
+                .zip(highest_executed)
+                .is_some_and(|(checkpoint, highest)| highest >= *checkpoint.sequence_number())
+        });
+        if executed {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    let dir = tempfile::tempdir()?;
+    let db_path = dir.path().join("live");
+    test_cluster.fullnode_handle.sui_node.with(|node| {
+        let state = node.state();
+        state.checkpoint_all_dbs(&db_path, &state.epoch_store_for_testing(), false)
+    })?;
+
+    let output_dir = dir.path().join("export");
+    let command = DbToolCommand::try_parse_from([
+        "db-tool",
+        "export-parquet",
+        "--table",
+        "checkpoint",
+        "--table",
+        "transaction",
+        "--output-dir",
+        output_dir.to_str().unwrap(),
+    ])?;
+    execute_db_tool_command(db_path.clone(), command).await?;
+
+    // Every executed checkpoint is exported.
+    let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+    let highest_executed = checkpoint_store
+        .get_highest_executed_checkpoint_seq_number()?
+        .unwrap();
+    let mut transactions = 0;
+    for sequence_number in 0..=highest_executed {
+        let checkpoint = checkpoint_store
+            .get_checkpoint_by_sequence_number(sequence_number)?
+            .unwrap();
+        transactions += checkpoint_store
+            .get_checkpoint_contents(&checkpoint.content_digest)?
+            .unwrap()
+            .size();
+    }
+    assert!(transactions > digests.len());
+    assert_eq!(
+        parquet_rows(&output_dir.join("checkpoints"))?,
+        highest_executed as i64 + 1
+    );
+    assert_eq!(
+        parquet_rows(&output_dir.join("transactions"))?,
+        transactions as i64
+    );
+
+    // Files are cut every `--checkpoint-interval` checkpoints within an epoch.
+    let output_dir = dir.path().join("export-interval");
+    let command = DbToolCommand::try_parse_from([
+        "db-tool",
+        "export-parquet",
+        "--table",
+        "checkpoint",
+        "--checkpoint-interval",
+        "2",
+        "--output-dir",
+        output_dir.to_str().unwrap(),
+    ])?;
+    execute_db_tool_command(db_path.clone(), command).await?;
+    let checkpoints_dir = output_dir.join("checkpoints");
+    assert_eq!(parquet_rows(&checkpoints_dir)?, highest_executed as i64 + 1);
+    assert_eq!(
+        parquet_files(&checkpoints_dir)?,
+        (highest_executed as usize + 1).div_ceil(2)
+    );
+    Ok(())
+}
@@ -0,0 +1,196 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::language_storage::StructTag;
+use std::cmp::Reverse;
+use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
+use sui_macros::sim_test;
+use sui_rest_api::client::sdk::Client;
+use sui_rest_api::{AccountCoinCursor, ListAccountCoinsQueryParameters};
+use sui_types::base_types::{dbg_addr, ObjectID, SuiAddress};
+use sui_types::gas_coin::GAS;
+use sui_types::parse_sui_struct_tag;
+use sui_types::sui_sdk_types_conversions::{struct_tag_core_to_sdk, struct_tag_sdk_to_core};
+use test_cluster::{TestCluster, TestClusterBuilder};
+
+use crate::transaction_checkpoint;
+
+/// (object id, version, balance) of a coin
+type CoinSummary = (ObjectID, u64, u64);
+
+/// List the SUI coins owned by `owner` by following the cursor, `limit` coins at a time.
+async fn list_sui_coins(
+    client: &Client,
+    owner: SuiAddress,
+    min_balance: Option<u64>,
+    limit: u32,
+) -> Vec<CoinSummary> {
+    let coin_type = struct_tag_core_to_sdk(GAS::type_()).unwrap();
+    let mut coins = Vec::new();
+    let mut start = None;
+
+    loop {
+        let parameters = ListAccountCoinsQueryParameters {
+            limit: Some(limit),
+            start,
+            min_balance,
+        };
+        let response = client
+            .list_account_coins(owner.into(), &coin_type, &parameters)
+            .await
+            .unwrap();
+        let cursor = response.parts().cursor.clone();
+        let page = response.into_inner();
+        assert!(page.len() <= limit as usize);
+
+        for coin in page {
+            assert_eq!(coin.coin_type, coin_type);
+            coins.push((ObjectID::from(coin.object_id), coin.version, coin.balance));
+        }
+
+        match cursor {
+            Some(cursor) => start = Some(cursor.parse::<AccountCoinCursor>().unwrap()),
+            None => return coins,
+        }
+    }
+}
+
+/// The SUI coins owned by `owner` according to JSON-RPC, in the order the REST API lists them.
+async fn json_rpc_sui_coins(test_cluster: &TestCluster, owner: SuiAddress) -> Vec<CoinSummary> {
+    let mut coins: Vec<_> = test_cluster
+        .sui_client()
+        .coin_read_api()
+        .get_coins(owner, None, None, None)
+        .await
+        .unwrap()
+        .data
+        .into_iter()
+        .map(|coin| (coin.coin_object_id, coin.version.value(), coin.balance))
+        .collect();
+    coins.sort_by_key(|(object_id, _, balance)| (Reverse(*balance), *object_id));
+    coins
+}
+
+/// Check the balances of `owner` against those returned by JSON-RPC.
+async fn check_balances(test_cluster: &TestCluster, client: &Client, owner: SuiAddress) {
+    let coin_read_api = test_cluster.sui_client().coin_read_api();
+
+    let mut expected: Vec<(StructTag, u64, u128)> = coin_read_api
+        .get_all_balances(owner)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|balance| {
+            (
+                parse_sui_struct_tag(&balance.coin_type).unwrap(),
+                balance.coin_object_count as u64,
+                balance.total_balance,
+            )
+        })
+        .collect();
+    expected.sort();
+
+    let mut balances: Vec<(StructTag, u64, u128)> = client
+        .list_account_balances(owner.into())
+        .await
+        .unwrap()
+        .into_inner()
+        .into_iter()
+        .map(|balance| {
+            (
+                struct_tag_sdk_to_core(balance.coin_type).unwrap(),
+                balance.coin_object_count,
+                balance.total_balance,
+            )
+        })
+        .collect();
+    balances.sort();
+    assert_eq!(balances, expected);
+
+    let sui_balance = coin_read_api.get_balance(owner, None).await.unwrap();
+    let balance = client
+        .get_account_balance(owner.into(), &struct_tag_core_to_sdk(GAS::type_()).unwrap())
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        struct_tag_sdk_to_core(balance.coin_type).unwrap(),
+        GAS::type_()
+    );
+    assert_eq!(
+        balance.coin_object_count,
+        sui_balance.coin_object_count as u64
+    );
+    assert_eq!(balance.total_balance, sui_balance.total_balance);
+}
+
+#[sim_test]
+async fn list_account_coins() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let client = Client::new(test_cluster.rpc_url()).unwrap();
+    let sender = test_cluster.test_transaction_builder().await.sender();
+    let recipient = dbg_addr(0x42);
+
+    // Coins with duplicate balances are ordered by their ids
+    let amounts = [5_000, 1_000, 3_000, 1_000, 2_000];
+    let mut last_digest = None;
+    for amount in amounts {
+        let tx_data = test_cluster
+            .test_transaction_builder_with_sender(sender)
+            .await
+            .transfer_sui(Some(amount), recipient)
+            .build();
+        let effects = test_cluster
+            .sign_and_execute_transaction(&tx_data)
+            .await
+            .effects
+            .unwrap();
+        last_digest = Some(*effects.transaction_digest());
+    }
+    transaction_checkpoint(&client, &last_digest.unwrap()).await;
+
+    let coins = list_sui_coins(&client, recipient, None, 50).await;
+    let balances: Vec<_> = coins.iter().map(|(_, _, balance)| *balance).collect();
+    assert_eq!(balances, [5_000, 3_000, 2_000, 1_000, 1_000]);
+    assert_eq!(coins, json_rpc_sui_coins(&test_cluster, recipient).await);
+
+    // Paging through the coins yields the same coins in the same order
+    for limit in 1..=3 {
+        assert_eq!(
+            list_sui_coins(&client, recipient, None, limit).await,
+            coins,
+            "{limit}"
+        );
+    }
+
+    // Coins with a balance below `min_balance` are excluded, including across pages
+    for min_balance in [1_000, 2_000, 2_001, 5_000, 5_001] {
+        let expected: Vec<_> = coins
+            .iter()
+            .filter(|(_, _, balance)| *balance >= min_balance)
+            .cloned()
+            .collect();
+        for limit in [1, 50] {
+            assert_eq!(
+                list_sui_coins(&client, recipient, Some(min_balance), limit).await,
+                expected,
+                "{min_balance} {limit}"
+            );
+        }
+    }
+
+    // The sender's gas coins have been used to pay for the transfers
+    let sender_coins = list_sui_coins(&client, sender, None, 2).await;
+    assert_eq!(
+        sender_coins,
+        json_rpc_sui_coins(&test_cluster, sender).await
+    );
+
+    check_balances(&test_cluster, &client, recipient).await;
+    check_balances(&test_cluster, &client, sender).await;
+
+    // An account without any coins has a zero balance
+    let empty = dbg_addr(0x43);
+    assert!(list_sui_coins(&client, empty, None, 50).await.is_empty());
+    check_balances(&test_cluster, &client, empty).await;
+}
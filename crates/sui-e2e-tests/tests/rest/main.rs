@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod accounts;
 mod checkpoints;
 mod committee;
 mod execute;
@@ -25,3 +26,23 @@ async fn transfer_coin(
     let resp = context.execute_transaction_must_succeed(txn).await;
     resp.digest.into()
 }
+
+/// Wait for the transaction to be included in a checkpoint executed by the fullnode.
+async fn transaction_checkpoint(
+    client: &sui_rest_api::client::sdk::Client,
+    digest: &sui_types::digests::TransactionDigest,
+) -> sui_types::messages_checkpoint::CheckpointSequenceNumber {
+    let digest = (*digest).into();
+    tokio::time::timeout(std::time::Duration::from_secs(30), async {
+        loop {
+            if let Ok(response) = client.get_transaction(&digest).await {
+                if let Some(checkpoint) = response.into_inner().checkpoint {
+                    return checkpoint;
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap()
+}
@@ -2,12 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prost::Message;
+use sui_json_rpc_types::{SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI};
 use sui_macros::sim_test;
 use sui_rest_api::client::sdk::Client;
 use sui_rest_api::client::Client as CoreClient;
-use sui_rest_api::ObjectResponse;
-use sui_sdk_types::types::Object;
-use test_cluster::TestClusterBuilder;
+use sui_rest_api::{ListDynamicFieldsQueryParameters, ObjectResponse};
+use sui_sdk_types::types::{Object, ObjectId};
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::{Argument, Command, ObjectArg, ProgrammableTransaction};
+use sui_types::{Identifier, TypeTag, SUI_FRAMEWORK_PACKAGE_ID};
+use test_cluster::{TestCluster, TestClusterBuilder};
+
+use crate::transaction_checkpoint;
 
 #[sim_test]
 async fn get_object() {
@@ -77,3 +86,345 @@ async fn get_object() {
     let url = format!("{}/v2/objects/0x5/version/1", test_cluster.rpc_url());
     raw_request(&url).await;
 }
+
+/// Execute the programmable transaction and wait for the checkpoint including it.
+async fn execute(
+    test_cluster: &TestCluster,
+    client: &Client,
+    sender: SuiAddress,
+    gas: ObjectRef,
+    pt: ProgrammableTransaction,
+) -> (SuiTransactionBlockEffects, CheckpointSequenceNumber) {
+    let tx_data = test_cluster
+        .test_transaction_builder_with_gas_object(sender, gas)
+        .await
+        .programmable(pt)
+        .build();
+    let effects = test_cluster
+        .sign_and_execute_transaction(&tx_data)
+        .await
+        .effects
+        .unwrap();
+    let checkpoint = transaction_checkpoint(client, effects.transaction_digest()).await;
+
+    (effects, checkpoint)
+}
+
+/// The version of the object as of `checkpoint`, or the status code of the error.
+async fn object_version_at_checkpoint(
+    client: &Client,
+    object_id: ObjectID,
+    checkpoint: CheckpointSequenceNumber,
+) -> Result<u64, reqwest::StatusCode> {
+    client
+        .get_object_at_checkpoint(object_id.into(), checkpoint)
+        .await
+        .map(|response| response.into_inner().version())
+        .map_err(|e| e.status().unwrap())
+}
+
+#[sim_test]
+async fn get_object_at_checkpoint() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let client = Client::new(test_cluster.rpc_url()).unwrap();
+
+    // Split a coin off of the gas coin, then merge it back in
+    let tx_builder = test_cluster.test_transaction_builder().await;
+    let sender = tx_builder.sender();
+    let effects = test_cluster
+        .sign_and_execute_transaction(&tx_builder.transfer_sui(Some(1), sender).build())
+        .await
+        .effects
+        .unwrap();
+    let split_checkpoint = transaction_checkpoint(&client, effects.transaction_digest()).await;
+    let gas = effects.gas_object().reference.to_object_ref();
+    let coin = effects.created()[0].reference.to_object_ref();
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let coin_arg = builder.obj(ObjectArg::ImmOrOwnedObject(coin)).unwrap();
+    builder.command(Command::MergeCoins(Argument::GasCoin, vec![coin_arg]));
+    let (effects, merge_checkpoint) =
+        execute(&test_cluster, &client, sender, gas, builder.finish()).await;
+    let merged_gas = effects.gas_object().reference.to_object_ref();
+    assert!(merge_checkpoint > split_checkpoint);
+
+    assert_eq!(
+        object_version_at_checkpoint(&client, gas.0, split_checkpoint).await,
+        Ok(gas.1.value())
+    );
+    assert_eq!(
+        object_version_at_checkpoint(&client, gas.0, merge_checkpoint - 1).await,
+        Ok(gas.1.value())
+    );
+    assert_eq!(
+        object_version_at_checkpoint(&client, gas.0, merge_checkpoint).await,
+        Ok(merged_gas.1.value())
+    );
+    assert_eq!(
+        object_version_at_checkpoint(&client, coin.0, split_checkpoint).await,
+        Ok(coin.1.value())
+    );
+
+    // The coin was deleted by the merge, and the random object never existed
+    assert_eq!(
+        object_version_at_checkpoint(&client, coin.0, merge_checkpoint).await,
+        Err(reqwest::StatusCode::NOT_FOUND)
+    );
+    assert_eq!(
+        object_version_at_checkpoint(&client, ObjectID::random(), merge_checkpoint).await,
+        Err(reqwest::StatusCode::NOT_FOUND)
+    );
+
+    // Checkpoints which haven't been executed yet
+    let latest_checkpoint = client
+        .get_latest_checkpoint()
+        .await
+        .unwrap()
+        .into_inner()
+        .checkpoint
+        .sequence_number;
+    assert_eq!(
+        object_version_at_checkpoint(&client, gas.0, latest_checkpoint + 1000).await,
+        Err(reqwest::StatusCode::NOT_FOUND)
+    );
+
+    // Make sure it works with json and protobuf
+    let url = format!(
+        "{}/v2/objects/{}/checkpoint/{merge_checkpoint}",
+        test_cluster.rpc_url(),
+        gas.0,
+    );
+    let client = reqwest::Client::new();
+    let object = client
+        .get(&url)
+        .header(reqwest::header::ACCEPT, sui_rest_api::APPLICATION_JSON)
+        .send()
+        .await
+        .unwrap()
+        .json::<ObjectResponse>()
+        .await
+        .unwrap();
+    assert_eq!(object.object.version(), merged_gas.1.value());
+    let bytes = client
+        .get(&url)
+        .header(reqwest::header::ACCEPT, sui_rest_api::APPLICATION_PROTOBUF)
+        .send()
+        .await
+        .unwrap()
+        .bytes()
+        .await
+        .unwrap();
+    let _object = sui_rest_api::proto::GetObjectResponse::decode(bytes).unwrap();
+
+    // Once the objects of the first epoch are pruned, their history is no longer available
+    test_cluster.trigger_reconfiguration().await;
+    test_cluster
+        .fullnode_handle
+        .sui_node
+        .with_async(|node| async move {
+            let state = node.state();
+            state
+                .database_for_testing()
+                .prune_objects_and_compact_for_testing(
+                    state.get_checkpoint_store(),
+                    state.rest_index.as_deref(),
+                )
+                .await;
+        })
+        .await;
+
+    let client = Client::new(test_cluster.rpc_url()).unwrap();
+    assert_eq!(
+        object_version_at_checkpoint(&client, gas.0, merge_checkpoint).await,
+        Err(reqwest::StatusCode::GONE)
+    );
+    let latest_checkpoint = client
+        .get_latest_checkpoint()
+        .await
+        .unwrap()
+        .into_inner()
+        .checkpoint
+        .sequence_number;
+    assert_eq!(
+        object_version_at_checkpoint(&client, gas.0, latest_checkpoint).await,
+        Ok(merged_gas.1.value())
+    );
+}
+
+fn bag_call(
+    builder: &mut ProgrammableTransactionBuilder,
+    function: &str,
+    bag: Argument,
+    args: &[u64],
+) -> Argument {
+    let mut arguments = vec![bag];
+    for arg in args {
+        arguments.push(builder.pure(*arg).unwrap());
+    }
+    builder.programmable_move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,
+        Identifier::new("bag").unwrap(),
+        Identifier::new(function).unwrap(),
+        vec![TypeTag::U64, TypeTag::U64],
+        arguments,
+    )
+}
+
+const ALL_FIELDS: ListDynamicFieldsQueryParameters = ListDynamicFieldsQueryParameters {
+    limit: None,
+    start: None,
+};
+
+/// Names of the `u64` keys of the bag as of `checkpoint`, along with their field ids.
+async fn bag_keys_at_checkpoint(
+    client: &Client,
+    bag: ObjectID,
+    checkpoint: CheckpointSequenceNumber,
+) -> Vec<(u64, ObjectID)> {
+    let mut keys: Vec<_> = client
+        .list_dynamic_fields_at_checkpoint(bag.into(), checkpoint, &ALL_FIELDS)
+        .await
+        .unwrap()
+        .into_inner()
+        .into_iter()
+        .map(|field| {
+            assert_eq!(field.parent, ObjectId::from(bag));
+            (
+                bcs::from_bytes::<u64>(&field.name_value).unwrap(),
+                ObjectID::from(field.field_id),
+            )
+        })
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[sim_test]
+async fn list_dynamic_fields_at_checkpoint() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let client = Client::new(test_cluster.rpc_url()).unwrap();
+    let tx_builder = test_cluster.test_transaction_builder().await;
+    let sender = tx_builder.sender();
+
+    // Create a bag with the keys 1 and 2
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let bag = builder.programmable_move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,
+        Identifier::new("bag").unwrap(),
+        Identifier::new("new").unwrap(),
+        vec![],
+        vec![],
+    );
+    bag_call(&mut builder, "add", bag, &[1, 10]);
+    bag_call(&mut builder, "add", bag, &[2, 20]);
+    builder.transfer_arg(sender, bag);
+    let (effects, create_checkpoint) = execute(
+        &test_cluster,
+        &client,
+        sender,
+        tx_builder.gas_object(),
+        builder.finish(),
+    )
+    .await;
+    let bag_id = effects
+        .created()
+        .iter()
+        .find(|object| object.owner == Owner::AddressOwner(sender))
+        .unwrap()
+        .reference
+        .object_id;
+    let object_ref = |effects: &SuiTransactionBlockEffects, id: ObjectID| {
+        effects
+            .mutated()
+            .iter()
+            .chain(effects.created())
+            .find(|object| object.reference.object_id == id)
+            .unwrap()
+            .reference
+            .to_object_ref()
+    };
+
+    // Remove the key 1
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let bag = builder
+        .obj(ObjectArg::ImmOrOwnedObject(object_ref(&effects, bag_id)))
+        .unwrap();
+    bag_call(&mut builder, "remove", bag, &[1]);
+    let gas = effects.gas_object().reference.to_object_ref();
+    let (effects, remove_checkpoint) =
+        execute(&test_cluster, &client, sender, gas, builder.finish()).await;
+
+    // Add the key 1 back
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let bag = builder
+        .obj(ObjectArg::ImmOrOwnedObject(object_ref(&effects, bag_id)))
+        .unwrap();
+    bag_call(&mut builder, "add", bag, &[1, 11]);
+    let gas = effects.gas_object().reference.to_object_ref();
+    let (_, add_checkpoint) = execute(&test_cluster, &client, sender, gas, builder.finish()).await;
+
+    let created = bag_keys_at_checkpoint(&client, bag_id, create_checkpoint).await;
+    assert_eq!(
+        created.iter().map(|(key, _)| *key).collect::<Vec<_>>(),
+        [1, 2]
+    );
+    assert_eq!(
+        bag_keys_at_checkpoint(&client, bag_id, remove_checkpoint).await,
+        [created[1]]
+    );
+    assert_eq!(
+        bag_keys_at_checkpoint(&client, bag_id, add_checkpoint - 1).await,
+        [created[1]]
+    );
+    // The field id is derived from the key, so the re-added field has the same id
+    assert_eq!(
+        bag_keys_at_checkpoint(&client, bag_id, add_checkpoint).await,
+        created
+    );
+
+    // The latest checkpoint matches the live dynamic fields
+    let live: Vec<_> = client
+        .list_dynamic_fields(bag_id.into(), &ALL_FIELDS)
+        .await
+        .unwrap()
+        .into_inner()
+        .into_iter()
+        .map(|field| field.field_id)
+        .collect();
+    let at_latest: Vec<_> = client
+        .list_dynamic_fields_at_checkpoint(bag_id.into(), add_checkpoint, &ALL_FIELDS)
+        .await
+        .unwrap()
+        .into_inner()
+        .into_iter()
+        .map(|field| field.field_id)
+        .collect();
+    assert_eq!(live, at_latest);
+
+    // Paginate through the fields one at a time
+    let parameters = ListDynamicFieldsQueryParameters {
+        limit: Some(1),
+        start: None,
+    };
+    let first_page = client
+        .list_dynamic_fields_at_checkpoint(bag_id.into(), create_checkpoint, &parameters)
+        .await
+        .unwrap();
+    let cursor = first_page.parts().cursor.clone().unwrap();
+    let first_page = first_page.into_inner();
+    let parameters = ListDynamicFieldsQueryParameters {
+        limit: Some(1),
+        start: Some(cursor.parse().unwrap()),
+    };
+    let second_page = client
+        .list_dynamic_fields_at_checkpoint(bag_id.into(), create_checkpoint, &parameters)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(first_page.len(), 1);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(
+        [first_page[0].field_id, second_page[0].field_id],
+        [live[0], live[1]]
+    );
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use prometheus::Registry;
 use std::sync::Arc;
 use std::time::Duration;
 use sui_core::authority_client::NetworkAuthorityClient;
@@ -8,15 +9,18 @@ use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_macros::sim_test;
 use sui_storage::key_value_store::TransactionKeyValueStore;
 use sui_storage::key_value_store_metrics::KeyValueStoreMetrics;
+use sui_storage::write_path_pending_tx_log::WritePathPendingTransactionLog;
 use sui_test_transaction_builder::{
     batch_make_transfer_transactions, make_staking_transaction, make_transfer_sui_transaction,
 };
+use sui_types::base_types::TransactionDigest;
 use sui_types::effects::TransactionEffectsAPI;
+use sui_types::message_envelope::Message;
 use sui_types::quorum_driver_types::{
     ExecuteTransactionRequestType, ExecuteTransactionRequestV3, ExecuteTransactionResponseV3,
-    FinalizedEffects, IsTransactionExecutedLocally, QuorumDriverError,
+    FinalizedEffects, IsTransactionExecutedLocally, QuorumDriverError, SubmissionStatus,
 };
-use sui_types::transaction::Transaction;
+use sui_types::transaction::{Transaction, VerifiedTransaction};
 use test_cluster::TestClusterBuilder;
 use tokio::time::timeout;
 use tracing::info;
@@ -411,3 +415,193 @@ async fn execute_transaction_v3_staking_transaction() -> Result<(), anyhow::Erro
 
     Ok(())
 }
+
+async fn wait_for_submission_status(
+    orchestrator: &TransactiondOrchestrator<NetworkAuthorityClient>,
+    digest: &TransactionDigest,
+    expected: impl Fn(&SubmissionStatus) -> bool,
+) -> SubmissionStatus {
+    timeout(Duration::from_secs(60), async {
+        loop {
+            if let Some(status) = orchestrator.get_submission_status(digest).unwrap() {
+                if expected(&status) {
+                    return status;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| {
+        panic!(
+            "Unexpected submission status of {digest:?}: {:?}",
+            orchestrator.get_submission_status(digest)
+        )
+    })
+}
+
+#[sim_test]
+async fn test_enqueue_transaction() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let handle = &test_cluster.fullnode_handle.sui_node;
+    let orchestrator = handle.with(|n| n.transaction_orchestrator().as_ref().unwrap().clone());
+
+    let txn = make_transfer_sui_transaction(&test_cluster.wallet, None, None).await;
+    let digest = orchestrator
+        .enqueue_transaction(ExecuteTransactionRequestV3::new_v2(txn.clone()), None)
+        .await?;
+    assert_eq!(digest, *txn.digest());
+
+    let status =
+        wait_for_submission_status(&orchestrator, &digest, SubmissionStatus::is_terminal).await;
+    let effects = handle
+        .state()
+        .get_transaction_cache_reader()
+        .notify_read_executed_effects(&[digest])
+        .await?;
+    assert_eq!(
+        status,
+        SubmissionStatus::Executed {
+            effects_digest: effects[0].digest()
+        }
+    );
+
+    // Enqueuing the transaction again does not reset its status.
+    orchestrator
+        .enqueue_transaction(ExecuteTransactionRequestV3::new_v2(txn), None)
+        .await?;
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert_eq!(orchestrator.get_submission_status(&digest)?, Some(status));
+
+    // The statuses of synchronously executed transactions are not tracked.
+    let txn = make_transfer_sui_transaction(&test_cluster.wallet, None, None).await;
+    let digest = *txn.digest();
+    execute_with_orchestrator(
+        &orchestrator,
+        txn,
+        ExecuteTransactionRequestType::WaitForEffectsCert,
+    )
+    .await?;
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert_eq!(orchestrator.get_submission_status(&digest)?, None);
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_enqueued_transaction_recovered_after_restart() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let handle = &test_cluster.fullnode_handle.sui_node;
+    let txn = make_transfer_sui_transaction(&test_cluster.wallet, None, None).await;
+    let digest = *txn.digest();
+
+    // A transaction which was enqueued before the fullnode stopped.
+    let dir = tempfile::tempdir()?;
+    {
+        let pending_tx_log =
+            WritePathPendingTransactionLog::new(dir.path().join("fullnode_pending_transactions"));
+        assert!(
+            pending_tx_log
+                .write_queued_transaction_maybe(&VerifiedTransaction::new_unchecked(txn))
+                .await?
+        );
+    }
+
+    // The restarted transaction orchestrator submits it again.
+    let orchestrator = handle.with(|n| {
+        TransactiondOrchestrator::new_with_auth_aggregator(
+            n.clone_authority_aggregator().unwrap(),
+            n.state(),
+            n.subscribe_to_epoch_change(),
+            dir.path(),
+            &Registry::new(),
+        )
+    });
+    assert_eq!(
+        orchestrator.get_submission_status(&digest)?,
+        Some(SubmissionStatus::Queued { last_error: None })
+    );
+    let status =
+        wait_for_submission_status(&orchestrator, &digest, SubmissionStatus::is_terminal).await;
+    assert!(
+        matches!(status, SubmissionStatus::Executed { .. }),
+        "{status:?}"
+    );
+    assert!(orchestrator.load_all_pending_transactions().is_empty());
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_enqueued_transaction_retried_in_next_epoch() -> Result<(), anyhow::Error> {
+    telemetry_subscribers::init_for_testing();
+    let test_cluster = TestClusterBuilder::new()
+        .with_epoch_duration_ms(600000)
+        .build()
+        .await;
+    let handle = &test_cluster.fullnode_handle.sui_node;
+    let orchestrator = handle.with(|n| n.transaction_orchestrator().as_ref().unwrap().clone());
+    let context = &test_cluster.wallet;
+    let recipient = context.get_addresses()[1];
+
+    // Lock the gas object on 2 validators with another transaction, so that no transaction using
+    // it can be certified in this epoch.
+    let aggregator = orchestrator.clone_authority_aggregator();
+    let names: Vec<_> = aggregator.authority_clients.keys().cloned().collect();
+    let locking_txn = make_transfer_sui_transaction(context, Some(recipient), Some(1)).await;
+    for name in &names[..2] {
+        aggregator
+            .clone_client_test_only(name)
+            .handle_transaction(locking_txn.clone(), Some(make_socket_addr()))
+            .await?;
+    }
+
+    // A synchronously executed transaction which fails is removed from the log.
+    let txn = make_transfer_sui_transaction(context, Some(recipient), Some(2)).await;
+    let err = execute_with_orchestrator(
+        &orchestrator,
+        txn,
+        ExecuteTransactionRequestType::WaitForEffectsCert,
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(err, QuorumDriverError::ObjectsDoubleUsed { .. }),
+        "{err:?}"
+    );
+    assert!(orchestrator.load_all_pending_transactions().is_empty());
+
+    // An enqueued transaction which fails with the same error waits for the next epoch.
+    let txn = make_transfer_sui_transaction(context, Some(recipient), Some(3)).await;
+    let digest = orchestrator
+        .enqueue_transaction(ExecuteTransactionRequestV3::new_v2(txn.clone()), None)
+        .await?;
+    wait_for_submission_status(&orchestrator, &digest, |status| {
+        matches!(
+            status,
+            SubmissionStatus::Queued {
+                last_error: Some(_)
+            }
+        )
+    })
+    .await;
+    let pending_txes: Vec<_> = orchestrator
+        .load_all_pending_transactions()
+        .into_iter()
+        .map(|t| t.into_inner())
+        .collect();
+    assert_eq!(pending_txes, vec![txn]);
+
+    // Object locks are reset in the next epoch, where the transaction goes through.
+    test_cluster.trigger_reconfiguration().await;
+    let status =
+        wait_for_submission_status(&orchestrator, &digest, SubmissionStatus::is_terminal).await;
+    assert!(
+        matches!(status, SubmissionStatus::Executed { .. }),
+        "{status:?}"
+    );
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(orchestrator.load_all_pending_transactions().is_empty());
+
+    Ok(())
+}
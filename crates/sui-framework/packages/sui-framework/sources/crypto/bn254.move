@@ -0,0 +1,229 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Group operations of BN254, also known as alt_bn128, the pairing-friendly curve supported by the
+/// Ethereum precompiles.
+module sui::bn254;
+
+use sui::group_ops::{Self, Element};
+
+public struct Scalar {}
+public struct G1 {}
+public struct G2 {}
+public struct GT {}
+
+// Scalars are encoded using little-endian byte order.
+// G1 and G2 are encoded using little-endian byte order and points are compressed, with the flags
+// stored in the two most significant bits of the last byte. A G2 element is encoded as the c0
+// coordinate followed by the c1 coordinate.
+// GT is encoded using little-endian byte order and points are uncompressed and not intended
+// to be deserialized.
+// Note that this differs from the big-endian uncompressed encoding used by the Ethereum precompiles.
+
+// Const elements.
+const SCALAR_ZERO_BYTES: vector<u8> =
+    x"0000000000000000000000000000000000000000000000000000000000000000";
+const SCALAR_ONE_BYTES: vector<u8> =
+    x"0100000000000000000000000000000000000000000000000000000000000000";
+const G1_IDENTITY_BYTES: vector<u8> =
+    x"0000000000000000000000000000000000000000000000000000000000000040";
+const G1_GENERATOR_BYTES: vector<u8> =
+    x"0100000000000000000000000000000000000000000000000000000000000000";
+const G2_IDENTITY_BYTES: vector<u8> =
+    x"00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000040";
+const G2_GENERATOR_BYTES: vector<u8> =
+    x"edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19";
+const GT_IDENTITY_BYTES: vector<u8> =
+    x"010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+
+// Internal types used by group_ops' native functions.
+const SCALAR_TYPE: u8 = 5;
+const G1_TYPE: u8 = 6;
+const G2_TYPE: u8 = 7;
+const GT_TYPE: u8 = 8;
+
+///////////////////////////////
+////// Scalar operations //////
+
+public fun scalar_from_bytes(bytes: &vector<u8>): Element<Scalar> {
+    group_ops::from_bytes(SCALAR_TYPE, bytes, false)
+}
+
+public fun scalar_from_u64(x: u64): Element<Scalar> {
+    let mut bytes = SCALAR_ZERO_BYTES;
+    group_ops::set_as_prefix(x, false, &mut bytes);
+    group_ops::from_bytes(SCALAR_TYPE, &bytes, true)
+}
+
+public fun scalar_zero(): Element<Scalar> {
+    let zero = SCALAR_ZERO_BYTES;
+    group_ops::from_bytes(SCALAR_TYPE, &zero, true)
+}
+
+public fun scalar_one(): Element<Scalar> {
+    let one = SCALAR_ONE_BYTES;
+    group_ops::from_bytes(SCALAR_TYPE, &one, true)
+}
+
+public fun scalar_add(e1: &Element<Scalar>, e2: &Element<Scalar>): Element<Scalar> {
+    group_ops::add(SCALAR_TYPE, e1, e2)
+}
+
+public fun scalar_sub(e1: &Element<Scalar>, e2: &Element<Scalar>): Element<Scalar> {
+    group_ops::sub(SCALAR_TYPE, e1, e2)
+}
+
+public fun scalar_mul(e1: &Element<Scalar>, e2: &Element<Scalar>): Element<Scalar> {
+    group_ops::mul(SCALAR_TYPE, e1, e2)
+}
+
+/// Returns e2/e1, fails if a is zero.
+public fun scalar_div(e1: &Element<Scalar>, e2: &Element<Scalar>): Element<Scalar> {
+    group_ops::div(SCALAR_TYPE, e1, e2)
+}
+
+public fun scalar_neg(e: &Element<Scalar>): Element<Scalar> {
+    scalar_sub(&scalar_zero(), e)
+}
+
+// Fails if e is zero.
+public fun scalar_inv(e: &Element<Scalar>): Element<Scalar> {
+    scalar_div(e, &scalar_one())
+}
+
+/////////////////////////////////
+////// G1 group operations //////
+
+public fun g1_from_bytes(bytes: &vector<u8>): Element<G1> {
+    group_ops::from_bytes(G1_TYPE, bytes, false)
+}
+
+public fun g1_identity(): Element<G1> {
+    let identity = G1_IDENTITY_BYTES;
+    group_ops::from_bytes(G1_TYPE, &identity, true)
+}
+
+public fun g1_generator(): Element<G1> {
+    let generator = G1_GENERATOR_BYTES;
+    group_ops::from_bytes(G1_TYPE, &generator, true)
+}
+
+public fun g1_add(e1: &Element<G1>, e2: &Element<G1>): Element<G1> {
+    group_ops::add(G1_TYPE, e1, e2)
+}
+
+public fun g1_sub(e1: &Element<G1>, e2: &Element<G1>): Element<G1> {
+    group_ops::sub(G1_TYPE, e1, e2)
+}
+
+public fun g1_mul(e1: &Element<Scalar>, e2: &Element<G1>): Element<G1> {
+    group_ops::mul(G1_TYPE, e1, e2)
+}
+
+/// Returns e2 / e1, fails if scalar is zero.
+public fun g1_div(e1: &Element<Scalar>, e2: &Element<G1>): Element<G1> {
+    group_ops::div(G1_TYPE, e1, e2)
+}
+
+public fun g1_neg(e: &Element<G1>): Element<G1> {
+    g1_sub(&g1_identity(), e)
+}
+
+/// Let 'scalars' be the vector [s1, s2, ..., sn] and 'elements' be the vector [e1, e2, ..., en].
+/// Returns s1*e1 + s2*e2 + ... + sn*en.
+/// Aborts with `EInputTooLong` if the vectors are larger than 32 (may increase in the future).
+public fun g1_multi_scalar_multiplication(
+    scalars: &vector<Element<Scalar>>,
+    elements: &vector<Element<G1>>,
+): Element<G1> {
+    group_ops::multi_scalar_multiplication(G1_TYPE, scalars, elements)
+}
+
+/////////////////////////////////
+////// G2 group operations //////
+
+public fun g2_from_bytes(bytes: &vector<u8>): Element<G2> {
+    group_ops::from_bytes(G2_TYPE, bytes, false)
+}
+
+public fun g2_identity(): Element<G2> {
+    let identity = G2_IDENTITY_BYTES;
+    group_ops::from_bytes(G2_TYPE, &identity, true)
+}
+
+public fun g2_generator(): Element<G2> {
+    let generator = G2_GENERATOR_BYTES;
+    group_ops::from_bytes(G2_TYPE, &generator, true)
+}
+
+public fun g2_add(e1: &Element<G2>, e2: &Element<G2>): Element<G2> {
+    group_ops::add(G2_TYPE, e1, e2)
+}
+
+public fun g2_sub(e1: &Element<G2>, e2: &Element<G2>): Element<G2> {
+    group_ops::sub(G2_TYPE, e1, e2)
+}
+
+public fun g2_mul(e1: &Element<Scalar>, e2: &Element<G2>): Element<G2> {
+    group_ops::mul(G2_TYPE, e1, e2)
+}
+
+/// Returns e2 / e1, fails if scalar is zero.
+public fun g2_div(e1: &Element<Scalar>, e2: &Element<G2>): Element<G2> {
+    group_ops::div(G2_TYPE, e1, e2)
+}
+
+public fun g2_neg(e: &Element<G2>): Element<G2> {
+    g2_sub(&g2_identity(), e)
+}
+
+/// Let 'scalars' be the vector [s1, s2, ..., sn] and 'elements' be the vector [e1, e2, ..., en].
+/// Returns s1*e1 + s2*e2 + ... + sn*en.
+/// Aborts with `EInputTooLong` if the vectors are larger than 32 (may increase in the future).
+public fun g2_multi_scalar_multiplication(
+    scalars: &vector<Element<Scalar>>,
+    elements: &vector<Element<G2>>,
+): Element<G2> {
+    group_ops::multi_scalar_multiplication(G2_TYPE, scalars, elements)
+}
+
+/////////////////////////////////
+////// Gt group operations //////
+
+public fun gt_identity(): Element<GT> {
+    let identity = GT_IDENTITY_BYTES;
+    group_ops::from_bytes(GT_TYPE, &identity, true)
+}
+
+/// The generator of GT is the pairing of the generators of G1 and G2.
+public fun gt_generator(): Element<GT> {
+    pairing(&g1_generator(), &g2_generator())
+}
+
+public fun gt_add(e1: &Element<GT>, e2: &Element<GT>): Element<GT> {
+    group_ops::add(GT_TYPE, e1, e2)
+}
+
+public fun gt_sub(e1: &Element<GT>, e2: &Element<GT>): Element<GT> {
+    group_ops::sub(GT_TYPE, e1, e2)
+}
+
+public fun gt_mul(e1: &Element<Scalar>, e2: &Element<GT>): Element<GT> {
+    group_ops::mul(GT_TYPE, e1, e2)
+}
+
+/// Returns e2 / e1, fails if scalar is zero.
+public fun gt_div(e1: &Element<Scalar>, e2: &Element<GT>): Element<GT> {
+    group_ops::div(GT_TYPE, e1, e2)
+}
+
+public fun gt_neg(e: &Element<GT>): Element<GT> {
+    gt_sub(&gt_identity(), e)
+}
+
+/////////////////////
+////// Pairing //////
+
+public fun pairing(e1: &Element<G1>, e2: &Element<G2>): Element<GT> {
+    group_ops::pairing(G1_TYPE, e1, e2)
+}
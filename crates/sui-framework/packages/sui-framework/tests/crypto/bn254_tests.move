@@ -0,0 +1,250 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[allow(implicit_const_copy)]
+#[test_only]
+module sui::bn254_tests {
+    use sui::bn254;
+    use sui::group_ops;
+    use sui::random;
+    use sui::test_utils::assert_eq;
+
+    const ORDER_BYTES: vector<u8> = x"010000f093f5e1439170b97948e833285d588181b64550b829a031e1724e6430";
+    const ORDER_MINUS_ONE_BYTES: vector<u8> = x"000000f093f5e1439170b97948e833285d588181b64550b829a031e1724e6430";
+    const LONG_SCALAR_BYTES: vector<u8> = x"000000f093f5e1439170b97948e833285d588181b64550b829a031e1724e643000";
+    const SHORT_SCALAR_BYTES: vector<u8> = x"000000f093f5e1439170b97948e833285d588181b64550b829a031e1724e64";
+    const LONG_G1_BYTES: vector<u8> = x"010000000000000000000000000000000000000000000000000000000000000000";
+    const SHORT_G1_BYTES: vector<u8> = x"0100000000000000000000000000000000000000000000000000000000000000";
+    const NOT_ON_CURVE_G1_BYTES: vector<u8> = x"0400000000000000000000000000000000000000000000000000000000000000";
+
+    #[test]
+    fun test_scalar_ops() {
+        let zero = bn254::scalar_from_u64(0);
+        let one = bn254::scalar_from_u64(1);
+        assert!(group_ops::equal(&zero, &bn254::scalar_zero()));
+        assert!(group_ops::equal(&one, &bn254::scalar_one()));
+        assert!(group_ops::equal(&zero, &bn254::scalar_one()) == false);
+
+        let two = bn254::scalar_add(&one, &one);
+        let four = bn254::scalar_add(&two, &two);
+        assert!(group_ops::equal(&four, &bn254::scalar_from_u64(4)));
+
+        let eight = bn254::scalar_mul(&four, &two);
+        assert!(group_ops::equal(&eight, &bn254::scalar_from_u64(8)));
+
+        let six = bn254::scalar_sub(&eight, &two);
+        assert!(group_ops::equal(&six, &bn254::scalar_from_u64(6)));
+
+        let three = bn254::scalar_div(&two, &six);
+        assert!(group_ops::equal(&three, &bn254::scalar_from_u64(3)));
+
+        let minus_three = bn254::scalar_neg(&three);
+        assert!(group_ops::equal(&bn254::scalar_add(&minus_three, &six), &bn254::scalar_from_u64(3)));
+
+        let inv_three = bn254::scalar_inv(&three);
+        assert!(group_ops::equal(&bn254::scalar_mul(&six, &inv_three), &bn254::scalar_from_u64(2)));
+
+        let order_minus_one = bn254::scalar_from_bytes(&ORDER_MINUS_ONE_BYTES);
+        assert!(group_ops::equal(&bn254::scalar_add(&order_minus_one, &one), &zero));
+    }
+
+    #[test]
+    fun test_scalar_to_bytes_regression() {
+        let eight = bn254::scalar_from_u64(8);
+        let eight_bytes = *group_ops::bytes(&eight);
+        let expected = x"0800000000000000000000000000000000000000000000000000000000000000";
+        assert_eq(expected, eight_bytes);
+
+        let minus_one = bn254::scalar_sub(&bn254::scalar_zero(), &bn254::scalar_one());
+        let minus_one_bytes = *group_ops::bytes(&minus_one);
+        assert_eq(ORDER_MINUS_ONE_BYTES, minus_one_bytes);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_scalar_order() {
+        let _ = bn254::scalar_from_bytes(&ORDER_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_scalar_too_short() {
+        let _ = bn254::scalar_from_bytes(&SHORT_SCALAR_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_scalar_too_long() {
+        let _ = bn254::scalar_from_bytes(&LONG_SCALAR_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_scalar_inv() {
+        let _ = bn254::scalar_inv(&bn254::scalar_zero());
+    }
+
+    #[test]
+    fun test_g1_ops() {
+        let id = bn254::g1_identity();
+        let g = bn254::g1_generator();
+
+        assert!(group_ops::equal(&id, &bn254::g1_sub(&g, &g)));
+        assert!(group_ops::equal(&g, &bn254::g1_add(&id, &g)));
+
+        let two_g = bn254::g1_add(&g, &g);
+        let four_g = bn254::g1_add(&two_g, &two_g);
+        assert!(group_ops::equal(&four_g, &bn254::g1_mul(&bn254::scalar_from_u64(4), &g)));
+        assert!(group_ops::equal(&id, &bn254::g1_mul(&bn254::scalar_zero(), &g)));
+        assert!(group_ops::equal(&two_g, &bn254::g1_div(&bn254::scalar_from_u64(2), &four_g)));
+
+        let minus_two_g = bn254::g1_neg(&two_g);
+        assert!(group_ops::equal(&two_g, &bn254::g1_add(&minus_two_g, &four_g)));
+
+        let order_minus_one = bn254::scalar_from_bytes(&ORDER_MINUS_ONE_BYTES);
+        assert!(group_ops::equal(&bn254::g1_neg(&g), &bn254::g1_mul(&order_minus_one, &g)));
+    }
+
+    #[test]
+    fun test_valid_g1_from_bytes() {
+        let g = bn254::g1_generator();
+        let g_from_bytes = bn254::g1_from_bytes(group_ops::bytes(&g));
+        assert!(group_ops::equal(&g, &g_from_bytes));
+
+        let id = bn254::g1_identity();
+        let id_from_bytes = bn254::g1_from_bytes(group_ops::bytes(&id));
+        assert!(group_ops::equal(&id, &id_from_bytes));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_g1_too_long() {
+        let _ = bn254::g1_from_bytes(&LONG_G1_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_g1_too_short() {
+        let _ = bn254::g1_from_bytes(&SHORT_G1_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_g1_not_on_curve() {
+        let _ = bn254::g1_from_bytes(&NOT_ON_CURVE_G1_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_g1_div() {
+        let _ = bn254::g1_div(&bn254::scalar_zero(), &bn254::g1_generator());
+    }
+
+    #[test]
+    fun test_g2_ops() {
+        let id = bn254::g2_identity();
+        let g = bn254::g2_generator();
+
+        assert!(group_ops::equal(&id, &bn254::g2_sub(&g, &g)));
+        assert!(group_ops::equal(&g, &bn254::g2_add(&id, &g)));
+
+        let two_g = bn254::g2_add(&g, &g);
+        let four_g = bn254::g2_add(&two_g, &two_g);
+        assert!(group_ops::equal(&four_g, &bn254::g2_mul(&bn254::scalar_from_u64(4), &g)));
+        assert!(group_ops::equal(&two_g, &bn254::g2_div(&bn254::scalar_from_u64(2), &four_g)));
+
+        let minus_two_g = bn254::g2_neg(&two_g);
+        assert!(group_ops::equal(&two_g, &bn254::g2_add(&minus_two_g, &four_g)));
+
+        let g_from_bytes = bn254::g2_from_bytes(group_ops::bytes(&g));
+        assert!(group_ops::equal(&g, &g_from_bytes));
+    }
+
+    #[test]
+    fun test_gt_ops() {
+        let id = bn254::gt_identity();
+        let g = bn254::gt_generator();
+
+        assert!(group_ops::equal(&id, &bn254::gt_sub(&g, &g)));
+        assert!(group_ops::equal(&g, &bn254::gt_add(&id, &g)));
+
+        let two_g = bn254::gt_add(&g, &g);
+        let four_g = bn254::gt_add(&two_g, &two_g);
+        assert!(group_ops::equal(&four_g, &bn254::gt_mul(&bn254::scalar_from_u64(4), &g)));
+        assert!(group_ops::equal(&id, &bn254::gt_mul(&bn254::scalar_zero(), &g)));
+        assert!(group_ops::equal(&two_g, &bn254::gt_div(&bn254::scalar_from_u64(2), &four_g)));
+    }
+
+    #[test]
+    fun test_pairing() {
+        let g1 = bn254::g1_generator();
+        let g2 = bn254::g2_generator();
+        let mut gen = random::new_generator_for_testing();
+        let a = bn254::scalar_from_u64(gen.generate_u64());
+        let b = bn254::scalar_from_u64(gen.generate_u64());
+
+        // e(a*g1, b*g2) = ab*e(g1, g2)
+        let lhs = bn254::pairing(&bn254::g1_mul(&a, &g1), &bn254::g2_mul(&b, &g2));
+        let rhs = bn254::gt_mul(&bn254::scalar_mul(&a, &b), &bn254::gt_generator());
+        assert!(group_ops::equal(&lhs, &rhs));
+
+        let id = bn254::pairing(&bn254::g1_identity(), &g2);
+        assert!(group_ops::equal(&id, &bn254::gt_identity()));
+    }
+
+    #[test]
+    fun test_msm_g1() {
+        let mut expected_result = bn254::g1_identity();
+        let g = bn254::g1_generator();
+        let mut scalars: vector<group_ops::Element<bn254::Scalar>> = vector[];
+        let mut elements: vector<group_ops::Element<bn254::G1>> = vector[];
+        let mut gen = random::new_generator_for_testing();
+        let mut i = gen.generate_u8() % 32 + 1;
+        while (i > 0) {
+            let base = bn254::g1_mul(&bn254::scalar_from_u64(gen.generate_u64()), &g);
+            let exponent_scalar = bn254::scalar_from_u64(gen.generate_u64());
+            expected_result = bn254::g1_add(&expected_result, &bn254::g1_mul(&exponent_scalar, &base));
+            elements.push_back(base);
+            scalars.push_back(exponent_scalar);
+            i = i - 1;
+        };
+        let result = bn254::g1_multi_scalar_multiplication(&scalars, &elements);
+        assert!(group_ops::equal(&result, &expected_result));
+    }
+
+    #[test]
+    fun test_msm_g2() {
+        let mut expected_result = bn254::g2_identity();
+        let g = bn254::g2_generator();
+        let mut scalars: vector<group_ops::Element<bn254::Scalar>> = vector[];
+        let mut elements: vector<group_ops::Element<bn254::G2>> = vector[];
+        let mut gen = random::new_generator_for_testing();
+        let mut i = gen.generate_u8() % 32 + 1;
+        while (i > 0) {
+            let base = bn254::g2_mul(&bn254::scalar_from_u64(gen.generate_u64()), &g);
+            let exponent_scalar = bn254::scalar_from_u64(gen.generate_u64());
+            expected_result = bn254::g2_add(&expected_result, &bn254::g2_mul(&exponent_scalar, &base));
+            elements.push_back(base);
+            scalars.push_back(exponent_scalar);
+            i = i - 1;
+        };
+        let result = bn254::g2_multi_scalar_multiplication(&scalars, &elements);
+        assert!(group_ops::equal(&result, &expected_result));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInputTooLong)]
+    fun test_msm_g1_too_long() {
+        let mut i = 1;
+        let g = bn254::g1_generator();
+        let mut scalars: vector<group_ops::Element<bn254::Scalar>> = vector[];
+        let mut elements: vector<group_ops::Element<bn254::G1>> = vector[];
+        while (i < 34) {
+            // this limit is defined in the protocol config
+            elements.push_back(g);
+            scalars.push_back(bn254::scalar_from_u64(i));
+            i = i + 1;
+        };
+        let _ = bn254::g1_multi_scalar_multiplication(&scalars, &elements);
+    }
+}
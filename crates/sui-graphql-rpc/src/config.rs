@@ -121,6 +121,8 @@ pub struct Limits {
     pub max_type_nodes: u32,
     /// Maximum deph of a move value.
     pub max_move_value_depth: u32,
+    /// Maximum number of bytes spent on type and field names when decoding a move value.
+    pub max_move_value_size: u32,
     /// Maximum number of transaction ids that can be passed to a `TransactionBlockFilter`.
     pub max_transaction_ids: u32,
     /// Maximum number of candidates to scan when gathering a page of results.
@@ -428,6 +430,7 @@ impl Limits {
             max_type_argument_width: self.max_type_argument_width as usize,
             max_type_nodes: self.max_type_nodes as usize,
             max_move_value_depth: self.max_move_value_depth as usize,
+            max_move_value_size: self.max_move_value_size as usize,
         }
     }
 }
@@ -507,6 +510,8 @@ impl Default for Limits {
             max_type_nodes: 256,
             // <https://github.com/MystenLabs/sui/blob/4b934f87acae862cecbcbefb3da34cabb79805aa/crates/sui-protocol-config/src/lib.rs#L1988>
             max_move_value_depth: 128,
+            // Matches the default budget used for bounded deserialization elsewhere.
+            max_move_value_size: 1024 * 1024,
             // Filter-specific limits, such as the number of transaction ids that can be specified
             // for the `TransactionBlockFilter`.
             max_transaction_ids: 1000,
@@ -593,6 +598,7 @@ mod tests {
                 max-type-argument-width = 64
                 max-type-nodes = 128
                 max-move-value-depth = 256
+                max-move-value-size = 4096
                 max-transaction-ids = 11
                 max-scan-limit = 50
             "#,
@@ -615,6 +621,7 @@ mod tests {
                 max_type_argument_width: 64,
                 max_type_nodes: 128,
                 max_move_value_depth: 256,
+                max_move_value_size: 4096,
                 max_transaction_ids: 11,
                 max_scan_limit: 50,
             },
@@ -681,6 +688,7 @@ mod tests {
                 max-type-argument-width = 64
                 max-type-nodes = 128
                 max-move-value-depth = 256
+                max-move-value-size = 4096
                 max-transaction-ids = 42
                 max-scan-limit = 420
 
@@ -706,6 +714,7 @@ mod tests {
                 max_type_argument_width: 64,
                 max_type_nodes: 128,
                 max_move_value_depth: 256,
+                max_move_value_size: 4096,
                 max_transaction_ids: 42,
                 max_scan_limit: 420,
             },
@@ -9,6 +9,7 @@ use move_core_types::{
     language_storage::{StructTag, TypeTag},
 };
 use serde::{Deserialize, Serialize};
+use sui_package_resolver::{error::Error as ResolverError, value};
 use sui_types::object::bounded_visitor::BoundedVisitor;
 
 use crate::data::package_resolver::PackageResolver;
@@ -161,7 +162,14 @@ impl MoveValue {
     }
 
     fn json_impl(&self, layout: A::MoveTypeLayout) -> Result<Json, Error> {
-        Ok(try_to_json_value(self.value_impl(layout)?)?.into())
+        let json = value::to_json(self.value_impl(layout)?).map_err(|e| match e {
+            ResolverError::UnexpectedSigner => unexpected_signer_error(),
+            e => Error::Internal(e.to_string()),
+        })?;
+
+        Ok(Value::from_json(json)
+            .map_err(|e| Error::Internal(format!("Failed to convert JSON: {e}")))?
+            .into())
     }
 }
 
@@ -248,82 +256,6 @@ impl TryFrom<(Identifier, A::MoveValue)> for MoveField {
     }
 }
 
-fn try_to_json_value(value: A::MoveValue) -> Result<Value, Error> {
-    use A::MoveValue as V;
-    Ok(match value {
-        V::U8(n) => Value::Number(n.into()),
-        V::U16(n) => Value::Number(n.into()),
-        V::U32(n) => Value::Number(n.into()),
-        V::U64(n) => Value::String(n.to_string()),
-        V::U128(n) => Value::String(n.to_string()),
-        V::U256(n) => Value::String(n.to_string()),
-
-        V::Bool(b) => Value::Boolean(b),
-        V::Address(a) => Value::String(a.to_canonical_string(/* with_prefix */ true)),
-
-        V::Vector(xs) => Value::List(
-            xs.into_iter()
-                .map(try_to_json_value)
-                .collect::<Result<_, _>>()?,
-        ),
-
-        V::Struct(s) => {
-            let A::MoveStruct { type_, fields } = s;
-            if is_type(&type_, &STD, MOD_OPTION, TYP_OPTION) {
-                // 0x1::option::Option
-                match extract_option(&type_, fields)? {
-                    Some(value) => try_to_json_value(value)?,
-                    None => Value::Null,
-                }
-            } else if is_type(&type_, &STD, MOD_ASCII, TYP_STRING)
-                || is_type(&type_, &STD, MOD_STRING, TYP_STRING)
-            {
-                // 0x1::ascii::String, 0x1::string::String
-                Value::String(extract_string(&type_, fields)?)
-            } else if is_type(&type_, &SUI, MOD_OBJECT, TYP_UID) {
-                // 0x2::object::UID
-                Value::String(
-                    extract_uid(&type_, fields)?.to_canonical_string(/* with_prefix */ true),
-                )
-            } else if is_type(&type_, &SUI, MOD_OBJECT, TYP_ID) {
-                // 0x2::object::ID
-                Value::String(
-                    extract_id(&type_, fields)?.to_canonical_string(/* with_prefix */ true),
-                )
-            } else {
-                // Arbitrary structs
-                Value::Object(
-                    fields
-                        .into_iter()
-                        .map(|(name, value)| {
-                            Ok((Name::new(name.to_string()), try_to_json_value(value)?))
-                        })
-                        .collect::<Result<_, Error>>()?,
-                )
-            }
-        }
-
-        V::Variant(A::MoveVariant {
-            type_: _,
-            variant_name,
-            tag: _,
-            fields,
-        }) => {
-            let fields = fields
-                .into_iter()
-                .map(|(name, value)| Ok((Name::new(name.to_string()), try_to_json_value(value)?)))
-                .collect::<Result<_, Error>>()?;
-            Value::Object(
-                vec![(Name::new(variant_name.to_string()), Value::Object(fields))]
-                    .into_iter()
-                    .collect(),
-            )
-        }
-        // Sui does not support `signer` as a type.
-        V::Signer(_) => return Err(unexpected_signer_error()),
-    })
-}
-
 fn is_type(tag: &StructTag, address: &AccountAddress, module: &IdentStr, name: &IdentStr) -> bool {
     &tag.address == address
         && tag.module.as_ident_str() == module
@@ -6,9 +6,14 @@ use crate::indexer_reader::IndexerReader;
 use async_trait::async_trait;
 use move_core_types::language_storage::StructTag;
 use sui_json_rpc::transaction_builder_api::TransactionBuilderApi as SuiTransactionBuilderApi;
-use sui_json_rpc_types::{SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse};
+use sui_json_rpc_types::{
+    CheckpointId, ProtocolConfigResponse, SuiObjectDataFilter, SuiObjectDataOptions,
+    SuiObjectResponse,
+};
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
 use sui_transaction_builder::DataReader;
 use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
+use sui_types::digests::ChainIdentifier;
 use sui_types::object::Object;
 
 pub(crate) struct TransactionBuilderApi {
@@ -59,6 +64,22 @@ impl DataReader for TransactionBuilderApi {
         Ok((result, options).try_into()?)
     }
 
+    async fn multi_get_object_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>, anyhow::Error> {
+        let stored_objects = self.inner.multi_get_objects(object_ids).await?;
+        let futures = stored_objects.into_iter().map(|stored_object| async {
+            let object_read = stored_object
+                .try_into_object_read(self.inner.package_resolver())
+                .await?;
+            Ok::<SuiObjectResponse, anyhow::Error>((object_read, options.clone()).try_into()?)
+        });
+
+        futures::future::try_join_all(futures).await
+    }
+
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
         let epoch_info = GovernanceReadApi::new(self.inner.clone())
             .get_epoch_info(None)
@@ -67,4 +88,19 @@ impl DataReader for TransactionBuilderApi {
             .reference_gas_price
             .ok_or_else(|| anyhow::anyhow!("missing latest reference_gas_price"))?)
     }
+
+    async fn get_protocol_config(&self) -> Result<ProtocolConfigResponse, anyhow::Error> {
+        let genesis = self
+            .inner
+            .get_checkpoint(CheckpointId::SequenceNumber(0))
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("missing genesis checkpoint"))?;
+        let chain = ChainIdentifier::from(genesis.digest).chain();
+
+        let latest_epoch = self.inner.get_latest_epoch_info_from_db().await?;
+        let version = ProtocolVersion::new(latest_epoch.protocol_version as u64);
+        ProtocolConfig::get_for_version_if_supported(version, chain)
+            .map(ProtocolConfigResponse::from)
+            .ok_or_else(|| anyhow::anyhow!("unsupported protocol version {version:?}"))
+    }
 }
@@ -0,0 +1,37 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Emits events containing enums and generic structs
+module events::events;
+
+use std::string::{Self, String};
+use sui::event;
+
+public enum Shape has copy, drop {
+    Circle { radius: u64 },
+    Square { side: u8 },
+}
+
+public struct Labelled<T: copy + drop> has copy, drop {
+    label: String,
+    value: T,
+    note: Option<u64>,
+}
+
+public struct ShapeEvent has copy, drop {
+    shape: Labelled<Shape>,
+}
+
+fun init(_ctx: &mut TxContext) {
+    emit(Shape::Circle { radius: 3 }, option::some(7));
+}
+
+public fun emit_square(side: u8) {
+    emit(Shape::Square { side }, option::none());
+}
+
+fun emit(shape: Shape, note: Option<u64>) {
+    event::emit(ShapeEvent {
+        shape: Labelled { label: string::utf8(b"shape"), value: shape, note },
+    });
+}
@@ -549,6 +549,137 @@ async fn test_get_metadata() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_events_of_enums_and_generics() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await;
+    let http_client = cluster.rpc_client();
+    let address = cluster.get_address_0();
+
+    let objects = http_client
+        .get_owned_objects(
+            address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new()
+                    .with_type()
+                    .with_owner()
+                    .with_previous_transaction(),
+            )),
+            None,
+            None,
+        )
+        .await?
+        .data;
+    let gas = objects.first().unwrap().object().unwrap();
+
+    // Publishing emits an event from the module initializer
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "data", "events"]);
+    let compiled_package = BuildConfig::new_for_testing().build(&path)?;
+    let compiled_modules_bytes =
+        compiled_package.get_package_base64(/* with_unpublished_deps */ false);
+    let dependencies = compiled_package.get_dependency_storage_package_ids();
+
+    let transaction_bytes: TransactionBlockBytes = http_client
+        .publish(
+            address,
+            compiled_modules_bytes,
+            dependencies,
+            Some(gas.object_id),
+            100_000_000.into(),
+        )
+        .await?;
+    let tx = cluster
+        .wallet
+        .sign_transaction(&transaction_bytes.to_data()?);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+
+    let tx_response = http_client
+        .execute_transaction_block(
+            tx_bytes,
+            signatures,
+            Some(
+                SuiTransactionBlockResponseOptions::new()
+                    .with_object_changes()
+                    .with_events(),
+            ),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+
+    let circle = serde_json::json!({
+        "shape": {
+            "label": "shape",
+            "value": { "variant": "Circle", "fields": { "radius": "3" } },
+            "note": "7",
+        }
+    });
+    let events = tx_response.events.unwrap().data;
+    assert_eq!(1, events.len());
+    assert_eq!(circle, events[0].parsed_json);
+
+    // The same event read back from the store renders identically
+    let events = http_client.get_events(tx_response.digest).await?;
+    assert_eq!(1, events.len());
+    assert_eq!(circle, events[0].parsed_json);
+
+    let package_id = tx_response
+        .object_changes
+        .unwrap()
+        .iter()
+        .find_map(|e| {
+            if let ObjectChange::Published { package_id, .. } = e {
+                Some(*package_id)
+            } else {
+                None
+            }
+        })
+        .unwrap();
+
+    let transaction_bytes: TransactionBlockBytes = http_client
+        .move_call(
+            address,
+            package_id,
+            "events".to_string(),
+            "emit_square".to_string(),
+            vec![],
+            call_args!("5")?,
+            Some(gas.object_id),
+            10_000_000.into(),
+            None,
+        )
+        .await?;
+    let tx = cluster
+        .wallet
+        .sign_transaction(&transaction_bytes.to_data()?);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+
+    let tx_response = http_client
+        .execute_transaction_block(
+            tx_bytes,
+            signatures,
+            Some(SuiTransactionBlockResponseOptions::new().with_events()),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+
+    let square = serde_json::json!({
+        "shape": {
+            "label": "shape",
+            "value": { "variant": "Square", "fields": { "side": 5 } },
+            "note": null,
+        }
+    });
+    let events = tx_response.events.unwrap().data;
+    assert_eq!(1, events.len());
+    assert_eq!(square, events[0].parsed_json);
+
+    let events = http_client.get_events(tx_response.digest).await?;
+    assert_eq!(1, events.len());
+    assert_eq!(square, events[0].parsed_json);
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_get_total_supply() -> Result<(), anyhow::Error> {
     let cluster = TestClusterBuilder::new().build().await;
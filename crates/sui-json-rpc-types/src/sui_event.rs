@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use fastcrypto::encoding::{Base58, Base64};
-use move_core_types::annotated_value::MoveDatatypeLayout;
+use move_core_types::annotated_value::{MoveDatatypeLayout, MoveValue};
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::StructTag;
 use mysten_metrics::monitored_scope;
@@ -100,6 +100,18 @@ impl SuiEvent {
         event_seq: u64,
         timestamp_ms: Option<u64>,
         layout: MoveDatatypeLayout,
+    ) -> SuiResult<Self> {
+        let move_value = Event::move_event_to_move_value(&event.contents, layout)?;
+        Self::try_from_move_value(event, tx_digest, event_seq, timestamp_ms, move_value)
+    }
+
+    /// Like [`SuiEvent::try_from`], for an event whose contents were already decoded.
+    pub fn try_from_move_value(
+        event: Event,
+        tx_digest: TransactionDigest,
+        event_seq: u64,
+        timestamp_ms: Option<u64>,
+        move_value: MoveValue,
     ) -> SuiResult<Self> {
         let Event {
             package_id,
@@ -111,7 +123,6 @@ impl SuiEvent {
 
         let bcs = contents.to_vec();
 
-        let move_value = Event::move_event_to_move_value(&contents, layout)?;
         let (type_, fields) = type_and_fields_from_move_event_data(move_value)?;
 
         Ok(SuiEvent {
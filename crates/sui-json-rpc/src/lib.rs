@@ -47,6 +47,7 @@ mod metrics;
 pub mod move_utils;
 pub mod name_service;
 mod object_changes;
+mod package_resolver;
 pub mod read_api;
 mod routing_layer;
 pub mod transaction_builder_api;
@@ -0,0 +1,147 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decodes the contents of events with the package resolver, the same way as GraphQL and the
+//! indexers, before they are converted to their JSON-RPC representation.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::TypeTag;
+use sui_json_rpc_types::{SuiEvent, SuiTransactionBlockEvents};
+use sui_package_resolver::{
+    error::Error as PackageResolverError, Package, PackageStore, PackageStoreWithLruCache, Resolver,
+};
+use sui_types::base_types::TransactionDigest;
+use sui_types::committee::EpochId;
+use sui_types::effects::TransactionEvents;
+use sui_types::event::Event;
+use sui_types::object::Object;
+use sui_types::storage::BackingPackageStore;
+use sui_types::SYSTEM_PACKAGE_ADDRESSES;
+
+use crate::error::Error;
+
+/// Serves packages to the resolver from the authority's package store.
+pub(crate) struct BackingPackageResolver(Arc<dyn BackingPackageStore + Send + Sync>);
+
+#[async_trait]
+impl PackageStore for BackingPackageResolver {
+    async fn fetch(&self, id: AccountAddress) -> sui_package_resolver::Result<Arc<Package>> {
+        let package = self
+            .0
+            .get_package_object(&id.into())
+            .map_err(|e| PackageResolverError::Store {
+                store: "BackingPackageStore",
+                error: e.to_string(),
+            })?
+            .ok_or(PackageResolverError::PackageNotFound(id))?;
+        Ok(Arc::new(Package::read_from_object(package.object())?))
+    }
+}
+
+pub(crate) type PackageCache = PackageStoreWithLruCache<BackingPackageResolver>;
+
+/// Resolver shared by all requests of an API, caching the packages it reads.
+pub(crate) struct PackageResolver {
+    resolver: Arc<Resolver<PackageCache>>,
+    /// The epoch of the last request. System packages can only be upgraded at epoch boundaries,
+    /// so they are evicted from the cache when it changes.
+    epoch: AtomicU64,
+}
+
+impl PackageResolver {
+    pub(crate) fn new(store: Arc<dyn BackingPackageStore + Send + Sync>) -> Self {
+        let cache = PackageStoreWithLruCache::new(BackingPackageResolver(store));
+        Self {
+            resolver: Arc::new(Resolver::new(cache)),
+            epoch: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the resolver for a request served in `epoch`.
+    pub(crate) fn resolver(&self, epoch: EpochId) -> Arc<Resolver<PackageCache>> {
+        if self.epoch.swap(epoch, Ordering::Relaxed) != epoch {
+            self.resolver
+                .package_store()
+                .evict(SYSTEM_PACKAGE_ADDRESSES.iter().copied());
+        }
+        self.resolver.clone()
+    }
+
+    /// Returns a resolver for the events of a transaction that was just executed, which may
+    /// reference packages that it published but that are not committed to the store yet.
+    pub(crate) fn post_execution_resolver(
+        &self,
+        epoch: EpochId,
+        output_objects: &Option<Vec<Object>>,
+    ) -> Result<Resolver<PostExecutionPackageStore>, Error> {
+        let mut new_packages = BTreeMap::new();
+        for object in output_objects.iter().flatten() {
+            if object.is_package() {
+                let package = Package::read_from_object(object)
+                    .map_err(|e| Error::InternalError(e.into()))?;
+                new_packages.insert(AccountAddress::from(object.id()), Arc::new(package));
+            }
+        }
+        Ok(Resolver::new(PostExecutionPackageStore {
+            new_packages,
+            shared: self.resolver(epoch),
+        }))
+    }
+}
+
+/// Serves the packages published by a transaction, and all other packages from the shared cache.
+pub(crate) struct PostExecutionPackageStore {
+    new_packages: BTreeMap<AccountAddress, Arc<Package>>,
+    shared: Arc<Resolver<PackageCache>>,
+}
+
+#[async_trait]
+impl PackageStore for PostExecutionPackageStore {
+    async fn fetch(&self, id: AccountAddress) -> sui_package_resolver::Result<Arc<Package>> {
+        match self.new_packages.get(&id) {
+            Some(package) => Ok(package.clone()),
+            None => self.shared.package_store().fetch(id).await,
+        }
+    }
+}
+
+pub(crate) async fn sui_event<S: PackageStore>(
+    resolver: &Resolver<S>,
+    event: Event,
+    tx_digest: TransactionDigest,
+    event_seq: u64,
+    timestamp_ms: Option<u64>,
+) -> Result<SuiEvent, Error> {
+    let value = resolver
+        .move_value(
+            TypeTag::Struct(Box::new(event.type_.clone())),
+            &event.contents,
+        )
+        .await
+        .map_err(|e| Error::InternalError(e.into()))?;
+    Ok(SuiEvent::try_from_move_value(
+        event,
+        tx_digest,
+        event_seq,
+        timestamp_ms,
+        value,
+    )?)
+}
+
+pub(crate) async fn sui_transaction_events<S: PackageStore>(
+    resolver: &Resolver<S>,
+    events: TransactionEvents,
+    tx_digest: TransactionDigest,
+    timestamp_ms: Option<u64>,
+) -> Result<SuiTransactionBlockEvents, Error> {
+    let mut data = Vec::with_capacity(events.data.len());
+    for (seq, event) in events.data.into_iter().enumerate() {
+        data.push(sui_event(resolver, event, tx_digest, seq as u64, timestamp_ms).await?);
+    }
+    Ok(SuiTransactionBlockEvents { data })
+}
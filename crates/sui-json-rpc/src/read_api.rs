@@ -55,6 +55,7 @@ use sui_types::transaction::TransactionDataAPI;
 
 use crate::authority_state::{StateRead, StateReadError, StateReadResult};
 use crate::error::{Error, RpcInterimResult, SuiRpcInputError};
+use crate::package_resolver::{sui_event, sui_transaction_events, PackageResolver};
 use crate::{
     get_balance_changes_from_effect, get_object_changes, ObjectProviderCache, SuiRpcModule,
 };
@@ -69,6 +70,7 @@ pub struct ReadApi {
     pub state: Arc<dyn StateRead>,
     pub transaction_kv_store: Arc<TransactionKeyValueStore>,
     pub metrics: Arc<JsonRpcMetrics>,
+    package_resolver: Arc<PackageResolver>,
 }
 
 // Internal data structure to make it easy to work with data returned from
@@ -106,10 +108,14 @@ impl ReadApi {
         transaction_kv_store: Arc<TransactionKeyValueStore>,
         metrics: Arc<JsonRpcMetrics>,
     ) -> Self {
+        let package_resolver = Arc::new(PackageResolver::new(
+            state.get_backing_package_store().clone(),
+        ));
         Self {
             state,
             transaction_kv_store,
             metrics,
+            package_resolver,
         }
     }
 
@@ -401,8 +407,13 @@ impl ReadApi {
                     let events = events_digest_to_events
                         .get(events_digest)
                         .cloned()
-                        .unwrap_or_else(|| panic!("Expect event digest {events_digest:?} to be found in cache for transaction {transaction_digest}"))
-                        .map(|events| to_sui_transaction_events(self, cache_entry.digest, events));
+                        .unwrap_or_else(|| panic!("Expect event digest {events_digest:?} to be found in cache for transaction {transaction_digest}"));
+                    let events = match events {
+                        Some(events) => {
+                            Some(to_sui_transaction_events(self, cache_entry.digest, events).await)
+                        }
+                        None => None,
+                    };
                     match events {
                         Some(Ok(e)) => cache_entry.events = Some(e),
                         Some(Err(e)) => cache_entry.errors.push(e.to_string()),
@@ -852,7 +863,7 @@ impl ReadApiServer for ReadApi {
                         })
                         .await
                         .map_err(Error::from)??;
-                    match to_sui_transaction_events(self, digest, events) {
+                    match to_sui_transaction_events(self, digest, events).await {
                         Ok(e) => temp_response.events = Some(e),
                         Err(e) => temp_response.errors.push(e.to_string()),
                     };
@@ -937,38 +948,32 @@ impl ReadApiServer for ReadApi {
     #[instrument(skip(self))]
     async fn get_events(&self, transaction_digest: TransactionDigest) -> RpcResult<Vec<SuiEvent>> {
         with_tracing!(async move {
-            let state = self.state.clone();
             let transaction_kv_store = self.transaction_kv_store.clone();
+            let resolver = self
+                .package_resolver
+                .resolver(self.state.load_epoch_store_one_call_per_task().epoch());
             spawn_monitored_task!(async move{
-            let store = state.load_epoch_store_one_call_per_task();
             let effect = transaction_kv_store
                 .get_fx_by_tx_digest(transaction_digest)
                 .await
                 .map_err(Error::from)?;
             let events = if let Some(event_digest) = effect.events_digest() {
-            transaction_kv_store
+            let events = transaction_kv_store
                 .get_events(*event_digest)
                 .await
                 .map_err(
                     |e| {
                         error!("Failed to get transaction events for event digest {event_digest:?} with error: {e:?}");
                         Error::StateReadError(e.into())
-                    })?
-                .data
-                .into_iter()
-                .enumerate()
-                .map(|(seq, e)| {
-                    let layout = store.executor().type_layout_resolver(Box::new(&state.get_backing_package_store().as_ref())).get_annotated_layout(&e.type_)?;
-                    SuiEvent::try_from(
-                        e,
-                        *effect.transaction_digest(),
-                        seq as u64,
-                        None,
-                        layout,
-                    )
-                })
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(Error::SuiError)?
+                    })?;
+            let mut sui_events = vec![];
+            for (seq, e) in events.data.into_iter().enumerate() {
+                sui_events.push(
+                    sui_event(&resolver, e, *effect.transaction_digest(), seq as u64, None)
+                        .await?,
+                );
+            }
+            sui_events
         } else {
             vec![]
         };
@@ -1110,22 +1115,18 @@ impl SuiRpcModule for ReadApi {
 }
 
 #[instrument(skip_all)]
-fn to_sui_transaction_events(
+async fn to_sui_transaction_events(
     fullnode_api: &ReadApi,
     tx_digest: TransactionDigest,
     events: TransactionEvents,
 ) -> Result<SuiTransactionBlockEvents, Error> {
-    let epoch_store = fullnode_api.state.load_epoch_store_one_call_per_task();
-    let backing_package_store = fullnode_api.state.get_backing_package_store();
-    let mut layout_resolver = epoch_store
-        .executor()
-        .type_layout_resolver(Box::new(backing_package_store.as_ref()));
-    Ok(SuiTransactionBlockEvents::try_from(
-        events,
-        tx_digest,
-        None,
-        layout_resolver.as_mut(),
-    )?)
+    let resolver = fullnode_api.package_resolver.resolver(
+        fullnode_api
+            .state
+            .load_epoch_store_one_call_per_task()
+            .epoch(),
+    );
+    sui_transaction_events(&resolver, events, tx_digest, None).await
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -12,7 +12,9 @@ use move_core_types::language_storage::StructTag;
 use sui_core::authority::AuthorityState;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_api::{TransactionBuilderOpenRpc, TransactionBuilderServer};
-use sui_json_rpc_types::{RPCTransactionRequestParams, SuiObjectDataFilter};
+use sui_json_rpc_types::{
+    ProtocolConfigResponse, RPCTransactionRequestParams, SuiObjectDataFilter,
+};
 use sui_json_rpc_types::{
     SuiObjectDataOptions, SuiObjectResponse, SuiTransactionBlockBuilderMode, SuiTypeTag,
     TransactionBlockBytes,
@@ -73,10 +75,29 @@ impl DataReader for AuthorityStateDataReader {
         Ok((result, options).try_into()?)
     }
 
+    async fn multi_get_object_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>, anyhow::Error> {
+        object_ids
+            .iter()
+            .map(|object_id| -> Result<SuiObjectResponse, anyhow::Error> {
+                let result = self.0.get_object_read(object_id)?;
+                Ok((result, options.clone()).try_into()?)
+            })
+            .collect()
+    }
+
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
         let epoch_store = self.0.load_epoch_store_one_call_per_task();
         Ok(epoch_store.reference_gas_price())
     }
+
+    async fn get_protocol_config(&self) -> Result<ProtocolConfigResponse, anyhow::Error> {
+        let epoch_store = self.0.load_epoch_store_one_call_per_task();
+        Ok(epoch_store.protocol_config().clone().into())
+    }
 }
 
 #[async_trait]
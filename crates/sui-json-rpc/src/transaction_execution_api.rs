@@ -12,6 +12,7 @@ use jsonrpsee::RpcModule;
 
 use crate::authority_state::StateRead;
 use crate::error::{Error, SuiRpcInputError};
+use crate::package_resolver::{sui_transaction_events, PackageResolver};
 use crate::{
     get_balance_changes_from_effect, get_object_changes, with_tracing, ObjectProviderCache,
     SuiRpcModule,
@@ -24,7 +25,7 @@ use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_json_rpc_api::{JsonRpcMetrics, WriteApiOpenRpc, WriteApiServer};
 use sui_json_rpc_types::{
     DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlock,
-    SuiTransactionBlockEvents, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::SuiAddress;
@@ -35,7 +36,6 @@ use sui_types::quorum_driver_types::{
     ExecuteTransactionRequestType, ExecuteTransactionRequestV3, ExecuteTransactionResponseV3,
 };
 use sui_types::signature::GenericSignature;
-use sui_types::storage::PostExecutionPackageResolver;
 use sui_types::sui_serde::BigInt;
 use sui_types::transaction::{
     InputObjectKind, Transaction, TransactionData, TransactionDataAPI, TransactionKind,
@@ -46,6 +46,7 @@ pub struct TransactionExecutionApi {
     state: Arc<dyn StateRead>,
     transaction_orchestrator: Arc<TransactiondOrchestrator<NetworkAuthorityClient>>,
     metrics: Arc<JsonRpcMetrics>,
+    package_resolver: Arc<PackageResolver>,
 }
 
 impl TransactionExecutionApi {
@@ -54,10 +55,14 @@ impl TransactionExecutionApi {
         transaction_orchestrator: Arc<TransactiondOrchestrator<NetworkAuthorityClient>>,
         metrics: Arc<JsonRpcMetrics>,
     ) -> Self {
+        let package_resolver = Arc::new(PackageResolver::new(
+            state.get_backing_package_store().clone(),
+        ));
         Self {
             state,
             transaction_orchestrator,
             metrics,
+            package_resolver,
         }
     }
 
@@ -184,20 +189,19 @@ impl TransactionExecutionApi {
         let _post_orch_timer = self.metrics.post_orchestrator_latency_ms.start_timer();
 
         let events = if opts.show_events {
-            let epoch_store = self.state.load_epoch_store_one_call_per_task();
-            let backing_package_store = PostExecutionPackageResolver::new(
-                self.state.get_backing_package_store().clone(),
+            let resolver = self.package_resolver.post_execution_resolver(
+                self.state.load_epoch_store_one_call_per_task().epoch(),
                 &response.output_objects,
-            );
-            let mut layout_resolver = epoch_store
-                .executor()
-                .type_layout_resolver(Box::new(backing_package_store));
-            Some(SuiTransactionBlockEvents::try_from(
-                response.events.unwrap_or_default(),
-                digest,
-                None,
-                layout_resolver.as_mut(),
-            )?)
+            )?;
+            Some(
+                sui_transaction_events(
+                    &resolver,
+                    response.events.unwrap_or_default(),
+                    digest,
+                    None,
+                )
+                .await?,
+            )
         } else {
             None
         };
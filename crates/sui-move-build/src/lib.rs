@@ -5,6 +5,7 @@ extern crate move_ir_types;
 
 use std::{
     collections::{BTreeMap, BTreeSet, HashSet},
+    fmt,
     io::Write,
     path::Path,
     str::FromStr,
@@ -52,6 +53,7 @@ use sui_types::{
     BRIDGE_ADDRESS, DEEPBOOK_ADDRESS, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS,
     SUI_SYSTEM_ADDRESS,
 };
+use sui_verifier::unbounded_loop_verifier::{self, UnboundedLoop};
 use sui_verifier::verifier as sui_bytecode_verifier;
 
 #[cfg(test)]
@@ -71,6 +73,30 @@ pub struct CompiledPackage {
     pub bytecode_deps: Vec<(PackageName, CompiledModule)>,
 }
 
+/// A loop over an unbounded collection found by the opt-in bytecode lint, along with its location
+/// in the sources of the package.
+#[derive(Debug, Clone)]
+pub struct UnboundedLoopWarning {
+    pub lint: UnboundedLoop,
+    /// `path:line:column` of the loop, if the source map of the module covers it.
+    pub location: Option<String>,
+    /// Name of the collection parameter in the sources.
+    pub parameter_name: Option<String>,
+}
+
+impl fmt::Display for UnboundedLoopWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(location) = &self.location {
+            write!(f, "{}: ", location)?;
+        }
+        write!(f, "{}", self.lint)?;
+        if let Some(name) = &self.parameter_name {
+            write!(f, " (parameter `{}`)", name)?;
+        }
+        Ok(())
+    }
+}
+
 /// Wrapper around the core Move `BuildConfig` with some Sui-specific info
 #[derive(Clone)]
 pub struct BuildConfig {
@@ -591,6 +617,68 @@ impl CompiledPackage {
     pub fn published_dependency_ids(&self) -> Vec<ObjectID> {
         self.dependency_ids.published.values().cloned().collect()
     }
+
+    /// Runs the opt-in lint for loops over unbounded collections on the modules of this package
+    /// (not including deps). These lints are not enforced on chain.
+    pub fn lint_unbounded_loops(&self) -> Vec<UnboundedLoopWarning> {
+        let mut warnings = vec![];
+        for unit in &self.package.root_compiled_units {
+            let lints = unbounded_loop_verifier::lint_module(&unit.unit.module);
+            if lints.is_empty() {
+                continue;
+            }
+            let source = std::fs::read_to_string(&unit.source_path).ok();
+            let source_map = &unit.unit.source_map;
+            for lint in lints {
+                let location = source.as_ref().and_then(|source| {
+                    let loc = source_map
+                        .get_code_location(lint.function_index, lint.code_offset)
+                        .ok()?;
+                    let (line, column) = line_and_column(source, loc.start() as usize)?;
+                    Some(format!(
+                        "{}:{}:{}",
+                        unit.source_path.display(),
+                        line,
+                        column
+                    ))
+                });
+                let parameter_name = source_map
+                    .get_parameter_or_local_name(lint.function_index, lint.parameter as u64)
+                    .ok()
+                    .map(|(name, _)| name);
+                warnings.push(UnboundedLoopWarning {
+                    lint,
+                    location,
+                    parameter_name,
+                });
+            }
+        }
+        warnings
+    }
+}
+
+/// Runs the opt-in lint for loops over unbounded collections on modules whose sources are not
+/// available, so the warnings carry no location.
+pub fn lint_unbounded_loops<'a>(
+    modules: impl IntoIterator<Item = &'a CompiledModule>,
+) -> Vec<UnboundedLoopWarning> {
+    modules
+        .into_iter()
+        .flat_map(unbounded_loop_verifier::lint_module)
+        .map(|lint| UnboundedLoopWarning {
+            lint,
+            location: None,
+            parameter_name: None,
+        })
+        .collect()
+}
+
+/// Returns the 1-based line and column of a byte offset in `source`.
+fn line_and_column(source: &str, offset: usize) -> Option<(usize, usize)> {
+    let prefix = source.get(..offset)?;
+    let line = prefix.matches('\n').count() + 1;
+    let column = prefix.len() - prefix.rfind('\n').map_or(0, |idx| idx + 1) + 1;
+    Some((line, column))
 }
 
 impl Default for BuildConfig {
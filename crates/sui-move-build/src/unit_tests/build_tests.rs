@@ -46,3 +46,36 @@ fn development_mode_not_allowed() {
         .to_string()
         .contains(&Edition::DEVELOPMENT.unknown_edition_error().to_string()));
 }
+
+#[test]
+fn lint_unbounded_loops() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .to_path_buf()
+        .join("src")
+        .join("unit_tests")
+        .join("data")
+        .join("unbounded_loops");
+    let pkg = BuildConfig::new_for_testing().build(&path).unwrap();
+    let warnings = pkg.lint_unbounded_loops();
+    assert_eq!(warnings.len(), 2, "{warnings:#?}");
+
+    let warning = &warnings[0];
+    assert_eq!(warning.lint.function.as_str(), "add_all");
+    assert_eq!(warning.lint.collection, "vector<u64>");
+    assert_eq!(warning.lint.callee, None);
+    assert_eq!(warning.parameter_name.as_deref(), Some("amounts"));
+    assert!(warning
+        .location
+        .as_ref()
+        .is_some_and(|location| location.contains("unbounded_loops.move:")));
+
+    // The loop of `sum` is reported at its call in `add_sum`
+    let warning = &warnings[1];
+    assert_eq!(warning.lint.function.as_str(), "add_sum");
+    assert_eq!(warning.lint.collection, "vector<u64>");
+    assert_eq!(
+        warning.lint.callee.as_ref().map(|callee| callee.as_str()),
+        Some("sum")
+    );
+    assert_eq!(warning.parameter_name.as_deref(), Some("amounts"));
+}
@@ -0,0 +1,44 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module unbounded_loops::unbounded_loops {
+    public struct Counter has key {
+        id: UID,
+        value: u64,
+    }
+
+    /// Loops over a vector provided by the caller, so it should be flagged.
+    entry fun add_all(counter: &mut Counter, amounts: vector<u64>) {
+        let mut i = 0;
+        while (i < amounts.length()) {
+            counter.value = counter.value + amounts[i];
+            i = i + 1;
+        };
+    }
+
+    /// Loops a fixed number of times, so it should not be flagged.
+    entry fun add_ten(counter: &mut Counter, amount: u64) {
+        let mut i = 0;
+        while (i < 10) {
+            counter.value = counter.value + amount;
+            i = i + 1;
+        };
+    }
+
+    /// Not callable from transactions, so it is not flagged itself.
+    fun sum(amounts: &vector<u64>): u64 {
+        let mut total = 0;
+        let mut i = 0;
+        while (i < amounts.length()) {
+            total = total + amounts[i];
+            i = i + 1;
+        };
+        total
+    }
+
+    /// Passes the vector provided by the caller to `sum`, which loops over it, so it should be
+    /// flagged.
+    entry fun add_sum(counter: &mut Counter, amounts: vector<u64>) {
+        counter.value = counter.value + sum(&amounts);
+    }
+}
@@ -29,6 +29,11 @@ pub struct Build {
     /// and events.
     #[clap(long, global = true)]
     pub generate_struct_layouts: bool,
+    /// If true, warn about `entry` and `public` functions looping over `vector`, `Table`, or
+    /// other collections they receive as arguments, whose size callers may control. These lints
+    /// run on the compiled bytecode and are not enforced on chain.
+    #[clap(long, global = true)]
+    pub lint_unbounded_loops: bool,
     /// The chain ID, if resolved. Required when the dump_bytecode_as_base64 is true,
     /// for automated address management, where package addresses are resolved for the
     /// respective chain in the Move.lock file.
@@ -50,6 +55,7 @@ impl Build {
             self.with_unpublished_dependencies,
             self.dump_bytecode_as_base64,
             self.generate_struct_layouts,
+            self.lint_unbounded_loops,
             self.chain_id.clone(),
         )
     }
@@ -60,6 +66,7 @@ impl Build {
         with_unpublished_deps: bool,
         dump_bytecode_as_base64: bool,
         generate_struct_layouts: bool,
+        lint_unbounded_loops: bool,
         chain_id: Option<String>,
     ) -> anyhow::Result<()> {
         let pkg = BuildConfig {
@@ -69,6 +76,12 @@ impl Build {
             chain_id,
         }
         .build(rerooted_path)?;
+        if lint_unbounded_loops {
+            for warning in pkg.lint_unbounded_loops() {
+                eprintln!("warning: {warning}");
+            }
+        }
+
         if dump_bytecode_as_base64 {
             check_invalid_dependencies(&pkg.dependency_ids.invalid)?;
             if !with_unpublished_deps {
@@ -3,22 +3,28 @@
 
 use crate::SuiNode;
 use axum::{
-    extract::{Query, State},
+    extract::{ConnectInfo, Query, State},
     http::StatusCode,
     routing::{get, post},
     Router,
 };
 use base64::Engine;
-use humantime::parse_duration;
+use humantime::{format_rfc3339_seconds, parse_duration};
+use parking_lot::Mutex;
 use serde::Deserialize;
+use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     str::FromStr,
 };
+use sui_core::overload_monitor::OverloadSignals;
 use sui_types::{
-    base_types::AuthorityName,
+    base_types::{AuthorityName, SuiAddress},
     crypto::{RandomnessPartialSignature, RandomnessRound, RandomnessSignature},
+    digests::TransactionDigest,
     error::SuiError,
 };
 use telemetry_subscribers::TracingHandle;
@@ -68,6 +74,38 @@ use tracing::info;
 // Inject a full signature from another node, bypassing validity checks.
 //
 //  $ curl 'http://127.0.0.1:1337/randomness-inject-full-sig?round=123&sigs=base64encodedsig'
+//
+// View the overload signals, the load shedding overrides and the recent changes to them:
+//
+//  $ curl 'http://127.0.0.1:1337/overload'
+//
+// Shed 30% of transactions regardless of the overload signals, until cleared. The reason is
+// recorded with the change:
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/pin-load-shedding?percentage=30&reason=incident-123'
+//
+// Never shed more than 50% of transactions:
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/cap-load-shedding?percentage=50'
+//
+// Remove the pinned and capped load shedding percentages:
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/clear-load-shedding-override'
+//
+// Exempt transactions from a sender from load shedding for 10 minutes, or remove the exemption:
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/exempt-from-load-shedding?sender=hexencodedaddress&duration=10m'
+//  $ curl -X POST 'http://127.0.0.1:1337/clear-load-shedding-exemption?sender=hexencodedaddress'
+//
+// Trace the stages of 1% of transactions, or stop tracing with a rate of 0. Sampled traces are
+// also exported as spans when OTLP tracing is enabled and TRACE_FILTER includes
+// sui_core::transaction_trace:
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/set-transaction-trace-sample-rate?rate=0.01'
+//
+// View the time a sampled transaction reached each stage:
+//
+//  $ curl 'http://127.0.0.1:1337/transaction-trace?digest=base58encodeddigest'
 
 const LOGGING_ROUTE: &str = "/logging";
 const TRACING_ROUTE: &str = "/enable-tracing";
@@ -80,10 +118,22 @@ const NODE_CONFIG: &str = "/node-config";
 const RANDOMNESS_PARTIAL_SIGS_ROUTE: &str = "/randomness-partial-sigs";
 const RANDOMNESS_INJECT_PARTIAL_SIGS_ROUTE: &str = "/randomness-inject-partial-sigs";
 const RANDOMNESS_INJECT_FULL_SIG_ROUTE: &str = "/randomness-inject-full-sig";
+const OVERLOAD_ROUTE: &str = "/overload";
+const PIN_LOAD_SHEDDING_ROUTE: &str = "/pin-load-shedding";
+const CAP_LOAD_SHEDDING_ROUTE: &str = "/cap-load-shedding";
+const CLEAR_LOAD_SHEDDING_OVERRIDE_ROUTE: &str = "/clear-load-shedding-override";
+const EXEMPT_FROM_LOAD_SHEDDING_ROUTE: &str = "/exempt-from-load-shedding";
+const CLEAR_LOAD_SHEDDING_EXEMPTION_ROUTE: &str = "/clear-load-shedding-exemption";
+const TRANSACTION_TRACE_ROUTE: &str = "/transaction-trace";
+const SET_TRANSACTION_TRACE_SAMPLE_RATE_ROUTE: &str = "/set-transaction-trace-sample-rate";
+
+// The number of load shedding override changes kept for the overload route.
+const MAX_OVERLOAD_AUDIT_LOG_ENTRIES: usize = 100;
 
 struct AppState {
     node: Arc<SuiNode>,
     tracing_handle: TracingHandle,
+    overload_audit_log: Mutex<VecDeque<String>>,
 }
 
 pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: TracingHandle) {
@@ -92,6 +142,7 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
     let app_state = AppState {
         node,
         tracing_handle,
+        overload_audit_log: Mutex::new(VecDeque::new()),
     };
 
     let app = Router::new()
@@ -119,6 +170,26 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
             RANDOMNESS_INJECT_FULL_SIG_ROUTE,
             post(randomness_inject_full_sig),
         )
+        .route(OVERLOAD_ROUTE, get(overload))
+        .route(PIN_LOAD_SHEDDING_ROUTE, post(pin_load_shedding))
+        .route(CAP_LOAD_SHEDDING_ROUTE, post(cap_load_shedding))
+        .route(
+            CLEAR_LOAD_SHEDDING_OVERRIDE_ROUTE,
+            post(clear_load_shedding_override),
+        )
+        .route(
+            EXEMPT_FROM_LOAD_SHEDDING_ROUTE,
+            post(exempt_from_load_shedding),
+        )
+        .route(
+            CLEAR_LOAD_SHEDDING_EXEMPTION_ROUTE,
+            post(clear_load_shedding_exemption),
+        )
+        .route(TRANSACTION_TRACE_ROUTE, get(transaction_trace))
+        .route(
+            SET_TRANSACTION_TRACE_SAMPLE_RATE_ROUTE,
+            post(set_transaction_trace_sample_rate),
+        )
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -443,3 +514,260 @@ async fn randomness_inject_full_sig(
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
     }
 }
+
+// Logs a change to the load shedding overrides and keeps it for the overload route.
+fn audit_overload_change(
+    state: &AppState,
+    client: SocketAddr,
+    change: String,
+    reason: Option<String>,
+) {
+    let reason = reason.unwrap_or_default();
+    info!(client =% client, reason =% reason, "{change}");
+    let mut audit_log = state.overload_audit_log.lock();
+    if audit_log.len() == MAX_OVERLOAD_AUDIT_LOG_ENTRIES {
+        audit_log.pop_front();
+    }
+    audit_log.push_back(format!(
+        "{} {client}: {change} (reason: {reason:?})",
+        format_rfc3339_seconds(SystemTime::now())
+    ));
+}
+
+async fn overload(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let authority_state = state.node.state();
+    let overload_info = &authority_state.overload_info;
+
+    let load_shedding_override = overload_info.load_shedding_override();
+
+    let mut output = format!(
+        "{:#?}\nis_overload: {}\nload_shedding_percentage: {}\npinned_percentage: {:?}\nmax_percentage: {:?}\n",
+        OverloadSignals::collect(&authority_state),
+        overload_info.is_overload.load(Ordering::Relaxed),
+        overload_info
+            .load_shedding_percentage
+            .load(Ordering::Relaxed),
+        load_shedding_override.pinned_percentage,
+        load_shedding_override.max_percentage,
+    );
+    output.push_str("exempt senders:\n");
+    let now = Instant::now();
+    for (sender, deadline) in load_shedding_override.exempt_senders {
+        if deadline > now {
+            output.push_str(&format!("{sender}: expires in {:?}\n", deadline - now));
+        }
+    }
+    output.push_str("recent changes:\n");
+    for change in state.overload_audit_log.lock().iter() {
+        output.push_str(&format!("{change}\n"));
+    }
+
+    (StatusCode::OK, output)
+}
+
+#[derive(Deserialize)]
+struct LoadSheddingPercentage {
+    percentage: u32,
+    reason: Option<String>,
+}
+
+async fn pin_load_shedding(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    args: Query<LoadSheddingPercentage>,
+) -> (StatusCode, String) {
+    let Query(LoadSheddingPercentage { percentage, reason }) = args;
+    if percentage > 100 {
+        return (
+            StatusCode::BAD_REQUEST,
+            "percentage must be at most 100\n".to_string(),
+        );
+    }
+
+    state
+        .node
+        .state()
+        .overload_info
+        .pin_load_shedding(Some(percentage));
+    let change = format!("load shedding pinned to {percentage}%");
+    audit_overload_change(&state, client, change.clone(), reason);
+    (StatusCode::OK, format!("{change}\n"))
+}
+
+async fn cap_load_shedding(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    args: Query<LoadSheddingPercentage>,
+) -> (StatusCode, String) {
+    let Query(LoadSheddingPercentage { percentage, reason }) = args;
+    if percentage > 100 {
+        return (
+            StatusCode::BAD_REQUEST,
+            "percentage must be at most 100\n".to_string(),
+        );
+    }
+
+    state
+        .node
+        .state()
+        .overload_info
+        .cap_load_shedding(Some(percentage));
+    let change = format!("load shedding capped at {percentage}%");
+    audit_overload_change(&state, client, change.clone(), reason);
+    (StatusCode::OK, format!("{change}\n"))
+}
+
+#[derive(Deserialize)]
+struct Reason {
+    reason: Option<String>,
+}
+
+async fn clear_load_shedding_override(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    args: Query<Reason>,
+) -> (StatusCode, String) {
+    let Query(Reason { reason }) = args;
+
+    let authority_state = state.node.state();
+    authority_state.overload_info.pin_load_shedding(None);
+    authority_state.overload_info.cap_load_shedding(None);
+    let change = "load shedding override cleared".to_string();
+    audit_overload_change(&state, client, change.clone(), reason);
+    (StatusCode::OK, format!("{change}\n"))
+}
+
+#[derive(Deserialize)]
+struct ExemptSender {
+    sender: String,
+    duration: String,
+    reason: Option<String>,
+}
+
+async fn exempt_from_load_shedding(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    args: Query<ExemptSender>,
+) -> (StatusCode, String) {
+    let Query(ExemptSender {
+        sender,
+        duration,
+        reason,
+    }) = args;
+
+    let sender = match SuiAddress::from_str(&sender) {
+        Ok(sender) => sender,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    };
+    let Ok(duration) = parse_duration(&duration) else {
+        return (StatusCode::BAD_REQUEST, "invalid duration\n".to_string());
+    };
+
+    state
+        .node
+        .state()
+        .overload_info
+        .exempt_sender(sender, duration);
+    let change = format!("{sender} exempt from load shedding for {duration:?}");
+    audit_overload_change(&state, client, change.clone(), reason);
+    (StatusCode::OK, format!("{change}\n"))
+}
+
+#[derive(Deserialize)]
+struct Sender {
+    sender: String,
+    reason: Option<String>,
+}
+
+async fn clear_load_shedding_exemption(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    args: Query<Sender>,
+) -> (StatusCode, String) {
+    let Query(Sender { sender, reason }) = args;
+    let sender = match SuiAddress::from_str(&sender) {
+        Ok(sender) => sender,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    };
+
+    if !state
+        .node
+        .state()
+        .overload_info
+        .remove_exempt_sender(&sender)
+    {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("{sender} is not exempt from load shedding\n"),
+        );
+    }
+    let change = format!("{sender} no longer exempt from load shedding");
+    audit_overload_change(&state, client, change.clone(), reason);
+    (StatusCode::OK, format!("{change}\n"))
+}
+
+#[derive(Deserialize)]
+struct TransactionTraceArgs {
+    digest: String,
+}
+
+async fn transaction_trace(
+    State(state): State<Arc<AppState>>,
+    args: Query<TransactionTraceArgs>,
+) -> (StatusCode, String) {
+    let Query(TransactionTraceArgs { digest }) = args;
+    let digest = match TransactionDigest::from_str(&digest) {
+        Ok(digest) => digest,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    };
+
+    let Some(trace) = state.node.state().metrics.transaction_tracer.get(&digest) else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("no trace of transaction {digest}, it may not have been sampled\n"),
+        );
+    };
+
+    let mut output = format!(
+        "transaction {digest} first seen at {}\n",
+        format_rfc3339_seconds(trace.start_time)
+    );
+    let mut previous = Duration::ZERO;
+    for (stage, elapsed) in trace.stages {
+        output.push_str(&format!(
+            "{stage:?}: +{elapsed:?} (stage took {:?})\n",
+            elapsed.saturating_sub(previous)
+        ));
+        previous = elapsed;
+    }
+    (StatusCode::OK, output)
+}
+
+#[derive(Deserialize)]
+struct SampleRate {
+    rate: f64,
+}
+
+async fn set_transaction_trace_sample_rate(
+    State(state): State<Arc<AppState>>,
+    args: Query<SampleRate>,
+) -> (StatusCode, String) {
+    let Query(SampleRate { rate }) = args;
+    if !(0.0..=1.0).contains(&rate) {
+        return (
+            StatusCode::BAD_REQUEST,
+            "rate must be between 0 and 1\n".to_string(),
+        );
+    }
+
+    let tracer = &state.node.state().metrics.transaction_tracer;
+    tracer.set_sample_rate(rate);
+    info!(rate, "transaction trace sample rate set");
+    (
+        StatusCode::OK,
+        format!(
+            "transaction trace sample rate set to {}\n",
+            tracer.sample_rate()
+        ),
+    )
+}
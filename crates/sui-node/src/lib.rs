@@ -114,7 +114,7 @@ use sui_network::api::ValidatorServer;
 use sui_network::discovery;
 use sui_network::discovery::TrustedPeerChangeEvent;
 use sui_network::state_sync;
-use sui_protocol_config::{Chain, ProtocolConfig};
+use sui_protocol_config::{Chain, PerObjectCongestionControlMode, ProtocolConfig};
 use sui_snapshot::uploader::StateSnapshotUploader;
 use sui_storage::{
     http_key_value_store::HttpKVStore,
@@ -276,6 +276,9 @@ impl fmt::Debug for SuiNode {
 
 static MAX_JWK_KEYS_PER_FETCH: usize = 100;
 
+/// How often locally measured execution times are shared through consensus.
+const EXECUTION_TIME_OBSERVATION_INTERVAL: Duration = Duration::from_secs(5);
+
 impl SuiNode {
     pub async fn start(
         config: NodeConfig,
@@ -420,6 +423,42 @@ impl SuiNode {
         }
     }
 
+    fn start_execution_time_observer(
+        authority: AuthorityName,
+        epoch_store: Arc<AuthorityPerEpochStore>,
+        consensus_adapter: Arc<ConsensusAdapter>,
+    ) {
+        let epoch = epoch_store.epoch();
+        info!(
+            interval = ?EXECUTION_TIME_OBSERVATION_INTERVAL,
+            "Starting execution time observer task"
+        );
+
+        spawn_monitored_task!(epoch_store.clone().within_alive_epoch(
+            async move {
+                loop {
+                    tokio::time::sleep(EXECUTION_TIME_OBSERVATION_INTERVAL).await;
+                    let Some(observation) = epoch_store.take_execution_time_observation(authority)
+                    else {
+                        continue;
+                    };
+                    debug!(
+                        "Submitting {} execution time estimates to consensus",
+                        observation.estimates.len()
+                    );
+                    let txn = ConsensusTransaction::new_execution_time_observation(observation);
+                    consensus_adapter
+                        .submit(txn, None, &epoch_store)
+                        .tap_err(|e| {
+                            warn!("Error when submitting execution time observation to consensus {:?}", e)
+                        })
+                        .ok();
+                }
+            }
+            .instrument(error_span!("execution_time_observer_task", epoch)),
+        ));
+    }
+
     pub async fn start_async(
         config: NodeConfig,
         registry_service: RegistryService,
@@ -1333,6 +1372,7 @@ impl SuiNode {
         ));
 
         consensus_adapter.swap_throughput_profiler(throughput_profiler);
+        consensus_adapter.set_transaction_tracer(state.metrics.transaction_tracer.clone());
 
         let consensus_handler_initializer = ConsensusHandlerInitializer::new(
             state.clone(),
@@ -1367,6 +1407,18 @@ impl SuiNode {
             );
         }
 
+        if epoch_store
+            .protocol_config()
+            .per_object_congestion_control_mode()
+            == PerObjectCongestionControlMode::ExecutionTimeEstimate
+        {
+            Self::start_execution_time_observer(
+                state.name,
+                epoch_store.clone(),
+                consensus_adapter.clone(),
+            );
+        }
+
         Ok(ValidatorComponents {
             validator_server_handle,
             validator_overload_monitor_handle,
@@ -19,6 +19,18 @@ pub enum Error {
     #[error("{0}")]
     Deserialize(VMError),
 
+    #[error("Display template value cannot be empty")]
+    DisplayEmptyLookup,
+
+    #[error("Field '{0}' not found")]
+    DisplayFieldNotFound(String),
+
+    #[error("Display template value of {0} exceeds maximum depth of {1}")]
+    DisplayLookupDepth(usize, usize),
+
+    #[error("Vector of name {0} is not supported as a Display value")]
+    DisplayVector(String),
+
     #[error("Package has no modules: {0}")]
     EmptyPackage(AccountAddress),
 
@@ -35,6 +47,9 @@ pub enum Error {
     #[error("Linkage not found for package: {0}")]
     LinkageNotFound(AccountAddress),
 
+    #[error("Malformed value of type {0}: {1}")]
+    MalformedValue(String, String),
+
     #[error("Module not found: {0}::{1}")]
     ModuleNotFound(AccountAddress, String),
 
@@ -77,6 +92,12 @@ pub enum Error {
     #[error("Unexpected error: {0}")]
     UnexpectedError(Arc<dyn std::error::Error + Send + Sync + 'static>),
 
+    #[error("Failed to deserialize value of type {0}: {1}")]
+    ValueDeserialize(String, String),
+
     #[error("Type layout nesting exceeded limit of {0}")]
     ValueNesting(usize),
+
+    #[error("Annotated value exceeded size limit of {0} bytes")]
+    ValueTooLarge(usize),
 }
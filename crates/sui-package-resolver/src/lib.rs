@@ -30,14 +30,16 @@ use move_binary_format::{
 };
 use move_core_types::{
     account_address::AccountAddress,
-    annotated_value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout},
+    annotated_value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout, MoveValue},
     language_storage::{StructTag, TypeTag},
 };
 use sui_types::move_package::{MovePackage, TypeOrigin};
+use sui_types::object::bounded_visitor::{self, BoundedVisitor};
 use sui_types::object::Object;
 use sui_types::{base_types::SequenceNumber, Identifier};
 
 pub mod error;
+pub mod value;
 
 // TODO Move to ServiceConfig
 
@@ -65,6 +67,8 @@ pub struct Limits {
     pub max_type_nodes: usize,
     /// Maximum recursion depth through struct fields.
     pub max_move_value_depth: usize,
+    /// Maximum number of bytes to spend on type and field names when decoding a Move value.
+    pub max_move_value_size: usize,
 }
 
 /// Store which fetches package for the given address from the backend db and caches it
@@ -401,6 +405,39 @@ impl<S: PackageStore> Resolver<S> {
         Ok(context.resolve_type_layout(&tag, max_depth)?.0)
     }
 
+    /// Decode `bytes` as a value of type `tag`, annotated with the names of its types, fields, and
+    /// enum variants. Types in the value refer to their defining IDs, as in
+    /// [`Resolver::type_layout`]. Fails if the bytes do not represent a value of that type, or if
+    /// the annotated value exceeds the resolver's limits.
+    pub async fn move_value(&self, tag: TypeTag, bytes: &[u8]) -> Result<MoveValue> {
+        let layout = self.type_layout(tag).await?;
+
+        let mut visitor = match &self.limits {
+            Some(limits) => BoundedVisitor::new(limits.max_move_value_size),
+            None => BoundedVisitor::default(),
+        };
+
+        MoveValue::visit_deserialize(bytes, &layout, &mut visitor).map_err(|e| {
+            match e.downcast_ref::<bounded_visitor::Error>() {
+                Some(bounded_visitor::Error::OutOfBudget) => Error::ValueTooLarge(
+                    self.limits
+                        .as_ref()
+                        .map_or(usize::MAX, |l| l.max_move_value_size),
+                ),
+                _ => Error::ValueDeserialize(
+                    TypeTag::from(&layout).to_canonical_string(/* with_prefix */ true),
+                    e.to_string(),
+                ),
+            }
+        })
+    }
+
+    /// Decode `bytes` as a value of type `tag` (see [`Resolver::move_value`]), and represent it as
+    /// JSON (see [`value::to_json`]).
+    pub async fn move_json(&self, tag: TypeTag, bytes: &[u8]) -> Result<serde_json::Value> {
+        value::to_json(self.move_value(tag, bytes).await?)
+    }
+
     /// Return the abilities of a concrete type, based on the abilities in its type definition, and
     /// the abilities of its concrete type parameters: An instance of a generic type has `store`,
     /// `copy, or `drop` if its definition has the ability, and all its non-phantom type parameters
@@ -1992,6 +2029,78 @@ mod tests {
         insta::assert_snapshot!(fmt(struct_layout, enum_layout));
     }
 
+    /// Decode a value of a generic enum, instantiated with a struct.
+    #[tokio::test]
+    async fn test_move_value() {
+        let (_, cache) = package_cache([(1, build_package("a0"), a0_types())]);
+        let resolver = Resolver::new(cache);
+
+        // 0xa0::m::E1::V { a: @0x42, p: 0xa0::m::T2 { x: 1 }, q: vector[2, 3] }
+        let bytes = bcs::to_bytes(&(0u8, addr("0x42"), 1u8, vec![2u16, 3u16])).unwrap();
+        let tag = type_("0xa0::m::E1<0xa0::m::T2, u16>");
+
+        let value = resolver.move_value(tag.clone(), &bytes).await.unwrap();
+        let MoveValue::Variant(variant) = &value else {
+            panic!("Expected a variant, got: {value:?}");
+        };
+
+        assert_eq!(TypeTag::from(variant.type_.clone()), tag);
+        assert_eq!(variant.variant_name.as_str(), "V");
+
+        let json = resolver.move_json(tag, &bytes).await.unwrap();
+        insta::assert_snapshot!(serde_json::to_string_pretty(&json).unwrap(), @r###"
+        {
+          "V": {
+            "a": "0x0000000000000000000000000000000000000000000000000000000000000042",
+            "p": {
+              "x": 1
+            },
+            "q": [
+              2,
+              3
+            ]
+          }
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn test_err_move_value_too_large() {
+        let (_, cache) = package_cache([(1, build_package("a0"), a0_types())]);
+
+        let resolver = Resolver::new_with_limits(
+            cache,
+            Limits {
+                max_type_argument_width: 100,
+                max_type_argument_depth: 100,
+                max_type_nodes: 100,
+                max_move_value_depth: 100,
+                max_move_value_size: 16,
+            },
+        );
+
+        let bytes = bcs::to_bytes(&(addr("0x42"), 1u8, vec![2u16, 3u16])).unwrap();
+        let err = resolver
+            .move_value(type_("0xa0::m::T1<u8, u16>"), &bytes)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ValueTooLarge(16)));
+    }
+
+    #[tokio::test]
+    async fn test_err_move_value_malformed() {
+        let (_, cache) = package_cache([(1, build_package("a0"), a0_types())]);
+        let resolver = Resolver::new(cache);
+
+        // Missing the vector of `u16`s.
+        let bytes = bcs::to_bytes(&(addr("0x42"), 1u8)).unwrap();
+        let err = resolver
+            .move_value(type_("0xa0::m::T1<u8, u16>"), &bytes)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ValueDeserialize(_, _)));
+    }
+
     #[tokio::test]
     async fn test_value_nesting_boundary_layout() {
         let (_, cache) = package_cache([(1, build_package("a0"), a0_types())]);
@@ -2003,6 +2112,7 @@ mod tests {
                 max_type_argument_depth: 100,
                 max_type_nodes: 100,
                 max_move_value_depth: 3,
+                max_move_value_size: 1024,
             },
         );
 
@@ -2029,6 +2139,7 @@ mod tests {
                 max_type_argument_depth: 100,
                 max_type_nodes: 100,
                 max_move_value_depth: 2,
+                max_move_value_size: 1024,
             },
         );
 
@@ -2056,6 +2167,7 @@ mod tests {
                 max_type_argument_depth: 100,
                 max_type_nodes: 100,
                 max_move_value_depth: 3,
+                max_move_value_size: 1024,
             },
         );
 
@@ -2087,6 +2199,7 @@ mod tests {
                 max_type_argument_depth: 100,
                 max_type_nodes: 100,
                 max_move_value_depth: 3,
+                max_move_value_size: 1024,
             },
         );
 
@@ -2129,6 +2242,7 @@ mod tests {
                 max_type_argument_depth: 100,
                 max_type_nodes: 100,
                 max_move_value_depth: 3,
+                max_move_value_size: 1024,
             },
         );
 
@@ -2636,6 +2750,7 @@ mod tests {
                 max_type_argument_depth: 100,
                 max_type_nodes: 100,
                 max_move_value_depth: 100,
+                max_move_value_size: 1024,
             },
         );
 
@@ -2663,6 +2778,7 @@ mod tests {
                 max_type_argument_depth: 100,
                 max_type_nodes: 2,
                 max_move_value_depth: 100,
+                max_move_value_size: 1024,
             },
         );
 
@@ -2699,6 +2815,7 @@ mod tests {
                 max_type_argument_depth: 2,
                 max_type_nodes: 100,
                 max_move_value_depth: 100,
+                max_move_value_size: 1024,
             },
         );
 
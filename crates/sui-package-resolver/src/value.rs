@@ -0,0 +1,495 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Presentation of annotated Move values (as produced by [`crate::Resolver::move_value`]), as JSON
+//! and as strings substituted into `Display` templates.
+
+use move_core_types::{
+    account_address::AccountAddress,
+    annotated_value as A, ident_str,
+    identifier::{IdentStr, Identifier},
+    language_storage::StructTag,
+};
+use serde_json::{Map, Value};
+
+use crate::{error::Error, Result};
+
+/// Maximum number of fields that can be chained in a single `Display` template lookup, e.g.
+/// `{a.b.c}` chains three fields.
+pub const MAX_DISPLAY_LOOKUP_DEPTH: usize = 10;
+
+const STD: AccountAddress = AccountAddress::ONE;
+const SUI: AccountAddress = AccountAddress::TWO;
+
+const MOD_ASCII: &IdentStr = ident_str!("ascii");
+const MOD_BALANCE: &IdentStr = ident_str!("balance");
+const MOD_OBJECT: &IdentStr = ident_str!("object");
+const MOD_OPTION: &IdentStr = ident_str!("option");
+const MOD_STRING: &IdentStr = ident_str!("string");
+const MOD_URL: &IdentStr = ident_str!("url");
+
+const TYP_BALANCE: &IdentStr = ident_str!("Balance");
+const TYP_ID: &IdentStr = ident_str!("ID");
+const TYP_OPTION: &IdentStr = ident_str!("Option");
+const TYP_STRING: &IdentStr = ident_str!("String");
+const TYP_UID: &IdentStr = ident_str!("UID");
+const TYP_URL: &IdentStr = ident_str!("Url");
+
+/// Representation of a Move value in JSON, where:
+///
+/// - Addresses, IDs, and UIDs are represented in canonical form, as JSON strings.
+/// - Bools are represented by JSON boolean literals.
+/// - u8, u16, and u32 are represented as JSON numbers.
+/// - u64, u128, and u256 are represented as JSON strings.
+/// - Strings (ASCII and UTF8) are represented as JSON strings.
+/// - Vectors are represented by JSON arrays.
+/// - Structs are represented by JSON objects, with fields in declaration order.
+/// - Enums are represented by a JSON object with a single field, named after the variant, whose
+///   value is an object containing the variant's fields.
+/// - Empty optional values are represented by `null`, and non-empty optional values by their
+///   contents.
+pub fn to_json(value: A::MoveValue) -> Result<Value> {
+    use A::MoveValue as V;
+    Ok(match value {
+        V::U8(n) => Value::from(n),
+        V::U16(n) => Value::from(n),
+        V::U32(n) => Value::from(n),
+        V::U64(n) => Value::String(n.to_string()),
+        V::U128(n) => Value::String(n.to_string()),
+        V::U256(n) => Value::String(n.to_string()),
+
+        V::Bool(b) => Value::Bool(b),
+        V::Address(a) => Value::String(a.to_canonical_string(/* with_prefix */ true)),
+
+        V::Vector(xs) => Value::Array(xs.into_iter().map(to_json).collect::<Result<_>>()?),
+
+        V::Struct(A::MoveStruct { type_, fields }) => {
+            if is_type(&type_, &STD, MOD_OPTION, TYP_OPTION) {
+                match extract_option(&type_, fields)? {
+                    Some(value) => to_json(value)?,
+                    None => Value::Null,
+                }
+            } else if is_string(&type_) {
+                Value::String(extract_string(&type_, fields)?)
+            } else if is_type(&type_, &SUI, MOD_OBJECT, TYP_UID) {
+                Value::String(extract_uid(&type_, fields)?.to_canonical_string(true))
+            } else if is_type(&type_, &SUI, MOD_OBJECT, TYP_ID) {
+                Value::String(extract_id(&type_, fields)?.to_canonical_string(true))
+            } else {
+                Value::Object(fields_to_json(fields)?)
+            }
+        }
+
+        V::Variant(A::MoveVariant {
+            variant_name,
+            fields,
+            ..
+        }) => {
+            let mut variant = Map::new();
+            variant.insert(
+                variant_name.to_string(),
+                Value::Object(fields_to_json(fields)?),
+            );
+            Value::Object(variant)
+        }
+
+        // Sui does not support `signer` as a type.
+        V::Signer(_) => return Err(Error::UnexpectedSigner),
+    })
+}
+
+/// Substitute fields from `value` into a `Display` template, following the grammar:
+///
+/// ```notrust
+///     TEMPLATE ::= PART*
+///     PART     ::= '{' CHAIN '}' | '\{' | '\}' | [:utf8:]
+///     CHAIN    ::= IDENT | CHAIN '.' IDENT
+/// ```
+///
+/// Each `CHAIN` is looked up through the fields of structs and enum variants, starting from
+/// `value`, and must end at a value that can be represented as a string: a primitive, a string, an
+/// ID, a URL, a balance, or an optional of one of these (rendered as the empty string if it is
+/// absent). Structs and enums without special handling are rendered in their JSON representation,
+/// while vectors are not supported.
+pub fn render_display(template: &str, value: &A::MoveValue) -> Result<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => output.extend(chars.next()),
+
+            '{' => {
+                let mut chain = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    chain.push(c);
+                }
+
+                if closed {
+                    output.push_str(&display_string(lookup(value, &chain)?, &chain)?);
+                } else {
+                    // An unterminated lookup is treated as literal text.
+                    output.push('{');
+                    output.push_str(&chain);
+                }
+            }
+
+            c => output.push(c),
+        }
+    }
+
+    Ok(output)
+}
+
+fn fields_to_json(fields: Vec<(Identifier, A::MoveValue)>) -> Result<Map<String, Value>> {
+    fields
+        .into_iter()
+        .map(|(name, value)| Ok((name.into_string(), to_json(value)?)))
+        .collect()
+}
+
+/// Follow the chain of field names in `chain` (separated by `.`), starting at `value`.
+fn lookup<'v>(value: &'v A::MoveValue, chain: &str) -> Result<&'v A::MoveValue> {
+    if chain.is_empty() {
+        return Err(Error::DisplayEmptyLookup);
+    }
+
+    let parts: Vec<_> = chain.split('.').collect();
+    if parts.len() > MAX_DISPLAY_LOOKUP_DEPTH {
+        return Err(Error::DisplayLookupDepth(
+            parts.len(),
+            MAX_DISPLAY_LOOKUP_DEPTH,
+        ));
+    }
+
+    parts.into_iter().try_fold(value, |value, part| {
+        let fields = match value {
+            A::MoveValue::Struct(A::MoveStruct { fields, .. }) => fields,
+            A::MoveValue::Variant(A::MoveVariant { fields, .. }) => fields,
+            _ => return Err(Error::DisplayFieldNotFound(part.to_string())),
+        };
+
+        fields
+            .iter()
+            .find_map(|(name, value)| (name.as_str() == part).then_some(value))
+            .ok_or_else(|| Error::DisplayFieldNotFound(part.to_string()))
+    })
+}
+
+/// The string that `value` (found by looking up `chain`) is substituted with in a `Display`
+/// template.
+fn display_string(value: &A::MoveValue, chain: &str) -> Result<String> {
+    use A::MoveValue as V;
+    Ok(match value {
+        V::U8(n) => n.to_string(),
+        V::U16(n) => n.to_string(),
+        V::U32(n) => n.to_string(),
+        V::U64(n) => n.to_string(),
+        V::U128(n) => n.to_string(),
+        V::U256(n) => n.to_string(),
+        V::Bool(b) => b.to_string(),
+        V::Address(a) => a.to_canonical_string(/* with_prefix */ true),
+
+        V::Vector(_) => return Err(Error::DisplayVector(chain.to_string())),
+
+        V::Struct(A::MoveStruct { type_, fields }) => {
+            if is_type(type_, &STD, MOD_OPTION, TYP_OPTION) {
+                match extract_option(type_, fields.clone())? {
+                    Some(value) => display_string(&value, chain)?,
+                    None => String::new(),
+                }
+            } else if is_string(type_) {
+                extract_string(type_, fields.clone())?
+            } else if is_type(type_, &SUI, MOD_URL, TYP_URL) {
+                let url = extract_field(type_, fields.clone(), ident_str!("url"))?;
+                display_string(&url, chain)?
+            } else if is_type(type_, &SUI, MOD_BALANCE, TYP_BALANCE) {
+                let balance = extract_field(type_, fields.clone(), ident_str!("value"))?;
+                display_string(&balance, chain)?
+            } else if is_type(type_, &SUI, MOD_OBJECT, TYP_UID) {
+                extract_uid(type_, fields.clone())?.to_canonical_string(true)
+            } else if is_type(type_, &SUI, MOD_OBJECT, TYP_ID) {
+                extract_id(type_, fields.clone())?.to_canonical_string(true)
+            } else {
+                to_json(value.clone())?.to_string()
+            }
+        }
+
+        V::Variant(_) => to_json(value.clone())?.to_string(),
+
+        // Sui does not support `signer` as a type.
+        V::Signer(_) => return Err(Error::UnexpectedSigner),
+    })
+}
+
+fn is_type(tag: &StructTag, address: &AccountAddress, module: &IdentStr, name: &IdentStr) -> bool {
+    &tag.address == address
+        && tag.module.as_ident_str() == module
+        && tag.name.as_ident_str() == name
+}
+
+/// `0x1::ascii::String` or `0x1::string::String`.
+fn is_string(tag: &StructTag) -> bool {
+    is_type(tag, &STD, MOD_ASCII, TYP_STRING) || is_type(tag, &STD, MOD_STRING, TYP_STRING)
+}
+
+fn malformed(type_: &StructTag, reason: impl Into<String>) -> Error {
+    Error::MalformedValue(
+        type_.to_canonical_string(/* with_prefix */ true),
+        reason.into(),
+    )
+}
+
+/// Extracts the value of field `name` from the contents of a Move struct of type `type_`.
+fn extract_field(
+    type_: &StructTag,
+    fields: Vec<(Identifier, A::MoveValue)>,
+    name: &IdentStr,
+) -> Result<A::MoveValue> {
+    fields
+        .into_iter()
+        .find_map(|(field, value)| (field.as_ident_str() == name).then_some(value))
+        .ok_or_else(|| malformed(type_, format!("missing field '{name}'")))
+}
+
+/// Extracts a Rust String from the contents of a Move struct, assuming it has the shape
+/// `{ bytes: vector<u8> }`, which is conformed to by both `std::ascii::String` and
+/// `std::string::String`.
+fn extract_string(type_: &StructTag, fields: Vec<(Identifier, A::MoveValue)>) -> Result<String> {
+    let A::MoveValue::Vector(elements) = extract_field(type_, fields, ident_str!("bytes"))? else {
+        return Err(malformed(type_, "expected 'bytes' to be a vector"));
+    };
+
+    let bytes = elements
+        .into_iter()
+        .map(|element| match element {
+            A::MoveValue::U8(byte) => Ok(byte),
+            _ => Err(malformed(type_, "expected 'bytes' to contain bytes")),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    String::from_utf8(bytes).map_err(|e| malformed(type_, e.to_string()))
+}
+
+/// Extracts an address from the contents of a Move struct, assuming it has the shape
+/// `{ bytes: address }`, which matches `0x2::object::ID`.
+fn extract_id(
+    type_: &StructTag,
+    fields: Vec<(Identifier, A::MoveValue)>,
+) -> Result<AccountAddress> {
+    let A::MoveValue::Address(addr) = extract_field(type_, fields, ident_str!("bytes"))? else {
+        return Err(malformed(type_, "expected 'bytes' to be an address"));
+    };
+
+    Ok(addr)
+}
+
+/// Extracts an address from the contents of a Move struct, assuming it has the shape
+/// `{ id: 0x2::object::ID { bytes: address } }`, which matches `0x2::object::UID`.
+fn extract_uid(
+    type_: &StructTag,
+    fields: Vec<(Identifier, A::MoveValue)>,
+) -> Result<AccountAddress> {
+    let A::MoveValue::Struct(A::MoveStruct {
+        type_: id_type,
+        fields,
+    }) = extract_field(type_, fields, ident_str!("id"))?
+    else {
+        return Err(malformed(type_, "expected 'id' to be a struct"));
+    };
+
+    if !is_type(&id_type, &SUI, MOD_OBJECT, TYP_ID) {
+        return Err(malformed(type_, "expected 'id' to have type ID"));
+    }
+
+    extract_id(&id_type, fields)
+}
+
+/// Extracts the contents of a Move struct, assuming it has the shape `{ vec: vector<T> }`, where
+/// `vec` contains at most one element, which matches `0x1::option::Option<T>`.
+fn extract_option(
+    type_: &StructTag,
+    fields: Vec<(Identifier, A::MoveValue)>,
+) -> Result<Option<A::MoveValue>> {
+    let A::MoveValue::Vector(mut elements) = extract_field(type_, fields, ident_str!("vec"))?
+    else {
+        return Err(malformed(type_, "expected 'vec' to be a vector"));
+    };
+
+    if elements.len() > 1 {
+        return Err(malformed(
+            type_,
+            "expected 'vec' to contain at most one element",
+        ));
+    }
+
+    Ok(elements.pop())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use move_core_types::u256::U256;
+
+    use super::*;
+
+    fn struct_(type_: &str, fields: Vec<(&str, A::MoveValue)>) -> A::MoveValue {
+        A::MoveValue::Struct(A::MoveStruct {
+            type_: StructTag::from_str(type_).unwrap(),
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| (Identifier::new(name).unwrap(), value))
+                .collect(),
+        })
+    }
+
+    fn string(s: &str) -> A::MoveValue {
+        struct_(
+            "0x1::string::String",
+            vec![(
+                "bytes",
+                A::MoveValue::Vector(s.bytes().map(A::MoveValue::U8).collect()),
+            )],
+        )
+    }
+
+    fn option(value: Option<A::MoveValue>) -> A::MoveValue {
+        struct_(
+            "0x1::option::Option<u64>",
+            vec![("vec", A::MoveValue::Vector(value.into_iter().collect()))],
+        )
+    }
+
+    fn uid(a: &str) -> A::MoveValue {
+        struct_(
+            "0x2::object::UID",
+            vec![(
+                "id",
+                struct_(
+                    "0x2::object::ID",
+                    vec![(
+                        "bytes",
+                        A::MoveValue::Address(AccountAddress::from_str(a).unwrap()),
+                    )],
+                ),
+            )],
+        )
+    }
+
+    fn boar() -> A::MoveValue {
+        struct_(
+            "0x42::boars::Boar",
+            vec![
+                ("id", uid("0x43")),
+                ("name", string("First Boar")),
+                ("creator", option(Some(string("Will")))),
+                ("price", option(None)),
+                (
+                    "metadata",
+                    struct_(
+                        "0x42::boars::Metadata",
+                        vec![("age", A::MoveValue::U64(10))],
+                    ),
+                ),
+                (
+                    "kind",
+                    A::MoveValue::Variant(A::MoveVariant {
+                        type_: StructTag::from_str("0x42::boars::Kind").unwrap(),
+                        variant_name: Identifier::new("Wild").unwrap(),
+                        tag: 1,
+                        fields: vec![(Identifier::new("tusks").unwrap(), A::MoveValue::U8(2))],
+                    }),
+                ),
+                (
+                    "nums",
+                    A::MoveValue::Vector(vec![A::MoveValue::U256(U256::from(1u8))]),
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn json() {
+        let json = to_json(boar()).unwrap();
+        insta::assert_snapshot!(serde_json::to_string_pretty(&json).unwrap(), @r###"
+        {
+          "id": "0x0000000000000000000000000000000000000000000000000000000000000043",
+          "name": "First Boar",
+          "creator": "Will",
+          "price": null,
+          "metadata": {
+            "age": "10"
+          },
+          "kind": {
+            "Wild": {
+              "tusks": 2
+            }
+          },
+          "nums": [
+            "1"
+          ]
+        }
+        "###);
+    }
+
+    #[test]
+    fn json_signer() {
+        let err = to_json(A::MoveValue::Vector(vec![A::MoveValue::Signer(
+            AccountAddress::ONE,
+        )]))
+        .unwrap_err();
+        assert!(matches!(err, Error::UnexpectedSigner));
+    }
+
+    #[test]
+    fn json_malformed_string() {
+        let value = struct_(
+            "0x1::string::String",
+            vec![("bytes", A::MoveValue::Vector(vec![A::MoveValue::U8(0xff)]))],
+        );
+
+        let err = to_json(value).unwrap_err();
+        assert!(matches!(err, Error::MalformedValue(_, _)));
+    }
+
+    #[test]
+    fn display() {
+        let boar = boar();
+        let render = |template| render_display(template, &boar);
+
+        assert_eq!(
+            render("{name} ({id}) by {creator}").unwrap(),
+            "First Boar (0x0000000000000000000000000000000000000000000000000000000000000043) by Will",
+        );
+        assert_eq!(render("price: {price}").unwrap(), "price: ");
+        assert_eq!(render("age: {metadata.age}").unwrap(), "age: 10");
+        assert_eq!(render("tusks: {kind.tusks}").unwrap(), "tusks: 2");
+        assert_eq!(render(r"\{name\}").unwrap(), "{name}");
+        assert_eq!(render("{name").unwrap(), "{name");
+        assert_eq!(render("{metadata}").unwrap(), r#"{"age":"10"}"#,);
+    }
+
+    #[test]
+    fn display_errors() {
+        let boar = boar();
+        let render = |template| render_display(template, &boar).unwrap_err().to_string();
+
+        assert_eq!(render("{}"), "Display template value cannot be empty");
+        assert_eq!(render("{namee}"), "Field 'namee' not found");
+        assert_eq!(render("{name.bytes.len}"), "Field 'len' not found");
+        assert_eq!(
+            render("{nums}"),
+            "Vector of name nums is not supported as a Display value",
+        );
+        assert_eq!(
+            render("{a.b.c.d.e.f.g.h.i.j.k}"),
+            "Display template value of 11 exceeds maximum depth of 10",
+        );
+    }
+}
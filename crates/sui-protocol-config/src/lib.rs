@@ -196,6 +196,7 @@ const MAX_PROTOCOL_VERSION: u64 = 69;
 //             Further reduce minimum number of random beacon shares.
 //             Disallow adding new modules in `deps-only` packages.
 // Version 69: Sets number of rounds allowed for fastpath voting in consensus.
+//             Enable BN254 group ops in devnet.
 
 #[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProtocolVersion(u64);
@@ -565,6 +566,15 @@ struct FeatureFlags {
 
     #[serde(skip_serializing_if = "is_false")]
     disallow_new_modules_in_deps_only_packages: bool,
+
+    // Leader scoring strategy used by consensus to build the leader schedule. Only takes
+    // effect when `consensus_distributed_vote_scoring_strategy` is enabled.
+    #[serde(skip_serializing_if = "ConsensusLeaderScoringStrategy::is_none")]
+    consensus_leader_scoring_strategy: ConsensusLeaderScoringStrategy,
+
+    // Enable group ops native functions for BN254 (alt_bn128)
+    #[serde(skip_serializing_if = "is_false")]
+    enable_group_ops_native_functions_bn254: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -599,6 +609,7 @@ pub enum PerObjectCongestionControlMode {
     TotalGasBudget,        // Use txn gas budget as execution cost.
     TotalTxCount,          // Use total txn count as execution cost.
     TotalGasBudgetWithCap, // Use txn gas budget as execution cost with a cap.
+    ExecutionTimeEstimate, // Use execution time estimated from validators' observations as execution cost.
 }
 
 impl PerObjectCongestionControlMode {
@@ -607,6 +618,23 @@ impl PerObjectCongestionControlMode {
     }
 }
 
+// The strategy used by consensus to score authorities for the leader schedule.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum ConsensusLeaderScoringStrategy {
+    #[default]
+    None, // Use the default strategy, which is DistributedVote.
+    DistributedVote,    // Score the stake of all blocks that include a leader vote.
+    CertifiedVote,      // Score only leader votes that were included by a quorum.
+    CertificateLatency, // Score leader votes by how quickly they were certified.
+    PropagationDelay,   // Score authorities by how quickly their blocks reach a quorum.
+}
+
+impl ConsensusLeaderScoringStrategy {
+    pub fn is_none(&self) -> bool {
+        matches!(self, ConsensusLeaderScoringStrategy::None)
+    }
+}
+
 // Configuration options for consensus algorithm.
 #[derive(Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum ConsensusChoice {
@@ -1159,6 +1187,31 @@ pub struct ProtocolConfig {
     group_ops_bls12381_uncompressed_g1_sum_base_cost: Option<u64>,
     group_ops_bls12381_uncompressed_g1_sum_cost_per_term: Option<u64>,
     group_ops_bls12381_uncompressed_g1_sum_max_terms: Option<u64>,
+    group_ops_bn254_decode_scalar_cost: Option<u64>,
+    group_ops_bn254_decode_g1_cost: Option<u64>,
+    group_ops_bn254_decode_g2_cost: Option<u64>,
+    group_ops_bn254_scalar_add_cost: Option<u64>,
+    group_ops_bn254_g1_add_cost: Option<u64>,
+    group_ops_bn254_g2_add_cost: Option<u64>,
+    group_ops_bn254_gt_add_cost: Option<u64>,
+    group_ops_bn254_scalar_sub_cost: Option<u64>,
+    group_ops_bn254_g1_sub_cost: Option<u64>,
+    group_ops_bn254_g2_sub_cost: Option<u64>,
+    group_ops_bn254_gt_sub_cost: Option<u64>,
+    group_ops_bn254_scalar_mul_cost: Option<u64>,
+    group_ops_bn254_g1_mul_cost: Option<u64>,
+    group_ops_bn254_g2_mul_cost: Option<u64>,
+    group_ops_bn254_gt_mul_cost: Option<u64>,
+    group_ops_bn254_scalar_div_cost: Option<u64>,
+    group_ops_bn254_g1_div_cost: Option<u64>,
+    group_ops_bn254_g2_div_cost: Option<u64>,
+    group_ops_bn254_gt_div_cost: Option<u64>,
+    group_ops_bn254_g1_msm_base_cost: Option<u64>,
+    group_ops_bn254_g2_msm_base_cost: Option<u64>,
+    group_ops_bn254_g1_msm_base_cost_per_input: Option<u64>,
+    group_ops_bn254_g2_msm_base_cost_per_input: Option<u64>,
+    group_ops_bn254_msm_max_len: Option<u32>,
+    group_ops_bn254_pairing_cost: Option<u64>,
 
     // hmac::hmac_sha3_256
     hmac_hmac_sha3_256_cost_base: Option<u64>,
@@ -1308,6 +1361,14 @@ pub struct ProtocolConfig {
     /// Adds an absolute cap on the maximum transaction cost when using TotalGasBudgetWithCap at
     /// the given multiple of the per-commit budget.
     gas_budget_based_txn_cost_absolute_cap_commit_count: Option<u64>,
+
+    /// Execution cost, in microseconds, of a command whose execution time has not been estimated
+    /// yet, when using ExecutionTimeEstimate as shared object congestion control strategy.
+    execution_time_estimate_default_command_cost_us: Option<u64>,
+
+    /// The maximum number of estimates in a single execution time observation, shared by a
+    /// validator through consensus.
+    max_execution_time_observation_estimates: Option<u64>,
 }
 
 // feature flags
@@ -1648,6 +1709,10 @@ impl ProtocolConfig {
         self.feature_flags.consensus_round_prober
     }
 
+    pub fn consensus_leader_scoring_strategy(&self) -> ConsensusLeaderScoringStrategy {
+        self.feature_flags.consensus_leader_scoring_strategy
+    }
+
     pub fn validate_identifier_inputs(&self) -> bool {
         self.feature_flags.validate_identifier_inputs
     }
@@ -1675,6 +1740,10 @@ impl ProtocolConfig {
         self.feature_flags
             .disallow_new_modules_in_deps_only_packages
     }
+
+    pub fn enable_group_ops_native_functions_bn254(&self) -> bool {
+        self.feature_flags.enable_group_ops_native_functions_bn254
+    }
 }
 
 #[cfg(not(msim))]
@@ -2100,6 +2169,31 @@ impl ProtocolConfig {
             group_ops_bls12381_uncompressed_g1_sum_base_cost: None,
             group_ops_bls12381_uncompressed_g1_sum_cost_per_term: None,
             group_ops_bls12381_uncompressed_g1_sum_max_terms: None,
+            group_ops_bn254_decode_scalar_cost: None,
+            group_ops_bn254_decode_g1_cost: None,
+            group_ops_bn254_decode_g2_cost: None,
+            group_ops_bn254_scalar_add_cost: None,
+            group_ops_bn254_g1_add_cost: None,
+            group_ops_bn254_g2_add_cost: None,
+            group_ops_bn254_gt_add_cost: None,
+            group_ops_bn254_scalar_sub_cost: None,
+            group_ops_bn254_g1_sub_cost: None,
+            group_ops_bn254_g2_sub_cost: None,
+            group_ops_bn254_gt_sub_cost: None,
+            group_ops_bn254_scalar_mul_cost: None,
+            group_ops_bn254_g1_mul_cost: None,
+            group_ops_bn254_g2_mul_cost: None,
+            group_ops_bn254_gt_mul_cost: None,
+            group_ops_bn254_scalar_div_cost: None,
+            group_ops_bn254_g1_div_cost: None,
+            group_ops_bn254_g2_div_cost: None,
+            group_ops_bn254_gt_div_cost: None,
+            group_ops_bn254_g1_msm_base_cost: None,
+            group_ops_bn254_g2_msm_base_cost: None,
+            group_ops_bn254_g1_msm_base_cost_per_input: None,
+            group_ops_bn254_g2_msm_base_cost_per_input: None,
+            group_ops_bn254_msm_max_len: None,
+            group_ops_bn254_pairing_cost: None,
 
             // zklogin::check_zklogin_id
             check_zklogin_id_cost_base: None,
@@ -2205,6 +2299,10 @@ impl ProtocolConfig {
             gas_budget_based_txn_cost_cap_factor: None,
 
             gas_budget_based_txn_cost_absolute_cap_commit_count: None,
+
+            execution_time_estimate_default_command_cost_us: None,
+
+            max_execution_time_observation_estimates: None,
             // When adding a new constant, set it to None in the earliest version, like this:
             // new_constant: None,
         };
@@ -2944,6 +3042,37 @@ impl ProtocolConfig {
                 69 => {
                     // Sets number of rounds allowed for fastpath voting in consensus.
                     cfg.consensus_voting_rounds = Some(40);
+
+                    // Add costs for group ops on BN254.
+                    cfg.group_ops_bn254_decode_scalar_cost = Some(52);
+                    cfg.group_ops_bn254_decode_g1_cost = Some(52);
+                    cfg.group_ops_bn254_decode_g2_cost = Some(52);
+                    cfg.group_ops_bn254_scalar_add_cost = Some(52);
+                    cfg.group_ops_bn254_g1_add_cost = Some(52);
+                    cfg.group_ops_bn254_g2_add_cost = Some(52);
+                    cfg.group_ops_bn254_gt_add_cost = Some(52);
+                    cfg.group_ops_bn254_scalar_sub_cost = Some(52);
+                    cfg.group_ops_bn254_g1_sub_cost = Some(52);
+                    cfg.group_ops_bn254_g2_sub_cost = Some(52);
+                    cfg.group_ops_bn254_gt_sub_cost = Some(52);
+                    cfg.group_ops_bn254_scalar_mul_cost = Some(52);
+                    cfg.group_ops_bn254_g1_mul_cost = Some(52);
+                    cfg.group_ops_bn254_g2_mul_cost = Some(52);
+                    cfg.group_ops_bn254_gt_mul_cost = Some(52);
+                    cfg.group_ops_bn254_scalar_div_cost = Some(52);
+                    cfg.group_ops_bn254_g1_div_cost = Some(52);
+                    cfg.group_ops_bn254_g2_div_cost = Some(52);
+                    cfg.group_ops_bn254_gt_div_cost = Some(52);
+                    cfg.group_ops_bn254_g1_msm_base_cost = Some(52);
+                    cfg.group_ops_bn254_g2_msm_base_cost = Some(52);
+                    cfg.group_ops_bn254_g1_msm_base_cost_per_input = Some(52);
+                    cfg.group_ops_bn254_g2_msm_base_cost_per_input = Some(52);
+                    cfg.group_ops_bn254_msm_max_len = Some(32);
+                    cfg.group_ops_bn254_pairing_cost = Some(52);
+
+                    if chain != Chain::Mainnet && chain != Chain::Testnet {
+                        cfg.feature_flags.enable_group_ops_native_functions_bn254 = true;
+                    }
                 }
                 // Use this template when making changes:
                 //
@@ -3107,6 +3236,13 @@ impl ProtocolConfig {
         self.feature_flags.consensus_round_prober = val;
     }
 
+    pub fn set_consensus_leader_scoring_strategy_for_testing(
+        &mut self,
+        val: ConsensusLeaderScoringStrategy,
+    ) {
+        self.feature_flags.consensus_leader_scoring_strategy = val;
+    }
+
     pub fn set_disallow_new_modules_in_deps_only_packages_for_testing(&mut self, val: bool) {
         self.feature_flags
             .disallow_new_modules_in_deps_only_packages = val;
@@ -20,7 +20,7 @@ use crate::config::get_rpc_url;
 use crate::replay::ExecutionSandboxState;
 use crate::replay::LocalExec;
 use crate::replay::ProtocolVersionSummary;
-use move_vm_config::runtime::get_default_output_filepath;
+use move_vm_config::runtime::{get_default_output_filepath, VMProfilerConfig};
 use std::env;
 use std::io::BufRead;
 use std::path::PathBuf;
@@ -83,6 +83,10 @@ pub enum ReplayToolCommand {
         /// Optional output filepath for the profile generated by this run, if not specified defaults to `gas_profile_{tx_digest}_{unix_timestamp}.json in the working directory.
         #[arg(long, short, allow_hyphen_values = true)]
         profile_output: Option<PathBuf>,
+        /// Break the gas of each command and Move function down by bytecode instruction class.
+        /// Off by default, as tracking every instruction makes profiles much larger.
+        #[arg(long)]
+        track_instructions: bool,
         /// Required config objects and versions of the config objects to use if replaying a
         /// transaction that utilizes the config object for regulated coin types and that has been
         /// denied.
@@ -355,9 +359,14 @@ pub async fn execute_replay_command(
             executor_version,
             protocol_version,
             profile_output,
+            track_instructions,
             config_objects,
         } => {
-            let output_path = profile_output.or(Some(get_default_output_filepath()));
+            let profiler_config = VMProfilerConfig {
+                full_path: profile_output.unwrap_or_else(get_default_output_filepath),
+                track_bytecode_instructions: track_instructions,
+                use_long_function_name: false,
+            };
 
             let tx_digest = TransactionDigest::from_str(&tx_digest)?;
             info!("Executing tx: {}", tx_digest);
@@ -368,7 +377,7 @@ pub async fn execute_replay_command(
                 use_authority,
                 executor_version,
                 protocol_version,
-                output_path,
+                Some(profiler_config),
                 parse_configs_versions(config_objects),
             )
             .await?;
@@ -31,7 +31,7 @@ use std::{
 };
 use sui_config::node::ExpensiveSafetyCheckConfig;
 use sui_core::authority::NodeStateDump;
-use sui_execution::Executor;
+use sui_execution::{Executor, VMProfilerConfig};
 use sui_framework::BuiltInFramework;
 use sui_json_rpc_types::{
     SuiExecutionStatus, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
@@ -239,9 +239,9 @@ pub struct LocalExec {
     // -1 implies use latest version
     // None implies use the protocol version at the time of execution
     pub protocol_version: Option<i64>,
-    // Whether or not to enable the gas profiler, the PathBuf contains either a user specified
+    // Whether or not to enable the gas profiler, the config contains either a user specified
     // filepath or the default current directory and name format for the profile output
-    pub enable_profiler: Option<PathBuf>,
+    pub enable_profiler: Option<VMProfilerConfig>,
     pub config_and_versions: Option<Vec<(ObjectID, SequenceNumber)>>,
     // Retry policies due to RPC errors
     pub num_retries_for_timeout: u32,
@@ -325,7 +325,7 @@ impl LocalExec {
         use_authority: bool,
         executor_version: Option<i64>,
         protocol_version: Option<i64>,
-        enable_profiler: Option<PathBuf>,
+        enable_profiler: Option<VMProfilerConfig>,
         config_and_versions: Option<Vec<(ObjectID, SequenceNumber)>>,
     ) -> Result<ExecutionSandboxState, ReplayEngineError> {
         info!("Using RPC URL: {}", rpc_url);
@@ -1005,7 +1005,7 @@ impl LocalExec {
         use_authority: bool,
         executor_version: Option<i64>,
         protocol_version: Option<i64>,
-        enable_profiler: Option<PathBuf>,
+        enable_profiler: Option<VMProfilerConfig>,
         config_and_versions: Option<Vec<(ObjectID, SequenceNumber)>>,
     ) -> Result<ExecutionSandboxState, ReplayEngineError> {
         self.executor_version = executor_version;
@@ -2162,7 +2162,7 @@ pub fn get_executor(
     executor_version_override: Option<i64>,
     protocol_config: &ProtocolConfig,
     _expensive_safety_check_config: ExpensiveSafetyCheckConfig,
-    enable_profiler: Option<PathBuf>,
+    enable_profiler: Option<VMProfilerConfig>,
 ) -> Arc<dyn Executor + Send + Sync> {
     let protocol_config = executor_version_override
         .map(|q| {
@@ -4,12 +4,16 @@
 use crate::openapi::{ApiEndpoint, OperationBuilder, ResponseBuilder, RouteHandler};
 use crate::reader::StateReader;
 use crate::{response::ResponseContent, Result};
-use crate::{Page, RestService};
+use crate::{Page, RestError, RestService};
 use axum::extract::Query;
 use axum::extract::{Path, State};
+use axum::Json;
 use openapiv3::v3_1::Operation;
-use sui_sdk_types::types::{Address, ObjectId, StructTag, Version};
-use sui_types::sui_sdk_types_conversions::struct_tag_core_to_sdk;
+use sui_sdk_types::types::{
+    Address, ObjectDigest, ObjectId, StructTag, TransactionDigest, Version,
+};
+use sui_types::storage::AccountBalanceInfo;
+use sui_types::sui_sdk_types_conversions::{struct_tag_core_to_sdk, struct_tag_sdk_to_core};
 use tap::Pipe;
 
 pub struct ListAccountObjects;
@@ -110,3 +114,294 @@ pub struct AccountOwnedObjectInfo {
     #[serde(rename = "type")]
     pub type_: StructTag,
 }
+
+pub struct ListAccountBalances;
+
+impl ApiEndpoint<RestService> for ListAccountBalances {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/accounts/{account}/balances"
+    }
+
+    fn operation(&self, generator: &mut schemars::gen::SchemaGenerator) -> Operation {
+        OperationBuilder::new()
+            .tag("Account")
+            .operation_id("ListAccountBalances")
+            .path_parameter::<Address>("account", generator)
+            .response(
+                200,
+                ResponseBuilder::new()
+                    .json_content::<Vec<Balance>>(generator)
+                    .build(),
+            )
+            .build()
+    }
+
+    fn handler(&self) -> crate::openapi::RouteHandler<RestService> {
+        RouteHandler::new(self.method(), list_account_balances)
+    }
+}
+
+/// List the balance of every coin type owned by an account.
+async fn list_account_balances(
+    Path(address): Path<Address>,
+    State(state): State<StateReader>,
+) -> Result<Json<Vec<Balance>>> {
+    state
+        .inner()
+        .get_account_balances(address.into())?
+        .into_iter()
+        .map(Balance::try_from)
+        .collect::<Result<Vec<_>>>()?
+        .pipe(Json)
+        .pipe(Ok)
+}
+
+pub struct GetAccountBalance;
+
+impl ApiEndpoint<RestService> for GetAccountBalance {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/accounts/{account}/balances/{coin_type}"
+    }
+
+    fn operation(&self, generator: &mut schemars::gen::SchemaGenerator) -> Operation {
+        OperationBuilder::new()
+            .tag("Account")
+            .operation_id("GetAccountBalance")
+            .path_parameter::<Address>("account", generator)
+            .path_parameter::<StructTag>("coin_type", generator)
+            .response(
+                200,
+                ResponseBuilder::new()
+                    .json_content::<Balance>(generator)
+                    .build(),
+            )
+            .build()
+    }
+
+    fn handler(&self) -> crate::openapi::RouteHandler<RestService> {
+        RouteHandler::new(self.method(), get_account_balance)
+    }
+}
+
+/// Get the balance of a coin type owned by an account.
+///
+/// An account which doesn't own any coins of the type has a balance of zero.
+async fn get_account_balance(
+    Path((address, coin_type)): Path<(Address, StructTag)>,
+    State(state): State<StateReader>,
+) -> Result<Json<Balance>> {
+    let coin_type = struct_tag_sdk_to_core(coin_type)?;
+
+    state
+        .inner()
+        .get_account_balance(address.into(), &coin_type)?
+        .pipe(Balance::try_from)?
+        .pipe(Json)
+        .pipe(Ok)
+}
+
+/// Balance of a coin type owned by an account
+#[serde_with::serde_as]
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct Balance {
+    pub coin_type: StructTag,
+    /// Number of coin objects of this type owned by the account
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u64>")]
+    #[schemars(with = "crate::_schemars::U64")]
+    pub coin_object_count: u64,
+    /// Sum of the balances of all coin objects of this type owned by the account
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u128>")]
+    #[schemars(with = "crate::_schemars::U128")]
+    pub total_balance: u128,
+}
+
+impl TryFrom<AccountBalanceInfo> for Balance {
+    type Error = RestError;
+
+    fn try_from(value: AccountBalanceInfo) -> Result<Self, Self::Error> {
+        let AccountBalanceInfo {
+            coin_type,
+            coin_object_count,
+            total_balance,
+        } = value;
+
+        Self {
+            coin_type: struct_tag_core_to_sdk(coin_type)?,
+            coin_object_count,
+            total_balance,
+        }
+        .pipe(Ok)
+    }
+}
+
+pub struct ListAccountCoins;
+
+impl ApiEndpoint<RestService> for ListAccountCoins {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/accounts/{account}/coins/{coin_type}"
+    }
+
+    fn operation(&self, generator: &mut schemars::gen::SchemaGenerator) -> Operation {
+        OperationBuilder::new()
+            .tag("Account")
+            .operation_id("ListAccountCoins")
+            .path_parameter::<Address>("account", generator)
+            .path_parameter::<StructTag>("coin_type", generator)
+            .query_parameters::<ListAccountCoinsQueryParameters>(generator)
+            .response(
+                200,
+                ResponseBuilder::new()
+                    .json_content::<Vec<AccountCoinInfo>>(generator)
+                    .header::<String>(crate::types::X_SUI_CURSOR, generator)
+                    .build(),
+            )
+            .response(400, ResponseBuilder::new().build())
+            .build()
+    }
+
+    fn handler(&self) -> crate::openapi::RouteHandler<RestService> {
+        RouteHandler::new(self.method(), list_account_coins)
+    }
+}
+
+/// List the coins of a coin type owned by an account, ordered from greatest to least balance.
+async fn list_account_coins(
+    Path((address, coin_type)): Path<(Address, StructTag)>,
+    Query(parameters): Query<ListAccountCoinsQueryParameters>,
+    State(state): State<StateReader>,
+) -> Result<Page<AccountCoinInfo, AccountCoinCursor>> {
+    let limit = parameters.limit();
+    let min_balance = parameters.min_balance.unwrap_or(0);
+    let core_coin_type = struct_tag_sdk_to_core(coin_type.clone())?;
+    let start = parameters
+        .start
+        .map(|cursor| (cursor.balance, cursor.object_id.into()));
+
+    let mut coins = state
+        .inner()
+        .account_coins_iter(address.into(), &core_coin_type, start)?
+        .take_while(|coin| coin.balance >= min_balance)
+        .take(limit + 1)
+        .map(|coin| AccountCoinInfo {
+            coin_type: coin_type.clone(),
+            object_id: coin.object_id.into(),
+            version: coin.version.into(),
+            digest: coin.digest.into(),
+            balance: coin.balance,
+            previous_transaction: coin.previous_transaction.into(),
+        })
+        .collect::<Vec<_>>();
+
+    let cursor = if coins.len() > limit {
+        // SAFETY: We've already verified that coins is greater than limit, which is
+        // gaurenteed to be >= 1.
+        let coin = coins.pop().unwrap();
+        Some(AccountCoinCursor {
+            balance: coin.balance,
+            object_id: coin.object_id,
+        })
+    } else {
+        None
+    };
+
+    coins
+        .pipe(ResponseContent::Json)
+        .pipe(|entries| Page { entries, cursor })
+        .pipe(Ok)
+}
+
+/// A Cursor that points at a coin in the list of an account's coins.
+///
+/// Has the format of: `<balance>.<object_id>`
+/// where `<balance>` is the balance of the coin and `<object_id>` its id. Coins are ordered by
+/// balance, so the cursor carries the balance needed to resume the iteration.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AccountCoinCursor {
+    balance: u64,
+    object_id: ObjectId,
+}
+
+impl std::fmt::Display for AccountCoinCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.balance, self.object_id)
+    }
+}
+
+impl std::str::FromStr for AccountCoinCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (balance, object_id) = s
+            .split_once('.')
+            .ok_or_else(|| anyhow::anyhow!("expected <balance>.<object_id>, got {s}"))?;
+        Self {
+            balance: balance.parse()?,
+            object_id: object_id.parse()?,
+        }
+        .pipe(Ok)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for AccountCoinCursor {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde_with::DeserializeAs;
+        serde_with::DisplayFromStr::deserialize_as(deserializer)
+    }
+}
+
+impl serde::Serialize for AccountCoinCursor {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde_with::SerializeAs;
+        serde_with::DisplayFromStr::serialize_as(self, serializer)
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct ListAccountCoinsQueryParameters {
+    pub limit: Option<u32>,
+    #[schemars(with = "Option<String>")]
+    pub start: Option<AccountCoinCursor>,
+    /// Only list coins with a balance of at least this amount
+    pub min_balance: Option<u64>,
+}
+
+impl ListAccountCoinsQueryParameters {
+    pub fn limit(&self) -> usize {
+        self.limit
+            .map(|l| (l as usize).clamp(1, crate::MAX_PAGE_SIZE))
+            .unwrap_or(crate::DEFAULT_PAGE_SIZE)
+    }
+}
+
+#[serde_with::serde_as]
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct AccountCoinInfo {
+    pub coin_type: StructTag,
+    pub object_id: ObjectId,
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u64>")]
+    #[schemars(with = "crate::_schemars::U64")]
+    pub version: Version,
+    pub digest: ObjectDigest,
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u64>")]
+    #[schemars(with = "crate::_schemars::U64")]
+    pub balance: u64,
+    pub previous_transaction: TransactionDigest,
+}
@@ -21,7 +21,10 @@ use sui_sdk_types::types::ValidatorCommittee;
 use sui_sdk_types::types::Version;
 use tap::Pipe;
 
+use crate::accounts::AccountCoinInfo;
 use crate::accounts::AccountOwnedObjectInfo;
+use crate::accounts::Balance;
+use crate::accounts::ListAccountCoinsQueryParameters;
 use crate::accounts::ListAccountOwnedObjectsQueryParameters;
 use crate::checkpoints::CheckpointResponse;
 use crate::checkpoints::ListCheckpointsQueryParameters;
@@ -41,6 +44,8 @@ use crate::transactions::ResolveTransactionResponse;
 use crate::transactions::TransactionExecutionResponse;
 use crate::transactions::TransactionResponse;
 use crate::transactions::TransactionSimulationResponse;
+use crate::transactions::TransactionSubmissionResponse;
+use crate::transactions::TransactionSubmissionStatus;
 use crate::types::X_SUI_CHAIN;
 use crate::types::X_SUI_CHAIN_ID;
 use crate::types::X_SUI_CHECKPOINT_HEIGHT;
@@ -128,6 +133,43 @@ impl Client {
         self.json(request).await
     }
 
+    pub async fn list_account_balances(&self, account: Address) -> Result<Response<Vec<Balance>>> {
+        let url = self.url().join(&format!("accounts/{account}/balances"))?;
+
+        let request = self.inner.get(url);
+
+        self.json(request).await
+    }
+
+    pub async fn get_account_balance(
+        &self,
+        account: Address,
+        coin_type: &StructTag,
+    ) -> Result<Response<Balance>> {
+        let url = self
+            .url()
+            .join(&format!("accounts/{account}/balances/{coin_type}"))?;
+
+        let request = self.inner.get(url);
+
+        self.json(request).await
+    }
+
+    pub async fn list_account_coins(
+        &self,
+        account: Address,
+        coin_type: &StructTag,
+        parameters: &ListAccountCoinsQueryParameters,
+    ) -> Result<Response<Vec<AccountCoinInfo>>> {
+        let url = self
+            .url()
+            .join(&format!("accounts/{account}/coins/{coin_type}"))?;
+
+        let request = self.inner.get(url).query(parameters);
+
+        self.json(request).await
+    }
+
     pub async fn get_object(&self, object_id: ObjectId) -> Result<Response<Object>> {
         let url = self.url().join(&format!("objects/{object_id}"))?;
 
@@ -150,6 +192,20 @@ impl Client {
         self.bcs(request).await
     }
 
+    pub async fn get_object_at_checkpoint(
+        &self,
+        object_id: ObjectId,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<Response<Object>> {
+        let url = self
+            .url()
+            .join(&format!("objects/{object_id}/checkpoint/{checkpoint}"))?;
+
+        let request = self.inner.get(url);
+
+        self.bcs(request).await
+    }
+
     pub async fn list_dynamic_fields(
         &self,
         object_id: ObjectId,
@@ -162,6 +218,21 @@ impl Client {
         self.json(request).await
     }
 
+    pub async fn list_dynamic_fields_at_checkpoint(
+        &self,
+        object_id: ObjectId,
+        checkpoint: CheckpointSequenceNumber,
+        parameters: &ListDynamicFieldsQueryParameters,
+    ) -> Result<Response<Vec<DynamicFieldInfo>>> {
+        let url = self.url().join(&format!(
+            "objects/{object_id}/dynamic-fields/checkpoint/{checkpoint}"
+        ))?;
+
+        let request = self.inner.get(url).query(parameters);
+
+        self.json(request).await
+    }
+
     pub async fn get_gas_info(&self) -> Result<Response<GasInfo>> {
         let url = self.url().join("system/gas")?;
 
@@ -330,6 +401,36 @@ impl Client {
         self.bcs(request).await
     }
 
+    pub async fn submit_transaction(
+        &self,
+        transaction: &SignedTransaction,
+    ) -> Result<Response<TransactionSubmissionResponse>> {
+        let url = self.url().join("transactions/submit")?;
+
+        let body = bcs::to_bytes(transaction)?;
+
+        let request = self
+            .inner
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, crate::APPLICATION_BCS)
+            .body(body);
+
+        self.json(request).await
+    }
+
+    pub async fn get_submission_status(
+        &self,
+        transaction: &TransactionDigest,
+    ) -> Result<Response<TransactionSubmissionStatus>> {
+        let url = self
+            .url()
+            .join(&format!("transactions/{transaction}/submission"))?;
+
+        let request = self.inner.get(url);
+
+        self.json(request).await
+    }
+
     pub async fn simulate_transaction(
         &self,
         transaction: &Transaction,
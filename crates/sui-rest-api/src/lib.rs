@@ -34,12 +34,13 @@ mod system;
 pub mod transactions;
 pub mod types;
 
+pub use accounts::{AccountCoinCursor, AccountCoinInfo, Balance, ListAccountCoinsQueryParameters};
 pub use checkpoints::CheckpointResponse;
 pub use checkpoints::ListCheckpointsQueryParameters;
 pub use client::Client;
 pub use error::{RestError, Result};
 pub use metrics::RestMetrics;
-pub use objects::ObjectResponse;
+pub use objects::{DynamicFieldInfo, ListDynamicFieldsQueryParameters, ObjectResponse};
 pub use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
 pub use transactions::ExecuteTransactionQueryParameters;
 
@@ -110,9 +111,14 @@ const ENDPOINTS: &[&dyn ApiEndpoint<RestService>] = &[
     &checkpoints::GetCheckpoint,
     // unstable APIs
     &accounts::ListAccountObjects,
+    &accounts::ListAccountBalances,
+    &accounts::GetAccountBalance,
+    &accounts::ListAccountCoins,
     &objects::GetObject,
     &objects::GetObjectWithVersion,
+    &objects::GetObjectAtCheckpoint,
     &objects::ListDynamicFields,
+    &objects::ListDynamicFieldsAtCheckpoint,
     &checkpoints::GetFullCheckpoint,
     &transactions::GetTransaction,
     &transactions::ListTransactions,
@@ -124,6 +130,8 @@ const ENDPOINTS: &[&dyn ApiEndpoint<RestService>] = &[
     &system::GetGasInfo,
     &transactions::ExecuteTransaction,
     &transactions::SimulateTransaction,
+    &transactions::SubmitTransaction,
+    &transactions::GetSubmissionStatus,
     &transactions::ResolveTransaction,
     &coins::GetCoinInfo,
 ];
@@ -286,6 +294,7 @@ mod _schemars {
     use schemars::JsonSchema;
 
     pub(crate) struct U64;
+    pub(crate) struct U128;
 
     impl JsonSchema for U64 {
         fn schema_name() -> String {
@@ -309,6 +318,29 @@ mod _schemars {
             false
         }
     }
+
+    impl JsonSchema for U128 {
+        fn schema_name() -> String {
+            "u128".to_owned()
+        }
+
+        fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+            SchemaObject {
+                metadata: Some(Box::new(Metadata {
+                    description: Some("Radix-10 encoded 128-bit unsigned integer".to_owned()),
+                    ..Default::default()
+                })),
+                instance_type: Some(InstanceType::String.into()),
+                format: Some("u128".to_owned()),
+                ..Default::default()
+            }
+            .into()
+        }
+
+        fn is_referenceable() -> bool {
+            false
+        }
+    }
 }
 
 #[cfg(test)]
@@ -11,8 +11,11 @@ use crate::{
 };
 use axum::extract::Query;
 use axum::extract::{Path, State};
+use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
-use sui_sdk_types::types::{Object, ObjectDigest, ObjectId, TypeTag, Version};
+use sui_sdk_types::types::{
+    CheckpointSequenceNumber, Object, ObjectDigest, ObjectId, TypeTag, Version,
+};
 use sui_types::sui_sdk_types_conversions::type_tag_core_to_sdk;
 use sui_types::{
     storage::{DynamicFieldIndexInfo, DynamicFieldKey},
@@ -147,10 +150,124 @@ pub async fn get_object_with_version(
     .pipe(Ok)
 }
 
+pub struct GetObjectAtCheckpoint;
+
+impl ApiEndpoint<RestService> for GetObjectAtCheckpoint {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/objects/{object_id}/checkpoint/{checkpoint}"
+    }
+
+    fn operation(
+        &self,
+        generator: &mut schemars::gen::SchemaGenerator,
+    ) -> openapiv3::v3_1::Operation {
+        OperationBuilder::new()
+            .tag("Objects")
+            .operation_id("GetObjectAtCheckpoint")
+            .path_parameter::<ObjectId>("object_id", generator)
+            .path_parameter::<CheckpointSequenceNumber>("checkpoint", generator)
+            .response(
+                200,
+                ResponseBuilder::new()
+                    .json_content::<ObjectResponse>(generator)
+                    .protobuf_content()
+                    .bcs_content()
+                    .build(),
+            )
+            .response(404, ResponseBuilder::new().build())
+            .response(410, ResponseBuilder::new().build())
+            .build()
+    }
+
+    fn handler(&self) -> crate::openapi::RouteHandler<RestService> {
+        RouteHandler::new(self.method(), get_object_at_checkpoint)
+    }
+}
+
+/// Fetch an object as it was at the end of the provided checkpoint.
+///
+/// Returns a 404 if the object didn't exist (or was deleted or wrapped) at that checkpoint and a
+/// 410 if the history needed to resolve its version, or the object data at that version, has been
+/// pruned.
+pub async fn get_object_at_checkpoint(
+    Path((object_id, checkpoint)): Path<(ObjectId, CheckpointSequenceNumber)>,
+    accept: AcceptJsonProtobufBcs,
+    State(state): State<StateReader>,
+) -> Result<JsonProtobufBcs<ObjectResponse, GetObjectResponse, Object>> {
+    check_object_history_available(&state, checkpoint)?;
+
+    let version = state
+        .inner()
+        .get_object_version_at_checkpoint(object_id.into(), checkpoint)?
+        .ok_or_else(|| ObjectNotFoundError::new_at_checkpoint(object_id, checkpoint))?;
+    // The version may still be indexed after its data was pruned, e.g. when the object pruner
+    // advanced after the availability check above.
+    let object = state
+        .get_object_with_version(object_id, version.value())?
+        .ok_or_else(|| {
+            RestError::new(
+                StatusCode::GONE,
+                format!(
+                    "Object {object_id} at version {} as of checkpoint {checkpoint} has been pruned",
+                    version.value()
+                ),
+            )
+        })?;
+
+    let object = ObjectResponse {
+        digest: object.digest(),
+        object,
+    };
+
+    match accept {
+        AcceptJsonProtobufBcs::Json => JsonProtobufBcs::Json(object),
+        AcceptJsonProtobufBcs::Protobuf => {
+            JsonProtobufBcs::Protobuf(GetObjectResponse::try_from(object)?)
+        }
+        AcceptJsonProtobufBcs::Bcs => JsonProtobufBcs::Bcs(object.object),
+    }
+    .pipe(Ok)
+}
+
+/// Check that the node still has the history needed to reconstruct object state as of the end of
+/// `checkpoint`.
+fn check_object_history_available(
+    state: &StateReader,
+    checkpoint: CheckpointSequenceNumber,
+) -> Result<()> {
+    let latest_checkpoint = state.inner().get_latest_checkpoint()?.sequence_number;
+    if checkpoint > latest_checkpoint {
+        return Err(RestError::new(
+            StatusCode::NOT_FOUND,
+            format!("Checkpoint {checkpoint} not found"),
+        ));
+    }
+
+    let lowest_available_checkpoint = state
+        .inner()
+        .get_lowest_available_checkpoint_object_history()?;
+    if checkpoint < lowest_available_checkpoint {
+        return Err(RestError::new(
+            StatusCode::GONE,
+            format!(
+                "Object history for checkpoint {checkpoint} has been pruned; \
+                the lowest available checkpoint is {lowest_available_checkpoint}"
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
 #[derive(Debug)]
 pub struct ObjectNotFoundError {
     object_id: ObjectId,
     version: Option<Version>,
+    checkpoint: Option<CheckpointSequenceNumber>,
 }
 
 impl ObjectNotFoundError {
@@ -158,6 +275,7 @@ impl ObjectNotFoundError {
         Self {
             object_id,
             version: None,
+            checkpoint: None,
         }
     }
 
@@ -165,6 +283,15 @@ impl ObjectNotFoundError {
         Self {
             object_id,
             version: Some(version),
+            checkpoint: None,
+        }
+    }
+
+    pub fn new_at_checkpoint(object_id: ObjectId, checkpoint: CheckpointSequenceNumber) -> Self {
+        Self {
+            object_id,
+            version: None,
+            checkpoint: Some(checkpoint),
         }
     }
 }
@@ -177,7 +304,13 @@ impl std::fmt::Display for ObjectNotFoundError {
             write!(f, " with version {version}")?;
         }
 
-        write!(f, " not found")
+        write!(f, " not found")?;
+
+        if let Some(checkpoint) = self.checkpoint {
+            write!(f, " at checkpoint {checkpoint}")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -268,6 +401,93 @@ async fn list_dynamic_fields(
         .pipe(Ok)
 }
 
+pub struct ListDynamicFieldsAtCheckpoint;
+
+impl ApiEndpoint<RestService> for ListDynamicFieldsAtCheckpoint {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/objects/{object_id}/dynamic-fields/checkpoint/{checkpoint}"
+    }
+
+    fn operation(
+        &self,
+        generator: &mut schemars::gen::SchemaGenerator,
+    ) -> openapiv3::v3_1::Operation {
+        OperationBuilder::new()
+            .tag("Objects")
+            .operation_id("ListDynamicFieldsAtCheckpoint")
+            .path_parameter::<ObjectId>("object_id", generator)
+            .path_parameter::<CheckpointSequenceNumber>("checkpoint", generator)
+            .query_parameters::<ListDynamicFieldsQueryParameters>(generator)
+            .response(
+                200,
+                ResponseBuilder::new()
+                    .json_content::<Vec<DynamicFieldInfo>>(generator)
+                    .header::<String>(crate::types::X_SUI_CURSOR, generator)
+                    .build(),
+            )
+            .response(404, ResponseBuilder::new().build())
+            .response(410, ResponseBuilder::new().build())
+            .build()
+    }
+
+    fn handler(&self) -> crate::openapi::RouteHandler<RestService> {
+        RouteHandler::new(self.method(), list_dynamic_fields_at_checkpoint)
+    }
+}
+
+/// List the dynamic fields a parent object had at the end of the provided checkpoint.
+///
+/// Returns a 410 if the history of the dynamic fields at that checkpoint has been pruned.
+async fn list_dynamic_fields_at_checkpoint(
+    Path((parent, checkpoint)): Path<(ObjectId, CheckpointSequenceNumber)>,
+    Query(parameters): Query<ListDynamicFieldsQueryParameters>,
+    accept: AcceptFormat,
+    State(state): State<StateReader>,
+) -> Result<Page<DynamicFieldInfo, ObjectId>> {
+    match accept {
+        AcceptFormat::Json => {}
+        _ => {
+            return Err(RestError::new(
+                axum::http::StatusCode::BAD_REQUEST,
+                "invalid accept type",
+            ))
+        }
+    }
+
+    check_object_history_available(&state, checkpoint)?;
+
+    let limit = parameters.limit();
+    let start = parameters.start();
+
+    let mut dynamic_fields = state
+        .inner()
+        .dynamic_field_iter_at_checkpoint(parent.into(), checkpoint, start)?
+        .take(limit + 1)
+        .map(DynamicFieldInfo::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let cursor = if dynamic_fields.len() > limit {
+        // SAFETY: We've already verified that dynamic_fields is greater than limit, which is
+        // gaurenteed to be >= 1.
+        dynamic_fields
+            .pop()
+            .unwrap()
+            .field_id
+            .pipe(ObjectId::from)
+            .pipe(Some)
+    } else {
+        None
+    };
+
+    ResponseContent::Json(dynamic_fields)
+        .pipe(|entries| Page { entries, cursor })
+        .pipe(Ok)
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 pub struct ListDynamicFieldsQueryParameters {
     pub limit: Option<u32>,
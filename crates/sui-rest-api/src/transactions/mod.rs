@@ -10,6 +10,12 @@ pub use execution::SimulateTransactionQueryParameters;
 pub use execution::TransactionExecutionResponse;
 pub use execution::TransactionSimulationResponse;
 
+mod submission;
+pub use submission::GetSubmissionStatus;
+pub use submission::SubmitTransaction;
+pub use submission::TransactionSubmissionResponse;
+pub use submission::TransactionSubmissionStatus;
+
 mod resolve;
 pub use resolve::ResolveTransaction;
 pub use resolve::ResolveTransactionQueryParameters;
@@ -0,0 +1,173 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::openapi::{
+    ApiEndpoint, OperationBuilder, RequestBodyBuilder, ResponseBuilder, RouteHandler,
+};
+use crate::response::Bcs;
+use crate::{RestError, RestService, Result};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use schemars::JsonSchema;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use sui_sdk_types::types::{SignedTransaction, TransactionDigest, TransactionEffectsDigest};
+use sui_types::quorum_driver_types::SubmissionStatus;
+use sui_types::transaction_executor::TransactionExecutor;
+
+pub struct SubmitTransaction;
+
+impl ApiEndpoint<RestService> for SubmitTransaction {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::POST
+    }
+
+    fn path(&self) -> &'static str {
+        "/transactions/submit"
+    }
+
+    fn operation(
+        &self,
+        generator: &mut schemars::gen::SchemaGenerator,
+    ) -> openapiv3::v3_1::Operation {
+        OperationBuilder::new()
+            .tag("Transactions")
+            .operation_id("SubmitTransaction")
+            .request_body(RequestBodyBuilder::new().bcs_content().build())
+            .response(
+                202,
+                ResponseBuilder::new()
+                    .json_content::<TransactionSubmissionResponse>(generator)
+                    .build(),
+            )
+            .build()
+    }
+
+    fn handler(&self) -> RouteHandler<RestService> {
+        RouteHandler::new(self.method(), submit_transaction)
+    }
+}
+
+/// Submit Transaction REST endpoint.
+///
+/// Adds the provided signed transaction to the durable submission queue of the node and returns
+/// without waiting for it to be finalized. Queued transactions survive node restarts and are
+/// retried in the next epoch when they fail with transient errors. The returned transaction
+/// digest tracks the progress of the transaction through the submission status endpoint.
+async fn submit_transaction(
+    State(state): State<Option<Arc<dyn TransactionExecutor>>>,
+    client_address: Option<axum::extract::ConnectInfo<SocketAddr>>,
+    Bcs(transaction): Bcs<SignedTransaction>,
+) -> Result<(StatusCode, Json<TransactionSubmissionResponse>)> {
+    let executor = state.ok_or_else(|| anyhow::anyhow!("No Transaction Executor"))?;
+    let request = sui_types::quorum_driver_types::ExecuteTransactionRequestV3 {
+        transaction: transaction.try_into()?,
+        include_events: true,
+        include_input_objects: false,
+        include_output_objects: false,
+        include_auxiliary_data: false,
+    };
+
+    let digest = executor
+        .submit_transaction(request, client_address.map(|a| a.0))
+        .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(TransactionSubmissionResponse {
+            digest: digest.into(),
+        }),
+    ))
+}
+
+pub struct GetSubmissionStatus;
+
+impl ApiEndpoint<RestService> for GetSubmissionStatus {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/transactions/{transaction}/submission"
+    }
+
+    fn operation(
+        &self,
+        generator: &mut schemars::gen::SchemaGenerator,
+    ) -> openapiv3::v3_1::Operation {
+        OperationBuilder::new()
+            .tag("Transactions")
+            .operation_id("GetSubmissionStatus")
+            .path_parameter::<TransactionDigest>("transaction", generator)
+            .response(
+                200,
+                ResponseBuilder::new()
+                    .json_content::<TransactionSubmissionStatus>(generator)
+                    .build(),
+            )
+            .response(404, ResponseBuilder::new().build())
+            .build()
+    }
+
+    fn handler(&self) -> RouteHandler<RestService> {
+        RouteHandler::new(self.method(), get_submission_status)
+    }
+}
+
+async fn get_submission_status(
+    Path(transaction_digest): Path<TransactionDigest>,
+    State(state): State<Option<Arc<dyn TransactionExecutor>>>,
+) -> Result<Json<TransactionSubmissionStatus>> {
+    let executor = state.ok_or_else(|| anyhow::anyhow!("No Transaction Executor"))?;
+    executor
+        .get_submission_status(&transaction_digest.into())
+        .map_err(anyhow::Error::from)?
+        .ok_or_else(|| {
+            RestError::new(
+                StatusCode::NOT_FOUND,
+                format!("Transaction {transaction_digest} was not submitted to this node"),
+            )
+        })
+        .map(|status| Json(status.into()))
+}
+
+/// Response type for the submit transaction endpoint
+#[derive(Debug, serde::Serialize, serde::Deserialize, JsonSchema)]
+pub struct TransactionSubmissionResponse {
+    /// Digest of the submitted transaction, which tracks its submission status
+    pub digest: TransactionDigest,
+}
+
+/// Progress of a transaction submitted to the submission queue of the node
+#[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum TransactionSubmissionStatus {
+    /// Waiting to be sent to validators. When a previous attempt failed with a transient error,
+    /// the transaction is sent again in the next epoch.
+    Queued { last_error: Option<String> },
+    /// Sent to validators
+    Submitted { attempts: u32 },
+    /// Certified by a quorum of validators, waiting for effects
+    Certified,
+    /// Finalized with these effects
+    Executed {
+        effects_digest: TransactionEffectsDigest,
+    },
+    /// Failed with an error which retrying cannot fix
+    Failed { reason: String },
+}
+
+impl From<SubmissionStatus> for TransactionSubmissionStatus {
+    fn from(status: SubmissionStatus) -> Self {
+        match status {
+            SubmissionStatus::Queued { last_error } => Self::Queued { last_error },
+            SubmissionStatus::Submitted { attempts } => Self::Submitted { attempts },
+            SubmissionStatus::Certified => Self::Certified,
+            SubmissionStatus::Executed { effects_digest } => Self::Executed {
+                effects_digest: TransactionEffectsDigest::new(effects_digest.into_inner()),
+            },
+            SubmissionStatus::Failed { reason } => Self::Failed { reason },
+        }
+    }
+}
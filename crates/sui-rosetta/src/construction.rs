@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use axum::extract::State;
@@ -12,13 +13,14 @@ use futures::StreamExt;
 
 use shared_crypto::intent::{Intent, IntentMessage};
 use sui_json_rpc_types::{
-    StakeStatus, SuiObjectDataOptions, SuiTransactionBlockEffectsAPI,
+    StakeStatus, SuiObjectDataOptions, SuiProtocolConfigValue, SuiTransactionBlockEffectsAPI,
     SuiTransactionBlockResponseOptions,
 };
 use sui_sdk::rpc_types::SuiExecutionStatus;
 use sui_types::base_types::{ObjectRef, SuiAddress};
 use sui_types::crypto::{DefaultHash, SignatureScheme, ToFromBytes};
 use sui_types::error::SuiError;
+use sui_types::multisig::{MultiSig, MultiSigPublicKey};
 use sui_types::signature::{GenericSignature, VerifyParams};
 use sui_types::signature_verification::{
     verify_sender_signed_data_message_signatures, VerifiedDigestCache,
@@ -27,12 +29,13 @@ use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI};
 
 use crate::errors::Error;
 use crate::types::{
-    Amount, ConstructionCombineRequest, ConstructionCombineResponse, ConstructionDeriveRequest,
-    ConstructionDeriveResponse, ConstructionHashRequest, ConstructionMetadata,
-    ConstructionMetadataRequest, ConstructionMetadataResponse, ConstructionParseRequest,
-    ConstructionParseResponse, ConstructionPayloadsRequest, ConstructionPayloadsResponse,
-    ConstructionPreprocessRequest, ConstructionPreprocessResponse, ConstructionSubmitRequest,
-    InternalOperation, MetadataOptions, SignatureType, SigningPayload, TransactionIdentifier,
+    AccountIdentifier, AccountIdentifierMetadata, Amount, ConstructionCombineRequest,
+    ConstructionCombineResponse, ConstructionDeriveRequest, ConstructionDeriveResponse,
+    ConstructionHashRequest, ConstructionMetadata, ConstructionMetadataRequest,
+    ConstructionMetadataResponse, ConstructionParseRequest, ConstructionParseResponse,
+    ConstructionPayloadsRequest, ConstructionPayloadsResponse, ConstructionPreprocessRequest,
+    ConstructionPreprocessResponse, ConstructionSubmitRequest, InternalOperation, MetadataOptions,
+    PreprocessMetadata, SignatureType, SigningPayload, TransactionIdentifier,
     TransactionIdentifierResponse,
 };
 use crate::{OnlineServerContext, SuiEnv};
@@ -64,7 +67,8 @@ pub async fn payloads(
 ) -> Result<ConstructionPayloadsResponse, Error> {
     env.check_network_identifier(&request.network_identifier)?;
     let metadata = request.metadata.ok_or(Error::MissingMetadata)?;
-    let address = metadata.sender;
+    let signers = metadata.signers();
+    let multisig_public_keys = metadata.multisig_public_keys.clone();
 
     let data = request
         .operations
@@ -76,14 +80,40 @@ pub async fn payloads(
     let mut hasher = DefaultHash::default();
     hasher.update(bcs::to_bytes(&intent_msg).expect("Message serialization should not fail"));
     let digest = hasher.finalize().digest;
+    let hex_bytes = Hex::encode(digest);
+
+    // Every signer signs the same digest, MultiSig accounts get one payload per member key.
+    let mut payloads = vec![];
+    for signer in signers {
+        let multisig_pk = multisig_public_keys
+            .iter()
+            .find(|pk| SuiAddress::from(*pk) == signer);
+        let Some(multisig_pk) = multisig_pk else {
+            payloads.push(SigningPayload {
+                account_identifier: signer.into(),
+                hex_bytes: hex_bytes.clone(),
+                signature_type: Some(SignatureType::Ed25519),
+            });
+            continue;
+        };
+        for (pk, _) in multisig_pk.pubkeys() {
+            payloads.push(SigningPayload {
+                account_identifier: AccountIdentifier {
+                    address: pk.into(),
+                    sub_account: None,
+                    metadata: Some(AccountIdentifierMetadata {
+                        multisig_public_key: multisig_pk.clone(),
+                    }),
+                },
+                hex_bytes: hex_bytes.clone(),
+                signature_type: Some(pk.try_into()?),
+            });
+        }
+    }
 
     Ok(ConstructionPayloadsResponse {
         unsigned_transaction: Hex::from_bytes(&intent_msg_bytes),
-        payloads: vec![SigningPayload {
-            account_identifier: address.into(),
-            hex_bytes: Hex::encode(digest),
-            signature_type: Some(SignatureType::Ed25519),
-        }],
+        payloads,
     })
 }
 
@@ -98,24 +128,45 @@ pub async fn combine(
     env.check_network_identifier(&request.network_identifier)?;
     let unsigned_tx = request.unsigned_transaction.to_vec()?;
     let intent_msg: IntentMessage<TransactionData> = bcs::from_bytes(&unsigned_tx)?;
-    let sig = request
-        .signatures
-        .first()
-        .ok_or_else(|| Error::MissingInput("Signature".to_string()))?;
-    let sig_bytes = sig.hex_bytes.to_vec()?;
-    let pub_key = sig.public_key.hex_bytes.to_vec()?;
-    let flag = vec![match sig.signature_type {
-        SignatureType::Ed25519 => SignatureScheme::ED25519,
-        SignatureType::Ecdsa => SignatureScheme::Secp256k1,
+    if request.signatures.is_empty() {
+        return Err(Error::MissingInput("Signature".to_string()));
+    }
+
+    let mut signatures = vec![];
+    // MultiSig member signatures, grouped by the MultiSig account.
+    let mut multisig_signatures: BTreeMap<SuiAddress, (MultiSigPublicKey, Vec<_>)> =
+        BTreeMap::new();
+    for sig in &request.signatures {
+        let sig_bytes = sig.hex_bytes.to_vec()?;
+        let pub_key = sig.public_key.hex_bytes.to_vec()?;
+        let flag = vec![SignatureScheme::from(&sig.signature_type).flag()];
+        let signature = GenericSignature::from_bytes(&[&*flag, &*sig_bytes, &*pub_key].concat())?;
+
+        match &sig.signing_payload.account_identifier.metadata {
+            Some(AccountIdentifierMetadata {
+                multisig_public_key,
+            }) => {
+                multisig_signatures
+                    .entry(multisig_public_key.into())
+                    .or_insert_with(|| (multisig_public_key.clone(), vec![]))
+                    .1
+                    .push(signature);
+            }
+            None => signatures.push(signature),
+        }
+    }
+    for (_, (multisig_pk, mut member_signatures)) in multisig_signatures {
+        // MultiSig expects the signatures in the same order as the public keys.
+        member_signatures.sort_by_key(|sig| {
+            sig.to_public_key()
+                .ok()
+                .and_then(|pk| multisig_pk.get_index(&pk))
+        });
+        let multisig = MultiSig::combine(member_signatures, multisig_pk)?;
+        signatures.push(GenericSignature::MultiSig(multisig));
     }
-    .flag()];
-
-    let signed_tx = Transaction::from_generic_sig_data(
-        intent_msg.value,
-        vec![GenericSignature::from_bytes(
-            &[&*flag, &*sig_bytes, &*pub_key].concat(),
-        )?],
-    );
+
+    let signed_tx = Transaction::from_generic_sig_data(intent_msg.value, signatures);
     // TODO: this will likely fail with zklogin authenticator, since we do not know the current epoch.
     // As long as coinbase doesn't need to use zklogin for custodial wallets this is okay.
     let place_holder_epoch = 0;
@@ -197,13 +248,24 @@ pub async fn preprocess(
 
     let internal_operation = request.operations.into_internal()?;
     let sender = internal_operation.sender();
-    let budget = request.metadata.and_then(|m| m.budget);
+    let PreprocessMetadata {
+        budget,
+        sponsor,
+        multisig_public_keys,
+    } = request.metadata.unwrap_or_default();
+
+    let mut required_public_keys = vec![sender.into()];
+    if let Some(sponsor) = sponsor.filter(|sponsor| *sponsor != sender) {
+        required_public_keys.push(sponsor.into());
+    }
     Ok(ConstructionPreprocessResponse {
         options: Some(MetadataOptions {
             internal_operation,
             budget,
+            sponsor,
+            multisig_public_keys,
         }),
-        required_public_keys: vec![sender.into()],
+        required_public_keys,
     })
 }
 
@@ -238,8 +300,17 @@ pub async fn metadata(
     let option = request.options.ok_or(Error::MissingMetadata)?;
     let budget = option.budget;
     let sender = option.internal_operation.sender();
+    let sponsor = option.sponsor.filter(|sponsor| *sponsor != sender);
+    let gas_owner = sponsor.unwrap_or(sender);
+    if sponsor.is_some() && option.internal_operation.spends_gas_coin() {
+        return Err(Error::InvalidInput(
+            "Sponsored transactions cannot transfer SUI from the gas coin".into(),
+        ));
+    }
     let currency = match &option.internal_operation {
-        InternalOperation::PayCoin { currency, .. } => Some(currency.clone()),
+        InternalOperation::PayCoin { currency, .. }
+        | InternalOperation::MergeCoins { currency, .. }
+        | InternalOperation::SplitCoin { currency, .. } => Some(currency.clone()),
         _ => None,
     };
     let coin_type = currency.as_ref().map(|c| c.metadata.coin_type.clone());
@@ -253,7 +324,7 @@ pub async fn metadata(
     gas_price += 100;
 
     // Get amount, objects, for the operation
-    let (total_required_amount, objects) = match &option.internal_operation {
+    let (total_required_amount, mut objects) = match &option.internal_operation {
         InternalOperation::PaySui { amounts, .. } => {
             let amount = amounts.iter().sum::<u64>();
             (Some(amount), vec![])
@@ -313,6 +384,44 @@ pub async fn metadata(
 
             (Some(0), stake_refs)
         }
+        InternalOperation::MergeCoins { currency, .. } if currency.is_sui() => {
+            // All SUI coins are used as gas payment and merged into the gas coin.
+            (None, vec![])
+        }
+        InternalOperation::MergeCoins { .. } => {
+            // Leave room for at least one gas coin, the remaining coins can be merged by a
+            // subsequent transaction.
+            let max_coins = max_input_objects(&context).await?.saturating_sub(1);
+            let coin_objs: Vec<ObjectRef> = context
+                .client
+                .coin_read_api()
+                .get_coins_stream(sender, coin_type)
+                .take(max_coins)
+                .map(|coin| coin.object_ref())
+                .collect()
+                .await;
+            if coin_objs.len() < 2 {
+                return Err(Error::InvalidInput(
+                    "At least two coins are required for merging".into(),
+                ));
+            }
+            (Some(0), coin_objs) // amount is 0 for gas coin
+        }
+        InternalOperation::SplitCoin {
+            amounts, currency, ..
+        } if currency.is_sui() => (Some(amounts.iter().sum::<u64>()), vec![]),
+        InternalOperation::SplitCoin { amounts, .. } => {
+            let amount = amounts.iter().sum::<u64>();
+            let coin_objs: Vec<ObjectRef> = context
+                .client
+                .coin_read_api()
+                .select_coins(sender, coin_type, amount.into(), vec![])
+                .await?
+                .iter()
+                .map(|coin| coin.object_ref())
+                .collect();
+            (Some(0), coin_objs) // amount is 0 for gas coin
+        }
     };
 
     // Get budget for suggested_fee and metadata.budget
@@ -333,6 +442,8 @@ pub async fn metadata(
                     // MAX BUDGET
                     budget: 50_000_000_000,
                     currency: currency.clone(),
+                    sponsor,
+                    multisig_public_keys: option.multisig_public_keys.clone(),
                 })?;
 
             let dry_run = context
@@ -355,27 +466,43 @@ pub async fn metadata(
         context
             .client
             .coin_read_api()
-            .select_coins(sender, None, total_amount.into(), vec![])
+            .select_coins(gas_owner, None, total_amount.into(), vec![])
             .await
             .ok()
     } else {
         None
     };
 
-    // If required amount is None (all SUI) or failed to select coin (might not have enough SUI), select all coins.
+    // If required amount is None (all SUI) or failed to select coin (might not have enough SUI), select all coins,
+    // up to the number of coins that can be used as gas payment.
     let coins = if let Some(coins) = coins {
         coins
     } else {
+        let max_gas_coins = max_gas_payment_objects(&context).await?;
         context
             .client
             .coin_read_api()
-            .get_coins_stream(sender, None)
+            .get_coins_stream(gas_owner, None)
+            .take(max_gas_coins)
             .collect::<Vec<_>>()
             .await
     };
 
     let total_coin_value = coins.iter().fold(0, |sum, coin| sum + coin.balance);
 
+    // Gas coins count towards the input objects limit too, drop the merge inputs that don't fit.
+    if let InternalOperation::MergeCoins { .. } = &option.internal_operation {
+        let max_objects = max_input_objects(&context).await?;
+        let max_coins = max_objects.saturating_sub(coins.len());
+        if max_coins < 2 && !objects.is_empty() {
+            return Err(Error::InvalidInput(format!(
+                "Cannot merge coins with {} gas coins, the transaction is limited to {max_objects} input objects",
+                coins.len(),
+            )));
+        }
+        objects.truncate(max_coins);
+    }
+
     let coins = coins
         .into_iter()
         .map(|c| c.object_ref())
@@ -390,11 +517,34 @@ pub async fn metadata(
             gas_price,
             budget,
             currency,
+            sponsor,
+            multisig_public_keys: option.multisig_public_keys,
         },
         suggested_fee: vec![Amount::new(budget as i128, None)],
     })
 }
 
+/// Returns the maximum number of input objects, including gas coins, allowed in a transaction.
+async fn max_input_objects(context: &OnlineServerContext) -> Result<usize, Error> {
+    protocol_config_limit(context, "max_input_objects").await
+}
+
+/// Returns the maximum number of coins which can be used as gas payment of a transaction.
+async fn max_gas_payment_objects(context: &OnlineServerContext) -> Result<usize, Error> {
+    protocol_config_limit(context, "max_gas_payment_objects").await
+}
+
+async fn protocol_config_limit(context: &OnlineServerContext, name: &str) -> Result<usize, Error> {
+    let config = context.client.read_api().get_protocol_config(None).await?;
+    match config.attributes.get(name) {
+        Some(Some(SuiProtocolConfigValue::U64(max))) => Ok(*max as usize),
+        Some(Some(SuiProtocolConfigValue::U32(max))) => Ok(*max as usize),
+        _ => Err(Error::DataError(format!(
+            "{name} is missing from the protocol config"
+        ))),
+    }
+}
+
 ///  This is run as a sanity check before signing (after /construction/payloads)
 /// and before broadcast (after /construction/combine).
 ///
@@ -414,7 +564,10 @@ pub async fn parse(
         intent.value
     };
     let account_identifier_signers = if request.signed {
-        vec![data.sender().into()]
+        data.signers()
+            .into_iter()
+            .map(|signer| signer.into())
+            .collect()
     } else {
         vec![]
     };
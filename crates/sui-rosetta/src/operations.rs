@@ -31,7 +31,7 @@ use sui_types::{SUI_SYSTEM_ADDRESS, SUI_SYSTEM_PACKAGE_ID};
 
 use crate::types::{
     AccountIdentifier, Amount, CoinAction, CoinChange, CoinID, CoinIdentifier, Currency,
-    InternalOperation, OperationIdentifier, OperationStatus, OperationType,
+    InternalOperation, OperationIdentifier, OperationStatus, OperationType, SPLIT_COIN_MARKER,
 };
 use crate::{CoinMetadataCache, Error, SUI};
 
@@ -104,6 +104,8 @@ impl Operations {
             OperationType::PayCoin => self.pay_coin_ops_to_internal(),
             OperationType::Stake => self.stake_ops_to_internal(),
             OperationType::WithdrawStake => self.withdraw_stake_ops_to_internal(),
+            OperationType::MergeCoins => self.merge_coins_ops_to_internal(),
+            OperationType::SplitCoin => self.split_coin_ops_to_internal(),
             op => Err(Error::UnsupportedOperation(op)),
         }
     }
@@ -246,6 +248,60 @@ impl Operations {
         Ok(InternalOperation::WithdrawStake { sender, stake_ids })
     }
 
+    fn merge_coins_ops_to_internal(self) -> Result<InternalOperation, Error> {
+        let op = self.single_op_of_type(OperationType::MergeCoins)?;
+        let sender = op
+            .account
+            .ok_or_else(|| Error::MissingInput("Sender address".to_string()))?
+            .address;
+        let currency = match op.metadata {
+            Some(OperationMetadata::MergeCoins { currency }) => currency,
+            Some(_) => {
+                return Err(Error::InvalidInput(
+                    "Cannot find merge coins info from metadata.".into(),
+                ))
+            }
+            None => SUI.clone(),
+        };
+        Ok(InternalOperation::MergeCoins { sender, currency })
+    }
+
+    fn split_coin_ops_to_internal(self) -> Result<InternalOperation, Error> {
+        let op = self.single_op_of_type(OperationType::SplitCoin)?;
+        let sender = op
+            .account
+            .ok_or_else(|| Error::MissingInput("Sender address".to_string()))?
+            .address;
+        let Some(OperationMetadata::SplitCoin { currency, amounts }) = op.metadata else {
+            return Err(Error::InvalidInput(
+                "Cannot find split coin info from metadata.".into(),
+            ));
+        };
+        if amounts.is_empty() {
+            return Err(Error::MissingInput("Split amounts".to_string()));
+        }
+        Ok(InternalOperation::SplitCoin {
+            sender,
+            amounts,
+            currency,
+        })
+    }
+
+    fn single_op_of_type(self, type_: OperationType) -> Result<Operation, Error> {
+        let mut ops = self
+            .0
+            .into_iter()
+            .filter(|op| op.type_ == type_)
+            .collect::<Vec<_>>();
+        if ops.len() != 1 {
+            return Err(Error::MalformedOperationError(format!(
+                "{type_:?} should only have one operation."
+            )));
+        }
+        // Checked above, safe to unwrap.
+        Ok(ops.pop().unwrap())
+    }
+
     fn from_transaction(
         tx: SuiTransactionBlockKind,
         sender: SuiAddress,
@@ -312,7 +368,7 @@ impl Operations {
             Some(amounts)
         }
         fn transfer_object(
-            aggregated_recipients: &mut HashMap<SuiAddress, u64>,
+            transfers: &mut Vec<(SuiAddress, u64)>,
             inputs: &[SuiCallArg],
             known_results: &[Vec<KnownValue>],
             objs: &[SuiArgument],
@@ -336,11 +392,99 @@ impl Operations {
                     }
                     SuiArgument::GasCoin | SuiArgument::Input(_) => return None,
                 };
-                let aggregate = aggregated_recipients.entry(addr).or_default();
-                *aggregate += value;
+                transfers.push((addr, value));
             }
             Some(vec![])
         }
+        // Returns true if the transaction has exactly the shape built for a SUI MergeCoins
+        // operation, i.e. only transfers the gas coin back to the sender, see the MergeCoins case
+        // of InternalOperation::try_into_data.
+        fn is_sui_merge_coins(
+            inputs: &[SuiCallArg],
+            commands: &[SuiCommand],
+            sender: SuiAddress,
+        ) -> bool {
+            let ([recipient], [SuiCommand::TransferObjects(objs, SuiArgument::Input(0))]) =
+                (inputs, commands)
+            else {
+                return false;
+            };
+            matches!(objs.as_slice(), [SuiArgument::GasCoin])
+                && recipient.pure().and_then(|v| v.to_sui_address().ok()) == Some(sender)
+        }
+        fn pure_string(arg: &SuiCallArg) -> Option<String> {
+            let SuiCallArg::Pure(value) = arg else {
+                return None;
+            };
+            let bytes = value
+                .value()
+                .to_json_value()
+                .as_array()?
+                .clone()
+                .into_iter()
+                .map(|v| v.as_u64().map(|n| n as u8))
+                .collect::<Option<Vec<u8>>>()?;
+            bcs::from_bytes::<String>(&bytes).ok()
+        }
+        fn currency_marker(inputs: &[SuiCallArg]) -> Option<Currency> {
+            // See the PayCoin case of InternalOperation::try_into_data for how the marker is set.
+            inputs
+                .iter()
+                .last()
+                .and_then(pure_string)
+                .and_then(|bcs_str| serde_json::from_str(&bcs_str).ok())
+        }
+        // Returns the currency of the transaction if it has exactly the shape built for a
+        // SplitCoin operation, see the SplitCoin case of InternalOperation::try_into_data.
+        fn split_coin_currency(
+            inputs: &[SuiCallArg],
+            commands: &[SuiCommand],
+            sender: SuiAddress,
+        ) -> Option<Currency> {
+            let [.., marker, _] = inputs else {
+                return None;
+            };
+            if pure_string(marker)? != SPLIT_COIN_MARKER {
+                return None;
+            }
+            let currency = currency_marker(inputs)?;
+            let (merged, split_index) = match commands {
+                [SuiCommand::MergeCoins(primary @ SuiArgument::Input(_), coins), _, _]
+                    if coins.iter().all(|c| matches!(c, SuiArgument::Input(_))) =>
+                {
+                    (Some(*primary), 1)
+                }
+                [_, _] => (None, 0),
+                _ => return None,
+            };
+            let [SuiCommand::SplitCoins(coin, amounts), SuiCommand::TransferObjects(objs, recipient)] =
+                &commands[split_index..]
+            else {
+                return None;
+            };
+            let coin_matches = match coin {
+                SuiArgument::GasCoin => merged.is_none() && currency.is_sui(),
+                SuiArgument::Input(_) => {
+                    merged.map_or(true, |primary| primary == *coin) && !currency.is_sui()
+                }
+                SuiArgument::Result(_) | SuiArgument::NestedResult(_, _) => false,
+            };
+            let transfers_all_splits = objs.len() == amounts.len()
+                && objs.iter().enumerate().all(|(j, obj)| {
+                    *obj == SuiArgument::NestedResult(split_index as u16, j as u16)
+                });
+            let to_sender = match recipient {
+                SuiArgument::Input(i) => {
+                    inputs
+                        .get(*i as usize)
+                        .and_then(|input| input.pure())
+                        .and_then(|v| v.to_sui_address().ok())
+                        == Some(sender)
+                }
+                _ => false,
+            };
+            (coin_matches && transfers_all_splits && to_sender).then_some(currency)
+        }
         fn stake_call(
             inputs: &[SuiCallArg],
             known_results: &[Vec<KnownValue>],
@@ -395,23 +539,35 @@ impl Operations {
         }
         let SuiProgrammableTransactionBlock { inputs, commands } = &pt;
         let mut known_results: Vec<Vec<KnownValue>> = vec![];
-        let mut aggregated_recipients: HashMap<SuiAddress, u64> = HashMap::new();
+        let mut transfers: Vec<(SuiAddress, u64)> = vec![];
         let mut needs_generic = false;
         let mut operations = vec![];
         let mut stake_ids = vec![];
-        let mut currency: Option<Currency> = None;
+        // Coins of unknown type (i.e. not the gas coin) have been split or merged.
+        let mut used_input_coins = false;
+        let mut merged_gas_coins = false;
+        let sui_merge_coins = is_sui_merge_coins(inputs, commands, sender);
         for command in commands {
             let result = match command {
                 SuiCommand::SplitCoins(coin, amounts) => {
+                    used_input_coins |= matches!(coin, SuiArgument::Input(_));
                     split_coins(inputs, &known_results, *coin, amounts)
                 }
-                SuiCommand::TransferObjects(objs, addr) => transfer_object(
-                    &mut aggregated_recipients,
-                    inputs,
-                    &known_results,
-                    objs,
-                    *addr,
-                ),
+                SuiCommand::MergeCoins(coin, _) => {
+                    match coin {
+                        SuiArgument::GasCoin => merged_gas_coins = true,
+                        _ => used_input_coins = true,
+                    }
+                    Some(vec![])
+                }
+                // Transferring the gas coin back to the sender merges all the gas payment coins.
+                SuiCommand::TransferObjects(..) if sui_merge_coins => {
+                    merged_gas_coins = true;
+                    Some(vec![])
+                }
+                SuiCommand::TransferObjects(objs, addr) => {
+                    transfer_object(&mut transfers, inputs, &known_results, objs, *addr)
+                }
                 SuiCommand::MoveCall(m) if Self::is_stake_call(m) => {
                     stake_call(inputs, &known_results, m)?.map(|(amount, validator)| {
                         let amount = amount.map(|amount| Amount::new(-(amount as i128), None));
@@ -442,38 +598,42 @@ impl Operations {
             }
         }
 
-        if !needs_generic && !aggregated_recipients.is_empty() {
+        let currency = currency_marker(inputs);
+        // A merge only moves coins within the sender's account, the currency is SUI unless coins
+        // other than the gas coin are involved, in which case it must be given by the marker.
+        let self_currency = if used_input_coins {
+            currency.clone()
+        } else {
+            Some(currency.clone().unwrap_or_else(|| SUI.clone()))
+        };
+        let split_currency = split_coin_currency(inputs, commands, sender);
+
+        if !needs_generic && !transfers.is_empty() && split_currency.is_some() {
+            let amounts = transfers.into_iter().map(|(_, amount)| amount).collect();
+            // Checked above, safe to unwrap.
+            operations.push(Operation::split_coin(
+                status,
+                sender,
+                amounts,
+                split_currency.unwrap(),
+            ));
+        } else if !needs_generic && !transfers.is_empty() {
+            let aggregated_recipients = transfers.into_iter().fold(
+                HashMap::<SuiAddress, u64>::new(),
+                |mut aggregated, (recipient, amount)| {
+                    *aggregated.entry(recipient).or_default() += amount;
+                    aggregated
+                },
+            );
             let total_paid: u64 = aggregated_recipients.values().copied().sum();
             operations.extend(
                 aggregated_recipients
                     .into_iter()
-                    .map(|(recipient, amount)| {
-                        currency = inputs.iter().last().and_then(|arg| {
-                            if let SuiCallArg::Pure(value) = arg {
-                                let bytes = value
-                                    .value()
-                                    .to_json_value()
-                                    .as_array()?
-                                    .clone()
-                                    .into_iter()
-                                    .map(|v| v.as_u64().map(|n| n as u8))
-                                    .collect::<Option<Vec<u8>>>()?;
-                                bcs::from_bytes::<String>(&bytes)
-                                    .ok()
-                                    .and_then(|bcs_str| serde_json::from_str(&bcs_str).ok())
-                            } else {
-                                None
-                            }
-                        });
-                        match currency {
-                            Some(_) => Operation::pay_coin(
-                                status,
-                                recipient,
-                                amount.into(),
-                                currency.clone(),
-                            ),
-                            None => Operation::pay_sui(status, recipient, amount.into()),
+                    .map(|(recipient, amount)| match currency {
+                        Some(_) => {
+                            Operation::pay_coin(status, recipient, amount.into(), currency.clone())
                         }
+                        None => Operation::pay_sui(status, recipient, amount.into()),
                     }),
             );
             match currency {
@@ -485,6 +645,18 @@ impl Operations {
                 )),
                 _ => operations.push(Operation::pay_sui(status, sender, -(total_paid as i128))),
             }
+        } else if !needs_generic
+            && operations.is_empty()
+            && stake_ids.is_empty()
+            && (merged_gas_coins || used_input_coins)
+            && self_currency.is_some()
+        {
+            // Checked above, safe to unwrap.
+            operations.push(Operation::merge_coins(
+                status,
+                sender,
+                self_currency.unwrap(),
+            ));
         } else if !stake_ids.is_empty() {
             let stake_ids = stake_ids.into_iter().flatten().collect::<Vec<_>>();
             let metadata = stake_ids
@@ -770,8 +942,19 @@ impl PartialEq for Operation {
 #[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
 pub enum OperationMetadata {
     GenericTransaction(SuiTransactionBlockKind),
-    Stake { validator: SuiAddress },
-    WithdrawStake { stake_ids: Vec<ObjectID> },
+    Stake {
+        validator: SuiAddress,
+    },
+    WithdrawStake {
+        stake_ids: Vec<ObjectID>,
+    },
+    MergeCoins {
+        currency: Currency,
+    },
+    SplitCoin {
+        currency: Currency,
+        amounts: Vec<u64>,
+    },
 }
 
 impl Operation {
@@ -840,6 +1023,39 @@ impl Operation {
         }
     }
 
+    fn merge_coins(
+        status: Option<OperationStatus>,
+        address: SuiAddress,
+        currency: Currency,
+    ) -> Self {
+        Operation {
+            operation_identifier: Default::default(),
+            type_: OperationType::MergeCoins,
+            status,
+            account: Some(address.into()),
+            amount: None,
+            coin_change: None,
+            metadata: Some(OperationMetadata::MergeCoins { currency }),
+        }
+    }
+
+    fn split_coin(
+        status: Option<OperationStatus>,
+        address: SuiAddress,
+        amounts: Vec<u64>,
+        currency: Currency,
+    ) -> Self {
+        Operation {
+            operation_identifier: Default::default(),
+            type_: OperationType::SplitCoin,
+            status,
+            account: Some(address.into()),
+            amount: None,
+            coin_change: None,
+            metadata: Some(OperationMetadata::SplitCoin { currency, amounts }),
+        }
+    }
+
     fn balance_change(
         status: Option<OperationStatus>,
         addr: SuiAddress,
@@ -7,6 +7,7 @@ use std::str::FromStr;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use fastcrypto::encoding::Hex;
+use move_core_types::language_storage::TypeTag;
 use serde::de::Error as DeError;
 use serde::{Deserialize, Serializer};
 use serde::{Deserializer, Serialize};
@@ -18,8 +19,10 @@ use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockKind};
 use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::crypto::PublicKey as SuiPublicKey;
 use sui_types::crypto::SignatureScheme;
+use sui_types::gas_coin::GAS;
 use sui_types::governance::{ADD_STAKE_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::messages_checkpoint::CheckpointDigest;
+use sui_types::multisig::MultiSigPublicKey;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{Argument, CallArg, Command, ObjectArg, TransactionData};
@@ -75,6 +78,15 @@ pub struct AccountIdentifier {
     pub address: SuiAddress,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sub_account: Option<SubAccount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<AccountIdentifierMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct AccountIdentifierMetadata {
+    /// Set on signing payloads of MultiSig members, the MultiSig public key is needed to assemble
+    /// the members' signatures into the MultiSig account's signature in /construction/combine.
+    pub multisig_public_key: MultiSigPublicKey,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -95,6 +107,7 @@ impl From<SuiAddress> for AccountIdentifier {
         AccountIdentifier {
             address,
             sub_account: None,
+            metadata: None,
         }
     }
 }
@@ -124,6 +137,14 @@ impl Default for Currency {
     }
 }
 
+impl Currency {
+    /// Returns true if the currency's coin type refers to the SUI gas coin, regardless of the
+    /// address format used in the coin type string.
+    pub fn is_sui(&self) -> bool {
+        TypeTag::from_str(&self.metadata.coin_type).is_ok_and(|tag| tag == GAS::type_tag())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 #[serde(transparent)]
 pub struct Currencies(pub Vec<Currency>);
@@ -460,6 +481,8 @@ pub enum OperationType {
     PayCoin,
     Stake,
     WithdrawStake,
+    MergeCoins,
+    SplitCoin,
     // All other Sui transaction types, readonly
     EpochChange,
     Genesis,
@@ -553,6 +576,30 @@ pub enum SignatureType {
     Ecdsa,
 }
 
+impl TryFrom<&SuiPublicKey> for SignatureType {
+    type Error = Error;
+
+    fn try_from(pk: &SuiPublicKey) -> Result<Self, Self::Error> {
+        match pk {
+            SuiPublicKey::Ed25519(_) => Ok(SignatureType::Ed25519),
+            SuiPublicKey::Secp256k1(_) => Ok(SignatureType::Ecdsa),
+            _ => Err(Error::InvalidInput(format!(
+                "Unsupported signature scheme: {:?}",
+                pk.scheme()
+            ))),
+        }
+    }
+}
+
+impl From<&SignatureType> for SignatureScheme {
+    fn from(type_: &SignatureType) -> Self {
+        match type_ {
+            SignatureType::Ed25519 => SignatureScheme::ED25519,
+            SignatureType::Ecdsa => SignatureScheme::Secp256k1,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct ConstructionCombineRequest {
     pub network_identifier: NetworkIdentifier,
@@ -610,10 +657,16 @@ pub struct ConstructionPreprocessRequest {
     pub metadata: Option<PreprocessMetadata>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Default)]
 pub struct PreprocessMetadata {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub budget: Option<u64>,
+    /// Address paying for gas, if different from the sender.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsor: Option<SuiAddress>,
+    /// Public keys of the MultiSig accounts (sender and/or sponsor) signing the transaction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub multisig_public_keys: Vec<MultiSigPublicKey>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -629,6 +682,10 @@ pub struct MetadataOptions {
     pub internal_operation: InternalOperation,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub budget: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsor: Option<SuiAddress>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub multisig_public_keys: Vec<MultiSigPublicKey>,
 }
 
 impl IntoResponse for ConstructionPreprocessResponse {
@@ -668,6 +725,26 @@ pub struct ConstructionMetadata {
     pub gas_price: u64,
     pub budget: u64,
     pub currency: Option<Currency>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsor: Option<SuiAddress>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub multisig_public_keys: Vec<MultiSigPublicKey>,
+}
+
+impl ConstructionMetadata {
+    /// The address paying for gas, i.e. the sponsor if there is one, otherwise the sender.
+    pub fn gas_owner(&self) -> SuiAddress {
+        self.sponsor.unwrap_or(self.sender)
+    }
+
+    /// All addresses which need to sign the transaction, the sender first.
+    pub fn signers(&self) -> Vec<SuiAddress> {
+        let mut signers = vec![self.sender];
+        if self.gas_owner() != self.sender {
+            signers.push(self.gas_owner());
+        }
+        signers
+    }
 }
 
 impl IntoResponse for ConstructionMetadataResponse {
@@ -934,6 +1011,15 @@ pub enum InternalOperation {
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         stake_ids: Vec<ObjectID>,
     },
+    MergeCoins {
+        sender: SuiAddress,
+        currency: Currency,
+    },
+    SplitCoin {
+        sender: SuiAddress,
+        amounts: Vec<u64>,
+        currency: Currency,
+    },
 }
 
 impl InternalOperation {
@@ -942,9 +1028,22 @@ impl InternalOperation {
             InternalOperation::PaySui { sender, .. }
             | InternalOperation::PayCoin { sender, .. }
             | InternalOperation::Stake { sender, .. }
-            | InternalOperation::WithdrawStake { sender, .. } => *sender,
+            | InternalOperation::WithdrawStake { sender, .. }
+            | InternalOperation::MergeCoins { sender, .. }
+            | InternalOperation::SplitCoin { sender, .. } => *sender,
         }
     }
+    /// Returns true if the operation transfers SUI out of the gas coin, which is not possible when
+    /// the gas coin is owned by a sponsor.
+    pub fn spends_gas_coin(&self) -> bool {
+        match self {
+            InternalOperation::PaySui { .. } | InternalOperation::Stake { .. } => true,
+            InternalOperation::MergeCoins { currency, .. }
+            | InternalOperation::SplitCoin { currency, .. } => currency.is_sui(),
+            InternalOperation::PayCoin { .. } | InternalOperation::WithdrawStake { .. } => false,
+        }
+    }
+
     /// Combine with ConstructionMetadata to form the TransactionData
     pub fn try_into_data(self, metadata: ConstructionMetadata) -> Result<TransactionData, Error> {
         let pt = match self {
@@ -1034,14 +1133,72 @@ impl InternalOperation {
                 }
                 builder.finish()
             }
+            InternalOperation::MergeCoins { sender, currency } => {
+                let mut builder = ProgrammableTransactionBuilder::new();
+                if currency.is_sui() {
+                    // All SUI coins of the sender are provided as gas payment and get merged into
+                    // the gas coin, transferring the gas coin back to the sender keeps it intact.
+                    builder.transfer_arg(sender, Argument::GasCoin);
+                } else {
+                    let mut coins = metadata.objects.into_iter();
+                    let primary = coins
+                        .next()
+                        .ok_or_else(|| Error::InvalidInput("No coins to merge".into()))?;
+                    let primary = builder.obj(ObjectArg::ImmOrOwnedObject(primary))?;
+                    let coins = coins
+                        .map(|coin| builder.obj(ObjectArg::ImmOrOwnedObject(coin)))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if coins.is_empty() {
+                        return Err(Error::InvalidInput(
+                            "At least two coins are required for merging".into(),
+                        ));
+                    }
+                    builder.command(Command::MergeCoins(primary, coins));
+                    add_currency_marker(&mut builder, &currency)?;
+                }
+                builder.finish()
+            }
+            InternalOperation::SplitCoin {
+                sender,
+                amounts,
+                currency,
+            } => {
+                let mut builder = ProgrammableTransactionBuilder::new();
+                let recipients = vec![sender; amounts.len()];
+                if currency.is_sui() {
+                    builder.pay_sui(recipients, amounts)?;
+                } else {
+                    builder.pay(metadata.objects, recipients, amounts)?;
+                }
+                // A split is a pay to the sender itself, the marker tells it apart from a PaySui
+                // or PayCoin whose recipient happens to be the sender.
+                builder.pure(SPLIT_COIN_MARKER.to_string())?;
+                add_currency_marker(&mut builder, &currency)?;
+                builder.finish()
+            }
         };
 
-        Ok(TransactionData::new_programmable(
+        Ok(TransactionData::new_programmable_allow_sponsor(
             metadata.sender,
             metadata.coins,
             pt,
             metadata.budget,
             metadata.gas_price,
+            metadata.sponsor.unwrap_or(metadata.sender),
         ))
     }
 }
+
+/// Pure input placed right before the currency marker of a SplitCoin transaction.
+pub(crate) const SPLIT_COIN_MARKER: &str = "sui-rosetta::SplitCoin";
+
+/// Append the currency as the last pure input of the transaction, see the PayCoin case of
+/// [InternalOperation::try_into_data] for why this is needed.
+fn add_currency_marker(
+    builder: &mut ProgrammableTransactionBuilder,
+    currency: &Currency,
+) -> Result<(), Error> {
+    let currency_str = serde_json::to_string(currency).map_err(anyhow::Error::from)?;
+    builder.pure(currency_str)?;
+    Ok(())
+}
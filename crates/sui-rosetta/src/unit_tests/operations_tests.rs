@@ -5,10 +5,14 @@ use move_core_types::annotated_value::MoveTypeLayout;
 use sui_json_rpc_types::SuiCallArg;
 use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
-use sui_types::transaction::{CallArg, TransactionData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER};
+use sui_types::transaction::{
+    Argument, CallArg, Command, ObjectArg, TransactionData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+};
 
-use crate::operations::Operations;
-use crate::types::{ConstructionMetadata, OperationType};
+use crate::operations::{OperationMetadata, Operations};
+use crate::types::{
+    ConstructionMetadata, Currency, CurrencyMetadata, OperationType, SPLIT_COIN_MARKER,
+};
 use crate::SUI;
 
 #[tokio::test]
@@ -49,6 +53,8 @@ async fn test_operation_data_parsing_pay_sui() -> Result<(), anyhow::Error> {
         gas_price,
         budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
         currency: None,
+        sponsor: None,
+        multisig_public_keys: vec![],
     };
     let parsed_data = ops.into_internal()?.try_into_data(metadata)?;
     assert_eq!(data, parsed_data);
@@ -105,12 +111,345 @@ async fn test_operation_data_parsing_pay_coin() -> Result<(), anyhow::Error> {
         gas_price,
         budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
         currency: Some(SUI.clone()),
+        sponsor: None,
+        multisig_public_keys: vec![],
     };
     let parsed_data = ops.into_internal()?.try_into_data(metadata)?;
     assert_eq!(data, parsed_data);
 
     Ok(())
 }
+#[tokio::test]
+async fn test_operation_data_parsing_split_coin() -> Result<(), anyhow::Error> {
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+
+    let coin = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+
+    let sender = SuiAddress::random_for_testing_only();
+    let currency = test_currency();
+
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .pay(vec![coin], vec![sender, sender], vec![10000, 20000])
+            .unwrap();
+        builder.pure(SPLIT_COIN_MARKER.to_string())?;
+        builder.pure(serde_json::to_string(&currency)?)?;
+        builder.finish()
+    };
+    let gas_price = 10;
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        pt,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+        gas_price,
+    );
+
+    let ops: Operations = data.clone().try_into()?;
+    assert_eq!(ops.0.len(), 1);
+    assert_eq!(ops.0[0].type_, OperationType::SplitCoin);
+    assert_eq!(
+        ops.0[0].metadata,
+        Some(OperationMetadata::SplitCoin {
+            currency: currency.clone(),
+            amounts: vec![10000, 20000],
+        })
+    );
+    let metadata = ConstructionMetadata {
+        sender,
+        coins: vec![gas],
+        objects: vec![coin],
+        total_coin_value: 0,
+        gas_price,
+        budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+        currency: Some(currency),
+        sponsor: None,
+        multisig_public_keys: vec![],
+    };
+    let parsed_data = ops.into_internal()?.try_into_data(metadata)?;
+    assert_eq!(data, parsed_data);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_operation_data_parsing_split_sui() -> Result<(), anyhow::Error> {
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+
+    let sender = SuiAddress::random_for_testing_only();
+
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.pay_sui(vec![sender], vec![10000]).unwrap();
+        builder.pure(SPLIT_COIN_MARKER.to_string())?;
+        builder.pure(serde_json::to_string(&*SUI)?)?;
+        builder.finish()
+    };
+    let gas_price = 10;
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        pt,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+        gas_price,
+    );
+
+    let ops: Operations = data.clone().try_into()?;
+    assert_eq!(ops.0.len(), 1);
+    assert_eq!(
+        ops.0[0].metadata,
+        Some(OperationMetadata::SplitCoin {
+            currency: SUI.clone(),
+            amounts: vec![10000],
+        })
+    );
+    let metadata = ConstructionMetadata {
+        sender,
+        coins: vec![gas],
+        objects: vec![],
+        total_coin_value: 0,
+        gas_price,
+        budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+        currency: Some(SUI.clone()),
+        sponsor: None,
+        multisig_public_keys: vec![],
+    };
+    let parsed_data = ops.into_internal()?.try_into_data(metadata)?;
+    assert_eq!(data, parsed_data);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_operation_data_parsing_pay_to_self_is_not_split() -> Result<(), anyhow::Error> {
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let coin = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let sender = SuiAddress::random_for_testing_only();
+    let currency = test_currency();
+    let gas_price = 10;
+
+    let pay_sui = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.pay_sui(vec![sender], vec![10000]).unwrap();
+        builder.finish()
+    };
+    let pay_coin = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .pay(vec![coin], vec![sender, sender], vec![10000, 20000])
+            .unwrap();
+        builder.pure(serde_json::to_string(&currency)?)?;
+        builder.finish()
+    };
+
+    for (pt, expected) in [
+        (pay_sui, OperationType::PaySui),
+        (pay_coin, OperationType::PayCoin),
+    ] {
+        let data = TransactionData::new_programmable(
+            sender,
+            vec![gas],
+            pt,
+            TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+            gas_price,
+        );
+        let ops: Operations = data.try_into()?;
+        assert!(!ops.0.is_empty());
+        ops.0.iter().for_each(|op| assert_eq!(op.type_, expected));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_operation_data_parsing_merge_coins() -> Result<(), anyhow::Error> {
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+
+    let coins = (0..3)
+        .map(|_| {
+            (
+                ObjectID::random(),
+                SequenceNumber::new(),
+                ObjectDigest::random(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let sender = SuiAddress::random_for_testing_only();
+    let currency = test_currency();
+
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let primary = builder.obj(ObjectArg::ImmOrOwnedObject(coins[0]))?;
+        let rest = coins[1..]
+            .iter()
+            .map(|coin| builder.obj(ObjectArg::ImmOrOwnedObject(*coin)))
+            .collect::<Result<Vec<_>, _>>()?;
+        builder.command(Command::MergeCoins(primary, rest));
+        builder.pure(serde_json::to_string(&currency)?)?;
+        builder.finish()
+    };
+    let gas_price = 10;
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        pt,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+        gas_price,
+    );
+
+    let ops: Operations = data.clone().try_into()?;
+    assert_eq!(ops.0.len(), 1);
+    assert_eq!(ops.0[0].type_, OperationType::MergeCoins);
+    let metadata = ConstructionMetadata {
+        sender,
+        coins: vec![gas],
+        objects: coins,
+        total_coin_value: 0,
+        gas_price,
+        budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+        currency: Some(currency),
+        sponsor: None,
+        multisig_public_keys: vec![],
+    };
+    let parsed_data = ops.into_internal()?.try_into_data(metadata)?;
+    assert_eq!(data, parsed_data);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_operation_data_parsing_merge_sui() -> Result<(), anyhow::Error> {
+    let gas = (0..3)
+        .map(|_| {
+            (
+                ObjectID::random(),
+                SequenceNumber::new(),
+                ObjectDigest::random(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let sender = SuiAddress::random_for_testing_only();
+
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_arg(sender, Argument::GasCoin);
+        builder.finish()
+    };
+    let gas_price = 10;
+    let data = TransactionData::new_programmable(
+        sender,
+        gas.clone(),
+        pt,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+        gas_price,
+    );
+
+    let ops: Operations = data.clone().try_into()?;
+    assert_eq!(ops.0.len(), 1);
+    assert_eq!(
+        ops.0[0].metadata,
+        Some(OperationMetadata::MergeCoins {
+            currency: SUI.clone()
+        })
+    );
+    let metadata = ConstructionMetadata {
+        sender,
+        coins: gas,
+        objects: vec![],
+        total_coin_value: 0,
+        gas_price,
+        budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+        currency: Some(SUI.clone()),
+        sponsor: None,
+        multisig_public_keys: vec![],
+    };
+    let parsed_data = ops.into_internal()?.try_into_data(metadata)?;
+    assert_eq!(data, parsed_data);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_operation_data_parsing_gas_coin_transfer_to_sender() -> Result<(), anyhow::Error> {
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let coin = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+
+    let sender = SuiAddress::random_for_testing_only();
+
+    // Transferring the gas coin back to the sender is only a SUI MergeCoins operation when it is
+    // the only command of the transaction.
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let coin = builder.obj(ObjectArg::ImmOrOwnedObject(coin))?;
+        builder.command(Command::MergeCoins(Argument::GasCoin, vec![coin]));
+        builder.transfer_arg(sender, Argument::GasCoin);
+        builder.finish()
+    };
+    let gas_price = 10;
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        pt,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+        gas_price,
+    );
+
+    let ops: Operations = data.try_into()?;
+    assert_eq!(ops.0.len(), 1);
+    assert_ne!(ops.0[0].type_, OperationType::MergeCoins);
+    assert!(matches!(
+        ops.0[0].metadata,
+        Some(OperationMetadata::GenericTransaction(_))
+    ));
+
+    Ok(())
+}
+
+fn test_currency() -> Currency {
+    Currency {
+        symbol: "TEST_COIN".to_string(),
+        decimals: 6,
+        metadata: CurrencyMetadata {
+            coin_type: "0x42::test_coin::TEST_COIN".to_string(),
+        },
+    }
+}
+
 #[tokio::test]
 async fn test_sui_json() {
     let arg1 = CallArg::Pure(bcs::to_bytes(&1000000u64).unwrap());
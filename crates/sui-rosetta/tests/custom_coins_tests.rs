@@ -15,7 +15,7 @@ use sui_json_rpc_types::{
 use sui_rosetta::operations::Operations;
 use sui_rosetta::types::{
     AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, Currency, CurrencyMetadata,
-    NetworkIdentifier, SuiEnv,
+    NetworkIdentifier, PreprocessMetadata, SuiEnv,
 };
 use sui_rosetta::types::{Currencies, OperationType};
 use sui_rosetta::CoinMetadataCache;
@@ -77,6 +77,7 @@ async fn test_custom_coin_balance() {
         account_identifier: AccountIdentifier {
             address: address1,
             sub_account: None,
+            metadata: None,
         },
         block_identifier: Default::default(),
         currencies: Currencies(vec![sui_currency, test_coin_currency]),
@@ -301,3 +302,212 @@ async fn test_custom_coin_without_symbol() {
         }
     }
 }
+
+#[tokio::test]
+async fn test_custom_coin_split_and_merge() {
+    const COIN1_BALANCE: u64 = 100_000_000;
+    const COIN2_BALANCE: u64 = 200_000_000;
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let sender = test_cluster.get_address_0();
+    let client = test_cluster.wallet.get_client().await.unwrap();
+    let keystore = &test_cluster.wallet.config.keystore;
+
+    // TEST_COIN setup and mint two coins to the sender
+    let init_ret = init_package(
+        &client,
+        keystore,
+        sender,
+        Path::new("tests/custom_coins/test_coin"),
+    )
+    .await
+    .unwrap();
+    let balances_to = vec![(COIN1_BALANCE, sender), (COIN2_BALANCE, sender)];
+    let coin_type = init_ret.coin_tag.to_canonical_string(true);
+    let _mint_res = mint(&client, keystore, init_ret, balances_to)
+        .await
+        .unwrap();
+
+    let (rosetta_client, _handle) = start_rosetta_test_server(client.clone()).await;
+    let currency = json!({
+        "symbol": "TEST_COIN",
+        "decimals": 6,
+        "metadata": {
+            "coin_type": coin_type.clone(),
+        }
+    });
+
+    // Merge all TEST_COIN coins of the sender
+    let ops: Operations = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"MergeCoins",
+            "account": { "address" : sender.to_string() },
+            "metadata": { "MergeCoins": { "currency": currency.clone() } }
+        }]
+    ))
+    .unwrap();
+    let response = rosetta_client.rosetta_flow(&ops, keystore).await;
+    let coin_cache = CoinMetadataCache::new(client.clone(), NonZeroUsize::new(2).unwrap());
+    assert_flow_succeeded(&client, &coin_cache, response, &ops).await;
+
+    let coins = client
+        .coin_read_api()
+        .get_coins(sender, Some(coin_type.clone()), None, None)
+        .await
+        .unwrap();
+    assert_eq!(coins.data.len(), 1);
+    assert_eq!(coins.data[0].balance, COIN1_BALANCE + COIN2_BALANCE);
+
+    // Split the merged coin back into smaller coins
+    let ops: Operations = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"SplitCoin",
+            "account": { "address" : sender.to_string() },
+            "metadata": {
+                "SplitCoin": {
+                    "currency": currency,
+                    "amounts": [10_000_000, 20_000_000, 30_000_000]
+                }
+            }
+        }]
+    ))
+    .unwrap();
+    let response = rosetta_client.rosetta_flow(&ops, keystore).await;
+    assert_flow_succeeded(&client, &coin_cache, response, &ops).await;
+
+    let coins = client
+        .coin_read_api()
+        .get_coins(sender, Some(coin_type), None, None)
+        .await
+        .unwrap();
+    let mut balances = coins.data.iter().map(|c| c.balance).collect::<Vec<_>>();
+    balances.sort();
+    assert_eq!(
+        balances,
+        vec![
+            10_000_000,
+            20_000_000,
+            30_000_000,
+            COIN1_BALANCE + COIN2_BALANCE - 60_000_000
+        ]
+    );
+}
+
+async fn assert_flow_succeeded(
+    client: &sui_sdk::SuiClient,
+    coin_cache: &CoinMetadataCache,
+    response: sui_rosetta::types::TransactionIdentifierResponse,
+    ops: &Operations,
+) {
+    let tx = client
+        .read_api()
+        .get_transaction_with_options(
+            response.transaction_identifier.hash,
+            SuiTransactionBlockResponseOptions::new()
+                .with_input()
+                .with_effects()
+                .with_balance_changes()
+                .with_events(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        &SuiExecutionStatus::Success,
+        tx.effects.as_ref().unwrap().status()
+    );
+    let ops2 = Operations::try_from_response(tx, coin_cache).await.unwrap();
+    assert!(
+        ops2.contains(ops),
+        "Operation mismatch. expecting:{}, got:{}",
+        serde_json::to_string(ops).unwrap(),
+        serde_json::to_string(&ops2).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_sponsored_custom_coin_transfer() {
+    const COIN1_BALANCE: u64 = 100_000_000;
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let sender = test_cluster.get_address_0();
+    let recipient = test_cluster.get_address_1();
+    let sponsor = test_cluster.get_address_2();
+    let client = test_cluster.wallet.get_client().await.unwrap();
+    let keystore = &test_cluster.wallet.config.keystore;
+
+    // TEST_COIN setup and mint
+    let init_ret = init_package(
+        &client,
+        keystore,
+        sender,
+        Path::new("tests/custom_coins/test_coin"),
+    )
+    .await
+    .unwrap();
+    let balances_to = vec![(COIN1_BALANCE, sender)];
+    let coin_type = init_ret.coin_tag.to_canonical_string(true);
+    let _mint_res = mint(&client, keystore, init_ret, balances_to)
+        .await
+        .unwrap();
+
+    let (rosetta_client, _handle) = start_rosetta_test_server(client.clone()).await;
+    let sender_sui_balance = client
+        .coin_read_api()
+        .get_balance(sender, None)
+        .await
+        .unwrap()
+        .total_balance;
+
+    let ops: Operations = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"PayCoin",
+            "account": { "address" : recipient.to_string() },
+            "amount" : {
+                "value": "30000000",
+                "currency": {
+                    "symbol": "TEST_COIN",
+                    "decimals": 6,
+                    "metadata": {
+                        "coin_type": coin_type.clone(),
+                    }
+                }
+            },
+        },
+        {
+            "operation_identifier":{"index":1},
+            "type":"PayCoin",
+            "account": { "address" : sender.to_string() },
+            "amount" : {
+                "value": "-30000000",
+                "currency": {
+                    "symbol": "TEST_COIN",
+                    "decimals": 6,
+                    "metadata": {
+                        "coin_type": coin_type.clone(),
+                    }
+                }
+            },
+        }]
+    ))
+    .unwrap();
+    let metadata = PreprocessMetadata {
+        sponsor: Some(sponsor),
+        ..Default::default()
+    };
+    let response = rosetta_client
+        .rosetta_flow_with_metadata(&ops, keystore, Some(metadata))
+        .await;
+    let coin_cache = CoinMetadataCache::new(client.clone(), NonZeroUsize::new(2).unwrap());
+    assert_flow_succeeded(&client, &coin_cache, response, &ops).await;
+
+    // Gas is paid by the sponsor
+    let balance = client
+        .coin_read_api()
+        .get_balance(sender, None)
+        .await
+        .unwrap()
+        .total_balance;
+    assert_eq!(balance, sender_sui_balance);
+}
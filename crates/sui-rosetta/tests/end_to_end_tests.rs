@@ -12,11 +12,13 @@ use sui_rosetta::operations::Operations;
 use sui_rosetta::types::Currencies;
 use sui_rosetta::types::{
     AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, Currency, NetworkIdentifier,
-    SubAccount, SubAccountType, SuiEnv,
+    PreprocessMetadata, SubAccount, SubAccountType, SuiEnv,
 };
 use sui_rosetta::CoinMetadataCache;
 use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
 use sui_swarm_config::genesis_config::{DEFAULT_GAS_AMOUNT, DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT};
+use sui_types::base_types::SuiAddress;
+use sui_types::multisig::MultiSigPublicKey;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::utils::to_sender_signed_transaction;
 use test_cluster::TestClusterBuilder;
@@ -46,6 +48,7 @@ async fn test_get_staked_sui() {
         account_identifier: AccountIdentifier {
             address,
             sub_account: None,
+            metadata: None,
         },
         block_identifier: Default::default(),
         currencies: Currencies(vec![Currency::default()]),
@@ -67,6 +70,7 @@ async fn test_get_staked_sui() {
             sub_account: Some(SubAccount {
                 account_type: SubAccountType::PendingStake,
             }),
+            metadata: None,
         },
         block_identifier: Default::default(),
         currencies: Currencies(vec![Currency::default()]),
@@ -501,3 +505,89 @@ async fn test_pay_sui_multiple_times() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_pay_sui_from_multisig() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let sender = test_cluster.get_address_0();
+    let recipient = test_cluster.get_address_1();
+    let client = test_cluster.wallet.get_client().await.unwrap();
+    let keystore = &test_cluster.wallet.config.keystore;
+
+    let (rosetta_client, _handle) = start_rosetta_test_server(client.clone()).await;
+
+    // 2-of-3 MultiSig account made of keys in the test keystore.
+    let public_keys = test_cluster.get_addresses()[1..4]
+        .iter()
+        .map(|address| keystore.get_key(address).unwrap().public())
+        .collect();
+    let multisig_pk = MultiSigPublicKey::new(public_keys, vec![1, 1, 1], 2).unwrap();
+    let multisig_address = SuiAddress::from(&multisig_pk);
+
+    // Fund the MultiSig account
+    let ops = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"PaySui",
+            "account": { "address" : multisig_address.to_string() },
+            "amount" : { "value": "10000000000" }
+        },{
+            "operation_identifier":{"index":1},
+            "type":"PaySui",
+            "account": { "address" : sender.to_string() },
+            "amount" : { "value": "-10000000000" }
+        }]
+    ))
+    .unwrap();
+    rosetta_client.rosetta_flow(&ops, keystore).await;
+
+    let ops = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"PaySui",
+            "account": { "address" : recipient.to_string() },
+            "amount" : { "value": "1000000000" }
+        },{
+            "operation_identifier":{"index":1},
+            "type":"PaySui",
+            "account": { "address" : multisig_address.to_string() },
+            "amount" : { "value": "-1000000000" }
+        }]
+    ))
+    .unwrap();
+    let metadata = PreprocessMetadata {
+        multisig_public_keys: vec![multisig_pk],
+        ..Default::default()
+    };
+    let response = rosetta_client
+        .rosetta_flow_with_metadata(&ops, keystore, Some(metadata))
+        .await;
+
+    let tx = client
+        .read_api()
+        .get_transaction_with_options(
+            response.transaction_identifier.hash,
+            SuiTransactionBlockResponseOptions::new()
+                .with_input()
+                .with_effects()
+                .with_balance_changes()
+                .with_events(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        &SuiExecutionStatus::Success,
+        tx.effects.as_ref().unwrap().status()
+    );
+    let coin_cache = CoinMetadataCache::new(client, NonZeroUsize::new(2).unwrap());
+    let ops2 = Operations::try_from_response(tx, &coin_cache)
+        .await
+        .unwrap();
+    assert!(
+        ops2.contains(&ops),
+        "Operation mismatch. expecting:{}, got:{}",
+        serde_json::to_string(&ops).unwrap(),
+        serde_json::to_string(&ops2).unwrap()
+    );
+}
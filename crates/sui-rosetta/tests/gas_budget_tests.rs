@@ -86,6 +86,8 @@ async fn pay_with_gas_budget(budget: u64) -> TransactionIdentifierResponseResult
 
     let metadata = Some(PreprocessMetadata {
         budget: Some(budget),
+        sponsor: None,
+        multisig_public_keys: vec![],
     });
 
     let preprocess: ConstructionPreprocessResponse = rosetta_client
@@ -21,7 +21,8 @@ use sui_rosetta::types::{
     ConstructionCombineResponse, ConstructionMetadataRequest, ConstructionMetadataResponse,
     ConstructionPayloadsRequest, ConstructionPayloadsResponse, ConstructionPreprocessRequest,
     ConstructionPreprocessResponse, ConstructionSubmitRequest, Currencies, NetworkIdentifier,
-    Signature, SignatureType, SubAccount, SubAccountType, SuiEnv, TransactionIdentifierResponse,
+    PreprocessMetadata, Signature, SignatureType, SubAccount, SubAccountType, SuiEnv,
+    TransactionIdentifierResponse,
 };
 use sui_rosetta::{RosettaOfflineServer, RosettaOnlineServer};
 use sui_sdk::SuiClient;
@@ -107,6 +108,18 @@ impl RosettaClient {
         &self,
         operations: &Operations,
         keystore: &Keystore,
+    ) -> TransactionIdentifierResponse {
+        self.rosetta_flow_with_metadata(operations, keystore, None)
+            .await
+    }
+
+    /// rosetta construction e2e flow with preprocess metadata, all signing payloads are signed
+    /// with the keys in `keystore`.
+    pub async fn rosetta_flow_with_metadata(
+        &self,
+        operations: &Operations,
+        keystore: &Keystore,
+        metadata: Option<PreprocessMetadata>,
     ) -> TransactionIdentifierResponse {
         let network_identifier = NetworkIdentifier {
             blockchain: "sui".to_string(),
@@ -119,7 +132,7 @@ impl RosettaClient {
                 &ConstructionPreprocessRequest {
                     network_identifier: network_identifier.clone(),
                     operations: operations.clone(),
-                    metadata: None,
+                    metadata,
                 },
             )
             .await;
@@ -150,23 +163,32 @@ impl RosettaClient {
             .await;
         println!("Payload : {payloads:?}");
         // Combine
-        let signing_payload = payloads.payloads.first().unwrap();
-        let bytes = Hex::decode(&signing_payload.hex_bytes).unwrap();
-        let signer = signing_payload.account_identifier.address;
-        let signature = keystore.sign_hashed(&signer, &bytes).unwrap();
-        let public_key = keystore.get_key(&signer).unwrap().public();
+        let signatures = payloads
+            .payloads
+            .iter()
+            .map(|signing_payload| {
+                let bytes = Hex::decode(&signing_payload.hex_bytes).unwrap();
+                let signer = signing_payload.account_identifier.address;
+                let signature = keystore.sign_hashed(&signer, &bytes).unwrap();
+                let public_key = keystore.get_key(&signer).unwrap().public();
+                Signature {
+                    signing_payload: signing_payload.clone(),
+                    public_key: public_key.into(),
+                    signature_type: signing_payload
+                        .signature_type
+                        .clone()
+                        .unwrap_or(SignatureType::Ed25519),
+                    hex_bytes: Hex::from_bytes(SuiSignature::signature_bytes(&signature)),
+                }
+            })
+            .collect();
         let combine: ConstructionCombineResponse = self
             .call(
                 RosettaEndpoint::Combine,
                 &ConstructionCombineRequest {
                     network_identifier: network_identifier.clone(),
                     unsigned_transaction: payloads.unsigned_transaction,
-                    signatures: vec![Signature {
-                        signing_payload: signing_payload.clone(),
-                        public_key: public_key.into(),
-                        signature_type: SignatureType::Ed25519,
-                        hex_bytes: Hex::from_bytes(SuiSignature::signature_bytes(&signature)),
-                    }],
+                    signatures,
                 },
             )
             .await;
@@ -197,6 +219,7 @@ impl RosettaClient {
             account_identifier: AccountIdentifier {
                 address,
                 sub_account,
+                metadata: None,
             },
             block_identifier: Default::default(),
             currencies: Currencies(vec![]),
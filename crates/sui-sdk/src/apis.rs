@@ -5,8 +5,10 @@ use fastcrypto::encoding::Base64;
 use futures::stream;
 use futures::StreamExt;
 use futures_core::Stream;
-use jsonrpsee::core::client::Subscription;
-use std::collections::BTreeMap;
+use jsonrpsee::core::client::{ClientT, Subscription};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::future;
 use std::sync::Arc;
 use std::time::Duration;
@@ -14,7 +16,9 @@ use std::time::Instant;
 use sui_json_rpc_types::DevInspectArgs;
 use sui_json_rpc_types::SuiData;
 
+use crate::cache::{CacheStats, ResponseCache};
 use crate::error::{Error, SuiRpcResult};
+use crate::failover::is_transport_error;
 use crate::RpcClient;
 use sui_json_rpc_api::{
     CoinReadApiClient, GovernanceReadApiClient, IndexerApiClient, MoveUtilsClient, ReadApiClient,
@@ -34,6 +38,7 @@ use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::event::EventID;
+use sui_types::is_system_package;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::sui_serde::BigInt;
@@ -48,12 +53,28 @@ const WAIT_FOR_LOCAL_EXECUTION_INTERVAL: Duration = Duration::from_secs(2);
 #[derive(Debug)]
 pub struct ReadApi {
     api: Arc<RpcClient>,
+    cache: Option<Arc<ResponseCache>>,
 }
 
 impl ReadApi {
-    pub(crate) fn new(api: Arc<RpcClient>) -> Self {
-        Self { api }
+    pub(crate) fn new(api: Arc<RpcClient>, cache: Option<Arc<ResponseCache>>) -> Self {
+        Self { api, cache }
+    }
+
+    pub(crate) fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
+    fn cached<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.cache.as_ref()?.get(key)
     }
+
+    fn cache<T: Serialize>(&self, key: String, value: &T) {
+        if let Some(cache) = &self.cache {
+            cache.insert(key, value);
+        }
+    }
+
     /// Return a paginated response with the objects owned by the given address, or an error upon failure.
     ///
     /// Note that if the address owns more than `QUERY_MAX_RESULT_LIMIT` objects (default is 50),
@@ -215,11 +236,24 @@ impl ReadApi {
         version: SequenceNumber,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<SuiPastObjectResponse> {
-        Ok(self
+        let key = is_cacheable_past_object(&options)
+            .then(|| past_object_key(object_id, version, &options));
+        if let Some(response) = key.as_ref().and_then(|key| self.cached(key)) {
+            return Ok(response);
+        }
+
+        let response = self
             .api
             .http
             .try_get_past_object(object_id, version, Some(options))
-            .await?)
+            .await?;
+
+        if let Some(key) = key {
+            if matches!(response, SuiPastObjectResponse::VersionFound(_)) {
+                self.cache(key, &response);
+            }
+        }
+        Ok(response)
     }
 
     /// Return a list of [SuiPastObjectResponse] objects, or an error upon failure.
@@ -296,11 +330,53 @@ impl ReadApi {
         past_objects: Vec<SuiGetPastObjectRequest>,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<Vec<SuiPastObjectResponse>> {
-        Ok(self
-            .api
-            .http
-            .try_multi_get_past_objects(past_objects, Some(options))
-            .await?)
+        if self.cache.is_none() || !is_cacheable_past_object(&options) {
+            return Ok(self
+                .api
+                .http
+                .try_multi_get_past_objects(past_objects, Some(options))
+                .await?);
+        }
+
+        let keys: Vec<_> = past_objects
+            .iter()
+            .map(|req| past_object_key(req.object_id, req.version, &options))
+            .collect();
+        let mut responses: Vec<Option<SuiPastObjectResponse>> =
+            keys.iter().map(|key| self.cached(key)).collect();
+
+        let (missing_indices, missing): (Vec<_>, Vec<_>) = past_objects
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| responses[*i].is_none())
+            .unzip();
+
+        if !missing.is_empty() {
+            let fetched = self
+                .api
+                .http
+                .try_multi_get_past_objects(missing, Some(options))
+                .await?;
+
+            if fetched.len() != missing_indices.len() {
+                return Err(Error::DataError(format!(
+                    "Requested {} past objects, but got {}",
+                    missing_indices.len(),
+                    fetched.len(),
+                )));
+            }
+
+            // Responses are returned in the order objects were requested in.
+            for (i, response) in missing_indices.into_iter().zip(fetched) {
+                if matches!(response, SuiPastObjectResponse::VersionFound(_)) {
+                    self.cache(keys[i].clone(), &response);
+                }
+                responses[i] = Some(response);
+            }
+        }
+
+        // Every response is either cached or fetched at this point.
+        Ok(responses.into_iter().map(Option::unwrap).collect())
     }
 
     /// Return a [SuiObjectResponse] based on the provided [ObjectID] and [SuiObjectDataOptions], or an error upon failure.
@@ -459,11 +535,22 @@ impl ReadApi {
         digest: TransactionDigest,
         options: SuiTransactionBlockResponseOptions,
     ) -> SuiRpcResult<SuiTransactionBlockResponse> {
-        Ok(self
+        let key = transaction_key(&digest, &options);
+        if let Some(response) = self.cached(&key) {
+            return Ok(response);
+        }
+
+        let response = self
             .api
             .http
             .get_transaction_block(digest, Some(options))
-            .await?)
+            .await?;
+
+        // Transactions can only be cached once they are in a checkpoint, and final.
+        if response.checkpoint.is_some() {
+            self.cache(key, &response);
+        }
+        Ok(response)
     }
     /// Return a list of [SuiTransactionBlockResponse] based on the given vector of [TransactionDigest], or an error upon failure.
     ///
@@ -474,11 +561,44 @@ impl ReadApi {
         digests: Vec<TransactionDigest>,
         options: SuiTransactionBlockResponseOptions,
     ) -> SuiRpcResult<Vec<SuiTransactionBlockResponse>> {
-        Ok(self
-            .api
-            .http
-            .multi_get_transaction_blocks(digests, Some(options))
-            .await?)
+        if self.cache.is_none() {
+            return Ok(self
+                .api
+                .http
+                .multi_get_transaction_blocks(digests, Some(options))
+                .await?);
+        }
+
+        let mut cached = HashMap::new();
+        let mut missing = vec![];
+        for digest in &digests {
+            match self.cached(&transaction_key(digest, &options)) {
+                Some(response) => {
+                    cached.insert(*digest, response);
+                }
+                None => missing.push(*digest),
+            }
+        }
+
+        if !missing.is_empty() {
+            let fetched = self
+                .api
+                .http
+                .multi_get_transaction_blocks(missing, Some(options.clone()))
+                .await?;
+
+            for response in fetched {
+                if response.checkpoint.is_some() {
+                    self.cache(transaction_key(&response.digest, &options), &response);
+                }
+                cached.insert(response.digest, response);
+            }
+        }
+
+        Ok(digests
+            .iter()
+            .filter_map(|digest| cached.get(digest).cloned())
+            .collect())
     }
 
     /// Return the [SuiCommittee] information for the provided `epoch`, or an error upon failure.
@@ -534,7 +654,17 @@ impl ReadApi {
     /// A Sui checkpoint is a sequence of transaction sets that a quorum of validators
     /// agree upon as having been executed within the Sui system.
     pub async fn get_checkpoint(&self, id: CheckpointId) -> SuiRpcResult<Checkpoint> {
-        Ok(self.api.http.get_checkpoint(id).await?)
+        let key = match &id {
+            CheckpointId::SequenceNumber(seq) => format!("checkpoint:{seq}"),
+            CheckpointId::Digest(digest) => format!("checkpoint:{digest}"),
+        };
+        if let Some(checkpoint) = self.cached(&key) {
+            return Ok(checkpoint);
+        }
+
+        let checkpoint = self.api.http.get_checkpoint(id).await?;
+        self.cache(key, &checkpoint);
+        Ok(checkpoint)
     }
 
     /// Return a paginated list of checkpoints, or an error upon failure.
@@ -617,11 +747,27 @@ impl ReadApi {
         &self,
         package: ObjectID,
     ) -> SuiRpcResult<BTreeMap<String, SuiMoveNormalizedModule>> {
-        Ok(self
+        // System packages are upgraded in place, so they are the only packages that can change.
+        if is_system_package(package) {
+            return Ok(self
+                .api
+                .http
+                .get_normalized_move_modules_by_package(package)
+                .await?);
+        }
+
+        let key = format!("package_modules:{package}");
+        if let Some(modules) = self.cached(&key) {
+            return Ok(modules);
+        }
+
+        let modules = self
             .api
             .http
             .get_normalized_move_modules_by_package(package)
-            .await?)
+            .await?;
+        self.cache(key, &modules);
+        Ok(modules)
     }
 
     // TODO(devx): we can probably cache this given an epoch
@@ -1134,22 +1280,11 @@ impl QuorumDriverApi {
         options: SuiTransactionBlockResponseOptions,
         request_type: Option<ExecuteTransactionRequestType>,
     ) -> SuiRpcResult<SuiTransactionBlockResponse> {
-        let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
         let request_type = request_type.unwrap_or_else(|| options.default_execution_request_type());
 
         let start = Instant::now();
-        let response = self
-            .api
-            .http
-            .execute_transaction_block(
-                tx_bytes.clone(),
-                signatures.clone(),
-                Some(options.clone()),
-                // Ignore the request type as we emulate WaitForLocalExecution below.
-                // It will default to WaitForEffectsCert on the RPC nodes.
-                None,
-            )
-            .await?;
+        let response =
+            execute_or_refetch(&self.api.http, &tx, &options, self.api.http.is_failover()).await?;
 
         if let ExecuteTransactionRequestType::WaitForEffectsCert = request_type {
             return Ok(response);
@@ -1240,3 +1375,362 @@ impl GovernanceApi {
         Ok(*self.api.http.get_reference_gas_price().await?)
     }
 }
+
+/// Execute `tx` through `http`. If `refetch_on_transport_error` is set and the request fails to
+/// reach its endpoint, the transaction is looked up by its digest, in case it was executed even
+/// though its response was lost. This is only worth doing when the lookup can be sent to another
+/// endpoint than the one that failed, i.e. when the client fails over between endpoints.
+async fn execute_or_refetch<C: ClientT + Sync>(
+    http: &C,
+    tx: &Transaction,
+    options: &SuiTransactionBlockResponseOptions,
+    refetch_on_transport_error: bool,
+) -> SuiRpcResult<SuiTransactionBlockResponse> {
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+    let result = http
+        .execute_transaction_block(
+            tx_bytes,
+            signatures,
+            Some(options.clone()),
+            // Ignore the request type as we emulate WaitForLocalExecution below.
+            // It will default to WaitForEffectsCert on the RPC nodes.
+            None,
+        )
+        .await;
+
+    match result {
+        Ok(response) => Ok(response),
+        Err(e) if refetch_on_transport_error && is_transport_error(&e) => Ok(http
+            .get_transaction_block(*tx.digest(), Some(options.clone()))
+            .await
+            .map_err(|_| e)?),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Whether past objects fetched with `options` can be cached. Display is rendered from the latest
+/// `Display` object for the object's type, which can change after the version was written, so
+/// responses that include it are not cached.
+fn is_cacheable_past_object(options: &SuiObjectDataOptions) -> bool {
+    !options.show_display
+}
+
+/// Key that a past object is cached under, when fetched with `options`.
+fn past_object_key(
+    object_id: ObjectID,
+    version: SequenceNumber,
+    options: &SuiObjectDataOptions,
+) -> String {
+    let options = serde_json::to_string(options).unwrap_or_default();
+    format!("past_object:{object_id}:{}:{options}", version.value())
+}
+
+/// Key that a transaction is cached under, when fetched with `options`.
+fn transaction_key(
+    digest: &TransactionDigest,
+    options: &SuiTransactionBlockResponseOptions,
+) -> String {
+    let options = serde_json::to_string(options).unwrap_or_default();
+    format!("transaction:{digest}:{options}")
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_json_rpc_types::SuiObjectData;
+    use sui_types::base_types::random_object_ref;
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::digests::ObjectDigest;
+    use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
+
+    use crate::cache::CacheConfig;
+    use crate::failover::tests::MockClient;
+    use crate::failover::HttpTransport;
+    use crate::{RpcClient, ServerInfo};
+
+    use super::*;
+
+    const EXECUTE: &str = "sui_executeTransactionBlock";
+    const GET_TRANSACTION: &str = "sui_getTransactionBlock";
+    const GET_PAST_OBJECT: &str = "sui_tryGetPastObject";
+    const MULTI_GET_PAST_OBJECTS: &str = "sui_tryMultiGetPastObjects";
+    const GET_MODULES: &str = "sui_getNormalizedMoveModulesByPackage";
+
+    fn transaction() -> Transaction {
+        let (sender, key): (_, AccountKeyPair) = get_key_pair();
+        let data = TransactionData::new_transfer_sui(
+            SuiAddress::ZERO,
+            sender,
+            None,
+            random_object_ref(),
+            1_000_000,
+            1_000,
+        );
+        Transaction::from_data_and_signer(data, vec![&key])
+    }
+
+    /// A client that fails to send execution requests, but finds the transaction by its digest.
+    fn lossy_client(tx: &Transaction) -> MockClient {
+        let response = SuiTransactionBlockResponse::new(*tx.digest());
+        MockClient::default().respond(GET_TRANSACTION, serde_json::to_value(response).unwrap())
+    }
+
+    #[tokio::test]
+    async fn refetches_lost_execution_with_failover() {
+        let tx = transaction();
+        let client = lossy_client(&tx);
+
+        let response = execute_or_refetch(&client, &tx, &Default::default(), true)
+            .await
+            .unwrap();
+        assert_eq!(response.digest, *tx.digest());
+        assert_eq!(client.requests(), vec![EXECUTE, GET_TRANSACTION]);
+    }
+
+    #[tokio::test]
+    async fn does_not_refetch_without_failover() {
+        let tx = transaction();
+        let client = lossy_client(&tx);
+
+        let err = execute_or_refetch(&client, &tx, &Default::default(), false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::RpcError(_)), "{err}");
+        assert_eq!(client.requests(), vec![EXECUTE]);
+    }
+
+    /// A read API over `client`, caching responses in memory.
+    fn cached_read_api(client: MockClient) -> ReadApi {
+        let api = RpcClient {
+            http: HttpTransport::Mock(client),
+            ws: None,
+            info: ServerInfo {
+                rpc_methods: vec![],
+                subscriptions: vec![],
+                version: "test".to_string(),
+            },
+        };
+        let cache = ResponseCache::new(CacheConfig::default(), "4c78adac").unwrap();
+        ReadApi::new(Arc::new(api), Some(Arc::new(cache)))
+    }
+
+    fn requests(read_api: &ReadApi) -> Vec<String> {
+        let HttpTransport::Mock(client) = &read_api.api.http else {
+            unreachable!("read API is not backed by a mock client");
+        };
+        client.requests()
+    }
+
+    fn past_object(version: u64) -> SuiPastObjectResponse {
+        SuiPastObjectResponse::VersionFound(SuiObjectData {
+            object_id: ObjectID::random(),
+            version: SequenceNumber::from_u64(version),
+            digest: ObjectDigest::random(),
+            type_: None,
+            owner: None,
+            previous_transaction: None,
+            storage_rebate: None,
+            display: None,
+            content: None,
+            bcs: None,
+        })
+    }
+
+    fn request(response: &SuiPastObjectResponse) -> SuiGetPastObjectRequest {
+        let object = response.object().unwrap();
+        SuiGetPastObjectRequest {
+            object_id: object.object_id,
+            version: object.version,
+        }
+    }
+
+    #[tokio::test]
+    async fn caches_past_objects() {
+        let object = past_object(1);
+        let read_api = cached_read_api(
+            MockClient::default().respond(GET_PAST_OBJECT, serde_json::to_value(&object).unwrap()),
+        );
+
+        let SuiGetPastObjectRequest { object_id, version } = request(&object);
+        for _ in 0..2 {
+            let response = read_api
+                .try_get_parsed_past_object(object_id, version, Default::default())
+                .await
+                .unwrap();
+            assert_eq!(response, object);
+        }
+
+        assert_eq!(requests(&read_api), vec![GET_PAST_OBJECT]);
+        let stats = read_api.cache_stats().unwrap();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn does_not_cache_missing_past_objects() {
+        let missing =
+            SuiPastObjectResponse::VersionNotFound(ObjectID::random(), SequenceNumber::from_u64(1));
+        let read_api = cached_read_api(
+            MockClient::default().respond(GET_PAST_OBJECT, serde_json::to_value(&missing).unwrap()),
+        );
+
+        let SuiPastObjectResponse::VersionNotFound(object_id, version) = missing else {
+            unreachable!();
+        };
+        for _ in 0..2 {
+            read_api
+                .try_get_parsed_past_object(object_id, version, Default::default())
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(requests(&read_api), vec![GET_PAST_OBJECT, GET_PAST_OBJECT]);
+    }
+
+    #[tokio::test]
+    async fn does_not_cache_past_objects_with_display() {
+        let object = past_object(1);
+        let read_api = cached_read_api(
+            MockClient::default()
+                .respond(GET_PAST_OBJECT, serde_json::to_value(&object).unwrap())
+                .respond(
+                    MULTI_GET_PAST_OBJECTS,
+                    serde_json::to_value(vec![&object]).unwrap(),
+                ),
+        );
+
+        let options = SuiObjectDataOptions::new().with_display();
+        let SuiGetPastObjectRequest { object_id, version } = request(&object);
+        for _ in 0..2 {
+            read_api
+                .try_get_parsed_past_object(object_id, version, options.clone())
+                .await
+                .unwrap();
+            read_api
+                .try_multi_get_parsed_past_object(vec![request(&object)], options.clone())
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            requests(&read_api),
+            vec![
+                GET_PAST_OBJECT,
+                MULTI_GET_PAST_OBJECTS,
+                GET_PAST_OBJECT,
+                MULTI_GET_PAST_OBJECTS
+            ]
+        );
+        let stats = read_api.cache_stats().unwrap();
+        assert_eq!((stats.hits, stats.misses), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn multi_get_only_fetches_uncached_past_objects() {
+        let (cached, uncached) = (past_object(1), past_object(2));
+        let read_api = cached_read_api(
+            MockClient::default()
+                .respond(GET_PAST_OBJECT, serde_json::to_value(&cached).unwrap())
+                .respond(
+                    MULTI_GET_PAST_OBJECTS,
+                    serde_json::to_value(vec![&uncached]).unwrap(),
+                ),
+        );
+
+        let SuiGetPastObjectRequest { object_id, version } = request(&cached);
+        read_api
+            .try_get_parsed_past_object(object_id, version, Default::default())
+            .await
+            .unwrap();
+
+        // The uncached object is fetched, and the responses keep the order of the requests.
+        let requested = vec![request(&uncached), request(&cached)];
+        let responses = read_api
+            .try_multi_get_parsed_past_object(requested.clone(), Default::default())
+            .await
+            .unwrap();
+        assert_eq!(responses, vec![uncached.clone(), cached.clone()]);
+
+        // Both objects are cached by now.
+        let responses = read_api
+            .try_multi_get_parsed_past_object(requested, Default::default())
+            .await
+            .unwrap();
+        assert_eq!(responses, vec![uncached, cached]);
+
+        assert_eq!(
+            requests(&read_api),
+            vec![GET_PAST_OBJECT, MULTI_GET_PAST_OBJECTS]
+        );
+    }
+
+    #[tokio::test]
+    async fn multi_get_rejects_missing_responses() {
+        let read_api = cached_read_api(MockClient::default().respond(
+            MULTI_GET_PAST_OBJECTS,
+            serde_json::to_value(vec![past_object(1)]).unwrap(),
+        ));
+
+        let requested = vec![request(&past_object(1)), request(&past_object(1))];
+        let err = read_api
+            .try_multi_get_parsed_past_object(requested, Default::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::DataError(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn caches_modules_of_user_packages_only() {
+        let read_api =
+            cached_read_api(MockClient::default().respond(GET_MODULES, serde_json::json!({})));
+
+        let package = ObjectID::random();
+        for _ in 0..2 {
+            read_api
+                .get_normalized_move_modules_by_package(package)
+                .await
+                .unwrap();
+        }
+        assert_eq!(requests(&read_api), vec![GET_MODULES]);
+
+        // System packages can be upgraded in place, so their modules are always fetched.
+        for _ in 0..2 {
+            read_api
+                .get_normalized_move_modules_by_package(SUI_FRAMEWORK_PACKAGE_ID)
+                .await
+                .unwrap();
+        }
+        assert_eq!(requests(&read_api), vec![GET_MODULES; 3]);
+    }
+
+    #[tokio::test]
+    async fn caches_only_checkpointed_transactions() {
+        let pending = SuiTransactionBlockResponse::new(TransactionDigest::random());
+        let read_api = cached_read_api(
+            MockClient::default().respond(GET_TRANSACTION, serde_json::to_value(&pending).unwrap()),
+        );
+
+        for _ in 0..2 {
+            read_api
+                .get_transaction_with_options(pending.digest, Default::default())
+                .await
+                .unwrap();
+        }
+        assert_eq!(requests(&read_api), vec![GET_TRANSACTION; 2]);
+
+        let checkpointed = SuiTransactionBlockResponse {
+            checkpoint: Some(1),
+            ..SuiTransactionBlockResponse::new(TransactionDigest::random())
+        };
+        let read_api = cached_read_api(MockClient::default().respond(
+            GET_TRANSACTION,
+            serde_json::to_value(&checkpointed).unwrap(),
+        ));
+
+        for _ in 0..2 {
+            read_api
+                .get_transaction_with_options(checkpointed.digest, Default::default())
+                .await
+                .unwrap();
+        }
+        assert_eq!(requests(&read_api), vec![GET_TRANSACTION]);
+    }
+}
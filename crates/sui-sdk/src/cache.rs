@@ -0,0 +1,379 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An opt-in cache for responses that never change once they exist (checkpoints, finalized
+//! transactions, packages, and specific versions of objects), enabled with
+//! [crate::SuiClientBuilder::cache].
+//!
+//! Responses are kept in memory, in a least-recently-used cache bounded by the total size of the
+//! responses, and can optionally be persisted to disk, so that they survive restarts. Persisted
+//! responses are kept in a separate directory for each chain, so that clients of different chains
+//! can share a cache directory.
+
+use std::fmt::{Debug, Formatter};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use lru::LruCache;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tempfile::NamedTempFile;
+use tracing::warn;
+
+/// Configures the response cache of a [crate::SuiClient].
+#[derive(Clone, Debug)]
+pub struct CacheConfig {
+    /// Maximum total size, in bytes, of the responses kept in memory.
+    pub max_memory_bytes: u64,
+    /// Directory to persist responses to, in a subdirectory named after the chain identifier.
+    /// Responses are only kept in memory if this is not set.
+    pub disk_path: Option<PathBuf>,
+    /// Maximum total size, in bytes, of the responses persisted to disk. Once the limit is
+    /// reached, new responses are only kept in memory. The limit applies to each chain separately.
+    pub max_disk_bytes: u64,
+}
+
+/// Counters describing how the response cache has been used.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Requests answered from memory.
+    pub hits: u64,
+    /// Requests answered from disk.
+    pub disk_hits: u64,
+    /// Requests that had to be sent to the full node.
+    pub misses: u64,
+    /// Responses evicted from memory to stay within the memory limit.
+    pub evictions: u64,
+    pub memory_bytes: u64,
+    pub disk_bytes: u64,
+}
+
+pub(crate) struct ResponseCache {
+    config: CacheConfig,
+    /// Directory that responses from this cache's chain are persisted to.
+    disk_dir: Option<PathBuf>,
+    memory: Mutex<Memory>,
+    metrics: Metrics,
+}
+
+struct Memory {
+    entries: LruCache<String, Arc<Vec<u8>>>,
+    bytes: u64,
+}
+
+#[derive(Default)]
+struct Metrics {
+    hits: AtomicU64,
+    disk_hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    memory_bytes: AtomicU64,
+    disk_bytes: AtomicU64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_memory_bytes: 256 << 20,
+            disk_path: None,
+            max_disk_bytes: 4 << 30,
+        }
+    }
+}
+
+impl ResponseCache {
+    /// Open a cache for responses from the chain identified by `chain_id`.
+    pub(crate) fn new(config: CacheConfig, chain_id: &str) -> io::Result<Self> {
+        let metrics = Metrics::default();
+        let disk_dir = config.disk_path.as_ref().map(|path| path.join(chain_id));
+        if let Some(dir) = &disk_dir {
+            fs::create_dir_all(dir)?;
+            let disk_bytes = dir_size(dir)?;
+            metrics.disk_bytes.store(disk_bytes, Ordering::Relaxed);
+        }
+
+        Ok(Self {
+            config,
+            disk_dir,
+            memory: Mutex::new(Memory {
+                entries: LruCache::unbounded(),
+                bytes: 0,
+            }),
+            metrics,
+        })
+    }
+
+    /// Look up the response cached under `key`, from memory, and then from disk.
+    pub(crate) fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let cached = self.memory.lock().unwrap().entries.get(key).cloned();
+        if let Some(bytes) = cached {
+            self.metrics.hits.fetch_add(1, Ordering::Relaxed);
+            return serde_json::from_slice(&bytes).ok();
+        }
+
+        if let Some(bytes) = self.read_disk(key) {
+            self.metrics.disk_hits.fetch_add(1, Ordering::Relaxed);
+            let value = serde_json::from_slice(&bytes).ok();
+            self.insert_memory(key.to_string(), Arc::new(bytes));
+            return value;
+        }
+
+        self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+
+    /// Cache `value` under `key`. Callers are responsible for only caching responses that will
+    /// never change.
+    pub(crate) fn insert<T: Serialize>(&self, key: String, value: &T) {
+        let Ok(bytes) = serde_json::to_vec(value) else {
+            return;
+        };
+
+        self.write_disk(&key, &bytes);
+        self.insert_memory(key, Arc::new(bytes));
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        let Metrics {
+            hits,
+            disk_hits,
+            misses,
+            evictions,
+            memory_bytes,
+            disk_bytes,
+        } = &self.metrics;
+
+        CacheStats {
+            hits: hits.load(Ordering::Relaxed),
+            disk_hits: disk_hits.load(Ordering::Relaxed),
+            misses: misses.load(Ordering::Relaxed),
+            evictions: evictions.load(Ordering::Relaxed),
+            memory_bytes: memory_bytes.load(Ordering::Relaxed),
+            disk_bytes: disk_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    fn insert_memory(&self, key: String, bytes: Arc<Vec<u8>>) {
+        let size = bytes.len() as u64;
+        if size > self.config.max_memory_bytes {
+            return;
+        }
+
+        let mut memory = self.memory.lock().unwrap();
+        if let Some(replaced) = memory.entries.put(key, bytes) {
+            memory.bytes -= replaced.len() as u64;
+        }
+        memory.bytes += size;
+
+        while memory.bytes > self.config.max_memory_bytes {
+            let Some((_, evicted)) = memory.entries.pop_lru() else {
+                break;
+            };
+            memory.bytes -= evicted.len() as u64;
+            self.metrics.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+
+        self.metrics
+            .memory_bytes
+            .store(memory.bytes, Ordering::Relaxed);
+    }
+
+    fn read_disk(&self, key: &str) -> Option<Vec<u8>> {
+        let path = self.disk_entry(key)?;
+        fs::read(path).ok()
+    }
+
+    fn write_disk(&self, key: &str, bytes: &[u8]) {
+        let (Some(dir), Some(path)) = (&self.disk_dir, self.disk_entry(key)) else {
+            return;
+        };
+
+        let size = bytes.len() as u64;
+        if self.metrics.disk_bytes.load(Ordering::Relaxed) + size > self.config.max_disk_bytes
+            || path.exists()
+        {
+            return;
+        }
+
+        // Write to a uniquely named temporary file first, so that readers (in this process or
+        // others sharing the directory) never see a partially written entry.
+        let persisted = NamedTempFile::new_in(dir).and_then(|mut tmp| {
+            tmp.write_all(bytes)?;
+            tmp.persist(&path).map_err(|e| e.error)?;
+            Ok(())
+        });
+
+        match persisted {
+            Ok(()) => {
+                self.metrics.disk_bytes.fetch_add(size, Ordering::Relaxed);
+            }
+            Err(e) => warn!(
+                "Failed to persist cached response to {}: {e}",
+                path.display()
+            ),
+        }
+    }
+
+    /// Path of the file that the response cached under `key` is persisted to, if responses are
+    /// persisted.
+    fn disk_entry(&self, key: &str) -> Option<PathBuf> {
+        let dir = self.disk_dir.as_ref()?;
+        let digest = Blake2b256::digest(key.as_bytes());
+        Some(dir.join(Hex::encode(digest.digest)))
+    }
+}
+
+impl Debug for ResponseCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("config", &self.config)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+fn dir_size(path: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(path)? {
+        size += entry?.metadata()?.len();
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAIN: &str = "4c78adac";
+
+    fn memory_cache(max_memory_bytes: u64) -> ResponseCache {
+        ResponseCache::new(
+            CacheConfig {
+                max_memory_bytes,
+                ..Default::default()
+            },
+            CHAIN,
+        )
+        .unwrap()
+    }
+
+    fn disk_cache(path: &Path, max_disk_bytes: u64) -> ResponseCache {
+        ResponseCache::new(
+            CacheConfig {
+                disk_path: Some(path.to_path_buf()),
+                max_disk_bytes,
+                ..Default::default()
+            },
+            CHAIN,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn memory_hits_and_misses() {
+        let cache = memory_cache(1 << 20);
+        assert_eq!(cache.get::<String>("a"), None);
+
+        cache.insert("a".to_string(), &"alpha");
+        assert_eq!(cache.get::<String>("a").as_deref(), Some("alpha"));
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.memory_bytes, "\"alpha\"".len() as u64);
+        assert_eq!(stats.disk_bytes, 0);
+    }
+
+    #[test]
+    fn memory_evicts_least_recently_used() {
+        // Every entry is 5 bytes ("\"aaa\""), so only two fit.
+        let cache = memory_cache(10);
+        cache.insert("a".to_string(), &"aaa");
+        cache.insert("b".to_string(), &"bbb");
+
+        // Touch "a", so that "b" is the least recently used entry.
+        assert!(cache.get::<String>("a").is_some());
+        cache.insert("c".to_string(), &"ccc");
+
+        assert!(cache.get::<String>("a").is_some());
+        assert!(cache.get::<String>("b").is_none());
+        assert!(cache.get::<String>("c").is_some());
+
+        let stats = cache.stats();
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.memory_bytes, 10);
+    }
+
+    #[test]
+    fn memory_skips_oversized_responses() {
+        let cache = memory_cache(4);
+        cache.insert("a".to_string(), &"aaa");
+        assert!(cache.get::<String>("a").is_none());
+        assert_eq!(cache.stats().memory_bytes, 0);
+    }
+
+    #[test]
+    fn disk_survives_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let cache = disk_cache(dir.path(), 1 << 20);
+        cache.insert("a".to_string(), &"alpha");
+        assert_eq!(cache.stats().disk_bytes, "\"alpha\"".len() as u64);
+        drop(cache);
+
+        let cache = disk_cache(dir.path(), 1 << 20);
+        assert_eq!(cache.stats().disk_bytes, "\"alpha\"".len() as u64);
+        assert_eq!(cache.get::<String>("a").as_deref(), Some("alpha"));
+        // The response is promoted to memory once it has been read from disk.
+        assert_eq!(cache.get::<String>("a").as_deref(), Some("alpha"));
+
+        let stats = cache.stats();
+        assert_eq!(stats.disk_hits, 1);
+        assert_eq!(stats.hits, 1);
+
+        // Only the persisted entry is left in the chain's directory.
+        let entries = fs::read_dir(dir.path().join(CHAIN)).unwrap().count();
+        assert_eq!(entries, 1);
+    }
+
+    #[test]
+    fn disk_is_namespaced_by_chain() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let cache = disk_cache(dir.path(), 1 << 20);
+        cache.insert("a".to_string(), &"alpha");
+        drop(cache);
+
+        let other = ResponseCache::new(
+            CacheConfig {
+                disk_path: Some(dir.path().to_path_buf()),
+                ..Default::default()
+            },
+            "35834a8a",
+        )
+        .unwrap();
+        assert!(other.get::<String>("a").is_none());
+        assert_eq!(other.stats().disk_bytes, 0);
+    }
+
+    #[test]
+    fn disk_respects_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let cache = disk_cache(dir.path(), 8);
+        cache.insert("a".to_string(), &"aaa");
+        cache.insert("b".to_string(), &"bbb");
+        drop(cache);
+
+        // Only the first response fit on disk.
+        let cache = disk_cache(dir.path(), 8);
+        assert!(cache.get::<String>("a").is_some());
+        assert!(cache.get::<String>("b").is_none());
+        assert_eq!(cache.stats().disk_bytes, 5);
+    }
+}
@@ -0,0 +1,701 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A JSON-RPC transport that spreads requests over several full node endpoints, used by
+//! [crate::SuiClientBuilder::build_with_endpoints].
+//!
+//! Requests go to the available endpoint with the lowest expected latency, taking into account
+//! the requests already in flight to it. Idempotent requests (reads, and transaction execution,
+//! which full nodes deduplicate by digest) are retried on another endpoint if they fail because of
+//! the transport. Endpoints that keep failing are taken out of rotation for a while (their circuit
+//! is opened), and all endpoints are probed in the background to keep their health and latency up
+//! to date.
+
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use jsonrpsee::core::client::{BatchResponse, ClientT};
+use jsonrpsee::core::params::BatchRequestBuilder;
+use jsonrpsee::core::traits::ToRpcParams;
+use jsonrpsee::core::Error;
+use jsonrpsee::http_client::HttpClient;
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+use serde_json::Value;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+/// Method used to execute transactions, which is safe to retry because full nodes deduplicate
+/// transactions by digest.
+const EXECUTE_TRANSACTION_METHOD: &str = "sui_executeTransactionBlock";
+
+/// Prefixes of methods that only read state, and are safe to retry.
+const READ_METHOD_PREFIXES: &[&str] = &[
+    "rpc.discover",
+    "sui_devInspect",
+    "sui_dryRun",
+    "sui_get",
+    "sui_multiGet",
+    "sui_tryGet",
+    "suix_get",
+    "suix_query",
+    "suix_resolve",
+];
+
+/// Method used to probe the health and latency of endpoints.
+const HEALTH_CHECK_METHOD: &str = "sui_getLatestCheckpointSequenceNumber";
+
+/// Weight given to the latest latency sample, in the moving average of an endpoint's latency.
+const LATENCY_SMOOTHING: f64 = 0.2;
+
+/// Configures how a client built with several endpoints fails over between them.
+#[derive(Clone, Debug)]
+pub struct FailoverConfig {
+    /// Number of times an idempotent request is retried after a transport error.
+    pub max_retries: usize,
+    /// Delay before the first retry, doubled for every subsequent retry.
+    pub retry_backoff: Duration,
+    /// Interval between background health probes of every endpoint.
+    pub health_check_interval: Duration,
+    /// Number of consecutive failures after which an endpoint's circuit is opened.
+    pub failure_threshold: usize,
+    /// How long an endpoint is taken out of rotation for, once its circuit is opened. Afterwards,
+    /// requests are sent to it again, and a single failure re-opens the circuit.
+    pub circuit_open_duration: Duration,
+}
+
+/// Transport for the JSON-RPC API of a [crate::SuiClient].
+#[derive(Debug)]
+pub(crate) enum HttpTransport {
+    Single(HttpClient),
+    Failover(FailoverClient),
+    /// Responds to requests with fixed values, to test the APIs built on top of the transport.
+    #[cfg(test)]
+    Mock(tests::MockClient),
+}
+
+/// Transport for a client connected to several endpoints.
+pub(crate) struct FailoverClient<C = HttpClient> {
+    endpoints: Arc<Vec<Endpoint<C>>>,
+    config: FailoverConfig,
+    health_check: JoinHandle<()>,
+}
+
+struct Endpoint<C> {
+    url: String,
+    client: C,
+    in_flight: AtomicUsize,
+    health: Mutex<Health>,
+}
+
+#[derive(Default)]
+struct Health {
+    /// Moving average of the latency of successful requests.
+    latency: Option<Duration>,
+    consecutive_failures: usize,
+    /// The endpoint is out of rotation until this time.
+    open_until: Option<Instant>,
+}
+
+/// Request parameters that have already been serialized, so they can be sent more than once.
+struct RawParams(Option<Box<RawValue>>);
+
+/// Counts a request as in flight to an endpoint until it is dropped, including when the request's
+/// future is dropped before it completes.
+struct InFlightGuard<'a>(&'a AtomicUsize);
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            retry_backoff: Duration::from_millis(100),
+            health_check_interval: Duration::from_secs(10),
+            failure_threshold: 5,
+            circuit_open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+impl<C: ClientT + Send + Sync + 'static> FailoverClient<C> {
+    pub(crate) fn new(clients: Vec<(String, C)>, config: FailoverConfig) -> Self {
+        let endpoints: Arc<Vec<_>> = Arc::new(
+            clients
+                .into_iter()
+                .map(|(url, client)| Endpoint {
+                    url,
+                    client,
+                    in_flight: AtomicUsize::new(0),
+                    health: Mutex::new(Health::default()),
+                })
+                .collect(),
+        );
+
+        let health_check = tokio::spawn(health_check(Arc::downgrade(&endpoints), config.clone()));
+
+        Self {
+            endpoints,
+            config,
+            health_check,
+        }
+    }
+
+    /// The client for the first endpoint.
+    pub(crate) fn primary(&self) -> &C {
+        &self.endpoints[0].client
+    }
+
+    /// Pick the endpoint to send the next attempt at a request to, preferring endpoints that
+    /// have not been `tried` for this request yet. Returns `None` if every endpoint's circuit is
+    /// open.
+    fn select(&self, tried: &[usize]) -> Option<usize> {
+        let now = Instant::now();
+        let available: Vec<_> = (0..self.endpoints.len())
+            .filter(|i| self.endpoints[*i].is_available(now))
+            .collect();
+
+        let untried: Vec<_> = available
+            .iter()
+            .copied()
+            .filter(|i| !tried.contains(i))
+            .collect();
+
+        let candidates = if untried.is_empty() {
+            available
+        } else {
+            untried
+        };
+
+        candidates
+            .into_iter()
+            .min_by_key(|i| self.endpoints[*i].score())
+    }
+}
+
+impl<'a> InFlightGuard<'a> {
+    fn new(in_flight: &'a AtomicUsize) -> Self {
+        in_flight.fetch_add(1, Ordering::Relaxed);
+        Self(in_flight)
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl<C: ClientT + Send + Sync> Endpoint<C> {
+    fn is_available(&self, now: Instant) -> bool {
+        let health = self.health.lock().unwrap();
+        health.open_until.map_or(true, |until| until <= now)
+    }
+
+    /// Expected cost of sending a request to this endpoint: its latency, scaled by the number of
+    /// requests in flight to it. Endpoints with no latency samples are tried first.
+    fn score(&self) -> u128 {
+        let latency = self.health.lock().unwrap().latency.unwrap_or_default();
+        latency.as_micros() * (self.in_flight.load(Ordering::Relaxed) as u128 + 1)
+    }
+
+    fn record_success(&self, latency: Duration) {
+        let mut health = self.health.lock().unwrap();
+        health.latency = Some(match health.latency {
+            None => latency,
+            Some(average) => {
+                average.mul_f64(1.0 - LATENCY_SMOOTHING) + latency.mul_f64(LATENCY_SMOOTHING)
+            }
+        });
+        health.consecutive_failures = 0;
+        health.open_until = None;
+    }
+
+    fn record_failure(&self, config: &FailoverConfig) {
+        let mut health = self.health.lock().unwrap();
+        health.consecutive_failures += 1;
+        if health.consecutive_failures >= config.failure_threshold {
+            if health.open_until.is_none() {
+                warn!("Taking endpoint {} out of rotation", self.url);
+            }
+            health.open_until = Some(Instant::now() + config.circuit_open_duration);
+        }
+    }
+
+    async fn request<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: &Option<Box<RawValue>>,
+    ) -> Result<R, Error> {
+        let in_flight = InFlightGuard::new(&self.in_flight);
+        let start = Instant::now();
+        let result = self.client.request(method, RawParams(params.clone())).await;
+        drop(in_flight);
+
+        match &result {
+            Err(e) if is_transport_error(e) => {}
+            // The endpoint responded, even if the response is an error.
+            _ => self.record_success(start.elapsed()),
+        }
+
+        result
+    }
+}
+
+#[async_trait]
+impl<C: ClientT + Send + Sync + 'static> ClientT for FailoverClient<C> {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        let Some(i) = self.select(&[]) else {
+            return Err(all_unavailable());
+        };
+        self.endpoints[i].client.notification(method, params).await
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let params = params.to_rpc_params()?;
+        let retries = if is_idempotent(method) {
+            self.config.max_retries
+        } else {
+            0
+        };
+
+        let mut tried = vec![];
+        let mut backoff = self.config.retry_backoff;
+        loop {
+            let Some(i) = self.select(&tried) else {
+                return Err(all_unavailable());
+            };
+
+            let endpoint = &self.endpoints[i];
+            let err = match endpoint.request(method, &params).await {
+                Err(e) if is_transport_error(&e) => e,
+                result => return result,
+            };
+
+            endpoint.record_failure(&self.config);
+            if tried.len() >= retries {
+                return Err(err);
+            }
+
+            debug!("Retrying {method} after error from {}: {err}", endpoint.url);
+            tried.push(i);
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + Debug + 'a,
+    {
+        let Some(i) = self.select(&[]) else {
+            return Err(all_unavailable());
+        };
+        self.endpoints[i].client.batch_request(batch).await
+    }
+}
+
+impl HttpTransport {
+    /// The underlying client, or the client for the first endpoint, if there are several.
+    pub(crate) fn client(&self) -> &HttpClient {
+        match self {
+            HttpTransport::Single(client) => client,
+            HttpTransport::Failover(failover) => failover.primary(),
+            #[cfg(test)]
+            HttpTransport::Mock(_) => unimplemented!("the mock transport has no HTTP client"),
+        }
+    }
+
+    /// Whether requests fail over between several endpoints.
+    pub(crate) fn is_failover(&self) -> bool {
+        matches!(self, HttpTransport::Failover(_))
+    }
+}
+
+#[async_trait]
+impl ClientT for HttpTransport {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        match self {
+            HttpTransport::Single(client) => client.notification(method, params).await,
+            HttpTransport::Failover(failover) => failover.notification(method, params).await,
+            #[cfg(test)]
+            HttpTransport::Mock(mock) => mock.notification(method, params).await,
+        }
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        match self {
+            HttpTransport::Single(client) => client.request(method, params).await,
+            HttpTransport::Failover(failover) => failover.request(method, params).await,
+            #[cfg(test)]
+            HttpTransport::Mock(mock) => mock.request(method, params).await,
+        }
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + Debug + 'a,
+    {
+        match self {
+            HttpTransport::Single(client) => client.batch_request(batch).await,
+            HttpTransport::Failover(failover) => failover.batch_request(batch).await,
+            #[cfg(test)]
+            HttpTransport::Mock(mock) => mock.batch_request(batch).await,
+        }
+    }
+}
+
+impl ToRpcParams for RawParams {
+    fn to_rpc_params(self) -> Result<Option<Box<RawValue>>, serde_json::Error> {
+        Ok(self.0)
+    }
+}
+
+impl<C> Drop for FailoverClient<C> {
+    fn drop(&mut self) {
+        self.health_check.abort();
+    }
+}
+
+impl<C> Debug for FailoverClient<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.endpoints.iter().map(|e| &e.url))
+            .finish()
+    }
+}
+
+/// Whether `err` is caused by failing to reach the endpoint, rather than the endpoint
+/// responding with an error.
+pub(crate) fn is_transport_error(err: &Error) -> bool {
+    matches!(
+        err,
+        Error::Transport(_) | Error::RequestTimeout | Error::RestartNeeded(_)
+    )
+}
+
+fn is_idempotent(method: &str) -> bool {
+    method == EXECUTE_TRANSACTION_METHOD
+        || READ_METHOD_PREFIXES
+            .iter()
+            .any(|prefix| method.starts_with(prefix))
+}
+
+fn all_unavailable() -> Error {
+    Error::Custom("All endpoints are unavailable".to_string())
+}
+
+/// Periodically probe every endpoint, until the client is dropped.
+async fn health_check<C: ClientT + Send + Sync>(
+    endpoints: Weak<Vec<Endpoint<C>>>,
+    config: FailoverConfig,
+) {
+    let mut interval = tokio::time::interval(config.health_check_interval);
+    loop {
+        interval.tick().await;
+        let Some(endpoints) = endpoints.upgrade() else {
+            return;
+        };
+
+        futures::future::join_all(endpoints.iter().map(|endpoint| async {
+            let probe = endpoint.request::<Value>(HEALTH_CHECK_METHOD, &None).await;
+            if let Err(e) = probe {
+                if is_transport_error(&e) {
+                    endpoint.record_failure(&config);
+                }
+            }
+        }))
+        .await;
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::HashMap;
+
+    use jsonrpsee::rpc_params;
+
+    use super::*;
+
+    /// A client that responds to some methods with fixed values, and fails to reach its endpoint
+    /// for every other method.
+    #[derive(Debug, Default)]
+    pub(crate) struct MockClient {
+        responses: Mutex<HashMap<String, Value>>,
+        stalled: Mutex<Vec<String>>,
+        requests: Mutex<Vec<String>>,
+    }
+
+    impl MockClient {
+        pub(crate) fn respond(self, method: &str, response: Value) -> Self {
+            self.responses
+                .lock()
+                .unwrap()
+                .insert(method.to_string(), response);
+            self
+        }
+
+        /// Never respond to requests for `method`.
+        pub(crate) fn stall(self, method: &str) -> Self {
+            self.stalled.lock().unwrap().push(method.to_string());
+            self
+        }
+
+        /// Methods requested from this client, excluding health checks.
+        pub(crate) fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl ClientT for MockClient {
+        async fn notification<Params>(&self, _method: &str, _params: Params) -> Result<(), Error>
+        where
+            Params: ToRpcParams + Send,
+        {
+            Ok(())
+        }
+
+        async fn request<R, Params>(&self, method: &str, _params: Params) -> Result<R, Error>
+        where
+            R: DeserializeOwned,
+            Params: ToRpcParams + Send,
+        {
+            if method != HEALTH_CHECK_METHOD {
+                self.requests.lock().unwrap().push(method.to_string());
+            }
+
+            let stalled = self.stalled.lock().unwrap().iter().any(|m| m == method);
+            if stalled {
+                futures::future::pending::<()>().await;
+            }
+
+            let response = self.responses.lock().unwrap().get(method).cloned();
+            match response {
+                Some(response) => serde_json::from_value(response).map_err(Error::ParseError),
+                None => Err(Error::Transport(anyhow::anyhow!("{method} is unreachable"))),
+            }
+        }
+
+        async fn batch_request<'a, R>(
+            &self,
+            _batch: BatchRequestBuilder<'a>,
+        ) -> Result<BatchResponse<'a, R>, Error>
+        where
+            R: DeserializeOwned + Debug + 'a,
+        {
+            unimplemented!()
+        }
+    }
+
+    const READ_METHOD: &str = "sui_getObject";
+    const WRITE_METHOD: &str = "unsafe_moveCall";
+
+    /// A failover client over `clients`, without background health checks.
+    fn failover(clients: Vec<MockClient>, config: FailoverConfig) -> FailoverClient<MockClient> {
+        FailoverClient {
+            endpoints: Arc::new(
+                clients
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, client)| Endpoint {
+                        url: format!("endpoint-{i}"),
+                        client,
+                        in_flight: AtomicUsize::new(0),
+                        health: Mutex::new(Health::default()),
+                    })
+                    .collect(),
+            ),
+            config,
+            health_check: tokio::spawn(async {}),
+        }
+    }
+
+    fn config() -> FailoverConfig {
+        FailoverConfig {
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(1),
+            health_check_interval: Duration::from_secs(60),
+            failure_threshold: 2,
+            circuit_open_duration: Duration::from_secs(60),
+        }
+    }
+
+    #[tokio::test]
+    async fn selects_lowest_expected_latency() {
+        let client = failover(vec![MockClient::default(), MockClient::default()], config());
+
+        // Endpoints without latency samples are tried first.
+        client.endpoints[0].record_success(Duration::from_millis(100));
+        assert_eq!(client.select(&[]), Some(1));
+
+        client.endpoints[1].record_success(Duration::from_millis(10));
+        assert_eq!(client.select(&[]), Some(1));
+
+        // Endpoints that have not been tried for a request are preferred.
+        assert_eq!(client.select(&[1]), Some(0));
+
+        // Latency is scaled by the requests in flight.
+        client.endpoints[1].in_flight.store(20, Ordering::Relaxed);
+        assert_eq!(client.select(&[]), Some(0));
+    }
+
+    #[tokio::test]
+    async fn dropped_requests_are_not_in_flight() {
+        let client = failover(vec![MockClient::default().stall(READ_METHOD)], config());
+
+        // The request times out while it is in flight, and is dropped.
+        let request = client.request::<u64, _>(READ_METHOD, rpc_params![]);
+        assert!(tokio::time::timeout(Duration::from_millis(10), request)
+            .await
+            .is_err());
+        assert_eq!(client.endpoints[0].client.requests(), vec![READ_METHOD]);
+        assert_eq!(client.endpoints[0].in_flight.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn retries_reads_on_another_endpoint() {
+        let client = failover(
+            vec![
+                MockClient::default(),
+                MockClient::default().respond(READ_METHOD, Value::from(42)),
+            ],
+            config(),
+        );
+
+        let response: u64 = client.request(READ_METHOD, rpc_params![]).await.unwrap();
+        assert_eq!(response, 42);
+        assert_eq!(client.endpoints[0].client.requests(), vec![READ_METHOD]);
+        assert_eq!(client.endpoints[1].client.requests(), vec![READ_METHOD]);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_writes() {
+        let client = failover(
+            vec![
+                MockClient::default(),
+                MockClient::default().respond(WRITE_METHOD, Value::from(42)),
+            ],
+            config(),
+        );
+
+        let err = client
+            .request::<u64, _>(WRITE_METHOD, rpc_params![])
+            .await
+            .unwrap_err();
+        assert!(is_transport_error(&err), "{err}");
+        assert!(client.endpoints[1].client.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let client = failover(
+            vec![MockClient::default(), MockClient::default()],
+            FailoverConfig {
+                failure_threshold: 10,
+                ..config()
+            },
+        );
+
+        let err = client
+            .request::<u64, _>(READ_METHOD, rpc_params![])
+            .await
+            .unwrap_err();
+        assert!(is_transport_error(&err), "{err}");
+
+        // One attempt, and two retries, which go back to the first endpoint once every endpoint
+        // has been tried.
+        assert_eq!(client.endpoints[0].client.requests().len(), 2);
+        assert_eq!(client.endpoints[1].client.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn opens_circuit_after_consecutive_failures() {
+        let client = failover(
+            vec![
+                MockClient::default(),
+                MockClient::default().respond(WRITE_METHOD, Value::from(42)),
+            ],
+            config(),
+        );
+
+        // Writes are not retried, so each failure is reported, until the first endpoint's circuit
+        // opens, after `failure_threshold` failures.
+        for _ in 0..2 {
+            assert!(client
+                .request::<u64, _>(WRITE_METHOD, rpc_params![])
+                .await
+                .is_err());
+        }
+        assert!(!client.endpoints[0].is_available(Instant::now()));
+
+        let response: u64 = client.request(WRITE_METHOD, rpc_params![]).await.unwrap();
+        assert_eq!(response, 42);
+        assert_eq!(client.endpoints[0].client.requests().len(), 2);
+
+        // The circuit closes again once it has been open for `circuit_open_duration`.
+        let later = Instant::now() + config().circuit_open_duration;
+        assert!(client.endpoints[0].is_available(later));
+
+        // A response, even an error, closes the circuit.
+        client.endpoints[0].record_success(Duration::from_millis(1));
+        assert!(client.endpoints[0].is_available(Instant::now()));
+    }
+
+    #[tokio::test]
+    async fn fails_when_every_circuit_is_open() {
+        let client = failover(vec![MockClient::default()], config());
+        for _ in 0..2 {
+            client.endpoints[0].record_failure(&client.config);
+        }
+
+        let err = client
+            .request::<u64, _>(READ_METHOD, rpc_params![])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("unavailable"), "{err}");
+        assert!(client.endpoints[0].client.requests().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn health_check_opens_circuit_of_unreachable_endpoints() {
+        let client = FailoverClient::new(
+            vec![
+                ("unreachable".to_string(), MockClient::default()),
+                (
+                    "reachable".to_string(),
+                    MockClient::default().respond(HEALTH_CHECK_METHOD, Value::from(1)),
+                ),
+            ],
+            config(),
+        );
+
+        // Let the health check probe the endpoints `failure_threshold` times.
+        tokio::time::sleep(config().health_check_interval * 2).await;
+
+        assert!(!client.endpoints[0].is_available(Instant::now()));
+        assert!(client.endpoints[1].is_available(Instant::now()));
+        assert!(client.endpoints[1].health.lock().unwrap().latency.is_some());
+    }
+}
@@ -90,18 +90,22 @@ use sui_json_rpc_api::{
 };
 pub use sui_json_rpc_types as rpc_types;
 use sui_json_rpc_types::{
-    ObjectsPage, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
-    SuiObjectResponseQuery,
+    ObjectsPage, ProtocolConfigResponse, SuiObjectDataFilter, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery,
 };
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
 
 use crate::apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriverApi, ReadApi};
+use crate::cache::{CacheConfig, CacheStats, ResponseCache};
 use crate::error::{Error, SuiRpcResult};
+use crate::failover::{FailoverClient, FailoverConfig, HttpTransport};
 
 pub mod apis;
+pub mod cache;
 pub mod error;
+pub mod failover;
 pub mod json_rpc_error;
 pub mod sui_client_config;
 pub mod wallet_context;
@@ -142,6 +146,8 @@ pub struct SuiClientBuilder {
     ws_url: Option<String>,
     ws_ping_interval: Option<Duration>,
     basic_auth: Option<(String, String)>,
+    failover: FailoverConfig,
+    cache: Option<CacheConfig>,
 }
 
 impl Default for SuiClientBuilder {
@@ -152,6 +158,8 @@ impl Default for SuiClientBuilder {
             ws_url: None,
             ws_ping_interval: None,
             basic_auth: None,
+            failover: FailoverConfig::default(),
+            cache: None,
         }
     }
 }
@@ -187,6 +195,20 @@ impl SuiClientBuilder {
         self
     }
 
+    /// Set how clients built with [Self::build_with_endpoints] fail over between endpoints
+    pub fn failover(mut self, config: FailoverConfig) -> Self {
+        self.failover = config;
+        self
+    }
+
+    /// Cache responses that never change (checkpoints, finalized transactions, packages, and
+    /// specific versions of objects), instead of fetching them again on every call to the
+    /// [ReadApi]. See [CacheConfig] for how the cache is configured.
+    pub fn cache(mut self, config: CacheConfig) -> Self {
+        self.cache = Some(config);
+        self
+    }
+
     /// Returns a [SuiClient] object connected to the Sui network running at the URI provided.
     ///
     /// # Examples
@@ -205,6 +227,58 @@ impl SuiClientBuilder {
     /// }
     /// ```
     pub async fn build(self, http: impl AsRef<str>) -> SuiRpcResult<SuiClient> {
+        let headers = self.headers();
+        let http = self.http_client(http.as_ref(), &headers)?;
+        self.build_with_transport(HttpTransport::Single(http), headers)
+            .await
+    }
+
+    /// Returns a [SuiClient] object that spreads its requests over several full nodes, all
+    /// connected to the same Sui network.
+    ///
+    /// Requests go to the healthy endpoint with the lowest expected latency, and reads and
+    /// transaction executions are retried on another endpoint if they fail to reach the one they
+    /// were sent to. See [FailoverConfig] for how failover is configured. Subscriptions still go
+    /// through the WebSocket URL, if one is set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use sui_sdk::SuiClientBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let sui = SuiClientBuilder::default()
+    ///         .build_with_endpoints(["http://127.0.0.1:9000", "http://127.0.0.1:9001"])
+    ///         .await?;
+    ///
+    ///     println!("Sui local version: {:?}", sui.api_version());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn build_with_endpoints(
+        self,
+        endpoints: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> SuiRpcResult<SuiClient> {
+        let headers = self.headers();
+        let clients = endpoints
+            .into_iter()
+            .map(|url| {
+                let url = url.as_ref();
+                Ok((url.to_string(), self.http_client(url, &headers)?))
+            })
+            .collect::<SuiRpcResult<Vec<_>>>()?;
+
+        if clients.is_empty() {
+            return Err(Error::DataError("At least one endpoint is required".into()));
+        }
+
+        let failover = FailoverClient::new(clients, self.failover.clone());
+        self.build_with_transport(HttpTransport::Failover(failover), headers)
+            .await
+    }
+
+    fn headers(&self) -> HeaderMap {
         let client_version = env!("CARGO_PKG_VERSION");
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -218,7 +292,7 @@ impl SuiClientBuilder {
         );
         headers.insert(CLIENT_SDK_TYPE_HEADER, HeaderValue::from_static("rust"));
 
-        if let Some((username, password)) = self.basic_auth {
+        if let Some((username, password)) = &self.basic_auth {
             let auth = base64::engine::general_purpose::STANDARD
                 .encode(format!("{}:{}", username, password));
             headers.insert(
@@ -228,6 +302,23 @@ impl SuiClientBuilder {
             );
         }
 
+        headers
+    }
+
+    fn http_client(&self, url: &str, headers: &HeaderMap) -> SuiRpcResult<HttpClient> {
+        Ok(HttpClientBuilder::default()
+            .max_request_body_size(2 << 30)
+            .max_concurrent_requests(self.max_concurrent_requests)
+            .set_headers(headers.clone())
+            .request_timeout(self.request_timeout)
+            .build(url)?)
+    }
+
+    async fn build_with_transport(
+        self,
+        http: HttpTransport,
+        headers: HeaderMap,
+    ) -> SuiRpcResult<SuiClient> {
         let ws = if let Some(url) = self.ws_url {
             let mut builder = WsClientBuilder::default()
                 .max_request_body_size(2 << 30)
@@ -244,18 +335,26 @@ impl SuiClientBuilder {
             None
         };
 
-        let http = HttpClientBuilder::default()
-            .max_request_body_size(2 << 30)
-            .max_concurrent_requests(self.max_concurrent_requests)
-            .set_headers(headers.clone())
-            .request_timeout(self.request_timeout)
-            .build(http)?;
-
         let info = Self::get_server_info(&http, &ws).await?;
 
+        let cache = match self.cache {
+            Some(config) => {
+                // Responses are namespaced by chain, so that a cache directory shared by clients
+                // of different networks never serves responses from the wrong one.
+                let chain_id: String = http
+                    .request("sui_getChainIdentifier", rpc_params![])
+                    .await?;
+                let cache = ResponseCache::new(config, &chain_id).map_err(|e| {
+                    Error::DataError(format!("Failed to open the response cache: {e}"))
+                })?;
+                Some(Arc::new(cache))
+            }
+            None => None,
+        };
+
         let rpc = RpcClient { http, ws, info };
         let api = Arc::new(rpc);
-        let read_api = Arc::new(ReadApi::new(api.clone()));
+        let read_api = Arc::new(ReadApi::new(api.clone(), cache));
         let quorum_driver_api = QuorumDriverApi::new(api.clone());
         let event_api = EventApi::new(api.clone());
         let transaction_builder = TransactionBuilder::new(read_api.clone());
@@ -348,7 +447,7 @@ impl SuiClientBuilder {
     ///
     /// Fails with an error if it cannot call the RPC discover.
     async fn get_server_info(
-        http: &HttpClient,
+        http: &HttpTransport,
         ws: &Option<WsClient>,
     ) -> Result<ServerInfo, Error> {
         let rpc_spec: Value = http.request("rpc.discover", rpc_params![]).await?;
@@ -439,7 +538,7 @@ pub struct SuiClient {
 }
 
 pub(crate) struct RpcClient {
-    http: HttpClient,
+    http: HttpTransport,
     ws: Option<WsClient>,
     info: ServerInfo,
 }
@@ -518,14 +617,21 @@ impl SuiClient {
         &self.read_api
     }
 
+    /// Returns statistics about the use of the response cache, if the client was built with one.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.read_api.cache_stats()
+    }
+
     /// Returns a reference to the transaction builder API.
     pub fn transaction_builder(&self) -> &TransactionBuilder {
         &self.transaction_builder
     }
 
-    /// Returns a reference to the underlying http client.
+    /// Returns a reference to the underlying http client. For clients built with several
+    /// endpoints, this is the client for the first endpoint, and requests made with it do not
+    /// fail over.
     pub fn http(&self) -> &HttpClient {
-        &self.api.http
+        self.api.http.client()
     }
 
     /// Returns a reference to the underlying WebSocket client, if any.
@@ -582,8 +688,24 @@ impl DataReader for ReadApi {
         Ok(self.get_object_with_options(object_id, options).await?)
     }
 
+    async fn multi_get_object_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>, anyhow::Error> {
+        Ok(self
+            .multi_get_object_with_options(object_ids, options)
+            .await?)
+    }
+
     /// Returns the reference gas price as a u64 or an error otherwise
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
         Ok(self.get_reference_gas_price().await?)
     }
+
+    /// Returns the protocol config of the current epoch, as reported by the node, so that it is
+    /// available even for protocol versions that are newer than this SDK
+    async fn get_protocol_config(&self) -> Result<ProtocolConfigResponse, anyhow::Error> {
+        Ok(self.get_protocol_config(None).await?)
+    }
 }
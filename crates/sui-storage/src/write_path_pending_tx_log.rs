@@ -5,23 +5,62 @@
 //! TransactionOrchestrator) for transaction submission processing. It helps to achieve:
 //! 1. At one time, a transaction is only processed once.
 //! 2. When Fullnode crashes and restarts, the pending transaction will be loaded and retried.
+//! 3. Clients can track the submission status of a transaction submitted through the durable
+//!    submission queue, until some time after it finishes.
 
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use sui_types::base_types::TransactionDigest;
 use sui_types::crypto::EmptySignInfo;
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::message_envelope::TrustedEnvelope;
+use sui_types::quorum_driver_types::SubmissionStatus;
 use sui_types::transaction::{SenderSignedData, VerifiedTransaction};
-use typed_store::rocks::MetricConf;
+use typed_store::rocks::{DBTransaction, MetricConf};
 use typed_store::traits::{TableSummary, TypedStoreDebug};
 use typed_store::DBMapUtils;
 use typed_store::{rocks::DBMap, traits::Map};
 
 pub type IsFirstRecord = bool;
 
+// How many statuses are deleted in one write batch when pruning.
+const PRUNE_BATCH_SIZE: usize = 1000;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct SubmissionStatusRecord {
+    status: SubmissionStatus,
+    updated_at_ms: u64,
+}
+
+impl SubmissionStatusRecord {
+    fn new(status: SubmissionStatus) -> Self {
+        Self {
+            status,
+            updated_at_ms: now_ms(),
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
 #[derive(DBMapUtils)]
 struct WritePathPendingTransactionTable {
     logs: DBMap<TransactionDigest, TrustedEnvelope<SenderSignedData, EmptySignInfo>>,
+    // Transactions in `logs` which were submitted through the durable submission queue. Only
+    // these are retried in the next epoch when they fail with transient errors.
+    queued: DBMap<TransactionDigest, ()>,
+    // Statuses of queued transactions, which outlive the transactions in `logs` until they are
+    // pruned.
+    statuses: DBMap<TransactionDigest, SubmissionStatusRecord>,
+    // `statuses` ordered by the time of their last update, so that they can be pruned without
+    // scanning all of them.
+    statuses_by_time: DBMap<(u64, TransactionDigest), ()>,
 }
 
 pub struct WritePathPendingTransactionLog {
@@ -50,20 +89,64 @@ impl WritePathPendingTransactionLog {
         &self,
         tx: &VerifiedTransaction,
     ) -> SuiResult<IsFirstRecord> {
+        self.write_transaction_maybe(tx, false)
+    }
+
+    // Same as `write_pending_transaction_maybe`, but also marks the transaction as submitted
+    // through the durable submission queue and starts tracking its status, even if it was already
+    // in record.
+    pub async fn write_queued_transaction_maybe(
+        &self,
+        tx: &VerifiedTransaction,
+    ) -> SuiResult<IsFirstRecord> {
+        self.write_transaction_maybe(tx, true)
+    }
+
+    fn write_transaction_maybe(
+        &self,
+        tx: &VerifiedTransaction,
+        queued: bool,
+    ) -> SuiResult<IsFirstRecord> {
+        let tables = &self.pending_transactions;
         let tx_digest = tx.digest();
-        let mut transaction = self.pending_transactions.logs.transaction()?;
-        if transaction
-            .get(&self.pending_transactions.logs, tx_digest)?
-            .is_some()
-        {
+        let mut transaction = tables.logs.transaction()?;
+        let is_first_record = transaction.get(&tables.logs, tx_digest)?.is_none();
+        let is_newly_queued = queued && transaction.get(&tables.queued, tx_digest)?.is_none();
+        if !is_first_record && !is_newly_queued {
             return Ok(false);
         }
+        if is_first_record {
+            transaction.insert_batch(&tables.logs, [(tx_digest, tx.serializable_ref())])?;
+        }
+        if is_newly_queued {
+            transaction.insert_batch(&tables.queued, [(tx_digest, ())])?;
+            let previous = transaction.get(&tables.statuses, tx_digest)?;
+            let record = SubmissionStatusRecord::new(SubmissionStatus::Queued { last_error: None });
+            Self::write_status(&mut transaction, tables, tx_digest, previous, record)?;
+        }
+        let result = transaction.commit();
+        Ok(is_first_record && result.is_ok())
+    }
+
+    fn write_status(
+        transaction: &mut DBTransaction<'_>,
+        tables: &WritePathPendingTransactionTable,
+        tx_digest: &TransactionDigest,
+        previous: Option<SubmissionStatusRecord>,
+        record: SubmissionStatusRecord,
+    ) -> SuiResult {
+        if let Some(previous) = previous {
+            transaction.delete_batch(
+                &tables.statuses_by_time,
+                [(previous.updated_at_ms, *tx_digest)],
+            )?;
+        }
         transaction.insert_batch(
-            &self.pending_transactions.logs,
-            [(tx_digest, tx.serializable_ref())],
+            &tables.statuses_by_time,
+            [((record.updated_at_ms, *tx_digest), ())],
         )?;
-        let result = transaction.commit();
-        Ok(result.is_ok())
+        transaction.insert_batch(&tables.statuses, [(tx_digest, record)])?;
+        Ok(())
     }
 
     // This function does not need to be behind a lock because:
@@ -79,9 +162,15 @@ impl WritePathPendingTransactionLog {
     pub fn finish_transaction(&self, tx: &TransactionDigest) -> SuiResult {
         let mut write_batch = self.pending_transactions.logs.batch();
         write_batch.delete_batch(&self.pending_transactions.logs, std::iter::once(tx))?;
+        write_batch.delete_batch(&self.pending_transactions.queued, std::iter::once(tx))?;
         write_batch.write().map_err(SuiError::from)
     }
 
+    /// Whether the pending transaction was submitted through the durable submission queue.
+    pub fn is_queued(&self, tx: &TransactionDigest) -> SuiResult<bool> {
+        Ok(self.pending_transactions.queued.contains_key(tx)?)
+    }
+
     pub fn load_all_pending_transactions(&self) -> Vec<VerifiedTransaction> {
         self.pending_transactions
             .logs
@@ -89,6 +178,65 @@ impl WritePathPendingTransactionLog {
             .map(|(_tx_digest, tx)| VerifiedTransaction::from(tx))
             .collect()
     }
+
+    /// Updates the status of the transaction, if it is queued. The statuses of transactions
+    /// which are not queued are not tracked.
+    pub fn update_status(&self, tx: &TransactionDigest, status: SubmissionStatus) -> SuiResult {
+        let tables = &self.pending_transactions;
+        let mut transaction = tables.queued.transaction()?;
+        if transaction.get_for_update(&tables.queued, tx)?.is_none() {
+            return Ok(());
+        }
+        let previous = transaction.get_for_update(&tables.statuses, tx)?;
+        let record = SubmissionStatusRecord::new(status);
+        Self::write_status(&mut transaction, tables, tx, previous, record)?;
+        transaction.commit().map_err(SuiError::from)
+    }
+
+    pub fn get_status(&self, tx: &TransactionDigest) -> SuiResult<Option<SubmissionStatus>> {
+        Ok(self
+            .pending_transactions
+            .statuses
+            .get(tx)?
+            .map(|record| record.status))
+    }
+
+    /// Deletes the statuses which were last updated more than `retention` ago, of transactions
+    /// which finished, or which are no longer pending without having finished, e.g. because they
+    /// were lost in a crash. Returns the number of deleted statuses.
+    pub fn prune_statuses(&self, retention: Duration) -> SuiResult<usize> {
+        let tables = &self.pending_transactions;
+        let cutoff_ms = now_ms().saturating_sub(retention.as_millis() as u64);
+        let mut pruned = 0;
+        let mut batch = tables.statuses.batch();
+        let mut batch_size = 0;
+        for entry in tables
+            .statuses_by_time
+            .safe_range_iter(..(cutoff_ms, TransactionDigest::ZERO))
+        {
+            let ((updated_at_ms, tx_digest), _) = entry?;
+            match tables.statuses.get(&tx_digest)? {
+                Some(record) if record.updated_at_ms == updated_at_ms => {
+                    // Pending transactions, e.g. waiting for the next epoch, are kept.
+                    if !record.status.is_terminal() && tables.logs.contains_key(&tx_digest)? {
+                        continue;
+                    }
+                    batch.delete_batch(&tables.statuses, [tx_digest])?;
+                    pruned += 1;
+                }
+                // The status was updated since, only the index entry is stale.
+                _ => {}
+            }
+            batch.delete_batch(&tables.statuses_by_time, [(updated_at_ms, tx_digest)])?;
+            batch_size += 1;
+            if batch_size == PRUNE_BATCH_SIZE {
+                std::mem::replace(&mut batch, tables.statuses.batch()).write()?;
+                batch_size = 0;
+            }
+        }
+        batch.write()?;
+        Ok(pruned)
+    }
 }
 
 #[cfg(test)]
@@ -96,6 +244,7 @@ mod tests {
     use super::*;
     use anyhow;
     use std::collections::HashSet;
+    use sui_types::digests::TransactionEffectsDigest;
     use sui_types::utils::create_fake_transaction;
 
     #[tokio::test]
@@ -117,6 +266,13 @@ mod tests {
         let loaded_txes = pending_txes.load_all_pending_transactions();
         assert_eq!(vec![tx], loaded_txes);
 
+        // The statuses of transactions which are not queued are not tracked.
+        assert!(!pending_txes.is_queued(&tx_digest).unwrap());
+        pending_txes
+            .update_status(&tx_digest, SubmissionStatus::Certified)
+            .unwrap();
+        assert_eq!(pending_txes.get_status(&tx_digest).unwrap(), None);
+
         pending_txes.finish_transaction(&tx_digest).unwrap();
         let loaded_txes = pending_txes.load_all_pending_transactions();
         assert!(loaded_txes.is_empty());
@@ -162,4 +318,81 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_pending_tx_log_statuses() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let pending_txes = WritePathPendingTransactionLog::new(temp_dir.path().to_path_buf());
+        let executed = VerifiedTransaction::new_unchecked(create_fake_transaction());
+        let submitted = VerifiedTransaction::new_unchecked(create_fake_transaction());
+        let lost = VerifiedTransaction::new_unchecked(create_fake_transaction());
+        for tx in [&executed, &lost] {
+            assert!(pending_txes.write_queued_transaction_maybe(tx).await?);
+            assert_eq!(
+                pending_txes.get_status(tx.digest())?,
+                Some(SubmissionStatus::Queued { last_error: None })
+            );
+        }
+        // A transaction which is already pending is queued when it is submitted again.
+        assert!(
+            pending_txes
+                .write_pending_transaction_maybe(&submitted)
+                .await?
+        );
+        assert!(!pending_txes.is_queued(submitted.digest())?);
+        assert!(
+            !pending_txes
+                .write_queued_transaction_maybe(&submitted)
+                .await?
+        );
+        assert!(pending_txes.is_queued(submitted.digest())?);
+        assert!(
+            !pending_txes
+                .write_queued_transaction_maybe(&submitted)
+                .await?
+        );
+
+        let executed_status = SubmissionStatus::Executed {
+            effects_digest: TransactionEffectsDigest::random(),
+        };
+        pending_txes.update_status(executed.digest(), executed_status.clone())?;
+        pending_txes.finish_transaction(executed.digest())?;
+        pending_txes.update_status(
+            submitted.digest(),
+            SubmissionStatus::Submitted { attempts: 1 },
+        )?;
+        // A transaction which is no longer pending, without having finished.
+        pending_txes.finish_transaction(lost.digest())?;
+        assert!(!pending_txes.is_queued(lost.digest())?);
+
+        // Statuses are kept after transactions finish.
+        assert_eq!(
+            pending_txes.get_status(executed.digest())?,
+            Some(executed_status)
+        );
+
+        // Only the statuses of transactions which are no longer pending are pruned.
+        assert_eq!(pending_txes.prune_statuses(Duration::from_secs(3600))?, 0);
+        std::thread::sleep(Duration::from_millis(2));
+        assert_eq!(pending_txes.prune_statuses(Duration::ZERO)?, 2);
+        assert_eq!(pending_txes.get_status(executed.digest())?, None);
+        assert_eq!(pending_txes.get_status(lost.digest())?, None);
+        assert_eq!(
+            pending_txes.get_status(submitted.digest())?,
+            Some(SubmissionStatus::Submitted { attempts: 1 })
+        );
+
+        // Pruned index entries are not visited again.
+        assert_eq!(
+            pending_txes
+                .pending_transactions
+                .statuses_by_time
+                .unbounded_iter()
+                .count(),
+            1
+        );
+        assert_eq!(pending_txes.prune_statuses(Duration::ZERO)?, 0);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,456 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail};
+use move_binary_format::file_format::Visibility;
+use move_binary_format::normalized::{Function, Module, Type};
+use move_core_types::language_storage::StructTag;
+use sui_move_build::BuildConfig;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::object::Object;
+use sui_types::transaction::CallArg;
+use sui_types::Identifier;
+
+/// Maximum number of calls kept in a [`CallLog`]. Violations can only be reproduced from the calls
+/// that are still in the log when they are found.
+const MAX_CALL_LOG_LEN: usize = 10_000;
+
+/// Maximum number of times the calls leading to a violation are replayed while shrinking them.
+/// Shrinking stops with the shortest sequence found so far once this is reached.
+pub(crate) const MAX_SHRINK_REPLAYS: usize = 100;
+
+/// Checks run against every object that a surfed transaction creates or modifies.
+#[derive(Clone)]
+pub enum Invariant {
+    /// A public function `module::function(&T): bool` in one of the surfed packages. It is called
+    /// (by dev-inspect) on every changed object of type `T`, and the invariant is violated if it
+    /// returns `false` or aborts.
+    MoveView {
+        module: Identifier,
+        function: Identifier,
+    },
+    /// A check over the state of every changed object, which returns an error describing why the
+    /// object violates the invariant.
+    Rust {
+        name: String,
+        check: Arc<dyn Fn(&Object) -> Result<(), String> + Send + Sync>,
+    },
+}
+
+/// An argument to a surfed call.
+#[derive(Clone, Debug)]
+pub enum SurfedArg {
+    Object(ObjectID),
+    Pure {
+        /// The value, in the syntax accepted by `sui client ptb`.
+        value: String,
+        /// The BCS bytes the call was made with, to replay it.
+        bytes: Vec<u8>,
+    },
+}
+
+/// A transaction executed by the surfer.
+#[derive(Clone, Debug)]
+pub struct SurfedCall {
+    pub sender: SuiAddress,
+    pub package: ObjectID,
+    pub module: String,
+    pub function: String,
+    pub args: Vec<SurfedArg>,
+    /// Objects the call took as input.
+    pub inputs: Vec<ObjectID>,
+    /// Objects the call created, modified, or deleted.
+    pub outputs: Vec<ObjectID>,
+    /// Objects the call created, along with their types.
+    pub created: Vec<(ObjectID, StructTag)>,
+}
+
+/// The most recent calls made by all surfers, in the order they were executed.
+#[derive(Default)]
+pub struct CallLog {
+    calls: VecDeque<SurfedCall>,
+    /// Number of calls that have been dropped from the front of the log.
+    dropped: usize,
+}
+
+/// An invariant that did not hold after a surfed transaction, along with the sequence of calls
+/// that led to it.
+#[derive(Clone, Debug)]
+pub struct InvariantViolation {
+    pub invariant: String,
+    pub object: ObjectID,
+    pub object_type: StructTag,
+    pub error: String,
+    /// The calls that the violation depends on, ending with the call after which the invariant
+    /// was checked. Once surfing stops, these are shrunk to a subsequence that still violates the
+    /// invariant when replayed.
+    pub calls: Vec<SurfedCall>,
+}
+
+impl Invariant {
+    pub fn move_view(module: &str, function: &str) -> anyhow::Result<Self> {
+        Ok(Self::MoveView {
+            module: Identifier::new(module)
+                .map_err(|_| anyhow!("Invalid module name '{module}'"))?,
+            function: Identifier::new(function)
+                .map_err(|_| anyhow!("Invalid function name '{function}'"))?,
+        })
+    }
+
+    pub fn rust(
+        name: impl Into<String>,
+        check: impl Fn(&Object) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        Self::Rust {
+            name: name.into(),
+            check: Arc::new(check),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::MoveView { module, function } => format!("{module}::{function}"),
+            Self::Rust { name, .. } => name.clone(),
+        }
+    }
+}
+
+impl FromStr for Invariant {
+    type Err = anyhow::Error;
+
+    /// Parse a Move invariant, of the form `module::function`.
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let Some((module, function)) = s.split_once("::") else {
+            bail!("Invariant '{s}' is not of the form module::function");
+        };
+        Self::move_view(module, function)
+    }
+}
+
+/// Check that every Move invariant names a function `module::function(&T): bool` in one of the
+/// packages at `package_paths`.
+pub fn check_invariants_defined(
+    invariants: &[Invariant],
+    package_paths: &[PathBuf],
+) -> anyhow::Result<()> {
+    let mut defined = HashSet::new();
+    for path in package_paths {
+        let package = BuildConfig::new_for_testing().build(path)?;
+        for module in package.get_modules() {
+            let module = Module::new(module);
+            for (function, func) in &module.functions {
+                if invariant_parameter(func).is_some() {
+                    defined.insert((module.name.clone(), function.clone()));
+                }
+            }
+        }
+    }
+
+    for invariant in invariants {
+        let Invariant::MoveView { module, function } = invariant else {
+            continue;
+        };
+        if !defined.contains(&(module.clone(), function.clone())) {
+            bail!(
+                "Invariant {} does not match a public function `(&T): bool` in the surfed packages",
+                invariant.name()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+impl SurfedCall {
+    pub fn new(
+        sender: SuiAddress,
+        package: ObjectID,
+        module: String,
+        function: String,
+        parameters: &[Type],
+        args: &[CallArg],
+    ) -> Self {
+        Self {
+            sender,
+            package,
+            module,
+            function,
+            args: parameters
+                .iter()
+                .zip(args)
+                .map(|(param, arg)| match arg {
+                    CallArg::Object(obj) => SurfedArg::Object(obj.id()),
+                    CallArg::Pure(bytes) => SurfedArg::Pure {
+                        value: pure_argument(param, bytes),
+                        bytes: bytes.clone(),
+                    },
+                })
+                .collect(),
+            inputs: args
+                .iter()
+                .filter_map(|arg| match arg {
+                    CallArg::Pure(_) => None,
+                    CallArg::Object(obj) => Some(obj.id()),
+                })
+                .collect(),
+            outputs: vec![],
+            created: vec![],
+        }
+    }
+
+    /// The `sui client ptb` command that makes this call, referring to its package and object
+    /// arguments by the names in `names`, if they have one.
+    fn command(&self, names: &HashMap<ObjectID, String>) -> String {
+        let name = |id: &ObjectID| {
+            names
+                .get(id)
+                .map_or_else(|| id.to_string(), |name| format!("${name}"))
+        };
+
+        let mut command = format!(
+            "sui client ptb --move-call {}::{}::{}",
+            name(&self.package),
+            self.module,
+            self.function
+        );
+        for arg in &self.args {
+            let _ = match arg {
+                SurfedArg::Object(id) => write!(command, " @{}", name(id)),
+                SurfedArg::Pure { value, .. } => write!(command, " {value}"),
+            };
+        }
+        command
+    }
+}
+
+impl fmt::Display for SurfedCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.command(&HashMap::new()))
+    }
+}
+
+impl CallLog {
+    /// Record `call`, and return its position in the log.
+    pub(crate) fn push(&mut self, call: SurfedCall) -> usize {
+        if self.calls.len() == MAX_CALL_LOG_LEN {
+            self.calls.pop_front();
+            self.dropped += 1;
+        }
+        self.calls.push_back(call);
+        self.dropped + self.calls.len() - 1
+    }
+
+    /// The calls that the violation of an invariant on `object`, checked after the call at
+    /// position `failing`, can depend on: the failing call, and every earlier call that touched
+    /// one of the objects that a call already in the slice takes as input.
+    ///
+    /// This keeps every call that touched a relevant object, whether or not the violation needs
+    /// it, and is the starting point for shrinking by replay.
+    pub(crate) fn dependency_slice(&self, failing: usize, object: ObjectID) -> Vec<SurfedCall> {
+        let Some(failing) = failing.checked_sub(self.dropped) else {
+            return vec![];
+        };
+
+        let mut relevant: HashSet<ObjectID> = HashSet::from([object]);
+        relevant.extend(&self.calls[failing].inputs);
+
+        let mut kept = vec![self.calls[failing].clone()];
+        for call in self.calls.range(..failing).rev() {
+            if call.outputs.iter().any(|id| relevant.contains(id)) {
+                relevant.extend(&call.inputs);
+                kept.push(call.clone());
+            }
+        }
+
+        kept.reverse();
+        kept
+    }
+}
+
+impl InvariantViolation {
+    /// Shell script that replays the calls leading to this violation, one `sui client ptb` per
+    /// call.
+    ///
+    /// Packages are assigned to variables at the top of the script, to be pointed at a fresh
+    /// publish of the surfed packages. Objects created by a call in the script are read from the
+    /// output of that call (using `jq`), picking created objects of the same type in the order
+    /// they were created while surfing. Other objects are referred to by the IDs they had while
+    /// surfing.
+    pub fn reproduction_script(&self) -> String {
+        let mut script = String::new();
+        let _ = writeln!(
+            script,
+            "# Violates {} on object {}: {}",
+            self.invariant, self.object, self.error
+        );
+
+        let mut names = HashMap::new();
+        for call in &self.calls {
+            if !names.contains_key(&call.package) {
+                let name = format!("package_{}", names.len());
+                let _ = writeln!(script, "{name}={}", call.package);
+                names.insert(call.package, name);
+            }
+        }
+
+        let used: HashSet<ObjectID> = self.calls.iter().flat_map(|c| &c.inputs).copied().collect();
+
+        let mut sender = None;
+        for (i, call) in self.calls.iter().enumerate() {
+            if sender != Some(call.sender) {
+                let _ = writeln!(script, "sui client switch --address {}", call.sender);
+                sender = Some(call.sender);
+            }
+
+            let command = call.command(&names);
+            if !call.created.iter().any(|(id, _)| used.contains(id)) {
+                let _ = writeln!(script, "{command}");
+                continue;
+            }
+
+            let output = format!("call_{i}");
+            let _ = writeln!(script, "{output}=$({command} --json)");
+
+            let mut nth_of_type: HashMap<(&str, &str), usize> = HashMap::new();
+            for (j, (id, type_)) in call.created.iter().enumerate() {
+                let key = (type_.module.as_str(), type_.name.as_str());
+                let nth = *nth_of_type.entry(key).and_modify(|n| *n += 1).or_default();
+                if !used.contains(id) {
+                    continue;
+                }
+
+                let name = format!("object_{i}_{j}");
+                let _ = writeln!(
+                    script,
+                    "{name}=$(echo \"${output}\" | jq -r '[.objectChanges[] | \
+                     select(.type == \"created\" and (.objectType | test(\"::{}::{}(<|$)\")))]\
+                     [{nth}].objectId')",
+                    key.0, key.1,
+                );
+                names.insert(*id, name);
+            }
+        }
+
+        script
+    }
+}
+
+/// The candidates that delta debugging tries when shrinking a sequence of `len` calls split into
+/// `chunks` chunks, as the indices of the calls they keep: each chunk on its own, then each chunk's
+/// complement. With a single chunk, the only candidate is the empty sequence.
+pub(crate) fn delta_candidates(len: usize, chunks: usize) -> Vec<Vec<usize>> {
+    let chunks = chunks.clamp(1, len.max(1));
+    if chunks == 1 {
+        return vec![vec![]];
+    }
+
+    let bounds: Vec<_> = (0..=chunks).map(|i| i * len / chunks).collect();
+    let subsets: Vec<Vec<usize>> = bounds.windows(2).map(|w| (w[0]..w[1]).collect()).collect();
+
+    // With two chunks, each complement is the other chunk.
+    let complements = if chunks == 2 {
+        vec![]
+    } else {
+        bounds
+            .windows(2)
+            .map(|w| (0..w[0]).chain(w[1]..len).collect())
+            .collect()
+    };
+
+    subsets.into_iter().chain(complements).collect()
+}
+
+/// Pair the objects that a call created while surfing with the objects that replaying it created,
+/// matching objects of the same type in the order they are listed. Types are compared by module
+/// and name only, because replayed calls use a fresh publish of the surfed packages.
+pub(crate) fn match_created(
+    surfed: &[(ObjectID, StructTag)],
+    replayed: &[(ObjectID, StructTag)],
+) -> Vec<(ObjectID, ObjectID)> {
+    let mut by_type: HashMap<(&str, &str), VecDeque<ObjectID>> = HashMap::new();
+    for (id, type_) in replayed {
+        by_type
+            .entry((type_.module.as_str(), type_.name.as_str()))
+            .or_default()
+            .push_back(*id);
+    }
+
+    surfed
+        .iter()
+        .filter_map(|(id, type_)| {
+            let replayed = by_type
+                .get_mut(&(type_.module.as_str(), type_.name.as_str()))?
+                .pop_front()?;
+            Some((*id, replayed))
+        })
+        .collect()
+}
+
+/// If `func` can check a Move invariant, i.e. it is a public function with signature `(&T): bool`,
+/// return `T`.
+pub(crate) fn invariant_parameter(func: &Function) -> Option<StructTag> {
+    if !matches!(func.visibility, Visibility::Public)
+        || !func.type_parameters.is_empty()
+        || func.return_ != [Type::Bool]
+    {
+        return None;
+    }
+
+    let [Type::Reference(param)] = &func.parameters[..] else {
+        return None;
+    };
+
+    match param.as_ref() {
+        Type::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } if type_arguments.is_empty() => Some(StructTag {
+            address: *address,
+            module: module.clone(),
+            name: name.clone(),
+            type_params: vec![],
+        }),
+        _ => None,
+    }
+}
+
+/// Render the pure value `bytes`, of type `ty`, in the syntax accepted by `sui client ptb`.
+fn pure_argument(ty: &Type, bytes: &[u8]) -> String {
+    let rendered = match ty {
+        Type::Bool => bcs::from_bytes::<bool>(bytes).ok().map(|v| v.to_string()),
+        Type::U8 => bcs::from_bytes::<u8>(bytes).ok().map(|v| format!("{v}u8")),
+        Type::U16 => bcs::from_bytes::<u16>(bytes)
+            .ok()
+            .map(|v| format!("{v}u16")),
+        Type::U32 => bcs::from_bytes::<u32>(bytes)
+            .ok()
+            .map(|v| format!("{v}u32")),
+        Type::U64 => bcs::from_bytes::<u64>(bytes)
+            .ok()
+            .map(|v| format!("{v}u64")),
+        Type::U128 => bcs::from_bytes::<u128>(bytes)
+            .ok()
+            .map(|v| format!("{v}u128")),
+        Type::Address => bcs::from_bytes::<SuiAddress>(bytes)
+            .ok()
+            .map(|v| format!("@{v}")),
+        _ => return raw_bytes(bytes),
+    };
+
+    rendered.unwrap_or_else(|| raw_bytes(bytes))
+}
+
+/// Fallback rendering for values whose type is not supported: their raw bytes, as a
+/// `vector<u8>`, which is only valid for some types, but still shows what was passed.
+fn raw_bytes(bytes: &[u8]) -> String {
+    let bytes: Vec<_> = bytes.iter().map(|b| format!("{b}u8")).collect();
+    format!("vector[{}]", bytes.join(","))
+}
@@ -17,6 +17,7 @@ use tracing::info;
 use crate::surfer_state::SurfStatistics;
 use crate::surfer_task::SurferTask;
 
+pub mod invariant;
 pub mod surf_strategy;
 mod surfer_state;
 mod surfer_task;
@@ -30,6 +31,21 @@ pub async fn run(
     run_duration: Duration,
     epoch_duration: Duration,
     package_paths: Vec<PathBuf>,
+) -> SurfStatistics {
+    run_with_strategy(
+        SurfStrategy::default(),
+        run_duration,
+        epoch_duration,
+        package_paths,
+    )
+    .await
+}
+
+pub async fn run_with_strategy(
+    surf_strategy: SurfStrategy,
+    run_duration: Duration,
+    epoch_duration: Duration,
+    package_paths: Vec<PathBuf>,
 ) -> SurfStatistics {
     let cluster = TestClusterBuilder::new()
         .with_num_validators(VALIDATOR_COUNT)
@@ -48,7 +64,14 @@ pub async fn run(
         VALIDATOR_COUNT,
         epoch_duration.as_millis()
     );
-    run_with_test_cluster(run_duration, package_paths, cluster.into(), 0).await
+    run_with_test_cluster_and_strategy(
+        surf_strategy,
+        run_duration,
+        package_paths,
+        cluster.into(),
+        0,
+    )
+    .await
 }
 
 pub async fn run_with_test_cluster(
@@ -3,6 +3,10 @@
 
 use clap::Parser;
 use std::{path::PathBuf, time::Duration};
+use sui_surfer::{
+    invariant::{check_invariants_defined, Invariant},
+    surf_strategy::SurfStrategy,
+};
 use tracing::info;
 
 #[derive(Parser)]
@@ -16,6 +20,13 @@ struct Args {
 
     #[clap(long, help = "List of package paths to surf")]
     packages: Vec<PathBuf>,
+
+    #[clap(
+        long,
+        help = "Move functions `module::function(&T): bool` in the surfed packages, to check on \
+                every object of type `T` changed by a surfed transaction"
+    )]
+    invariants: Vec<String>,
 }
 
 const DEFAULT_RUN_DURATION: u64 = 30;
@@ -34,7 +45,24 @@ async fn main() {
         .with_env()
         .init();
 
-    let results = sui_surfer::run(
+    let invariants = match args
+        .invariants
+        .iter()
+        .map(|invariant| invariant.parse::<Invariant>())
+        .collect::<anyhow::Result<Vec<_>>>()
+        .and_then(|invariants| {
+            check_invariants_defined(&invariants, &args.packages)?;
+            Ok(invariants)
+        }) {
+        Ok(invariants) => invariants,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
+
+    let results = sui_surfer::run_with_strategy(
+        SurfStrategy::default().with_invariants(invariants),
         Duration::from_secs(args.run_duration.unwrap_or(DEFAULT_RUN_DURATION)),
         Duration::from_secs(args.run_duration.unwrap_or(DEFAULT_EPOCH_DURATION)),
         args.packages,
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use move_binary_format::normalized::Type;
@@ -13,6 +14,7 @@ use sui_types::{
 use tokio::time::Instant;
 use tracing::debug;
 
+use crate::invariant::{Invariant, SurfedCall};
 use crate::surfer_state::{EntryFunction, SurferState};
 
 enum InputObjectPassKind {
@@ -24,11 +26,25 @@ enum InputObjectPassKind {
 #[derive(Clone, Default)]
 pub struct SurfStrategy {
     min_tx_interval: Duration,
+    invariants: Arc<Vec<Invariant>>,
 }
 
 impl SurfStrategy {
     pub fn new(min_tx_interval: Duration) -> Self {
-        Self { min_tx_interval }
+        Self {
+            min_tx_interval,
+            invariants: Arc::new(vec![]),
+        }
+    }
+
+    /// Check `invariants` after every surfed transaction.
+    pub fn with_invariants(mut self, invariants: Vec<Invariant>) -> Self {
+        self.invariants = Arc::new(invariants);
+        self
+    }
+
+    pub(crate) fn invariants(&self) -> &[Invariant] {
+        &self.invariants
     }
 
     /// Given a state and a list of callable Move entry functions,
@@ -43,16 +59,30 @@ impl SurfStrategy {
         entry_functions.shuffle(&mut state.rng);
         for entry in entry_functions {
             let next_tx_time = Instant::now() + self.min_tx_interval;
-            let Some(args) = Self::choose_function_call_args(state, entry.parameters).await else {
+            let Some(args) = Self::choose_function_call_args(state, entry.parameters.clone()).await
+            else {
                 debug!(
                     "Failed to choose arguments for Move function {:?}::{:?}",
                     entry.module, entry.function
                 );
                 continue;
             };
-            state
+            let call = SurfedCall::new(
+                state.address,
+                entry.package,
+                entry.module.clone(),
+                entry.function.clone(),
+                &entry.parameters,
+                &args,
+            );
+            let effects = state
                 .execute_move_transaction(entry.package, entry.module, entry.function, args)
                 .await;
+            if !self.invariants.is_empty() {
+                state
+                    .check_invariants(&self.invariants, call, &effects)
+                    .await;
+            }
             tokio::time::sleep_until(next_tx_time).await;
         }
     }
@@ -73,7 +103,14 @@ impl SurfStrategy {
                 Type::U64 => CallArg::Pure(bcs::to_bytes(&state.rng.gen::<u64>()).unwrap()),
                 Type::U128 => CallArg::Pure(bcs::to_bytes(&state.rng.gen::<u128>()).unwrap()),
                 Type::Address => CallArg::Pure(
-                    bcs::to_bytes(&state.cluster.get_addresses().choose(&mut state.rng)).unwrap(),
+                    bcs::to_bytes(
+                        state
+                            .cluster
+                            .get_addresses()
+                            .choose(&mut state.rng)
+                            .unwrap(),
+                    )
+                    .unwrap(),
                 ),
                 ty @ Type::Struct { .. } => {
                     match Self::choose_object_call_arg(
@@ -16,13 +16,21 @@ use sui_protocol_config::{Chain, ProtocolConfig};
 use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
 use sui_types::execution_config_utils::to_binary_config;
 use sui_types::object::{Object, Owner};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::storage::WriteKind;
-use sui_types::transaction::{CallArg, ObjectArg, TransactionData, TEST_ONLY_GAS_UNIT_FOR_PUBLISH};
+use sui_types::transaction::{
+    CallArg, ObjectArg, TransactionData, TransactionKind, TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+};
 use sui_types::{Identifier, SUI_FRAMEWORK_ADDRESS};
 use test_cluster::TestCluster;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info};
 
+use crate::invariant::{
+    delta_candidates, invariant_parameter, match_created, CallLog, Invariant, InvariantViolation,
+    SurfedArg, SurfedCall, MAX_SHRINK_REPLAYS,
+};
+
 #[derive(Debug, Clone)]
 pub struct EntryFunction {
     pub package: ObjectID,
@@ -38,6 +46,7 @@ pub struct SurfStatistics {
     pub num_owned_obj_transactions: u64,
     pub num_shared_obj_transactions: u64,
     pub unique_move_functions_called: HashSet<(ObjectID, String, String)>,
+    pub invariant_violations: Vec<InvariantViolation>,
 }
 
 impl SurfStatistics {
@@ -73,6 +82,9 @@ impl SurfStatistics {
             result
                 .unique_move_functions_called
                 .extend(stat.unique_move_functions_called);
+            result
+                .invariant_violations
+                .extend(stat.invariant_violations);
         }
         result
     }
@@ -92,6 +104,16 @@ impl SurfStatistics {
             "Unique move functions called: {}",
             self.unique_move_functions_called.len()
         );
+        if !self.invariant_violations.is_empty() {
+            error!("{} invariant violations", self.invariant_violations.len());
+        }
+        for violation in &self.invariant_violations {
+            error!(
+                "Reproduction for violation of {}:\n{}",
+                violation.invariant,
+                violation.reproduction_script()
+            );
+        }
     }
 }
 
@@ -103,6 +125,25 @@ pub type ImmObjects = Arc<RwLock<HashMap<StructTag, Vec<ObjectRef>>>>;
 /// (object ID, initial shared version).
 pub type SharedObjects = Arc<RwLock<HashMap<StructTag, Vec<(ObjectID, SequenceNumber)>>>>;
 
+/// Map from (module, function) to the packages that define a function `module::function(&T): bool`
+/// that can check a Move invariant, along with `T`.
+pub type ViewFunctions = Arc<RwLock<HashMap<(Identifier, Identifier), Vec<(ObjectID, StructTag)>>>>;
+
+/// The most recent calls made by all surfers.
+pub type SurfedCalls = Arc<RwLock<CallLog>>;
+
+/// A package published for surfing, kept to publish it again when replaying calls.
+#[derive(Clone, Debug)]
+pub struct PublishedPackage {
+    pub modules: Vec<Vec<u8>>,
+    pub dependencies: Vec<ObjectID>,
+    /// Objects created by the package's initializers, along with their types.
+    pub created: Vec<(ObjectID, StructTag)>,
+}
+
+/// Map from the ID of a surfed package to how it was published.
+pub type PublishedPackages = Arc<RwLock<HashMap<ObjectID, PublishedPackage>>>;
+
 pub struct SurferState {
     pub id: usize,
     pub cluster: Arc<TestCluster>,
@@ -114,6 +155,9 @@ pub struct SurferState {
     pub immutable_objects: ImmObjects,
     pub shared_objects: SharedObjects,
     pub entry_functions: Arc<RwLock<Vec<EntryFunction>>>,
+    pub view_functions: ViewFunctions,
+    pub call_log: SurfedCalls,
+    pub published_packages: PublishedPackages,
 
     pub stats: SurfStatistics,
 }
@@ -129,6 +173,9 @@ impl SurferState {
         immutable_objects: ImmObjects,
         shared_objects: SharedObjects,
         entry_functions: Arc<RwLock<Vec<EntryFunction>>>,
+        view_functions: ViewFunctions,
+        call_log: SurfedCalls,
+        published_packages: PublishedPackages,
     ) -> Self {
         Self {
             id,
@@ -140,6 +187,9 @@ impl SurferState {
             immutable_objects,
             shared_objects,
             entry_functions,
+            view_functions,
+            call_log,
+            published_packages,
             stats: Default::default(),
         }
     }
@@ -151,7 +201,7 @@ impl SurferState {
         module: String,
         function: String,
         args: Vec<CallArg>,
-    ) {
+    ) -> SuiTransactionBlockEffects {
         let rgp = self.cluster.get_reference_gas_price().await;
         let use_shared_object = args
             .iter()
@@ -203,6 +253,186 @@ impl SurferState {
             function,
         );
         self.process_tx_effects(&effects).await;
+        effects
+    }
+
+    /// Check `invariants` against the objects changed by `call`, which produced `effects`, and
+    /// record any violations, along with the calls that led to them.
+    #[tracing::instrument(skip_all, fields(surfer_id = self.id))]
+    pub async fn check_invariants(
+        &mut self,
+        invariants: &[Invariant],
+        mut call: SurfedCall,
+        effects: &SuiTransactionBlockEffects,
+    ) {
+        let changed = effects.all_changed_objects();
+        call.outputs = changed
+            .iter()
+            .map(|(obj, _)| obj.object_id())
+            .chain(
+                effects
+                    .all_deleted_objects()
+                    .iter()
+                    .map(|(obj, _)| obj.object_id),
+            )
+            .collect();
+        call.created = self.created_objects(effects).await;
+
+        let failing = self.call_log.write().await.push(call);
+
+        for (owned_ref, _) in changed {
+            if matches!(owned_ref.owner, Owner::ObjectOwner(_)) {
+                continue;
+            }
+            let Some(object) = self
+                .cluster
+                .get_object_from_fullnode_store(&owned_ref.object_id())
+                .await
+            else {
+                continue;
+            };
+            if object.is_package() {
+                continue;
+            }
+
+            for invariant in invariants {
+                let name = invariant.name();
+                if self
+                    .stats
+                    .invariant_violations
+                    .iter()
+                    .any(|v| v.invariant == name && v.object == object.id())
+                {
+                    // Only report the first violation of an invariant for each object.
+                    continue;
+                }
+
+                let Err(error) = self.check_invariant(invariant, &object).await else {
+                    continue;
+                };
+
+                error!(
+                    "Invariant {name} violated on object {}: {error}",
+                    object.id()
+                );
+                let calls = self
+                    .call_log
+                    .read()
+                    .await
+                    .dependency_slice(failing, object.id());
+                self.stats.invariant_violations.push(InvariantViolation {
+                    invariant: name,
+                    object: object.id(),
+                    object_type: object.struct_tag().unwrap(),
+                    error,
+                    calls,
+                });
+            }
+        }
+    }
+
+    async fn check_invariant(&self, invariant: &Invariant, object: &Object) -> Result<(), String> {
+        let (module, function) = match invariant {
+            Invariant::Rust { check, .. } => return check(object),
+            Invariant::MoveView { module, function } => (module, function),
+        };
+
+        let Some(struct_tag) = object.struct_tag() else {
+            return Ok(());
+        };
+        for package in self.view_packages(module, function, &struct_tag).await {
+            self.check_move_view(package, module, function, object)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// The packages that define the Move invariant `module::function` for objects of type
+    /// `struct_tag`.
+    async fn view_packages(
+        &self,
+        module: &Identifier,
+        function: &Identifier,
+        struct_tag: &StructTag,
+    ) -> Vec<ObjectID> {
+        self.view_functions
+            .read()
+            .await
+            .get(&(module.clone(), function.clone()))
+            .into_iter()
+            .flatten()
+            .filter(|(_, param)| param == struct_tag)
+            .map(|(package, _)| *package)
+            .collect()
+    }
+
+    /// Call `package::module::function` on `object` using dev-inspect, and check that it returns
+    /// `true`.
+    async fn check_move_view(
+        &self,
+        package: ObjectID,
+        module: &Identifier,
+        function: &Identifier,
+        object: &Object,
+    ) -> Result<(), String> {
+        let arg = match object.owner {
+            Owner::Shared {
+                initial_shared_version,
+            } => ObjectArg::SharedObject {
+                id: object.id(),
+                initial_shared_version,
+                mutable: false,
+            },
+            _ => ObjectArg::ImmOrOwnedObject(object.compute_object_reference()),
+        };
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .move_call(
+                package,
+                module.clone(),
+                function.clone(),
+                vec![],
+                vec![CallArg::Object(arg)],
+            )
+            .unwrap();
+
+        let results = match self
+            .cluster
+            .sui_client()
+            .read_api()
+            .dev_inspect_transaction_block(
+                self.address,
+                TransactionKind::programmable(builder.finish()),
+                None,
+                None,
+                None,
+            )
+            .await
+        {
+            Ok(results) => results,
+            Err(e) => {
+                // Failing to run the check is not a violation of the invariant.
+                error!("Failed to check invariant {module}::{function}: {e}");
+                return Ok(());
+            }
+        };
+
+        if let Some(error) = results.error {
+            return Err(error);
+        }
+
+        let returned = results
+            .results
+            .and_then(|results| results.into_iter().next())
+            .and_then(|result| result.return_values.into_iter().next());
+
+        match returned {
+            Some((bytes, _)) if bytes == [1] => Ok(()),
+            Some(_) => Err(format!("{package}::{module}::{function} returned false")),
+            None => Err(format!("{package}::{module}::{function} returned nothing")),
+        }
     }
 
     #[tracing::instrument(skip_all, fields(surfer_id = self.id))]
@@ -270,9 +500,22 @@ impl SurferState {
         let proto_version = self.cluster.highest_protocol_version();
         let config = ProtocolConfig::get_for_version(proto_version, Chain::Unknown);
         let binary_config = to_binary_config(&config);
-        let entry_functions: Vec<_> = move_package
-            .normalize(&binary_config)
-            .unwrap()
+        let modules = move_package.normalize(&binary_config).unwrap();
+
+        let mut view_functions = self.view_functions.write().await;
+        for module in modules.values() {
+            for (func_name, func) in &module.functions {
+                if let Some(param) = invariant_parameter(func) {
+                    view_functions
+                        .entry((module.name.clone(), func_name.clone()))
+                        .or_default()
+                        .push((package_id, param));
+                }
+            }
+        }
+        drop(view_functions);
+
+        let entry_functions: Vec<_> = modules
             .into_iter()
             .flat_map(|(module_name, module)| {
                 module
@@ -320,11 +563,12 @@ impl SurferState {
         let rgp = self.cluster.get_reference_gas_price().await;
         let package = BuildConfig::new_for_testing().build(path).unwrap();
         let modules = package.get_package_bytes(false);
+        let dependencies: Vec<_> = package.dependency_ids.published.values().cloned().collect();
         let tx_data = TransactionData::new_module(
             self.address,
             self.gas_object,
-            modules,
-            package.dependency_ids.published.values().cloned().collect(),
+            modules.clone(),
+            dependencies.clone(),
             TEST_ONLY_GAS_UNIT_FOR_PUBLISH * rgp,
             rgp,
         );
@@ -346,7 +590,336 @@ impl SurferState {
             }
         };
         info!("Successfully published package in {:?}", path);
-        self.process_tx_effects(&response.effects.unwrap()).await;
+        let effects = response.effects.unwrap();
+        if let Some(package) = self.published_package(&effects).await {
+            let created = self.created_objects(&effects).await;
+            self.published_packages.write().await.insert(
+                package,
+                PublishedPackage {
+                    modules,
+                    dependencies,
+                    created,
+                },
+            );
+        }
+        self.process_tx_effects(&effects).await;
+    }
+
+    /// The objects created by the transaction with `effects`, along with their types. Packages are
+    /// left out.
+    async fn created_objects(
+        &self,
+        effects: &SuiTransactionBlockEffects,
+    ) -> Vec<(ObjectID, StructTag)> {
+        let mut created = vec![];
+        for object in effects.created() {
+            let id = object.object_id();
+            if let Some(type_) = self
+                .cluster
+                .get_object_from_fullnode_store(&id)
+                .await
+                .and_then(|object| object.struct_tag())
+            {
+                created.push((id, type_));
+            }
+        }
+        created
+    }
+
+    /// The ID of the package published by the transaction with `effects`.
+    async fn published_package(&self, effects: &SuiTransactionBlockEffects) -> Option<ObjectID> {
+        for object in effects.created() {
+            if object.owner != Owner::Immutable {
+                continue;
+            }
+            let id = object.object_id();
+            if let Some(object) = self.cluster.get_object_from_fullnode_store(&id).await {
+                if object.is_package() {
+                    return Some(id);
+                }
+            }
+        }
+        None
+    }
+
+    /// Shrink the calls leading to each invariant violation found by this surfer, to a
+    /// subsequence that still violates the invariant when it is replayed, using delta debugging.
+    ///
+    /// Candidate subsequences always keep the call after which the invariant was checked, and are
+    /// replayed by this surfer's account against a fresh publish of the surfed packages, mapping
+    /// the objects created while surfing to the objects created by the replay. A candidate that
+    /// uses an object that none of its calls created, and that is not shared or immutable, cannot
+    /// be replayed and does not count as a reproduction. If the calls do not violate the invariant
+    /// when replayed in full, they are left as they are.
+    pub(crate) async fn shrink_violations(&mut self, invariants: &[Invariant]) {
+        let mut violations = std::mem::take(&mut self.stats.invariant_violations);
+        for violation in &mut violations {
+            let Some(invariant) = invariants.iter().find(|i| i.name() == violation.invariant)
+            else {
+                continue;
+            };
+
+            let before = violation.calls.len();
+            violation.calls = self.shrink(invariant, violation).await;
+            info!(
+                "Shrunk the reproduction for violation of {} from {before} to {} calls",
+                violation.invariant,
+                violation.calls.len()
+            );
+        }
+        self.stats.invariant_violations = violations;
+    }
+
+    async fn shrink(
+        &mut self,
+        invariant: &Invariant,
+        violation: &InvariantViolation,
+    ) -> Vec<SurfedCall> {
+        let Some((last, prefix)) = violation.calls.split_last() else {
+            return vec![];
+        };
+
+        // Objects that exist because of surfing, and that a replay must create for itself.
+        let mut surfed: HashSet<ObjectID> = violation
+            .calls
+            .iter()
+            .flat_map(|call| call.created.iter().map(|(id, _)| *id))
+            .collect();
+        for (package, published) in self.published_packages.read().await.iter() {
+            surfed.insert(*package);
+            surfed.extend(published.created.iter().map(|(id, _)| *id));
+        }
+
+        if !self
+            .replay_violates(invariant, violation, &violation.calls, &surfed)
+            .await
+        {
+            info!(
+                "Replaying the calls leading to violation of {} does not reproduce it",
+                violation.invariant
+            );
+            return violation.calls.clone();
+        }
+
+        let mut prefix = prefix.to_vec();
+        let mut chunks = 2;
+        let mut replays = 1;
+        'shrink: while !prefix.is_empty() {
+            let chunks_used = chunks.min(prefix.len());
+            for (i, kept) in delta_candidates(prefix.len(), chunks_used)
+                .into_iter()
+                .enumerate()
+            {
+                if replays == MAX_SHRINK_REPLAYS {
+                    break 'shrink;
+                }
+                replays += 1;
+
+                let candidate: Vec<_> = kept
+                    .iter()
+                    .map(|i| prefix[*i].clone())
+                    .chain([last.clone()])
+                    .collect();
+                if self
+                    .replay_violates(invariant, violation, &candidate, &surfed)
+                    .await
+                {
+                    prefix = kept.iter().map(|i| prefix[*i].clone()).collect();
+                    // Start over from two chunks after keeping a single chunk, otherwise keep the
+                    // granularity, which now covers one chunk less.
+                    chunks = if i < chunks_used {
+                        2
+                    } else {
+                        (chunks_used - 1).max(2)
+                    };
+                    continue 'shrink;
+                }
+            }
+
+            if chunks_used == prefix.len() {
+                break;
+            }
+            chunks = (chunks_used * 2).min(prefix.len());
+        }
+
+        prefix.push(last.clone());
+        prefix
+    }
+
+    /// Replay `calls` as this surfer, against a fresh publish of their packages, and check whether
+    /// `invariant` is violated on the object that corresponds to the violation's object afterwards.
+    async fn replay_violates(
+        &mut self,
+        invariant: &Invariant,
+        violation: &InvariantViolation,
+        calls: &[SurfedCall],
+        surfed: &HashSet<ObjectID>,
+    ) -> bool {
+        let rgp = self.cluster.get_reference_gas_price().await;
+        let mut replayed: HashMap<ObjectID, ObjectID> = HashMap::new();
+
+        let mut packages: Vec<ObjectID> = calls.iter().map(|call| call.package).collect();
+        packages.push(ObjectID::from(violation.object_type.address));
+        for package in packages {
+            if replayed.contains_key(&package) {
+                continue;
+            }
+            let Some(published) = self.published_packages.read().await.get(&package).cloned()
+            else {
+                // Not a surfed package, so the replay can use it as it is.
+                continue;
+            };
+
+            let tx_data = TransactionData::new_module(
+                self.address,
+                self.gas_object,
+                published.modules,
+                published.dependencies,
+                TEST_ONLY_GAS_UNIT_FOR_PUBLISH * rgp,
+                rgp,
+            );
+            let Some(effects) = self.execute_replay(tx_data).await else {
+                return false;
+            };
+            let Some(republished) = self.published_package(&effects).await else {
+                return false;
+            };
+
+            replayed.insert(package, republished);
+            let created_again = self.created_objects(&effects).await;
+            replayed.extend(match_created(&published.created, &created_again));
+        }
+
+        for call in calls {
+            let mut args = vec![];
+            for arg in &call.args {
+                let arg = match arg {
+                    SurfedArg::Pure { bytes, .. } => CallArg::Pure(bytes.clone()),
+                    SurfedArg::Object(id) => {
+                        let Some(arg) = self.replay_object_arg(*id, &replayed, surfed).await else {
+                            return false;
+                        };
+                        arg
+                    }
+                };
+                args.push(arg);
+            }
+
+            let Ok(module) = Identifier::new(call.module.as_str()) else {
+                return false;
+            };
+            let Ok(function) = Identifier::new(call.function.as_str()) else {
+                return false;
+            };
+            let Ok(tx_data) = TransactionData::new_move_call(
+                self.address,
+                replayed.get(&call.package).copied().unwrap_or(call.package),
+                module,
+                function,
+                vec![],
+                self.gas_object,
+                args,
+                TEST_ONLY_GAS_UNIT_FOR_PUBLISH * rgp,
+                rgp,
+            ) else {
+                return false;
+            };
+
+            let Some(effects) = self.execute_replay(tx_data).await else {
+                return false;
+            };
+            let created_again = self.created_objects(&effects).await;
+            replayed.extend(match_created(&call.created, &created_again));
+        }
+
+        let Some(object) = replayed.get(&violation.object) else {
+            return false;
+        };
+        let Some(object) = self.cluster.get_object_from_fullnode_store(object).await else {
+            return false;
+        };
+
+        let result = match invariant {
+            Invariant::Rust { check, .. } => check(&object),
+            Invariant::MoveView { module, function } => {
+                let mut result = Ok(());
+                for package in self
+                    .view_packages(module, function, &violation.object_type)
+                    .await
+                {
+                    let package = replayed.get(&package).copied().unwrap_or(package);
+                    result = self
+                        .check_move_view(package, module, function, &object)
+                        .await;
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                result
+            }
+        };
+        result.is_err()
+    }
+
+    /// The argument that replays passing the object `id` to a call, given the objects `replayed`
+    /// has created so far in place of the `surfed` objects.
+    async fn replay_object_arg(
+        &self,
+        id: ObjectID,
+        replayed: &HashMap<ObjectID, ObjectID>,
+        surfed: &HashSet<ObjectID>,
+    ) -> Option<CallArg> {
+        let (id, created_by_replay) = match replayed.get(&id) {
+            Some(id) => (*id, true),
+            None if surfed.contains(&id) => return None,
+            None => (id, false),
+        };
+
+        let object = self.cluster.get_object_from_fullnode_store(&id).await?;
+        let arg = match object.owner {
+            Owner::Shared {
+                initial_shared_version,
+            } => ObjectArg::SharedObject {
+                id,
+                initial_shared_version,
+                mutable: true,
+            },
+            Owner::Immutable => ObjectArg::ImmOrOwnedObject(object.compute_object_reference()),
+            // Objects that were not created by the replay belong to the surfers, and are not
+            // replayed with.
+            Owner::AddressOwner(owner) if owner == self.address && created_by_replay => {
+                ObjectArg::ImmOrOwnedObject(object.compute_object_reference())
+            }
+            Owner::AddressOwner(_) | Owner::ObjectOwner(_) => return None,
+        };
+        Some(CallArg::Object(arg))
+    }
+
+    /// Execute a transaction made while replaying calls. Its effects are not recorded in this
+    /// surfer's inventory, so the surfer does not make calls with the replay's objects.
+    async fn execute_replay(
+        &mut self,
+        tx_data: TransactionData,
+    ) -> Option<SuiTransactionBlockEffects> {
+        let tx = self.cluster.wallet.sign_transaction(&tx_data);
+        let result = self.cluster.wallet.execute_transaction_may_fail(tx).await;
+
+        // Gas is charged even if the transaction failed to execute.
+        if let Some(gas) = self
+            .cluster
+            .get_object_from_fullnode_store(&self.gas_object.0)
+            .await
+        {
+            self.gas_object = gas.compute_object_reference();
+        }
+
+        match result {
+            Ok(response) => response.effects,
+            Err(e) => {
+                error!("Failed to replay transaction: {e:?}");
+                None
+            }
+        }
     }
 
     pub fn matching_owned_objects_count(&self, type_tag: &StructTag) -> usize {
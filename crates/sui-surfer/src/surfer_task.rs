@@ -13,8 +13,12 @@ use test_cluster::TestCluster;
 use tokio::sync::{watch, RwLock};
 
 use crate::{
+    invariant::CallLog,
     surf_strategy::SurfStrategy,
-    surfer_state::{ImmObjects, OwnedObjects, SharedObjects, SurfStatistics, SurferState},
+    surfer_state::{
+        ImmObjects, OwnedObjects, PublishedPackages, SharedObjects, SurfStatistics, SurfedCalls,
+        SurferState, ViewFunctions,
+    },
 };
 
 pub struct SurferTask {
@@ -99,6 +103,9 @@ impl SurferTask {
             }
         }
         let entry_functions = Arc::new(RwLock::new(vec![]));
+        let view_functions: ViewFunctions = Arc::new(RwLock::new(HashMap::new()));
+        let call_log: SurfedCalls = Arc::new(RwLock::new(CallLog::default()));
+        let published_packages: PublishedPackages = Arc::new(RwLock::new(HashMap::new()));
         accounts
             .into_iter()
             .enumerate()
@@ -115,6 +122,9 @@ impl SurferTask {
                     immutable_objects.clone(),
                     shared_objects.clone(),
                     entry_functions.clone(),
+                    view_functions.clone(),
+                    call_log.clone(),
+                    published_packages.clone(),
                 );
                 SurferTask {
                     state,
@@ -136,6 +146,9 @@ impl SurferTask {
                 }
 
                 _ = self.exit_rcv.changed() => {
+                    self.state
+                        .shrink_violations(self.surf_strategy.invariants())
+                        .await;
                     return self.state.stats;
                 }
             }
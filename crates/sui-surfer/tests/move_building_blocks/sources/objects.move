@@ -93,6 +93,10 @@ module move_building_blocks::objects {
         }
     }
 
+    public fun has_no_wrapped_child(object: &Object): bool {
+        option::is_none(&object.wrapped)
+    }
+
     public fun table_contains_child(object: &Object, index: u8) {
         let _ = table::contains(&object.table, index);
     }
@@ -4,6 +4,7 @@
 use std::path::PathBuf;
 use std::time::Duration;
 use sui_macros::sim_test;
+use sui_surfer::{invariant::Invariant, surf_strategy::SurfStrategy};
 
 #[sim_test]
 async fn smoke_test() {
@@ -15,3 +16,48 @@ async fn smoke_test() {
     assert!(results.num_successful_transactions > 0);
     assert!(!results.unique_move_functions_called.is_empty());
 }
+
+#[sim_test]
+async fn invariant_test() {
+    // `has_no_wrapped_child` stops holding once a child is wrapped into an object, which the
+    // surfer should run into, and reproduce.
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "move_building_blocks"]);
+    let strategy = SurfStrategy::default().with_invariants(vec![Invariant::move_view(
+        "objects",
+        "has_no_wrapped_child",
+    )
+    .unwrap()]);
+    let results = sui_surfer::run_with_strategy(
+        strategy,
+        Duration::from_secs(30),
+        Duration::from_secs(15),
+        vec![path],
+    )
+    .await;
+
+    let violation = results
+        .invariant_violations
+        .first()
+        .expect("Invariant should have been violated");
+    let last = violation.calls.last().unwrap();
+    assert!(last.function.ends_with("wrap_child"), "{last:?}");
+    // Shrinking by replay drops calls that touch the object without changing it.
+    assert!(
+        violation
+            .calls
+            .iter()
+            .all(|call| call.function != "table_contains_child"),
+        "{:?}",
+        violation.calls
+    );
+    let script = violation.reproduction_script();
+    assert!(
+        script.contains(&format!("package_0={}", last.package)),
+        "{script}"
+    );
+    assert!(
+        script.contains("sui client ptb --move-call $package_0::"),
+        "{script}"
+    );
+}
@@ -10,6 +10,7 @@ use crate::{
     GroupedObjectOutput, SnapshotVerifyMode, VerboseObjectOutput,
 };
 use anyhow::Result;
+use consensus_core::DagExport;
 use futures::{future::join_all, StreamExt};
 use std::path::PathBuf;
 use std::{collections::BTreeMap, env, sync::Arc};
@@ -42,6 +43,14 @@ pub enum Verbosity {
     Verbose,
 }
 
+#[derive(Parser, Clone, ValueEnum)]
+pub enum ConsensusDagFormat {
+    Json,
+    Graphviz,
+    /// The DAG syntax used by consensus unit tests.
+    Dag,
+}
+
 #[derive(Parser)]
 pub enum ToolCommand {
     /// Inspect if a specific object is or all gas objects owned by an address are locked by validators
@@ -130,6 +139,24 @@ pub enum ToolCommand {
         cmd: Option<DbToolCommand>,
     },
 
+    /// Export the consensus DAG of a validator over a range of rounds, read offline
+    /// from its consensus store. The validator must not be running.
+    #[command(name = "export-consensus-dag")]
+    ExportConsensusDag {
+        /// Path of the consensus store of an epoch, i.e. `<consensus db path>/<epoch>`
+        #[arg(long = "db-path")]
+        db_path: PathBuf,
+        #[arg(long = "start-round")]
+        start_round: u32,
+        #[arg(long = "end-round")]
+        end_round: u32,
+        #[arg(long = "format", value_enum, default_value = "json")]
+        format: ConsensusDagFormat,
+        /// File to write the export to. Prints to stdout if not provided.
+        #[arg(long = "output")]
+        output: Option<PathBuf>,
+    },
+
     /// Tool to verify the archive store
     #[command(name = "verify-archive")]
     VerifyArchive {
@@ -615,6 +642,24 @@ impl ToolCommand {
                     }
                 }
             }
+            ToolCommand::ExportConsensusDag {
+                db_path,
+                start_round,
+                end_round,
+                format,
+                output,
+            } => {
+                let export = DagExport::from_rocksdb(&db_path, start_round, end_round)?;
+                let content = match format {
+                    ConsensusDagFormat::Json => serde_json::to_string_pretty(&export)?,
+                    ConsensusDagFormat::Graphviz => export.to_graphviz(),
+                    ConsensusDagFormat::Dag => export.to_dag_string()?,
+                };
+                match output {
+                    Some(path) => std::fs::write(path, content)?,
+                    None => println!("{content}"),
+                }
+            }
             ToolCommand::DumpGenesis { genesis } => {
                 let genesis = Genesis::load(genesis)?;
                 println!("{:#?}", genesis);
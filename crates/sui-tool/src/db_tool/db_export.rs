@@ -0,0 +1,223 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exports the checkpoints, transactions, effects, objects and events of a stopped node's DB to
+//! parquet files, using the table schemas of the analytics indexer.
+
+use super::ExportParquetOptions;
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::Arc;
+use sui_analytics_indexer::export::export_parquet;
+use sui_analytics_indexer::PackageFallback;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::checkpoints::CheckpointStore;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Object;
+use sui_types::storage::{ObjectKey, ObjectStore};
+
+/// Resolves packages from the node DB, so that object and event types can be exported without a
+/// full node.
+struct NodeDbPackages(Arc<AuthorityPerpetualTables>);
+
+#[async_trait]
+impl PackageFallback for NodeDbPackages {
+    async fn get_package(&self, id: ObjectID) -> anyhow::Result<Object> {
+        self.0
+            .get_object(&id)?
+            .ok_or_else(|| anyhow!("Package {id} not found in the node DB"))
+    }
+}
+
+pub async fn export_db_to_parquet(path: &Path, opt: ExportParquetOptions) -> anyhow::Result<()> {
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&path.join("store"), None));
+    let checkpoint_store = CheckpointStore::new(&path.join("checkpoints"));
+
+    let Some(highest_executed) = checkpoint_store.get_highest_executed_checkpoint_seq_number()?
+    else {
+        bail!("No checkpoint has been executed, there is nothing to export");
+    };
+    // The transactions and effects of checkpoints up to the checkpoints pruner watermark have been
+    // pruned, and so have the object versions read by the checkpoints up to the objects pruner
+    // watermark, and the versions they wrote that were overwritten since.
+    let checkpoints_pruned = checkpoint_store.get_highest_pruned_checkpoint_seq_number()?;
+    let objects_pruned = perpetual_db.get_highest_pruned_checkpoint()?;
+    let lowest_available = match checkpoints_pruned.max(objects_pruned) {
+        0 => 0,
+        highest_pruned => highest_pruned + 1,
+    };
+    let start = opt.start_checkpoint.unwrap_or(lowest_available);
+    let end = opt.end_checkpoint.unwrap_or(highest_executed + 1);
+    if checkpoints_pruned > 0 && start <= checkpoints_pruned {
+        bail!(
+            "The transactions of checkpoints up to {checkpoints_pruned} have been pruned, the export must start at checkpoint {} or later",
+            checkpoints_pruned + 1
+        );
+    }
+    if objects_pruned > 0 && start <= objects_pruned {
+        bail!(
+            "The object versions of checkpoints up to {objects_pruned} have been pruned by the object version retention of the node, the export must start at checkpoint {} or later",
+            objects_pruned + 1
+        );
+    }
+    if end > highest_executed + 1 {
+        bail!("Checkpoints after {highest_executed} have not been executed");
+    }
+    if start >= end {
+        bail!("Checkpoint range {start}..{end} is empty");
+    }
+
+    check_object_versions(&perpetual_db, &checkpoint_store, start, end)?;
+
+    // Packages read from the node DB are only cached for the duration of the export.
+    let package_cache = tempfile::tempdir()?;
+    for file_type in opt.tables {
+        let checkpoints = (start..end).map(|sequence_number| {
+            load_checkpoint_data(&perpetual_db, &checkpoint_store, sequence_number)
+        });
+        let rows = export_parquet(
+            file_type,
+            &opt.output_dir,
+            opt.checkpoint_interval,
+            package_cache.path(),
+            Arc::new(NodeDbPackages(perpetual_db.clone())),
+            checkpoints,
+        )
+        .await?;
+        println!(
+            "Exported {rows} rows of {} for checkpoints {start}..{end}",
+            file_type.dir_prefix()
+        );
+    }
+    Ok(())
+}
+
+/// Checks that every object version read or written by the checkpoints in `start..end` is in the
+/// DB before any file is written. Versions can be missing even above the pruner watermarks, e.g.
+/// when the DB was restored from a snapshot, which only contains the live object set.
+fn check_object_versions(
+    perpetual_db: &AuthorityPerpetualTables,
+    checkpoint_store: &CheckpointStore,
+    start: CheckpointSequenceNumber,
+    end: CheckpointSequenceNumber,
+) -> anyhow::Result<()> {
+    let mut missing = 0;
+    let mut first_missing = None;
+    let mut last_missing_checkpoint = None;
+    for sequence_number in start..end {
+        let checkpoint = checkpoint_store
+            .get_checkpoint_by_sequence_number(sequence_number)?
+            .ok_or_else(|| anyhow!("Missing checkpoint {sequence_number}"))?;
+        let checkpoint_contents = checkpoint_store
+            .get_checkpoint_contents(&checkpoint.content_digest)?
+            .ok_or_else(|| anyhow!("Missing contents of checkpoint {sequence_number}"))?;
+
+        let mut object_keys = vec![];
+        for digests in checkpoint_contents.iter() {
+            let digest = &digests.transaction;
+            let effects = perpetual_db
+                .get_effects(digest)?
+                .ok_or_else(|| anyhow!("Missing effects of transaction {digest}"))?;
+            object_keys.extend(
+                effects
+                    .modified_at_versions()
+                    .into_iter()
+                    .chain(
+                        effects
+                            .all_changed_objects()
+                            .into_iter()
+                            .map(|((id, version, _), _, _)| (id, version)),
+                    )
+                    .map(|(id, version)| ObjectKey(id, version)),
+            );
+        }
+
+        let exists = perpetual_db.multi_object_exists_by_key(&object_keys)?;
+        for (ObjectKey(id, version), exists) in object_keys.into_iter().zip(exists) {
+            if !exists {
+                missing += 1;
+                first_missing.get_or_insert((id, version, sequence_number));
+                last_missing_checkpoint = Some(sequence_number);
+            }
+        }
+    }
+
+    if let (Some((id, version, sequence_number)), Some(last_missing_checkpoint)) =
+        (first_missing, last_missing_checkpoint)
+    {
+        bail!(
+            "{missing} object versions of checkpoints {start}..{end} are missing from the DB, the first is object {id} at version {version} of checkpoint {sequence_number}. \
+            The export must start after checkpoint {last_missing_checkpoint}"
+        );
+    }
+    Ok(())
+}
+
+fn load_checkpoint_data(
+    perpetual_db: &AuthorityPerpetualTables,
+    checkpoint_store: &CheckpointStore,
+    sequence_number: CheckpointSequenceNumber,
+) -> anyhow::Result<CheckpointData> {
+    let checkpoint = checkpoint_store
+        .get_checkpoint_by_sequence_number(sequence_number)?
+        .ok_or_else(|| anyhow!("Missing checkpoint {sequence_number}"))?;
+    let checkpoint_contents = checkpoint_store
+        .get_checkpoint_contents(&checkpoint.content_digest)?
+        .ok_or_else(|| anyhow!("Missing contents of checkpoint {sequence_number}"))?;
+
+    let mut transactions = Vec::with_capacity(checkpoint_contents.size());
+    for digests in checkpoint_contents.iter() {
+        let digest = &digests.transaction;
+        let transaction = perpetual_db
+            .get_transaction(digest)?
+            .ok_or_else(|| anyhow!("Missing transaction {digest}"))?;
+        let effects = perpetual_db
+            .get_effects(digest)?
+            .ok_or_else(|| anyhow!("Missing effects of transaction {digest}"))?;
+        let events = effects
+            .events_digest()
+            .map(|events_digest| {
+                perpetual_db
+                    .get_events(events_digest)?
+                    .ok_or_else(|| anyhow!("Missing events of transaction {digest}"))
+            })
+            .transpose()?;
+        let input_objects = effects
+            .modified_at_versions()
+            .into_iter()
+            .map(|(id, version)| load_object(perpetual_db, id, version))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let output_objects = effects
+            .all_changed_objects()
+            .into_iter()
+            .map(|((id, version, _), _, _)| load_object(perpetual_db, id, version))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        transactions.push(CheckpointTransaction {
+            transaction: transaction.into_inner(),
+            effects,
+            events,
+            input_objects,
+            output_objects,
+        });
+    }
+
+    Ok(CheckpointData {
+        checkpoint_summary: checkpoint.into_inner(),
+        checkpoint_contents,
+        transactions,
+    })
+}
+
+fn load_object(
+    perpetual_db: &AuthorityPerpetualTables,
+    id: ObjectID,
+    version: SequenceNumber,
+) -> anyhow::Result<Object> {
+    perpetual_db
+        .get_object_by_key(&id, version)?
+        .ok_or_else(|| anyhow!("Missing object {id} at version {version}"))
+}
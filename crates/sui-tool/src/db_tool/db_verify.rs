@@ -0,0 +1,684 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cross-checks the tables of a node's DB against each other, as of the highest executed
+//! checkpoint. The node must be stopped while the DB is verified: transactions that are executed
+//! concurrently, or that were executed ahead of the highest executed checkpoint, are reported as
+//! inconsistencies.
+
+use super::VerifyOptions;
+use anyhow::bail;
+use fastcrypto::hash::MultisetHash;
+use std::fmt::Debug;
+use std::path::Path;
+use sui_core::authority::authority_per_epoch_store::AuthorityEpochTables;
+use sui_core::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
+use sui_core::checkpoints::CheckpointStore;
+use sui_core::jsonrpc_index::{
+    CoinIndexKey2, CoinInfo, IndexStoreTables, IndexStoreTablesReadOnly,
+};
+use sui_core::rest_index::{OwnerIndexInfo, RestIndexStore};
+use sui_core::state_accumulator::StateAccumulator;
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::{ObjectID, ObjectInfo, ObjectRef};
+use sui_types::digests::TransactionDigest;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::message_envelope::Message;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::{Object, Owner};
+use tracing::info;
+use typed_store::rocks::MetricConf;
+use typed_store::traits::Map;
+
+/// The outcome of a single consistency check.
+struct CheckReport {
+    name: &'static str,
+    checked: u64,
+    skipped: u64,
+    mismatches: u64,
+    reported: Vec<String>,
+    max_reported: usize,
+}
+
+impl CheckReport {
+    fn new(name: &'static str, max_reported: usize) -> Self {
+        Self {
+            name,
+            checked: 0,
+            skipped: 0,
+            mismatches: 0,
+            reported: vec![],
+            max_reported,
+        }
+    }
+
+    fn mismatch(&mut self, message: String) {
+        self.mismatches += 1;
+        if self.reported.len() < self.max_reported {
+            self.reported.push(message);
+        }
+    }
+
+    fn print(&self) {
+        println!(
+            "{}: checked {}, skipped {}, {} mismatches",
+            self.name, self.checked, self.skipped, self.mismatches
+        );
+        for message in &self.reported {
+            println!("  {message}");
+        }
+        if self.mismatches > self.reported.len() as u64 {
+            println!(
+                "  ... and {} more",
+                self.mismatches - self.reported.len() as u64
+            );
+        }
+    }
+}
+
+pub fn verify_db(path: &Path, opt: VerifyOptions) -> anyhow::Result<()> {
+    let perpetual_db = AuthorityPerpetualTables::open(&path.join("store"), None);
+    let checkpoint_store = CheckpointStore::new(&path.join("checkpoints"));
+
+    let Some(highest_executed) = checkpoint_store.get_highest_executed_checkpoint_seq_number()?
+    else {
+        bail!("No checkpoint has been executed, there is nothing to verify");
+    };
+    // Transactions and effects of pruned checkpoints are no longer available.
+    let highest_pruned = perpetual_db.get_highest_pruned_checkpoint()?;
+    let lowest_available = if highest_pruned > 0 {
+        highest_pruned + 1
+    } else {
+        0
+    };
+    let from_checkpoint = opt.from_checkpoint.unwrap_or(0).max(lowest_available);
+    println!("Verifying checkpoints {from_checkpoint} to {highest_executed}");
+
+    let mut reports = vec![];
+    let (contents_report, effects_report) = verify_checkpoint_range(
+        &perpetual_db,
+        &checkpoint_store,
+        from_checkpoint,
+        highest_executed,
+        opt.max_reported_mismatches,
+    )?;
+    reports.push(contents_report);
+    reports.push(effects_report);
+
+    let mut live_objects = LiveObjectChecks::new(
+        path,
+        &opt,
+        &checkpoint_store,
+        from_checkpoint,
+        highest_executed,
+    )?;
+    live_objects.run(&perpetual_db, highest_pruned > 0)?;
+    reports.extend(live_objects.finish(
+        path,
+        &perpetual_db,
+        &checkpoint_store,
+        highest_executed,
+    )?);
+
+    let mut inconsistencies = 0;
+    for report in &reports {
+        report.print();
+        inconsistencies += report.mismatches;
+    }
+    if inconsistencies > 0 {
+        bail!("Found {inconsistencies} inconsistencies");
+    }
+    println!("No inconsistencies found");
+    Ok(())
+}
+
+/// Checks that the transactions of every checkpoint in the range were executed with the effects
+/// listed in the checkpoint contents, and that the live object table reflects those effects.
+fn verify_checkpoint_range(
+    perpetual_db: &AuthorityPerpetualTables,
+    checkpoint_store: &CheckpointStore,
+    from_checkpoint: CheckpointSequenceNumber,
+    to_checkpoint: CheckpointSequenceNumber,
+    max_reported: usize,
+) -> anyhow::Result<(CheckReport, CheckReport)> {
+    let mut contents_report = CheckReport::new("checkpoint contents", max_reported);
+    let mut effects_report = CheckReport::new("executed effects", max_reported);
+
+    for seq in from_checkpoint..=to_checkpoint {
+        let Some(checkpoint) = checkpoint_store.get_checkpoint_by_sequence_number(seq)? else {
+            contents_report.mismatch(format!("checkpoint {seq} is missing"));
+            continue;
+        };
+        let Some(contents) =
+            checkpoint_store.get_checkpoint_contents(&checkpoint.content_digest)?
+        else {
+            contents_report.mismatch(format!(
+                "contents {:?} of checkpoint {seq} are missing",
+                checkpoint.content_digest
+            ));
+            continue;
+        };
+        if seq % 10_000 == 0 {
+            info!("Verifying checkpoint {seq}");
+        }
+
+        for digests in contents.iter() {
+            contents_report.checked += 1;
+            let tx_digest = digests.transaction;
+
+            if perpetual_db.get_transaction(&tx_digest)?.is_none() {
+                contents_report.mismatch(format!(
+                    "transaction {tx_digest:?} of checkpoint {seq} is missing"
+                ));
+            }
+            if let Some((epoch, executed_in)) =
+                perpetual_db.get_checkpoint_sequence_number(&tx_digest)?
+            {
+                if (epoch, executed_in) != (checkpoint.epoch(), seq) {
+                    contents_report.mismatch(format!(
+                        "transaction {tx_digest:?} of checkpoint {seq} (epoch {}) is recorded as executed in checkpoint {executed_in} (epoch {epoch})",
+                        checkpoint.epoch()
+                    ));
+                }
+            }
+            let Some(effects) = perpetual_db.get_effects(&tx_digest)? else {
+                contents_report.mismatch(format!(
+                    "transaction {tx_digest:?} of checkpoint {seq} has not been executed"
+                ));
+                continue;
+            };
+            if effects.digest() != digests.effects {
+                contents_report.mismatch(format!(
+                    "transaction {tx_digest:?} of checkpoint {seq} was executed with effects {:?}, but the checkpoint contains effects {:?}",
+                    effects.digest(),
+                    digests.effects
+                ));
+                continue;
+            }
+
+            let written = effects
+                .created()
+                .into_iter()
+                .chain(effects.mutated())
+                .chain(effects.unwrapped())
+                .map(|(object_ref, _)| object_ref)
+                .chain(effects.deleted())
+                .chain(effects.wrapped());
+            for object_ref in written {
+                effects_report.checked += 1;
+                let (id, version, digest) = object_ref;
+                match perpetual_db.get_latest_object_ref_or_tombstone(id)? {
+                    Some((_, latest_version, latest_digest)) => {
+                        if latest_version < version {
+                            effects_report.mismatch(format!(
+                                "transaction {tx_digest:?} wrote object {id} at version {version}, but the latest version in the object table is {latest_version}"
+                            ));
+                        } else if latest_version == version && latest_digest != digest {
+                            effects_report.mismatch(format!(
+                                "transaction {tx_digest:?} wrote object {id} at version {version} with digest {digest}, but the object table has digest {latest_digest}"
+                            ));
+                        }
+                    }
+                    // Tombstones of deleted and wrapped objects may have been pruned.
+                    None if digest.is_alive() => {
+                        effects_report.mismatch(format!(
+                            "transaction {tx_digest:?} wrote object {id} at version {version}, but the object is missing from the object table"
+                        ));
+                    }
+                    None => effects_report.skipped += 1,
+                }
+            }
+        }
+    }
+
+    Ok((contents_report, effects_report))
+}
+
+/// Checks that are computed in a single pass over the live object set.
+///
+/// The live object set is ordered by object ID while the owner indexes are ordered by owner, so
+/// the two can't be merged in one pass. Instead, the entry of every live address-owned object is
+/// looked up in the indexes while the live object set is read, and the live object of every index
+/// entry is looked up in `finish`, so that the memory used doesn't grow with the size of the DB.
+struct LiveObjectChecks {
+    max_reported: usize,
+    previous_transaction_report: CheckReport,
+    /// Accumulators of the live objects, and of the wrapped object tombstones, which are only part
+    /// of the state hash in older protocol versions.
+    accumulators: Option<(Accumulator, Accumulator)>,
+    /// The JSON-RPC index, with the reports of its owner and coin index checks.
+    jsonrpc_index: Option<(IndexStoreTablesReadOnly, CheckReport, CheckReport)>,
+    /// The REST index, with the report of its owner index check.
+    rest_index: Option<(RestIndexStore, CheckReport)>,
+    /// Reports of the checks of the indexes' transaction tables, computed from checkpoints.
+    index_transaction_reports: Vec<CheckReport>,
+}
+
+impl LiveObjectChecks {
+    fn new(
+        path: &Path,
+        opt: &VerifyOptions,
+        checkpoint_store: &CheckpointStore,
+        from_checkpoint: CheckpointSequenceNumber,
+        to_checkpoint: CheckpointSequenceNumber,
+    ) -> anyhow::Result<Self> {
+        let max_reported = opt.max_reported_mismatches;
+        let mut checks = Self {
+            max_reported,
+            previous_transaction_report: CheckReport::new(
+                "live object previous transactions",
+                max_reported,
+            ),
+            accumulators: (!opt.skip_accumulator)
+                .then(|| (Accumulator::default(), Accumulator::default())),
+            jsonrpc_index: None,
+            rest_index: None,
+            index_transaction_reports: vec![],
+        };
+        if opt.skip_indexes {
+            return Ok(checks);
+        }
+
+        let jsonrpc_path = path.join("indexes");
+        if jsonrpc_path.exists() {
+            let tables = IndexStoreTables::get_read_only_handle(
+                jsonrpc_path,
+                None,
+                None,
+                MetricConf::default(),
+            );
+            let mut report = CheckReport::new("jsonrpc_index transactions", max_reported);
+            for_each_transaction(
+                checkpoint_store,
+                from_checkpoint,
+                to_checkpoint,
+                |seq, tx| {
+                    report.checked += 1;
+                    if tables.transactions_seq.get(tx)?.is_none() {
+                        report.mismatch(format!(
+                            "transaction {tx:?} of checkpoint {seq} is not indexed"
+                        ));
+                    }
+                    Ok(())
+                },
+            )?;
+            checks.index_transaction_reports.push(report);
+            checks.jsonrpc_index = Some((
+                tables,
+                CheckReport::new("jsonrpc_index owner_index", max_reported),
+                CheckReport::new("jsonrpc_index coin_index", max_reported),
+            ));
+        } else {
+            println!("jsonrpc_index not found, skipping");
+        }
+
+        let rest_path = path.join("rest_index");
+        if rest_path.exists() {
+            let rest_index = RestIndexStore::new_without_init(rest_path);
+            let mut report = CheckReport::new("rest_index transactions", max_reported);
+            for_each_transaction(
+                checkpoint_store,
+                from_checkpoint,
+                to_checkpoint,
+                |seq, tx| {
+                    report.checked += 1;
+                    match rest_index.get_transaction_info(tx)? {
+                        None => report.mismatch(format!(
+                            "transaction {tx:?} of checkpoint {seq} is not indexed"
+                        )),
+                        Some(info) if info.checkpoint != seq => report.mismatch(format!(
+                            "transaction {tx:?} of checkpoint {seq} is indexed in checkpoint {}",
+                            info.checkpoint
+                        )),
+                        Some(_) => {}
+                    }
+                    Ok(())
+                },
+            )?;
+            checks.index_transaction_reports.push(report);
+            checks.rest_index = Some((
+                rest_index,
+                CheckReport::new("rest_index owner", max_reported),
+            ));
+        } else {
+            println!("rest_index not found, skipping");
+        }
+
+        Ok(checks)
+    }
+
+    fn run(
+        &mut self,
+        perpetual_db: &AuthorityPerpetualTables,
+        is_pruned: bool,
+    ) -> anyhow::Result<()> {
+        info!("Reading live object set");
+        for live_object in perpetual_db.iter_live_object_set(true) {
+            if let Some((objects, wrapped)) = &mut self.accumulators {
+                match &live_object {
+                    LiveObject::Normal(_) => {
+                        StateAccumulator::accumulate_live_object(objects, &live_object)
+                    }
+                    LiveObject::Wrapped(_) => {
+                        StateAccumulator::accumulate_live_object(wrapped, &live_object)
+                    }
+                }
+            }
+
+            let LiveObject::Normal(object) = live_object else {
+                continue;
+            };
+            let object_ref = object.compute_object_reference();
+            self.check_previous_transaction(perpetual_db, object_ref, &object, is_pruned)?;
+
+            let Owner::AddressOwner(owner) = object.owner else {
+                continue;
+            };
+            if let Some((tables, owner_report, coin_report)) = &mut self.jsonrpc_index {
+                let key = (owner, object.id());
+                let actual = tables.owner_index.get(&key)?;
+                compare_entry(
+                    owner_report,
+                    key,
+                    ObjectInfo::new(&object_ref, &object),
+                    actual,
+                );
+                if let Some((key, info)) = coin_index_entry(&object) {
+                    let actual = tables.coin_index_2.get(&key)?;
+                    compare_entry(coin_report, key, info, actual);
+                }
+            }
+            if let Some((rest_index, report)) = &mut self.rest_index {
+                let actual = rest_index.get_owner_index_info(owner, object.id())?;
+                compare_entry(
+                    report,
+                    (owner, object.id()),
+                    OwnerIndexInfo::new(&object),
+                    actual,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the effects of the transaction that last wrote a live object agree with it.
+    fn check_previous_transaction(
+        &mut self,
+        perpetual_db: &AuthorityPerpetualTables,
+        object_ref: ObjectRef,
+        object: &Object,
+        is_pruned: bool,
+    ) -> anyhow::Result<()> {
+        let report = &mut self.previous_transaction_report;
+        let tx_digest = object.previous_transaction;
+        let (id, version, digest) = object_ref;
+
+        let Some(effects) = perpetual_db.get_effects(&tx_digest)? else {
+            if is_pruned {
+                report.skipped += 1;
+            } else {
+                report.mismatch(format!(
+                    "object {id} at version {version} was written by transaction {tx_digest:?}, which has not been executed"
+                ));
+            }
+            return Ok(());
+        };
+
+        report.checked += 1;
+        let written = effects
+            .created()
+            .into_iter()
+            .chain(effects.mutated())
+            .chain(effects.unwrapped())
+            .any(|(written_ref, _)| written_ref == object_ref);
+        if !written {
+            report.mismatch(format!(
+                "object {id} at version {version} with digest {digest} is not among the objects written by its previous transaction {tx_digest:?}"
+            ));
+        }
+        Ok(())
+    }
+
+    fn finish(
+        self,
+        path: &Path,
+        perpetual_db: &AuthorityPerpetualTables,
+        checkpoint_store: &CheckpointStore,
+        highest_executed: CheckpointSequenceNumber,
+    ) -> anyhow::Result<Vec<CheckReport>> {
+        let max_reported = self.max_reported;
+        let mut reports = vec![self.previous_transaction_report];
+
+        if let Some((objects, wrapped)) = self.accumulators {
+            reports.push(verify_accumulator(
+                path,
+                perpetual_db,
+                checkpoint_store,
+                highest_executed,
+                objects,
+                wrapped,
+                max_reported,
+            )?);
+        }
+
+        // Entries of live objects were compared while reading the live object set, so only the
+        // entries that don't belong to a live object owned by the indexed owner are left to find.
+        if let Some((tables, mut owner_report, mut coin_report)) = self.jsonrpc_index {
+            for (key, value) in tables.owner_index.unbounded_iter() {
+                let (owner, id) = key;
+                let live = get_live_object(perpetual_db, id)?;
+                if !live.is_some_and(|object| object.owner == Owner::AddressOwner(owner)) {
+                    owner_report.mismatch(format!("unexpected entry {key:?}: {value:?}"));
+                }
+            }
+            for (key, value) in tables.coin_index_2.unbounded_iter() {
+                let live = get_live_object(perpetual_db, key.object_id)?;
+                let expected = live.as_ref().and_then(coin_index_entry);
+                if !expected.is_some_and(|(expected_key, _)| expected_key == key) {
+                    coin_report.mismatch(format!("unexpected entry {key:?}: {value:?}"));
+                }
+            }
+            reports.push(owner_report);
+            reports.push(coin_report);
+        }
+
+        if let Some((rest_index, mut report)) = self.rest_index {
+            for (key, value) in rest_index.owner_index_iter() {
+                let live = get_live_object(perpetual_db, key.object_id)?;
+                if !live.is_some_and(|object| object.owner == Owner::AddressOwner(key.owner)) {
+                    report.mismatch(format!("unexpected entry {key:?}: {value:?}"));
+                }
+            }
+            reports.push(report);
+        }
+
+        reports.extend(self.index_transaction_reports);
+        Ok(reports)
+    }
+}
+
+/// Compares the live object set against the state accumulator as of the highest executed
+/// checkpoint: the root state hash if it is the last checkpoint of its epoch, or the running root
+/// accumulator otherwise.
+fn verify_accumulator(
+    path: &Path,
+    perpetual_db: &AuthorityPerpetualTables,
+    checkpoint_store: &CheckpointStore,
+    highest_executed: CheckpointSequenceNumber,
+    objects: Accumulator,
+    wrapped: Accumulator,
+    max_reported: usize,
+) -> anyhow::Result<CheckReport> {
+    let mut report = CheckReport::new("state accumulator", max_reported);
+    let Some(checkpoint) = checkpoint_store.get_checkpoint_by_sequence_number(highest_executed)?
+    else {
+        report.mismatch(format!("checkpoint {highest_executed} is missing"));
+        return Ok(report);
+    };
+    let epoch = checkpoint.epoch();
+
+    let expected = match perpetual_db.get_root_state_hash(epoch)? {
+        Some((last_checkpoint, root)) if last_checkpoint == highest_executed => Some(root),
+        _ => AuthorityEpochTables::open_readonly(epoch, &path.join("store"))
+            .running_root_accumulators
+            .get(&highest_executed)?,
+    };
+    let Some(expected) = expected else {
+        println!("No state accumulator was found for checkpoint {highest_executed}, skipping");
+        report.skipped += 1;
+        return Ok(report);
+    };
+
+    report.checked += 1;
+    let mut with_wrapped = objects.clone();
+    with_wrapped.union(&wrapped);
+    if expected != objects && expected != with_wrapped {
+        report.mismatch(format!(
+            "the live object set does not match the state accumulator of checkpoint {highest_executed} (epoch {epoch})"
+        ));
+    }
+    Ok(report)
+}
+
+/// Compares the entry of an index table against the entry expected from a live object.
+fn compare_entry<K: Debug, V: PartialEq + Debug>(
+    report: &mut CheckReport,
+    key: K,
+    expected: V,
+    actual: Option<V>,
+) {
+    report.checked += 1;
+    match actual {
+        None => report.mismatch(format!("missing entry {key:?}: {expected:?}")),
+        Some(actual) if actual != expected => report.mismatch(format!(
+            "entry {key:?} is {actual:?}, expected {expected:?}"
+        )),
+        Some(_) => {}
+    }
+}
+
+/// The entry of the JSON-RPC coin index expected for an address-owned object, if it is a coin.
+fn coin_index_entry(object: &Object) -> Option<(CoinIndexKey2, CoinInfo)> {
+    let Owner::AddressOwner(owner) = object.owner else {
+        return None;
+    };
+    let type_tag = object.coin_type_maybe()?;
+    let info = CoinInfo::from_object(object).expect("already checked that this is a coin type");
+    let key = CoinIndexKey2::new(owner, type_tag.to_string(), info.balance, object.id());
+    Some((key, info))
+}
+
+/// The latest version of an object, if it is live.
+fn get_live_object(
+    perpetual_db: &AuthorityPerpetualTables,
+    id: ObjectID,
+) -> anyhow::Result<Option<Object>> {
+    let Some((key, store_object)) = perpetual_db.get_latest_object_or_tombstone(id)? else {
+        return Ok(None);
+    };
+    Ok(perpetual_db.object(&key, store_object)?)
+}
+
+fn for_each_transaction(
+    checkpoint_store: &CheckpointStore,
+    from_checkpoint: CheckpointSequenceNumber,
+    to_checkpoint: CheckpointSequenceNumber,
+    mut f: impl FnMut(CheckpointSequenceNumber, &TransactionDigest) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    for seq in from_checkpoint..=to_checkpoint {
+        // Missing checkpoints and contents are reported by `verify_checkpoint_range`.
+        let Some(checkpoint) = checkpoint_store.get_checkpoint_by_sequence_number(seq)? else {
+            continue;
+        };
+        let Some(contents) =
+            checkpoint_store.get_checkpoint_contents(&checkpoint.content_digest)?
+        else {
+            continue;
+        };
+        for digests in contents.iter() {
+            f(seq, &digests.transaction)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sui_core::jsonrpc_index::IndexStore;
+    use sui_types::base_types::{SequenceNumber, SuiAddress};
+
+    #[tokio::test]
+    async fn live_object_checks_report_corrupted_index_entries() -> anyhow::Result<()> {
+        let path = tempfile::tempdir()?;
+        let perpetual_db = AuthorityPerpetualTables::open(&path.path().join("store"), None);
+        let checkpoint_store = CheckpointStore::new(&path.path().join("checkpoints"));
+
+        let owner = SuiAddress::random_for_testing_only();
+        let coins: Vec<_> = [100, 200, 300]
+            .into_iter()
+            .map(|balance| {
+                Object::with_id_owner_gas_for_testing(ObjectID::random(), owner, balance)
+            })
+            .collect();
+        for coin in &coins {
+            perpetual_db.insert_object_test_only(coin.clone())?;
+        }
+        let not_live = Object::with_id_owner_gas_for_testing(ObjectID::random(), owner, 400);
+        let (id, _, digest) = coins[0].compute_object_reference();
+        let (key, _) = coin_index_entry(&coins[1]).unwrap();
+
+        {
+            let index = IndexStore::new_without_init(
+                path.path().join("indexes"),
+                &prometheus::Registry::new(),
+                None,
+                false,
+            );
+            let tables = index.tables();
+            for coin in coins.iter().chain([&not_live]) {
+                let info = ObjectInfo::new(&coin.compute_object_reference(), coin);
+                tables.owner_index().insert(&(owner, coin.id()), &info)?;
+                let (key, info) = coin_index_entry(coin).unwrap();
+                tables.coin_index().insert(&key, &info)?;
+            }
+
+            // An owner index entry at the wrong version, and a missing coin index entry.
+            let stale = ObjectInfo::new(&(id, SequenceNumber::from(7), digest), &coins[0]);
+            tables.owner_index().insert(&(owner, id), &stale)?;
+            tables.coin_index().remove(&key)?;
+        }
+
+        let opt = VerifyOptions {
+            from_checkpoint: None,
+            skip_indexes: false,
+            skip_accumulator: true,
+            max_reported_mismatches: 10,
+        };
+        // An empty checkpoint range, only the live object set is checked.
+        let mut checks = LiveObjectChecks::new(path.path(), &opt, &checkpoint_store, 1, 0)?;
+        checks.run(&perpetual_db, true)?;
+        let reports = checks.finish(path.path(), &perpetual_db, &checkpoint_store, 0)?;
+
+        let report = |name| {
+            reports
+                .iter()
+                .find(|report| report.name == name)
+                .expect("report is missing")
+        };
+        let owner_index = report("jsonrpc_index owner_index");
+        assert_eq!((owner_index.checked, owner_index.mismatches), (3, 2));
+        assert!(owner_index.reported[0].starts_with(&format!("entry {:?}", (owner, id))));
+        assert!(owner_index.reported[1].starts_with("unexpected entry"));
+
+        let coin_index = report("jsonrpc_index coin_index");
+        assert_eq!((coin_index.checked, coin_index.mismatches), (3, 2));
+        assert!(coin_index.reported[0].starts_with(&format!("missing entry {key:?}")));
+        assert!(coin_index.reported[1].starts_with("unexpected entry"));
+
+        assert!(reports
+            .iter()
+            .all(|report| report.name != "rest_index owner"));
+        Ok(())
+    }
+}
@@ -4,9 +4,14 @@
 use self::db_dump::{dump_table, duplicate_objects_summary, list_tables, table_summary, StoreName};
 use self::index_search::{search_index, SearchRange};
 use crate::db_tool::db_dump::{compact, print_table_metadata, prune_checkpoints, prune_objects};
+#[cfg(feature = "parquet-export")]
+use crate::db_tool::db_export::export_db_to_parquet;
+use crate::db_tool::db_verify::verify_db;
 use anyhow::{anyhow, bail};
 use clap::Parser;
 use std::path::{Path, PathBuf};
+#[cfg(feature = "parquet-export")]
+use sui_analytics_indexer::FileType;
 use sui_core::authority::authority_per_epoch_store::AuthorityEpochTables;
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::checkpoints::CheckpointStore;
@@ -17,6 +22,9 @@ use sui_types::messages_checkpoint::{CheckpointDigest, CheckpointSequenceNumber}
 use sui_types::storage::ObjectStore;
 use typed_store::rocks::MetricConf;
 pub mod db_dump;
+#[cfg(feature = "parquet-export")]
+mod db_export;
+mod db_verify;
 mod index_search;
 
 #[derive(Parser)]
@@ -41,6 +49,9 @@ pub enum DbToolCommand {
     PruneObjects,
     PruneCheckpoints,
     SetCheckpointWatermark(SetCheckpointWatermarkOptions),
+    Verify(VerifyOptions),
+    #[cfg(feature = "parquet-export")]
+    ExportParquet(ExportParquetOptions),
 }
 
 #[derive(Parser)]
@@ -177,6 +188,60 @@ pub struct SetCheckpointWatermarkOptions {
     highest_synced: Option<CheckpointSequenceNumber>,
 }
 
+/// Cross-check the tables of a stopped node's DB against each other, as of the highest executed
+/// checkpoint, and report every inconsistency found.
+/// Run with: cargo run --package sui-tool -- db-tool --db-path /opt/sui/db/authorities_db/live verify
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct VerifyOptions {
+    /// The first checkpoint whose transactions are verified. Defaults to the lowest checkpoint
+    /// that has not been pruned.
+    #[arg(long)]
+    from_checkpoint: Option<CheckpointSequenceNumber>,
+
+    /// Skip verifying the jsonrpc_index and rest_index tables.
+    #[arg(long)]
+    skip_indexes: bool,
+
+    /// Skip verifying the state accumulator against the live object set.
+    #[arg(long)]
+    skip_accumulator: bool,
+
+    /// The maximum number of mismatches printed for each check.
+    #[arg(long, default_value_t = 100)]
+    max_reported_mismatches: usize,
+}
+
+/// Export the tables of a stopped node's DB for a range of checkpoints to parquet files, with the
+/// same schemas as the analytics indexer.
+/// Run with: cargo run --package sui-tool --features parquet-export -- db-tool --db-path /opt/sui/db/authorities_db/live export-parquet --table object --output-dir /tmp/export
+#[cfg(feature = "parquet-export")]
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct ExportParquetOptions {
+    /// The analytics tables to export, can be repeated. Transaction effects are exported as part
+    /// of the `transaction` and `transaction-objects` tables.
+    #[arg(long = "table", value_enum, required = true)]
+    tables: Vec<FileType>,
+
+    /// The directory to write the parquet files to, laid out like the analytics indexer output.
+    #[arg(long)]
+    output_dir: PathBuf,
+
+    /// The first checkpoint to export. Defaults to the lowest checkpoint that has not been pruned.
+    #[arg(long)]
+    start_checkpoint: Option<CheckpointSequenceNumber>,
+
+    /// The checkpoint to stop exporting at (exclusive). Defaults to the checkpoint after the
+    /// highest executed checkpoint.
+    #[arg(long)]
+    end_checkpoint: Option<CheckpointSequenceNumber>,
+
+    /// The number of checkpoints to write to each file. Files are also cut at epoch boundaries.
+    #[arg(long, default_value = "10000")]
+    checkpoint_interval: u64,
+}
+
 pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::Result<()> {
     match cmd {
         DbToolCommand::ListTables => print_db_all_tables(db_path),
@@ -233,6 +298,9 @@ pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> an
             Ok(())
         }
         DbToolCommand::SetCheckpointWatermark(d) => set_checkpoint_watermark(&db_path, d),
+        DbToolCommand::Verify(d) => verify_db(&db_path, d),
+        #[cfg(feature = "parquet-export")]
+        DbToolCommand::ExportParquet(d) => export_db_to_parquet(&db_path, d).await,
     }
 }
 
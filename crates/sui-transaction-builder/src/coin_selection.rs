@@ -0,0 +1,256 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail};
+use sui_json_rpc_types::{ProtocolConfigResponse, SuiProtocolConfigValue};
+use sui_types::base_types::{ObjectID, ObjectRef};
+use sui_types::transaction::TransactionKind;
+
+/// Upper bound on the number of bytes an owned coin adds to a transaction when it is used as an
+/// input, used to keep planned transactions under the transaction size limit.
+const COIN_INPUT_SIZE_BYTES: u64 = 100;
+
+/// A coin owned by the sender, along with its balance.
+#[derive(Clone, Debug)]
+pub struct PlannerCoin {
+    pub object_ref: ObjectRef,
+    pub balance: u64,
+}
+
+/// Limits that coin selection works within, so that the transactions it plans can be executed.
+#[derive(Clone, Debug)]
+pub struct CoinSelectionLimits {
+    /// Maximum number of coins used by a single transaction.
+    pub max_coins_per_transaction: usize,
+    /// Maximum number of coins merged into another coin by a single `MergeCoins` command.
+    pub max_coins_per_merge: usize,
+    /// Coins whose balance is at most this amount are dust: they are merged into the payment when
+    /// the transaction has room for them, even if they are not needed to reach the target amount.
+    pub dust_threshold: u64,
+}
+
+/// The outcome of selecting coins to reach a target amount.
+#[derive(Clone, Debug)]
+pub enum CoinSelection {
+    /// The target amount can be reached with coins that fit in a single transaction. Coins are
+    /// listed by decreasing balance, followed by dust coins.
+    Ready(Vec<PlannerCoin>),
+    /// Reaching the target amount needs more coins than fit in a single transaction. Each batch
+    /// should be merged into its first coin, before selecting coins again.
+    Consolidate(Vec<Vec<PlannerCoin>>),
+}
+
+/// A transaction planned by [`crate::TransactionBuilder::plan_pay`], to be turned into
+/// `TransactionData` by [`crate::TransactionBuilder::planned_tx_data`].
+#[derive(Clone, Debug)]
+pub struct PlannedTransaction {
+    pub kind: TransactionKind,
+    /// Coins to pay for gas with. If empty, a gas coin is selected when building the transaction.
+    pub gas_payment: Vec<ObjectID>,
+}
+
+/// The transactions that pay a set of recipients in some coin type.
+#[derive(Clone, Debug)]
+pub enum CoinPlan {
+    /// The payment fits in a single transaction.
+    Pay(PlannedTransaction),
+    /// The payment needs too many coins to fit in a single transaction. These transactions merge
+    /// coins together, they only touch distinct coins and can be executed in any order, after
+    /// which the payment needs to be planned again.
+    Consolidate(Vec<PlannedTransaction>),
+}
+
+impl CoinSelectionLimits {
+    /// Limits for coins that are used as regular transaction inputs.
+    pub fn for_inputs(
+        config: &ProtocolConfigResponse,
+        dust_threshold: u64,
+    ) -> anyhow::Result<Self> {
+        let max_inputs = (config_value(config, "max_input_objects")? as usize)
+            // Leave room for the gas coin.
+            .saturating_sub(1)
+            .min((config_value(config, "max_tx_size_bytes")? / COIN_INPUT_SIZE_BYTES) as usize);
+
+        Ok(Self {
+            max_coins_per_transaction: max_inputs.max(1),
+            // The coin being merged into also counts towards the command's arguments.
+            max_coins_per_merge: (config_value(config, "max_arguments")? as usize)
+                .saturating_sub(1)
+                .max(1),
+            dust_threshold,
+        })
+    }
+
+    /// Limits for SUI coins that are used to pay for gas, and are merged by gas smashing.
+    pub fn for_gas(config: &ProtocolConfigResponse, dust_threshold: u64) -> anyhow::Result<Self> {
+        Ok(Self {
+            max_coins_per_transaction: (config_value(config, "max_gas_payment_objects")? as usize)
+                .max(1),
+            ..Self::for_inputs(config, dust_threshold)?
+        })
+    }
+}
+
+/// Read the integer attribute `name` from a protocol config returned by the RPC, so that limits
+/// come from the chain, even if its protocol version is newer than the one this crate knows about.
+fn config_value(config: &ProtocolConfigResponse, name: &str) -> anyhow::Result<u64> {
+    match config.attributes.get(name) {
+        Some(Some(SuiProtocolConfigValue::U16(value))) => Ok(*value as u64),
+        Some(Some(SuiProtocolConfigValue::U32(value))) => Ok(*value as u64),
+        Some(Some(SuiProtocolConfigValue::U64(value))) => Ok(*value),
+        Some(Some(value)) => bail!("Protocol config attribute {name} is not an integer: {value:?}"),
+        Some(None) | None => Err(anyhow!(
+            "Protocol config attribute {name} is not set for protocol version {}",
+            config.protocol_version.as_u64()
+        )),
+    }
+}
+
+/// Select coins from `coins` whose balances add up to at least `target`, using as few coins as
+/// possible, by picking coins in decreasing order of balance. Dust coins are added to the
+/// selection while the transaction has room for them, to reduce fragmentation.
+///
+/// Fails if the total balance of `coins` is less than `target`.
+pub fn select_coins(
+    mut coins: Vec<PlannerCoin>,
+    target: u64,
+    limits: &CoinSelectionLimits,
+) -> anyhow::Result<CoinSelection> {
+    let total: u128 = coins.iter().map(|c| c.balance as u128).sum();
+    if total < target as u128 || coins.is_empty() {
+        bail!("Insufficient balance: needed {target}, but coins only add up to {total}");
+    }
+
+    // Largest coins first, breaking ties by ID so that selection is deterministic.
+    coins.sort_by(|a, b| {
+        b.balance
+            .cmp(&a.balance)
+            .then_with(|| a.object_ref.0.cmp(&b.object_ref.0))
+    });
+
+    let mut selected = 0;
+    let mut sum = 0u128;
+    while selected == 0 || sum < target as u128 {
+        sum += coins[selected].balance as u128;
+        selected += 1;
+    }
+
+    let max_coins = limits.max_coins_per_transaction;
+    let mut rest = coins.split_off(selected);
+    if selected > max_coins {
+        let batches = coins
+            .chunks(max_coins)
+            // A batch of one coin has nothing to merge.
+            .filter(|batch| batch.len() > 1)
+            .map(|batch| batch.to_vec())
+            .collect();
+        return Ok(CoinSelection::Consolidate(batches));
+    }
+
+    // Add dust, smallest first, while there is room for it.
+    rest.reverse();
+    coins.extend(
+        rest.into_iter()
+            .take_while(|c| c.balance <= limits.dust_threshold)
+            .take(max_coins - selected),
+    );
+
+    Ok(CoinSelection::Ready(coins))
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::base_types::random_object_ref;
+
+    use super::*;
+
+    fn coins(balances: &[u64]) -> Vec<PlannerCoin> {
+        balances
+            .iter()
+            .map(|balance| PlannerCoin {
+                object_ref: random_object_ref(),
+                balance: *balance,
+            })
+            .collect()
+    }
+
+    fn limits(max_coins_per_transaction: usize, dust_threshold: u64) -> CoinSelectionLimits {
+        CoinSelectionLimits {
+            max_coins_per_transaction,
+            max_coins_per_merge: 2,
+            dust_threshold,
+        }
+    }
+
+    fn balances(coins: &[PlannerCoin]) -> Vec<u64> {
+        coins.iter().map(|coin| coin.balance).collect()
+    }
+
+    #[test]
+    fn selects_largest_coins_first() {
+        let CoinSelection::Ready(selected) =
+            select_coins(coins(&[10, 50, 30, 20]), 70, &limits(10, 0)).unwrap()
+        else {
+            panic!("expected coins to fit in a single transaction");
+        };
+
+        assert_eq!(balances(&selected), vec![50, 30]);
+    }
+
+    #[test]
+    fn selects_at_least_one_coin() {
+        let CoinSelection::Ready(selected) =
+            select_coins(coins(&[10, 50]), 0, &limits(10, 0)).unwrap()
+        else {
+            panic!("expected coins to fit in a single transaction");
+        };
+
+        assert_eq!(balances(&selected), vec![50]);
+    }
+
+    #[test]
+    fn adds_dust_smallest_first() {
+        let CoinSelection::Ready(selected) =
+            select_coins(coins(&[100, 3, 1, 2, 40]), 100, &limits(3, 2)).unwrap()
+        else {
+            panic!("expected coins to fit in a single transaction");
+        };
+
+        // Only two dust coins fit next to the coin that reaches the target, and coins above the
+        // dust threshold are not added.
+        assert_eq!(balances(&selected), vec![100, 1, 2]);
+    }
+
+    #[test]
+    fn insufficient_balance() {
+        let err = select_coins(coins(&[10, 20]), 31, &limits(10, 0)).unwrap_err();
+        assert!(err.to_string().contains("Insufficient balance"), "{err}");
+
+        let err = select_coins(vec![], 0, &limits(10, 0)).unwrap_err();
+        assert!(err.to_string().contains("Insufficient balance"), "{err}");
+    }
+
+    #[test]
+    fn consolidates_beyond_max_inputs() {
+        let CoinSelection::Consolidate(batches) =
+            select_coins(coins(&[10; 7]), 65, &limits(3, 0)).unwrap()
+        else {
+            panic!("expected coins to need consolidation");
+        };
+
+        // The 7 coins needed are merged 3 at a time, and the last coin is left as it is.
+        let batches: Vec<_> = batches.iter().map(|batch| batch.len()).collect();
+        assert_eq!(batches, vec![3, 3]);
+    }
+
+    #[test]
+    fn max_inputs_is_respected_exactly() {
+        let CoinSelection::Ready(selected) =
+            select_coins(coins(&[10; 5]), 30, &limits(3, 10)).unwrap()
+        else {
+            panic!("expected coins to fit in a single transaction");
+        };
+
+        assert_eq!(selected.len(), 3);
+    }
+}
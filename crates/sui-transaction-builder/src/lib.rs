@@ -17,23 +17,36 @@ use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 use sui_json::{is_receiving_argument, resolve_move_function_args, ResolvedCallArg, SuiJsonValue};
 use sui_json_rpc_types::{
-    RPCTransactionRequestParams, SuiData, SuiObjectDataOptions, SuiObjectResponse, SuiRawData,
-    SuiTypeTag,
+    ProtocolConfigResponse, RPCTransactionRequestParams, SuiData, SuiObjectDataOptions,
+    SuiObjectResponse, SuiRawData, SuiTypeTag,
 };
 use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::{ObjectID, ObjectInfo, ObjectRef, ObjectType, SuiAddress};
+use sui_types::coin::Coin;
 use sui_types::error::UserInputError;
-use sui_types::gas_coin::GasCoin;
+use sui_types::gas_coin::{GasCoin, GAS};
 use sui_types::governance::{ADD_STAKE_MUL_COIN_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::move_package::MovePackage;
 use sui_types::object::{Object, Owner};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{
-    Argument, CallArg, Command, InputObjectKind, ObjectArg, TransactionData, TransactionKind,
+    Argument, CallArg, Command, InputObjectKind, ObjectArg, ProgrammableTransaction,
+    TransactionData, TransactionKind,
 };
 use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
+use crate::coin_selection::{select_coins, CoinSelection, CoinSelectionLimits, PlannerCoin};
+pub use crate::coin_selection::{CoinPlan, PlannedTransaction};
+use crate::manifest::{Manifest, ManifestBuilder};
+
+pub mod coin_selection;
+pub mod manifest;
+
+/// Maximum number of objects fetched by a single `multi_get_object_with_options` call, matching the
+/// default query limit of the JSON-RPC API.
+const MAX_OBJECTS_PER_REQUEST: usize = 50;
+
 #[async_trait]
 pub trait DataReader {
     async fn get_owned_objects(
@@ -48,7 +61,17 @@ pub trait DataReader {
         options: SuiObjectDataOptions,
     ) -> Result<SuiObjectResponse, anyhow::Error>;
 
+    /// Get several objects at once. Responses are not necessarily in the order of `object_ids`.
+    async fn multi_get_object_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>, anyhow::Error>;
+
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
+
+    /// The protocol config of the chain's current epoch.
+    async fn get_protocol_config(&self) -> Result<ProtocolConfigResponse, anyhow::Error>;
 }
 
 #[derive(Clone)]
@@ -135,14 +158,7 @@ impl TransactionBuilder {
         gas_sponsor: Option<SuiAddress>,
     ) -> Result<TransactionData, anyhow::Error> {
         let gas_payment = if gas_payment.is_empty() {
-            let input_objs = kind
-                .input_objects()?
-                .iter()
-                .flat_map(|obj| match obj {
-                    InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
-                    _ => None,
-                })
-                .collect();
+            let input_objs = owned_input_ids(&kind)?;
             vec![
                 self.select_gas(sender, None, gas_budget, input_objs, gas_price)
                     .await?,
@@ -357,6 +373,186 @@ impl TransactionBuilder {
         ))
     }
 
+    /// Plan the transactions that pay `amounts` of `coin_type` to `recipients`, selecting coins
+    /// owned by `sender` rather than requiring them to be listed explicitly. Coins with a balance
+    /// of at most `dust_threshold` are merged into the payment when there is room for them.
+    ///
+    /// When paying in SUI without a sponsor, the selected coins also pay for gas. Otherwise, gas is
+    /// selected from the coins of `gas_sponsor` (or `sender`), when the planned transaction is
+    /// built with [`Self::planned_tx_data`]. If the payment needs more coins than fit in a single
+    /// transaction, the plan consolidates coins first (see [`CoinPlan::Consolidate`]).
+    pub async fn plan_pay(
+        &self,
+        sender: SuiAddress,
+        coin_type: TypeTag,
+        recipients: Vec<SuiAddress>,
+        amounts: Vec<u64>,
+        gas_budget: u64,
+        gas_sponsor: Option<SuiAddress>,
+        dust_threshold: u64,
+    ) -> anyhow::Result<CoinPlan> {
+        ensure!(
+            recipients.len() == amounts.len(),
+            "Recipients and amounts mismatch. Got {} recipients but {} amounts",
+            recipients.len(),
+            amounts.len()
+        );
+
+        let pays_gas =
+            GAS::is_gas_type(&coin_type) && gas_sponsor.map_or(true, |sponsor| sponsor == sender);
+
+        let mut target = amounts
+            .iter()
+            .try_fold(0u64, |total, amount| total.checked_add(*amount))
+            .ok_or_else(|| anyhow!("Total amount to pay overflows u64"))?;
+        if pays_gas {
+            target = target
+                .checked_add(gas_budget)
+                .ok_or_else(|| anyhow!("Total amount to pay and gas budget overflows u64"))?;
+        }
+
+        let config = self.0.get_protocol_config().await?;
+        let limits = if pays_gas {
+            CoinSelectionLimits::for_gas(&config, dust_threshold)?
+        } else {
+            CoinSelectionLimits::for_inputs(&config, dust_threshold)?
+        };
+
+        let coins = self.owned_coins(sender, coin_type).await?;
+        match select_coins(coins, target, &limits)? {
+            CoinSelection::Ready(coins) => {
+                let mut builder = ProgrammableTransactionBuilder::new();
+                let gas_payment = if pays_gas {
+                    builder.pay_sui(recipients, amounts)?;
+                    coins.iter().map(|coin| coin.object_ref.0).collect()
+                } else {
+                    merge_coins(&mut builder, &coins, limits.max_coins_per_merge)?;
+                    builder.pay(vec![coins[0].object_ref], recipients, amounts)?;
+                    vec![]
+                };
+
+                Ok(CoinPlan::Pay(PlannedTransaction {
+                    kind: TransactionKind::programmable(builder.finish()),
+                    gas_payment,
+                }))
+            }
+
+            CoinSelection::Consolidate(batches) => {
+                let mut planned = Vec::with_capacity(batches.len());
+                for batch in batches {
+                    // A batch that is merged by gas smashing also pays for the merge, so it must
+                    // cover the gas budget. Batches are ordered by decreasing balance, so the
+                    // batches that are left out are consolidated by a later plan.
+                    let balance: u128 = batch.iter().map(|coin| coin.balance as u128).sum();
+                    if pays_gas && balance < gas_budget as u128 {
+                        continue;
+                    }
+
+                    let mut builder = ProgrammableTransactionBuilder::new();
+                    let gas_payment = if pays_gas {
+                        // Gas smashing merges the batch into its first coin.
+                        builder.pay_all_sui(sender);
+                        batch.iter().map(|coin| coin.object_ref.0).collect()
+                    } else {
+                        merge_coins(&mut builder, &batch, limits.max_coins_per_merge)?;
+                        vec![]
+                    };
+
+                    planned.push(PlannedTransaction {
+                        kind: TransactionKind::programmable(builder.finish()),
+                        gas_payment,
+                    });
+                }
+
+                ensure!(
+                    !planned.is_empty(),
+                    "Cannot consolidate coins: merging {} coins at a time does not cover the gas \
+                     budget of {gas_budget}",
+                    limits.max_coins_per_transaction
+                );
+
+                Ok(CoinPlan::Consolidate(planned))
+            }
+        }
+    }
+
+    /// Construct the transaction data for a transaction planned by [`Self::plan_pay`]. Gas coins
+    /// are fetched when the transaction is built, so that the consolidation transactions of a plan
+    /// can each be built after the previous one has been executed.
+    pub async fn planned_tx_data(
+        &self,
+        sender: SuiAddress,
+        planned: PlannedTransaction,
+        gas_budget: u64,
+        gas_sponsor: Option<SuiAddress>,
+    ) -> anyhow::Result<TransactionData> {
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_owner = gas_sponsor.unwrap_or(sender);
+        let gas_payment = if planned.gas_payment.is_empty() {
+            let input_objs = owned_input_ids(&planned.kind)?;
+            vec![
+                self.select_gas(gas_owner, None, gas_budget, input_objs, gas_price)
+                    .await?,
+            ]
+        } else {
+            self.input_refs(&planned.gas_payment).await?
+        };
+
+        Ok(TransactionData::new_with_gas_coins_allow_sponsor(
+            planned.kind,
+            sender,
+            gas_payment,
+            gas_budget,
+            gas_price,
+            gas_owner,
+        ))
+    }
+
+    /// Get the coins of type `coin_type` owned by `owner`, with their balances.
+    async fn owned_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+    ) -> anyhow::Result<Vec<PlannerCoin>> {
+        let coin_objs = self
+            .0
+            .get_owned_objects(owner, Coin::type_(coin_type))
+            .await?;
+
+        let mut responses = Vec::with_capacity(coin_objs.len());
+        for chunk in coin_objs.chunks(MAX_OBJECTS_PER_REQUEST) {
+            let object_ids = chunk.iter().map(|obj| obj.object_id).collect();
+            responses.extend(
+                self.0
+                    .multi_get_object_with_options(
+                        object_ids,
+                        SuiObjectDataOptions::new().with_bcs(),
+                    )
+                    .await?,
+            );
+        }
+
+        responses
+            .into_iter()
+            .map(|response| {
+                let obj = response.into_object()?;
+                let coin = Coin::from_bcs_bytes(
+                    &obj.bcs
+                        .as_ref()
+                        .ok_or_else(|| anyhow!("bcs field is unexpectedly empty"))?
+                        .try_as_move()
+                        .ok_or_else(|| anyhow!("Cannot parse move object to coin object"))?
+                        .bcs_bytes,
+                )?;
+
+                Ok(PlannerCoin {
+                    object_ref: obj.object_ref(),
+                    balance: coin.value(),
+                })
+            })
+            .collect()
+    }
+
     pub async fn move_call_tx_kind(
         &self,
         package_object_id: ObjectID,
@@ -570,6 +766,17 @@ impl TransactionBuilder {
         Ok(args)
     }
 
+    /// Build the programmable transaction described by `manifest`, resolving the objects it
+    /// refers to.
+    pub async fn manifest_ptb(
+        &self,
+        manifest: &Manifest,
+    ) -> anyhow::Result<ProgrammableTransaction> {
+        ManifestBuilder::new()
+            .build(self.0.as_ref(), manifest)
+            .await
+    }
+
     pub async fn publish_tx_kind(
         &self,
         sender: SuiAddress,
@@ -1083,3 +1290,397 @@ impl TransactionBuilder {
         Ok((object.object_ref(), object.object_type()?))
     }
 }
+
+/// IDs of the owned objects that `kind` takes as inputs.
+fn owned_input_ids(kind: &TransactionKind) -> anyhow::Result<Vec<ObjectID>> {
+    Ok(kind
+        .input_objects()?
+        .iter()
+        .flat_map(|obj| match obj {
+            InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Merge `coins` into the first of them, using as many `MergeCoins` commands as needed to merge at
+/// most `max_coins_per_merge` coins per command.
+fn merge_coins(
+    builder: &mut ProgrammableTransactionBuilder,
+    coins: &[PlannerCoin],
+    max_coins_per_merge: usize,
+) -> anyhow::Result<()> {
+    let Some((primary, rest)) = coins.split_first() else {
+        return Ok(());
+    };
+
+    let primary = builder.obj(ObjectArg::ImmOrOwnedObject(primary.object_ref))?;
+    for chunk in rest.chunks(max_coins_per_merge) {
+        let merged = chunk
+            .iter()
+            .map(|coin| builder.obj(ObjectArg::ImmOrOwnedObject(coin.object_ref)))
+            .collect::<anyhow::Result<_>>()?;
+        builder.command(Command::MergeCoins(primary, merged));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_json_rpc_types::{SuiObjectData, SuiProtocolConfigValue, SuiRawMoveObject};
+    use sui_protocol_config::ProtocolVersion;
+    use sui_types::base_types::{MoveObjectType, SequenceNumber};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::object::MoveObject;
+    use sui_types::transaction::TransactionDataAPI;
+
+    use super::*;
+
+    const GAS_PRICE: u64 = 1000;
+
+    /// Serves a fixed set of objects, and a protocol config with the given gas payment limit.
+    struct MockReader {
+        objects: BTreeMap<ObjectID, Object>,
+        max_gas_payment_objects: u32,
+    }
+
+    impl MockReader {
+        fn response(&self, object_id: ObjectID) -> anyhow::Result<SuiObjectResponse> {
+            let object = self
+                .objects
+                .get(&object_id)
+                .ok_or_else(|| anyhow!("Object {object_id} not found"))?;
+            let move_object = object.data.try_as_move().unwrap();
+
+            Ok(SuiObjectResponse::new_with_data(SuiObjectData {
+                object_id,
+                version: object.version(),
+                digest: object.digest(),
+                type_: Some(ObjectType::from(object)),
+                owner: Some(object.owner.clone()),
+                previous_transaction: None,
+                storage_rebate: None,
+                display: None,
+                content: None,
+                bcs: Some(SuiRawData::MoveObject(SuiRawMoveObject {
+                    type_: object.struct_tag().unwrap(),
+                    has_public_transfer: move_object.has_public_transfer(),
+                    version: object.version(),
+                    bcs_bytes: move_object.contents().to_vec(),
+                })),
+            }))
+        }
+    }
+
+    #[async_trait]
+    impl DataReader for MockReader {
+        async fn get_owned_objects(
+            &self,
+            address: SuiAddress,
+            object_type: StructTag,
+        ) -> anyhow::Result<Vec<ObjectInfo>> {
+            Ok(self
+                .objects
+                .values()
+                .filter(|object| {
+                    object.owner == Owner::AddressOwner(address)
+                        && object.struct_tag().as_ref() == Some(&object_type)
+                })
+                .map(|object| ObjectInfo::new(&object.compute_object_reference(), object))
+                .collect())
+        }
+
+        async fn get_object_with_options(
+            &self,
+            object_id: ObjectID,
+            _options: SuiObjectDataOptions,
+        ) -> anyhow::Result<SuiObjectResponse> {
+            self.response(object_id)
+        }
+
+        async fn multi_get_object_with_options(
+            &self,
+            object_ids: Vec<ObjectID>,
+            _options: SuiObjectDataOptions,
+        ) -> anyhow::Result<Vec<SuiObjectResponse>> {
+            object_ids
+                .into_iter()
+                .map(|object_id| self.response(object_id))
+                .collect()
+        }
+
+        async fn get_reference_gas_price(&self) -> anyhow::Result<u64> {
+            Ok(GAS_PRICE)
+        }
+
+        async fn get_protocol_config(&self) -> anyhow::Result<ProtocolConfigResponse> {
+            let attributes = [
+                ("max_input_objects", SuiProtocolConfigValue::U64(2048)),
+                ("max_tx_size_bytes", SuiProtocolConfigValue::U64(128 * 1024)),
+                ("max_arguments", SuiProtocolConfigValue::U32(512)),
+                (
+                    "max_gas_payment_objects",
+                    SuiProtocolConfigValue::U32(self.max_gas_payment_objects),
+                ),
+            ];
+
+            // A version newer than any this crate knows about, to check that limits are read from
+            // the response alone.
+            Ok(ProtocolConfigResponse {
+                min_supported_protocol_version: ProtocolVersion::MIN,
+                max_supported_protocol_version: ProtocolVersion::new(u64::MAX),
+                protocol_version: ProtocolVersion::new(u64::MAX),
+                feature_flags: BTreeMap::new(),
+                attributes: attributes
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), Some(value)))
+                    .collect(),
+            })
+        }
+    }
+
+    fn usd() -> TypeTag {
+        TypeTag::from_str("0x42::usd::USD").unwrap()
+    }
+
+    /// Create coins of `coin_type` owned by `owner`, returning their IDs in the order of `balances`.
+    fn add_coins(
+        objects: &mut BTreeMap<ObjectID, Object>,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        balances: &[u64],
+    ) -> Vec<ObjectID> {
+        balances
+            .iter()
+            .map(|balance| {
+                let id = ObjectID::random();
+                let coin = MoveObject::new_coin(
+                    MoveObjectType::from(Coin::type_(coin_type.clone())),
+                    SequenceNumber::from_u64(1),
+                    id,
+                    *balance,
+                );
+                let object = Object::new_move(
+                    coin,
+                    Owner::AddressOwner(owner),
+                    TransactionDigest::genesis_marker(),
+                );
+                objects.insert(id, object);
+                id
+            })
+            .collect()
+    }
+
+    fn builder(
+        objects: BTreeMap<ObjectID, Object>,
+        max_gas_payment_objects: u32,
+    ) -> TransactionBuilder {
+        TransactionBuilder::new(Arc::new(MockReader {
+            objects,
+            max_gas_payment_objects,
+        }))
+    }
+
+    #[tokio::test]
+    async fn plan_pay_sui_pays_gas_with_selected_coins() {
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let mut objects = BTreeMap::new();
+        let ids = add_coins(&mut objects, sender, GAS::type_tag(), &[30, 100, 50]);
+        let refs: Vec<_> = ids
+            .iter()
+            .map(|id| objects[id].compute_object_reference())
+            .collect();
+        let builder = builder(objects, 256);
+
+        let CoinPlan::Pay(planned) = builder
+            .plan_pay(
+                sender,
+                GAS::type_tag(),
+                vec![recipient],
+                vec![60],
+                50,
+                None,
+                0,
+            )
+            .await
+            .unwrap()
+        else {
+            panic!("expected the payment to fit in a single transaction");
+        };
+
+        // The payment and the gas budget are covered by the two largest coins, which are smashed
+        // into the gas coin rather than being used as inputs.
+        assert_eq!(planned.gas_payment, vec![ids[1], ids[2]]);
+        assert!(owned_input_ids(&planned.kind).unwrap().is_empty());
+
+        let tx_data = builder
+            .planned_tx_data(sender, planned, 50, None)
+            .await
+            .unwrap();
+        assert_eq!(tx_data.gas(), &[refs[1], refs[2]]);
+        assert_eq!(tx_data.gas_owner(), sender);
+        assert_eq!(tx_data.gas_budget(), 50);
+        assert_eq!(tx_data.gas_price(), GAS_PRICE);
+    }
+
+    #[tokio::test]
+    async fn plan_pay_coin_selects_gas_when_building() {
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let mut objects = BTreeMap::new();
+        let coins = add_coins(&mut objects, sender, usd(), &[20, 40, 30]);
+        let gas = add_coins(&mut objects, sender, GAS::type_tag(), &[1_000_000]);
+        let gas_ref = objects[&gas[0]].compute_object_reference();
+        let builder = builder(objects, 256);
+
+        let CoinPlan::Pay(planned) = builder
+            .plan_pay(sender, usd(), vec![recipient], vec![60], 10_000, None, 0)
+            .await
+            .unwrap()
+        else {
+            panic!("expected the payment to fit in a single transaction");
+        };
+
+        // The two largest coins are merged and used as inputs, and gas is left to be selected.
+        assert!(planned.gas_payment.is_empty());
+        let mut inputs = owned_input_ids(&planned.kind).unwrap();
+        inputs.sort();
+        let mut expected = vec![coins[1], coins[2]];
+        expected.sort();
+        assert_eq!(inputs, expected);
+
+        let tx_data = builder
+            .planned_tx_data(sender, planned, 10_000, None)
+            .await
+            .unwrap();
+        assert_eq!(tx_data.gas(), &[gas_ref]);
+        assert_eq!(tx_data.gas_owner(), sender);
+    }
+
+    #[tokio::test]
+    async fn plan_pay_sponsored_sui_selects_sponsor_gas() {
+        let sender = SuiAddress::random_for_testing_only();
+        let sponsor = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let mut objects = BTreeMap::new();
+        let coins = add_coins(&mut objects, sender, GAS::type_tag(), &[100]);
+        let gas = add_coins(&mut objects, sponsor, GAS::type_tag(), &[1_000_000]);
+        let gas_ref = objects[&gas[0]].compute_object_reference();
+        let builder = builder(objects, 256);
+
+        let CoinPlan::Pay(planned) = builder
+            .plan_pay(
+                sender,
+                GAS::type_tag(),
+                vec![recipient],
+                vec![60],
+                10_000,
+                Some(sponsor),
+                0,
+            )
+            .await
+            .unwrap()
+        else {
+            panic!("expected the payment to fit in a single transaction");
+        };
+
+        // The sender's coin is an input, because the sponsor pays for gas.
+        assert!(planned.gas_payment.is_empty());
+        assert_eq!(owned_input_ids(&planned.kind).unwrap(), coins);
+
+        let tx_data = builder
+            .planned_tx_data(sender, planned, 10_000, Some(sponsor))
+            .await
+            .unwrap();
+        assert_eq!(tx_data.gas(), &[gas_ref]);
+        assert_eq!(tx_data.gas_owner(), sponsor);
+        assert_eq!(tx_data.sender(), sender);
+    }
+
+    #[tokio::test]
+    async fn plan_pay_sui_consolidates_beyond_gas_payment_limit() {
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let mut objects = BTreeMap::new();
+        let ids = add_coins(&mut objects, sender, GAS::type_tag(), &[10; 5]);
+        let builder = builder(objects, 2);
+
+        // 30 to pay and a gas budget of 5 need four coins, but only two fit in a gas payment.
+        let CoinPlan::Consolidate(planned) = builder
+            .plan_pay(
+                sender,
+                GAS::type_tag(),
+                vec![recipient],
+                vec![30],
+                5,
+                None,
+                0,
+            )
+            .await
+            .unwrap()
+        else {
+            panic!("expected coins to need consolidation");
+        };
+
+        assert_eq!(planned.len(), 2);
+        let mut smashed: Vec<_> = planned
+            .iter()
+            .flat_map(|planned| {
+                assert_eq!(planned.gas_payment.len(), 2);
+                planned.gas_payment.clone()
+            })
+            .collect();
+        smashed.sort();
+        smashed.dedup();
+        assert_eq!(smashed.len(), 4);
+        assert!(smashed.iter().all(|id| ids.contains(id)));
+
+        let tx_data = builder
+            .planned_tx_data(sender, planned[0].clone(), 5, None)
+            .await
+            .unwrap();
+        assert_eq!(tx_data.gas().len(), 2);
+        assert_eq!(tx_data.gas_owner(), sender);
+    }
+
+    #[tokio::test]
+    async fn plan_pay_sui_fails_when_batches_cannot_pay_for_gas() {
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let mut objects = BTreeMap::new();
+        add_coins(&mut objects, sender, GAS::type_tag(), &[10; 5]);
+        let builder = builder(objects, 2);
+
+        // Merging two coins at a time only ever covers 20, less than the gas budget.
+        let err = builder
+            .plan_pay(
+                sender,
+                GAS::type_tag(),
+                vec![recipient],
+                vec![10],
+                25,
+                None,
+                0,
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Cannot consolidate coins"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn plan_pay_rejects_mismatched_amounts() {
+        let sender = SuiAddress::random_for_testing_only();
+        let builder = builder(BTreeMap::new(), 256);
+
+        let err = builder
+            .plan_pay(sender, usd(), vec![sender], vec![1, 2], 10_000, None, 0)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("mismatch"), "{err}");
+    }
+}
@@ -0,0 +1,666 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A declarative format for programmable transactions, that can be written by hand (as JSON or
+//! YAML), checked into source control, and turned into a [`ProgrammableTransaction`] by
+//! [`crate::TransactionBuilder::manifest_ptb`].
+//!
+//! A manifest is a list of named inputs, followed by a list of commands that map 1:1 to the
+//! commands of a programmable transaction. Command arguments refer to inputs and to the results
+//! of earlier commands by name:
+//!
+//! ```yaml
+//! inputs:
+//!   - name: amount
+//!     pure: { type: u64, value: 1000 }
+//!   - name: recipient
+//!     pure: { type: address, value: "0x42" }
+//! commands:
+//!   - split_coins: { coin: gas, amounts: [amount] }
+//!     result: coin
+//!   - transfer_objects: { objects: [coin.0], address: recipient }
+//! ```
+//!
+//! Arguments are one of:
+//!
+//! - `gas`, for the gas coin,
+//! - the name of an input,
+//! - the name of a command's result, or `<name>.<index>` to refer to one of its nested results.
+
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use move_core_types::annotated_value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout as L};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::SuiObjectDataOptions;
+use sui_types::base_types::{
+    ObjectID, ObjectRef, RESOLVED_ASCII_STR, RESOLVED_STD_OPTION, RESOLVED_UTF8_STR,
+};
+use sui_types::id::RESOLVED_SUI_ID;
+use sui_types::object::Owner;
+use sui_types::parse_sui_type_tag;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::{Argument, CallArg, Command, ObjectArg, ProgrammableTransaction};
+
+use crate::DataReader;
+
+/// The name that refers to the gas coin in command arguments.
+pub const GAS_COIN: &str = "gas";
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<ManifestInput>,
+    pub commands: Vec<ManifestCommand>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ManifestInput {
+    pub name: String,
+    #[serde(flatten)]
+    pub value: InputValue,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InputValue {
+    /// A pure value of a primitive type (integers, `bool`, `address`, `0x2::object::ID`, strings,
+    /// and vectors and options of those), written as it would be for a JSON-RPC Move call.
+    Pure {
+        #[serde(rename = "type")]
+        type_: String,
+        value: JsonValue,
+    },
+    /// A pure value, as Base64-encoded BCS bytes.
+    Bcs(String),
+    /// An object, which is used at its latest version if it is owned or immutable, and by mutable
+    /// reference if it is shared.
+    Object(ObjectID),
+    /// A shared object, used by mutable or immutable reference.
+    SharedObject { id: ObjectID, mutable: bool },
+    /// An object sent to an object that is an input to this transaction, to be received.
+    Receiving(ObjectID),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ManifestCommand {
+    /// Name to bind this command's result to, so later commands can refer to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    #[serde(flatten)]
+    pub command: CommandSpec,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandSpec {
+    MoveCall {
+        /// Fully qualified function name, `<package>::<module>::<function>`.
+        function: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        type_arguments: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        arguments: Vec<String>,
+    },
+    TransferObjects {
+        objects: Vec<String>,
+        address: String,
+    },
+    SplitCoins {
+        coin: String,
+        amounts: Vec<String>,
+    },
+    MergeCoins {
+        coin: String,
+        coins: Vec<String>,
+    },
+    MakeMoveVec {
+        #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+        type_: Option<String>,
+        elements: Vec<String>,
+    },
+    Publish {
+        /// Base64-encoded compiled modules.
+        modules: Vec<String>,
+        dependencies: Vec<ObjectID>,
+    },
+    Upgrade {
+        /// Base64-encoded compiled modules.
+        modules: Vec<String>,
+        dependencies: Vec<ObjectID>,
+        package: ObjectID,
+        ticket: String,
+    },
+}
+
+impl Manifest {
+    /// Describe an existing programmable transaction as a manifest. Pure inputs are exported as
+    /// BCS bytes, and objects are exported by ID, so building the manifest again produces the
+    /// same transaction, except that owned objects are used at their latest versions.
+    pub fn from_ptb(ptb: &ProgrammableTransaction) -> Self {
+        let inputs = ptb
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| ManifestInput {
+                name: format!("input_{i}"),
+                value: match input {
+                    CallArg::Pure(bytes) => InputValue::Bcs(Base64::encode(bytes)),
+                    CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _))) => {
+                        InputValue::Object(*id)
+                    }
+                    CallArg::Object(ObjectArg::SharedObject { id, mutable, .. }) => {
+                        InputValue::SharedObject {
+                            id: *id,
+                            mutable: *mutable,
+                        }
+                    }
+                    CallArg::Object(ObjectArg::Receiving((id, _, _))) => InputValue::Receiving(*id),
+                },
+            })
+            .collect();
+
+        // Only name the results that are referred to by later commands.
+        let used_results: BTreeSet<u16> = ptb
+            .commands
+            .iter()
+            .flat_map(command_arguments)
+            .filter_map(|arg| match arg {
+                Argument::Result(i) | Argument::NestedResult(i, _) => Some(*i),
+                Argument::GasCoin | Argument::Input(_) => None,
+            })
+            .collect();
+
+        let commands = ptb
+            .commands
+            .iter()
+            .enumerate()
+            .map(|(i, command)| ManifestCommand {
+                result: used_results
+                    .contains(&(i as u16))
+                    .then(|| format!("result_{i}")),
+                command: CommandSpec::from_command(command),
+            })
+            .collect();
+
+        Self { inputs, commands }
+    }
+}
+
+impl CommandSpec {
+    fn from_command(command: &Command) -> Self {
+        let args = |args: &[Argument]| args.iter().map(argument_name).collect();
+        let modules = |modules: &[Vec<u8>]| modules.iter().map(Base64::encode).collect();
+
+        match command {
+            Command::MoveCall(call) => CommandSpec::MoveCall {
+                function: format!("{}::{}::{}", call.package, call.module, call.function),
+                type_arguments: call
+                    .type_arguments
+                    .iter()
+                    .map(|t| t.to_canonical_string(/* with_prefix */ true))
+                    .collect(),
+                arguments: args(&call.arguments),
+            },
+            Command::TransferObjects(objects, address) => CommandSpec::TransferObjects {
+                objects: args(objects),
+                address: argument_name(address),
+            },
+            Command::SplitCoins(coin, amounts) => CommandSpec::SplitCoins {
+                coin: argument_name(coin),
+                amounts: args(amounts),
+            },
+            Command::MergeCoins(coin, coins) => CommandSpec::MergeCoins {
+                coin: argument_name(coin),
+                coins: args(coins),
+            },
+            Command::MakeMoveVec(type_, elements) => CommandSpec::MakeMoveVec {
+                type_: type_
+                    .as_ref()
+                    .map(|t| t.to_canonical_string(/* with_prefix */ true)),
+                elements: args(elements),
+            },
+            Command::Publish(modules_, dependencies) => CommandSpec::Publish {
+                modules: modules(modules_),
+                dependencies: dependencies.clone(),
+            },
+            Command::Upgrade(modules_, dependencies, package, ticket) => CommandSpec::Upgrade {
+                modules: modules(modules_),
+                dependencies: dependencies.clone(),
+                package: *package,
+                ticket: argument_name(ticket),
+            },
+        }
+    }
+}
+
+/// Builds a [`ProgrammableTransaction`] from a [`Manifest`], resolving its named arguments as it
+/// goes.
+pub(crate) struct ManifestBuilder {
+    builder: ProgrammableTransactionBuilder,
+    names: HashMap<String, Argument>,
+}
+
+impl ManifestBuilder {
+    pub(crate) fn new() -> Self {
+        Self {
+            builder: ProgrammableTransactionBuilder::new(),
+            names: HashMap::new(),
+        }
+    }
+
+    pub(crate) async fn build(
+        mut self,
+        reader: &(dyn DataReader + Sync + Send),
+        manifest: &Manifest,
+    ) -> anyhow::Result<ProgrammableTransaction> {
+        for ManifestInput { name, value } in &manifest.inputs {
+            let arg = self
+                .input(reader, value)
+                .await
+                .with_context(|| format!("Failed to resolve input '{name}'"))?;
+            self.bind(name, arg)?;
+        }
+
+        for (i, ManifestCommand { result, command }) in manifest.commands.iter().enumerate() {
+            let command = self
+                .command(command)
+                .with_context(|| format!("Failed to build command {i}"))?;
+            let arg = self.builder.command(command);
+            if let Some(name) = result {
+                self.bind(name, arg)?;
+            }
+        }
+
+        Ok(self.builder.finish())
+    }
+
+    fn bind(&mut self, name: &str, arg: Argument) -> anyhow::Result<()> {
+        ensure!(
+            name != GAS_COIN && !name.contains('.'),
+            "'{name}' cannot be used as a name"
+        );
+        ensure!(
+            self.names.insert(name.to_string(), arg).is_none(),
+            "'{name}' is bound more than once"
+        );
+        Ok(())
+    }
+
+    async fn input(
+        &mut self,
+        reader: &(dyn DataReader + Sync + Send),
+        value: &InputValue,
+    ) -> anyhow::Result<Argument> {
+        let obj_arg = match value {
+            InputValue::Pure { type_, value } => {
+                let tag = parse_sui_type_tag(type_)?;
+                let bytes = SuiJsonValue::new(value.clone())?.to_bcs_bytes(&pure_layout(&tag)?)?;
+                // Pure inputs are kept separate, so that inputs keep the indices they have in the
+                // manifest.
+                return Ok(self.builder.pure_bytes(bytes, /* force_separate */ true));
+            }
+
+            InputValue::Bcs(bytes) => {
+                let bytes = Base64::decode(bytes).map_err(|e| anyhow!("Invalid Base64: {e}"))?;
+                return Ok(self.builder.pure_bytes(bytes, /* force_separate */ true));
+            }
+
+            InputValue::Object(id) => match get_object(reader, *id).await? {
+                (
+                    _,
+                    Owner::Shared {
+                        initial_shared_version,
+                    },
+                ) => ObjectArg::SharedObject {
+                    id: *id,
+                    initial_shared_version,
+                    mutable: true,
+                },
+                (object_ref, Owner::AddressOwner(_) | Owner::ObjectOwner(_) | Owner::Immutable) => {
+                    ObjectArg::ImmOrOwnedObject(object_ref)
+                }
+            },
+
+            InputValue::SharedObject { id, mutable } => {
+                let (
+                    _,
+                    Owner::Shared {
+                        initial_shared_version,
+                    },
+                ) = get_object(reader, *id).await?
+                else {
+                    bail!("Object {id} is not shared");
+                };
+                ObjectArg::SharedObject {
+                    id: *id,
+                    initial_shared_version,
+                    mutable: *mutable,
+                }
+            }
+
+            InputValue::Receiving(id) => ObjectArg::Receiving(get_object(reader, *id).await?.0),
+        };
+
+        self.builder.obj(obj_arg)
+    }
+
+    fn command(&self, command: &CommandSpec) -> anyhow::Result<Command> {
+        let modules = |modules: &[String]| {
+            modules
+                .iter()
+                .map(|m| Base64::decode(m).map_err(|e| anyhow!("Invalid Base64 module: {e}")))
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+
+        Ok(match command {
+            CommandSpec::MoveCall {
+                function,
+                type_arguments,
+                arguments,
+            } => {
+                let [package, module, function] = function.split("::").collect::<Vec<_>>()[..]
+                else {
+                    bail!("Expected function of the form <package>::<module>::<function>, got '{function}'");
+                };
+
+                Command::move_call(
+                    ObjectID::from_str(package)?,
+                    Identifier::new(module)?,
+                    Identifier::new(function)?,
+                    type_arguments
+                        .iter()
+                        .map(|t| parse_sui_type_tag(t))
+                        .collect::<anyhow::Result<_>>()?,
+                    self.args(arguments)?,
+                )
+            }
+
+            CommandSpec::TransferObjects { objects, address } => {
+                Command::TransferObjects(self.args(objects)?, self.arg(address)?)
+            }
+
+            CommandSpec::SplitCoins { coin, amounts } => {
+                Command::SplitCoins(self.arg(coin)?, self.args(amounts)?)
+            }
+
+            CommandSpec::MergeCoins { coin, coins } => {
+                Command::MergeCoins(self.arg(coin)?, self.args(coins)?)
+            }
+
+            CommandSpec::MakeMoveVec { type_, elements } => Command::make_move_vec(
+                type_.as_deref().map(parse_sui_type_tag).transpose()?,
+                self.args(elements)?,
+            ),
+
+            CommandSpec::Publish {
+                modules: modules_,
+                dependencies,
+            } => Command::Publish(modules(modules_)?, dependencies.clone()),
+
+            CommandSpec::Upgrade {
+                modules: modules_,
+                dependencies,
+                package,
+                ticket,
+            } => Command::Upgrade(
+                modules(modules_)?,
+                dependencies.clone(),
+                *package,
+                self.arg(ticket)?,
+            ),
+        })
+    }
+
+    fn args(&self, names: &[String]) -> anyhow::Result<Vec<Argument>> {
+        names.iter().map(|name| self.arg(name)).collect()
+    }
+
+    fn arg(&self, name: &str) -> anyhow::Result<Argument> {
+        if name == GAS_COIN {
+            return Ok(Argument::GasCoin);
+        }
+
+        let (base, nested) = match name.split_once('.') {
+            Some((base, ix)) => (
+                base,
+                Some(
+                    ix.parse::<u16>()
+                        .map_err(|_| anyhow!("Invalid result index in '{name}'"))?,
+                ),
+            ),
+            None => (name, None),
+        };
+
+        let Some(arg) = self.names.get(base) else {
+            bail!("Unknown name '{base}'");
+        };
+
+        Ok(match (*arg, nested) {
+            (arg, None) => arg,
+            (Argument::Result(i), Some(j)) => Argument::NestedResult(i, j),
+            (_, Some(_)) => bail!("'{base}' is not a command result, so '{name}' is not valid"),
+        })
+    }
+}
+
+/// The latest reference to the object with ID `id`, and its owner.
+async fn get_object(
+    reader: &(dyn DataReader + Sync + Send),
+    id: ObjectID,
+) -> anyhow::Result<(ObjectRef, Owner)> {
+    let object = reader
+        .get_object_with_options(id, SuiObjectDataOptions::new().with_owner())
+        .await?
+        .into_object()?;
+
+    let owner = object
+        .owner
+        .ok_or_else(|| anyhow!("Owner of object {id} is not available"))?;
+
+    Ok((object.object_ref(), owner))
+}
+
+/// The name that refers to `arg` in manifests produced by [`Manifest::from_ptb`].
+fn argument_name(arg: &Argument) -> String {
+    match arg {
+        Argument::GasCoin => GAS_COIN.to_string(),
+        Argument::Input(i) => format!("input_{i}"),
+        Argument::Result(i) => format!("result_{i}"),
+        Argument::NestedResult(i, j) => format!("result_{i}.{j}"),
+    }
+}
+
+fn command_arguments(command: &Command) -> Vec<&Argument> {
+    match command {
+        Command::MoveCall(call) => call.arguments.iter().collect(),
+        Command::TransferObjects(objects, address) => {
+            objects.iter().chain(std::iter::once(address)).collect()
+        }
+        Command::SplitCoins(coin, args) | Command::MergeCoins(coin, args) => {
+            std::iter::once(coin).chain(args).collect()
+        }
+        Command::MakeMoveVec(_, elements) => elements.iter().collect(),
+        Command::Publish(_, _) => vec![],
+        Command::Upgrade(_, _, _, ticket) => vec![ticket],
+    }
+}
+
+/// Layout of pure values of type `tag`, as understood by [`SuiJsonValue::to_bcs_bytes`].
+fn pure_layout(tag: &TypeTag) -> anyhow::Result<L> {
+    Ok(match tag {
+        TypeTag::Bool => L::Bool,
+        TypeTag::U8 => L::U8,
+        TypeTag::U16 => L::U16,
+        TypeTag::U32 => L::U32,
+        TypeTag::U64 => L::U64,
+        TypeTag::U128 => L::U128,
+        TypeTag::U256 => L::U256,
+        TypeTag::Address => L::Address,
+        TypeTag::Vector(inner) => L::Vector(Box::new(pure_layout(inner)?)),
+
+        TypeTag::Struct(s) => {
+            let resolved = (&s.address, s.module.as_ident_str(), s.name.as_ident_str());
+            let (field, layout) = if resolved == RESOLVED_SUI_ID {
+                ("bytes", L::Address)
+            } else if resolved == RESOLVED_UTF8_STR || resolved == RESOLVED_ASCII_STR {
+                ("bytes", L::Vector(Box::new(L::U8)))
+            } else if resolved == RESOLVED_STD_OPTION && s.type_params.len() == 1 {
+                ("vec", L::Vector(Box::new(pure_layout(&s.type_params[0])?)))
+            } else {
+                bail!("Type {tag} cannot be used as a pure value");
+            };
+
+            L::Struct(Box::new(MoveStructLayout::new(
+                StructTag::clone(s),
+                vec![MoveFieldLayout::new(Identifier::new(field)?, layout)],
+            )))
+        }
+
+        TypeTag::Signer => bail!("Type {tag} cannot be used as a pure value"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use async_trait::async_trait;
+    use sui_json_rpc_types::{ProtocolConfigResponse, SuiObjectData, SuiObjectResponse};
+    use sui_types::base_types::{random_object_ref, ObjectInfo, SequenceNumber, SuiAddress};
+
+    use super::*;
+
+    /// Serves the latest versions and owners of a fixed set of objects.
+    struct MockReader(BTreeMap<ObjectID, (ObjectRef, Owner)>);
+
+    #[async_trait]
+    impl DataReader for MockReader {
+        async fn get_owned_objects(
+            &self,
+            _address: SuiAddress,
+            _object_type: StructTag,
+        ) -> anyhow::Result<Vec<ObjectInfo>> {
+            unimplemented!()
+        }
+
+        async fn get_object_with_options(
+            &self,
+            object_id: ObjectID,
+            _options: SuiObjectDataOptions,
+        ) -> anyhow::Result<SuiObjectResponse> {
+            let ((object_id, version, digest), owner) = self
+                .0
+                .get(&object_id)
+                .cloned()
+                .ok_or_else(|| anyhow!("Object {object_id} not found"))?;
+
+            Ok(SuiObjectResponse::new_with_data(SuiObjectData {
+                object_id,
+                version,
+                digest,
+                type_: None,
+                owner: Some(owner),
+                previous_transaction: None,
+                storage_rebate: None,
+                display: None,
+                content: None,
+                bcs: None,
+            }))
+        }
+
+        async fn multi_get_object_with_options(
+            &self,
+            _object_ids: Vec<ObjectID>,
+            _options: SuiObjectDataOptions,
+        ) -> anyhow::Result<Vec<SuiObjectResponse>> {
+            unimplemented!()
+        }
+
+        async fn get_reference_gas_price(&self) -> anyhow::Result<u64> {
+            unimplemented!()
+        }
+
+        async fn get_protocol_config(&self) -> anyhow::Result<ProtocolConfigResponse> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn manifest_round_trip() {
+        let owned = random_object_ref();
+        let shared = random_object_ref();
+        let receiving = random_object_ref();
+        let initial_shared_version = SequenceNumber::from_u64(1);
+
+        let reader = MockReader(BTreeMap::from([
+            (owned.0, (owned, Owner::AddressOwner(SuiAddress::ZERO))),
+            (
+                shared.0,
+                (
+                    shared,
+                    Owner::Shared {
+                        initial_shared_version,
+                    },
+                ),
+            ),
+            (receiving.0, (receiving, Owner::ObjectOwner(owned.0.into()))),
+        ]));
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let amount = builder.pure(1000u64).unwrap();
+        let recipient = builder.pure(SuiAddress::ZERO).unwrap();
+        let owned = builder.obj(ObjectArg::ImmOrOwnedObject(owned)).unwrap();
+        let shared = builder
+            .obj(ObjectArg::SharedObject {
+                id: shared.0,
+                initial_shared_version,
+                mutable: false,
+            })
+            .unwrap();
+        let receiving = builder.obj(ObjectArg::Receiving(receiving)).unwrap();
+
+        let Argument::Result(split) =
+            builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount, amount]))
+        else {
+            panic!("expected a command result");
+        };
+        builder.command(Command::MergeCoins(
+            Argument::NestedResult(split, 0),
+            vec![Argument::NestedResult(split, 1)],
+        ));
+        let call = builder.command(Command::move_call(
+            ObjectID::from_single_byte(0x42),
+            Identifier::new("module").unwrap(),
+            Identifier::new("function").unwrap(),
+            vec![parse_sui_type_tag("0x2::sui::SUI").unwrap()],
+            vec![owned, shared, receiving, Argument::NestedResult(split, 0)],
+        ));
+        let vec = builder.command(Command::make_move_vec(
+            Some(TypeTag::U64),
+            vec![amount, amount],
+        ));
+        builder.command(Command::TransferObjects(vec![call, vec], recipient));
+        let ptb = builder.finish();
+
+        let manifest = Manifest::from_ptb(&ptb);
+
+        let yaml = serde_yaml::to_string(&manifest).unwrap();
+        assert_eq!(manifest, serde_yaml::from_str::<Manifest>(&yaml).unwrap());
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert_eq!(manifest, serde_json::from_str::<Manifest>(&json).unwrap());
+
+        let rebuilt = ManifestBuilder::new()
+            .build(&reader, &manifest)
+            .await
+            .unwrap();
+        assert_eq!(ptb, rebuilt);
+    }
+}
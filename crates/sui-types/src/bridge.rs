@@ -478,6 +478,21 @@ pub struct MoveTypeBridgeTransferRecord {
     total_amount: u64,
 }
 
+impl MoveTypeBridgeTransferRecord {
+    /// Returns the amount transferred in the 24 hour window ending at `current_hour`, as the
+    /// Move limiter computes it before checking a new transfer. `per_hour_amounts` may hold
+    /// stale hours that the limiter only drops on the next transfer.
+    pub fn window_amount(&self, current_hour: u64) -> u64 {
+        let window_start = current_hour.saturating_sub(23);
+        self.per_hour_amounts
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.hour_tail + *i as u64 >= window_start)
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+}
+
 /// Rust version of the Move message::BridgeMessage type.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MoveTypeBridgeMessage {
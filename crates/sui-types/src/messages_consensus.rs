@@ -8,7 +8,7 @@ use crate::messages_checkpoint::{CheckpointSequenceNumber, CheckpointSignatureMe
 use crate::supported_protocol_versions::{
     Chain, SupportedProtocolVersions, SupportedProtocolVersionsWithHashes,
 };
-use crate::transaction::{CertifiedTransaction, Transaction};
+use crate::transaction::{CertifiedTransaction, Command, Transaction};
 use byteorder::{BigEndian, ReadBytesExt};
 use fastcrypto::error::FastCryptoResult;
 use fastcrypto::groups::bls12381;
@@ -20,7 +20,7 @@ use std::collections::hash_map::DefaultHasher;
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// The index of an authority in the consensus committee.
 /// The value should be the same in Sui committee.
@@ -92,6 +92,23 @@ pub fn check_total_jwk_size(id: &JwkId, jwk: &JWK) -> bool {
         <= MAX_TOTAL_JWK_SIZE
 }
 
+// Move identifiers are at most 128 bytes long, so 512 leaves plenty of space for the module and
+// function names of any entry point while preventing DoSes.
+static MAX_EXECUTION_TIME_OBSERVATION_KEY_SIZE: usize = 512;
+
+pub fn check_execution_time_observation_size(
+    observation: &ExecutionTimeObservation,
+    max_estimates: u64,
+) -> bool {
+    observation.estimates.len() as u64 <= max_estimates
+        && observation.estimates.iter().all(|(key, _)| match key {
+            ExecutionTimeObservationKey::MoveEntryPoint {
+                module, function, ..
+            } => module.len() + function.len() <= MAX_EXECUTION_TIME_OBSERVATION_KEY_SIZE,
+            _ => true,
+        })
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ConsensusTransaction {
     /// Encodes an u64 unique tracking id to allow us trace a message between Sui and consensus.
@@ -111,6 +128,7 @@ pub enum ConsensusTransactionKey {
     NewJWKFetched(Box<(AuthorityName, JwkId, JWK)>),
     RandomnessDkgMessage(AuthorityName),
     RandomnessDkgConfirmation(AuthorityName),
+    ExecutionTimeObservation(AuthorityName, u64 /* generation */),
 }
 
 impl Debug for ConsensusTransactionKey {
@@ -143,6 +161,12 @@ impl Debug for ConsensusTransactionKey {
             Self::RandomnessDkgConfirmation(name) => {
                 write!(f, "RandomnessDkgConfirmation({:?})", name.concise())
             }
+            Self::ExecutionTimeObservation(name, generation) => write!(
+                f,
+                "ExecutionTimeObservation({:?}, {:?})",
+                name.concise(),
+                generation
+            ),
         }
     }
 }
@@ -264,6 +288,87 @@ impl AuthorityCapabilitiesV2 {
     }
 }
 
+/// The unit of work whose execution time is observed by validators, for congestion control.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ExecutionTimeObservationKey {
+    MoveEntryPoint {
+        package: ObjectID,
+        module: String,
+        function: String,
+    },
+    TransferObjects,
+    SplitCoins,
+    MergeCoins,
+    Publish,
+    MakeMoveVec,
+    Upgrade,
+}
+
+impl ExecutionTimeObservationKey {
+    pub fn from_command(command: &Command) -> Self {
+        match command {
+            Command::MoveCall(call) => Self::MoveEntryPoint {
+                package: call.package,
+                module: call.module.clone(),
+                function: call.function.clone(),
+            },
+            Command::TransferObjects(_, _) => Self::TransferObjects,
+            Command::SplitCoins(_, _) => Self::SplitCoins,
+            Command::MergeCoins(_, _) => Self::MergeCoins,
+            Command::Publish(_, _) => Self::Publish,
+            Command::MakeMoveVec(_, _) => Self::MakeMoveVec,
+            Command::Upgrade(_, _, _, _) => Self::Upgrade,
+        }
+    }
+}
+
+/// Used to share the execution times observed by each authority via consensus, so that validators
+/// agree on the estimated cost of transactions used for congestion control.
+#[derive(Serialize, Deserialize, Clone, Hash)]
+pub struct ExecutionTimeObservation {
+    /// Originating authority - must match transaction source authority from consensus.
+    pub authority: AuthorityName,
+    /// Generation number set by sending authority. Used to determine which of multiple
+    /// ExecutionTimeObservation messages from the same authority is the most recent.
+    ///
+    /// (Currently, we just set this to the current time in milliseconds since the epoch, but this
+    /// should not be interpreted as a timestamp.)
+    pub generation: u64,
+
+    /// The authority's latest estimate of the execution time of each key whose estimate changed
+    /// since its previous observation. Estimates for other keys are unchanged.
+    pub estimates: Vec<(ExecutionTimeObservationKey, Duration)>,
+}
+
+impl Debug for ExecutionTimeObservation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExecutionTimeObservation")
+            .field("authority", &self.authority.concise())
+            .field("generation", &self.generation)
+            .field("estimates", &self.estimates)
+            .finish()
+    }
+}
+
+impl ExecutionTimeObservation {
+    pub fn new(
+        authority: AuthorityName,
+        estimates: Vec<(ExecutionTimeObservationKey, Duration)>,
+    ) -> Self {
+        let generation = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Sui did not exist prior to 1970")
+            .as_millis()
+            .try_into()
+            .expect("This build of sui is not supported in the year 500,000,000");
+        Self {
+            authority,
+            generation,
+            estimates,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum ConsensusTransactionKind {
     CertifiedTransaction(Box<CertifiedTransaction>),
@@ -286,6 +391,8 @@ pub enum ConsensusTransactionKind {
     CapabilityNotificationV2(AuthorityCapabilitiesV2),
 
     UserTransaction(Box<Transaction>),
+
+    ExecutionTimeObservation(ExecutionTimeObservation),
 }
 
 impl ConsensusTransactionKind {
@@ -454,6 +561,16 @@ impl ConsensusTransaction {
         }
     }
 
+    pub fn new_execution_time_observation(observation: ExecutionTimeObservation) -> Self {
+        let mut hasher = DefaultHasher::new();
+        observation.hash(&mut hasher);
+        let tracking_id = hasher.finish().to_le_bytes();
+        Self {
+            tracking_id,
+            kind: ConsensusTransactionKind::ExecutionTimeObservation(observation),
+        }
+    }
+
     pub fn new_mysticeti_certificate(
         round: u64,
         offset: u64,
@@ -558,6 +675,12 @@ impl ConsensusTransaction {
                 // between CertifiedTransaction and UserTransaction.
                 ConsensusTransactionKey::Certificate(*tx.digest())
             }
+            ConsensusTransactionKind::ExecutionTimeObservation(observation) => {
+                ConsensusTransactionKey::ExecutionTimeObservation(
+                    observation.authority,
+                    observation.generation,
+                )
+            }
         }
     }
 
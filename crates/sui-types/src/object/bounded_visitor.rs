@@ -71,7 +71,9 @@ static MAX_BOUND: Lazy<usize> = Lazy::new(|| {
 });
 
 impl BoundedVisitor {
-    fn new(bound: usize) -> Self {
+    /// Create a visitor with a budget of `bound` bytes, instead of the process-wide default
+    /// budget.
+    pub fn new(bound: usize) -> Self {
         Self { bound }
     }
 
@@ -7,6 +7,7 @@ use std::collections::BTreeMap;
 use crate::base_types::{AuthorityName, EpochId, ObjectRef, TransactionDigest};
 use crate::committee::StakeUnit;
 use crate::crypto::{AuthorityStrongQuorumSignInfo, ConciseAuthorityPublicKeyBytes};
+use crate::digests::TransactionEffectsDigest;
 use crate::effects::{
     CertifiedTransactionEffects, TransactionEffects, TransactionEvents,
     VerifiedCertifiedTransactionEffects,
@@ -161,6 +162,34 @@ pub struct ExecuteTransactionResponseV3 {
     pub auxiliary_data: Option<Vec<u8>>,
 }
 
+/// Progress of a transaction submitted to the durable submission queue of a fullnode, which
+/// clients can track by transaction digest across fullnode restarts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionStatus {
+    /// Waiting to be sent to validators. `last_error` is set when a previous attempt failed with
+    /// a transient error, in which case the transaction is sent again in the next epoch.
+    Queued { last_error: Option<String> },
+    /// Sent to validators, `attempts` times so far.
+    Submitted { attempts: u32 },
+    /// Certified by a quorum of validators, waiting for effects.
+    Certified,
+    /// Finalized with these effects.
+    Executed {
+        effects_digest: TransactionEffectsDigest,
+    },
+    /// Failed with an error which retrying cannot fix.
+    Failed { reason: String },
+}
+
+impl SubmissionStatus {
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            SubmissionStatus::Executed { .. } | SubmissionStatus::Failed { .. }
+        )
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct FinalizedEffects {
     pub effects: TransactionEffects,
@@ -22,6 +22,8 @@ use itertools::Itertools;
 use move_binary_format::CompiledModule;
 use move_core_types::language_storage::ModuleId;
 pub use object_store_trait::ObjectStore;
+pub use read_store::AccountBalanceInfo;
+pub use read_store::AccountCoinInfo;
 pub use read_store::AccountOwnedObjectInfo;
 pub use read_store::CoinInfo;
 pub use read_store::DynamicFieldIndexInfo;
@@ -6,7 +6,7 @@ use super::ObjectStore;
 use crate::base_types::{EpochId, MoveObjectType, ObjectID, SequenceNumber, SuiAddress};
 use crate::committee::Committee;
 use crate::digests::{
-    ChainIdentifier, CheckpointContentsDigest, CheckpointDigest, TransactionDigest,
+    ChainIdentifier, CheckpointContentsDigest, CheckpointDigest, ObjectDigest, TransactionDigest,
     TransactionEventsDigest,
 };
 use crate::dynamic_field::DynamicFieldType;
@@ -658,6 +658,12 @@ pub trait RestStateReader: ObjectStore + ReadStore + Send + Sync {
     /// available.
     fn get_lowest_available_checkpoint_objects(&self) -> Result<CheckpointSequenceNumber>;
 
+    /// Lowest available checkpoint for which the historical state of objects can be requested.
+    ///
+    /// Specifically this is the lowest checkpoint `N` for which the version an object had as of
+    /// `N` can be resolved and the object data at that version is still available.
+    fn get_lowest_available_checkpoint_object_history(&self) -> Result<CheckpointSequenceNumber>;
+
     fn get_chain_identifier(&self) -> Result<ChainIdentifier>;
 
     fn account_owned_objects_info_iter(
@@ -672,7 +678,58 @@ pub trait RestStateReader: ObjectStore + ReadStore + Send + Sync {
         cursor: Option<ObjectID>,
     ) -> Result<Box<dyn Iterator<Item = (DynamicFieldKey, DynamicFieldIndexInfo)> + '_>>;
 
+    /// Resolve the version of an object as of the end of `checkpoint`.
+    ///
+    /// Returns `None` if the object did not exist, or was deleted or wrapped, at that checkpoint.
+    fn get_object_version_at_checkpoint(
+        &self,
+        object_id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<Option<SequenceNumber>>;
+
+    /// Iterate over the dynamic fields a parent had as of the end of `checkpoint`.
+    fn dynamic_field_iter_at_checkpoint(
+        &self,
+        parent: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+        cursor: Option<ObjectID>,
+    ) -> Result<Box<dyn Iterator<Item = (DynamicFieldKey, DynamicFieldIndexInfo)> + '_>>;
+
     fn get_coin_info(&self, coin_type: &StructTag) -> Result<Option<CoinInfo>>;
+
+    /// Iterate over the coins of `coin_type` owned by `owner`, ordered from greatest to least
+    /// balance.
+    ///
+    /// When a `(balance, object_id)` cursor is provided, iteration starts at that coin.
+    fn account_coins_iter(
+        &self,
+        owner: SuiAddress,
+        coin_type: &StructTag,
+        cursor: Option<(u64, ObjectID)>,
+    ) -> Result<Box<dyn Iterator<Item = AccountCoinInfo> + '_>>;
+
+    /// Balances of every coin type owned by `owner`.
+    fn get_account_balances(&self, owner: SuiAddress) -> Result<Vec<AccountBalanceInfo>>;
+
+    /// Balance of the coins of `coin_type` owned by `owner`.
+    fn get_account_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: &StructTag,
+    ) -> Result<AccountBalanceInfo> {
+        let mut balance = AccountBalanceInfo {
+            coin_type: coin_type.to_owned(),
+            coin_object_count: 0,
+            total_balance: 0,
+        };
+
+        for coin in self.account_coins_iter(owner, coin_type, None)? {
+            balance.coin_object_count += 1;
+            balance.total_balance += coin.balance as u128;
+        }
+
+        Ok(balance)
+    }
 }
 
 pub struct AccountOwnedObjectInfo {
@@ -682,6 +739,20 @@ pub struct AccountOwnedObjectInfo {
     pub type_: MoveObjectType,
 }
 
+pub struct AccountCoinInfo {
+    pub object_id: ObjectID,
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+    pub balance: u64,
+    pub previous_transaction: TransactionDigest,
+}
+
+pub struct AccountBalanceInfo {
+    pub coin_type: StructTag,
+    pub coin_object_count: u64,
+    pub total_balance: u128,
+}
+
 #[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub struct DynamicFieldKey {
     pub parent: ObjectID,
@@ -3,7 +3,7 @@
 
 use std::collections::BTreeMap;
 
-use crate::base_types::ObjectID;
+use crate::base_types::{ObjectID, TransactionDigest};
 use crate::effects::TransactionEffects;
 use crate::effects::TransactionEvents;
 use crate::error::SuiError;
@@ -11,6 +11,7 @@ use crate::object::Object;
 use crate::quorum_driver_types::ExecuteTransactionRequestV3;
 use crate::quorum_driver_types::ExecuteTransactionResponseV3;
 use crate::quorum_driver_types::QuorumDriverError;
+use crate::quorum_driver_types::SubmissionStatus;
 use crate::transaction::TransactionData;
 
 /// Trait to define the interface for how the REST service interacts with a a QuorumDriver or a
@@ -23,6 +24,19 @@ pub trait TransactionExecutor: Send + Sync {
         client_addr: Option<std::net::SocketAddr>,
     ) -> Result<ExecuteTransactionResponseV3, QuorumDriverError>;
 
+    /// Submits the transaction without waiting for it to be finalized. Its progress can be
+    /// tracked by digest through `get_submission_status`.
+    async fn submit_transaction(
+        &self,
+        request: ExecuteTransactionRequestV3,
+        client_addr: Option<std::net::SocketAddr>,
+    ) -> Result<TransactionDigest, QuorumDriverError>;
+
+    fn get_submission_status(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Result<Option<SubmissionStatus>, SuiError>;
+
     fn simulate_transaction(
         &self,
         transaction: TransactionData,
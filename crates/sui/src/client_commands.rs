@@ -62,6 +62,7 @@ use sui_sdk::{
     SuiClient, SUI_COIN_TYPE, SUI_DEVNET_URL, SUI_LOCAL_NETWORK_URL, SUI_LOCAL_NETWORK_URL_0,
     SUI_TESTNET_URL,
 };
+use sui_transaction_builder::manifest::Manifest;
 use sui_types::{
     base_types::{ObjectID, SequenceNumber, SuiAddress},
     crypto::{EmptySignInfo, SignatureScheme},
@@ -343,6 +344,30 @@ pub enum SuiClientCommands {
     #[clap(name = "ptb")]
     PTB(PTB),
 
+    /// Run a PTB described by a JSON or YAML manifest
+    #[clap(name = "ptb-manifest")]
+    PtbManifest {
+        /// Path to the manifest. Files with a `.json` extension are read as JSON, and all other
+        /// files are read as YAML.
+        #[clap(value_hint = ValueHint::FilePath)]
+        manifest: PathBuf,
+
+        #[clap(flatten)]
+        opts: OptsWithGas,
+    },
+
+    /// Print the PTB of an executed transaction as a manifest, that can be run with `ptb-manifest`
+    #[clap(name = "export-ptb-manifest")]
+    ExportPtbManifest {
+        /// Digest of the transaction block
+        #[clap(name = "digest")]
+        digest: TransactionDigest,
+
+        /// Print the manifest as JSON instead of YAML
+        #[clap(long = "as-json")]
+        as_json: bool,
+    },
+
     /// Publish Move modules
     #[clap(name = "publish")]
     Publish {
@@ -486,6 +511,11 @@ pub enum SuiClientCommands {
         #[clap(name = "module", long, action = clap::ArgAction::Append, global = true)]
         module_paths: Vec<PathBuf>,
 
+        /// Also warn about `entry` and `public` functions looping over collections they receive
+        /// as arguments. These lints are not enforced on chain.
+        #[clap(long)]
+        lint_unbounded_loops: bool,
+
         /// Package build options
         #[clap(flatten)]
         build_config: MoveBuildConfig,
@@ -516,7 +546,7 @@ pub enum SuiClientCommands {
         address_override: Option<ObjectID>,
     },
 
-    /// Profile the gas usage of a transaction. Unless an output filepath is not specified, outputs a file `gas_profile_{tx_digest}_{unix_timestamp}.json` which can be opened in a flamegraph tool such as speedscope.
+    /// Profile the gas usage of a transaction. Unless an output filepath is not specified, outputs a file `gas_profile_{tx_digest}_{unix_timestamp}.json` which can be opened in a flamegraph tool such as speedscope, along with a `.folded` file of folded stacks per command and function (and instruction class with `--track-instructions`), and a `.summary.json` file with the gas of each command and the computation and storage costs.
     #[clap(name = "profile-transaction")]
     ProfileTransaction {
         /// The digest of the transaction to replay
@@ -527,6 +557,11 @@ pub enum SuiClientCommands {
         /// If an output filepath is not specified, it will output a file `gas_profile_{tx_digest}_{unix_timestamp}.json` to the working directory
         #[arg(long, short)]
         profile_output: Option<PathBuf>,
+
+        /// Break the gas of each command and Move function down by bytecode instruction class.
+        /// Off by default, as tracking every instruction makes profiles much larger.
+        #[arg(long)]
+        track_instructions: bool,
     },
 
     /// Replay a given transaction to view transaction effects. Set environment variable MOVE_VM_STEP=1 to debug.
@@ -678,6 +713,7 @@ impl SuiClientCommands {
             SuiClientCommands::ProfileTransaction {
                 tx_digest,
                 profile_output,
+                track_instructions,
             } => {
                 move_vm_profiler::tracing_feature_disabled! {
                     bail!(
@@ -691,6 +727,7 @@ impl SuiClientCommands {
                     executor_version: None,
                     protocol_version: None,
                     profile_output,
+                    track_instructions,
                     config_objects: None,
                 };
                 let rpc = context.config.get_active_env()?.rpc.clone();
@@ -1055,6 +1092,7 @@ impl SuiClientCommands {
                 protocol_version,
                 module_paths,
                 package_path,
+                lint_unbounded_loops,
                 build_config,
             } => {
                 let protocol_version =
@@ -1065,7 +1103,7 @@ impl SuiClientCommands {
                 let registry = &Registry::new();
                 let bytecode_verifier_metrics = Arc::new(BytecodeVerifierMetrics::new(registry));
 
-                let (pkg_name, modules) = match (module_paths, package_path) {
+                let (pkg_name, modules, lints) = match (module_paths, package_path) {
                     (paths, Some(_)) if !paths.is_empty() => {
                         bail!("Cannot specify both a module path and a package path")
                     }
@@ -1079,7 +1117,12 @@ impl SuiClientCommands {
                                 .context("Failed to deserialize module")?;
                             modules.push(module);
                         }
-                        ("<unknown>".to_string(), modules)
+                        let lints = if lint_unbounded_loops {
+                            sui_move_build::lint_unbounded_loops(&modules)
+                        } else {
+                            vec![]
+                        };
+                        ("<unknown>".to_string(), modules, lints)
                     }
 
                     (_, package_path) => {
@@ -1090,10 +1133,19 @@ impl SuiClientCommands {
                             .compiled_package_info
                             .package_name
                             .to_string();
-                        (name, package.get_modules().cloned().collect())
+                        let lints = if lint_unbounded_loops {
+                            package.lint_unbounded_loops()
+                        } else {
+                            vec![]
+                        };
+                        (name, package.get_modules().cloned().collect(), lints)
                     }
                 };
 
+                for warning in lints {
+                    eprintln!("warning: {warning}");
+                }
+
                 let signing_limits = Some(VerifierSigningConfig::default().limits_for_signing());
                 let mut verifier = sui_execution::verifier(
                     &protocol_config,
@@ -1650,6 +1702,58 @@ impl SuiClientCommands {
                 ptb.execute(context).await?;
                 SuiClientCommandResult::NoOutput
             }
+
+            SuiClientCommands::PtbManifest { manifest, opts } => {
+                let contents = std::fs::read_to_string(&manifest)
+                    .map_err(|e| anyhow!("Failed to read manifest {}: {e}", manifest.display()))?;
+                let manifest: Manifest = if manifest.extension().is_some_and(|e| e == "json") {
+                    serde_json::from_str(&contents)?
+                } else {
+                    serde_yaml::from_str(&contents)?
+                };
+
+                let ptb = context
+                    .get_client()
+                    .await?
+                    .transaction_builder()
+                    .manifest_ptb(&manifest)
+                    .await?;
+
+                let sender = match context.try_get_object_owner(&opts.gas).await? {
+                    Some(sender) => sender,
+                    None => context.active_address()?,
+                };
+
+                dry_run_or_execute_or_serialize(
+                    sender,
+                    TransactionKind::ProgrammableTransaction(ptb),
+                    context,
+                    None,
+                    None,
+                    opts.gas,
+                    opts.rest,
+                )
+                .await?
+            }
+
+            SuiClientCommands::ExportPtbManifest { digest, as_json } => {
+                let client = context.get_client().await?;
+                let response = client
+                    .read_api()
+                    .get_transaction_with_options(
+                        digest,
+                        SuiTransactionBlockResponseOptions::new().with_raw_input(),
+                    )
+                    .await?;
+
+                let tx: SenderSignedData = bcs::from_bytes(&response.raw_transaction)?;
+                let TransactionKind::ProgrammableTransaction(ptb) = tx.transaction_data().kind()
+                else {
+                    bail!("Transaction {digest} is not a programmable transaction");
+                };
+
+                SuiClientCommandResult::PtbManifest(Manifest::from_ptb(ptb), as_json)
+            }
         };
         Ok(ret.prerender_clever_errors(context).await)
     }
@@ -2103,6 +2207,17 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::SyncClientState => {
                 writeln!(writer, "Client state sync complete.")?;
             }
+            SuiClientCommandResult::PtbManifest(manifest, as_json) => {
+                if *as_json {
+                    writeln!(
+                        writer,
+                        "{}",
+                        serde_json::to_string_pretty(manifest).unwrap()
+                    )?;
+                } else {
+                    write!(writer, "{}", serde_yaml::to_string(manifest).unwrap())?;
+                }
+            }
             SuiClientCommandResult::ChainIdentifier(ci) => {
                 writeln!(writer, "{}", ci)?;
             }
@@ -2271,6 +2386,9 @@ impl Debug for SuiClientCommandResult {
                 let raw_object = raw_object_read.object()?;
                 Ok(serde_json::to_string_pretty(&raw_object)?)
             }
+            SuiClientCommandResult::PtbManifest(manifest, _) => {
+                Ok(serde_json::to_string_pretty(manifest)?)
+            }
             _ => Ok(serde_json::to_string_pretty(self)?),
         });
         write!(f, "{}", s)
@@ -2345,6 +2463,7 @@ impl SuiClientCommandResult {
             | SuiClientCommandResult::NoOutput
             | SuiClientCommandResult::Object(_)
             | SuiClientCommandResult::Objects(_)
+            | SuiClientCommandResult::PtbManifest(_, _)
             | SuiClientCommandResult::RawObject(_)
             | SuiClientCommandResult::SerializedSignedTransaction(_)
             | SuiClientCommandResult::SerializedUnsignedTransaction(_)
@@ -2495,6 +2614,7 @@ pub enum SuiClientCommandResult {
     NoOutput,
     Object(SuiObjectResponse),
     Objects(Vec<SuiObjectResponse>),
+    PtbManifest(Manifest, bool),
     RawObject(SuiObjectResponse),
     SerializedSignedTransaction(SenderSignedData),
     SerializedUnsignedTransaction(TransactionData),
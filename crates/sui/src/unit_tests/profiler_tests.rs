@@ -50,6 +50,7 @@ async fn test_profiler() {
         executor_version: None,
         protocol_version: None,
         profile_output: Some(profile_output),
+        track_instructions: false,
         config_objects: None,
     };
 
@@ -72,3 +73,87 @@ async fn test_profiler() {
     }
     assert!(found);
 }
+
+#[cfg(feature = "tracing")]
+#[tokio::test]
+async fn test_dry_run_profile_has_command_frames() {
+    use rand::rngs::OsRng;
+    use std::fs;
+    use std::time::Duration;
+    use sui_config::node::DryRunGasProfileConfig;
+    use sui_test_transaction_builder::{publish_basics_package, TestTransactionBuilder};
+    use tempfile::tempdir;
+    use test_cluster::TestClusterBuilder;
+
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let package = publish_basics_package(&test_cluster.wallet).await;
+    let (sender, gas) = test_cluster
+        .wallet
+        .get_one_gas_object()
+        .await
+        .unwrap()
+        .unwrap();
+    let rgp = test_cluster.get_reference_gas_price().await;
+
+    // Profile every dry-run, but write at most one profile
+    let output_dir = tempdir().unwrap();
+    let mut config = test_cluster
+        .fullnode_config_builder()
+        .build(&mut OsRng, test_cluster.swarm.config());
+    config.dry_run_gas_profile_config = Some(DryRunGasProfileConfig {
+        path: output_dir.path().join("profile.json"),
+        sample_rate: 1,
+        max_profiles: 1,
+    });
+    let state = test_cluster
+        .start_fullnode_from_config(config)
+        .await
+        .sui_node
+        .state();
+
+    // Wait for the new fullnode to sync the package and the gas object
+    tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            let package_synced = state.get_object(&package.0).await.unwrap().is_some();
+            let gas_synced = state
+                .get_object(&gas.0)
+                .await
+                .unwrap()
+                .is_some_and(|o| o.version() == gas.1);
+            if package_synced && gas_synced {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    let tx_data = TestTransactionBuilder::new(sender, gas, rgp)
+        .call_counter_create(package.0)
+        .build();
+    for _ in 0..2 {
+        state
+            .dry_exec_transaction(tx_data.clone(), tx_data.digest())
+            .await
+            .unwrap();
+    }
+
+    let folded = fs::read_dir(output_dir.path())
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "folded"))
+        .collect::<Vec<_>>();
+    assert_eq!(folded.len(), 1, "profiles must be capped at max_profiles");
+
+    // The gas of the Move call is attributed to the frame of its command
+    let stacks = fs::read_to_string(&folded[0]).unwrap();
+    assert!(
+        stacks
+            .lines()
+            .any(|line| line.starts_with("root;command_0:MoveCall;")),
+        "no command frame in profile:\n{}",
+        stacks
+    );
+}
@@ -19,11 +19,12 @@ use sui::key_identity::{get_identity_address, KeyIdentity};
 use sui::sui_commands::IndexerArgs;
 use sui_sdk::SuiClient;
 use sui_test_transaction_builder::batch_make_transfer_transactions;
+use sui_transaction_builder::manifest::Manifest;
 use sui_types::object::Owner;
 use sui_types::transaction::{
-    TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
-    TEST_ONLY_GAS_UNIT_FOR_PUBLISH, TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
-    TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+    SenderSignedData, TransactionDataAPI, TransactionKind, TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+    TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS, TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+    TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
 };
 use tokio::time::sleep;
 
@@ -43,6 +44,7 @@ use sui_json_rpc_types::{
     get_new_package_obj_from_response, OwnedObjectRef, SuiExecutionStatus, SuiObjectData,
     SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
     SuiTransactionBlockDataAPI, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponseOptions,
 };
 use sui_keys::keystore::AccountKeystore;
 use sui_macros::sim_test;
@@ -3607,6 +3609,79 @@ async fn test_pay_all_sui() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_ptb_manifest_round_trip() -> Result<(), anyhow::Error> {
+    let (mut test_cluster, client, rgp, objects, recipients, _) = test_cluster_helper().await;
+    let context = &mut test_cluster.wallet;
+    let gas_budget = rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER;
+
+    let SuiClientCommandResult::TransactionBlock(response) = SuiClientCommands::PaySui {
+        input_coins: vec![objects[0]],
+        recipients: vec![recipients[0].clone()],
+        amounts: vec![1000],
+        opts: Opts::for_testing(gas_budget),
+    }
+    .execute(context)
+    .await?
+    else {
+        panic!("PaySui command failed");
+    };
+    assert!(response.status_ok().unwrap());
+
+    let executed = client
+        .read_api()
+        .get_transaction_with_options(
+            response.digest,
+            SuiTransactionBlockResponseOptions::new().with_raw_input(),
+        )
+        .await?;
+    let executed: SenderSignedData = bcs::from_bytes(&executed.raw_transaction)?;
+    let executed_kind = executed.transaction_data().kind().clone();
+    let TransactionKind::ProgrammableTransaction(ptb) = &executed_kind else {
+        panic!("PaySui should execute a programmable transaction");
+    };
+
+    let tmp_dir = tempfile::tempdir()?;
+    for (as_json, file_name) in [(false, "manifest.yaml"), (true, "manifest.json")] {
+        // Export the transaction as a manifest...
+        let output = SuiClientCommands::ExportPtbManifest {
+            digest: response.digest,
+            as_json,
+        }
+        .execute(context)
+        .await?;
+        let SuiClientCommandResult::PtbManifest(manifest, _) = &output else {
+            panic!("ExportPtbManifest command failed");
+        };
+        assert_eq!(manifest, &Manifest::from_ptb(ptb));
+
+        // ...and build it again, which produces the same transaction, because it only uses pure
+        // inputs and the gas coin.
+        let path = tmp_dir.path().join(file_name);
+        std::fs::write(&path, output.to_string())?;
+
+        let SuiClientCommandResult::SerializedUnsignedTransaction(tx_data) =
+            SuiClientCommands::PtbManifest {
+                manifest: path,
+                opts: OptsWithGas {
+                    gas: Some(objects[1]),
+                    rest: Opts {
+                        serialize_unsigned_transaction: true,
+                        ..Opts::for_testing(gas_budget)
+                    },
+                },
+            }
+            .execute(context)
+            .await?
+        else {
+            panic!("PtbManifest command failed");
+        };
+        assert_eq!(tx_data.kind(), &executed_kind);
+    }
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_transfer() -> Result<(), anyhow::Error> {
     let (mut test_cluster, client, rgp, objects, recipients, addresses) =
@@ -34,6 +34,10 @@ use crate::file_exporter::{CachedOpenFile, FileExporter};
 mod file_exporter;
 pub mod span_latency_prom;
 
+/// Spans with this field set to `true` are always sampled by the OTLP exporter, regardless of the
+/// trace sample rate. Callers are expected to do their own sampling.
+pub const FORCE_SAMPLE_FIELD: &str = "force_sample";
+
 /// Alias for a type-erased error type.
 pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
@@ -520,8 +524,14 @@ impl ShouldSample for SamplingFilter {
         attributes: &[KeyValue],
         links: &[Link],
     ) -> SamplingResult {
-        let sample_rate = self.sample_rate.load(Ordering::Relaxed);
-        let sampler = Sampler::TraceIdRatioBased(sample_rate);
+        let force_sample = attributes
+            .iter()
+            .any(|kv| kv.key.as_str() == FORCE_SAMPLE_FIELD && kv.value == true.into());
+        let sampler = if force_sample {
+            Sampler::AlwaysOn
+        } else {
+            Sampler::TraceIdRatioBased(self.sample_rate.load(Ordering::Relaxed))
+        };
 
         sampler.should_sample(parent_context, trace_id, name, span_kind, attributes, links)
     }
@@ -30,6 +30,32 @@ pub struct Event {
     at: u64,
 }
 
+/// Gas cost of the profiled transaction as charged by the embedder, e.g. with storage costs
+/// which are not metered by the VM.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProfileCostSummary {
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    pub non_refundable_storage_fee: u64,
+}
+
+/// Gas attributed to a top level frame, e.g. a command of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FrameGas {
+    pub name: String,
+    pub gas: u64,
+}
+
+/// Companion of the speedscope profile, breaking down the metered gas per top level frame.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileSummary {
+    pub name: String,
+    pub metered_gas: u64,
+    pub top_level_frames: Vec<FrameGas>,
+    pub cost_summary: Option<ProfileCostSummary>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Profile {
@@ -59,6 +85,8 @@ pub struct GasProfiler {
     #[serde(skip)]
     pub config: Option<VMProfilerConfig>,
     #[serde(skip)]
+    cost_summary: Option<ProfileCostSummary>,
+    #[serde(skip)]
     finished: bool,
 }
 
@@ -91,6 +119,7 @@ impl GasProfiler {
             }],
             start_gas,
             config: config.clone(),
+            cost_summary: None,
             finished: false,
         };
         profile_open_frame_impl!(
@@ -115,6 +144,23 @@ impl GasProfiler {
         s.split("::").last().unwrap_or(s).to_string()
     }
 
+    /// Returns the instruction class of an instruction, i.e. its opcode without operands, so
+    /// that e.g. all `LdU64(_)` instructions are attributed to the same frame.
+    #[cfg(feature = "tracing")]
+    pub fn instruction_class(instruction: &str) -> String {
+        instruction
+            .split(|c: char| c == '(' || c == ' ' || c == '{')
+            .next()
+            .unwrap_or(instruction)
+            .to_string()
+    }
+
+    /// Records the gas cost of the transaction, written to the profile summary.
+    #[cfg(feature = "tracing")]
+    pub fn set_cost_summary(&mut self, cost_summary: ProfileCostSummary) {
+        self.cost_summary = Some(cost_summary);
+    }
+
     #[cfg(feature = "tracing")]
     fn is_metered(&self) -> bool {
         (self.profiles[0].end_value != 0) && (self.start_gas != 0)
@@ -178,6 +224,65 @@ impl GasProfiler {
         self.profiles[0].end_value = start - gas_end;
     }
 
+    /// Replays the profile events and returns the gas spent in each stack of frames, excluding
+    /// the gas spent in nested frames. Keys are frame names joined by `;`.
+    #[cfg(feature = "tracing")]
+    pub fn folded_stacks(&self) -> BTreeMap<String, u64> {
+        let mut stacks = BTreeMap::new();
+        let mut stack: Vec<&str> = vec![];
+        let mut last_at = 0;
+        for event in &self.profiles[0].events {
+            let gas = event.at.saturating_sub(last_at);
+            if gas > 0 && !stack.is_empty() {
+                *stacks.entry(stack.join(";")).or_insert(0) += gas;
+            }
+            last_at = event.at;
+            let name = self.shared.frames[event.frame as usize].name.as_str();
+            if event.ty == Self::OPEN_FRAME_IDENT {
+                stack.push(name);
+            } else if let Some(idx) = stack.iter().rposition(|frame| *frame == name) {
+                // Frames left open above the closed one, e.g. on abort, are closed with it
+                stack.truncate(idx);
+            }
+        }
+        stacks
+    }
+
+    /// Returns the gas spent in each frame directly below the top level frame, including the
+    /// gas spent in nested frames, in the order the frames were opened.
+    #[cfg(feature = "tracing")]
+    pub fn top_level_frames(&self) -> Vec<FrameGas> {
+        let mut frames = vec![];
+        let mut stack: Vec<(&str, u64)> = vec![];
+        for event in &self.profiles[0].events {
+            let name = self.shared.frames[event.frame as usize].name.as_str();
+            if event.ty == Self::OPEN_FRAME_IDENT {
+                stack.push((name, event.at));
+            } else if let Some(idx) = stack.iter().rposition(|(frame, _)| *frame == name) {
+                if idx == 1 {
+                    frames.push(FrameGas {
+                        name: name.to_string(),
+                        gas: event.at.saturating_sub(stack[idx].1),
+                    });
+                }
+                stack.truncate(idx);
+            }
+        }
+        frames
+    }
+
+    #[cfg(feature = "tracing")]
+    pub fn summary(&self) -> ProfileSummary {
+        ProfileSummary {
+            name: self.name.clone(),
+            metered_gas: self.profiles[0].end_value,
+            top_level_frames: self.top_level_frames(),
+            cost_summary: self.cost_summary.clone(),
+        }
+    }
+
+    /// Writes the speedscope profile, along with the same profile in the folded stacks format
+    /// used by flamegraph tools and a summary of the gas per top level frame.
     #[cfg(feature = "tracing")]
     pub fn to_file(&self) {
         use std::ffi::{OsStr, OsString};
@@ -206,8 +311,21 @@ impl GasProfiler {
                 .as_nanos()
                 .to_string(),
         );
+        let mut folded_path = p.clone();
+        let mut summary_path = p.clone();
+        let extension = p
+            .extension()
+            .unwrap_or_else(|| OsStr::new("json"))
+            .to_owned();
+
+        let mut folded_filename = filename.clone();
+        folded_filename.push(".folded");
+        folded_path.set_file_name(folded_filename);
+        let mut summary_filename = filename.clone();
+        summary_filename.push(".summary.json");
+        summary_path.set_file_name(summary_filename);
         filename.push(".");
-        filename.push(p.extension().unwrap_or_else(|| OsStr::new("json")));
+        filename.push(extension);
         p.set_file_name(filename);
 
         let mut file = File::create(&p).expect("Unable to create file");
@@ -216,6 +334,25 @@ impl GasProfiler {
         file.write_all(json.as_bytes())
             .expect("Unable to write to file");
         info!("Gas profile written to file: {}", p.display());
+
+        let mut file = File::create(&folded_path).expect("Unable to create file");
+        for (stack, gas) in self.folded_stacks() {
+            writeln!(file, "{} {}", stack, gas).expect("Unable to write to file");
+        }
+        info!(
+            "Folded gas stacks written to file: {}",
+            folded_path.display()
+        );
+
+        let mut file = File::create(&summary_path).expect("Unable to create file");
+        let json =
+            serde_json::to_string_pretty(&self.summary()).expect("Unable to serialize summary");
+        file.write_all(json.as_bytes())
+            .expect("Unable to write to file");
+        info!(
+            "Gas profile summary written to file: {}",
+            summary_path.display()
+        );
     }
 
     #[cfg(feature = "tracing")]
@@ -315,7 +452,8 @@ macro_rules! profile_open_instr {
             if let Some(profiler) = $gas_meter.get_profiler_mut() {
                 if let Some(config) = &profiler.config {
                     if config.track_bytecode_instructions {
-                        profiler.open_frame($frame_name.clone(), $frame_name, gas_rem)
+                        let class = move_vm_profiler::GasProfiler::instruction_class(&$frame_name);
+                        profiler.open_frame(class.clone(), class, gas_rem)
                     }
                 }
             }
@@ -332,7 +470,8 @@ macro_rules! profile_close_instr {
             if let Some(profiler) = $gas_meter.get_profiler_mut() {
                 if let Some(config) = &profiler.config {
                     if config.track_bytecode_instructions {
-                        profiler.close_frame($frame_name.clone(), $frame_name, gas_rem)
+                        let class = move_vm_profiler::GasProfiler::instruction_class(&$frame_name);
+                        profiler.close_frame(class.clone(), class, gas_rem)
                     }
                 }
             }
@@ -379,3 +518,74 @@ macro_rules! tracing_feature_disabled {
 macro_rules! tracing_feature_disabled {
     ( $( $tt:tt )* ) => {};
 }
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+
+    fn test_profiler(start_gas: u64) -> GasProfiler {
+        let config = VMProfilerConfig {
+            full_path: std::env::temp_dir().join("gas_profile.json"),
+            track_bytecode_instructions: true,
+            use_long_function_name: false,
+        };
+        GasProfiler::init(&Some(config), "test".to_string(), start_gas)
+    }
+
+    fn open(prof: &mut GasProfiler, name: &str, remaining: u64) {
+        prof.open_frame(name.to_string(), name.to_string(), remaining);
+    }
+
+    fn close(prof: &mut GasProfiler, name: &str, remaining: u64) {
+        prof.close_frame(name.to_string(), name.to_string(), remaining);
+    }
+
+    #[test]
+    fn test_instruction_class() {
+        assert_eq!(GasProfiler::instruction_class("LdU64(42)"), "LdU64");
+        assert_eq!(
+            GasProfiler::instruction_class("Call(FunctionHandleIndex(3))"),
+            "Call"
+        );
+        assert_eq!(GasProfiler::instruction_class("Add"), "Add");
+    }
+
+    #[test]
+    fn test_folded_stacks_and_top_level_frames() {
+        let mut prof = test_profiler(1000);
+        open(&mut prof, "command_0", 1000);
+        open(&mut prof, "f", 990);
+        open(&mut prof, "Add", 980);
+        close(&mut prof, "Add", 970);
+        close(&mut prof, "f", 950);
+        close(&mut prof, "command_0", 950);
+        open(&mut prof, "command_1", 940);
+        open(&mut prof, "g", 940);
+        // `g` aborts without being closed
+        close(&mut prof, "command_1", 900);
+
+        let stacks = prof.folded_stacks();
+        assert_eq!(stacks["root;command_0"], 10);
+        assert_eq!(stacks["root;command_0;f"], 30);
+        assert_eq!(stacks["root;command_0;f;Add"], 10);
+        assert_eq!(stacks["root"], 10);
+        assert_eq!(stacks["root;command_1;g"], 40);
+        assert!(!stacks.contains_key("root;command_1"));
+
+        assert_eq!(
+            prof.top_level_frames(),
+            vec![
+                FrameGas {
+                    name: "command_0".to_string(),
+                    gas: 50,
+                },
+                FrameGas {
+                    name: "command_1".to_string(),
+                    gas: 40,
+                },
+            ]
+        );
+        // Do not write the profile to disk on drop
+        prof.finished = true;
+    }
+}
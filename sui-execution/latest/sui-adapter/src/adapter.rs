@@ -4,9 +4,7 @@
 pub use checked::*;
 #[sui_macros::with_checked_arithmetic]
 mod checked {
-    #[cfg(feature = "tracing")]
     use move_vm_config::runtime::VMProfilerConfig;
-    use std::path::PathBuf;
     use std::{collections::BTreeMap, sync::Arc};
 
     use anyhow::Result;
@@ -42,16 +40,12 @@ mod checked {
     pub fn new_move_vm(
         natives: NativeFunctionTable,
         protocol_config: &ProtocolConfig,
-        _enable_profiler: Option<PathBuf>,
+        _enable_profiler: Option<VMProfilerConfig>,
     ) -> Result<MoveVM, SuiError> {
         #[cfg(not(feature = "tracing"))]
         let vm_profiler_config = None;
         #[cfg(feature = "tracing")]
-        let vm_profiler_config = _enable_profiler.clone().map(|path| VMProfilerConfig {
-            full_path: path,
-            track_bytecode_instructions: false,
-            use_long_function_name: false,
-        });
+        let vm_profiler_config = _enable_profiler;
         MoveVM::new_with_config(
             natives,
             VMConfig {
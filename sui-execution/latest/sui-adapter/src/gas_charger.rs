@@ -326,6 +326,20 @@ pub mod checked {
                 let cost_summary = self.gas_status.summary();
                 let gas_used = cost_summary.net_gas_usage();
 
+                // Record the final charges, including storage, in the gas profile
+                #[skip_checked_arithmetic]
+                move_vm_profiler::tracing_feature_enabled! {
+                    use move_vm_types::gas::GasMeter;
+                    if let Some(profiler) = self.move_gas_status_mut().get_profiler_mut() {
+                        profiler.set_cost_summary(move_vm_profiler::ProfileCostSummary {
+                            computation_cost: cost_summary.computation_cost,
+                            storage_cost: cost_summary.storage_cost,
+                            storage_rebate: cost_summary.storage_rebate,
+                            non_refundable_storage_fee: cost_summary.non_refundable_storage_fee,
+                        });
+                    }
+                }
+
                 let mut gas_object = temporary_store.read_object(&gas_object_id).unwrap().clone();
                 deduct_gas(&mut gas_object, gas_used);
                 #[skip_checked_arithmetic]
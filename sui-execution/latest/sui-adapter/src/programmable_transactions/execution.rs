@@ -27,6 +27,8 @@ mod checked {
         move_vm::MoveVM,
         session::{LoadedFunctionInstantiation, SerializedReturnValues},
     };
+    #[cfg(feature = "tracing")]
+    use move_vm_types::gas::GasMeter;
     use move_vm_types::loaded_data::runtime_types::{CachedDatatype, Type};
     use serde::{de::DeserializeSeed, Deserialize};
     use std::{
@@ -88,7 +90,19 @@ mod checked {
         // execute commands
         let mut mode_results = Mode::empty_results();
         for (idx, command) in commands.into_iter().enumerate() {
-            if let Err(err) = execute_command::<Mode>(&mut context, &mut mode_results, command) {
+            // Attribute the gas of each command to its own frame when profiling
+            #[cfg(feature = "tracing")]
+            let command_frame = command_frame_name(idx, &command);
+            move_vm_profiler::profile_open_frame!(
+                context.gas_charger.move_gas_status_mut(),
+                command_frame.clone()
+            );
+            let result = execute_command::<Mode>(&mut context, &mut mode_results, command);
+            move_vm_profiler::profile_close_frame!(
+                context.gas_charger.move_gas_status_mut(),
+                command_frame
+            );
+            if let Err(err) = result {
                 let object_runtime: &ObjectRuntime = context.object_runtime();
                 // We still need to record the loaded child objects for replay
                 let loaded_runtime_objects = object_runtime.loaded_runtime_objects();
@@ -118,6 +132,21 @@ mod checked {
         Ok(mode_results)
     }
 
+    /// Name of the profiler frame of a command, e.g. `command_0:MoveCall`
+    #[cfg(feature = "tracing")]
+    fn command_frame_name(idx: usize, command: &Command) -> String {
+        let kind = match command {
+            Command::MoveCall(_) => "MoveCall",
+            Command::TransferObjects(_, _) => "TransferObjects",
+            Command::SplitCoins(_, _) => "SplitCoins",
+            Command::MergeCoins(_, _) => "MergeCoins",
+            Command::Publish(_, _) => "Publish",
+            Command::MakeMoveVec(_, _) => "MakeMoveVec",
+            Command::Upgrade(_, _, _, _) => "Upgrade",
+        };
+        format!("command_{}:{}", idx, kind)
+    }
+
     /// Execute a single command
     #[instrument(level = "trace", skip_all)]
     fn execute_command<Mode: ExecutionMode>(
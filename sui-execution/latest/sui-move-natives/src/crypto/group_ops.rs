@@ -4,7 +4,7 @@ use crate::object_runtime::ObjectRuntime;
 use crate::NativesCostTable;
 use fastcrypto::error::{FastCryptoError, FastCryptoResult};
 use fastcrypto::groups::{
-    bls12381 as bls, FromTrustedByteArray, GroupElement, HashToGroupElement, MultiScalarMul,
+    bls12381 as bls, bn254, FromTrustedByteArray, GroupElement, HashToGroupElement, MultiScalarMul,
     Pairing,
 };
 use fastcrypto::serde_helpers::ToFromByteArray;
@@ -42,6 +42,22 @@ fn is_msm_supported(context: &NativeContext) -> bool {
         .enable_group_ops_native_function_msm()
 }
 
+fn is_bn254_supported(context: &NativeContext) -> bool {
+    context
+        .extensions()
+        .get::<ObjectRuntime>()
+        .protocol_config
+        .enable_group_ops_native_functions_bn254()
+}
+
+// Checks that the group is enabled, in addition to the checks of the individual functions.
+fn is_group_supported(context: &NativeContext, group_type: u8) -> bool {
+    match Groups::from_u8(group_type) {
+        Some(group) if group.is_bn254() => is_bn254_supported(context),
+        _ => true,
+    }
+}
+
 fn is_uncompressed_g1_supported(context: &NativeContext) -> bool {
     context
         .extensions()
@@ -102,6 +118,38 @@ pub struct GroupOpsCostParams {
     pub bls12381_uncompressed_g1_sum_cost_per_term: Option<InternalGas>,
     // limit the number of terms in a sum
     pub bls12381_uncompressed_g1_sum_max_terms: Option<u64>,
+    // costs for decode and validate of BN254 elements
+    pub bn254_decode_scalar_cost: Option<InternalGas>,
+    pub bn254_decode_g1_cost: Option<InternalGas>,
+    pub bn254_decode_g2_cost: Option<InternalGas>,
+    // costs for decode, add, and encode output of BN254 elements
+    pub bn254_scalar_add_cost: Option<InternalGas>,
+    pub bn254_g1_add_cost: Option<InternalGas>,
+    pub bn254_g2_add_cost: Option<InternalGas>,
+    pub bn254_gt_add_cost: Option<InternalGas>,
+    // costs for decode, sub, and encode output of BN254 elements
+    pub bn254_scalar_sub_cost: Option<InternalGas>,
+    pub bn254_g1_sub_cost: Option<InternalGas>,
+    pub bn254_g2_sub_cost: Option<InternalGas>,
+    pub bn254_gt_sub_cost: Option<InternalGas>,
+    // costs for decode, mul, and encode output of BN254 elements
+    pub bn254_scalar_mul_cost: Option<InternalGas>,
+    pub bn254_g1_mul_cost: Option<InternalGas>,
+    pub bn254_g2_mul_cost: Option<InternalGas>,
+    pub bn254_gt_mul_cost: Option<InternalGas>,
+    // costs for decode, div, and encode output of BN254 elements
+    pub bn254_scalar_div_cost: Option<InternalGas>,
+    pub bn254_g1_div_cost: Option<InternalGas>,
+    pub bn254_g2_div_cost: Option<InternalGas>,
+    pub bn254_gt_div_cost: Option<InternalGas>,
+    // costs for MSM of BN254 elements, as for BLS12-381
+    pub bn254_g1_msm_base_cost: Option<InternalGas>,
+    pub bn254_g2_msm_base_cost: Option<InternalGas>,
+    pub bn254_g1_msm_base_cost_per_input: Option<InternalGas>,
+    pub bn254_g2_msm_base_cost_per_input: Option<InternalGas>,
+    pub bn254_msm_max_len: Option<u32>,
+    // costs for decode, pairing, and encode output of BN254 elements
+    pub bn254_pairing_cost: Option<InternalGas>,
 }
 
 macro_rules! native_charge_gas_early_exit_option {
@@ -126,6 +174,10 @@ enum Groups {
     BLS12381G2 = 2,
     BLS12381GT = 3,
     BLS12381UncompressedG1 = 4,
+    BN254Scalar = 5,
+    BN254G1 = 6,
+    BN254G2 = 7,
+    BN254GT = 8,
 }
 
 impl Groups {
@@ -136,11 +188,28 @@ impl Groups {
             2 => Some(Groups::BLS12381G2),
             3 => Some(Groups::BLS12381GT),
             4 => Some(Groups::BLS12381UncompressedG1),
+            5 => Some(Groups::BN254Scalar),
+            6 => Some(Groups::BN254G1),
+            7 => Some(Groups::BN254G2),
+            8 => Some(Groups::BN254GT),
             _ => None,
         }
     }
+
+    fn is_bn254(&self) -> bool {
+        matches!(
+            self,
+            Groups::BN254Scalar | Groups::BN254G1 | Groups::BN254G2 | Groups::BN254GT
+        )
+    }
 }
 
+// Encoded lengths of BN254 elements, see sui::bn254.
+const BN254_SCALAR_LENGTH: usize = 32;
+const BN254_G1_LENGTH: usize = 32;
+const BN254_G2_LENGTH: usize = 64;
+const BN254_GT_LENGTH: usize = 384;
+
 fn parse_untrusted<G: ToFromByteArray<S> + FromTrustedByteArray<S>, const S: usize>(
     e: &[u8],
 ) -> FastCryptoResult<G> {
@@ -186,7 +255,8 @@ fn binary_op<G: ToFromByteArray<S> + FromTrustedByteArray<S>, const S: usize>(
 /***************************************************************************************************
  * native fun internal_validate
  * Implementation of the Move native function `internal_validate(type: u8, bytes: &vector<u8>): bool`
- *   gas cost: group_ops_decode_bls12381_X_cost where X is the requested type
+ *   gas cost: group_ops_decode_bls12381_X_cost or group_ops_decode_bn254_X_cost where X is the
+ *             requested type
  **************************************************************************************************/
 
 pub fn internal_validate(
@@ -206,6 +276,10 @@ pub fn internal_validate(
     let bytes = bytes_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type) {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()
@@ -225,6 +299,18 @@ pub fn internal_validate(
             native_charge_gas_early_exit_option!(context, cost_params.bls12381_decode_g2_cost);
             parse_untrusted::<bls::G2Element, { bls::G2Element::BYTE_LENGTH }>(&bytes).is_ok()
         }
+        Some(Groups::BN254Scalar) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_decode_scalar_cost);
+            parse_untrusted::<bn254::Scalar, BN254_SCALAR_LENGTH>(&bytes).is_ok()
+        }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_decode_g1_cost);
+            parse_untrusted::<bn254::G1Element, BN254_G1_LENGTH>(&bytes).is_ok()
+        }
+        Some(Groups::BN254G2) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_decode_g2_cost);
+            parse_untrusted::<bn254::G2Element, BN254_G2_LENGTH>(&bytes).is_ok()
+        }
         _ => false,
     };
 
@@ -234,7 +320,8 @@ pub fn internal_validate(
 /***************************************************************************************************
  * native fun internal_add
  * Implementation of the Move native function `internal_add(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>`
- *   gas cost: group_ops_bls12381_X_add_cost where X is the requested type
+ *   gas cost: group_ops_bls12381_X_add_cost or group_ops_bn254_X_add_cost where X is the requested
+ *             type
  **************************************************************************************************/
 pub fn internal_add(
     context: &mut NativeContext,
@@ -255,6 +342,10 @@ pub fn internal_add(
     let e1 = e1_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type) {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()
@@ -278,6 +369,22 @@ pub fn internal_add(
             native_charge_gas_early_exit_option!(context, cost_params.bls12381_gt_add_cost);
             binary_op::<bls::GTElement, { bls::GTElement::BYTE_LENGTH }>(|a, b| Ok(a + b), &e1, &e2)
         }
+        Some(Groups::BN254Scalar) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_scalar_add_cost);
+            binary_op::<bn254::Scalar, BN254_SCALAR_LENGTH>(|a, b| Ok(a + b), &e1, &e2)
+        }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g1_add_cost);
+            binary_op::<bn254::G1Element, BN254_G1_LENGTH>(|a, b| Ok(a + b), &e1, &e2)
+        }
+        Some(Groups::BN254G2) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g2_add_cost);
+            binary_op::<bn254::G2Element, BN254_G2_LENGTH>(|a, b| Ok(a + b), &e1, &e2)
+        }
+        Some(Groups::BN254GT) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_gt_add_cost);
+            binary_op::<bn254::GTElement, BN254_GT_LENGTH>(|a, b| Ok(a + b), &e1, &e2)
+        }
         _ => Err(FastCryptoError::InvalidInput),
     };
 
@@ -291,7 +398,8 @@ pub fn internal_add(
 /***************************************************************************************************
  * native fun internal_sub
  * Implementation of the Move native function `internal_sub(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>`
- *   gas cost: group_ops_bls12381_X_sub_cost where X is the requested type
+ *   gas cost: group_ops_bls12381_X_sub_cost or group_ops_bn254_X_sub_cost where X is the requested
+ *             type
  **************************************************************************************************/
 pub fn internal_sub(
     context: &mut NativeContext,
@@ -312,6 +420,10 @@ pub fn internal_sub(
     let e1 = e1_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type) {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()
@@ -335,6 +447,22 @@ pub fn internal_sub(
             native_charge_gas_early_exit_option!(context, cost_params.bls12381_gt_sub_cost);
             binary_op::<bls::GTElement, { bls::GTElement::BYTE_LENGTH }>(|a, b| Ok(a - b), &e1, &e2)
         }
+        Some(Groups::BN254Scalar) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_scalar_sub_cost);
+            binary_op::<bn254::Scalar, BN254_SCALAR_LENGTH>(|a, b| Ok(a - b), &e1, &e2)
+        }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g1_sub_cost);
+            binary_op::<bn254::G1Element, BN254_G1_LENGTH>(|a, b| Ok(a - b), &e1, &e2)
+        }
+        Some(Groups::BN254G2) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g2_sub_cost);
+            binary_op::<bn254::G2Element, BN254_G2_LENGTH>(|a, b| Ok(a - b), &e1, &e2)
+        }
+        Some(Groups::BN254GT) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_gt_sub_cost);
+            binary_op::<bn254::GTElement, BN254_GT_LENGTH>(|a, b| Ok(a - b), &e1, &e2)
+        }
         _ => Err(FastCryptoError::InvalidInput),
     };
 
@@ -348,7 +476,8 @@ pub fn internal_sub(
 /***************************************************************************************************
  * native fun internal_mul
  * Implementation of the Move native function `internal_mul(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>`
- *   gas cost: group_ops_bls12381_X_mul_cost where X is the requested type
+ *   gas cost: group_ops_bls12381_X_mul_cost or group_ops_bn254_X_mul_cost where X is the requested
+ *             type
  **************************************************************************************************/
 pub fn internal_mul(
     context: &mut NativeContext,
@@ -369,6 +498,10 @@ pub fn internal_mul(
     let e1 = e1_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type) {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()
@@ -407,6 +540,34 @@ pub fn internal_mul(
                 { bls::GTElement::BYTE_LENGTH },
             >(|a, b| Ok(b * a), &e1, &e2)
         }
+        Some(Groups::BN254Scalar) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_scalar_mul_cost);
+            binary_op::<bn254::Scalar, BN254_SCALAR_LENGTH>(|a, b| Ok(b * a), &e1, &e2)
+        }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g1_mul_cost);
+            binary_op_diff::<bn254::Scalar, bn254::G1Element, BN254_SCALAR_LENGTH, BN254_G1_LENGTH>(
+                |a, b| Ok(b * a),
+                &e1,
+                &e2,
+            )
+        }
+        Some(Groups::BN254G2) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g2_mul_cost);
+            binary_op_diff::<bn254::Scalar, bn254::G2Element, BN254_SCALAR_LENGTH, BN254_G2_LENGTH>(
+                |a, b| Ok(b * a),
+                &e1,
+                &e2,
+            )
+        }
+        Some(Groups::BN254GT) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_gt_mul_cost);
+            binary_op_diff::<bn254::Scalar, bn254::GTElement, BN254_SCALAR_LENGTH, BN254_GT_LENGTH>(
+                |a, b| Ok(b * a),
+                &e1,
+                &e2,
+            )
+        }
         _ => Err(FastCryptoError::InvalidInput),
     };
 
@@ -420,7 +581,8 @@ pub fn internal_mul(
 /***************************************************************************************************
  * native fun internal_div
  * Implementation of the Move native function `internal_div(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>`
- *   gas cost: group_ops_bls12381_X_div_cost where X is the requested type
+ *   gas cost: group_ops_bls12381_X_div_cost or group_ops_bn254_X_div_cost where X is the requested
+ *             type
  **************************************************************************************************/
 pub fn internal_div(
     context: &mut NativeContext,
@@ -441,6 +603,10 @@ pub fn internal_div(
     let e1 = e1_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type) {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()
@@ -479,6 +645,34 @@ pub fn internal_div(
                 { bls::GTElement::BYTE_LENGTH },
             >(|a, b| b / a, &e1, &e2)
         }
+        Some(Groups::BN254Scalar) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_scalar_div_cost);
+            binary_op::<bn254::Scalar, BN254_SCALAR_LENGTH>(|a, b| b / a, &e1, &e2)
+        }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g1_div_cost);
+            binary_op_diff::<bn254::Scalar, bn254::G1Element, BN254_SCALAR_LENGTH, BN254_G1_LENGTH>(
+                |a, b| b / a,
+                &e1,
+                &e2,
+            )
+        }
+        Some(Groups::BN254G2) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g2_div_cost);
+            binary_op_diff::<bn254::Scalar, bn254::G2Element, BN254_SCALAR_LENGTH, BN254_G2_LENGTH>(
+                |a, b| b / a,
+                &e1,
+                &e2,
+            )
+        }
+        Some(Groups::BN254GT) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_gt_div_cost);
+            binary_op_diff::<bn254::Scalar, bn254::GTElement, BN254_SCALAR_LENGTH, BN254_GT_LENGTH>(
+                |a, b| b / a,
+                &e1,
+                &e2,
+            )
+        }
         _ => Err(FastCryptoError::InvalidInput),
     };
 
@@ -654,7 +848,8 @@ where
  * native fun internal_multi_scalar_mul
  * Implementation of the Move native function `internal_multi_scalar_mul(type: u8, scalars: &vector<u8>, elements: &vector<u8>): vector<u8>`
  *   gas cost: (bls12381_decode_scalar_cost + bls12381_decode_X_cost) * N + bls12381_X_msm_base_cost +
- *             bls12381_X_msm_base_cost_per_input * num_of_additions(N)
+ *             bls12381_X_msm_base_cost_per_input * num_of_additions(N), and similarly with the bn254
+ *             costs for BN254 groups
  **************************************************************************************************/
 pub fn internal_multi_scalar_mul(
     context: &mut NativeContext,
@@ -675,13 +870,21 @@ pub fn internal_multi_scalar_mul(
     let scalars = scalars_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type) {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()
         .group_ops_cost_params
         .clone();
 
-    let max_len = cost_params.bls12381_msm_max_len.ok_or_else(|| {
+    let max_len = match Groups::from_u8(group_type) {
+        Some(group) if group.is_bn254() => cost_params.bn254_msm_max_len,
+        _ => cost_params.bls12381_msm_max_len,
+    }
+    .ok_or_else(|| {
         PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
             .with_message("Max len for MSM is not set".to_string())
     })?;
@@ -716,6 +919,30 @@ pub fn internal_multi_scalar_mul(
             scalars.as_ref(),
             elements.as_ref(),
         ),
+        Some(Groups::BN254G1) => {
+            multi_scalar_mul::<bn254::G1Element, BN254_SCALAR_LENGTH, BN254_G1_LENGTH>(
+                context,
+                cost_params.bn254_decode_scalar_cost,
+                cost_params.bn254_decode_g1_cost,
+                cost_params.bn254_g1_msm_base_cost,
+                cost_params.bn254_g1_msm_base_cost_per_input,
+                max_len,
+                scalars.as_ref(),
+                elements.as_ref(),
+            )
+        }
+        Some(Groups::BN254G2) => {
+            multi_scalar_mul::<bn254::G2Element, BN254_SCALAR_LENGTH, BN254_G2_LENGTH>(
+                context,
+                cost_params.bn254_decode_scalar_cost,
+                cost_params.bn254_decode_g2_cost,
+                cost_params.bn254_g2_msm_base_cost,
+                cost_params.bn254_g2_msm_base_cost_per_input,
+                max_len,
+                scalars.as_ref(),
+                elements.as_ref(),
+            )
+        }
         _ => Ok(NativeResult::err(cost, INVALID_INPUT_ERROR)),
     }
 }
@@ -723,7 +950,7 @@ pub fn internal_multi_scalar_mul(
 /***************************************************************************************************
  * native fun internal_pairing
  * Implementation of the Move native function `internal_pairing(type:u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>`
- *   gas cost: group_ops_bls12381_pairing_cost
+ *   gas cost: group_ops_bls12381_pairing_cost or group_ops_bn254_pairing_cost
  **************************************************************************************************/
 pub fn internal_pairing(
     context: &mut NativeContext,
@@ -744,6 +971,10 @@ pub fn internal_pairing(
     let e1 = e1_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type) {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()
@@ -760,6 +991,15 @@ pub fn internal_pairing(
                 })
             })
         }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_pairing_cost);
+            parse_trusted::<bn254::G1Element, BN254_G1_LENGTH>(&e1).and_then(|e1| {
+                parse_trusted::<bn254::G2Element, BN254_G2_LENGTH>(&e2).map(|e2| {
+                    let e3 = e1.pairing(&e2);
+                    e3.to_byte_array().to_vec()
+                })
+            })
+        }
         _ => Err(FastCryptoError::InvalidInput),
     };
 
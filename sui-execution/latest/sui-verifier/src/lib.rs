@@ -10,6 +10,7 @@ pub mod meter;
 pub mod one_time_witness_verifier;
 pub mod private_generics;
 pub mod struct_with_key_verifier;
+pub mod unbounded_loop_verifier;
 
 use move_core_types::{ident_str, identifier::IdentStr, vm_status::StatusCode};
 use sui_types::error::{ExecutionError, ExecutionErrorKind};
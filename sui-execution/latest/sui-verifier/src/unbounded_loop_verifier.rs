@@ -0,0 +1,353 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lint for functions callable from transactions that loop over a collection they receive as a
+//! parameter, such as a `vector` or a `Table`. The size of such collections is not bounded, so
+//! callers can make these functions run out of gas, e.g. by growing a shared table. Loops in
+//! functions of the same module that the collection is passed to are reported at the call.
+//!
+//! This pass is opt-in and only reports warnings: it is not part of `verifier::sui_verify_module`
+//! and is never run on chain.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use move_binary_format::{
+    file_format::{
+        Bytecode, CodeOffset, FunctionDefinition, FunctionDefinitionIndex, FunctionHandleIndex,
+        LocalIndex, SignatureToken, Visibility,
+    },
+    CompiledModule,
+};
+use move_bytecode_utils::format_signature_token;
+use move_core_types::{
+    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+};
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+/// Collections of the Sui framework that can grow without bounds, by module and type name.
+const UNBOUNDED_COLLECTIONS: &[(&str, &str)] = &[
+    ("bag", "Bag"),
+    ("linked_table", "LinkedTable"),
+    ("object_bag", "ObjectBag"),
+    ("object_table", "ObjectTable"),
+    ("table", "Table"),
+    ("table_vec", "TableVec"),
+    ("vec_map", "VecMap"),
+    ("vec_set", "VecSet"),
+];
+
+/// A loop of a function callable from transactions, which iterates over a collection received as
+/// a parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnboundedLoop {
+    pub module: ModuleId,
+    pub function: Identifier,
+    pub function_index: FunctionDefinitionIndex,
+    /// Offset of the branch closing the loop, or of the call to `callee` looping over the
+    /// collection.
+    pub code_offset: CodeOffset,
+    /// Index of the parameter holding the collection.
+    pub parameter: LocalIndex,
+    /// Type of the collection, e.g. `vector<u64>` or `0x2::table::Table<address, u64>`.
+    pub collection: String,
+    /// Function of the same module that the collection is passed to and that loops over it,
+    /// directly or through its own callees.
+    pub callee: Option<Identifier>,
+}
+
+impl fmt::Display for UnboundedLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{} ", self.module, self.function)?;
+        match &self.callee {
+            None => write!(f, "loops over ")?,
+            Some(callee) => write!(f, "passes to {}, which loops over it, ", callee)?,
+        }
+        write!(
+            f,
+            "parameter {} of type {}, whose size is controlled by callers. \
+             This may let callers exhaust the gas budget of transactions calling this function",
+            self.parameter, self.collection,
+        )
+    }
+}
+
+/// Returns the loops over unbounded collections in the `entry` and `public` functions of `module`.
+pub fn lint_module(module: &CompiledModule) -> Vec<UnboundedLoop> {
+    let mut loops = vec![];
+    for (idx, func_def) in module.function_defs.iter().enumerate() {
+        if !func_def.is_entry && func_def.visibility != Visibility::Public {
+            continue;
+        }
+        loops.extend(lint_function(
+            module,
+            FunctionDefinitionIndex(idx as u16),
+            func_def,
+        ));
+    }
+    loops
+}
+
+fn lint_function(
+    module: &CompiledModule,
+    function_index: FunctionDefinitionIndex,
+    func_def: &FunctionDefinition,
+) -> Vec<UnboundedLoop> {
+    let handle = module.function_handle_at(func_def.function);
+    let collections = collection_parameters(module, func_def);
+    let mut visiting = BTreeSet::from([function_index]);
+    loops_over_parameters(module, func_def, &collections, &mut visiting)
+        .into_iter()
+        .map(|(code_offset, parameter, callee)| UnboundedLoop {
+            module: module.self_id(),
+            function: module.identifier_at(handle.name).to_owned(),
+            function_index,
+            code_offset,
+            parameter,
+            collection: collections[&parameter].clone(),
+            callee,
+        })
+        .collect()
+}
+
+/// Returns the parameters of the function holding an unbounded collection, with their types.
+fn collection_parameters(
+    module: &CompiledModule,
+    func_def: &FunctionDefinition,
+) -> BTreeMap<LocalIndex, String> {
+    let handle = module.function_handle_at(func_def.function);
+    module
+        .signature_at(handle.parameters)
+        .0
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| is_unbounded_collection(module, token))
+        .map(|(idx, token)| (idx as LocalIndex, format_signature_token(module, token)))
+        .collect()
+}
+
+/// Returns the loops of the function over one of `collections`, as the offset of the loop, the
+/// parameter looped over, and the function of this module looping over it, if it is not looped
+/// over directly. `visiting` holds the functions being analyzed, to stop on recursive calls.
+fn loops_over_parameters(
+    module: &CompiledModule,
+    func_def: &FunctionDefinition,
+    collections: &BTreeMap<LocalIndex, String>,
+    visiting: &mut BTreeSet<FunctionDefinitionIndex>,
+) -> Vec<(CodeOffset, LocalIndex, Option<Identifier>)> {
+    let Some(code) = &func_def.code else {
+        return vec![];
+    };
+    if collections.is_empty() {
+        return vec![];
+    }
+
+    let aliases = collection_aliases(&code.code, collections);
+    let mut loops = vec![];
+    for (offset, instr) in code.code.iter().enumerate() {
+        let offset = offset as CodeOffset;
+        let head = match instr {
+            Bytecode::Branch(target) | Bytecode::BrTrue(target) | Bytecode::BrFalse(target)
+                if *target <= offset =>
+            {
+                *target
+            }
+            _ => continue,
+        };
+        let body = &code.code[head as usize..=offset as usize];
+        if !body.iter().any(|instr| is_collection_access(module, instr)) {
+            continue;
+        }
+        let parameters: BTreeSet<LocalIndex> = body
+            .iter()
+            .filter_map(|instr| aliases.get(&local_read(instr)?).copied())
+            .collect();
+        loops.extend(
+            parameters
+                .into_iter()
+                .map(|parameter| (offset, parameter, None)),
+        );
+    }
+
+    // Follow the collections into the functions of this module they are passed to
+    for (offset, instr) in code.code.iter().enumerate() {
+        let Some((callee_index, callee_def)) = local_callee(module, instr) else {
+            continue;
+        };
+        if !visiting.insert(callee_index) {
+            continue;
+        }
+        let callee_collections = collection_parameters(module, callee_def);
+        let callee_loops = loops_over_parameters(module, callee_def, &callee_collections, visiting);
+        visiting.remove(&callee_index);
+
+        let arguments = call_arguments(module, &code.code, offset, callee_def);
+        let callee_name = module
+            .identifier_at(module.function_handle_at(callee_def.function).name)
+            .to_owned();
+        let parameters: BTreeSet<LocalIndex> = callee_loops
+            .iter()
+            .filter_map(|(_, callee_parameter, _)| {
+                let local = arguments
+                    .get(*callee_parameter as usize)
+                    .copied()
+                    .flatten()?;
+                aliases.get(&local).copied()
+            })
+            .collect();
+        loops.extend(
+            parameters
+                .into_iter()
+                .map(|parameter| (offset as CodeOffset, parameter, Some(callee_name.clone()))),
+        );
+    }
+    loops
+}
+
+/// Returns the function defined in this module that `instr` calls, if any.
+fn local_callee<'a>(
+    module: &'a CompiledModule,
+    instr: &Bytecode,
+) -> Option<(FunctionDefinitionIndex, &'a FunctionDefinition)> {
+    let handle_index = match instr {
+        Bytecode::Call(idx) => *idx,
+        Bytecode::CallGeneric(idx) => module.function_instantiation_at(*idx).handle,
+        _ => return None,
+    };
+    if module.function_handle_at(handle_index).module != module.self_handle_idx() {
+        return None;
+    }
+    module
+        .function_defs
+        .iter()
+        .enumerate()
+        .find(|(_, def)| def.function == handle_index)
+        .map(|(idx, def)| (FunctionDefinitionIndex(idx as u16), def))
+}
+
+/// Returns the local passed as each argument of the call at `offset`, when arguments are pushed
+/// by the instructions right before the call, e.g. `f(&v, x)`. Arguments computed by other
+/// instructions are not tracked.
+fn call_arguments(
+    module: &CompiledModule,
+    code: &[Bytecode],
+    offset: usize,
+    callee_def: &FunctionDefinition,
+) -> Vec<Option<LocalIndex>> {
+    let handle = module.function_handle_at(callee_def.function);
+    let arity = module.signature_at(handle.parameters).len();
+    let Some(start) = offset.checked_sub(arity) else {
+        return vec![];
+    };
+    let pushes = &code[start..offset];
+    if !pushes.iter().all(is_single_push) {
+        return vec![];
+    }
+    pushes.iter().map(local_read).collect()
+}
+
+/// Returns true if the instruction pushes one value without popping any.
+fn is_single_push(instr: &Bytecode) -> bool {
+    matches!(
+        instr,
+        Bytecode::CopyLoc(_)
+            | Bytecode::MoveLoc(_)
+            | Bytecode::ImmBorrowLoc(_)
+            | Bytecode::MutBorrowLoc(_)
+            | Bytecode::LdU8(_)
+            | Bytecode::LdU16(_)
+            | Bytecode::LdU32(_)
+            | Bytecode::LdU64(_)
+            | Bytecode::LdU128(_)
+            | Bytecode::LdU256(_)
+            | Bytecode::LdConst(_)
+            | Bytecode::LdTrue
+            | Bytecode::LdFalse
+    )
+}
+
+/// Maps the locals holding a collection parameter, or a reference to it, to that parameter.
+/// Aliases are created by a copy, move, or borrow of a local directly stored into another local,
+/// e.g. when macros such as `vector::do!` bind their arguments.
+fn collection_aliases(
+    code: &[Bytecode],
+    collections: &BTreeMap<LocalIndex, String>,
+) -> BTreeMap<LocalIndex, LocalIndex> {
+    let mut aliases: BTreeMap<LocalIndex, LocalIndex> =
+        collections.keys().map(|idx| (*idx, *idx)).collect();
+    loop {
+        let mut changed = false;
+        for pair in code.windows(2) {
+            let (Some(source), Bytecode::StLoc(dest)) = (local_read(&pair[0]), &pair[1]) else {
+                continue;
+            };
+            if let Some(parameter) = aliases.get(&source).copied() {
+                if aliases.insert(*dest, parameter).is_none() {
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return aliases;
+        }
+    }
+}
+
+fn local_read(instr: &Bytecode) -> Option<LocalIndex> {
+    match instr {
+        Bytecode::CopyLoc(local)
+        | Bytecode::MoveLoc(local)
+        | Bytecode::ImmBorrowLoc(local)
+        | Bytecode::MutBorrowLoc(local) => Some(*local),
+        _ => None,
+    }
+}
+
+fn is_unbounded_collection(module: &CompiledModule, token: &SignatureToken) -> bool {
+    match token {
+        SignatureToken::Reference(inner) | SignatureToken::MutableReference(inner) => {
+            is_unbounded_collection(module, inner)
+        }
+        SignatureToken::Vector(_) => true,
+        SignatureToken::Datatype(idx) | SignatureToken::DatatypeInstantiation(idx, _) => {
+            let handle = module.datatype_handle_at(*idx);
+            let module_handle = module.module_handle_at(handle.module);
+            let address = module.address_identifier_at(module_handle.address);
+            let module_name = module.identifier_at(module_handle.name).as_str();
+            let name = module.identifier_at(handle.name).as_str();
+            *address == SUI_FRAMEWORK_ADDRESS
+                && UNBOUNDED_COLLECTIONS.contains(&(module_name, name))
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if the instruction reads the size or the elements of a collection.
+fn is_collection_access(module: &CompiledModule, instr: &Bytecode) -> bool {
+    match instr {
+        Bytecode::VecLen(_)
+        | Bytecode::VecImmBorrow(_)
+        | Bytecode::VecMutBorrow(_)
+        | Bytecode::VecPopBack(_)
+        | Bytecode::VecSwap(_) => true,
+        Bytecode::Call(idx) => is_collection_function(module, *idx),
+        Bytecode::CallGeneric(idx) => {
+            is_collection_function(module, module.function_instantiation_at(*idx).handle)
+        }
+        _ => false,
+    }
+}
+
+fn is_collection_function(module: &CompiledModule, idx: FunctionHandleIndex) -> bool {
+    let handle = module.function_handle_at(idx);
+    let module_handle = module.module_handle_at(handle.module);
+    let address: &AccountAddress = module.address_identifier_at(module_handle.address);
+    let module_name = module.identifier_at(module_handle.name).as_str();
+    (*address == MOVE_STDLIB_ADDRESS && module_name == "vector")
+        || (*address == SUI_FRAMEWORK_ADDRESS
+            && UNBOUNDED_COLLECTIONS
+                .iter()
+                .any(|(collection_module, _)| *collection_module == module_name))
+}
@@ -3,13 +3,13 @@
 
 // DO NOT MODIFY, Generated by ./scripts/execution-layer
 
-use std::path::PathBuf;
 use std::sync::Arc;
 
 use sui_protocol_config::ProtocolConfig;
 use sui_types::{error::SuiResult, metrics::BytecodeVerifierMetrics};
 
 pub use executor::Executor;
+pub use move_vm_config::runtime::VMProfilerConfig;
 pub use verifier::Verifier;
 
 pub mod executor;
@@ -26,7 +26,7 @@ mod tests;
 pub fn executor(
     protocol_config: &ProtocolConfig,
     silent: bool,
-    enable_profiler: Option<PathBuf>,
+    enable_profiler: Option<VMProfilerConfig>,
 ) -> SuiResult<Arc<dyn Executor + Send + Sync>> {
     let version = protocol_config.execution_version_as_option().unwrap_or(0);
     Ok(match version {
@@ -3,13 +3,13 @@
 
 // $GENERATED_MESSAGE
 
-use std::path::PathBuf;
 use std::sync::Arc;
 
 use sui_protocol_config::ProtocolConfig;
 use sui_types::{error::SuiResult, metrics::BytecodeVerifierMetrics};
 
 pub use executor::Executor;
+pub use move_vm_config::runtime::VMProfilerConfig;
 pub use verifier::Verifier;
 
 pub mod executor;
@@ -24,7 +24,7 @@ mod tests;
 pub fn executor(
     protocol_config: &ProtocolConfig,
     silent: bool,
-    enable_profiler: Option<PathBuf>,
+    enable_profiler: Option<VMProfilerConfig>,
 ) -> SuiResult<Arc<dyn Executor + Send + Sync>> {
     let version = protocol_config.execution_version_as_option().unwrap_or(0);
     Ok(match version {
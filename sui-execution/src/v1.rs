@@ -1,10 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::path::PathBuf;
 use std::{collections::HashSet, sync::Arc};
 
 use move_binary_format::CompiledModule;
+use move_vm_config::runtime::VMProfilerConfig;
 use move_vm_config::verifier::{MeterConfig, VerifierConfig};
 use sui_protocol_config::ProtocolConfig;
 use sui_types::{
@@ -47,12 +47,12 @@ impl Executor {
     pub(crate) fn new(
         protocol_config: &ProtocolConfig,
         silent: bool,
-        enable_profiler: Option<PathBuf>,
+        enable_profiler: Option<VMProfilerConfig>,
     ) -> Result<Self, SuiError> {
         Ok(Executor(Arc::new(new_move_vm(
             all_natives(silent),
             protocol_config,
-            enable_profiler,
+            enable_profiler.map(|config| config.full_path),
         )?)))
     }
 }